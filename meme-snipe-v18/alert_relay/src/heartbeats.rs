@@ -0,0 +1,86 @@
+// alert_relay/src/heartbeats.rs
+//! Backstop for healthd: while healthd's own heartbeats keep arriving it
+//! alerts on silent services itself (through this relay), so nothing is sent
+//! here. Once healthd goes quiet, any service that misses
+//! ALERT_RELAY_MISSED_HEARTBEATS of its intervals on `service_heartbeats` is
+//! alerted on directly, healthd included, and again when it comes back.
+
+use crate::{Alert, Sinks};
+use chrono::Utc;
+use redis::streams::StreamRangeReply;
+use redis::AsyncCommands;
+use shared_models::{ServiceHeartbeat, SERVICE_HEARTBEATS_STREAM};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const CHECK_INTERVAL_SECS: u64 = 15;
+const HEALTHD: &str = "healthd";
+
+pub async fn watch(client: redis::Client, sinks: Sinks) {
+    let missed: i64 = env::var("ALERT_RELAY_MISSED_HEARTBEATS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(3);
+    let mut cursor = "-".to_string();
+    let mut last_seen: HashMap<String, ServiceHeartbeat> = HashMap::new();
+    let mut silent: HashSet<String> = HashSet::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let start = if cursor == "-" { cursor.clone() } else { format!("({}", cursor) };
+        let reply: redis::RedisResult<StreamRangeReply> = match client.get_async_connection().await {
+            Ok(mut conn) => conn.xrange_count(SERVICE_HEARTBEATS_STREAM, start, "+", 10_000).await,
+            Err(e) => Err(e),
+        };
+        match reply {
+            Ok(reply) => {
+                for entry in &reply.ids {
+                    let heartbeat = entry
+                        .get::<String>("heartbeat")
+                        .and_then(|json| serde_json::from_str::<ServiceHeartbeat>(&json).ok());
+                    if let Some(heartbeat) = heartbeat {
+                        last_seen.insert(heartbeat.service.clone(), heartbeat);
+                    }
+                }
+                if let Some(entry) = reply.ids.last() {
+                    cursor = entry.id.clone();
+                }
+            }
+            Err(e) => {
+                warn!("Failed to read service heartbeats: {}", e);
+                continue;
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let is_silent = |heartbeat: &ServiceHeartbeat| {
+            now - heartbeat.timestamp > heartbeat.interval_secs as i64 * missed
+        };
+        let healthd_alive = last_seen.get(HEALTHD).is_some_and(|h| !is_silent(h));
+        for (service, heartbeat) in &last_seen {
+            let quiet = is_silent(heartbeat);
+            let message = if quiet && !silent.contains(service) {
+                if healthd_alive {
+                    continue; // healthd raises this one
+                }
+                silent.insert(service.clone());
+                format!("🚨 {} has sent no heartbeat for {}s", service, now - heartbeat.timestamp)
+            } else if !quiet && silent.remove(service) {
+                format!("✅ {} is sending heartbeats again", service)
+            } else {
+                continue;
+            };
+            info!("💓 {}", message);
+            sinks
+                .send(&Alert {
+                    level: if quiet { "CRITICAL" } else { "INFO" }.to_string(),
+                    message,
+                    timestamp: Utc::now().to_rfc3339(),
+                    service: SERVICE_HEARTBEATS_STREAM.to_string(),
+                })
+                .await;
+        }
+    }
+}
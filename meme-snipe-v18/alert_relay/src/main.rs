@@ -1,21 +1,91 @@
 // alert_relay/src/main.rs
+mod heartbeats;
+
 use anyhow::*;
+use axum::{routing::get, Router};
+use lazy_static::lazy_static;
+use prometheus::{register_counter_vec, CounterVec, Encoder, TextEncoder};
 use redis::AsyncCommands;
+use shared_models::{spawn_heartbeat, TradingStateRecord, KILL_SWITCH_CHANNEL};
 use std::env;
 use tracing::{info, warn, error};
 use chrono::Utc;
 
+lazy_static! {
+    static ref ALERTS_TOTAL: CounterVec = register_counter_vec!(
+        "alert_relay_alerts_total",
+        "Alerts relayed, by level and source channel.",
+        &["level", "service"]
+    )
+    .unwrap();
+    static ref DELIVERIES_TOTAL: CounterVec = register_counter_vec!(
+        "alert_relay_deliveries_total",
+        "Attempts to deliver an alert to a sink, by level and outcome.",
+        &["sink", "level", "outcome"]
+    )
+    .unwrap();
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
-struct Alert {
-    message: String,
-    timestamp: String,
-    service: String,
-    level: String,
+pub struct Alert {
+    pub message: String,
+    pub timestamp: String,
+    pub service: String,
+    pub level: String,
+}
+
+/// Where alerts are delivered; unset sinks are skipped.
+#[derive(Clone)]
+pub struct Sinks {
+    telegram_bot_token: Option<String>,
+    telegram_chat_id: Option<String>,
+    discord_webhook_url: Option<String>,
+}
+
+impl Sinks {
+    pub async fn send(&self, alert: &Alert) {
+        ALERTS_TOTAL
+            .with_label_values(&[&alert.level, &alert.service])
+            .inc();
+
+        // Send to Telegram
+        if let (Some(ref token), Some(ref chat_id)) = (&self.telegram_bot_token, &self.telegram_chat_id) {
+            let result = send_telegram_alert(token, chat_id, alert).await;
+            record_delivery("telegram", alert, &result);
+            if let Err(e) = result {
+                error!("Failed to send Telegram alert: {}", e);
+            }
+        }
+        
+        // Send to Discord
+        if let Some(ref webhook_url) = self.discord_webhook_url {
+            let result = send_discord_alert(webhook_url, alert).await;
+            record_delivery("discord", alert, &result);
+            if let Err(e) = result {
+                error!("Failed to send Discord alert: {}", e);
+            }
+        }
+    }
+}
+
+fn record_delivery(sink: &str, alert: &Alert, result: &Result<()>) {
+    let outcome = if result.is_ok() { "sent" } else { "failed" };
+    DELIVERIES_TOTAL
+        .with_label_values(&[sink, &alert.level, outcome])
+        .inc();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let log_level = shared_config::log_level::init();
     
     let redis_url = env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://redis:6379".to_string());
@@ -26,8 +96,27 @@ async fn main() -> Result<()> {
     info!("🚨 Starting Alert Relay...");
     info!("📱 Telegram: {}", if telegram_bot_token.is_some() { "Enabled" } else { "Disabled" });
     info!("💬 Discord: {}", if discord_webhook_url.is_some() { "Enabled" } else { "Disabled" });
+    let sinks = Sinks {
+        telegram_bot_token,
+        telegram_chat_id,
+        discord_webhook_url,
+    };
     
     let client = redis::Client::open(redis_url)?;
+    spawn_heartbeat!(client.clone(), "alert_relay");
+    tokio::spawn(heartbeats::watch(client.clone(), sinks.clone()));
+
+    let metrics = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let metrics_listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(metrics_listener, metrics).await {
+            error!("Metrics server stopped: {}", e);
+        }
+    });
+
     let mut conn = client.get_async_connection().await?;
     
     // Subscribe to alert channels
@@ -35,7 +124,7 @@ async fn main() -> Result<()> {
     pubsub.subscribe("alerts").await?;
     pubsub.subscribe("trading_alerts").await?;
     pubsub.subscribe("system_alerts").await?;
-    pubsub.subscribe("kill_switch_channel").await?;
+    pubsub.subscribe(KILL_SWITCH_CHANNEL).await?;
     
     info!("📡 Listening for alerts...");
     
@@ -43,7 +132,10 @@ async fn main() -> Result<()> {
         match pubsub.get_message().await {
             Ok(msg) => {
                 let channel: String = msg.get_channel_name().to_string();
-                let payload: String = msg.get_payload()?;
+                let mut payload: String = msg.get_payload()?;
+                if channel == KILL_SWITCH_CHANNEL {
+                    payload = describe_trading_state(&payload);
+                }
                 
                 info!("📨 Alert from {}: {}", channel, payload);
                 
@@ -54,19 +146,7 @@ async fn main() -> Result<()> {
                     level: determine_alert_level(&channel, &payload),
                 };
                 
-                // Send to Telegram
-                if let (Some(ref token), Some(ref chat_id)) = (&telegram_bot_token, &telegram_chat_id) {
-                    if let Err(e) = send_telegram_alert(token, chat_id, &alert).await {
-                        error!("Failed to send Telegram alert: {}", e);
-                    }
-                }
-                
-                // Send to Discord
-                if let Some(ref webhook_url) = discord_webhook_url {
-                    if let Err(e) = send_discord_alert(webhook_url, &alert).await {
-                        error!("Failed to send Discord alert: {}", e);
-                    }
-                }
+                sinks.send(&alert).await;
             }
             Err(e) => {
                 error!("Redis subscription error: {}", e);
@@ -76,8 +156,22 @@ async fn main() -> Result<()> {
     }
 }
 
+/// A trading state change as people read it; the channel carries a
+/// `TradingStateRecord`.
+fn describe_trading_state(payload: &str) -> String {
+    match serde_json::from_str::<TradingStateRecord>(payload) {
+        std::result::Result::Ok(record) if record.state.is_active() => {
+            format!("✅ Trading resumed by {}", record.set_by)
+        }
+        std::result::Result::Ok(record) => {
+            format!("🚨 Trading {} by {}", record.state, record.set_by)
+        }
+        Err(_) => payload.to_string(),
+    }
+}
+
 fn determine_alert_level(channel: &str, message: &str) -> String {
-    if channel == "kill_switch_channel" || message.contains("🚨") {
+    if (channel == KILL_SWITCH_CHANNEL && !message.starts_with('✅')) || message.contains("🚨") {
         "CRITICAL".to_string()
     } else if message.contains("⚠️") || channel.contains("system") {
         "WARNING".to_string()
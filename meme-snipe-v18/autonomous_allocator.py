@@ -106,8 +106,8 @@ class AutonomousAllocator:
         """Initialize default paper trading strategies"""
         default_strategies = [
             "momentum_5m", "mean_revert_1h", "social_buzz", "bridge_inflow",
-            "korean_time_burst", "liquidity_migration", "rug_pull_sniffer",
-            "airdrop_rotation", "dev_wallet_drain", "perp_basis_arb"
+            "korean_time_burst", "liquidity_migration", "lp_unlock_short",
+            "holder_growth", "dev_wallet_drain", "perp_basis_arb"
         ]
         
         for strategy_id in default_strategies:
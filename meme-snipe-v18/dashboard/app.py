@@ -3,7 +3,7 @@ import json
 import sqlite3
 import sys
 from datetime import datetime, timedelta
-from flask import Flask, jsonify, render_template
+from flask import Flask, jsonify, render_template, request
 import redis
 import humanize
 from dotenv import load_dotenv
@@ -170,7 +170,7 @@ def dashboard():
 
     try:
         # Fetch all trades and create a DataFrame
-        trades_df = pd.read_sql_query("SELECT * FROM trades WHERE status LIKE 'CLOSED_%'", conn)
+        trades_df = pd.read_sql_query("SELECT * FROM trades WHERE status LIKE 'CLOSED%'", conn)
         if not trades_df.empty:
             trades_df['timestamp'] = pd.to_datetime(trades_df['entry_time'])
             trades_df.set_index('timestamp', inplace=True)
@@ -283,5 +283,38 @@ def api_trades():
         print(f"Error fetching trades: {e}")
         return jsonify({'error': 'Failed to fetch trades'}), 500
 
+@app.route('/api/benchmarks')
+def api_benchmarks():
+    """Return the allocator's alpha/beta of each strategy and the portfolio vs SOL and the meme basket."""
+    try:
+        report = redis_client.get("benchmark_performance")
+        if not report:
+            return jsonify({'status': 'PENDING', 'message': 'No benchmark report has been published yet'})
+        return jsonify(json.loads(report))
+    except Exception as e:
+        print(f"Error fetching benchmark report: {e}")
+        return jsonify({'error': 'Failed to fetch benchmark report'}), 500
+
+@app.route('/api/allocations/explanations')
+def api_allocation_explanations():
+    """Return the allocator's last published allocation sets, newest first, with the inputs behind each weight."""
+    try:
+        limit = request.args.get('limit', default=20, type=int)
+        runs = redis_client.lrange("allocator:explanations", 0, max(limit, 1) - 1)
+        return jsonify([json.loads(run) for run in runs])
+    except Exception as e:
+        print(f"Error fetching allocation explanations: {e}")
+        return jsonify({'error': 'Failed to fetch allocation explanations'}), 500
+
+@app.route('/api/experiments')
+def api_experiments():
+    """Return the latest result of each A/B test of strategy variants, keyed by main spec id."""
+    try:
+        results = redis_client.hgetall("allocator:experiments")
+        return jsonify({main_id: json.loads(result) for main_id, result in results.items()})
+    except Exception as e:
+        print(f"Error fetching A/B test results: {e}")
+        return jsonify({'error': 'Failed to fetch A/B test results'}), 500
+
 if __name__ == '__main__':
     app.run(host='0.0.0.0', port=5000)
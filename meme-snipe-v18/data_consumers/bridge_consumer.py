@@ -1,37 +0,0 @@
-import redis
-import json
-import time
-import os
-import logging
-
-# Configure logging
-logging.basicConfig(level=logging.INFO, format='%(asctime)s - %(levelname)s - %(message)s')
-
-def main():
-    logging.warning("🚀 Starting Bridge Event Consumer (SIMULATED DATA)...")
-    logging.warning("This consumer is currently using simulated data and is not connected to a live bridge event feed.")
-    logging.warning("For production use, this must be replaced with a real data source for cross-chain events.")
-    
-    r = redis.Redis.from_url(os.getenv("REDIS_URL", "redis://redis:6379"), decode_responses=True)
-    
-    tokens = ["SOL_MEME1", "SOL_MEME2", "SOL_MEME3", "SOL_MEME4", "SOL_MEME5"]
-    
-    while True:
-        # This loop will do nothing in the refactored version to avoid publishing fake data.
-        # It will just sleep. A real implementation would poll a real data source here.
-        time.sleep(60)
-        # The following code is commented out to prevent fake data from being published.
-        # if random.random() < 0.1: 
-        #     event = {
-        #         "type": "Bridge",
-        #         "token_address": random.choice(tokens),
-        #         "source_chain": "ethereum",
-        #         "destination_chain": "solana",
-        #         "volume_usd": random.uniform(50_000, 250_000)
-        #     }
-        #     r.xadd("events:bridge", {"event": json.dumps(event)})
-        #     logging.info(f"Published SIMULATED Bridge Event: {event['token_address']} bridged ${event['volume_usd']:.2f}")
-        
-
-if __name__ == "__main__":
-    main()
@@ -0,0 +1,79 @@
+// data_consumers/bridge_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::{HashMap, HashSet};
+
+pub struct Config {
+    pub redis_url: String,
+    pub tracked_tokens: HashSet<String>, // Solana mints inflows are attributed to
+    pub wormhole_api_url: String,
+    pub wormhole_token_map: HashMap<(u16, String), String>, // (origin chain id, lowercase origin address) -> Solana mint
+    pub debridge_api_url: String,
+    pub poll_interval_secs: u64,
+    pub window_secs: i64, // Inflows are summed per token over this window
+    pub publish_interval_secs: u64,
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("bridge_consumer");
+        let wormhole_token_map: HashMap<(u16, String), String> = c
+            .list("WORMHOLE_TOKEN_MAP", "")
+            .iter()
+            .filter_map(|spec| {
+                let parts: Vec<&str> = spec.split(':').collect();
+                match parts.as_slice() {
+                    [chain, address, mint] => match chain.parse() {
+                        Ok(chain) => Some(((chain, address.to_lowercase()), mint.to_string())),
+                        Err(_) => {
+                            c.invalid(
+                                "WORMHOLE_TOKEN_MAP",
+                                format!("{} has a non-integer chain id", spec),
+                            );
+                            None
+                        }
+                    },
+                    _ => {
+                        c.invalid(
+                            "WORMHOLE_TOKEN_MAP",
+                            format!("{} is not CHAIN_ID:ORIGIN_ADDRESS:MINT", spec),
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        // A mapped mint is tracked without being listed twice
+        let tracked_tokens = c
+            .list("BRIDGE_TRACKED_TOKENS", "")
+            .into_iter()
+            .chain(wormhole_token_map.values().cloned())
+            .collect();
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            tracked_tokens,
+            wormhole_api_url: c.get(
+                "WORMHOLESCAN_API_URL",
+                "https://api.wormholescan.io".to_string(),
+            ),
+            wormhole_token_map,
+            debridge_api_url: c.get(
+                "DEBRIDGE_STATS_API_URL",
+                "https://stats-api.dln.trade".to_string(),
+            ),
+            poll_interval_secs: c.get("BRIDGE_POLL_INTERVAL_SECS", 30),
+            window_secs: c.get("BRIDGE_WINDOW_SECS", 3600),
+            publish_interval_secs: c.get("BRIDGE_PUBLISH_INTERVAL_SECS", 60),
+            stream_maxlen: c.get("BRIDGE_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("BRIDGE_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
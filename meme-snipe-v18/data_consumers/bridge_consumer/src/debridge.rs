@@ -0,0 +1,128 @@
+// data_consumers/bridge_consumer/src/debridge.rs
+//! deBridge (DLN) orders filled on Solana, from the DLN stats API. An order
+//! whose take side is a tracked mint is an inflow into that token, valued
+//! from our own price feed since the API doesn't quote USD.
+//!
+//! The API wraps most scalars in `{"stringValue": .., "bigIntegerValue": ..}`
+//! objects, so fields are read leniently from raw JSON.
+
+use crate::config::CONFIG;
+use crate::Inflow;
+use anyhow::{Context, Result};
+use consumer_common::prices::PriceCache;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const SOLANA_CHAIN_ID: u64 = 7565164; // deBridge's internal id for Solana
+const PAGE_SIZE: u64 = 100;
+
+fn chain_name(chain_id: u64) -> String {
+    match chain_id {
+        1 => "ethereum".to_string(),
+        10 => "optimism".to_string(),
+        56 => "bsc".to_string(),
+        137 => "polygon".to_string(),
+        8453 => "base".to_string(),
+        42161 => "arbitrum".to_string(),
+        43114 => "avalanche".to_string(),
+        other => format!("debridge:{}", other),
+    }
+}
+
+/// A scalar that may be a plain string or number, or wrapped in an object.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(map) => map
+            .get("stringValue")
+            .or_else(|| map.get("bigIntegerValue"))
+            .and_then(text),
+        _ => None,
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    text(value)?.parse().ok()
+}
+
+pub struct DeBridge {
+    http: Client,
+}
+
+impl DeBridge {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(15))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// The latest fulfilled orders into Solana for tracked tokens.
+    pub async fn inflows(&self, prices: &PriceCache) -> Result<Vec<Inflow>> {
+        let response: Value = self
+            .http
+            .post(format!(
+                "{}/api/Orders/filteredList",
+                CONFIG.debridge_api_url
+            ))
+            .json(&json!({
+                "takeChainIds": [SOLANA_CHAIN_ID],
+                "orderStates": ["Fulfilled", "SentUnlock", "ClaimedUnlock"],
+                "skip": 0,
+                "take": PAGE_SIZE,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse DLN orders")?;
+
+        let orders = response
+            .get("orders")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let mut inflows = Vec::new();
+        for order in &orders {
+            let give = &order["giveOfferWithMetadata"];
+            let take = &order["takeOfferWithMetadata"];
+            let Some(mint) = text(&take["tokenAddress"]) else {
+                continue;
+            };
+            if !CONFIG.tracked_tokens.contains(&mint) {
+                continue;
+            }
+            let source_chain = number(&give["chainId"]).unwrap_or(0.0) as u64;
+            if source_chain == SOLANA_CHAIN_ID {
+                continue; // Solana to Solana swaps aren't inflows
+            }
+            let (Some(id), Some(amount), Some(decimals), Some(price)) = (
+                text(&order["orderId"]),
+                number(&take["amount"]),
+                number(&take["metadata"]["decimals"]),
+                prices.price(&mint),
+            ) else {
+                continue;
+            };
+            let volume_usd = amount / 10f64.powi(decimals as i32) * price;
+            if volume_usd <= 0.0 {
+                continue;
+            }
+            inflows.push(Inflow {
+                id: format!("debridge:{}", id),
+                token_address: mint,
+                source_chain: chain_name(source_chain),
+                volume_usd,
+                timestamp: number(&order["creationTimestamp"])
+                    .map(|t| t as i64)
+                    .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            });
+        }
+        Ok(inflows)
+    }
+}
@@ -0,0 +1,265 @@
+// data_consumers/bridge_consumer/src/main.rs
+//! Watches Wormhole and deBridge transfers into Solana every
+//! BRIDGE_POLL_INTERVAL_SECS, attributes them to the tracked mints, and sums
+//! each token's inflow over the last BRIDGE_WINDOW_SECS. Every
+//! BRIDGE_PUBLISH_INTERVAL_SECS a BridgeEvent carrying that rolling total
+//! goes to `events:bridge` for each token whose total changed, its source
+//! chain being the one that contributed the most.
+
+mod config;
+mod debridge;
+mod wormhole;
+
+use crate::config::CONFIG;
+use crate::debridge::DeBridge;
+use crate::wormhole::Wormhole;
+use anyhow::Result;
+use axum::{routing::get, Router};
+use consumer_common::prices::{follow_prices, PriceCache};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{BridgeEvent, DataSourceHeartbeat, MarketEvent};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "bridge_consumer";
+const SEEN_CAPACITY: usize = 5_000; // Several polls' worth of transfer ids
+
+lazy_static! {
+    static ref INFLOWS: IntCounterVec = register_int_counter_vec!(
+        "bridge_inflows_total",
+        "Transfers into Solana attributed to a tracked token, by bridge.",
+        &["bridge"]
+    )
+    .unwrap();
+    static ref FETCH_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "bridge_fetch_errors_total",
+        "Failed bridge API requests.",
+        &["bridge"]
+    )
+    .unwrap();
+    static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "bridge_events_published_total",
+        "BridgeEvents published, by dominant source chain.",
+        &["source_chain"]
+    )
+    .unwrap();
+}
+
+/// One transfer into Solana of a tracked token.
+#[derive(Debug, Clone)]
+pub struct Inflow {
+    pub id: String, // Prefixed with the bridge, unique across both
+    pub token_address: String,
+    pub source_chain: String,
+    pub volume_usd: f64,
+    pub timestamp: i64,
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Transfer ids already counted, oldest evicted first.
+#[derive(Default)]
+struct Seen {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl Seen {
+    /// Records the id; false if it was already there.
+    fn insert(&mut self, id: &str) -> bool {
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push_back(id.to_string());
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Per-token inflows over the rolling window.
+#[derive(Default)]
+struct Flows {
+    inflows: HashMap<String, VecDeque<Inflow>>, // Token -> inflows, in arrival order
+    published: HashMap<String, f64>,            // Token -> total last published
+}
+
+impl Flows {
+    fn add(&mut self, inflow: Inflow) {
+        self.inflows
+            .entry(inflow.token_address.clone())
+            .or_default()
+            .push_back(inflow);
+    }
+
+    /// BridgeEvents for tokens whose windowed total changed since the last call.
+    fn events(&mut self, now: i64) -> Vec<BridgeEvent> {
+        let cutoff = now - CONFIG.window_secs;
+        let mut events = Vec::new();
+        self.inflows.retain(|token, inflows| {
+            inflows.retain(|inflow| inflow.timestamp >= cutoff);
+            let mut by_chain: HashMap<&str, f64> = HashMap::new();
+            for inflow in inflows.iter() {
+                *by_chain.entry(&inflow.source_chain).or_default() += inflow.volume_usd;
+            }
+            let total: f64 = by_chain.values().sum();
+            let previous = self.published.get(token).copied().unwrap_or(0.0);
+            if (total - previous).abs() > f64::EPSILON {
+                if let Some((chain, _)) = by_chain.iter().max_by(|a, b| a.1.total_cmp(b.1)) {
+                    events.push(BridgeEvent {
+                        timestamp: now,
+                        token_address: token.clone(),
+                        source_chain: chain.to_string(),
+                        destination_chain: "solana".to_string(),
+                        volume_usd: total,
+                    });
+                }
+            }
+            if inflows.is_empty() {
+                self.published.remove(token);
+                return false;
+            }
+            self.published.insert(token.clone(), total);
+            true
+        });
+        events
+    }
+}
+
+async fn bridge_loop(
+    mut conn: MultiplexedConnection,
+    prices: Arc<PriceCache>,
+    last_processed: Arc<AtomicI64>,
+) {
+    let wormhole = Wormhole::new();
+    let debridge = DeBridge::new();
+    let mut seen = Seen::default();
+    let mut flows = Flows::default();
+    let mut poll = tokio::time::interval(Duration::from_secs(CONFIG.poll_interval_secs));
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut flush = tokio::time::interval(Duration::from_secs(CONFIG.publish_interval_secs));
+    flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = poll.tick() => {
+                let cutoff = chrono::Utc::now().timestamp() - CONFIG.window_secs;
+                let sources = [
+                    ("wormhole", wormhole.inflows(&prices).await),
+                    ("debridge", debridge.inflows(&prices).await),
+                ];
+                for (bridge, result) in sources {
+                    let inflows = match result {
+                        Ok(inflows) => inflows,
+                        Err(e) => {
+                            warn!(error = %e, bridge, "Bridge API request failed.");
+                            FETCH_ERRORS.with_label_values(&[bridge]).inc();
+                            continue;
+                        }
+                    };
+                    for inflow in inflows {
+                        // Anything older than the window can't count towards a total
+                        if inflow.timestamp < cutoff || !seen.insert(&inflow.id) {
+                            continue;
+                        }
+                        INFLOWS.with_label_values(&[bridge]).inc();
+                        last_processed.fetch_max(inflow.timestamp, Ordering::Relaxed);
+                        flows.add(inflow);
+                    }
+                }
+            }
+            _ = flush.tick() => {
+                for event in flows.events(chrono::Utc::now().timestamp()) {
+                    let chain = event.source_chain.clone();
+                    let token = event.token_address.clone();
+                    match publish(&mut conn, "events:bridge", &MarketEvent::Bridge(event)).await {
+                        Ok(()) => EVENTS_PUBLISHED.with_label_values(&[&chain]).inc(),
+                        Err(e) => error!(error = %e, token = %token, "Failed to publish BridgeEvent."),
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🌉 Starting Bridge Consumer for {} tracked tokens ({}s window)...",
+        CONFIG.tracked_tokens.len(),
+        CONFIG.window_secs
+    );
+    if CONFIG.tracked_tokens.is_empty() {
+        anyhow::bail!("BRIDGE_TRACKED_TOKENS or WORMHOLE_TOKEN_MAP must name at least one token");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let prices = Arc::new(PriceCache::default());
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(follow_prices(client, prices.clone()));
+    tokio::spawn(bridge_loop(conn.clone(), prices, last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
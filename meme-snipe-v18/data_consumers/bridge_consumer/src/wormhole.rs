@@ -0,0 +1,172 @@
+// data_consumers/bridge_consumer/src/wormhole.rs
+//! Wormhole token-bridge transfers into Solana from Wormholescan's operations
+//! feed. A transfer is attributed to a Solana mint either directly, for
+//! Solana-native tokens coming back from another chain, or through
+//! WORMHOLE_TOKEN_MAP for tokens native elsewhere.
+
+use crate::config::CONFIG;
+use crate::Inflow;
+use anyhow::{Context, Result};
+use consumer_common::prices::PriceCache;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const SOLANA_CHAIN_ID: u16 = 1;
+const PAGE_SIZE: &str = "100";
+
+#[derive(Debug, Deserialize)]
+struct OperationsResponse {
+    #[serde(default)]
+    operations: Vec<Operation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Operation {
+    id: String,
+    #[serde(default)]
+    content: Option<Content>,
+    #[serde(default)]
+    source_chain: Option<SourceChain>,
+    #[serde(default)]
+    data: Option<OperationData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Content {
+    // Wormholescan's spelling
+    #[serde(rename = "standarizedProperties", default)]
+    properties: Option<Properties>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Properties {
+    #[serde(default)]
+    from_chain: u16,
+    #[serde(default)]
+    to_chain: u16,
+    #[serde(default)]
+    token_chain: u16,
+    #[serde(default)]
+    token_address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceChain {
+    #[serde(default)]
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationData {
+    #[serde(default)]
+    token_amount: Option<String>,
+    #[serde(default)]
+    usd_amount: Option<String>,
+}
+
+pub fn chain_name(chain_id: u16) -> String {
+    match chain_id {
+        2 => "ethereum".to_string(),
+        4 => "bsc".to_string(),
+        5 => "polygon".to_string(),
+        6 => "avalanche".to_string(),
+        23 => "arbitrum".to_string(),
+        24 => "optimism".to_string(),
+        30 => "base".to_string(),
+        other => format!("wormhole:{}", other),
+    }
+}
+
+/// The Solana mint a transfer of `token_address` (native to `token_chain`)
+/// arrives as, if it is known.
+fn solana_mint(token_chain: u16, token_address: &str) -> Option<String> {
+    if token_chain == SOLANA_CHAIN_ID {
+        // Wormhole's 32-byte universal form, or already base58
+        let hex_address = token_address.trim_start_matches("0x");
+        return match hex::decode(hex_address) {
+            Ok(bytes) if bytes.len() == 32 => Some(bs58::encode(bytes).into_string()),
+            _ => Some(token_address.to_string()),
+        };
+    }
+    CONFIG
+        .wormhole_token_map
+        .get(&(token_chain, token_address.to_lowercase()))
+        .cloned()
+}
+
+pub struct Wormhole {
+    http: Client,
+}
+
+impl Wormhole {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(15))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// The latest transfers into Solana of tracked tokens, newest first.
+    pub async fn inflows(&self, prices: &PriceCache) -> Result<Vec<Inflow>> {
+        let response: OperationsResponse = self
+            .http
+            .get(format!("{}/api/v1/operations", CONFIG.wormhole_api_url))
+            .query(&[
+                ("page", "0"),
+                ("pageSize", PAGE_SIZE),
+                ("sortOrder", "DESC"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Wormholescan operations")?;
+
+        let mut inflows = Vec::new();
+        for op in response.operations {
+            let Some(props) = op.content.and_then(|c| c.properties) else {
+                continue;
+            };
+            if props.to_chain != SOLANA_CHAIN_ID || props.from_chain == SOLANA_CHAIN_ID {
+                continue;
+            }
+            let Some(mint) = solana_mint(props.token_chain, &props.token_address) else {
+                continue;
+            };
+            if !CONFIG.tracked_tokens.contains(&mint) {
+                continue;
+            }
+            let data = op.data.as_ref();
+            let usd = data
+                .and_then(|d| d.usd_amount.as_deref())
+                .and_then(|usd| usd.parse::<f64>().ok())
+                .or_else(|| {
+                    // Wormholescan doesn't price every token; fall back to our own feed
+                    let amount: f64 = data?.token_amount.as_deref()?.parse().ok()?;
+                    Some(amount * prices.price(&mint)?)
+                });
+            let Some(usd) = usd.filter(|usd| *usd > 0.0) else {
+                continue;
+            };
+            inflows.push(Inflow {
+                id: format!("wormhole:{}", op.id),
+                token_address: mint,
+                source_chain: chain_name(props.from_chain),
+                volume_usd: usd,
+                timestamp: op
+                    .source_chain
+                    .and_then(|s| s.timestamp)
+                    .map(|t| t.timestamp())
+                    .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            });
+        }
+        Ok(inflows)
+    }
+}
@@ -0,0 +1,7 @@
+// data_consumers/consumer_common/src/lib.rs
+//! Pieces the data consumers share: USD prices followed from the price
+//! streams (`prices`) and the text scoring behind the social consumers'
+//! SocialMentions (`social`).
+
+pub mod prices;
+pub mod social;
@@ -0,0 +1,108 @@
+// data_consumers/consumer_common/src/prices.rs
+//! Latest USD prices, followed from the price feeds' own streams so consumers
+//! can value swaps, transfers and bridged amounts without another API.
+
+use parking_lot::RwLock;
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use shared_models::MarketEvent;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H7FYD4KCoNkY11McCe8BenwNYB";
+
+#[derive(Default)]
+pub struct PriceCache {
+    tokens: RwLock<HashMap<String, f64>>,
+    sol_usd: RwLock<Option<f64>>,
+}
+
+impl PriceCache {
+    /// USD price of `mint`: stablecoins at par, SOL from the SOL feed.
+    pub fn price(&self, mint: &str) -> Option<f64> {
+        match mint {
+            USDC_MINT | USDT_MINT => Some(1.0),
+            WSOL_MINT => self.sol_usd(),
+            _ => self.tokens.read().get(mint).copied(),
+        }
+    }
+
+    pub fn sol_usd(&self) -> Option<f64> {
+        *self.sol_usd.read()
+    }
+
+    fn apply(&self, event: MarketEvent) {
+        match event {
+            MarketEvent::Price(tick) if tick.price_usd > 0.0 => {
+                self.tokens
+                    .write()
+                    .insert(tick.token_address, tick.price_usd);
+            }
+            MarketEvent::SolPrice(sol) if sol.price_usd > 0.0 => {
+                *self.sol_usd.write() = Some(sol.price_usd);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Follows events:price and events:sol_price from their latest entries,
+/// reconnecting on errors.
+pub async fn follow_prices(client: redis::Client, cache: Arc<PriceCache>) {
+    info!("Following price streams for USD valuation.");
+    let streams = ["events:price", "events:sol_price"];
+    let mut last_ids = ["$".to_string(), "$".to_string()];
+    let opts = StreamReadOptions::default().count(500).block(5000);
+    loop {
+        let mut conn = match client.get_multiplexed_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Price follower failed to connect to Redis. Retrying in 5s.");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        // Seed SOL so transfers can be valued before its next tick
+        let latest: redis::RedisResult<redis::streams::StreamRangeReply> =
+            conn.xrevrange_count("events:sol_price", "+", "-", 1).await;
+        if let Ok(latest) = latest {
+            for entry in latest.ids {
+                if let Some(event) = entry
+                    .get::<String>("event")
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                {
+                    cache.apply(event);
+                }
+            }
+        }
+
+        loop {
+            let ids: Vec<&str> = last_ids.iter().map(String::as_str).collect();
+            let reply: StreamReadReply = match conn.xread_options(&streams, &ids, &opts).await {
+                Ok(reply) => reply,
+                Err(e) => {
+                    warn!(error = %e, "Error reading price streams. Reconnecting.");
+                    break;
+                }
+            };
+            for stream in reply.keys {
+                let Some(index) = streams.iter().position(|s| *s == stream.key) else {
+                    continue;
+                };
+                for entry in stream.ids {
+                    last_ids[index] = entry.id.clone();
+                    if let Some(event) = entry
+                        .get::<String>("event")
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                    {
+                        cache.apply(event);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// data_consumers/consumer_common/src/social.rs
+//! Text scoring shared by the social consumers. Each keeps its own lexicon
+//! and decides which mints count; splitting posts into words, spotting mint
+//! addresses, the negation-aware sentiment score and the audience-size
+//! influence scale are the same everywhere, so scores stay comparable across
+//! sources.
+
+const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "dont", "don't", "isnt", "isn't", "aint", "ain't",
+];
+
+/// Words of a post; emoji come out as words since posts space them out.
+pub fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| {
+        c.is_whitespace() || matches!(c, ',' | '.' | '!' | '?' | ':' | ';' | '(' | ')' | '"')
+    })
+    .filter(|w| !w.is_empty())
+}
+
+pub fn looks_like_mint(word: &str) -> bool {
+    (32..=44).contains(&word.len()) && word.chars().all(|c| BASE58.contains(c))
+}
+
+/// Mint addresses inside a link or path, e.g. `dexscreener.com/solana/<mint>`
+/// or `pump.fun/coin/<mint>`.
+pub fn mints_in_link(link: &str) -> impl Iterator<Item = &str> {
+    link.split(['/', '=', '?', '&'])
+        .filter(|part| looks_like_mint(part))
+}
+
+/// Sentiment in [-1, 1] from `lexicon`'s word weights; a negation flips the
+/// next scored word.
+pub fn sentiment(text: &str, lexicon: &[(&str, f64)]) -> f64 {
+    let lower = text.to_lowercase();
+    let mut total = 0.0;
+    let mut negated = false;
+    for word in words(&lower) {
+        if NEGATIONS.contains(&word) {
+            negated = true;
+            continue;
+        }
+        if let Some((_, weight)) = lexicon.iter().find(|(w, _)| *w == word) {
+            total += if negated { -weight } else { *weight };
+            negated = false;
+        }
+    }
+    (total / 2.0).tanh()
+}
+
+/// Influence in [0, 1] from an audience size (followers, channel members),
+/// matching the Python social consumer's score: 1M scores 1.
+pub fn audience_influence(audience: u64) -> f64 {
+    if audience <= 1 {
+        return 0.0;
+    }
+    ((audience as f64).log10() / 6.0).min(1.0)
+}
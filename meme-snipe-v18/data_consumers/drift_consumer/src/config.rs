@@ -0,0 +1,46 @@
+// data_consumers/drift_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::HashMap;
+
+pub struct Config {
+    pub redis_url: String,
+    pub data_api_url: String,
+    pub markets: HashMap<String, String>, // Perp symbol, e.g. "SOL-PERP" -> spot mint
+    pub poll_interval_secs: u64,
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("drift_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            data_api_url: c.get(
+                "DRIFT_DATA_API_URL",
+                "https://data.api.drift.trade".to_string(),
+            ),
+            markets: c
+                .list("DRIFT_PERP_MARKETS", "")
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((symbol, mint)) => Some((symbol.to_uppercase(), mint.to_string())),
+                    None => {
+                        c.invalid("DRIFT_PERP_MARKETS", format!("{} is not SYMBOL:MINT", spec));
+                        None
+                    }
+                })
+                .collect(),
+            poll_interval_secs: c.get("DRIFT_POLL_INTERVAL_SECS", 10),
+            stream_maxlen: c.get("DRIFT_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("DRIFT_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
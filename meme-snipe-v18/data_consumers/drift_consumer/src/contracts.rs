@@ -0,0 +1,81 @@
+// data_consumers/drift_consumer/src/contracts.rs
+//! Drift's `/contracts` endpoint: one entry per market with its last (mark)
+//! price, oracle index price, open interest and hourly funding. Numbers come
+//! back as strings.
+
+use crate::config::CONFIG;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+struct ContractsResponse {
+    #[serde(default)]
+    contracts: Vec<Contract>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Contract {
+    pub ticker_id: String,
+    #[serde(default)]
+    pub product_type: String,
+    #[serde(default, deserialize_with = "number")]
+    pub last_price: Option<f64>,
+    #[serde(default, deserialize_with = "number")]
+    pub index_price: Option<f64>,
+    #[serde(default, deserialize_with = "number")]
+    pub open_interest: Option<f64>, // Base units
+    #[serde(default, deserialize_with = "number")]
+    pub funding_rate: Option<f64>, // Percent, per hour
+    #[serde(default, deserialize_with = "number")]
+    pub next_funding_rate_timestamp: Option<f64>, // Unix seconds
+}
+
+/// Accepts a JSON number or a numeric string.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(f64),
+        Text(String),
+    }
+    Ok(match Option::<Raw>::deserialize(deserializer)? {
+        Some(Raw::Number(n)) => Some(n),
+        Some(Raw::Text(s)) => s.parse().ok(),
+        None => None,
+    })
+}
+
+pub struct DriftData {
+    http: Client,
+}
+
+impl DriftData {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// Every perp market Drift lists; spot markets are left out.
+    pub async fn perp_contracts(&self) -> Result<Vec<Contract>> {
+        let response: ContractsResponse = self
+            .http
+            .get(format!("{}/contracts", CONFIG.data_api_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Drift contracts")?;
+        Ok(response
+            .contracts
+            .into_iter()
+            .filter(|c| c.product_type.eq_ignore_ascii_case("perp"))
+            .collect())
+    }
+}
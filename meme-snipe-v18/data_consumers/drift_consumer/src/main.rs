@@ -0,0 +1,191 @@
+// data_consumers/drift_consumer/src/main.rs
+//! Polls Drift's market data every DRIFT_POLL_INTERVAL_SECS for the
+//! DRIFT_PERP_MARKETS perps and publishes, keyed by each perp's spot mint:
+//! a FundingEvent to `events:funding` carrying the hourly funding rate, mark
+//! and oracle prices and open interest, and a PriceTick of the perp mark to
+//! `events:perp_price`. Perp ticks get their own stream so spot strategies
+//! reading `events:price` never mistake a perp mark for a spot price.
+
+mod config;
+mod contracts;
+
+use crate::config::CONFIG;
+use crate::contracts::{Contract, DriftData};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, FundingEvent, MarketEvent, PriceTick};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "drift_consumer";
+
+lazy_static! {
+    static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "drift_events_published_total",
+        "Events published, by stream and perp market.",
+        &["stream", "market"]
+    )
+    .unwrap();
+    static ref FETCH_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "drift_fetch_errors_total",
+        "Failed Drift data API requests.",
+        &["endpoint"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// The market's events, or none if Drift left out its price or funding.
+fn market_events(
+    contract: &Contract,
+    mint: &str,
+    timestamp: i64,
+) -> Vec<(&'static str, MarketEvent)> {
+    let (Some(mark), Some(funding_rate)) = (contract.last_price, contract.funding_rate) else {
+        return Vec::new();
+    };
+    if mark <= 0.0 {
+        return Vec::new();
+    }
+    let oracle = contract.index_price.filter(|p| *p > 0.0);
+    let funding = MarketEvent::Funding(FundingEvent {
+        timestamp,
+        token_address: mint.to_string(),
+        funding_rate_pct: funding_rate / 100.0, // Drift reports percent; the event carries a fraction
+        next_funding_time_sec: contract
+            .next_funding_rate_timestamp
+            .map(|t| t as u64)
+            .unwrap_or_else(|| (timestamp + 3600 - timestamp % 3600) as u64), // Drift funds on the hour
+        mark_price_usd: Some(mark),
+        oracle_price_usd: oracle,
+        open_interest_usd: contract.open_interest.map(|oi| oi * oracle.unwrap_or(mark)),
+    });
+    let tick = MarketEvent::Price(PriceTick {
+        timestamp,
+        token_address: mint.to_string(),
+        price_usd: mark,
+        volume_usd_1m: 0.0, // Drift reports 24h volume only
+        venue: None,
+    });
+    vec![("events:funding", funding), ("events:perp_price", tick)]
+}
+
+async fn poll_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let drift = DriftData::new();
+    let mut missing_reported = HashSet::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.poll_interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let contracts = match drift.perp_contracts().await {
+            Ok(contracts) => contracts,
+            Err(e) => {
+                warn!(error = %e, "Drift contracts request failed.");
+                FETCH_ERRORS.with_label_values(&["contracts"]).inc();
+                continue;
+            }
+        };
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut seen = HashSet::new();
+        for contract in &contracts {
+            let market = contract.ticker_id.to_uppercase();
+            let Some(mint) = CONFIG.markets.get(&market) else {
+                continue;
+            };
+            seen.insert(market.clone());
+            for (stream, event) in market_events(contract, mint, timestamp) {
+                match publish(&mut conn, stream, &event).await {
+                    Ok(()) => {
+                        EVENTS_PUBLISHED.with_label_values(&[stream, &market]).inc();
+                        last_processed.store(timestamp, Ordering::Relaxed);
+                    }
+                    Err(e) => error!(error = %e, market = %market, "Failed to publish {}.", stream),
+                }
+            }
+        }
+        for market in CONFIG.markets.keys() {
+            // Logged once; a typo in DRIFT_PERP_MARKETS won't fix itself
+            if !seen.contains(market) && missing_reported.insert(market.clone()) {
+                warn!(market = %market, "Configured perp market not listed by Drift.");
+            }
+        }
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    let mut markets: Vec<&str> = CONFIG.markets.keys().map(String::as_str).collect();
+    markets.sort_unstable();
+    info!("🌊 Starting Drift Consumer for [{}]...", markets.join(", "));
+    if CONFIG.markets.is_empty() {
+        anyhow::bail!("DRIFT_PERP_MARKETS must name at least one market");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(poll_loop(conn.clone(), last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
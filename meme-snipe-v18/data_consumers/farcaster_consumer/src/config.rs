@@ -0,0 +1,56 @@
+// data_consumers/farcaster_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::HashMap;
+
+pub struct Config {
+    pub redis_url: String,
+    pub api_key: String,
+    pub api_url: String,
+    pub channels: Vec<String>, // Channel ids, e.g. "memes", "degen", "base"
+    pub tickers: HashMap<String, String>, // Uppercase symbol -> mint
+    pub poll_interval_secs: u64,
+    pub min_user_score: f64, // Neynar's 0-1 account quality score; filters bots and farms
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("farcaster_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            api_key: c.required("NEYNAR_API_KEY"),
+            api_url: c.get("NEYNAR_API_URL", "https://api.neynar.com".to_string()),
+            channels: c
+                .list("FARCASTER_CHANNELS", "")
+                .iter()
+                .map(|channel| channel.trim_start_matches('/').to_lowercase())
+                .collect(),
+            tickers: c
+                .list("FARCASTER_TICKERS", "")
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((symbol, mint)) => Some((
+                        symbol.trim_start_matches('$').to_uppercase(),
+                        mint.to_string(),
+                    )),
+                    None => {
+                        c.invalid("FARCASTER_TICKERS", format!("{} is not SYMBOL:MINT", spec));
+                        None
+                    }
+                })
+                .collect(),
+            poll_interval_secs: c.get("FARCASTER_POLL_INTERVAL_SECS", 15),
+            min_user_score: c.get("FARCASTER_MIN_USER_SCORE", 0.5),
+            stream_maxlen: c.get("FARCASTER_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("FARCASTER_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
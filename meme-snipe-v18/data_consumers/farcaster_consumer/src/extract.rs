@@ -0,0 +1,63 @@
+// data_consumers/farcaster_consumer/src/extract.rs
+//! Tokens a cast refers to, from `$TICKER`s listed in FARCASTER_TICKERS and
+//! mint addresses in its text or embedded links, and a lexicon sentiment in
+//! [-1, 1].
+
+use crate::config::CONFIG;
+use consumer_common::social::{self, looks_like_mint, mints_in_link, words};
+use std::collections::BTreeSet;
+
+const LEXICON: &[(&str, f64)] = &[
+    ("moon", 1.0),
+    ("bullish", 1.5),
+    ("buy", 0.5),
+    ("buying", 1.0),
+    ("aped", 1.0),
+    ("long", 0.5),
+    ("send", 0.5),
+    ("sending", 1.0),
+    ("gem", 1.0),
+    ("lfg", 1.0),
+    ("based", 0.5),
+    ("🚀", 1.0),
+    ("📈", 1.0),
+    ("🔥", 0.5),
+    ("dump", -1.0),
+    ("dumping", -1.5),
+    ("bearish", -1.5),
+    ("sell", -0.5),
+    ("selling", -1.0),
+    ("short", -0.5),
+    ("rug", -2.0),
+    ("rugged", -2.0),
+    ("scam", -2.0),
+    ("rekt", -1.0),
+    ("dead", -1.0),
+    ("📉", -1.0),
+];
+
+/// Mints the cast refers to, deduplicated.
+pub fn mentioned_mints<'a>(
+    text: &str,
+    embed_urls: impl Iterator<Item = &'a str>,
+) -> BTreeSet<String> {
+    let mut mints = BTreeSet::new();
+    for word in words(text) {
+        if let Some(symbol) = word.strip_prefix('$') {
+            if let Some(mint) = CONFIG.tickers.get(&symbol.to_uppercase()) {
+                mints.insert(mint.clone());
+            }
+        } else if looks_like_mint(word) {
+            mints.insert(word.to_string());
+        }
+    }
+    for url in embed_urls {
+        mints.extend(mints_in_link(url).map(str::to_string));
+    }
+    mints
+}
+
+/// Lexicon sentiment in [-1, 1]; a negation flips the next scored word.
+pub fn sentiment(text: &str) -> f64 {
+    social::sentiment(text, LEXICON)
+}
@@ -0,0 +1,210 @@
+// data_consumers/farcaster_consumer/src/main.rs
+//! Polls the FARCASTER_CHANNELS feed through Neynar every
+//! FARCASTER_POLL_INTERVAL_SECS and publishes a SocialMention to
+//! `events:social` for every token a new cast refers to. Casts by accounts
+//! under FARCASTER_MIN_USER_SCORE are dropped, which is what keeps this source
+//! cleaner than X: Neynar scores accounts on activity and network quality, so
+//! bots and farms score low.
+//!
+//! The first poll only records what is already in the feed, so a restart
+//! doesn't replay old casts.
+
+mod config;
+mod extract;
+mod neynar;
+
+use crate::config::CONFIG;
+use crate::extract::{mentioned_mints, sentiment};
+use crate::neynar::{Cast, Neynar};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use consumer_common::social::audience_influence;
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent, SocialMention};
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "farcaster_consumer";
+const SEEN_CAPACITY: usize = 2_000; // Several feed pages of cast hashes
+
+lazy_static! {
+    static ref CASTS: IntCounterVec = register_int_counter_vec!(
+        "farcaster_casts_total",
+        "New casts, by outcome (published, no_token, low_score).",
+        &["outcome"]
+    )
+    .unwrap();
+    static ref FETCH_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "farcaster_fetch_errors_total",
+        "Failed Neynar requests.",
+        &["endpoint"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Cast hashes already handled, oldest evicted first.
+#[derive(Default)]
+struct Seen {
+    order: VecDeque<String>,
+    hashes: HashSet<String>,
+}
+
+impl Seen {
+    /// Records the hash; false if it was already there.
+    fn insert(&mut self, hash: &str) -> bool {
+        if !self.hashes.insert(hash.to_string()) {
+            return false;
+        }
+        self.order.push_back(hash.to_string());
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+async fn handle_cast(conn: &mut MultiplexedConnection, cast: Cast) -> Option<i64> {
+    if cast.author.user_score() < CONFIG.min_user_score {
+        CASTS.with_label_values(&["low_score"]).inc();
+        return None;
+    }
+    let mints = mentioned_mints(
+        &cast.text,
+        cast.embeds.iter().filter_map(|e| e.url.as_deref()),
+    );
+    if mints.is_empty() {
+        CASTS.with_label_values(&["no_token"]).inc();
+        return None;
+    }
+
+    let timestamp = cast.timestamp.timestamp();
+    let sentiment = sentiment(&cast.text);
+    for mint in mints {
+        let mention = MarketEvent::Social(SocialMention {
+            timestamp,
+            token_address: mint.clone(),
+            source: "farcaster".to_string(),
+            sentiment,
+            author: Some(cast.author.username.clone()),
+            author_influence: Some(audience_influence(cast.author.follower_count)),
+            post_id: Some(cast.hash.clone()),
+            urgency: None,
+        });
+        if let Err(e) = publish(conn, "events:social", &mention).await {
+            error!(error = %e, token = %mint, "Failed to publish SocialMention.");
+        }
+    }
+    CASTS.with_label_values(&["published"]).inc();
+    Some(timestamp)
+}
+
+async fn poll_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let neynar = Neynar::new();
+    let mut seen = Seen::default();
+    let mut primed = false;
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.poll_interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let casts = match neynar.channel_feed().await {
+            Ok(casts) => casts,
+            Err(e) => {
+                warn!(error = %e, "Neynar channel feed request failed.");
+                FETCH_ERRORS.with_label_values(&["feed/channels"]).inc();
+                continue;
+            }
+        };
+        // Oldest first, so mentions go out in the order they were cast
+        for cast in casts.into_iter().rev() {
+            if !seen.insert(&cast.hash) || !primed {
+                continue;
+            }
+            if let Some(at) = handle_cast(&mut conn, cast).await {
+                last_processed.fetch_max(at, Ordering::Relaxed);
+            }
+        }
+        primed = true;
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🟪 Starting Farcaster Consumer for channels [{}] (min user score {})...",
+        CONFIG.channels.join(", "),
+        CONFIG.min_user_score
+    );
+    if CONFIG.channels.is_empty() {
+        anyhow::bail!("FARCASTER_CHANNELS must name at least one channel");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(poll_loop(conn.clone(), last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
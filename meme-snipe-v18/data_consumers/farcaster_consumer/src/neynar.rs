@@ -0,0 +1,96 @@
+// data_consumers/farcaster_consumer/src/neynar.rs
+//! Neynar channel feed: the latest casts in the configured channels, newest
+//! first, with the author's follower count and Neynar user score inlined.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+
+const FEED_LIMIT: &str = "100"; // The endpoint's maximum page size
+
+#[derive(Debug, Deserialize)]
+struct FeedResponse {
+    #[serde(default)]
+    casts: Vec<Cast>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cast {
+    pub hash: String,
+    pub text: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub author: Author,
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Author {
+    pub username: String,
+    #[serde(default)]
+    pub follower_count: u64,
+    #[serde(default)]
+    pub experimental: Option<Experimental>,
+}
+
+impl Author {
+    pub fn user_score(&self) -> f64 {
+        self.experimental
+            .as_ref()
+            .and_then(|e| e.neynar_user_score)
+            .unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Experimental {
+    #[serde(default)]
+    pub neynar_user_score: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Embed {
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+pub struct Neynar {
+    http: Client,
+}
+
+impl Neynar {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(15))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// Latest casts across the configured channels, recasts left out.
+    pub async fn channel_feed(&self) -> Result<Vec<Cast>> {
+        let response = self
+            .http
+            .get(format!("{}/v2/farcaster/feed/channels", CONFIG.api_url))
+            .header("x-api-key", &CONFIG.api_key)
+            .query(&[
+                ("channel_ids", CONFIG.channels.join(",").as_str()),
+                ("with_recasts", "false"),
+                ("limit", FEED_LIMIT),
+            ])
+            .send()
+            .await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(anyhow!("Neynar rate limited the request"));
+        }
+        let feed: FeedResponse = response
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Neynar channel feed")?;
+        Ok(feed.casts)
+    }
+}
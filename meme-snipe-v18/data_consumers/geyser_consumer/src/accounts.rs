@@ -0,0 +1,85 @@
+// data_consumers/geyser_consumer/src/accounts.rs
+//! Decoders for the raw account layouts the consumer subscribes to: SPL token
+//! accounts (pool vaults), SPL mints, and pump.fun bonding curves.
+
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const MINT_LEN: usize = 82;
+const PUMP_CURVE_MIN_LEN: usize = 49; // Discriminator, five u64s and `complete`
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> String {
+    bs58::encode(&data[offset..offset + 32]).into_string()
+}
+
+/// `COption<Pubkey>`: a u32 tag followed by the key.
+fn coption_pubkey_at(data: &[u8], offset: usize) -> Option<String> {
+    (data[offset] == 1).then(|| pubkey_at(data, offset + 4))
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenAccount {
+    pub mint: String,
+    pub amount: u64,
+}
+
+impl TokenAccount {
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        // Token-2022 accounts carry extensions past the base layout
+        if data.len() < TOKEN_ACCOUNT_LEN {
+            return None;
+        }
+        Some(Self {
+            mint: pubkey_at(data, 0),
+            amount: u64_at(data, 64),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mint {
+    pub mint_authority: Option<String>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub freeze_authority: Option<String>,
+}
+
+impl Mint {
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < MINT_LEN || data[45] != 1 {
+            return None; // Too short, or not initialized
+        }
+        Some(Self {
+            mint_authority: coption_pubkey_at(data, 0),
+            supply: u64_at(data, 36),
+            decimals: data[44],
+            freeze_authority: coption_pubkey_at(data, 46),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PumpCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub complete: bool, // Set once the curve fills and liquidity migrates
+}
+
+impl PumpCurve {
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < PUMP_CURVE_MIN_LEN {
+            return None;
+        }
+        Some(Self {
+            virtual_token_reserves: u64_at(data, 8),
+            virtual_sol_reserves: u64_at(data, 16),
+            real_sol_reserves: u64_at(data, 32),
+            complete: data[48] == 1,
+        })
+    }
+}
@@ -0,0 +1,119 @@
+// data_consumers/geyser_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+
+#[derive(Debug, Clone)]
+pub enum PoolKind {
+    /// Constant-product pool priced from its two SPL vaults, e.g. Raydium AMM v4
+    Vaults {
+        base_vault: String,
+        quote_vault: String,
+    },
+    /// pump.fun bonding curve account, priced from its virtual reserves
+    PumpCurve { curve: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolSpec {
+    pub mint: String,
+    pub kind: PoolKind,
+}
+
+impl PoolSpec {
+    /// Parses `MINT:vaults:BASE_VAULT:QUOTE_VAULT` or `MINT:pump:CURVE`.
+    fn parse(spec: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        let kind = match parts.as_slice() {
+            [_, "vaults", base, quote] => PoolKind::Vaults {
+                base_vault: base.to_string(),
+                quote_vault: quote.to_string(),
+            },
+            [_, "pump", curve] => PoolKind::PumpCurve {
+                curve: curve.to_string(),
+            },
+            _ => {
+                return Err(format!(
+                    "{} is not MINT:vaults:BASE_VAULT:QUOTE_VAULT or MINT:pump:CURVE",
+                    spec
+                ))
+            }
+        };
+        Ok(Self {
+            mint: parts[0].to_string(),
+            kind,
+        })
+    }
+
+    /// Accounts to subscribe to for this pool, the mint included.
+    pub fn accounts(&self) -> Vec<String> {
+        let mut accounts = vec![self.mint.clone()];
+        match &self.kind {
+            PoolKind::Vaults {
+                base_vault,
+                quote_vault,
+            } => {
+                accounts.push(base_vault.clone());
+                accounts.push(quote_vault.clone());
+            }
+            PoolKind::PumpCurve { curve } => accounts.push(curve.clone()),
+        }
+        accounts
+    }
+
+    /// Address the pool is reported under.
+    pub fn pool_address(&self) -> &str {
+        match &self.kind {
+            PoolKind::Vaults { base_vault, .. } => base_vault,
+            PoolKind::PumpCurve { curve } => curve,
+        }
+    }
+}
+
+pub struct Config {
+    pub redis_url: String,
+    pub rpc_url: String, // Seeds account state before each subscription
+    pub grpc_endpoint: String,
+    pub grpc_x_token: Option<String>,
+    pub pools: Vec<PoolSpec>,
+    pub fee_bps: f64, // Vault pools; pump.fun curves charge 100 bps
+    pub depth_band_pct: f64,
+    pub min_publish_interval_ms: i64, // Per pool
+    pub reserve_shift_pct: f64, // A single update moving the quote reserve this much is reported
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("geyser_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            rpc_url: c.required("SOLANA_RPC_URL"),
+            grpc_endpoint: c.required("GEYSER_GRPC_ENDPOINT"),
+            grpc_x_token: c.optional("GEYSER_GRPC_X_TOKEN"),
+            pools: c
+                .list("GEYSER_POOLS", "")
+                .iter()
+                .filter_map(|spec| match PoolSpec::parse(spec) {
+                    Ok(pool) => Some(pool),
+                    Err(e) => {
+                        c.invalid("GEYSER_POOLS", e);
+                        None
+                    }
+                })
+                .collect(),
+            fee_bps: c.get("GEYSER_POOL_FEE_BPS", 25.0),
+            depth_band_pct: c.get("GEYSER_DEPTH_BAND_PCT", 1.0),
+            min_publish_interval_ms: c.get("GEYSER_MIN_PUBLISH_INTERVAL_MS", 250),
+            reserve_shift_pct: c.get("GEYSER_RESERVE_SHIFT_PCT", 2.0),
+            stream_maxlen: c.get("GEYSER_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("GEYSER_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,264 @@
+// data_consumers/geyser_consumer/src/main.rs
+//! Streams account updates for the GEYSER_POOLS pools and their mints from a
+//! Yellowstone Geyser gRPC endpoint at processed commitment, for strategies
+//! that can't wait on HTTP polling or webhooks. Pool reserve changes become a
+//! DepthEvent on `events:depth` (at most once per
+//! GEYSER_MIN_PUBLISH_INTERVAL_MS per pool); mint supply and authority
+//! changes, large reserve shifts and completed pump.fun curves become
+//! OnChainEvents on `events:onchain`.
+//!
+//! Account state is seeded over SOLANA_RPC_URL before every subscription,
+//! since Geyser only sends accounts as they change.
+
+mod accounts;
+mod config;
+mod pools;
+mod rpc;
+mod sol_price;
+
+use crate::config::CONFIG;
+use crate::pools::Pools;
+use crate::sol_price::{follow_sol_price, SolPrice};
+use anyhow::{anyhow, Result};
+use axum::{routing::get, Router};
+use futures::{SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use prometheus::{
+    register_int_counter, register_int_counter_vec, Encoder, IntCounter, IntCounterVec, TextEncoder,
+};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts, SubscribeRequestPing,
+};
+
+const SOURCE_NAME: &str = "geyser_consumer";
+const MAX_RECONNECT_BACKOFF_SECS: u64 = 60;
+const STREAM_STALL_SECS: u64 = 60; // Servers ping every ~15s, so silence means a dead stream
+
+lazy_static! {
+    static ref ACCOUNT_UPDATES: IntCounter = register_int_counter!(
+        "geyser_account_updates_total",
+        "Account updates received from the Geyser stream."
+    )
+    .unwrap();
+    static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "geyser_events_published_total",
+        "Events published, by stream.",
+        &["stream"]
+    )
+    .unwrap();
+    static ref STREAM_RECONNECTS: IntCounter = register_int_counter!(
+        "geyser_stream_reconnects_total",
+        "Times the Geyser subscription was reopened."
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+fn subscribe_request(accounts: Vec<String>) -> SubscribeRequest {
+    let mut filters = HashMap::new();
+    filters.insert(
+        "memesnipe".to_string(),
+        SubscribeRequestFilterAccounts {
+            account: accounts,
+            ..Default::default()
+        },
+    );
+    SubscribeRequest {
+        accounts: filters,
+        commitment: Some(CommitmentLevel::Processed as i32),
+        ..Default::default()
+    }
+}
+
+/// One subscription, until the stream ends or fails. `received` is set once
+/// an account update arrives.
+async fn subscribe(
+    conn: &mut MultiplexedConnection,
+    pools: &mut Pools,
+    sol_price: &SolPrice,
+    last_processed: &AtomicI64,
+    received: &mut bool,
+) -> Result<()> {
+    let mut client = GeyserGrpcClient::connect(
+        CONFIG.grpc_endpoint.clone(),
+        CONFIG.grpc_x_token.clone(),
+        None,
+    )?;
+    let accounts = pools.accounts();
+    let (mut requests, mut updates) = client
+        .subscribe_with_request(Some(subscribe_request(accounts.clone())))
+        .await?;
+    // Seeded after subscribing so no change falls between the two; updates
+    // queued meanwhile are newer and applied after
+    let seeded = rpc::fetch_accounts(&accounts).await?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let sol_usd = *sol_price.read();
+    for (pubkey, data, slot) in &seeded {
+        for (stream, event) in pools.apply(pubkey, data, *slot, sol_usd, now_ms) {
+            if let Err(e) = publish(conn, stream, &event).await {
+                error!(error = %e, stream, "Failed to publish event.");
+            }
+        }
+    }
+    info!(
+        "Geyser subscription open for {} accounts ({} seeded over RPC).",
+        accounts.len(),
+        seeded.len()
+    );
+
+    loop {
+        let update = tokio::time::timeout(Duration::from_secs(STREAM_STALL_SECS), updates.next())
+            .await
+            .map_err(|_| anyhow!("no message for {}s", STREAM_STALL_SECS))?
+            .ok_or_else(|| anyhow!("stream closed by server"))??;
+        match update.update_oneof {
+            Some(UpdateOneof::Account(account_update)) => {
+                let Some(account) = account_update.account else {
+                    continue;
+                };
+                ACCOUNT_UPDATES.inc();
+                *received = true;
+                let pubkey = bs58::encode(&account.pubkey).into_string();
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                let sol_usd = *sol_price.read();
+                let events =
+                    pools.apply(&pubkey, &account.data, account_update.slot, sol_usd, now_ms);
+                for (stream, event) in events {
+                    match publish(conn, stream, &event).await {
+                        Ok(()) => EVENTS_PUBLISHED.with_label_values(&[stream]).inc(),
+                        Err(e) => error!(error = %e, stream, "Failed to publish event."),
+                    }
+                }
+                last_processed.store(now_ms / 1000, Ordering::Relaxed);
+            }
+            // Answered so load balancers in front of the endpoint keep the stream open
+            Some(UpdateOneof::Ping(_)) => {
+                requests
+                    .send(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(|e| anyhow!("failed to answer ping: {}", e))?;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keeps the subscription open, reconnecting with exponential backoff.
+async fn stream_loop(
+    mut conn: MultiplexedConnection,
+    sol_price: SolPrice,
+    last_processed: Arc<AtomicI64>,
+) {
+    let mut pools = Pools::new(CONFIG.pools.clone());
+    let mut backoff_secs = 1;
+    loop {
+        let mut received = false;
+        if let Err(e) = subscribe(
+            &mut conn,
+            &mut pools,
+            &sol_price,
+            &last_processed,
+            &mut received,
+        )
+        .await
+        {
+            warn!(error = %e, "Geyser stream ended. Reconnecting in {}s.", backoff_secs);
+        }
+        STREAM_RECONNECTS.inc();
+        tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+        // A stream that delivered updates was healthy; only back off on repeated failures
+        backoff_secs = if received {
+            1
+        } else {
+            (backoff_secs * 2).min(MAX_RECONNECT_BACKOFF_SECS)
+        };
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "⚡ Starting Geyser Consumer for {} pools via {}...",
+        CONFIG.pools.len(),
+        CONFIG.grpc_endpoint
+    );
+    if CONFIG.pools.is_empty() {
+        anyhow::bail!("GEYSER_POOLS must name at least one pool");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let sol_price = SolPrice::default();
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(follow_sol_price(client, sol_price.clone()));
+    tokio::spawn(stream_loop(conn.clone(), sol_price, last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
@@ -0,0 +1,321 @@
+// data_consumers/geyser_consumer/src/pools.rs
+//! Per-pool state rebuilt from account updates, and the events it yields:
+//! a DepthEvent whenever a pool's reserves change (throttled per pool), and
+//! OnChainEvents for mint supply and authority changes, large single-update
+//! reserve moves, and pump.fun curves completing.
+
+use crate::accounts::{Mint, PumpCurve, TokenAccount};
+use crate::config::{PoolKind, PoolSpec, CONFIG};
+use serde_json::json;
+use shared_models::{DepthEvent, MarketEvent, OnChainEvent};
+use std::collections::HashMap;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H7FYD4KCoNkY11McCe8BenwNYB";
+
+const PUMP_FEE_BPS: f64 = 100.0;
+const PUMP_TOKEN_DECIMALS: i32 = 6;
+const SOL_DECIMALS: i32 = 9;
+
+#[derive(Debug, Clone, Copy)]
+enum Role {
+    Mint,
+    BaseVault,
+    QuoteVault,
+    Curve,
+}
+
+#[derive(Default)]
+struct PoolState {
+    mint: Option<Mint>,
+    base: Option<(u64, u64)>, // (amount, slot)
+    quote: Option<(TokenAccount, u64)>,
+    curve: Option<PumpCurve>,
+    quote_reserve_usd: Option<f64>, // As of the last update, for shift detection
+    last_published_ms: i64,
+}
+
+/// Reserves in UI units with the USD value of one quote unit.
+struct Reserves {
+    base: f64,
+    quote: f64,
+    quote_usd: f64,
+    fee_bps: f64,
+}
+
+pub struct Pools {
+    specs: Vec<PoolSpec>,
+    states: Vec<PoolState>,
+    roles: HashMap<String, Vec<(usize, Role)>>,
+}
+
+fn quote_decimals_and_usd(mint: &str, sol_usd: Option<f64>) -> Option<(i32, f64)> {
+    match mint {
+        WSOL_MINT => sol_usd.map(|usd| (SOL_DECIMALS, usd)),
+        USDC_MINT | USDT_MINT => Some((6, 1.0)),
+        _ => None, // Token/token pools can't be valued
+    }
+}
+
+fn ui(amount: u64, decimals: i32) -> f64 {
+    amount as f64 / 10f64.powi(decimals)
+}
+
+fn onchain(mint: &str, event_type: &str, data: serde_json::Value, now: i64) -> MarketEvent {
+    MarketEvent::OnChain(OnChainEvent {
+        timestamp: now,
+        token_address: mint.to_string(),
+        event_type: event_type.to_string(),
+        data,
+    })
+}
+
+impl Pools {
+    pub fn new(specs: Vec<PoolSpec>) -> Self {
+        let mut roles: HashMap<String, Vec<(usize, Role)>> = HashMap::new();
+        for (index, spec) in specs.iter().enumerate() {
+            roles
+                .entry(spec.mint.clone())
+                .or_default()
+                .push((index, Role::Mint));
+            match &spec.kind {
+                PoolKind::Vaults {
+                    base_vault,
+                    quote_vault,
+                } => {
+                    roles
+                        .entry(base_vault.clone())
+                        .or_default()
+                        .push((index, Role::BaseVault));
+                    roles
+                        .entry(quote_vault.clone())
+                        .or_default()
+                        .push((index, Role::QuoteVault));
+                }
+                PoolKind::PumpCurve { curve } => {
+                    roles
+                        .entry(curve.clone())
+                        .or_default()
+                        .push((index, Role::Curve));
+                }
+            }
+        }
+        let states = specs.iter().map(|_| PoolState::default()).collect();
+        Self {
+            specs,
+            states,
+            roles,
+        }
+    }
+
+    /// Every account the subscription has to cover.
+    pub fn accounts(&self) -> Vec<String> {
+        self.roles.keys().cloned().collect()
+    }
+
+    /// Applies an account update and returns the events it yields, each with
+    /// the stream it belongs on.
+    pub fn apply(
+        &mut self,
+        pubkey: &str,
+        data: &[u8],
+        slot: u64,
+        sol_usd: Option<f64>,
+        now_ms: i64,
+    ) -> Vec<(&'static str, MarketEvent)> {
+        let Some(roles) = self.roles.get(pubkey).cloned() else {
+            return Vec::new();
+        };
+        let now = now_ms / 1000;
+        let mut events = Vec::new();
+        for (index, role) in roles {
+            let mint = self.specs[index].mint.clone();
+            let state = &mut self.states[index];
+            match role {
+                Role::Mint => {
+                    let Some(decoded) = Mint::decode(data) else {
+                        continue;
+                    };
+                    if let Some(previous) = state.mint.replace(decoded.clone()) {
+                        events.extend(
+                            mint_changes(&mint, &previous, &decoded, slot, now)
+                                .into_iter()
+                                .map(|event| ("events:onchain", event)),
+                        );
+                    }
+                    continue; // Supply doesn't move the pool's reserves
+                }
+                Role::BaseVault => match TokenAccount::decode(data) {
+                    Some(account) => state.base = Some((account.amount, slot)),
+                    None => continue,
+                },
+                Role::QuoteVault => match TokenAccount::decode(data) {
+                    Some(account) => state.quote = Some((account, slot)),
+                    None => continue,
+                },
+                Role::Curve => {
+                    let Some(curve) = PumpCurve::decode(data) else {
+                        continue;
+                    };
+                    let was_complete = state.curve.as_ref().is_some_and(|c| c.complete);
+                    if curve.complete && !was_complete && state.curve.is_some() {
+                        events.push((
+                            "events:onchain",
+                            onchain(
+                                &mint,
+                                "CurveComplete",
+                                json!({
+                                    "curve": self.specs[index].pool_address(),
+                                    "real_sol_reserves": ui(curve.real_sol_reserves, SOL_DECIMALS),
+                                    "slot": slot,
+                                }),
+                                now,
+                            ),
+                        ));
+                    }
+                    state.curve = Some(curve);
+                }
+            }
+            events.extend(self.reserve_events(index, slot, sol_usd, now_ms));
+        }
+        events
+    }
+
+    fn reserves(&self, index: usize, sol_usd: Option<f64>) -> Option<Reserves> {
+        let state = &self.states[index];
+        match &self.specs[index].kind {
+            PoolKind::Vaults { .. } => {
+                let (base_amount, base_slot) = state.base?;
+                let (quote, quote_slot) = state.quote.as_ref()?;
+                // A swap writes both vaults in the same slot; until the second
+                // write arrives the pair would price off a stale reserve
+                if base_slot != *quote_slot {
+                    return None;
+                }
+                let base_decimals = state.mint.as_ref()?.decimals as i32;
+                let (quote_decimals, quote_usd) = quote_decimals_and_usd(&quote.mint, sol_usd)?;
+                Some(Reserves {
+                    base: ui(base_amount, base_decimals),
+                    quote: ui(quote.amount, quote_decimals),
+                    quote_usd,
+                    fee_bps: CONFIG.fee_bps,
+                })
+            }
+            PoolKind::PumpCurve { .. } => {
+                let curve = state.curve.as_ref().filter(|c| !c.complete)?;
+                Some(Reserves {
+                    base: ui(curve.virtual_token_reserves, PUMP_TOKEN_DECIMALS),
+                    quote: ui(curve.virtual_sol_reserves, SOL_DECIMALS),
+                    quote_usd: sol_usd?,
+                    fee_bps: PUMP_FEE_BPS,
+                })
+            }
+        }
+    }
+
+    fn reserve_events(
+        &mut self,
+        index: usize,
+        slot: u64,
+        sol_usd: Option<f64>,
+        now_ms: i64,
+    ) -> Vec<(&'static str, MarketEvent)> {
+        let Some(reserves) = self.reserves(index, sol_usd) else {
+            return Vec::new();
+        };
+        if reserves.base <= 0.0 || reserves.quote <= 0.0 {
+            return Vec::new();
+        }
+        let spec = &self.specs[index];
+        let state = &mut self.states[index];
+        let now = now_ms / 1000;
+        let reserve_usd = reserves.quote * reserves.quote_usd;
+        let price_usd = reserves.quote / reserves.base * reserves.quote_usd;
+        let mut events = Vec::new();
+
+        // Shifts are checked on every update so the throttle can't hide them
+        if let Some(previous) = state.quote_reserve_usd.replace(reserve_usd) {
+            let shift_pct = (reserve_usd - previous) / previous.max(f64::EPSILON) * 100.0;
+            if shift_pct.abs() >= CONFIG.reserve_shift_pct {
+                events.push((
+                    "events:onchain",
+                    onchain(
+                        &spec.mint,
+                        "ReserveShift",
+                        json!({
+                            "pool": spec.pool_address(),
+                            "shift_pct": shift_pct,
+                            "quote_reserve_usd": reserve_usd,
+                            "price_usd": price_usd,
+                            "slot": slot,
+                        }),
+                        now,
+                    ),
+                ));
+            }
+        }
+
+        if now_ms - state.last_published_ms >= CONFIG.min_publish_interval_ms {
+            state.last_published_ms = now_ms;
+            let fee = reserves.fee_bps / 10_000.0;
+            let band = CONFIG.depth_band_pct / 100.0;
+            events.push((
+                "events:depth",
+                MarketEvent::Depth(DepthEvent {
+                    timestamp: now,
+                    token_address: spec.mint.clone(),
+                    // Crossing the pool costs its fee in either direction
+                    bid_price: price_usd * (1.0 - fee),
+                    ask_price: price_usd * (1.0 + fee),
+                    // The price scales with the square of the quote reserve, so
+                    // moving it by the band takes the reserve to sqrt(1 -/+ band)
+                    bid_size_usd: reserve_usd * (1.0 - (1.0 - band).max(0.0).sqrt()),
+                    ask_size_usd: reserve_usd * ((1.0 + band).sqrt() - 1.0),
+                }),
+            ));
+        }
+        events
+    }
+}
+
+/// Supply and authority changes between two states of a mint.
+fn mint_changes(
+    mint: &str,
+    previous: &Mint,
+    current: &Mint,
+    slot: u64,
+    now: i64,
+) -> Vec<MarketEvent> {
+    let mut events = Vec::new();
+    if current.supply != previous.supply {
+        let decimals = current.decimals as i32;
+        events.push(onchain(
+            mint,
+            "SupplyChange",
+            json!({
+                "previous_supply": ui(previous.supply, decimals),
+                "supply": ui(current.supply, decimals),
+                "slot": slot,
+            }),
+            now,
+        ));
+    }
+    if current.mint_authority != previous.mint_authority
+        || current.freeze_authority != previous.freeze_authority
+    {
+        events.push(onchain(
+            mint,
+            "AuthorityChange",
+            json!({
+                "previous_mint_authority": previous.mint_authority,
+                "mint_authority": current.mint_authority,
+                "previous_freeze_authority": previous.freeze_authority,
+                "freeze_authority": current.freeze_authority,
+                "slot": slot,
+            }),
+            now,
+        ));
+    }
+    events
+}
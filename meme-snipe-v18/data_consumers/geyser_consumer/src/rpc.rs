@@ -0,0 +1,70 @@
+// data_consumers/geyser_consumer/src/rpc.rs
+//! Current state of the subscribed accounts over JSON-RPC. Geyser only sends
+//! accounts as they change, so a mint that never changes would otherwise
+//! never report its decimals, and a quiet pool its reserves.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+const MAX_ACCOUNTS_PER_CALL: usize = 100; // getMultipleAccounts limit
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<RpcResult>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RpcResult {
+    context: RpcContext,
+    value: Vec<Option<RpcAccount>>,
+}
+
+#[derive(Deserialize)]
+struct RpcContext {
+    slot: u64,
+}
+
+#[derive(Deserialize)]
+struct RpcAccount {
+    data: (String, String), // (base64 data, encoding)
+}
+
+/// `(pubkey, data, slot)` for every account that exists.
+pub async fn fetch_accounts(accounts: &[String]) -> Result<Vec<(String, Vec<u8>, u64)>> {
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()?;
+    let mut found = Vec::new();
+    for batch in accounts.chunks(MAX_ACCOUNTS_PER_CALL) {
+        let response: RpcResponse = http
+            .post(&CONFIG.rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getMultipleAccounts",
+                "params": [batch, {"encoding": "base64", "commitment": "processed"}],
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse getMultipleAccounts response")?;
+        let result = match (response.result, response.error) {
+            (Some(result), _) => result,
+            (None, error) => return Err(anyhow!("getMultipleAccounts failed: {:?}", error)),
+        };
+        for (pubkey, account) in batch.iter().zip(result.value) {
+            if let Some(account) = account {
+                let data = base64::engine::general_purpose::STANDARD.decode(&account.data.0)?;
+                found.push((pubkey.clone(), data, result.context.slot));
+            }
+        }
+    }
+    Ok(found)
+}
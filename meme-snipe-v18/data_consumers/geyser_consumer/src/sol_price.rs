@@ -0,0 +1,65 @@
+// data_consumers/geyser_consumer/src/sol_price.rs
+//! Latest SOL/USD, followed from `events:sol_price` so SOL-quoted reserves
+//! can be valued without another API.
+
+use parking_lot::RwLock;
+use redis::streams::{StreamRangeReply, StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use shared_models::MarketEvent;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const STREAM: &str = "events:sol_price";
+
+pub type SolPrice = Arc<RwLock<Option<f64>>>;
+
+fn apply(sol_price: &SolPrice, json: Option<String>) {
+    if let Some(MarketEvent::SolPrice(sol)) = json.and_then(|j| serde_json::from_str(&j).ok()) {
+        if sol.price_usd > 0.0 {
+            *sol_price.write() = Some(sol.price_usd);
+        }
+    }
+}
+
+/// Follows events:sol_price from its latest entry, reconnecting on errors.
+pub async fn follow_sol_price(client: redis::Client, sol_price: SolPrice) {
+    info!("Following {} for SOL-quoted pools.", STREAM);
+    let mut last_id = "$".to_string();
+    let opts = StreamReadOptions::default().count(100).block(5000);
+    loop {
+        let mut conn = match client.get_multiplexed_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "SOL price follower failed to connect to Redis. Retrying in 5s.");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        // Seed from the latest tick so pools can be valued before the next one
+        let latest: redis::RedisResult<StreamRangeReply> =
+            conn.xrevrange_count(STREAM, "+", "-", 1).await;
+        if let Ok(latest) = latest {
+            for entry in latest.ids {
+                apply(&sol_price, entry.get("event"));
+            }
+        }
+
+        loop {
+            let reply: StreamReadReply = match conn
+                .xread_options(&[STREAM], &[last_id.as_str()], &opts)
+                .await
+            {
+                Ok(reply) => reply,
+                Err(e) => {
+                    warn!(error = %e, "Error reading {}. Reconnecting.", STREAM);
+                    break;
+                }
+            };
+            for entry in reply.keys.into_iter().flat_map(|key| key.ids) {
+                last_id = entry.id.clone();
+                apply(&sol_price, entry.get("event"));
+            }
+        }
+    }
+}
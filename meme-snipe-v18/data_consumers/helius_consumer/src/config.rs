@@ -0,0 +1,37 @@
+// data_consumers/helius_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::HashSet;
+
+pub struct Config {
+    pub redis_url: String,
+    pub webhook_auth_header: String, // Helius echoes the webhook's authHeader back on every POST
+    pub tracked_tokens: HashSet<String>, // Empty tracks every mint Helius delivers
+    pub large_transfer_min_usd: f64,
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("helius_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            webhook_auth_header: c.required("HELIUS_WEBHOOK_AUTH"),
+            tracked_tokens: c.list("HELIUS_TRACKED_TOKENS", "").into_iter().collect(),
+            large_transfer_min_usd: c.get("HELIUS_LARGE_TRANSFER_MIN_USD", 25_000.0),
+            stream_maxlen: c.get("HELIUS_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("HELIUS_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+
+    pub fn is_tracked(&self, mint: &str) -> bool {
+        self.tracked_tokens.is_empty() || self.tracked_tokens.contains(mint)
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,185 @@
+// data_consumers/helius_consumer/src/main.rs
+//! Receives Helius enhanced-transaction webhooks for the tracked tokens and
+//! publishes swaps, LP changes and large transfers to `events:onchain`.
+//! Enhanced webhooks are used rather than raw log subscriptions because
+//! Helius delivers them already parsed into swaps and token transfers.
+
+mod config;
+mod normalize;
+
+use crate::config::CONFIG;
+use crate::normalize::{normalize, EnhancedTransaction};
+use anyhow::Result;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Json, Router,
+};
+use consumer_common::prices::{follow_prices, PriceCache};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "helius_consumer";
+
+lazy_static! {
+    static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "helius_events_published_total",
+        "OnChain events published to events:onchain, by event type.",
+        &["event_type"]
+    )
+    .unwrap();
+    static ref WEBHOOK_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "helius_webhook_requests_total",
+        "Webhook deliveries received, by outcome.",
+        &["outcome"]
+    )
+    .unwrap();
+}
+
+struct AppState {
+    redis: redis::aio::MultiplexedConnection,
+    prices: Arc<PriceCache>,
+    last_processed: AtomicI64, // Timestamp of the newest transaction handled
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn webhook_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(transactions): Json<Vec<EnhancedTransaction>>,
+) -> StatusCode {
+    let authorized = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v == CONFIG.webhook_auth_header);
+    if !authorized {
+        WEBHOOK_REQUESTS.with_label_values(&["unauthorized"]).inc();
+        warn!("Rejected webhook delivery with a missing or wrong Authorization header.");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let mut conn = state.redis.clone();
+    for tx in &transactions {
+        for event in normalize(tx, &state.prices) {
+            let event_type = event.event_type.clone();
+            let payload = match serde_json::to_string(&MarketEvent::OnChain(event)) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    error!(error = %e, "Failed to serialize OnChain event.");
+                    continue;
+                }
+            };
+            let res: redis::RedisResult<String> = conn
+                .xadd_maxlen(
+                    "events:onchain",
+                    StreamMaxlen::Approx(CONFIG.stream_maxlen),
+                    "*",
+                    &[("event", payload)],
+                )
+                .await;
+            if let Err(e) = res {
+                // Helius retries failed deliveries, so let it redeliver the batch
+                error!(error = %e, signature = %tx.signature, "Failed to publish to events:onchain.");
+                WEBHOOK_REQUESTS.with_label_values(&["redis_error"]).inc();
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            EVENTS_PUBLISHED.with_label_values(&[&event_type]).inc();
+        }
+        state
+            .last_processed
+            .fetch_max(tx.timestamp, Ordering::Relaxed);
+    }
+    WEBHOOK_REQUESTS.with_label_values(&["ok"]).inc();
+    StatusCode::OK
+}
+
+/// Publishes a DataSourceHeartbeat so the executor can tell a quiet market
+/// from a dead webhook.
+async fn heartbeat_loop(state: Arc<AppState>) {
+    let mut conn = state.redis.clone();
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: state.last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        let payload = match serde_json::to_string(&heartbeat) {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!(error = %e, "Failed to serialize heartbeat.");
+                continue;
+            }
+        };
+        let res: redis::RedisResult<String> = conn
+            .xadd_maxlen(
+                "events:data_source_heartbeat",
+                StreamMaxlen::Approx(10_000),
+                "*",
+                &[("event", payload)],
+            )
+            .await;
+        if let Err(e) = res {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🛰️  Starting Helius Consumer for {} tracked tokens (large transfers >= ${:.0})...",
+        if CONFIG.tracked_tokens.is_empty() {
+            "all".to_string()
+        } else {
+            CONFIG.tracked_tokens.len().to_string()
+        },
+        CONFIG.large_transfer_min_usd
+    );
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let redis = client.get_multiplexed_async_connection().await?;
+    let prices = Arc::new(PriceCache::default());
+    tokio::spawn(follow_prices(client, prices.clone()));
+
+    let state = Arc::new(AppState {
+        redis,
+        prices,
+        last_processed: AtomicI64::new(0),
+    });
+    tokio::spawn(heartbeat_loop(state.clone()));
+
+    let app = Router::new()
+        .route("/webhook", post(webhook_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .with_state(state)
+        .merge(shared_config::log_level::router(log_level));
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📡 Listening for Helius webhooks on http://0.0.0.0:9090/webhook");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
@@ -0,0 +1,186 @@
+// data_consumers/helius_consumer/src/normalize.rs
+//! Turns Helius enhanced transactions into OnChainEvents for tracked mints:
+//!
+//! - "Swap": `{ wallet, direction: "buy" | "sell", amount_usd, token_amount, dex, signature }`
+//! - "LiquidityAdd" / "LiquidityRemove": `{ provider, amount_usd, token_amount, dex, signature }`
+//! - "LargeTransfer": `{ from, to, amount_usd, token_amount, signature }`, for
+//!   plain transfers worth at least the configured minimum
+//!
+//! Amounts are valued from the transaction's SOL or stablecoin leg where it
+//! has one, otherwise from the token's latest price.
+
+use crate::config::CONFIG;
+use consumer_common::prices::{PriceCache, USDC_MINT, USDT_MINT, WSOL_MINT};
+use serde::Deserialize;
+use serde_json::json;
+use shared_models::OnChainEvent;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedTransaction {
+    pub signature: String,
+    pub timestamp: i64,
+    #[serde(rename = "type")]
+    pub tx_type: String, // e.g. "SWAP", "TRANSFER", "ADD_LIQUIDITY"
+    #[serde(default)]
+    pub source: String, // Program family, e.g. "RAYDIUM", "JUPITER"
+    #[serde(default)]
+    pub fee_payer: String,
+    #[serde(default)]
+    pub token_transfers: Vec<TokenTransfer>,
+    #[serde(default)]
+    pub native_transfers: Vec<NativeTransfer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    #[serde(default)]
+    pub from_user_account: Option<String>,
+    #[serde(default)]
+    pub to_user_account: Option<String>,
+    pub mint: String,
+    pub token_amount: f64, // Already scaled by the mint's decimals
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTransfer {
+    #[serde(default)]
+    pub from_user_account: Option<String>,
+    #[serde(default)]
+    pub to_user_account: Option<String>,
+    pub amount: u64, // Lamports
+}
+
+fn is_quote_mint(mint: &str) -> bool {
+    matches!(mint, WSOL_MINT | USDC_MINT | USDT_MINT)
+}
+
+impl EnhancedTransaction {
+    /// USD the wallet paid or received in SOL and stablecoins; zero if the
+    /// transaction has no such leg or SOL has no price yet.
+    fn quote_leg_usd(&self, wallet: &str, prices: &PriceCache) -> f64 {
+        let touches = |from: &Option<String>, to: &Option<String>| {
+            from.as_deref() == Some(wallet) || to.as_deref() == Some(wallet)
+        };
+        let tokens: f64 = self
+            .token_transfers
+            .iter()
+            .filter(|t| is_quote_mint(&t.mint) && touches(&t.from_user_account, &t.to_user_account))
+            .filter_map(|t| Some(t.token_amount * prices.price(&t.mint)?))
+            .sum();
+        // Native SOL moved between the wallet and someone else, not fees or rent to itself
+        let native_sol: f64 = self
+            .native_transfers
+            .iter()
+            .filter(|t| {
+                touches(&t.from_user_account, &t.to_user_account)
+                    && t.from_user_account != t.to_user_account
+            })
+            .map(|t| t.amount as f64 / LAMPORTS_PER_SOL)
+            .sum();
+        tokens + native_sol * prices.sol_usd().unwrap_or(0.0)
+    }
+
+    fn event(&self, mint: &str, event_type: &str, data: serde_json::Value) -> OnChainEvent {
+        OnChainEvent {
+            timestamp: self.timestamp,
+            token_address: mint.to_string(),
+            event_type: event_type.to_string(),
+            data,
+        }
+    }
+}
+
+/// Normalized events for the tracked mints a transaction touches.
+pub fn normalize(tx: &EnhancedTransaction, prices: &PriceCache) -> Vec<OnChainEvent> {
+    let tracked = tx
+        .token_transfers
+        .iter()
+        .filter(|t| !is_quote_mint(&t.mint) && CONFIG.is_tracked(&t.mint));
+    let value = |t: &TokenTransfer, quote_usd: f64| {
+        if quote_usd > 0.0 {
+            quote_usd
+        } else {
+            prices.price(&t.mint).map_or(0.0, |p| p * t.token_amount)
+        }
+    };
+    let wallet = tx.fee_payer.as_str();
+
+    match tx.tx_type.as_str() {
+        "SWAP" => {
+            let quote_usd = tx.quote_leg_usd(wallet, prices);
+            tracked
+                .filter_map(|t| {
+                    let direction = if t.to_user_account.as_deref() == Some(wallet) {
+                        "buy"
+                    } else if t.from_user_account.as_deref() == Some(wallet) {
+                        "sell"
+                    } else {
+                        return None; // A hop through a pool, not the trader's side
+                    };
+                    Some(tx.event(
+                        &t.mint,
+                        "Swap",
+                        json!({
+                            "wallet": wallet,
+                            "direction": direction,
+                            "amount_usd": value(t, quote_usd),
+                            "token_amount": t.token_amount,
+                            "dex": tx.source,
+                            "signature": tx.signature,
+                        }),
+                    ))
+                })
+                .collect()
+        }
+        "ADD_LIQUIDITY" | "WITHDRAW_LIQUIDITY" => {
+            let event_type = if tx.tx_type == "ADD_LIQUIDITY" {
+                "LiquidityAdd"
+            } else {
+                "LiquidityRemove"
+            };
+            // Both sides of the pool move: the quote leg plus the token leg
+            let quote_usd = tx.quote_leg_usd(wallet, prices);
+            tracked
+                .map(|t| {
+                    let token_usd = prices.price(&t.mint).map_or(0.0, |p| p * t.token_amount);
+                    tx.event(
+                        &t.mint,
+                        event_type,
+                        json!({
+                            "provider": wallet,
+                            "amount_usd": quote_usd + token_usd,
+                            "token_amount": t.token_amount,
+                            "dex": tx.source,
+                            "signature": tx.signature,
+                        }),
+                    )
+                })
+                .collect()
+        }
+        "TRANSFER" => tracked
+            .filter_map(|t| {
+                let amount_usd = value(t, 0.0);
+                if amount_usd < CONFIG.large_transfer_min_usd {
+                    return None;
+                }
+                Some(tx.event(
+                    &t.mint,
+                    "LargeTransfer",
+                    json!({
+                        "from": t.from_user_account,
+                        "to": t.to_user_account,
+                        "amount_usd": amount_usd,
+                        "token_amount": t.token_amount,
+                        "signature": tx.signature,
+                    }),
+                ))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -1,135 +0,0 @@
-import redis
-import json
-import time
-import os
-import requests
-import logging
-from prometheus_client import start_http_server, Counter
-import threading
-
-# Configure logging
-logging.basicConfig(level=logging.INFO, format='%(asctime)s - %(levelname)s - %(message)s')
-
-# Prometheus metrics
-# Prometheus metrics
-EVENTS_PUBLISHED = Counter('price_events_published_total', 'Total number of price events published to Redis', ['source'])
-CURRENT_PRICE = Gauge('current_asset_price', 'The current price of an asset', ['asset'])
-API_ERRORS = Counter('price_api_errors_total', 'Total number of API errors encountered by the price consumer', ['source'])
-
-def start_metrics_server():
-    """Starts a Prometheus metrics server in a background thread."""
-
-def start_metrics_server():
-    """Starts a Prometheus metrics server in a background thread."""
-    start_http_server(8004)
-    logging.info("Prometheus metrics server started on port 8004.")
-
-def get_sol_price_from_pyth() -> float | None:
-    """Fetches real SOL/USD price from Pyth Network public API."""
-    try:
-        # Pyth SOL/USD price feed - public API
-        url = "https://hermes.pyth.network/api/latest_price_feeds"
-        params = {
-            "ids[]": "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"  # SOL/USD price feed ID
-        }
-        response = requests.get(url, params=params, timeout=10)
-        response.raise_for_status()
-        data = response.json()
-        
-        if data and len(data) > 0:
-            price_data = data[0]
-            price = float(price_data['price']['price']) / (10 ** abs(int(price_data['price']['expo'])))
-            print(f"✅ Real SOL price from Pyth: ${price:.4f}")
-            return price
-            
-    except Exception as e:
-        print(f"⚠️ Error fetching real SOL price from Pyth: {e}")
-        API_ERRORS.labels(source='pyth').inc()
-        return None
-
-def get_sol_price_from_coinbase() -> float | None:
-    """Backup: Fetch SOL price from Coinbase public API."""
-    try:
-        url = "https://api.coinbase.com/v2/exchange-rates?currency=SOL"
-        response = requests.get(url, timeout=5)
-        response.raise_for_status()
-        data = response.json()
-        
-        if 'data' in data and 'rates' in data['data'] and 'USD' in data['data']['rates']:
-            price = float(data['data']['rates']['USD'])
-            print(f"✅ Real SOL price from Coinbase: ${price:.4f}")
-            return price
-            
-    except Exception as e:
-        print(f"⚠️ Error fetching SOL price from Coinbase: {e}")
-        API_ERRORS.labels(source='coinbase').inc()
-        return None
-
-def get_real_sol_price() -> float:
-    """Get real SOL price with multiple fallbacks."""
-    # Try Pyth first (most reliable for DeFi)
-    price = get_sol_price_from_pyth()
-    if price and price > 0:
-        return price
-    
-    # Try Coinbase as backup
-    price = get_sol_price_from_coinbase()
-    if price and price > 0:
-        return price
-    
-    # Last resort: reasonable fallback (current market estimate)
-    print("🚨 All real price feeds failed, using market estimate fallback")
-    return 240.0  # Conservative SOL price estimate for July 2025
-
-def main():
-    logging.info("🚀 Starting Real SOL Price Consumer (Pyth + Coinbase APIs)...")
-    
-    # Start Prometheus metrics server in a background thread
-    metrics_thread = threading.Thread(target=start_metrics_server, daemon=True)
-    metrics_thread.start()
-    
-    redis_url = os.getenv("REDIS_URL", "redis://redis:6379")
-    r = redis.Redis.from_url(redis_url, decode_responses=True)
-
-    logging.info("📊 Using REAL market data sources:")
-    logging.info("   1. Pyth Network (Primary)")
-    logging.info("   2. Coinbase API (Backup)")
-    logging.info("   3. Market estimate (Emergency fallback)")
-
-    consecutive_failures = 0
-    max_failures = 3
-
-    while True:
-        try:
-            sol_price = get_real_sol_price()
-            
-            if sol_price > 0:
-                consecutive_failures = 0  # Reset failure counter
-                
-                event = {
-                    "type": "SolPrice",
-                    "price_usd": sol_price,
-                    "source": "real_market_data",
-                    "timestamp": time.time()
-                }
-                r.xadd("events:sol_price", {"event": json.dumps(event)})
-                EVENTS_PUBLISHED.inc()  # Increment counter for published events
-                logging.info(f"📈 Published REAL SOL price: ${sol_price:.4f}")
-            else:
-                consecutive_failures += 1
-                print(f"❌ Price fetch failed ({consecutive_failures}/{max_failures})")
-                
-                if consecutive_failures >= max_failures:
-                    print("🚨 Too many consecutive failures, check network connectivity")
-                    time.sleep(30)  # Wait longer on repeated failures
-                    consecutive_failures = 0
-                    continue
-                    
-        except Exception as e:
-            print(f"💥 Critical error in price consumer: {e}")
-            consecutive_failures += 1
-            
-        time.sleep(5)  # Update every 5 seconds
-
-if __name__ == "__main__":
-    main()
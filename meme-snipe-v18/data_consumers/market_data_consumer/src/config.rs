@@ -0,0 +1,46 @@
+// data_consumers/market_data_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+
+pub struct Config {
+    pub redis_url: String,
+    pub tracked_tokens: Vec<String>,
+    pub poll_interval_ms: u64,
+    pub dexscreener_url: String,
+    pub dexscreener_rpm: u32, // DexScreener allows 300/min on the token endpoints
+    pub birdeye_url: String,
+    pub birdeye_api_key: Option<String>, // Unset disables Birdeye
+    pub birdeye_rpm: u32,
+    pub spread_bps: f64,        // Assumed round-trip spread, roughly the pool fee
+    pub depth_band_pct: f64,    // Depth is the USD tradable before price moves this much
+    pub min_liquidity_usd: f64, // Pools thinner than this are ignored
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("market_data_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            tracked_tokens: c.list("MARKET_DATA_TOKENS", ""),
+            poll_interval_ms: c.get("MARKET_DATA_POLL_INTERVAL_MS", 2000),
+            dexscreener_url: c.get("DEXSCREENER_URL", "https://api.dexscreener.com".to_string()),
+            dexscreener_rpm: c.get("DEXSCREENER_RPM", 240),
+            birdeye_url: c.get("BIRDEYE_URL", "https://public-api.birdeye.so".to_string()),
+            birdeye_api_key: c.optional("BIRDEYE_API_KEY"),
+            birdeye_rpm: c.get("BIRDEYE_RPM", 60),
+            spread_bps: c.get("MARKET_DATA_SPREAD_BPS", 50.0),
+            depth_band_pct: c.get("MARKET_DATA_DEPTH_BAND_PCT", 1.0),
+            min_liquidity_usd: c.get("MARKET_DATA_MIN_LIQUIDITY_USD", 1000.0),
+            stream_maxlen: c.get("MARKET_DATA_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("MARKET_DATA_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,223 @@
+// data_consumers/market_data_consumer/src/main.rs
+//! Polls DexScreener (and Birdeye, for tokens it doesn't cover) for the
+//! MARKET_DATA_TOKENS every MARKET_DATA_POLL_INTERVAL_MS, publishing a
+//! PriceTick to `events:price` and a DepthEvent to `events:depth` per token.
+//!
+//! Neither API exposes an order book, so depth is a constant-product proxy:
+//! bid and ask sit MARKET_DATA_SPREAD_BPS apart around the price, and each
+//! side's size is the USD the pooled liquidity absorbs before the price moves
+//! MARKET_DATA_DEPTH_BAND_PCT. Requests are batched and spaced to each API's
+//! per-minute budget, so with many tokens a pass can take longer than the
+//! poll interval.
+
+mod config;
+mod rate_limit;
+mod sources;
+
+use crate::config::CONFIG;
+use crate::sources::{Sources, TokenMarket, BIRDEYE_BATCH, DEXSCREENER_BATCH};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, DepthEvent, MarketEvent, PriceTick};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "market_data_consumer";
+
+lazy_static! {
+    static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "market_data_events_published_total",
+        "Events published, by stream and the API the data came from.",
+        &["stream", "source"]
+    )
+    .unwrap();
+    static ref FETCH_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "market_data_fetch_errors_total",
+        "Failed API requests, by source.",
+        &["source"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Constant-product depth proxy around the current price.
+fn depth_proxy(token: &str, market: &TokenMarket, timestamp: i64) -> DepthEvent {
+    let half_spread = CONFIG.spread_bps / 2.0 / 10_000.0;
+    let band = CONFIG.depth_band_pct / 100.0;
+    let reserve_usd = market.liquidity_usd / 2.0; // Quote side of the pools
+    DepthEvent {
+        timestamp,
+        token_address: token.to_string(),
+        bid_price: market.price_usd * (1.0 - half_spread),
+        ask_price: market.price_usd * (1.0 + half_spread),
+        // The price scales with the square of the quote reserve, so moving it by
+        // the band takes the reserve to sqrt(1 - band) or sqrt(1 + band)
+        bid_size_usd: reserve_usd * (1.0 - (1.0 - band).max(0.0).sqrt()),
+        ask_size_usd: reserve_usd * ((1.0 + band).sqrt() - 1.0),
+    }
+}
+
+/// One pass over the tracked tokens.
+async fn fetch_markets(sources: &mut Sources) -> HashMap<String, TokenMarket> {
+    let tokens = &CONFIG.tracked_tokens;
+    let mut markets = HashMap::new();
+    for batch in tokens.chunks(DEXSCREENER_BATCH) {
+        match sources.dexscreener(batch).await {
+            Ok(found) => markets.extend(found),
+            Err(e) => {
+                warn!(error = %e, "DexScreener request failed.");
+                FETCH_ERRORS.with_label_values(&["dexscreener"]).inc();
+            }
+        }
+    }
+    let missing: Vec<String> = tokens
+        .iter()
+        .filter(|token| !markets.contains_key(*token))
+        .cloned()
+        .collect();
+    for batch in missing.chunks(BIRDEYE_BATCH) {
+        match sources.birdeye(batch).await {
+            Ok(Some(found)) => markets.extend(found),
+            Ok(None) => break, // No API key
+            Err(e) => {
+                warn!(error = %e, "Birdeye request failed.");
+                FETCH_ERRORS.with_label_values(&["birdeye"]).inc();
+            }
+        }
+    }
+    markets
+}
+
+async fn poll_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut sources = Sources::new();
+    let poll_interval = Duration::from_millis(CONFIG.poll_interval_ms);
+    let min_cycle = sources.min_cycle(CONFIG.tracked_tokens.len());
+    if min_cycle > poll_interval {
+        warn!(
+            "{} tokens need {:?} per pass at DEXSCREENER_RPM={}; publishing at that cadence instead of {:?}.",
+            CONFIG.tracked_tokens.len(),
+            min_cycle,
+            CONFIG.dexscreener_rpm,
+            poll_interval
+        );
+    }
+    loop {
+        let started = Instant::now();
+        let timestamp = chrono::Utc::now().timestamp();
+        for (token, market) in fetch_markets(&mut sources).await {
+            let tick = MarketEvent::Price(PriceTick {
+                timestamp,
+                token_address: token.clone(),
+                price_usd: market.price_usd,
+                volume_usd_1m: market.volume_usd_5m / 5.0,
+                venue: None,
+            });
+            if let Err(e) = publish(&mut conn, "events:price", &tick).await {
+                error!(error = %e, token = %token, "Failed to publish PriceTick.");
+                continue;
+            }
+            EVENTS_PUBLISHED
+                .with_label_values(&["events:price", market.source])
+                .inc();
+            // Without pool liquidity there is nothing to derive depth from
+            if market.liquidity_usd > 0.0 {
+                let depth = MarketEvent::Depth(depth_proxy(&token, &market, timestamp));
+                if let Err(e) = publish(&mut conn, "events:depth", &depth).await {
+                    error!(error = %e, token = %token, "Failed to publish DepthEvent.");
+                    continue;
+                }
+                EVENTS_PUBLISHED
+                    .with_label_values(&["events:depth", market.source])
+                    .inc();
+            }
+            last_processed.store(timestamp, Ordering::Relaxed);
+        }
+        tokio::time::sleep_until(started + poll_interval).await;
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "📈 Starting Market Data Consumer for {} tokens every {}ms (Birdeye {})...",
+        CONFIG.tracked_tokens.len(),
+        CONFIG.poll_interval_ms,
+        if CONFIG.birdeye_api_key.is_some() {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    if CONFIG.tracked_tokens.is_empty() {
+        warn!("MARKET_DATA_TOKENS is empty; only heartbeats will be published.");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(poll_loop(conn.clone(), last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+// data_consumers/market_data_consumer/src/rate_limit.rs
+//! Spaces requests to stay under an API's per-minute budget, and pauses it
+//! entirely after a 429 for as long as the API asks.
+
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub struct RateLimiter {
+    gap: Duration,
+    next_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            gap: Duration::from_millis(60_000 / requests.max(1) as u64),
+            next_at: Instant::now(),
+        }
+    }
+
+    /// Waits until the next request is within budget and books it.
+    pub async fn acquire(&mut self) {
+        tokio::time::sleep_until(self.next_at).await;
+        self.next_at = Instant::now().max(self.next_at) + self.gap;
+    }
+
+    /// Holds every request back for `retry_after`, after a 429.
+    pub fn back_off(&mut self, retry_after: Duration) {
+        self.next_at = self.next_at.max(Instant::now() + retry_after);
+    }
+
+    /// Shortest interval at which `requests` calls fit the budget.
+    pub fn min_cycle(&self, requests: usize) -> Duration {
+        self.gap * requests as u32
+    }
+}
+
+/// The Retry-After header in seconds, or a minute if the API didn't say.
+pub fn retry_after(response: &reqwest::Response) -> Duration {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(60))
+}
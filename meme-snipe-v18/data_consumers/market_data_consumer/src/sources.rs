@@ -0,0 +1,200 @@
+// data_consumers/market_data_consumer/src/sources.rs
+//! DexScreener and Birdeye clients. DexScreener gives price, pool liquidity
+//! and 5-minute volume for up to 30 tokens a request; Birdeye's multi-price
+//! covers up to 100 and fills in tokens DexScreener has no pools for yet.
+
+use crate::config::CONFIG;
+use crate::rate_limit::{retry_after, RateLimiter};
+use anyhow::{anyhow, Context, Result};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+
+pub const DEXSCREENER_BATCH: usize = 30;
+pub const BIRDEYE_BATCH: usize = 100;
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenMarket {
+    pub price_usd: f64,
+    pub liquidity_usd: f64, // Both sides, summed over the token's pools
+    pub volume_usd_5m: f64,
+    pub source: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DexPair {
+    base_token: DexToken,
+    #[serde(default, deserialize_with = "number_string")]
+    price_usd: Option<f64>,
+    #[serde(default)]
+    liquidity: Option<DexLiquidity>,
+    #[serde(default)]
+    volume: Option<DexVolume>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexToken {
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexLiquidity {
+    #[serde(default)]
+    usd: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexVolume {
+    #[serde(default)]
+    m5: Option<f64>,
+}
+
+/// DexScreener sends prices as strings.
+fn number_string<'de, D: Deserializer<'de>>(d: D) -> Result<Option<f64>, D::Error> {
+    let s: Option<String> = Option::deserialize(d)?;
+    Ok(s.and_then(|s| s.parse().ok()))
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeResponse {
+    #[serde(default)]
+    data: HashMap<String, Option<BirdeyePrice>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BirdeyePrice {
+    value: f64,
+    #[serde(default)]
+    liquidity: Option<f64>,
+}
+
+pub struct Sources {
+    http: Client,
+    dexscreener: RateLimiter,
+    birdeye: RateLimiter,
+}
+
+impl Sources {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+            dexscreener: RateLimiter::per_minute(CONFIG.dexscreener_rpm),
+            birdeye: RateLimiter::per_minute(CONFIG.birdeye_rpm),
+        }
+    }
+
+    /// How long one pass over `tokens` takes at the configured budgets.
+    pub fn min_cycle(&self, tokens: usize) -> std::time::Duration {
+        self.dexscreener
+            .min_cycle(tokens.div_ceil(DEXSCREENER_BATCH))
+    }
+
+    /// Price, liquidity and volume from each token's pools; the most liquid
+    /// pool's price wins. Tokens without a pool above the liquidity floor are
+    /// left out.
+    pub async fn dexscreener(&mut self, tokens: &[String]) -> Result<HashMap<String, TokenMarket>> {
+        self.dexscreener.acquire().await;
+        let url = format!(
+            "{}/tokens/v1/solana/{}",
+            CONFIG.dexscreener_url,
+            tokens.join(",")
+        );
+        let response = self.http.get(&url).send().await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            self.dexscreener.back_off(retry_after(&response));
+            return Err(anyhow!("DexScreener rate limited the request"));
+        }
+        let pairs: Vec<DexPair> = response
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse DexScreener response")?;
+
+        let mut markets: HashMap<String, (TokenMarket, f64)> = HashMap::new(); // With the best pool's liquidity
+        for pair in pairs {
+            let liquidity = pair.liquidity.and_then(|l| l.usd).unwrap_or(0.0);
+            let Some(price) = pair.price_usd.filter(|p| *p > 0.0) else {
+                continue;
+            };
+            if liquidity < CONFIG.min_liquidity_usd {
+                continue;
+            }
+            let (market, best) = markets.entry(pair.base_token.address).or_insert_with(|| {
+                (
+                    TokenMarket {
+                        source: "dexscreener",
+                        ..TokenMarket::default()
+                    },
+                    0.0,
+                )
+            });
+            market.liquidity_usd += liquidity;
+            market.volume_usd_5m += pair.volume.and_then(|v| v.m5).unwrap_or(0.0);
+            if liquidity > *best {
+                *best = liquidity;
+                market.price_usd = price;
+            }
+        }
+        Ok(markets
+            .into_iter()
+            .map(|(token, (market, _))| (token, market))
+            .collect())
+    }
+
+    /// Prices from Birdeye; None when no API key is configured.
+    pub async fn birdeye(
+        &mut self,
+        tokens: &[String],
+    ) -> Result<Option<HashMap<String, TokenMarket>>> {
+        let Some(api_key) = CONFIG.birdeye_api_key.as_deref() else {
+            return Ok(None);
+        };
+        self.birdeye.acquire().await;
+        let response = self
+            .http
+            .get(format!("{}/defi/multi_price", CONFIG.birdeye_url))
+            .query(&[
+                ("list_address", tokens.join(",")),
+                ("include_liquidity", "true".to_string()),
+            ])
+            .header("X-API-KEY", api_key)
+            .header("x-chain", "solana")
+            .send()
+            .await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            self.birdeye.back_off(retry_after(&response));
+            return Err(anyhow!("Birdeye rate limited the request"));
+        }
+        let body: BirdeyeResponse = response
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Birdeye response")?;
+        Ok(Some(
+            body.data
+                .into_iter()
+                .filter_map(|(token, price)| {
+                    let price = price.filter(|p| p.value > 0.0)?;
+                    let liquidity_usd = price.liquidity.unwrap_or(0.0);
+                    if liquidity_usd != 0.0 && liquidity_usd < CONFIG.min_liquidity_usd {
+                        return None;
+                    }
+                    Some((
+                        token,
+                        TokenMarket {
+                            price_usd: price.value,
+                            liquidity_usd,
+                            volume_usd_5m: 0.0, // Not in multi-price
+                            source: "birdeye",
+                        },
+                    ))
+                })
+                .collect(),
+        ))
+    }
+}
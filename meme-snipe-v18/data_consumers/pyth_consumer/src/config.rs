@@ -0,0 +1,64 @@
+// data_consumers/pyth_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::HashMap;
+
+pub struct Config {
+    pub redis_url: String,
+    pub hermes_url: String,
+    pub sol_usd_feed_id: String,
+    pub token_feeds: HashMap<String, String>, // Pyth feed id -> token mint
+    pub max_age_secs: i64,
+    pub max_conf_ratio: f64,
+    pub min_publish_interval_ms: u64, // Per feed; Hermes pushes roughly every 400ms
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("pyth_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            hermes_url: c.get("PYTH_HERMES_URL", "https://hermes.pyth.network".to_string()),
+            sol_usd_feed_id: normalize_feed_id(&c.get(
+                "PYTH_SOL_USD_FEED_ID",
+                "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".to_string(),
+            )),
+            token_feeds: c
+                .list("PYTH_TOKEN_FEEDS", "")
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((mint, feed_id)) => Some((normalize_feed_id(feed_id), mint.to_string())),
+                    None => {
+                        c.invalid("PYTH_TOKEN_FEEDS", format!("{} is not MINT:FEED_ID", spec));
+                        None
+                    }
+                })
+                .collect(),
+            max_age_secs: c.get("PYTH_MAX_AGE_SECS", 30),
+            max_conf_ratio: c.get("PYTH_MAX_CONF_RATIO", 0.01),
+            min_publish_interval_ms: c.get("PYTH_MIN_PUBLISH_INTERVAL_MS", 1000),
+            stream_maxlen: c.get("PYTH_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("PYTH_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+
+    /// Every feed id streamed: SOL/USD first, then the token feeds.
+    pub fn feed_ids(&self) -> Vec<&str> {
+        std::iter::once(self.sol_usd_feed_id.as_str())
+            .chain(self.token_feeds.keys().map(String::as_str))
+            .collect()
+    }
+}
+
+/// Hermes returns ids without the 0x prefix, in lowercase.
+fn normalize_feed_id(id: &str) -> String {
+    id.trim().trim_start_matches("0x").to_lowercase()
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,136 @@
+// data_consumers/pyth_consumer/src/hermes.rs
+//! Pyth Hermes price stream: server-sent events from
+//! `/v2/updates/price/stream`, each carrying the latest update of one or more
+//! feeds. Updates that are stale or whose confidence band is wider than
+//! PYTH_MAX_CONF_RATIO of the price are rejected rather than published.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct StreamUpdate {
+    #[serde(default)]
+    parsed: Vec<ParsedFeed>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedFeed {
+    id: String,
+    price: HermesPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    conf: String,
+    expo: i32,
+    publish_time: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedPrice {
+    pub feed_id: String,
+    pub price_usd: f64,
+    pub publish_time: i64,
+}
+
+/// Why an update was not published; used as a metric label.
+#[derive(Debug, Clone, Copy)]
+pub enum Rejection {
+    Malformed,
+    NonPositive,
+    Stale,
+    WideConfidence,
+}
+
+impl Rejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rejection::Malformed => "malformed",
+            Rejection::NonPositive => "non_positive",
+            Rejection::Stale => "stale",
+            Rejection::WideConfidence => "wide_confidence",
+        }
+    }
+}
+
+impl ParsedFeed {
+    fn validate(self, now: i64) -> Result<FeedPrice, Rejection> {
+        let scale = 10f64.powi(self.price.expo);
+        let price = self.price.price.parse::<i64>();
+        let conf = self.price.conf.parse::<u64>();
+        let (Ok(price), Ok(conf)) = (price, conf) else {
+            return Err(Rejection::Malformed);
+        };
+        let price_usd = price as f64 * scale;
+        let conf_usd = conf as f64 * scale;
+        if price_usd <= 0.0 {
+            return Err(Rejection::NonPositive);
+        }
+        if now - self.price.publish_time > CONFIG.max_age_secs {
+            return Err(Rejection::Stale);
+        }
+        if conf_usd / price_usd > CONFIG.max_conf_ratio {
+            return Err(Rejection::WideConfidence);
+        }
+        Ok(FeedPrice {
+            feed_id: self.id.trim_start_matches("0x").to_lowercase(),
+            price_usd,
+            publish_time: self.price.publish_time,
+        })
+    }
+}
+
+/// Streams the configured feeds, calling `on_price` for each update until
+/// Hermes closes the stream (it does so every 24h) or the connection fails.
+pub async fn stream_prices<F>(http: &Client, mut on_price: F) -> Result<()>
+where
+    F: FnMut(Result<FeedPrice, Rejection>),
+{
+    let ids: Vec<(&str, &str)> = CONFIG
+        .feed_ids()
+        .into_iter()
+        .map(|id| ("ids[]", id))
+        .collect();
+    let response = http
+        .get(format!("{}/v2/updates/price/stream", CONFIG.hermes_url))
+        .query(&ids)
+        .query(&[("parsed", "true")])
+        .send()
+        .await?
+        .error_for_status()
+        .context("Hermes refused the price stream")?;
+
+    let mut body = response.bytes_stream();
+    let mut buffer = String::new();
+    while let Some(chunk) = body.next().await {
+        buffer.push_str(&String::from_utf8_lossy(&chunk?));
+        // An SSE event ends at a blank line; its payload is on the `data:` lines
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            let data: String = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect();
+            if data.is_empty() {
+                continue;
+            }
+            let update: StreamUpdate = match serde_json::from_str(&data) {
+                Ok(update) => update,
+                Err(_) => {
+                    on_price(Err(Rejection::Malformed));
+                    continue;
+                }
+            };
+            let now = chrono::Utc::now().timestamp();
+            for feed in update.parsed {
+                on_price(feed.validate(now));
+            }
+        }
+    }
+    Err(anyhow!("Hermes closed the price stream"))
+}
@@ -0,0 +1,207 @@
+// data_consumers/pyth_consumer/src/main.rs
+//! Streams SOL/USD from Pyth Hermes into `events:sol_price`, and optionally
+//! the PYTH_TOKEN_FEEDS tokens into `events:price`. Each feed is published at
+//! most once per PYTH_MIN_PUBLISH_INTERVAL_MS, and a DataSourceHeartbeat goes
+//! out every PYTH_HEARTBEAT_INTERVAL_SECS so a dead stream is noticed.
+
+mod config;
+mod hermes;
+
+use crate::config::CONFIG;
+use crate::hermes::{stream_prices, FeedPrice};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use lazy_static::lazy_static;
+use prometheus::{
+    register_int_counter, register_int_counter_vec, Encoder, IntCounter, IntCounterVec, TextEncoder,
+};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent, PriceTick, SolPriceEvent};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "pyth_consumer";
+const MAX_RECONNECT_BACKOFF_SECS: u64 = 60;
+
+lazy_static! {
+    static ref PRICES_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "pyth_prices_published_total",
+        "Prices published, by feed (sol_usd or the token mint).",
+        &["feed"]
+    )
+    .unwrap();
+    static ref UPDATES_REJECTED: IntCounterVec = register_int_counter_vec!(
+        "pyth_updates_rejected_total",
+        "Hermes updates not published, by reason.",
+        &["reason"]
+    )
+    .unwrap();
+    static ref STREAM_RECONNECTS: IntCounter = register_int_counter!(
+        "pyth_stream_reconnects_total",
+        "Times the Hermes price stream was reopened."
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+/// Keeps the Hermes stream open, reconnecting with exponential backoff.
+async fn run_stream(updates: mpsc::UnboundedSender<FeedPrice>) {
+    let http = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to build HTTP client");
+    let mut backoff_secs = 1;
+    loop {
+        let mut received = false;
+        let res = stream_prices(&http, |update| match update {
+            Ok(price) => {
+                received = true;
+                let _ = updates.send(price);
+            }
+            Err(reason) => UPDATES_REJECTED.with_label_values(&[reason.as_str()]).inc(),
+        })
+        .await;
+        if let Err(e) = res {
+            warn!(error = %e, "Hermes price stream ended. Reconnecting in {}s.", backoff_secs);
+        }
+        STREAM_RECONNECTS.inc();
+        tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+        // A stream that delivered prices was healthy; only back off on repeated failures
+        backoff_secs = if received {
+            1
+        } else {
+            (backoff_secs * 2).min(MAX_RECONNECT_BACKOFF_SECS)
+        };
+    }
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Publishes validated prices, throttled per feed.
+async fn publish_prices(
+    mut conn: MultiplexedConnection,
+    mut updates: mpsc::UnboundedReceiver<FeedPrice>,
+    last_processed: Arc<AtomicI64>,
+) {
+    let min_interval_ms = CONFIG.min_publish_interval_ms as i64;
+    let mut published_at: HashMap<String, i64> = HashMap::new(); // Feed id -> ms
+    while let Some(price) = updates.recv().await {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        if published_at
+            .get(&price.feed_id)
+            .is_some_and(|at| now_ms - at < min_interval_ms)
+        {
+            continue;
+        }
+        let (stream, feed, event) = if price.feed_id == CONFIG.sol_usd_feed_id {
+            (
+                "events:sol_price",
+                "sol_usd",
+                MarketEvent::SolPrice(SolPriceEvent {
+                    timestamp: price.publish_time,
+                    price_usd: price.price_usd,
+                }),
+            )
+        } else if let Some(mint) = CONFIG.token_feeds.get(&price.feed_id) {
+            (
+                "events:price",
+                mint.as_str(),
+                MarketEvent::Price(PriceTick {
+                    timestamp: price.publish_time,
+                    token_address: mint.clone(),
+                    price_usd: price.price_usd,
+                    volume_usd_1m: 0.0, // Pyth carries no volume
+                    venue: None,
+                }),
+            )
+        } else {
+            continue;
+        };
+        if let Err(e) = publish(&mut conn, stream, &event).await {
+            error!(error = %e, feed, "Failed to publish Pyth price to {}.", stream);
+            continue;
+        }
+        published_at.insert(price.feed_id, now_ms);
+        last_processed.fetch_max(price.publish_time, Ordering::Relaxed);
+        PRICES_PUBLISHED.with_label_values(&[feed]).inc();
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🔮 Starting Pyth Consumer: SOL/USD plus {} token feeds from {}...",
+        CONFIG.token_feeds.len(),
+        CONFIG.hermes_url
+    );
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_stream(updates_tx));
+    tokio::spawn(publish_prices(
+        conn.clone(),
+        updates_rx,
+        last_processed.clone(),
+    ));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
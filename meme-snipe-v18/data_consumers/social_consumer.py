@@ -1,5 +1,6 @@
 import redis
 import json
+import math
 import time
 import os
 import requests
@@ -127,12 +128,19 @@ def analyze_tweet_sentiment(tweet_text):
     else:
         return "neutral", 0
 
+def author_influence(followers_count):
+    """Influence score in [0, 1] from an account's follower count; 1M followers scores 1."""
+    if followers_count <= 1:
+        return 0.0
+    return min(1.0, math.log10(followers_count) / 6.0)
+
 def process_tweets(tweet_data):
     """Process tweet data and extract relevant social signals."""
     if not tweet_data or 'data' not in tweet_data:
         return []
     
     social_events = []
+    users = {u['id']: u for u in tweet_data.get('includes', {}).get('users', [])}
     
     for tweet in tweet_data['data']:
         try:
@@ -147,6 +155,10 @@ def process_tweets(tweet_data):
                 metrics.get('reply_count', 0) * 2
             )
             
+            # Author handle and influence, for influence-weighted strategies
+            author = users.get(tweet.get('author_id'), {})
+            followers = author.get('public_metrics', {}).get('followers_count', 0)
+            
             # Create social event
             event = {
                 "type": "Social",
@@ -157,6 +169,8 @@ def process_tweets(tweet_data):
                 "text": tweet['text'][:200],  # Truncate for storage
                 "created_at": tweet.get('created_at'),
                 "author_id": tweet.get('author_id'),
+                "author": author.get('username'),
+                "author_influence": author_influence(followers),
                 "metrics": metrics,
                 "timestamp": time.time()
             }
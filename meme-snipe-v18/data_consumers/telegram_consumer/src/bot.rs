@@ -0,0 +1,128 @@
+// data_consumers/telegram_consumer/src/bot.rs
+//! Telegram Bot API client. A bot only sees channels it has been added to, so
+//! each call channel needs the ingest bot as a member (channels require it to
+//! be an admin, with no rights needed). Posts arrive as `channel_post`
+//! updates; groups deliver `message` updates, which are handled the same way.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+const LONG_POLL_SECS: u64 = 50;
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Update {
+    pub update_id: i64,
+    #[serde(default)]
+    pub channel_post: Option<Message>,
+    #[serde(default)]
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub message_id: i64,
+    pub date: i64,
+    pub chat: Chat,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub caption: Option<String>, // Text of photo and video posts
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Chat {
+    pub id: i64,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl Chat {
+    /// `@username` for public chats, the numeric id otherwise.
+    pub fn handle(&self) -> String {
+        match &self.username {
+            Some(username) => format!("@{}", username),
+            None => self.id.to_string(),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.username
+            .as_ref()
+            .is_some_and(|u| CONFIG.channels.contains(&u.to_lowercase()))
+            || CONFIG.channels.contains(&self.id.to_string())
+    }
+}
+
+pub struct Bot {
+    http: Client,
+}
+
+impl Bot {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(LONG_POLL_SECS + 10))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T> {
+        let url = format!("{}/bot{}/{}", CONFIG.api_url, CONFIG.bot_token, method);
+        let response: ApiResponse<T> = self
+            .http
+            .post(&url)
+            .json(&params)
+            .send()
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", method))?;
+        match response.result {
+            Some(result) if response.ok => Ok(result),
+            _ => Err(anyhow!(
+                "{} failed: {}",
+                method,
+                response.description.unwrap_or_default()
+            )),
+        }
+    }
+
+    /// Long-polls for updates after `offset`, waiting up to LONG_POLL_SECS.
+    pub async fn updates(&self, offset: i64) -> Result<Vec<Update>> {
+        self.call(
+            "getUpdates",
+            json!({
+                "offset": offset,
+                "timeout": LONG_POLL_SECS,
+                "allowed_updates": ["channel_post", "message"],
+            }),
+        )
+        .await
+    }
+
+    /// Member count of a chat the bot belongs to.
+    pub async fn member_count(&self, chat_id: i64) -> Result<u64> {
+        self.call("getChatMemberCount", json!({ "chat_id": chat_id }))
+            .await
+    }
+}
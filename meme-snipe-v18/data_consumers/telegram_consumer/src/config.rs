@@ -0,0 +1,56 @@
+// data_consumers/telegram_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::{HashMap, HashSet};
+
+pub struct Config {
+    pub redis_url: String,
+    pub bot_token: String, // Separate from the alert relay's bot: getUpdates allows one reader
+    pub api_url: String,
+    pub channels: HashSet<String>, // Lowercase usernames without the @, or numeric chat ids
+    pub tickers: HashMap<String, String>, // Uppercase symbol -> mint
+    pub member_refresh_secs: u64,
+    pub max_post_age_secs: i64, // Older posts, e.g. backlog after downtime, are skipped
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("telegram_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            bot_token: c.required("TELEGRAM_INGEST_BOT_TOKEN"),
+            api_url: c.get("TELEGRAM_API_URL", "https://api.telegram.org".to_string()),
+            channels: c
+                .list("TELEGRAM_CHANNELS", "")
+                .iter()
+                .map(|channel| channel.trim_start_matches('@').to_lowercase())
+                .collect(),
+            tickers: c
+                .list("TELEGRAM_TICKERS", "")
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((symbol, mint)) => Some((
+                        symbol.trim_start_matches('$').to_uppercase(),
+                        mint.to_string(),
+                    )),
+                    None => {
+                        c.invalid("TELEGRAM_TICKERS", format!("{} is not SYMBOL:MINT", spec));
+                        None
+                    }
+                })
+                .collect(),
+            member_refresh_secs: c.get("TELEGRAM_MEMBER_REFRESH_SECS", 3600),
+            max_post_age_secs: c.get("TELEGRAM_MAX_POST_AGE_SECS", 300),
+            stream_maxlen: c.get("TELEGRAM_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("TELEGRAM_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,97 @@
+// data_consumers/telegram_consumer/src/extract.rs
+//! What a channel post calls and how hard. Call channels post contract
+//! addresses more often than tickers, so every mint address in a post counts,
+//! as do `$TICKER`s listed in TELEGRAM_TICKERS. Sentiment is a lexicon score
+//! in [-1, 1]; urgency in [0, 1] comes from now-or-never wording, alarm emoji,
+//! exclamation marks and shouting.
+
+use crate::config::CONFIG;
+use consumer_common::social::{self, mints_in_link, words};
+use std::collections::BTreeSet;
+
+const SENTIMENT: &[(&str, f64)] = &[
+    ("moon", 1.0),
+    ("gem", 1.0),
+    ("bullish", 1.5),
+    ("buy", 0.5),
+    ("aped", 1.0),
+    ("ape", 0.5),
+    ("send", 0.5),
+    ("sending", 1.0),
+    ("lfg", 1.0),
+    ("x10", 1.0),
+    ("x100", 1.5),
+    ("🚀", 1.0),
+    ("📈", 1.0),
+    ("💎", 0.5),
+    ("🔥", 0.5),
+    ("✅", 0.5),
+    ("dump", -1.0),
+    ("dumping", -1.5),
+    ("bearish", -1.5),
+    ("sell", -0.5),
+    ("rug", -2.0),
+    ("rugged", -2.0),
+    ("scam", -2.0),
+    ("honeypot", -2.0),
+    ("avoid", -1.5),
+    ("dead", -1.0),
+    ("📉", -1.0),
+    ("⚠️", -0.5),
+];
+
+const URGENCY: &[(&str, f64)] = &[
+    ("now", 1.0),
+    ("live", 1.0),
+    ("launched", 1.0),
+    ("launching", 1.0),
+    ("just", 0.5),
+    ("early", 1.0),
+    ("fast", 1.0),
+    ("quick", 1.0),
+    ("hurry", 1.5),
+    ("asap", 1.5),
+    ("last", 0.5),
+    ("🚨", 1.5),
+    ("⚡", 1.0),
+    ("⏰", 1.0),
+];
+
+/// Mints the post refers to, deduplicated. Addresses inside links, e.g.
+/// `dexscreener.com/solana/<mint>` or `pump.fun/<mint>`, count too.
+pub fn mentioned_mints(text: &str) -> BTreeSet<String> {
+    let mut mints = BTreeSet::new();
+    for word in words(text) {
+        if let Some(symbol) = word.strip_prefix('$') {
+            if let Some(mint) = CONFIG.tickers.get(&symbol.to_uppercase()) {
+                mints.insert(mint.clone());
+            }
+            continue;
+        }
+        mints.extend(mints_in_link(word).map(str::to_string));
+    }
+    mints
+}
+
+/// Lexicon sentiment in [-1, 1]; a negation flips the next scored word.
+pub fn sentiment(text: &str) -> f64 {
+    social::sentiment(text, SENTIMENT)
+}
+
+/// Urgency in [0, 1].
+pub fn urgency(text: &str) -> f64 {
+    let lower = text.to_lowercase();
+    let mut total: f64 = words(&lower)
+        .filter_map(|word| URGENCY.iter().find(|(w, _)| *w == word))
+        .map(|(_, weight)| weight)
+        .sum();
+    total += (text.matches('!').count() as f64 * 0.25).min(1.0);
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() >= 10 {
+        let upper = letters.iter().filter(|c| c.is_uppercase()).count() as f64;
+        if upper / letters.len() as f64 > 0.6 {
+            total += 1.0; // Shouting
+        }
+    }
+    (total / 3.0).tanh()
+}
@@ -0,0 +1,226 @@
+// data_consumers/telegram_consumer/src/main.rs
+//! Reads posts from the TELEGRAM_CHANNELS through the ingest bot and
+//! publishes a SocialMention to `events:social` for every token a post
+//! calls, with sentiment, urgency, and the channel's member-count influence.
+//! Posts older than TELEGRAM_MAX_POST_AGE_SECS are skipped so a restart
+//! doesn't replay a backlog of stale calls.
+
+mod bot;
+mod config;
+mod extract;
+
+use crate::bot::{Bot, Message};
+use crate::config::CONFIG;
+use crate::extract::{mentioned_mints, sentiment, urgency};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use consumer_common::social::audience_influence;
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent, SocialMention};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "telegram_consumer";
+
+lazy_static! {
+    static ref POSTS: IntCounterVec = register_int_counter_vec!(
+        "telegram_posts_total",
+        "Posts received, by outcome (published, no_token, stale, other_chat).",
+        &["outcome"]
+    )
+    .unwrap();
+    static ref API_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "telegram_api_errors_total",
+        "Failed Bot API calls, by method.",
+        &["method"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Channel influence, refreshed every TELEGRAM_MEMBER_REFRESH_SECS.
+struct Influence {
+    by_chat: HashMap<i64, (f64, i64)>, // Chat id -> (influence, fetched at)
+}
+
+impl Influence {
+    async fn of(&mut self, bot: &Bot, chat_id: i64, now: i64) -> Option<f64> {
+        if let Some((influence, fetched_at)) = self.by_chat.get(&chat_id) {
+            if now - fetched_at < CONFIG.member_refresh_secs as i64 {
+                return Some(*influence);
+            }
+        }
+        match bot.member_count(chat_id).await {
+            Ok(members) => {
+                let influence = audience_influence(members);
+                self.by_chat.insert(chat_id, (influence, now));
+                Some(influence)
+            }
+            Err(e) => {
+                warn!(error = %e, chat_id, "Failed to fetch channel member count.");
+                API_ERRORS.with_label_values(&["getChatMemberCount"]).inc();
+                // Keep using the last known value rather than dropping influence
+                self.by_chat.get(&chat_id).map(|(influence, _)| *influence)
+            }
+        }
+    }
+}
+
+async fn handle_post(
+    conn: &mut MultiplexedConnection,
+    bot: &Bot,
+    influence: &mut Influence,
+    post: Message,
+) -> Option<i64> {
+    if !post.chat.is_configured() {
+        POSTS.with_label_values(&["other_chat"]).inc();
+        return None;
+    }
+    let now = chrono::Utc::now().timestamp();
+    if now - post.date > CONFIG.max_post_age_secs {
+        POSTS.with_label_values(&["stale"]).inc();
+        return None;
+    }
+    let text = post.text.or(post.caption).unwrap_or_default();
+    let mints = mentioned_mints(&text);
+    if mints.is_empty() {
+        POSTS.with_label_values(&["no_token"]).inc();
+        return None;
+    }
+
+    let handle = post.chat.handle();
+    let author_influence = influence.of(bot, post.chat.id, now).await;
+    let (sentiment, urgency) = (sentiment(&text), urgency(&text));
+    for mint in mints {
+        let mention = MarketEvent::Social(SocialMention {
+            timestamp: post.date,
+            token_address: mint.clone(),
+            source: "telegram".to_string(),
+            sentiment,
+            author: Some(handle.clone()),
+            author_influence,
+            post_id: Some(format!("{}/{}", handle, post.message_id)),
+            urgency: Some(urgency),
+        });
+        if let Err(e) = publish(conn, "events:social", &mention).await {
+            error!(error = %e, token = %mint, "Failed to publish SocialMention.");
+        }
+    }
+    info!(
+        channel = %handle,
+        title = post.chat.title.as_deref().unwrap_or(""),
+        "Published call: sentiment {:.2}, urgency {:.2}.",
+        sentiment,
+        urgency
+    );
+    POSTS.with_label_values(&["published"]).inc();
+    Some(post.date)
+}
+
+async fn poll_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let bot = Bot::new();
+    let mut influence = Influence {
+        by_chat: HashMap::new(),
+    };
+    let mut offset = 0;
+    loop {
+        let updates = match bot.updates(offset).await {
+            Ok(updates) => updates,
+            Err(e) => {
+                warn!(error = %e, "getUpdates failed. Retrying in 5s.");
+                API_ERRORS.with_label_values(&["getUpdates"]).inc();
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        for update in updates {
+            // Acknowledges everything up to this update on the next poll
+            offset = offset.max(update.update_id + 1);
+            let Some(post) = update.channel_post.or(update.message) else {
+                continue;
+            };
+            if let Some(at) = handle_post(&mut conn, &bot, &mut influence, post).await {
+                last_processed.fetch_max(at, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "✈️  Starting Telegram Consumer for {} channels and {} tickers...",
+        CONFIG.channels.len(),
+        CONFIG.tickers.len()
+    );
+    if CONFIG.channels.is_empty() {
+        warn!("TELEGRAM_CHANNELS is empty; every post will be ignored.");
+    }
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    tokio::spawn(poll_loop(conn.clone(), last_processed.clone()));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
@@ -0,0 +1,237 @@
+// data_consumers/twitter_consumer/src/api.rs
+//! X API v2 filtered stream. Stream rules are shared by every client of the
+//! app, so this service only touches rules tagged with RULE_TAG: on startup it
+//! deletes those and adds the current cashtag and account rules.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use futures_util::StreamExt;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+const RULE_TAG: &str = "memesnipe";
+const MAX_RULE_LEN: usize = 512; // Basic and Pro access limit
+/// X sends a keep-alive newline every 20s; this long without one means the
+/// connection is stalled.
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct RulesResponse {
+    #[serde(default)]
+    data: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rule {
+    id: String,
+    #[serde(default)]
+    tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamEnvelope {
+    pub data: Tweet,
+    #[serde(default)]
+    pub includes: Includes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tweet {
+    pub id: String,
+    pub text: String,
+    pub author_id: String,
+    #[serde(default)]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Includes {
+    #[serde(default)]
+    pub users: Vec<User>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    #[serde(default)]
+    pub public_metrics: Option<UserMetrics>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserMetrics {
+    pub followers_count: u64,
+}
+
+/// Why the stream ended, so the caller can pick a backoff.
+#[derive(Debug)]
+pub enum StreamEnd {
+    RateLimited,
+    Failed(anyhow::Error),
+}
+
+pub struct TwitterClient {
+    http: Client,
+}
+
+impl TwitterClient {
+    pub fn new() -> Self {
+        Self {
+            http: Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    fn rules_url(&self) -> String {
+        format!("{}/2/tweets/search/stream/rules", CONFIG.api_url)
+    }
+
+    /// Replaces this service's rules with ones for the configured cashtags and
+    /// accounts. Terms are OR-ed into as few rules as fit the length limit.
+    pub async fn sync_rules(&self) -> Result<usize> {
+        let existing: RulesResponse = self
+            .http
+            .get(self.rules_url())
+            .bearer_auth(&CONFIG.bearer_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse stream rules")?;
+        let ours: Vec<String> = existing
+            .data
+            .into_iter()
+            .filter(|rule| rule.tag.as_deref() == Some(RULE_TAG))
+            .map(|rule| rule.id)
+            .collect();
+        if !ours.is_empty() {
+            self.http
+                .post(self.rules_url())
+                .bearer_auth(&CONFIG.bearer_token)
+                .json(&json!({ "delete": { "ids": ours } }))
+                .send()
+                .await?
+                .error_for_status()
+                .context("Failed to delete old stream rules")?;
+        }
+
+        let terms = CONFIG
+            .cashtags
+            .keys()
+            .map(|symbol| format!("${}", symbol))
+            .chain(
+                CONFIG
+                    .accounts
+                    .iter()
+                    .map(|handle| format!("from:{}", handle)),
+            );
+        let rules = pack_rules(terms);
+        if rules.is_empty() {
+            return Ok(0);
+        }
+        let add: Vec<_> = rules
+            .iter()
+            .map(|value| json!({ "value": value, "tag": RULE_TAG }))
+            .collect();
+        self.http
+            .post(self.rules_url())
+            .bearer_auth(&CONFIG.bearer_token)
+            .json(&json!({ "add": add }))
+            .send()
+            .await?
+            .error_for_status()
+            .context("Failed to add stream rules")?;
+        Ok(rules.len())
+    }
+
+    /// Reads the filtered stream, calling `on_tweet` for each tweet, until the
+    /// connection drops or stalls.
+    pub async fn stream<F>(&self, mut on_tweet: F) -> StreamEnd
+    where
+        F: FnMut(StreamEnvelope),
+    {
+        let response = self
+            .http
+            .get(format!("{}/2/tweets/search/stream", CONFIG.api_url))
+            .bearer_auth(&CONFIG.bearer_token)
+            .query(&[
+                ("tweet.fields", "author_id,created_at"),
+                ("expansions", "author_id"),
+                ("user.fields", "username,public_metrics"),
+            ])
+            .send()
+            .await;
+        let response = match response {
+            Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                return StreamEnd::RateLimited;
+            }
+            Ok(response) => match response.error_for_status() {
+                Ok(response) => response,
+                Err(e) => return StreamEnd::Failed(e.into()),
+            },
+            Err(e) => return StreamEnd::Failed(e.into()),
+        };
+
+        let mut body = response.bytes_stream();
+        let mut buffer = String::new();
+        loop {
+            let chunk = match tokio::time::timeout(STALL_TIMEOUT, body.next()).await {
+                Ok(Some(Ok(chunk))) => chunk,
+                Ok(Some(Err(e))) => return StreamEnd::Failed(e.into()),
+                Ok(None) => return StreamEnd::Failed(anyhow!("X closed the stream")),
+                Err(_) => {
+                    return StreamEnd::Failed(anyhow!(
+                        "No data or keep-alive for {:?}",
+                        STALL_TIMEOUT
+                    ))
+                }
+            };
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            // One JSON object per line; blank lines are keep-alives
+            while let Some(end) = buffer.find('\n') {
+                let line: String = buffer.drain(..=end).collect();
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<StreamEnvelope>(line) {
+                    Ok(envelope) => on_tweet(envelope),
+                    Err(e) => tracing::debug!(error = %e, "Skipping non-tweet stream message."),
+                }
+            }
+        }
+    }
+}
+
+/// OR-joins terms into rules no longer than MAX_RULE_LEN, leaving retweets out.
+fn pack_rules(terms: impl Iterator<Item = String>) -> Vec<String> {
+    const SUFFIX: &str = ") -is:retweet";
+    let mut rules = Vec::new();
+    let mut current = String::new();
+    for term in terms {
+        let extra = if current.is_empty() {
+            1 + term.len()
+        } else {
+            4 + term.len()
+        };
+        if !current.is_empty() && current.len() + extra + SUFFIX.len() > MAX_RULE_LEN {
+            rules.push(format!("{}{}", current, SUFFIX));
+            current.clear();
+        }
+        if current.is_empty() {
+            current.push('(');
+        } else {
+            current.push_str(" OR ");
+        }
+        current.push_str(&term);
+    }
+    if !current.is_empty() {
+        rules.push(format!("{}{}", current, SUFFIX));
+    }
+    rules
+}
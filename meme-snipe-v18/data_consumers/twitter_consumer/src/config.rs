@@ -0,0 +1,58 @@
+// data_consumers/twitter_consumer/src/config.rs
+use lazy_static::lazy_static;
+use shared_config::Loader;
+use std::collections::{HashMap, HashSet};
+
+pub struct Config {
+    pub redis_url: String,
+    pub bearer_token: String,
+    pub api_url: String,
+    pub cashtags: HashMap<String, String>, // Uppercase symbol -> mint
+    pub accounts: HashSet<String>,         // Lowercase handles, without the @
+    pub min_followers: u64,                // Below this, untracked authors are ignored as spam
+    pub stream_maxlen: usize,
+    pub heartbeat_interval_secs: u64,
+}
+
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("twitter_consumer");
+        let config = Self {
+            redis_url: c.get("REDIS_URL", "redis://redis:6379".to_string()),
+            bearer_token: c.required("TWITTER_BEARER_TOKEN"),
+            api_url: c.get("TWITTER_API_URL", "https://api.twitter.com".to_string()),
+            cashtags: c
+                .list("TWITTER_CASHTAGS", "")
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((symbol, mint)) => Some((
+                        symbol.trim_start_matches('$').to_uppercase(),
+                        mint.to_string(),
+                    )),
+                    None => {
+                        c.invalid("TWITTER_CASHTAGS", format!("{} is not SYMBOL:MINT", spec));
+                        None
+                    }
+                })
+                .collect(),
+            accounts: c
+                .list("TWITTER_ACCOUNTS", "")
+                .iter()
+                .map(|handle| handle.trim_start_matches('@').to_lowercase())
+                .collect(),
+            min_followers: c.get("TWITTER_MIN_FOLLOWERS", 500),
+            stream_maxlen: c.get("TWITTER_STREAM_MAXLEN", 100_000),
+            heartbeat_interval_secs: c.get("TWITTER_HEARTBEAT_INTERVAL_SECS", 30),
+        };
+        c.finish();
+        config
+    }
+
+    pub fn is_tracked_mint(&self, mint: &str) -> bool {
+        self.cashtags.values().any(|m| m == mint)
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
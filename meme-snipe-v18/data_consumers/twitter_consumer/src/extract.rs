@@ -0,0 +1,67 @@
+// data_consumers/twitter_consumer/src/extract.rs
+//! What a tweet is about and how it feels. Tokens come from configured
+//! cashtags, and from mint addresses pasted in the text: any tracked mint, or
+//! any mint at all when the author is a tracked account, since calls from
+//! those usually carry the contract address before anyone has a cashtag for
+//! it. Sentiment is a small lexicon score squashed into [-1, 1].
+
+use crate::config::CONFIG;
+use consumer_common::social::{self, looks_like_mint, words};
+use std::collections::BTreeSet;
+
+/// Word weights; emoji are matched as words since tweets space them out.
+const LEXICON: &[(&str, f64)] = &[
+    ("moon", 1.0),
+    ("mooning", 1.5),
+    ("pump", 0.5),
+    ("pumping", 1.0),
+    ("bullish", 1.5),
+    ("buy", 0.5),
+    ("buying", 1.0),
+    ("aped", 1.0),
+    ("ape", 0.5),
+    ("send", 0.5),
+    ("sending", 1.0),
+    ("gem", 1.0),
+    ("lfg", 1.0),
+    ("breakout", 1.0),
+    ("🚀", 1.0),
+    ("📈", 1.0),
+    ("💎", 0.5),
+    ("🔥", 0.5),
+    ("dump", -1.0),
+    ("dumping", -1.5),
+    ("crash", -1.5),
+    ("bearish", -1.5),
+    ("sell", -0.5),
+    ("selling", -1.0),
+    ("sold", -0.5),
+    ("rug", -2.0),
+    ("rugged", -2.0),
+    ("scam", -2.0),
+    ("honeypot", -2.0),
+    ("rekt", -1.0),
+    ("dead", -1.0),
+    ("📉", -1.0),
+    ("💩", -1.0),
+];
+
+/// Mints the tweet refers to, deduplicated.
+pub fn mentioned_mints(text: &str, from_tracked_account: bool) -> BTreeSet<String> {
+    let mut mints = BTreeSet::new();
+    for word in words(text) {
+        if let Some(symbol) = word.strip_prefix('$') {
+            if let Some(mint) = CONFIG.cashtags.get(&symbol.to_uppercase()) {
+                mints.insert(mint.clone());
+            }
+        } else if looks_like_mint(word) && (from_tracked_account || CONFIG.is_tracked_mint(word)) {
+            mints.insert(word.to_string());
+        }
+    }
+    mints
+}
+
+/// Lexicon sentiment in [-1, 1]; a negation flips the next scored word.
+pub fn sentiment(text: &str) -> f64 {
+    social::sentiment(text, LEXICON)
+}
@@ -0,0 +1,211 @@
+// data_consumers/twitter_consumer/src/main.rs
+//! Follows the TWITTER_CASHTAGS and TWITTER_ACCOUNTS through the X filtered
+//! stream and publishes a SocialMention to `events:social` for every tracked
+//! token a tweet refers to, with lexicon sentiment and the author's handle
+//! and follower-based influence. Untracked authors under
+//! TWITTER_MIN_FOLLOWERS are dropped as spam.
+
+mod api;
+mod config;
+mod extract;
+
+use crate::api::{StreamEnd, StreamEnvelope, TwitterClient};
+use crate::config::CONFIG;
+use crate::extract::{mentioned_mints, sentiment};
+use anyhow::Result;
+use axum::{routing::get, Router};
+use consumer_common::social::audience_influence;
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+use shared_models::{DataSourceHeartbeat, MarketEvent, SocialMention};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+const SOURCE_NAME: &str = "twitter_consumer";
+
+lazy_static! {
+    static ref TWEETS: IntCounterVec = register_int_counter_vec!(
+        "twitter_tweets_total",
+        "Tweets received, by outcome (published, no_token, low_followers).",
+        &["outcome"]
+    )
+    .unwrap();
+    static ref STREAM_RECONNECTS: IntCounterVec = register_int_counter_vec!(
+        "twitter_stream_reconnects_total",
+        "Times the filtered stream was reopened, by reason.",
+        &["reason"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn publish(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    event: &MarketEvent,
+) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let _: String = conn
+        .xadd_maxlen(
+            stream,
+            StreamMaxlen::Approx(CONFIG.stream_maxlen),
+            "*",
+            &[("event", payload)],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Keeps the filtered stream open, backing off as X asks: exponentially from
+/// a minute when rate limited, from 5s on other failures.
+async fn run_stream(client: TwitterClient, tweets: mpsc::UnboundedSender<StreamEnvelope>) {
+    let mut backoff = Duration::ZERO;
+    loop {
+        let mut received = false;
+        let end = client
+            .stream(|envelope| {
+                received = true;
+                let _ = tweets.send(envelope);
+            })
+            .await;
+        if received {
+            backoff = Duration::ZERO;
+        }
+        backoff = match end {
+            StreamEnd::RateLimited => {
+                STREAM_RECONNECTS.with_label_values(&["rate_limited"]).inc();
+                (backoff * 2).clamp(Duration::from_secs(60), Duration::from_secs(900))
+            }
+            StreamEnd::Failed(e) => {
+                STREAM_RECONNECTS.with_label_values(&["failed"]).inc();
+                warn!(error = %e, "Filtered stream ended.");
+                (backoff * 2).clamp(Duration::from_secs(5), Duration::from_secs(320))
+            }
+        };
+        info!("Reconnecting to the filtered stream in {:?}.", backoff);
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+async fn publish_mentions(
+    mut conn: MultiplexedConnection,
+    mut tweets: mpsc::UnboundedReceiver<StreamEnvelope>,
+    last_processed: Arc<AtomicI64>,
+) {
+    while let Some(StreamEnvelope {
+        data: tweet,
+        includes,
+    }) = tweets.recv().await
+    {
+        let author = includes.users.iter().find(|u| u.id == tweet.author_id);
+        let handle = author.map(|u| u.username.clone());
+        let followers = author
+            .and_then(|u| u.public_metrics.as_ref())
+            .map_or(0, |m| m.followers_count);
+        let tracked_account = handle
+            .as_deref()
+            .is_some_and(|h| CONFIG.accounts.contains(&h.to_lowercase()));
+        if !tracked_account && followers < CONFIG.min_followers {
+            TWEETS.with_label_values(&["low_followers"]).inc();
+            continue;
+        }
+        let mints = mentioned_mints(&tweet.text, tracked_account);
+        if mints.is_empty() {
+            TWEETS.with_label_values(&["no_token"]).inc();
+            continue;
+        }
+
+        let timestamp = tweet
+            .created_at
+            .map_or_else(|| chrono::Utc::now().timestamp(), |at| at.timestamp());
+        let sentiment = sentiment(&tweet.text);
+        for mint in mints {
+            let mention = MarketEvent::Social(SocialMention {
+                timestamp,
+                token_address: mint.clone(),
+                source: "twitter".to_string(),
+                sentiment,
+                author: handle.clone(),
+                author_influence: Some(audience_influence(followers)),
+                post_id: Some(tweet.id.clone()),
+                urgency: None,
+            });
+            if let Err(e) = publish(&mut conn, "events:social", &mention).await {
+                error!(error = %e, token = %mint, "Failed to publish SocialMention.");
+            }
+        }
+        TWEETS.with_label_values(&["published"]).inc();
+        last_processed.fetch_max(timestamp, Ordering::Relaxed);
+    }
+}
+
+async fn heartbeat_loop(mut conn: MultiplexedConnection, last_processed: Arc<AtomicI64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.heartbeat_interval_secs));
+    loop {
+        interval.tick().await;
+        let heartbeat = MarketEvent::DataSourceHeartbeat(DataSourceHeartbeat {
+            source_name: SOURCE_NAME.to_string(),
+            last_processed_timestamp: last_processed.load(Ordering::Relaxed),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+        if let Err(e) = publish(&mut conn, "events:data_source_heartbeat", &heartbeat).await {
+            warn!(error = %e, "Failed to publish heartbeat.");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🐦 Starting Twitter Consumer for {} cashtags and {} accounts...",
+        CONFIG.cashtags.len(),
+        CONFIG.accounts.len()
+    );
+
+    let client = TwitterClient::new();
+    let rules = client.sync_rules().await?;
+    if rules == 0 {
+        warn!("TWITTER_CASHTAGS and TWITTER_ACCOUNTS are empty; the stream will deliver nothing.");
+    }
+    info!("Synced {} filtered stream rules.", rules);
+
+    let redis = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = redis.get_multiplexed_async_connection().await?;
+    let last_processed = Arc::new(AtomicI64::new(0));
+    let (tweets_tx, tweets_rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_stream(client, tweets_tx));
+    tokio::spawn(publish_mentions(
+        conn.clone(),
+        tweets_rx,
+        last_processed.clone(),
+    ));
+    tokio::spawn(heartbeat_loop(conn, last_processed));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
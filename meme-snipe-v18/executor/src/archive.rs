@@ -0,0 +1,185 @@
+// executor/src/archive.rs
+//! Reads the recorder's archives (`<dir>/<event type>/<YYYY-MM-DD>/*.parquet`
+//! or `*.jsonl.gz`) a day at a time, merging the requested event types into
+//! one stream ordered by when Redis received each entry. Shared by the
+//! backtest binary and the executor's replay mode.
+
+use anyhow::{Context, Result};
+use arrow_array::{Array, Int64Array, StringArray};
+use flate2::read::GzDecoder;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::Deserialize;
+use serde_json::Value;
+use shared_models::{EventType, MarketEvent};
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// An archived event and the time Redis received it.
+pub struct Recorded {
+    pub received_at_ms: i64,
+    pub event: MarketEvent,
+}
+
+/// The recorder's directory for an event type (its stream name minus `events:`).
+pub fn dir_for(event_type: &EventType) -> &'static str {
+    match event_type {
+        EventType::Price => "price",
+        EventType::Social => "social",
+        EventType::Depth => "depth",
+        EventType::Bridge => "bridge",
+        EventType::Funding => "funding",
+        EventType::OnChain => "onchain",
+        EventType::SolPrice => "sol_price",
+        EventType::DataSourceHeartbeat => "data_source_heartbeat",
+        EventType::LiquidityPool => "liquidity_pool",
+        EventType::WhaleTransfer => "whale_transfer",
+        EventType::TokenLaunch => "token_launch",
+    }
+}
+
+pub struct Archive {
+    dir: PathBuf,
+    types: Vec<&'static str>,
+}
+
+impl Archive {
+    pub fn new(dir: &Path, event_types: &HashSet<EventType>) -> Self {
+        let mut types: Vec<&'static str> = event_types.iter().map(dir_for).collect();
+        types.sort_unstable();
+        types.dedup();
+        Self {
+            dir: dir.to_path_buf(),
+            types,
+        }
+    }
+
+    /// Days with data for any of the event types, within `[from, to]` when given.
+    pub fn days(&self, from: Option<&str>, to: Option<&str>) -> Result<Vec<String>> {
+        let mut days = BTreeSet::new();
+        for event_type in &self.types {
+            let dir = self.dir.join(event_type);
+            if !dir.exists() {
+                warn!(event_type, "No archive for this event type.");
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let day = entry?.file_name().to_string_lossy().to_string();
+                // ISO dates compare correctly as strings
+                if from.map_or(true, |from| day.as_str() >= from)
+                    && to.map_or(true, |to| day.as_str() <= to)
+                {
+                    days.insert(day);
+                }
+            }
+        }
+        Ok(days.into_iter().collect())
+    }
+
+    /// Every event of the day, oldest first, with re-recorded duplicates dropped.
+    pub fn load_day(&self, day: &str) -> Result<Vec<Recorded>> {
+        let mut rows: Vec<(i64, (i64, u64), &'static str, String)> = Vec::new();
+        for event_type in &self.types {
+            let dir = self.dir.join(event_type).join(day);
+            if !dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let name = path.to_string_lossy();
+                let file_rows = if name.ends_with(".parquet") {
+                    read_parquet(&path)
+                } else if name.ends_with(".jsonl.gz") {
+                    read_jsonl(&path)
+                } else {
+                    continue; // Unfinished (.partial) or foreign files
+                }
+                .with_context(|| format!("reading {}", path.display()))?;
+                rows.extend(
+                    file_rows
+                        .into_iter()
+                        .map(|(id, at, event)| (at, parse_id(&id), *event_type, event)),
+                );
+            }
+        }
+        rows.sort_by(|a, b| (a.0, a.1, a.2).cmp(&(b.0, b.1, b.2)));
+        rows.dedup_by(|a, b| a.1 == b.1 && a.2 == b.2);
+
+        let mut events = Vec::with_capacity(rows.len());
+        let mut unparsed = 0;
+        for (received_at_ms, _, _, event) in rows {
+            match serde_json::from_str::<MarketEvent>(&event) {
+                Ok(event) => events.push(Recorded {
+                    received_at_ms,
+                    event,
+                }),
+                Err(_) => unparsed += 1,
+            }
+        }
+        if unparsed > 0 {
+            warn!(
+                day,
+                unparsed, "Skipped archived entries that aren't MarketEvents."
+            );
+        }
+        debug!(day, events = events.len(), "Loaded archive day.");
+        Ok(events)
+    }
+}
+
+/// The (milliseconds, sequence) of a stream id, for ordering.
+fn parse_id(id: &str) -> (i64, u64) {
+    let (ms, seq) = id.split_once('-').unwrap_or((id, "0"));
+    (ms.parse().unwrap_or(0), seq.parse().unwrap_or(0))
+}
+
+fn read_parquet(path: &Path) -> Result<Vec<(String, i64, String)>> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?;
+    let mut rows = Vec::new();
+    for batch in reader {
+        let batch = batch?;
+        let column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .with_context(|| format!("missing column {}", name))
+        };
+        let ids = column("id")?
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .context("id is not a string")?;
+        let received = column("received_at_ms")?
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .context("received_at_ms is not an int64")?;
+        let events = column("event")?
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .context("event is not a string")?;
+        for i in 0..batch.num_rows() {
+            rows.push((
+                ids.value(i).to_string(),
+                received.value(i),
+                events.value(i).to_string(),
+            ));
+        }
+    }
+    Ok(rows)
+}
+
+fn read_jsonl(path: &Path) -> Result<Vec<(String, i64, String)>> {
+    #[derive(Deserialize)]
+    struct Line {
+        id: String,
+        received_at_ms: i64,
+        event: Value,
+    }
+    let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+    let mut rows = Vec::new();
+    for line in reader.lines() {
+        let line: Line = serde_json::from_str(&line?)?;
+        rows.push((line.id, line.received_at_ms, line.event.to_string()));
+    }
+    Ok(rows)
+}
@@ -0,0 +1,187 @@
+// executor/src/audit.rs
+//! `GET /api/v1/trades/{id}/audit`: the causal chain behind one trade. The
+//! trade row and its `trade_transitions` give the decision, the risk approval
+//! (whose note carries the approved quote), the signature, the confirmation
+//! and the exit; the streams add what the journal doesn't keep — the latest
+//! market events on the token before entry, the execution report and any
+//! close requests. Streams are only searched around the trade's lifetime, so
+//! entries already trimmed by Redis are simply missing from the answer.
+
+use crate::database::{Database, TradeJournal, TradeTransition};
+use anyhow::Result;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use redis::streams::StreamRangeReply;
+use redis::AsyncCommands;
+use serde_json::{json, Value};
+use shared_models::TradeState;
+use std::sync::Arc;
+use tracing::error;
+
+/// Streams searched for the last event on the token before entry.
+const TRIGGER_STREAMS: &[&str] = &[
+    "events:price",
+    "events:depth",
+    "events:social",
+    "events:onchain",
+    "events:liquidity_pool",
+    "events:whale_transfer",
+    "events:token_launch",
+];
+const TRIGGER_SCAN_LIMIT: usize = 2_000; // Entries read back from entry per stream
+const STREAM_SCAN_LIMIT: usize = 10_000; // Entries read over the trade's lifetime per stream
+const WINDOW_PADDING_MS: i64 = 60_000;
+
+#[derive(Clone)]
+pub struct AuditState {
+    pub db: Arc<Database>,
+    pub redis_client: redis::Client,
+}
+
+pub async fn trade_audit_handler(
+    State(state): State<AuditState>,
+    Path(trade_id): Path<i64>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let journal = match state.db.get_trade_journal(trade_id) {
+        Ok(Some(journal)) => journal,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("Trade {} not found", trade_id),
+            ))
+        }
+        Err(e) => {
+            error!(trade_id, error = %e, "Failed to read trade journal.");
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+        }
+    };
+    match assemble(&state.redis_client, journal).await {
+        Ok(audit) => Ok(Json(audit)),
+        Err(e) => {
+            error!(trade_id, error = %e, "Failed to assemble trade audit.");
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        }
+    }
+}
+
+/// The first transition into `state`, if the trade ever reached it.
+fn reached(transitions: &[TradeTransition], state: TradeState) -> Option<&TradeTransition> {
+    transitions.iter().find(|t| t.to_state == state.as_str())
+}
+
+async fn assemble(redis_client: &redis::Client, journal: TradeJournal) -> Result<Value> {
+    let mut conn = redis_client.get_multiplexed_async_connection().await?;
+    let trade = &journal.trade;
+    let entry_ms = trade.entry_time * 1000;
+    let end_ms = trade
+        .close_time
+        .map(|t| t * 1000)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis())
+        + WINDOW_PADDING_MS;
+    let start_ms = entry_ms - WINDOW_PADDING_MS;
+
+    let mut triggering_events = Vec::new();
+    for stream in TRIGGER_STREAMS {
+        let reply: StreamRangeReply = conn
+            .xrevrange_count(*stream, entry_ms, "-", TRIGGER_SCAN_LIMIT)
+            .await?;
+        let latest = reply.ids.iter().find_map(|entry| {
+            let event: Value = serde_json::from_str(&entry.get::<String>("event")?).ok()?;
+            (event.get("token_address").and_then(Value::as_str) == Some(&trade.token_address))
+                .then(|| json!({ "stream": stream, "id": entry.id, "event": event }))
+        });
+        triggering_events.extend(latest);
+    }
+
+    let execution_reports = matching_entries(
+        &mut conn,
+        "execution_reports",
+        "report",
+        trade.id,
+        start_ms,
+        end_ms,
+    )
+    .await?;
+    let close_requests = matching_entries(
+        &mut conn,
+        "position_close_requests",
+        "request",
+        trade.id,
+        start_ms,
+        end_ms,
+    )
+    .await?;
+
+    let transitions = &journal.transitions;
+    let risk_approved = reached(transitions, TradeState::RiskApproved);
+    let failed = reached(transitions, TradeState::Failed);
+    Ok(json!({
+        "trade_id": trade.id,
+        "status": trade.status,
+        "decision": {
+            "strategy_id": trade.strategy_id,
+            "token_address": trade.token_address,
+            "side": trade.side,
+            "confidence": trade.confidence,
+            "mode": trade.mode,
+            "at": trade.entry_time,
+            "triggering_features": journal.triggering_features,
+            "exit_profile": journal.exit_profile,
+        },
+        "triggering_events": triggering_events,
+        "risk_check": {
+            "approved": risk_approved.is_some(),
+            "at": risk_approved.map(|t| t.at),
+            "rejection": failed.filter(|_| risk_approved.is_none()).and_then(|t| t.note.clone()),
+        },
+        "quote": risk_approved
+            .and_then(|t| t.note.as_deref())
+            .and_then(|note| serde_json::from_str::<Value>(note).ok()),
+        "submission": {
+            "signature": trade.signature,
+            "at": reached(transitions, TradeState::Submitted).map(|t| t.at),
+        },
+        "confirmation": {
+            "at": reached(transitions, TradeState::Confirmed).map(|t| t.at),
+            "entry_price_usd": trade.entry_price_usd,
+            "filled_token_amount": journal.filled_token_amount,
+            "filled_amount_usd": journal.filled_amount_usd,
+        },
+        "execution_reports": execution_reports,
+        "exit": {
+            "close_requests": close_requests,
+            "closing_at": reached(transitions, TradeState::Closing).map(|t| t.at),
+            "closed_at": trade.close_time,
+            "close_price_usd": trade.close_price_usd,
+            "pnl_usd": trade.pnl_usd,
+        },
+        "failure": failed.map(|t| json!({ "at": t.at, "reason": t.note })),
+        "transitions": transitions,
+    }))
+}
+
+/// Entries of `stream` between the two times whose JSON `field` is about `trade_id`.
+async fn matching_entries(
+    conn: &mut redis::aio::MultiplexedConnection,
+    stream: &str,
+    field: &str,
+    trade_id: i64,
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<Value>> {
+    let reply: StreamRangeReply = conn
+        .xrange_count(stream, start_ms, end_ms, STREAM_SCAN_LIMIT)
+        .await?;
+    Ok(reply
+        .ids
+        .iter()
+        .filter_map(|entry| {
+            let payload: Value = serde_json::from_str(&entry.get::<String>(field)?).ok()?;
+            (payload.get("trade_id").and_then(Value::as_i64) == Some(trade_id))
+                .then(|| json!({ "id": entry.id, (field): payload }))
+        })
+        .collect())
+}
@@ -0,0 +1,506 @@
+// executor/src/bin/backtest/main.rs
+//! Replays the recorder's archives through strategies built from a
+//! StrategySpec file, using the same Strategy trait and registry the executor
+//! runs live, and simulates their fills (see `sim`). Writes a JSON and HTML
+//! report with each strategy's stats, equity curve and trades.
+//!
+//!     backtest --spec specs.json [--data /app/recorder] [--from 2024-05-01]
+//!              [--to 2024-05-07] [--out backtest_report] [--capital 10000]
+//!              [--fee-bps 25] [--slippage-bps 10] [--latency-ms 500] ...
+//!
+//! The spec file holds one StrategySpec or a list of them. Every strategy
+//! gets the full `--capital` as its budget and its own book.
+//!
+//! With `--sweep FILE` in place of `--spec`, a grid of params is searched
+//! instead (see `sweep`). The ranked results go to `<out>.json` and the
+//! `--top` configs to `--specs-out`, each one ready to publish as the `spec`
+//! of a strategy_registry_stream entry.
+//!
+//! `backtest optimize --strategy FAMILY [--space FILE]` searches a family's
+//! params with an optimizer instead of a grid (see `optimize`), writing its
+//! outputs the same way, and `backtest walkforward --strategy FAMILY
+//! --train-days N --test-days M` repeats that search over rolling windows to
+//! check the params hold up (see `walkforward`).
+//!
+//! `backtest parity --spec FILE [--db PATH]` replays the specs a live
+//! executor ran and diffs their decisions against its logged trades (see
+//! `parity`), failing if any diverge.
+
+#[path = "../../strategies/mod.rs"]
+mod strategies;
+
+#[path = "../../archive.rs"]
+mod data;
+mod optimize;
+mod parity;
+mod report;
+mod runner;
+mod sim;
+mod sweep;
+mod walkforward;
+
+use crate::data::Archive;
+use crate::optimize::{OptimizeOptions, SearchOptions, SearchSpace};
+use crate::parity::ParityOptions;
+use crate::report::Report;
+use crate::runner::Runner;
+use crate::sim::Settings;
+use crate::sweep::{SweepOptions, SweepSpec};
+use crate::walkforward::WalkForwardOptions;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use shared_models::StrategySpec;
+use std::path::{Path, PathBuf};
+use tracing::{info, level_filters::LevelFilter};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str =
+    "usage: backtest ([parity] --spec FILE | --sweep FILE | (optimize | walkforward) --strategy FAMILY [--space FILE]) [--data DIR] [--from YYYY-MM-DD] [--to YYYY-MM-DD] \
+[--out PATH] [--capital USD] [--fee-bps BPS] [--slippage-bps BPS] [--latency-ms MS] \
+[--max-position-usd USD] [--max-positions-per-token N] [--trailing-stop-pct PCT] \
+[--drawdown-limit-pct PCT] [--curve-interval-secs SECS] [--parallelism N]
+sweep, optimize and walkforward: [--seed N] [--specs-out PATH]
+sweep and optimize: [--oos-fraction F] [--top N]
+sweep only: [--samples N]
+optimize and walkforward: [--trials N] [--batch N] [--patience ROUNDS] [--min-improvement SHARPE]
+walkforward only: --train-days N --test-days N [--step-days N]
+parity only: [--db PATH] [--tolerance-secs SECS] [--size-tolerance-pct PCT]";
+
+struct Args {
+    spec: Option<PathBuf>,
+    sweep: Option<PathBuf>,
+    command: Option<String>, // optimize, walkforward or parity
+    strategy: Option<String>,
+    space: Option<PathBuf>,
+    data: PathBuf,
+    from: Option<String>,
+    to: Option<String>,
+    out: PathBuf,
+    parallelism: usize,
+    samples: Option<usize>,
+    seed: u64,
+    oos_fraction: f64,
+    top: usize,
+    specs_out: PathBuf,
+    trials: usize,
+    batch: Option<usize>,
+    patience: usize,
+    min_improvement: f64,
+    train_days: usize,
+    test_days: usize,
+    step_days: Option<usize>,
+    db: PathBuf,
+    tolerance_secs: i64,
+    size_tolerance_pct: f64,
+    settings: Settings,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        spec: None,
+        sweep: None,
+        command: None,
+        strategy: None,
+        space: None,
+        data: PathBuf::from(
+            std::env::var("RECORDER_DIR").unwrap_or_else(|_| "/app/recorder".to_string()),
+        ),
+        from: None,
+        to: None,
+        out: PathBuf::from("backtest_report"),
+        parallelism: num_cpus::get(),
+        samples: None,
+        seed: 42,
+        oos_fraction: 0.3,
+        top: 5,
+        specs_out: PathBuf::from("top_specs.json"),
+        trials: 100,
+        batch: None,
+        patience: 5,
+        min_improvement: 0.01,
+        train_days: 0,
+        test_days: 0,
+        step_days: None,
+        db: PathBuf::from(
+            std::env::var("DATABASE_PATH")
+                .unwrap_or_else(|_| "/app/shared/trades_v18.db".to_string()),
+        ),
+        tolerance_secs: 60,
+        size_tolerance_pct: 1.0,
+        settings: Settings {
+            capital_usd: 10_000.0,
+            fee_bps: 25.0,
+            slippage_bps: 10.0,
+            latency_ms: 500,
+            max_position_usd: None,
+            max_positions_per_token: 1,
+            trailing_stop_pct: 15.0,
+            drawdown_limit_pct: 25.0,
+            curve_interval_ms: 300_000,
+        },
+    };
+    let mut iter = std::env::args().skip(1).peekable();
+    if let Some(command) =
+        iter.next_if(|arg| arg == "optimize" || arg == "walkforward" || arg == "parity")
+    {
+        args.command = Some(command);
+    }
+    while let Some(flag) = iter.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        let value = iter
+            .next()
+            .ok_or_else(|| anyhow!("{} needs a value\n{}", flag, USAGE))?;
+        let number = || -> Result<f64> {
+            value
+                .parse()
+                .with_context(|| format!("{} must be a number", flag))
+        };
+        match flag.as_str() {
+            "--spec" => args.spec = Some(PathBuf::from(&value)),
+            "--sweep" => args.sweep = Some(PathBuf::from(&value)),
+            "--strategy" => args.strategy = Some(value.clone()),
+            "--space" => args.space = Some(PathBuf::from(&value)),
+            "--trials" => args.trials = number()? as usize,
+            "--batch" => args.batch = Some(number()? as usize),
+            "--patience" => args.patience = number()? as usize,
+            "--min-improvement" => args.min_improvement = number()?,
+            "--train-days" => args.train_days = number()? as usize,
+            "--test-days" => args.test_days = number()? as usize,
+            "--step-days" => args.step_days = Some(number()? as usize),
+            "--db" => args.db = PathBuf::from(&value),
+            "--tolerance-secs" => args.tolerance_secs = number()? as i64,
+            "--size-tolerance-pct" => args.size_tolerance_pct = number()?,
+            "--data" => args.data = PathBuf::from(&value),
+            "--from" => args.from = Some(value.clone()),
+            "--to" => args.to = Some(value.clone()),
+            "--out" => args.out = PathBuf::from(&value),
+            "--parallelism" => args.parallelism = number()? as usize,
+            "--samples" => args.samples = Some(number()? as usize),
+            "--seed" => args.seed = number()? as u64,
+            "--oos-fraction" => args.oos_fraction = number()?,
+            "--top" => args.top = number()? as usize,
+            "--specs-out" => args.specs_out = PathBuf::from(&value),
+            "--capital" => args.settings.capital_usd = number()?,
+            "--fee-bps" => args.settings.fee_bps = number()?,
+            "--slippage-bps" => args.settings.slippage_bps = number()?,
+            "--latency-ms" => args.settings.latency_ms = number()? as i64,
+            "--max-position-usd" => args.settings.max_position_usd = Some(number()?),
+            "--max-positions-per-token" => {
+                args.settings.max_positions_per_token = number()? as usize
+            }
+            "--trailing-stop-pct" => args.settings.trailing_stop_pct = number()?,
+            "--drawdown-limit-pct" => args.settings.drawdown_limit_pct = number()?,
+            "--curve-interval-secs" => {
+                args.settings.curve_interval_ms = (number()? * 1000.0) as i64
+            }
+            _ => bail!("unknown flag {}\n{}", flag, USAGE),
+        }
+    }
+    if args.command.as_deref() == Some("parity") {
+        if args.spec.is_none() || args.sweep.is_some() || args.strategy.is_some() {
+            bail!(
+                "parity takes --spec and no --sweep or --strategy\n{}",
+                USAGE
+            );
+        }
+    } else if let Some(command) = &args.command {
+        if args.strategy.is_none() || args.spec.is_some() || args.sweep.is_some() {
+            bail!(
+                "{} takes --strategy and no --spec or --sweep\n{}",
+                command,
+                USAGE
+            );
+        }
+        if args.trials == 0 {
+            bail!("--trials must be positive");
+        }
+        if command == "walkforward" && (args.train_days == 0 || args.test_days == 0) {
+            bail!("walkforward needs --train-days and --test-days\n{}", USAGE);
+        }
+    } else if args.spec.is_some() == args.sweep.is_some() {
+        bail!("exactly one of --spec and --sweep is required\n{}", USAGE);
+    }
+    if args.settings.capital_usd <= 0.0 {
+        bail!("--capital must be positive");
+    }
+    if !(args.oos_fraction > 0.0 && args.oos_fraction < 1.0) {
+        bail!("--oos-fraction must be between 0 and 1");
+    }
+    Ok(args)
+}
+
+fn load_specs(path: &Path) -> Result<Vec<StrategySpec>> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let value: Value = serde_json::from_str(&text)?;
+    Ok(match value {
+        Value::Array(_) => serde_json::from_value(value)?,
+        _ => vec![serde_json::from_value(value)?],
+    })
+}
+
+async fn backtest(args: &Args, spec_path: &Path) -> Result<()> {
+    let mut runners = Vec::new();
+    for spec in load_specs(spec_path)? {
+        runners.push(Runner::new(spec, &args.settings).await?);
+    }
+    if runners.is_empty() {
+        bail!("{} holds no specs", spec_path.display());
+    }
+
+    let archive = Archive::new(&args.data, &runner::event_types(&runners));
+    let days = archive.days(args.from.as_deref(), args.to.as_deref())?;
+    if days.is_empty() {
+        bail!(
+            "no archived events in {} for the requested days",
+            args.data.display()
+        );
+    }
+    info!(
+        "🧪 Backtesting {} strategies over {} days ({} to {})...",
+        runners.len(),
+        days.len(),
+        days[0],
+        days[days.len() - 1]
+    );
+
+    let (strategies, events_replayed) =
+        runner::replay(&archive, &days, runners, &args.settings, args.parallelism).await?;
+    for strategy in &strategies {
+        info!(
+            strategy = %strategy.id,
+            trades = strategy.stats.trades,
+            net_pnl_usd = strategy.stats.net_pnl_usd,
+            return_pct = strategy.stats.return_pct,
+            max_drawdown_pct = strategy.stats.max_drawdown_pct,
+            "Strategy finished."
+        );
+    }
+    let report = Report {
+        generated_at: chrono::Utc::now().timestamp(),
+        from: args.from.clone(),
+        to: args.to.clone(),
+        days,
+        events_replayed,
+        settings: serde_json::to_value(&args.settings)?,
+        strategies,
+    };
+    report.write(&args.out)?;
+    info!(
+        "📄 Wrote {} and {}",
+        args.out.with_extension("json").display(),
+        args.out.with_extension("html").display()
+    );
+    Ok(())
+}
+
+async fn parity_check(args: &Args, spec_path: &Path) -> Result<()> {
+    let specs = load_specs(spec_path)?;
+    if specs.is_empty() {
+        bail!("{} holds no specs", spec_path.display());
+    }
+    let report = parity::run(
+        specs,
+        &args.data,
+        args.from.as_deref(),
+        args.to.as_deref(),
+        ParityOptions {
+            db: &args.db,
+            tolerance_secs: args.tolerance_secs,
+            size_tolerance_pct: args.size_tolerance_pct,
+            parallelism: args.parallelism,
+            settings: &args.settings,
+        },
+    )
+    .await?;
+    for strategy in &report.strategies {
+        info!(
+            strategy = %strategy.id,
+            live_trades = strategy.live_trades,
+            replay_decisions = strategy.replay_decisions,
+            matched = strategy.matched,
+            divergences = strategy.divergences.len(),
+            parity_pct = strategy.parity_pct,
+            "Parity checked."
+        );
+    }
+
+    let out = args.out.with_extension("json");
+    std::fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+    info!("📄 Wrote {}", out.display());
+    let divergences = report.divergences();
+    if divergences > 0 {
+        bail!(
+            "{} divergences between replay and live (see {})",
+            divergences,
+            out.display()
+        );
+    }
+    Ok(())
+}
+
+async fn param_sweep(args: &Args, sweep_path: &Path) -> Result<()> {
+    let sweep = SweepSpec::load(sweep_path)?;
+    let (report, top) = sweep::run(
+        &sweep,
+        &args.data,
+        args.from.as_deref(),
+        args.to.as_deref(),
+        SweepOptions {
+            samples: args.samples,
+            seed: args.seed,
+            oos_fraction: args.oos_fraction,
+            top: args.top,
+            parallelism: args.parallelism,
+            settings: &args.settings,
+        },
+    )
+    .await?;
+    for result in report.results.iter().take(args.top) {
+        info!(
+            rank = result.rank,
+            id = %result.id,
+            params = %result.params,
+            oos_sharpe = result.out_of_sample.sharpe_ratio,
+            oos_return_pct = result.out_of_sample.return_pct,
+            in_sample_sharpe = result.in_sample.sharpe_ratio,
+            "Top config."
+        );
+    }
+
+    let out = args.out.with_extension("json");
+    std::fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+    std::fs::write(&args.specs_out, serde_json::to_string_pretty(&top)?)?;
+    info!(
+        "📄 Wrote {} and the top {} specs to {} (publish each as the `spec` of a strategy_registry_stream entry)",
+        out.display(),
+        top.len(),
+        args.specs_out.display()
+    );
+    Ok(())
+}
+
+fn search_space(args: &Args) -> Result<SearchSpace> {
+    Ok(match &args.space {
+        Some(path) => SearchSpace::load(path)?,
+        None => SearchSpace::default(),
+    })
+}
+
+fn search_options(args: &Args) -> SearchOptions<'_> {
+    SearchOptions {
+        trials: args.trials,
+        batch: args.batch.unwrap_or(args.parallelism),
+        patience: args.patience,
+        min_improvement: args.min_improvement,
+        seed: args.seed,
+        parallelism: args.parallelism,
+        settings: &args.settings,
+    }
+}
+
+async fn optimize_params(args: &Args, family: &str) -> Result<()> {
+    let space = search_space(args)?;
+    let (report, top) = optimize::run(
+        family,
+        &space,
+        &args.data,
+        args.from.as_deref(),
+        args.to.as_deref(),
+        OptimizeOptions {
+            search: search_options(args),
+            oos_fraction: args.oos_fraction,
+            top: args.top,
+        },
+    )
+    .await?;
+    for result in report.results.iter().take(args.top) {
+        info!(
+            rank = result.rank,
+            id = %result.id,
+            params = %result.params,
+            oos_sharpe = result.out_of_sample.sharpe_ratio,
+            oos_return_pct = result.out_of_sample.return_pct,
+            in_sample_sharpe = result.in_sample.sharpe_ratio,
+            "Top config."
+        );
+    }
+
+    let out = args.out.with_extension("json");
+    std::fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+    std::fs::write(&args.specs_out, serde_json::to_string_pretty(&top)?)?;
+    info!(
+        "📄 Wrote {} after {} trials and the top {} specs to {} (publish each as the `spec` of a strategy_registry_stream entry)",
+        out.display(),
+        report.trials,
+        top.len(),
+        args.specs_out.display()
+    );
+    Ok(())
+}
+
+async fn walk_forward(args: &Args, family: &str) -> Result<()> {
+    let space = search_space(args)?;
+    let (report, latest) = walkforward::run(
+        family,
+        &space,
+        &args.data,
+        args.from.as_deref(),
+        args.to.as_deref(),
+        WalkForwardOptions {
+            search: search_options(args),
+            train_days: args.train_days,
+            test_days: args.test_days,
+            step_days: args.step_days.unwrap_or(args.test_days),
+        },
+    )
+    .await?;
+    let stability = &report.stability;
+    info!(
+        windows = stability.windows,
+        profitable_windows = stability.profitable_windows,
+        mean_train_sharpe = stability.mean_train_sharpe,
+        mean_test_sharpe = stability.mean_test_sharpe,
+        efficiency = ?stability.efficiency,
+        param_spread = stability.param_spread,
+        score = stability.score,
+        "Walk-forward finished."
+    );
+
+    let out = args.out.with_extension("json");
+    std::fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+    std::fs::write(&args.specs_out, serde_json::to_string_pretty(&[latest])?)?;
+    info!(
+        "📄 Wrote {} and the latest window's spec to {}",
+        out.display(),
+        args.specs_out.display()
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+
+    let args = parse_args()?;
+    match (&args.strategy, &args.spec, &args.sweep) {
+        (Some(family), _, _) if args.command.as_deref() == Some("optimize") => {
+            optimize_params(&args, family).await
+        }
+        (Some(family), _, _) if args.command.as_deref() == Some("walkforward") => {
+            walk_forward(&args, family).await
+        }
+        (_, Some(spec), _) if args.command.as_deref() == Some("parity") => {
+            parity_check(&args, spec).await
+        }
+        (_, Some(spec), _) => backtest(&args, spec).await,
+        (_, _, Some(sweep)) => param_sweep(&args, sweep).await,
+        _ => unreachable!("parse_args requires a mode"),
+    }
+}
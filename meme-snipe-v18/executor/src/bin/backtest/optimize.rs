@@ -0,0 +1,544 @@
+// executor/src/bin/backtest/optimize.rs
+//! Param optimization with a Tree-structured Parzen Estimator (TPE), for
+//! spaces too large to sweep. The search space is every Integer or Number
+//! param given a `min` and `max`, by the space file or else by the family's
+//! param schema:
+//!
+//!     {"id_prefix": "mom", "params": {"max_tokens": 200},
+//!      "space": {"lookback": {"min": 3, "max": 60},
+//!                "vol_multiplier": {"min": 0.5, "max": 10, "log": true}}}
+//!
+//! The first trials sample the space uniformly. After that each proposal is
+//! the candidate most likely under the best quarter of trials so far relative
+//! to the rest. Trials are scored by in-sample Sharpe and run `--batch` at a
+//! time in parallel, until `--trials` have run or `--patience` rounds pass
+//! without the best score improving by `--min-improvement`. Every distinct
+//! trial is then re-run out of sample and ranked as in a sweep.
+
+use crate::data::Archive;
+use crate::report::Stats;
+use crate::runner::{self, Runner};
+use crate::sim::Settings;
+use crate::strategies;
+use crate::sweep::{self, SweepResult};
+use anyhow::{anyhow, bail, Context, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use shared_models::{ParamKind, ParamSchema, StrategySpec};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tracing::info;
+
+const MIN_STARTUP_TRIALS: usize = 10; // Uniform samples before the model is trusted
+const GOOD_FRACTION: f64 = 0.25;
+const CANDIDATES: usize = 24; // Drawn per proposal, the most promising kept
+const MIN_BANDWIDTH: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Bounds {
+    pub min: f64,
+    pub max: f64,
+    #[serde(default)]
+    pub log: bool, // Search on a log scale, for params spanning magnitudes
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchSpace {
+    #[serde(default)]
+    pub id_prefix: Option<String>,
+    #[serde(default)]
+    pub params: Value, // Fixed params shared by every trial
+    #[serde(default)]
+    pub space: BTreeMap<String, Bounds>,
+    #[serde(default)]
+    pub token_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    pub token_denylist: Vec<String>,
+    #[serde(default)]
+    pub max_slippage_bps: Option<u16>,
+}
+
+impl SearchSpace {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+/// One searched param, mapped from the unit interval.
+struct Dim {
+    name: String,
+    integer: bool,
+    bounds: Bounds,
+}
+
+impl Dim {
+    fn value(&self, u: f64) -> Value {
+        let Bounds { min, max, log } = self.bounds;
+        let u = u.clamp(0.0, 1.0);
+        let x = if log {
+            (min.ln() + u * (max.ln() - min.ln())).exp()
+        } else {
+            min + u * (max - min)
+        };
+        if self.integer {
+            Value::from(x.round().clamp(min.ceil(), max.floor()) as i64)
+        } else {
+            Value::from((x * 1e9).round() / 1e9)
+        }
+    }
+
+    /// Where `x` sits in the unit interval; the inverse of `value`.
+    fn unit(&self, x: f64) -> f64 {
+        let Bounds { min, max, log } = self.bounds;
+        let u = if log {
+            (x.ln() - min.ln()) / (max.ln() - min.ln())
+        } else {
+            (x - min) / (max - min)
+        };
+        u.clamp(0.0, 1.0)
+    }
+}
+
+fn dims(family: &str, schema: &ParamSchema, space: &SearchSpace) -> Result<Vec<Dim>> {
+    for name in space.space.keys() {
+        if !schema.fields.iter().any(|f| &f.name == name) {
+            bail!("{} has no param {}", family, name);
+        }
+    }
+    let mut dims = Vec::new();
+    for field in &schema.fields {
+        let integer = match field.kind {
+            ParamKind::Integer => true,
+            ParamKind::Number => false,
+            _ if space.space.contains_key(&field.name) => bail!(
+                "param {} is {:?}; only Integer and Number params can be searched",
+                field.name,
+                field.kind
+            ),
+            _ => continue,
+        };
+        let bounds = match (space.space.get(&field.name), field.min, field.max) {
+            (Some(bounds), _, _) => *bounds,
+            (None, Some(min), Some(max)) => Bounds {
+                min,
+                max,
+                log: false,
+            },
+            _ => continue,
+        };
+        if bounds.max <= bounds.min || (bounds.log && bounds.min <= 0.0) {
+            bail!(
+                "param {}: max must exceed min, and a log scale needs a positive min",
+                field.name
+            );
+        }
+        if integer && bounds.min.ceil() > bounds.max.floor() {
+            bail!("param {}: no integer between min and max", field.name);
+        }
+        dims.push(Dim {
+            name: field.name.clone(),
+            integer,
+            bounds,
+        });
+    }
+    if dims.is_empty() {
+        bail!(
+            "nothing to search: give {}'s Integer or Number params a min and max in a --space file",
+            family
+        );
+    }
+    Ok(dims)
+}
+
+/// One-dimensional Parzen estimator: a Gaussian around each point, plus a
+/// uniform prior over the unit interval weighted as one more point.
+struct Parzen {
+    points: Vec<f64>,
+    bandwidth: f64,
+}
+
+impl Parzen {
+    fn new(points: Vec<f64>) -> Self {
+        let n = points.len() as f64;
+        let bandwidth = if points.len() < 2 {
+            0.25
+        } else {
+            let mean = points.iter().sum::<f64>() / n;
+            let std = (points.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n).sqrt();
+            (1.06 * std * n.powf(-0.2)).clamp(MIN_BANDWIDTH, 1.0)
+        };
+        Self { points, bandwidth }
+    }
+
+    fn density(&self, x: f64) -> f64 {
+        let kernels: f64 = self
+            .points
+            .iter()
+            .map(|p| {
+                let z = (x - p) / self.bandwidth;
+                (-0.5 * z * z).exp() / (self.bandwidth * (2.0 * std::f64::consts::PI).sqrt())
+            })
+            .sum();
+        (1.0 + kernels) / (1.0 + self.points.len() as f64)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let pick = rng.gen_range(0..=self.points.len());
+        if pick == self.points.len() {
+            return rng.gen();
+        }
+        (self.points[pick] + self.bandwidth * standard_normal(rng)).clamp(0.0, 1.0)
+    }
+}
+
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // (0, 1], so the log is finite
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Trials so far as points in the unit cube, with their scores.
+struct Tpe {
+    dims: usize,
+    startup: usize,
+    observed: Vec<(Vec<f64>, f64)>,
+    rng: StdRng,
+}
+
+impl Tpe {
+    fn propose(&mut self) -> Vec<f64> {
+        if self.observed.len() < self.startup {
+            return (0..self.dims).map(|_| self.rng.gen()).collect();
+        }
+        let mut sorted: Vec<&(Vec<f64>, f64)> = self.observed.iter().collect();
+        sorted.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let good_count = ((sorted.len() as f64 * GOOD_FRACTION).ceil() as usize).max(1);
+        let (good, bad) = sorted.split_at(good_count);
+        let per_dim = |trials: &[&(Vec<f64>, f64)]| -> Vec<Parzen> {
+            (0..self.dims)
+                .map(|d| Parzen::new(trials.iter().map(|(point, _)| point[d]).collect()))
+                .collect()
+        };
+        let (good, bad) = (per_dim(good), per_dim(bad));
+
+        let mut best = (f64::NEG_INFINITY, Vec::new());
+        for _ in 0..CANDIDATES {
+            let point: Vec<f64> = good.iter().map(|l| l.sample(&mut self.rng)).collect();
+            let ratio: f64 = point
+                .iter()
+                .zip(good.iter().zip(&bad))
+                .map(|(x, (l, g))| l.density(*x).ln() - g.density(*x).ln())
+                .sum();
+            if ratio > best.0 {
+                best = (ratio, point);
+            }
+        }
+        best.1
+    }
+
+    fn observe(&mut self, point: Vec<f64>, score: f64) {
+        self.observed.push((point, score));
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Trial {
+    pub trial: usize,
+    pub round: usize,
+    pub id: String,
+    pub params: Value,
+    pub in_sample_sharpe: Option<f64>, // None if the params failed the schema
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptimizeReport {
+    pub generated_at: i64,
+    pub family: String,
+    pub trials: usize,
+    pub rounds: usize,
+    pub stopped_early: bool,
+    pub in_sample_days: Vec<String>,
+    pub out_of_sample_days: Vec<String>,
+    pub settings: Value,
+    pub history: Vec<Trial>,
+    pub results: Vec<SweepResult>,
+}
+
+/// How a search runs; shared by `optimize` and `walkforward`.
+pub struct SearchOptions<'a> {
+    pub trials: usize,
+    pub batch: usize,
+    pub patience: usize, // Rounds without improvement before stopping; 0 never stops early
+    pub min_improvement: f64,
+    pub seed: u64,
+    pub parallelism: usize,
+    pub settings: &'a Settings,
+}
+
+/// The outcome of one search: every trial, and the stats of each distinct
+/// set of params that ran.
+pub struct Trials {
+    pub history: Vec<Trial>,
+    pub candidates: Vec<(StrategySpec, Stats)>,
+    pub rounds: usize,
+    pub stopped_early: bool,
+}
+
+/// A family's search space, resolved against its param schema.
+pub struct Search<'a> {
+    family: String,
+    space: &'a SearchSpace,
+    dims: Vec<Dim>,
+    base: Map<String, Value>,
+}
+
+impl<'a> Search<'a> {
+    pub fn new(family: &str, space: &'a SearchSpace) -> Result<Self> {
+        let schema = strategies::build_strategy(family)
+            .ok_or_else(|| anyhow!("unknown strategy family {}", family))?
+            .params_schema();
+        let base = match &space.params {
+            Value::Object(base) => base.clone(),
+            Value::Null => Map::new(),
+            _ => bail!("search space params must be an object"),
+        };
+        Ok(Self {
+            dims: dims(family, &schema, space)?,
+            family: family.to_string(),
+            space,
+            base,
+        })
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims.len()
+    }
+
+    /// `<id_prefix><label>_t<trial>`, the prefix defaulting to the family.
+    fn spec(&self, label: &str, trial: usize, point: &[f64]) -> StrategySpec {
+        let mut params = self.base.clone();
+        for (dim, u) in self.dims.iter().zip(point) {
+            params.insert(dim.name.clone(), dim.value(*u));
+        }
+        StrategySpec {
+            id: format!(
+                "{}{}_t{}",
+                self.space.id_prefix.as_deref().unwrap_or(&self.family),
+                label,
+                trial
+            ),
+            family: self.family.clone(),
+            params: Value::Object(params),
+            token_allowlist: self.space.token_allowlist.clone(),
+            token_denylist: self.space.token_denylist.clone(),
+            max_slippage_bps: self.space.max_slippage_bps,
+            challenger_params: None,
+            variant_of: None,
+        }
+    }
+
+    /// How much a set of chosen params disagree: the mean over searched
+    /// params of their standard deviation on the unit interval, 0 to 0.5.
+    pub fn spread(&self, chosen: &[&Value]) -> f64 {
+        if chosen.len() < 2 {
+            return 0.0;
+        }
+        let spreads = self.dims.iter().map(|dim| {
+            let units: Vec<f64> = chosen
+                .iter()
+                .filter_map(|params| params.get(&dim.name)?.as_f64())
+                .map(|x| dim.unit(x))
+                .collect();
+            let n = units.len().max(1) as f64;
+            let mean = units.iter().sum::<f64>() / n;
+            (units.iter().map(|u| (u - mean).powi(2)).sum::<f64>() / n).sqrt()
+        });
+        spreads.sum::<f64>() / self.dims.len() as f64
+    }
+
+    /// The archive holding the event types the family needs. Building the
+    /// centre of the space first shows early whether the fixed params fill in
+    /// every required param.
+    pub async fn archive(&self, data: &Path, settings: &Settings) -> Result<Archive> {
+        let probe = Runner::new(self.spec("", 0, &vec![0.5; self.dims.len()]), settings)
+            .await
+            .context("checking the centre of the search space")?;
+        Ok(Archive::new(data, &runner::event_types([&probe])))
+    }
+
+    /// Searches over `days`, scoring trials by Sharpe; `label` tells the spec
+    /// ids of separate searches apart.
+    pub async fn run(
+        &self,
+        archive: &Archive,
+        days: &[String],
+        label: &str,
+        options: &SearchOptions<'_>,
+    ) -> Result<Trials> {
+        let batch = options.batch.max(1);
+        let mut tpe = Tpe {
+            dims: self.dims.len(),
+            startup: MIN_STARTUP_TRIALS.max(batch),
+            observed: Vec::new(),
+            rng: StdRng::seed_from_u64(options.seed),
+        };
+        let mut history: Vec<Trial> = Vec::new();
+        let mut candidates: Vec<(StrategySpec, Stats)> = Vec::new();
+        // Integer params make repeats likely; each distinct set of params runs once
+        let mut scores: HashMap<String, Option<f64>> = HashMap::new();
+        let (mut best, mut stale, mut rounds, mut stopped_early) = (None::<f64>, 0, 0, false);
+        while history.len() < options.trials {
+            rounds += 1;
+            let round: Vec<(Vec<f64>, StrategySpec)> = (0..batch
+                .min(options.trials - history.len()))
+                .map(|i| {
+                    let point = tpe.propose();
+                    let spec = self.spec(label, history.len() + i + 1, &point);
+                    (point, spec)
+                })
+                .collect();
+            let mut fresh: Vec<StrategySpec> = Vec::new();
+            for (_, spec) in &round {
+                if !scores.contains_key(&spec.params.to_string())
+                    && !fresh.iter().any(|s| s.params == spec.params)
+                {
+                    fresh.push(spec.clone());
+                }
+            }
+
+            let runners = sweep::runners(&fresh, options.settings).await;
+            for spec in &fresh {
+                scores.insert(spec.params.to_string(), None);
+            }
+            if !runners.is_empty() {
+                let (reports, _) = runner::replay(
+                    archive,
+                    days,
+                    runners,
+                    options.settings,
+                    options.parallelism,
+                )
+                .await?;
+                for report in reports {
+                    scores.insert(report.params.to_string(), Some(report.stats.sharpe_ratio));
+                    if let Some(spec) = fresh.iter().find(|s| s.id == report.id) {
+                        candidates.push((spec.clone(), report.stats));
+                    }
+                }
+            }
+
+            for (point, spec) in round {
+                let score = scores[&spec.params.to_string()];
+                tpe.observe(point, score.unwrap_or(f64::NEG_INFINITY));
+                history.push(Trial {
+                    trial: history.len() + 1,
+                    round: rounds,
+                    id: spec.id,
+                    params: spec.params,
+                    in_sample_sharpe: score,
+                });
+            }
+
+            let round_best = history
+                .iter()
+                .filter_map(|t| t.in_sample_sharpe)
+                .max_by(f64::total_cmp);
+            match (round_best, best) {
+                (Some(score), None) => best = Some(score),
+                (Some(score), Some(previous)) if score > previous + options.min_improvement => {
+                    best = Some(score);
+                    stale = 0;
+                }
+                _ if history.len() > tpe.startup => stale += 1,
+                _ => {}
+            }
+            info!(
+                round = rounds,
+                trials = history.len(),
+                best_in_sample_sharpe = ?best,
+                "Optimization round finished."
+            );
+            if options.patience > 0 && stale >= options.patience {
+                info!(
+                    "⏹️ No improvement in {} rounds; stopping early.",
+                    options.patience
+                );
+                stopped_early = true;
+                break;
+            }
+        }
+        if candidates.is_empty() {
+            bail!("no trial passed {}'s param schema", self.family);
+        }
+        Ok(Trials {
+            history,
+            candidates,
+            rounds,
+            stopped_early,
+        })
+    }
+}
+
+pub struct OptimizeOptions<'a> {
+    pub search: SearchOptions<'a>,
+    pub oos_fraction: f64,
+    pub top: usize,
+}
+
+/// Optimizes `family`'s params over `space`; returns the report and the top
+/// specs by out-of-sample Sharpe.
+pub async fn run(
+    family: &str,
+    space: &SearchSpace,
+    data: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+    options: OptimizeOptions<'_>,
+) -> Result<(OptimizeReport, Vec<StrategySpec>)> {
+    let search = Search::new(family, space)?;
+    let settings = options.search.settings;
+    let archive = search.archive(data, settings).await?;
+    let days = archive.days(from, to)?;
+    let (is_days, oos_days) = sweep::split_days(&days, options.oos_fraction)?;
+    info!(
+        "🎯 Optimizing {} over {} params, up to {} trials {} at a time: {} days in sample ({} to {}), {} out ({} to {})...",
+        family,
+        search.dims(),
+        options.search.trials,
+        options.search.batch.max(1),
+        is_days.len(),
+        is_days[0],
+        is_days[is_days.len() - 1],
+        oos_days.len(),
+        oos_days[0],
+        oos_days[oos_days.len() - 1]
+    );
+
+    let trials = search.run(&archive, is_days, "", &options.search).await?;
+    let (results, top) = sweep::rank_out_of_sample(
+        &archive,
+        trials.candidates,
+        oos_days,
+        options.top,
+        settings,
+        options.search.parallelism,
+    )
+    .await?;
+    let report = OptimizeReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        family: family.to_string(),
+        trials: trials.history.len(),
+        rounds: trials.rounds,
+        stopped_early: trials.stopped_early,
+        in_sample_days: is_days.to_vec(),
+        out_of_sample_days: oos_days.to_vec(),
+        settings: serde_json::to_value(settings)?,
+        history: trials.history,
+        results,
+    };
+    Ok((report, top))
+}
@@ -0,0 +1,268 @@
+// executor/src/bin/backtest/parity.rs
+//! Backtest-vs-live parity: replays the archived days through a fresh
+//! instance of each spec, exactly as a backtest would, and lines the entries
+//! it asks for up against the trades the live executor logged for the same
+//! strategy id. A live trade is matched by the nearest unmatched replayed
+//! decision in the same token and side within `--tolerance-secs`; sizes more
+//! than `--size-tolerance-pct` apart are flagged too. Anything unmatched is a
+//! divergence: a live trade the replay never asked for (`missed`) or a
+//! replayed decision with no live trade (`extra`). Expect some `extra`s from
+//! orders the executor's risk gates refused before logging, and divergence
+//! around restarts, where the live instance had warm-up and restored state.
+
+use crate::data::Archive;
+use crate::report::Decision;
+use crate::runner::{self, Runner};
+use crate::sim::Settings;
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::Serialize;
+use shared_models::StrategySpec;
+use std::path::Path;
+use tracing::{info, warn};
+
+pub struct ParityOptions<'a> {
+    pub db: &'a Path,
+    pub tolerance_secs: i64,
+    pub size_tolerance_pct: f64,
+    pub parallelism: usize,
+    pub settings: &'a Settings,
+}
+
+/// An entry the live executor logged.
+#[derive(Debug, Serialize)]
+pub struct LiveTrade {
+    pub trade_id: i64,
+    pub at_ms: i64,
+    pub token_address: String,
+    pub side: String,
+    pub size_usd: f64,
+    pub status: String,
+    pub mode: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceKind {
+    Missed, // Live traded, the replay didn't ask to
+    Extra,  // The replay asked to, live never traded
+    Size,   // Matched, but the sizes disagree
+}
+
+#[derive(Debug, Serialize)]
+pub struct Divergence {
+    pub kind: DivergenceKind,
+    pub token_address: String,
+    pub side: String,
+    pub trade_id: Option<i64>,
+    pub live_at_ms: Option<i64>,
+    pub replay_at_ms: Option<i64>,
+    pub live_size_usd: Option<f64>,
+    pub replay_size_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrategyParity {
+    pub id: String,
+    pub live_trades: usize,
+    pub replay_decisions: usize,
+    pub matched: usize,
+    pub parity_pct: f64, // Matched at the right size, over the larger of the two counts
+    pub divergences: Vec<Divergence>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParityReport {
+    pub generated_at: i64,
+    pub days: Vec<String>,
+    pub tolerance_secs: i64,
+    pub size_tolerance_pct: f64,
+    pub strategies: Vec<StrategyParity>,
+}
+
+impl ParityReport {
+    pub fn divergences(&self) -> usize {
+        self.strategies.iter().map(|s| s.divergences.len()).sum()
+    }
+}
+
+/// Replays the days between `from` and `to` through `specs` and diffs each
+/// against its live trades.
+pub async fn run(
+    specs: Vec<StrategySpec>,
+    data: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+    options: ParityOptions<'_>,
+) -> Result<ParityReport> {
+    let mut runners = Vec::new();
+    for spec in specs {
+        runners.push(
+            Runner::new(spec, options.settings)
+                .await?
+                .record_decisions(),
+        );
+    }
+    let archive = Archive::new(data, &runner::event_types(&runners));
+    let days = archive.days(from, to)?;
+    if days.is_empty() {
+        bail!(
+            "no archived events in {} for the requested days",
+            data.display()
+        );
+    }
+    let start = day_start(&days[0])?;
+    let end = day_start(&days[days.len() - 1])? + 86_400;
+
+    let db = Connection::open_with_flags(options.db, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("opening {}", options.db.display()))?;
+    let mut live = Vec::with_capacity(runners.len());
+    for runner in &runners {
+        live.push(live_trades(&db, &runner.spec.id, start, end)?);
+    }
+    info!(
+        "🔍 Checking parity of {} strategies over {} days ({} to {}) against {} live trades...",
+        runners.len(),
+        days.len(),
+        days[0],
+        days[days.len() - 1],
+        live.iter().map(Vec::len).sum::<usize>()
+    );
+
+    let (reports, _) = runner::replay(
+        &archive,
+        &days,
+        runners,
+        options.settings,
+        options.parallelism,
+    )
+    .await?;
+    let strategies = reports
+        .into_iter()
+        .zip(live)
+        .map(|(report, live)| compare(report.id, live, report.decisions, &options))
+        .collect();
+    Ok(ParityReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        days,
+        tolerance_secs: options.tolerance_secs,
+        size_tolerance_pct: options.size_tolerance_pct,
+        strategies,
+    })
+}
+
+fn day_start(day: &str) -> Result<i64> {
+    Ok(NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .with_context(|| format!("bad archive day {}", day))?
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+        .timestamp())
+}
+
+fn live_trades(db: &Connection, strategy_id: &str, start: i64, end: i64) -> Result<Vec<LiveTrade>> {
+    let mut stmt = db.prepare(
+        "SELECT id, entry_time, token_address, side, amount_usd, status, mode FROM trades \
+         WHERE strategy_id = ?1 AND entry_time >= ?2 AND entry_time < ?3 ORDER BY entry_time",
+    )?;
+    let trades = stmt
+        .query_map(params![strategy_id, start, end], |row| {
+            Ok(LiveTrade {
+                trade_id: row.get(0)?,
+                at_ms: row.get::<_, i64>(1)? * 1000,
+                token_address: row.get(2)?,
+                side: row.get(3)?,
+                size_usd: row.get(4)?,
+                status: row.get(5)?,
+                mode: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(trades)
+}
+
+fn compare(
+    id: String,
+    live: Vec<LiveTrade>,
+    decisions: Vec<Decision>,
+    options: &ParityOptions<'_>,
+) -> StrategyParity {
+    let tolerance_ms = options.tolerance_secs * 1000;
+    let mut used = vec![false; decisions.len()];
+    let mut matched = 0;
+    let mut divergences = Vec::new();
+    for trade in &live {
+        let nearest = decisions
+            .iter()
+            .enumerate()
+            .filter(|(i, d)| {
+                !used[*i]
+                    && d.token_address == trade.token_address
+                    && d.side == trade.side
+                    && (d.at_ms - trade.at_ms).abs() <= tolerance_ms
+            })
+            .min_by_key(|(_, d)| (d.at_ms - trade.at_ms).abs());
+        let Some((index, decision)) = nearest else {
+            divergences.push(Divergence {
+                kind: DivergenceKind::Missed,
+                token_address: trade.token_address.clone(),
+                side: trade.side.clone(),
+                trade_id: Some(trade.trade_id),
+                live_at_ms: Some(trade.at_ms),
+                replay_at_ms: None,
+                live_size_usd: Some(trade.size_usd),
+                replay_size_usd: None,
+            });
+            continue;
+        };
+        used[index] = true;
+        let size_gap_pct = (decision.size_usd - trade.size_usd).abs()
+            / trade.size_usd.abs().max(f64::EPSILON)
+            * 100.0;
+        if size_gap_pct > options.size_tolerance_pct {
+            divergences.push(Divergence {
+                kind: DivergenceKind::Size,
+                token_address: trade.token_address.clone(),
+                side: trade.side.clone(),
+                trade_id: Some(trade.trade_id),
+                live_at_ms: Some(trade.at_ms),
+                replay_at_ms: Some(decision.at_ms),
+                live_size_usd: Some(trade.size_usd),
+                replay_size_usd: Some(decision.size_usd),
+            });
+        } else {
+            matched += 1;
+        }
+    }
+    for (decision, _) in decisions.iter().zip(&used).filter(|(_, used)| !**used) {
+        divergences.push(Divergence {
+            kind: DivergenceKind::Extra,
+            token_address: decision.token_address.clone(),
+            side: decision.side.clone(),
+            trade_id: None,
+            live_at_ms: None,
+            replay_at_ms: Some(decision.at_ms),
+            live_size_usd: None,
+            replay_size_usd: Some(decision.size_usd),
+        });
+    }
+    divergences.sort_by_key(|d| d.live_at_ms.or(d.replay_at_ms));
+
+    for divergence in divergences.iter().take(20) {
+        warn!(strategy = %id, divergence = ?divergence, "Parity divergence.");
+    }
+    let larger = live.len().max(decisions.len());
+    StrategyParity {
+        id,
+        live_trades: live.len(),
+        replay_decisions: decisions.len(),
+        matched,
+        parity_pct: if larger == 0 {
+            100.0
+        } else {
+            matched as f64 / larger as f64 * 100.0
+        },
+        divergences,
+    }
+}
@@ -0,0 +1,318 @@
+// executor/src/bin/backtest/report.rs
+//! The backtest report: per-strategy stats, equity curve and trades, written
+//! as JSON and as a self-contained HTML page.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+const MS_PER_YEAR: f64 = 365.0 * 86_400_000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClosedTrade {
+    pub id: i64,
+    pub token_address: String,
+    pub side: String,
+    pub size_usd: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub opened_at_ms: i64,
+    pub closed_at_ms: i64,
+    pub fees_usd: f64,
+    pub pnl_usd: f64, // Net of fees
+    pub exit_reason: String,
+}
+
+/// An entry leg a strategy asked for, before any fill simulation.
+#[derive(Debug, Clone, Serialize)]
+pub struct Decision {
+    pub at_ms: i64,
+    pub token_address: String,
+    pub side: String,
+    pub size_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EquityPoint {
+    pub timestamp_ms: i64,
+    pub equity_usd: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub signals: u64,
+    pub trades: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub net_pnl_usd: f64, // Closed trades only
+    pub fees_usd: f64,
+    pub return_pct: f64, // Final equity, open positions marked to market
+    pub max_drawdown_pct: f64,
+    pub sharpe_ratio: f64,          // Annualized, from the equity curve
+    pub profit_factor: Option<f64>, // None without a losing trade
+    pub avg_hold_secs: f64,
+    pub open_positions_at_end: usize,
+    pub rejections: BTreeMap<String, u64>,
+}
+
+impl Stats {
+    pub fn compute(
+        capital_usd: f64,
+        signals: u64,
+        trades: &[ClosedTrade],
+        curve: &[EquityPoint],
+        open_positions_at_end: usize,
+        rejections: BTreeMap<String, u64>,
+    ) -> Self {
+        let wins = trades.iter().filter(|t| t.pnl_usd > 0.0).count();
+        let gains: f64 = trades.iter().map(|t| t.pnl_usd.max(0.0)).sum();
+        let losses: f64 = trades.iter().map(|t| (-t.pnl_usd).max(0.0)).sum();
+        let final_equity = curve.last().map_or(capital_usd, |p| p.equity_usd);
+
+        let mut peak = capital_usd;
+        let mut max_drawdown_pct: f64 = 0.0;
+        for point in curve {
+            peak = peak.max(point.equity_usd);
+            if peak > 0.0 {
+                max_drawdown_pct = max_drawdown_pct.max((peak - point.equity_usd) / peak * 100.0);
+            }
+        }
+
+        let returns: Vec<f64> = curve
+            .windows(2)
+            .filter(|w| w[0].equity_usd > 0.0)
+            .map(|w| w[1].equity_usd / w[0].equity_usd - 1.0)
+            .collect();
+        let sharpe_ratio = if returns.len() >= 2 {
+            let n = returns.len() as f64;
+            let mean = returns.iter().sum::<f64>() / n;
+            let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+            let span_ms = (curve[curve.len() - 1].timestamp_ms - curve[0].timestamp_ms) as f64;
+            if std > 0.0 && span_ms > 0.0 {
+                mean / std * (n * MS_PER_YEAR / span_ms).sqrt()
+            } else {
+                0.0
+            }
+        } else {
+            0.0
+        };
+
+        Self {
+            signals,
+            trades: trades.len(),
+            wins,
+            win_rate: if trades.is_empty() {
+                0.0
+            } else {
+                wins as f64 / trades.len() as f64
+            },
+            net_pnl_usd: trades.iter().map(|t| t.pnl_usd).sum(),
+            fees_usd: trades.iter().map(|t| t.fees_usd).sum(),
+            return_pct: (final_equity / capital_usd - 1.0) * 100.0,
+            max_drawdown_pct,
+            sharpe_ratio,
+            profit_factor: (losses > 0.0).then(|| gains / losses),
+            avg_hold_secs: if trades.is_empty() {
+                0.0
+            } else {
+                trades
+                    .iter()
+                    .map(|t| (t.closed_at_ms - t.opened_at_ms) as f64 / 1000.0)
+                    .sum::<f64>()
+                    / trades.len() as f64
+            },
+            open_positions_at_end,
+            rejections,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrategyReport {
+    pub id: String,
+    pub family: String,
+    pub params: serde_json::Value,
+    pub stats: Stats,
+    pub equity_curve: Vec<EquityPoint>,
+    pub trades: Vec<ClosedTrade>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decisions: Vec<Decision>, // Only recorded for parity checks
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub generated_at: i64,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub days: Vec<String>,
+    pub events_replayed: u64,
+    pub settings: serde_json::Value,
+    pub strategies: Vec<StrategyReport>,
+}
+
+impl Report {
+    /// Writes `<out>.json` and `<out>.html`.
+    pub fn write(&self, out: &Path) -> Result<()> {
+        std::fs::write(
+            out.with_extension("json"),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        std::fs::write(out.with_extension("html"), self.html())?;
+        Ok(())
+    }
+
+    fn html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Backtest report</title>\
+             <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:1.5em}}\
+             td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right}}th{{background:#eee}}\
+             .pos{{color:#080}}.neg{{color:#b00}}</style></head><body>\
+             <h1>Backtest report</h1><p>{} days ({} to {}), {} events replayed.</p>",
+            self.days.len(),
+            escape(self.days.first().map_or("-", String::as_str)),
+            escape(self.days.last().map_or("-", String::as_str)),
+            self.events_replayed
+        );
+
+        html.push_str(
+            "<table><tr><th>Strategy</th><th>Family</th><th>Signals</th><th>Trades</th>\
+             <th>Win rate</th><th>Net PnL</th><th>Fees</th><th>Return</th><th>Max DD</th>\
+             <th>Sharpe</th><th>Profit factor</th><th>Avg hold</th><th>Open at end</th></tr>",
+        );
+        for strategy in &self.strategies {
+            let s = &strategy.stats;
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.1}%</td>\
+                 <td class=\"{}\">${:.2}</td><td>${:.2}</td><td class=\"{}\">{:.2}%</td>\
+                 <td>{:.2}%</td><td>{:.2}</td><td>{}</td><td>{:.0}s</td><td>{}</td></tr>",
+                escape(&strategy.id),
+                escape(&strategy.family),
+                s.signals,
+                s.trades,
+                s.win_rate * 100.0,
+                sign_class(s.net_pnl_usd),
+                s.net_pnl_usd,
+                s.fees_usd,
+                sign_class(s.return_pct),
+                s.return_pct,
+                s.max_drawdown_pct,
+                s.sharpe_ratio,
+                s.profit_factor
+                    .map_or("-".to_string(), |pf| format!("{:.2}", pf)),
+                s.avg_hold_secs,
+                s.open_positions_at_end
+            );
+        }
+        html.push_str("</table>");
+
+        for strategy in &self.strategies {
+            let _ = write!(html, "<h2>{}</h2>", escape(&strategy.id));
+            html.push_str(&equity_svg(&strategy.equity_curve));
+            if !strategy.stats.rejections.is_empty() {
+                let rejections: Vec<String> = strategy
+                    .stats
+                    .rejections
+                    .iter()
+                    .map(|(reason, count)| format!("{} {}", escape(reason), count))
+                    .collect();
+                let _ = write!(html, "<p>Rejected legs: {}</p>", rejections.join(", "));
+            }
+            html.push_str(
+                "<table><tr><th>Opened</th><th>Closed</th><th>Token</th><th>Side</th>\
+                 <th>Size</th><th>Entry</th><th>Exit</th><th>Fees</th><th>PnL</th><th>Reason</th></tr>",
+            );
+            for trade in &strategy.trades {
+                let _ = write!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>${:.2}</td>\
+                     <td>{:.8}</td><td>{:.8}</td><td>${:.2}</td><td class=\"{}\">${:.2}</td><td>{}</td></tr>",
+                    datetime(trade.opened_at_ms),
+                    datetime(trade.closed_at_ms),
+                    escape(&trade.token_address),
+                    escape(&trade.side),
+                    trade.size_usd,
+                    trade.entry_price,
+                    trade.exit_price,
+                    trade.fees_usd,
+                    sign_class(trade.pnl_usd),
+                    trade.pnl_usd,
+                    escape(&trade.exit_reason)
+                );
+            }
+            html.push_str("</table>");
+        }
+        html.push_str("</body></html>");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn sign_class(value: f64) -> &'static str {
+    if value >= 0.0 {
+        "pos"
+    } else {
+        "neg"
+    }
+}
+
+fn datetime(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms).map_or_else(
+        || ms.to_string(),
+        |t| t.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+
+/// The equity curve as an inline SVG polyline.
+fn equity_svg(curve: &[EquityPoint]) -> String {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 200.0;
+    if curve.len() < 2 {
+        return "<p>Not enough data for an equity curve.</p>".to_string();
+    }
+    let (t0, t1) = (
+        curve[0].timestamp_ms as f64,
+        curve[curve.len() - 1].timestamp_ms as f64,
+    );
+    let low = curve
+        .iter()
+        .map(|p| p.equity_usd)
+        .fold(f64::INFINITY, f64::min);
+    let high = curve
+        .iter()
+        .map(|p| p.equity_usd)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let (span_t, span_e) = ((t1 - t0).max(1.0), (high - low).max(1e-9));
+    let points: Vec<String> = curve
+        .iter()
+        .map(|p| {
+            format!(
+                "{:.1},{:.1}",
+                (p.timestamp_ms as f64 - t0) / span_t * WIDTH,
+                HEIGHT - (p.equity_usd - low) / span_e * HEIGHT
+            )
+        })
+        .collect();
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" style=\"border:1px solid #ccc\">\
+         <polyline fill=\"none\" stroke=\"#36c\" stroke-width=\"1.5\" points=\"{}\"/></svg>\
+         <p>Equity ${:.2} to ${:.2} (low ${:.2}, high ${:.2})</p>",
+        points.join(" "),
+        curve[0].equity_usd,
+        curve[curve.len() - 1].equity_usd,
+        low,
+        high,
+        w = WIDTH,
+        h = HEIGHT
+    )
+}
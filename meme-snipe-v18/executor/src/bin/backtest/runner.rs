@@ -0,0 +1,219 @@
+// executor/src/bin/backtest/runner.rs
+//! Drives strategies through the archive. Runners are dealt round-robin into
+//! lanes, each with its own view of the market, and each day's events are
+//! loaded once and replayed through every lane concurrently.
+
+use crate::data::{Archive, Recorded};
+use crate::report::{Decision, Stats, StrategyReport};
+use crate::sim::{Book, Market, Settings};
+use crate::strategies::{self, MetricsHandle, Strategy};
+use anyhow::{anyhow, Context, Result};
+use shared_models::{EventType, MarketEvent, StrategyAction, StrategySpec};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+const STALE_EVENT_SECS: i64 = 30; // The executor drops older events before strategies see them
+
+/// One spec under test.
+pub struct Runner {
+    pub spec: StrategySpec,
+    strategy: Box<dyn Strategy>,
+    pub subscriptions: HashSet<EventType>,
+    timer: Option<(i64, i64)>, // (period ms, next tick ms)
+    book: Book,
+    decisions: Option<Vec<Decision>>,
+}
+
+impl Runner {
+    pub async fn new(spec: StrategySpec, settings: &Settings) -> Result<Self> {
+        let mut strategy = strategies::build_strategy(&spec.family)
+            .ok_or_else(|| anyhow!("unknown strategy family {}", spec.family))?;
+        strategy
+            .params_schema()
+            .validate(&spec.params)
+            .with_context(|| format!("spec {}", spec.id))?;
+        strategy
+            .init(&spec.params, &MetricsHandle::new(&spec.id))
+            .await
+            .with_context(|| format!("initializing {}", spec.id))?;
+        Ok(Self {
+            subscriptions: strategy.subscriptions(),
+            timer: strategy
+                .timer_interval()
+                .map(|period| (period.as_millis().max(1) as i64, 0)),
+            strategy,
+            spec,
+            book: Book::new(settings.clone()),
+            decisions: None,
+        })
+    }
+
+    /// Keeps every entry leg the strategy asks for, for `parity`.
+    pub fn record_decisions(mut self) -> Self {
+        self.decisions = Some(Vec::new());
+        self
+    }
+
+    fn act(&mut self, action: StrategyAction, at_ms: i64, market: &Market) {
+        if let Some(decisions) = self.decisions.as_mut() {
+            let legs = match &action {
+                StrategyAction::Execute(details, _) => std::slice::from_ref(details),
+                StrategyAction::ExecuteMany(legs, _, _) => legs.as_slice(),
+                _ => &[],
+            };
+            decisions.extend(legs.iter().map(|details| Decision {
+                at_ms,
+                token_address: details.token_address.clone(),
+                side: details.side.to_string(),
+                size_usd: details.suggested_size_usd,
+            }));
+        }
+        self.book.act(action, at_ms, market);
+    }
+
+    fn admits(&self, token: &str) -> bool {
+        !self.spec.token_denylist.iter().any(|t| t == token)
+            && self
+                .spec
+                .token_allowlist
+                .as_ref()
+                .map_or(true, |allow| allow.iter().any(|t| t == token))
+    }
+
+    async fn on_event(&mut self, event: &MarketEvent, at_ms: i64, market: &Market) {
+        if let Some((period_ms, next_ms)) = self.timer.as_mut() {
+            if *next_ms == 0 {
+                *next_ms = at_ms + *period_ms; // Timers start with the first event
+            }
+            while *next_ms <= at_ms {
+                let tick_ms = *next_ms;
+                *next_ms += *period_ms;
+                match self.strategy.on_timer(tick_ms / 1000).await {
+                    Ok(action) => self.act(action, tick_ms, market),
+                    Err(e) => {
+                        warn!(strategy = %self.spec.id, error = %e, "Strategy timer returned an error.")
+                    }
+                }
+            }
+        }
+
+        self.book.step(at_ms, event, market);
+
+        if !self.subscriptions.contains(&event.get_type())
+            || !self.admits(event.token())
+            || at_ms / 1000 - event.timestamp() > STALE_EVENT_SECS
+        {
+            return;
+        }
+        let portfolio = self.book.portfolio(market);
+        match self.strategy.on_event(event, &portfolio).await {
+            Ok(action) => self.act(action, at_ms, market),
+            Err(e) => warn!(strategy = %self.spec.id, error = %e, "Strategy returned an error."),
+        }
+    }
+
+    fn finish(mut self, last_ms: i64, market: &Market, capital_usd: f64) -> StrategyReport {
+        let open_at_end = self.book.finish(last_ms, market);
+        let stats = Stats::compute(
+            capital_usd,
+            self.book.signals,
+            &self.book.trades,
+            &self.book.curve,
+            open_at_end,
+            std::mem::take(&mut self.book.rejections),
+        );
+        StrategyReport {
+            id: self.spec.id,
+            family: self.spec.family,
+            params: self.spec.params,
+            stats,
+            equity_curve: self.book.curve,
+            trades: self.book.trades,
+            decisions: self.decisions.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Lane {
+    market: Market,
+    runners: Vec<(usize, Runner)>, // Position in the caller's list, to restore its order
+    last_ms: i64,
+}
+
+impl Lane {
+    async fn replay(mut self, events: Arc<Vec<Recorded>>) -> Self {
+        for recorded in events.iter() {
+            self.market.update(&recorded.event, recorded.received_at_ms);
+            for (_, runner) in self.runners.iter_mut() {
+                runner
+                    .on_event(&recorded.event, recorded.received_at_ms, &self.market)
+                    .await;
+            }
+            self.last_ms = recorded.received_at_ms;
+        }
+        self
+    }
+}
+
+/// The event types every runner needs, plus the prices and depth fills use.
+pub fn event_types<'a>(runners: impl IntoIterator<Item = &'a Runner>) -> HashSet<EventType> {
+    let mut event_types: HashSet<EventType> = [EventType::Price, EventType::Depth].into();
+    for runner in runners {
+        event_types.extend(runner.subscriptions.iter().cloned());
+    }
+    event_types
+}
+
+/// Replays `days` through the runners, returning their reports in the order
+/// given and the number of events replayed.
+pub async fn replay(
+    archive: &Archive,
+    days: &[String],
+    runners: Vec<Runner>,
+    settings: &Settings,
+    parallelism: usize,
+) -> Result<(Vec<StrategyReport>, u64)> {
+    let lane_count = parallelism.clamp(1, runners.len().max(1));
+    let mut lanes: Vec<Lane> = (0..lane_count).map(|_| Lane::default()).collect();
+    for (index, runner) in runners.into_iter().enumerate() {
+        lanes[index % lane_count].runners.push((index, runner));
+    }
+
+    let mut events_replayed = 0u64;
+    for day in days {
+        let events = Arc::new(archive.load_day(day)?);
+        info!(day = %day, events = events.len(), "Replaying day.");
+        events_replayed += events.len() as u64;
+        let handles: Vec<_> = lanes
+            .drain(..)
+            .map(|lane| tokio::spawn(lane.replay(events.clone())))
+            .collect();
+        for handle in handles {
+            lanes.push(handle.await.context("backtest lane panicked")?);
+        }
+    }
+
+    let mut reports: Vec<(usize, StrategyReport)> = lanes
+        .into_iter()
+        .flat_map(|lane| {
+            let Lane {
+                market,
+                runners,
+                last_ms,
+            } = lane;
+            runners
+                .into_iter()
+                .map(|(index, runner)| {
+                    (index, runner.finish(last_ms, &market, settings.capital_usd))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    reports.sort_by_key(|(index, _)| *index);
+    Ok((
+        reports.into_iter().map(|(_, report)| report).collect(),
+        events_replayed,
+    ))
+}
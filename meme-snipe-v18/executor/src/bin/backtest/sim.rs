@@ -0,0 +1,599 @@
+// executor/src/bin/backtest/sim.rs
+//! Simulated execution for one strategy. Orders are worked the way the
+//! executor works them, against the archived prices:
+//!
+//! - An order is seen by the market `latency_ms` after the strategy emits it.
+//!   Market orders (and each TWAP slice, a separate position as in the
+//!   executor) then fill at the last Price tick for the token, provided it is
+//!   at most `PRICE_MAX_AGE_MS` old; otherwise they are rejected.
+//! - Limit orders fill at their price once a tick crosses it, until their TTL
+//!   runs out; StopEntry orders fill at market once a tick crosses the trigger.
+//! - All-or-nothing legs fill together or not at all.
+//! - Fills pay `fee_bps` of notional and slip by `slippage_bps`, plus the
+//!   impact read off the latest DepthEvent (share of the touch consumed plus
+//!   half the spread) when it is fresh, as in the executor's slippage model.
+//! - Open positions exit on their ExitProfile (take profit, trailing stop,
+//!   max hold) at the tick that triggers it, and on ClosePositions signals.
+//!
+//! Both legs of a perp trade are marked on the token's spot price.
+
+use crate::report::{ClosedTrade, EquityPoint};
+use crate::strategies::{OpenPosition, PortfolioContext};
+use serde::Serialize;
+use shared_models::{
+    DepthEvent, ExitProfile, LegPolicy, MarketEvent, OrderDetails, OrderType, Side, StrategyAction,
+};
+use std::collections::{BTreeMap, HashMap};
+
+const PRICE_MAX_AGE_MS: i64 = 60_000;
+const DEPTH_MAX_AGE_MS: i64 = 60_000;
+const STOP_ENTRY_MAX_WAIT_MS: i64 = 3_600_000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Settings {
+    pub capital_usd: f64,
+    pub fee_bps: f64,
+    pub slippage_bps: f64,
+    pub latency_ms: i64,
+    pub max_position_usd: Option<f64>,
+    pub max_positions_per_token: usize,
+    pub trailing_stop_pct: f64,
+    pub drawdown_limit_pct: f64, // 0 = no portfolio stop
+    pub curve_interval_ms: i64,
+}
+
+/// Latest prices and depth across all tokens, shared by every strategy.
+#[derive(Default)]
+pub struct Market {
+    prices: HashMap<String, (f64, i64)>, // Token -> (price, received at ms)
+    depth: HashMap<String, (DepthEvent, i64)>,
+}
+
+impl Market {
+    pub fn update(&mut self, event: &MarketEvent, at_ms: i64) {
+        match event {
+            MarketEvent::Price(tick) if tick.price_usd > 0.0 => {
+                self.prices
+                    .insert(tick.token_address.clone(), (tick.price_usd, at_ms));
+            }
+            MarketEvent::Depth(depth) => {
+                self.depth
+                    .insert(depth.token_address.clone(), (depth.clone(), at_ms));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn price(&self, token: &str, at_ms: i64) -> Option<f64> {
+        self.prices
+            .get(token)
+            .filter(|(_, seen)| at_ms - seen <= PRICE_MAX_AGE_MS)
+            .map(|(price, _)| *price)
+    }
+
+    pub fn last_price(&self, token: &str) -> Option<f64> {
+        self.prices.get(token).map(|(price, _)| *price)
+    }
+
+    /// Fractional price impact of trading `size_usd`, buying or selling.
+    fn impact(&self, token: &str, size_usd: f64, buying: bool, at_ms: i64) -> f64 {
+        let Some((depth, seen)) = self.depth.get(token) else {
+            return 0.0;
+        };
+        let touch_usd = if buying {
+            depth.ask_size_usd
+        } else {
+            depth.bid_size_usd
+        };
+        if at_ms - seen > DEPTH_MAX_AGE_MS || touch_usd <= 0.0 || depth.ask_price <= 0.0 {
+            return 0.0;
+        }
+        let mid = (depth.bid_price + depth.ask_price) / 2.0;
+        let half_spread = if mid > 0.0 {
+            (depth.ask_price - depth.bid_price).max(0.0) / mid / 2.0
+        } else {
+            0.0
+        };
+        size_usd / touch_usd + half_spread
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Trigger {
+    Market,
+    Limit { price: f64, expires_ms: i64 },
+    StopEntry { trigger_price: f64, expires_ms: i64 },
+}
+
+#[derive(Debug, Clone)]
+struct PendingOrder {
+    group: Option<u64>, // Shared by the legs of an all-or-nothing order
+    details: OrderDetails,
+    size_usd: f64,
+    due_ms: i64,
+    trigger: Trigger,
+}
+
+#[derive(Debug)]
+struct Position {
+    id: i64,
+    token: String,
+    side: Side,
+    size_usd: f64,
+    entry_price: f64,
+    opened_ms: i64,
+    entry_fee_usd: f64,
+    exit_profile: ExitProfile,
+    best_price: f64, // Highest since entry for longs, lowest for shorts
+}
+
+impl Position {
+    fn change(&self, price: f64) -> f64 {
+        let change = (price - self.entry_price) / self.entry_price;
+        match self.side {
+            Side::Long => change,
+            Side::Short => -change,
+        }
+    }
+}
+
+pub struct Book {
+    settings: Settings,
+    next_id: i64,
+    pending: Vec<PendingOrder>,
+    closing: BTreeMap<String, i64>, // Token -> due ms of a ClosePositions
+    open: Vec<Position>,
+    pub trades: Vec<ClosedTrade>,
+    pub curve: Vec<EquityPoint>,
+    next_sample_ms: i64,
+    realized_usd: f64,
+    peak_equity_usd: f64,
+    pub signals: u64,
+    pub rejections: BTreeMap<String, u64>, // Reason -> count
+}
+
+fn buys(side: &Side, entering: bool) -> bool {
+    matches!(side, Side::Long) == entering
+}
+
+impl Book {
+    pub fn new(settings: Settings) -> Self {
+        let capital = settings.capital_usd;
+        Self {
+            settings,
+            next_id: 1,
+            pending: Vec::new(),
+            closing: BTreeMap::new(),
+            open: Vec::new(),
+            trades: Vec::new(),
+            curve: Vec::new(),
+            next_sample_ms: 0,
+            realized_usd: 0.0,
+            peak_equity_usd: capital,
+            signals: 0,
+            rejections: BTreeMap::new(),
+        }
+    }
+
+    fn reject(&mut self, reason: &str, count: u64) {
+        *self.rejections.entry(reason.to_string()).or_default() += count;
+    }
+
+    pub fn equity_usd(&self, market: &Market) -> f64 {
+        let unrealized: f64 = self
+            .open
+            .iter()
+            .map(|p| {
+                market
+                    .last_price(&p.token)
+                    .map_or(0.0, |price| p.change(price) * p.size_usd)
+                    - p.entry_fee_usd
+            })
+            .sum();
+        self.settings.capital_usd + self.realized_usd + unrealized
+    }
+
+    fn drawdown_pct(&self, market: &Market) -> f64 {
+        if self.peak_equity_usd <= 0.0 {
+            return 0.0;
+        }
+        ((self.peak_equity_usd - self.equity_usd(market)) / self.peak_equity_usd * 100.0).max(0.0)
+    }
+
+    pub fn portfolio(&self, market: &Market) -> PortfolioContext {
+        PortfolioContext {
+            open_positions: self
+                .open
+                .iter()
+                .map(|p| OpenPosition {
+                    trade_id: p.id,
+                    token_address: p.token.clone(),
+                    side: p.side.clone(),
+                    size_usd: p.size_usd,
+                })
+                .collect(),
+            capital_budget_usd: self.settings.capital_usd,
+            drawdown_pct: self.drawdown_pct(market),
+            drawdown_limit_pct: self.settings.drawdown_limit_pct,
+        }
+    }
+
+    /// Queues what the strategy asked for at `now_ms`.
+    pub fn act(&mut self, action: StrategyAction, now_ms: i64, market: &Market) {
+        let (legs, policy) = match action {
+            StrategyAction::Execute(details, _) => (vec![details], LegPolicy::Independent),
+            StrategyAction::ExecuteMany(legs, policy, _) => (legs, policy),
+            StrategyAction::ClosePositions(token) => {
+                self.closing
+                    .entry(token)
+                    .or_insert(now_ms + self.settings.latency_ms);
+                return;
+            }
+            StrategyAction::Hold => return,
+        };
+        self.signals += 1;
+        let leg_count = legs.len() as u64;
+        if self.settings.drawdown_limit_pct > 0.0
+            && self.drawdown_pct(market) >= self.settings.drawdown_limit_pct
+        {
+            self.reject("drawdown_stop", leg_count);
+            return;
+        }
+        if policy == LegPolicy::AllOrNothing
+            && legs.iter().any(|leg| leg.order_type != OrderType::Market)
+        {
+            self.reject("invalid_legs", leg_count);
+            return;
+        }
+
+        // Legs in the same token are one position for the cap, as in the executor
+        let mut tokens: Vec<&str> = legs.iter().map(|l| l.token_address.as_str()).collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        let capped: Vec<String> = tokens
+            .into_iter()
+            .filter(|token| {
+                let held = self.open.iter().filter(|p| p.token == *token).count()
+                    + self
+                        .pending
+                        .iter()
+                        .filter(|o| o.details.token_address == *token)
+                        .count();
+                held >= self.settings.max_positions_per_token
+            })
+            .map(str::to_string)
+            .collect();
+        if !capped.is_empty() && policy == LegPolicy::AllOrNothing {
+            self.reject("position_cap", leg_count);
+            return;
+        }
+
+        let group = (policy == LegPolicy::AllOrNothing).then(|| {
+            self.next_id += 1;
+            self.next_id as u64
+        });
+        let due_ms = now_ms + self.settings.latency_ms;
+        for details in legs {
+            if capped.contains(&details.token_address) {
+                self.reject("position_cap", 1);
+                continue;
+            }
+            let size_usd = self
+                .settings
+                .max_position_usd
+                .map_or(details.suggested_size_usd, |max| {
+                    details.suggested_size_usd.min(max)
+                });
+            if size_usd <= 0.0 {
+                self.reject("zero_size", 1);
+                continue;
+            }
+            match details.order_type.clone() {
+                OrderType::Market => self.pending.push(PendingOrder {
+                    group,
+                    details,
+                    size_usd,
+                    due_ms,
+                    trigger: Trigger::Market,
+                }),
+                OrderType::Limit { price, ttl_secs } => self.pending.push(PendingOrder {
+                    group,
+                    details,
+                    size_usd,
+                    due_ms,
+                    trigger: Trigger::Limit {
+                        price,
+                        expires_ms: now_ms + ttl_secs as i64 * 1000,
+                    },
+                }),
+                OrderType::StopEntry { trigger_price } => self.pending.push(PendingOrder {
+                    group,
+                    details,
+                    size_usd,
+                    due_ms,
+                    trigger: Trigger::StopEntry {
+                        trigger_price,
+                        expires_ms: now_ms + STOP_ENTRY_MAX_WAIT_MS,
+                    },
+                }),
+                OrderType::Twap {
+                    duration_secs,
+                    slices,
+                } => {
+                    let slices = slices.max(1);
+                    let interval_ms = duration_secs as i64 * 1000 / slices as i64;
+                    for slice in 0..slices as i64 {
+                        self.pending.push(PendingOrder {
+                            group,
+                            details: details.clone(),
+                            size_usd: size_usd / slices as f64,
+                            due_ms: due_ms + slice * interval_ms,
+                            trigger: Trigger::Market,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Works pending orders, closes and exits against the market as of `now_ms`.
+    pub fn step(&mut self, now_ms: i64, event: &MarketEvent, market: &Market) {
+        self.fill_pending(now_ms, market);
+        self.close_requested(now_ms, market);
+        if let MarketEvent::Price(tick) = event {
+            self.check_exits(&tick.token_address, now_ms, market);
+        }
+        self.sample(now_ms, market);
+    }
+
+    fn fill_price(
+        &self,
+        token: &str,
+        size_usd: f64,
+        buying: bool,
+        price: f64,
+        at_ms: i64,
+        market: &Market,
+    ) -> f64 {
+        let slip =
+            self.settings.slippage_bps / 10_000.0 + market.impact(token, size_usd, buying, at_ms);
+        if buying {
+            price * (1.0 + slip)
+        } else {
+            price * (1.0 - slip)
+        }
+    }
+
+    fn open_position(&mut self, order: &PendingOrder, fill_price: f64, now_ms: i64) {
+        self.next_id += 1;
+        self.open.push(Position {
+            id: self.next_id,
+            token: order.details.token_address.clone(),
+            side: order.details.side.clone(),
+            size_usd: order.size_usd,
+            entry_price: fill_price,
+            opened_ms: now_ms,
+            entry_fee_usd: order.size_usd * self.settings.fee_bps / 10_000.0,
+            exit_profile: order.details.exit_profile.clone().unwrap_or_default(),
+            best_price: fill_price,
+        });
+    }
+
+    fn fill_pending(&mut self, now_ms: i64, market: &Market) {
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|order| order.due_ms <= now_ms);
+        self.pending = waiting;
+
+        let mut groups: BTreeMap<u64, Vec<PendingOrder>> = BTreeMap::new();
+        for order in due {
+            if let Some(group) = order.group {
+                groups.entry(group).or_default().push(order);
+                continue;
+            }
+            let token = order.details.token_address.clone();
+            let buying = buys(&order.details.side, true);
+            let Some(price) = market.price(&token, now_ms) else {
+                match order.trigger {
+                    Trigger::Market => self.reject("no_price", 1),
+                    _ => self.pending.push(order), // Keeps waiting for a tick
+                }
+                continue;
+            };
+            let fill = match order.trigger {
+                Trigger::Market => {
+                    Some(self.fill_price(&token, order.size_usd, buying, price, now_ms, market))
+                }
+                Trigger::Limit {
+                    price: limit,
+                    expires_ms,
+                } => {
+                    let crossed = if buying {
+                        price <= limit
+                    } else {
+                        price >= limit
+                    };
+                    if crossed {
+                        Some(limit)
+                    } else if now_ms >= expires_ms {
+                        self.reject("limit_expired", 1);
+                        continue;
+                    } else {
+                        None
+                    }
+                }
+                Trigger::StopEntry {
+                    trigger_price,
+                    expires_ms,
+                } => {
+                    let crossed = if buying {
+                        price >= trigger_price
+                    } else {
+                        price <= trigger_price
+                    };
+                    if crossed {
+                        Some(self.fill_price(&token, order.size_usd, buying, price, now_ms, market))
+                    } else if now_ms >= expires_ms {
+                        self.reject("stop_expired", 1);
+                        continue;
+                    } else {
+                        None
+                    }
+                }
+            };
+            match fill {
+                Some(fill) => self.open_position(&order, fill, now_ms),
+                None => self.pending.push(order),
+            }
+        }
+
+        for (_, legs) in groups {
+            let prices: Option<Vec<f64>> = legs
+                .iter()
+                .map(|leg| market.price(&leg.details.token_address, now_ms))
+                .collect();
+            let Some(prices) = prices else {
+                self.reject("no_price", legs.len() as u64);
+                continue;
+            };
+            for (leg, price) in legs.iter().zip(prices) {
+                let buying = buys(&leg.details.side, true);
+                let fill = self.fill_price(
+                    &leg.details.token_address,
+                    leg.size_usd,
+                    buying,
+                    price,
+                    now_ms,
+                    market,
+                );
+                self.open_position(leg, fill, now_ms);
+            }
+        }
+    }
+
+    fn close_requested(&mut self, now_ms: i64, market: &Market) {
+        let due: Vec<String> = self
+            .closing
+            .iter()
+            .filter(|(token, due_ms)| **due_ms <= now_ms && market.price(token, now_ms).is_some())
+            .map(|(token, _)| token.clone())
+            .collect();
+        for token in due {
+            self.closing.remove(&token);
+            // Entries still being worked in the token are cancelled too
+            self.pending.retain(|o| o.details.token_address != token);
+            let ids: Vec<i64> = self
+                .open
+                .iter()
+                .filter(|p| p.token == token)
+                .map(|p| p.id)
+                .collect();
+            let price = market.price(&token, now_ms).unwrap_or_default();
+            for id in ids {
+                self.close(id, price, now_ms, "strategy_exit", market);
+            }
+        }
+    }
+
+    fn check_exits(&mut self, token: &str, now_ms: i64, market: &Market) {
+        let Some(price) = market.price(token, now_ms) else {
+            return;
+        };
+        let mut exits = Vec::new();
+        for position in self.open.iter_mut().filter(|p| p.token == token) {
+            position.best_price = match position.side {
+                Side::Long => position.best_price.max(price),
+                Side::Short => position.best_price.min(price),
+            };
+            let trailing_pct = position
+                .exit_profile
+                .trailing_stop_pct
+                .unwrap_or(self.settings.trailing_stop_pct);
+            let from_best = match position.side {
+                Side::Long => (position.best_price - price) / position.best_price,
+                Side::Short => (price - position.best_price) / position.best_price,
+            } * 100.0;
+            let reason = if position
+                .exit_profile
+                .take_profit_pct
+                .map_or(false, |tp| position.change(price) * 100.0 >= tp)
+            {
+                Some("take_profit")
+            } else if trailing_pct > 0.0 && from_best >= trailing_pct {
+                Some("trailing_stop")
+            } else if position
+                .exit_profile
+                .max_hold_secs
+                .map_or(false, |max| now_ms - position.opened_ms >= max * 1000)
+            {
+                Some("max_hold")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                exits.push((position.id, reason));
+            }
+        }
+        for (id, reason) in exits {
+            self.close(id, price, now_ms, reason, market);
+        }
+    }
+
+    fn close(&mut self, id: i64, price: f64, now_ms: i64, reason: &str, market: &Market) {
+        let Some(index) = self.open.iter().position(|p| p.id == id) else {
+            return;
+        };
+        let position = self.open.remove(index);
+        let buying = buys(&position.side, false);
+        let exit_price = self.fill_price(
+            &position.token,
+            position.size_usd,
+            buying,
+            price,
+            now_ms,
+            market,
+        );
+        let gross_usd = position.change(exit_price) * position.size_usd;
+        let exit_notional = position.size_usd * exit_price / position.entry_price;
+        let fees_usd = position.entry_fee_usd + exit_notional * self.settings.fee_bps / 10_000.0;
+        let pnl_usd = gross_usd - fees_usd;
+        self.realized_usd += pnl_usd;
+        self.trades.push(ClosedTrade {
+            id: position.id,
+            token_address: position.token,
+            side: position.side.to_string(),
+            size_usd: position.size_usd,
+            entry_price: position.entry_price,
+            exit_price,
+            opened_at_ms: position.opened_ms,
+            closed_at_ms: now_ms,
+            fees_usd,
+            pnl_usd,
+            exit_reason: reason.to_string(),
+        });
+    }
+
+    fn sample(&mut self, now_ms: i64, market: &Market) {
+        let equity_usd = self.equity_usd(market);
+        self.peak_equity_usd = self.peak_equity_usd.max(equity_usd);
+        if now_ms < self.next_sample_ms {
+            return;
+        }
+        self.curve.push(EquityPoint {
+            timestamp_ms: now_ms,
+            equity_usd,
+        });
+        self.next_sample_ms = now_ms + self.settings.curve_interval_ms;
+    }
+
+    /// Final equity point; positions still open are reported, not closed.
+    pub fn finish(&mut self, now_ms: i64, market: &Market) -> usize {
+        let unfilled = self.pending.len() as u64;
+        if unfilled > 0 {
+            self.reject("unfilled_at_end", unfilled);
+        }
+        self.next_sample_ms = 0;
+        self.sample(now_ms, market);
+        self.open.len()
+    }
+}
@@ -0,0 +1,326 @@
+// executor/src/bin/backtest/sweep.rs
+//! Param sweeps. A sweep file names a strategy family, base params and a grid
+//! of values per swept param, either listed or as `{"min", "max", "step"}`:
+//!
+//!     {"family": "momentum_5m", "id_prefix": "mom",
+//!      "params": {"size_usd": 100},
+//!      "grid": {"lookback": [5, 10, 20],
+//!               "threshold_pct": {"min": 1.0, "max": 3.0, "step": 0.5}},
+//!      "samples": 20}
+//!
+//! Every combination (or `samples` of them, drawn without replacement) is
+//! run over the in-sample days and, separately and from a fresh start, the
+//! last `--oos-fraction` of the days. Results are ranked by out-of-sample
+//! Sharpe; in-sample stats are kept alongside to show overfitting.
+
+use crate::data::Archive;
+use crate::report::Stats;
+use crate::runner::{self, Runner};
+use crate::sim::Settings;
+use anyhow::{bail, Context, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared_models::StrategySpec;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tracing::{info, warn};
+
+const MAX_COMBINATIONS: usize = 100_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Axis {
+    Values(Vec<Value>),
+    Range { min: f64, max: f64, step: f64 },
+}
+
+impl Axis {
+    fn values(&self, name: &str) -> Result<Vec<Value>> {
+        let values = match self {
+            Axis::Values(values) => values.clone(),
+            Axis::Range { min, max, step } => {
+                if *step <= 0.0 || max < min {
+                    bail!("grid {}: step must be positive and max at least min", name);
+                }
+                let count = ((max - min) / step + 1e-9).floor() as usize + 1;
+                if count > MAX_COMBINATIONS {
+                    bail!("grid {}: {} values is too many", name, count);
+                }
+                // Whole-number ranges stay integers so Integer params validate
+                let integral = [min, max, step].iter().all(|v| v.fract() == 0.0);
+                (0..count)
+                    .map(|i| {
+                        let v = min + step * i as f64;
+                        if integral {
+                            Value::from(v as i64)
+                        } else {
+                            Value::from((v * 1e9).round() / 1e9)
+                        }
+                    })
+                    .collect()
+            }
+        };
+        if values.is_empty() {
+            bail!("grid {} has no values", name);
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SweepSpec {
+    pub family: String,
+    #[serde(default)]
+    pub id_prefix: Option<String>,
+    #[serde(default)]
+    pub params: Value, // Shared by every combination
+    pub grid: BTreeMap<String, Axis>,
+    #[serde(default)]
+    pub samples: Option<usize>,
+    #[serde(default)]
+    pub token_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    pub token_denylist: Vec<String>,
+    #[serde(default)]
+    pub max_slippage_bps: Option<u16>,
+}
+
+impl SweepSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// The specs to run, one per sampled combination of the grid.
+    fn expand(&self, samples: Option<usize>, seed: u64) -> Result<Vec<StrategySpec>> {
+        let axes: Vec<(&String, Vec<Value>)> = self
+            .grid
+            .iter()
+            .map(|(name, axis)| Ok((name, axis.values(name)?)))
+            .collect::<Result<_>>()?;
+        let total = axes
+            .iter()
+            .try_fold(1usize, |total, (_, values)| total.checked_mul(values.len()))
+            .context("grid has too many combinations to count")?;
+        let indices: Vec<usize> = match samples.or(self.samples) {
+            Some(samples) if samples < total => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut picked = rand::seq::index::sample(&mut rng, total, samples).into_vec();
+                picked.sort_unstable();
+                picked
+            }
+            _ if total > MAX_COMBINATIONS => {
+                bail!(
+                    "grid has {} combinations; sample at most {}",
+                    total,
+                    MAX_COMBINATIONS
+                )
+            }
+            _ => (0..total).collect(),
+        };
+
+        let prefix = self.id_prefix.as_deref().unwrap_or(&self.family);
+        indices
+            .into_iter()
+            .map(|index| {
+                // Mixed-radix decode: the last axis varies fastest
+                let mut params = match &self.params {
+                    Value::Object(base) => base.clone(),
+                    Value::Null => serde_json::Map::new(),
+                    _ => bail!("sweep params must be an object"),
+                };
+                let mut rest = index;
+                for (name, values) in axes.iter().rev() {
+                    params.insert(name.to_string(), values[rest % values.len()].clone());
+                    rest /= values.len();
+                }
+                Ok(StrategySpec {
+                    id: format!("{}_g{}", prefix, index),
+                    family: self.family.clone(),
+                    params: Value::Object(params),
+                    token_allowlist: self.token_allowlist.clone(),
+                    token_denylist: self.token_denylist.clone(),
+                    max_slippage_bps: self.max_slippage_bps,
+                    challenger_params: None,
+                    variant_of: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepResult {
+    pub rank: usize,
+    pub id: String,
+    pub params: Value,
+    pub in_sample: Stats,
+    pub out_of_sample: Stats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepReport {
+    pub generated_at: i64,
+    pub family: String,
+    pub combinations: usize,
+    pub in_sample_days: Vec<String>,
+    pub out_of_sample_days: Vec<String>,
+    pub settings: Value,
+    pub results: Vec<SweepResult>,
+}
+
+pub struct SweepOptions<'a> {
+    pub samples: Option<usize>,
+    pub seed: u64,
+    pub oos_fraction: f64,
+    pub top: usize,
+    pub parallelism: usize,
+    pub settings: &'a Settings,
+}
+
+/// A runner per spec, skipping any that fail their param schema.
+pub async fn runners(specs: &[StrategySpec], settings: &Settings) -> Vec<Runner> {
+    let mut runners = Vec::with_capacity(specs.len());
+    for spec in specs {
+        match Runner::new(spec.clone(), settings).await {
+            Ok(runner) => runners.push(runner),
+            Err(e) => {
+                warn!(spec = %spec.id, params = %spec.params, error = %e, "Skipping combination.")
+            }
+        }
+    }
+    runners
+}
+
+/// Splits `days` into the in-sample days and the trailing `oos_fraction` of
+/// them held out of sample, keeping at least one day on each side.
+pub fn split_days(days: &[String], oos_fraction: f64) -> Result<(&[String], &[String])> {
+    if days.len() < 2 {
+        bail!("need at least two archived days to hold some out of sample");
+    }
+    let oos_days = ((days.len() as f64 * oos_fraction).round() as usize).clamp(1, days.len() - 1);
+    Ok(days.split_at(days.len() - oos_days))
+}
+
+/// Replays fresh instances of the candidates over `oos_days`, so no positions
+/// or state carry over, and ranks them by out-of-sample Sharpe. Returns the
+/// ranked results and the specs of the `top` of them.
+pub async fn rank_out_of_sample(
+    archive: &Archive,
+    candidates: Vec<(StrategySpec, Stats)>,
+    oos_days: &[String],
+    top: usize,
+    settings: &Settings,
+    parallelism: usize,
+) -> Result<(Vec<SweepResult>, Vec<StrategySpec>)> {
+    let specs: Vec<StrategySpec> = candidates.iter().map(|(spec, _)| spec.clone()).collect();
+    let out_of_sample = runners(&specs, settings).await;
+    let (oos_reports, _) =
+        runner::replay(archive, oos_days, out_of_sample, settings, parallelism).await?;
+
+    let mut oos_stats: HashMap<String, Stats> =
+        oos_reports.into_iter().map(|r| (r.id, r.stats)).collect();
+    let mut results: Vec<SweepResult> = candidates
+        .into_iter()
+        .filter_map(|(spec, in_sample)| {
+            Some(SweepResult {
+                rank: 0,
+                out_of_sample: oos_stats.remove(&spec.id)?,
+                id: spec.id,
+                params: spec.params,
+                in_sample,
+            })
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        b.out_of_sample
+            .sharpe_ratio
+            .total_cmp(&a.out_of_sample.sharpe_ratio)
+            .then(
+                b.out_of_sample
+                    .return_pct
+                    .total_cmp(&a.out_of_sample.return_pct),
+            )
+    });
+    for (rank, result) in results.iter_mut().enumerate() {
+        result.rank = rank + 1;
+    }
+
+    let top: Vec<StrategySpec> = results
+        .iter()
+        .take(top)
+        .filter_map(|r| specs.iter().find(|s| s.id == r.id).cloned())
+        .collect();
+    Ok((results, top))
+}
+
+/// Runs the sweep over `days`; returns the ranked report and the top specs.
+pub async fn run(
+    sweep: &SweepSpec,
+    data: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+    options: SweepOptions<'_>,
+) -> Result<(SweepReport, Vec<StrategySpec>)> {
+    let specs = sweep.expand(options.samples, options.seed)?;
+    let in_sample = runners(&specs, options.settings).await;
+    if in_sample.is_empty() {
+        bail!(
+            "no combination of the grid passed {}'s param schema",
+            sweep.family
+        );
+    }
+    let archive = Archive::new(data, &runner::event_types(&in_sample));
+    let days = archive.days(from, to)?;
+    let (is_days, oos_days) = split_days(&days, options.oos_fraction)?;
+    info!(
+        "🔬 Sweeping {} combinations of {}: {} days in sample ({} to {}), {} out ({} to {})...",
+        in_sample.len(),
+        sweep.family,
+        is_days.len(),
+        is_days[0],
+        is_days[is_days.len() - 1],
+        oos_days.len(),
+        oos_days[0],
+        oos_days[oos_days.len() - 1]
+    );
+
+    let (is_reports, _) = runner::replay(
+        &archive,
+        is_days,
+        in_sample,
+        options.settings,
+        options.parallelism,
+    )
+    .await?;
+    let candidates: Vec<(StrategySpec, Stats)> = is_reports
+        .into_iter()
+        .filter_map(|r| {
+            let spec = specs.iter().find(|s| s.id == r.id)?.clone();
+            Some((spec, r.stats))
+        })
+        .collect();
+    let (results, top) = rank_out_of_sample(
+        &archive,
+        candidates,
+        oos_days,
+        options.top,
+        options.settings,
+        options.parallelism,
+    )
+    .await?;
+
+    let report = SweepReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        family: sweep.family.clone(),
+        combinations: results.len(),
+        in_sample_days: is_days.to_vec(),
+        out_of_sample_days: oos_days.to_vec(),
+        settings: serde_json::to_value(options.settings)?,
+        results,
+    };
+    Ok((report, top))
+}
@@ -0,0 +1,217 @@
+// executor/src/bin/backtest/walkforward.rs
+//! Walk-forward analysis: optimize on `--train-days` archived days, trade the
+//! best in-sample params unchanged over the following `--test-days`, then roll
+//! both windows forward by `--step-days` (the test length by default) and go
+//! again. A curve-fit strategy shows up as test windows doing far worse than
+//! their training windows, or as the chosen params jumping around between
+//! windows; the stability score folds both into one number.
+
+use crate::optimize::{Search, SearchOptions, SearchSpace};
+use crate::report::Stats;
+use crate::runner;
+use crate::sweep;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use shared_models::StrategySpec;
+use std::path::Path;
+use tracing::info;
+
+#[derive(Debug, Serialize)]
+pub struct WindowResult {
+    pub window: usize,
+    pub train_from: String,
+    pub train_to: String,
+    pub test_from: String,
+    pub test_to: String,
+    pub trials: usize,
+    pub id: String,
+    pub params: Value,
+    pub train: Stats,
+    pub test: Stats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stability {
+    pub windows: usize,
+    pub profitable_windows: usize,
+    pub consistency: f64, // Share of test windows with a positive return
+    pub mean_train_sharpe: f64,
+    pub mean_test_sharpe: f64,
+    pub efficiency: Option<f64>, // Mean test over mean train Sharpe; None unless training Sharpe was positive
+    pub param_spread: f64,       // See `Search::spread`
+    /// consistency × efficiency (capped to 0..1) × (1 - 2 × param_spread):
+    /// 1 means every window made money, tested as well as it trained and
+    /// chose the same params.
+    pub score: f64,
+}
+
+impl Stability {
+    fn compute(windows: &[WindowResult], param_spread: f64) -> Self {
+        let n = windows.len().max(1) as f64;
+        let profitable_windows = windows.iter().filter(|w| w.test.return_pct > 0.0).count();
+        let mean_train_sharpe = windows.iter().map(|w| w.train.sharpe_ratio).sum::<f64>() / n;
+        let mean_test_sharpe = windows.iter().map(|w| w.test.sharpe_ratio).sum::<f64>() / n;
+        let efficiency = (mean_train_sharpe > 0.0).then(|| mean_test_sharpe / mean_train_sharpe);
+        let consistency = profitable_windows as f64 / n;
+        Self {
+            windows: windows.len(),
+            profitable_windows,
+            consistency,
+            mean_train_sharpe,
+            mean_test_sharpe,
+            efficiency,
+            param_spread,
+            score: consistency
+                * efficiency.unwrap_or(0.0).clamp(0.0, 1.0)
+                * (1.0 - 2.0 * param_spread).max(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalkForwardReport {
+    pub generated_at: i64,
+    pub family: String,
+    pub train_days: usize,
+    pub test_days: usize,
+    pub step_days: usize,
+    pub settings: Value,
+    pub windows: Vec<WindowResult>,
+    pub stability: Stability,
+}
+
+pub struct WalkForwardOptions<'a> {
+    pub search: SearchOptions<'a>,
+    pub train_days: usize,
+    pub test_days: usize,
+    pub step_days: usize,
+}
+
+/// Walks `family` forward through the archive; returns the report and the
+/// last window's params, trained on the most recent data.
+pub async fn run(
+    family: &str,
+    space: &SearchSpace,
+    data: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+    options: WalkForwardOptions<'_>,
+) -> Result<(WalkForwardReport, StrategySpec)> {
+    let search = Search::new(family, space)?;
+    let settings = options.search.settings;
+    let archive = search.archive(data, settings).await?;
+    let days = archive.days(from, to)?;
+    let span = options.train_days + options.test_days;
+    if days.len() < span {
+        bail!(
+            "walk-forward needs at least {} archived days for one window, found {}",
+            span,
+            days.len()
+        );
+    }
+    let starts: Vec<usize> = (0..=days.len() - span)
+        .step_by(options.step_days.max(1))
+        .collect();
+    info!(
+        "🚶 Walking {} forward over {} windows of {} training and {} test days ({} to {})...",
+        family,
+        starts.len(),
+        options.train_days,
+        options.test_days,
+        days[0],
+        days[days.len() - 1]
+    );
+
+    let mut windows = Vec::with_capacity(starts.len());
+    let mut last_spec = None;
+    for (window, start) in starts.into_iter().enumerate() {
+        let train = &days[start..start + options.train_days];
+        let test = &days[start + options.train_days..start + span];
+        info!(
+            window = window + 1,
+            train = %format!("{} to {}", train[0], train[train.len() - 1]),
+            test = %format!("{} to {}", test[0], test[test.len() - 1]),
+            "Optimizing window."
+        );
+        let window_options = SearchOptions {
+            seed: options.search.seed.wrapping_add(window as u64),
+            ..options.search
+        };
+        let trials = search
+            .run(
+                &archive,
+                train,
+                &format!("_w{}", window + 1),
+                &window_options,
+            )
+            .await?;
+        let trial_count = trials.history.len();
+        let (spec, train_stats) = trials
+            .candidates
+            .into_iter()
+            .max_by(|(_, a), (_, b)| {
+                a.sharpe_ratio
+                    .total_cmp(&b.sharpe_ratio)
+                    .then(a.return_pct.total_cmp(&b.return_pct))
+            })
+            .expect("a search returns at least one candidate");
+
+        // A fresh instance, so the test window starts flat
+        let runners = sweep::runners(std::slice::from_ref(&spec), settings).await;
+        let (reports, _) = runner::replay(
+            &archive,
+            test,
+            runners,
+            settings,
+            options.search.parallelism,
+        )
+        .await?;
+        let Some(test_report) = reports.into_iter().next() else {
+            bail!(
+                "window {}: {} failed to start for testing",
+                window + 1,
+                spec.id
+            );
+        };
+        info!(
+            window = window + 1,
+            id = %spec.id,
+            params = %spec.params,
+            train_sharpe = train_stats.sharpe_ratio,
+            test_sharpe = test_report.stats.sharpe_ratio,
+            test_return_pct = test_report.stats.return_pct,
+            "Window finished."
+        );
+        windows.push(WindowResult {
+            window: window + 1,
+            train_from: train[0].clone(),
+            train_to: train[train.len() - 1].clone(),
+            test_from: test[0].clone(),
+            test_to: test[test.len() - 1].clone(),
+            trials: trial_count,
+            id: spec.id.clone(),
+            params: spec.params.clone(),
+            train: train_stats,
+            test: test_report.stats,
+        });
+        last_spec = Some(spec);
+    }
+
+    let chosen: Vec<&Value> = windows.iter().map(|w| &w.params).collect();
+    let stability = Stability::compute(&windows, search.spread(&chosen));
+    let report = WalkForwardReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        family: family.to_string(),
+        train_days: options.train_days,
+        test_days: options.test_days,
+        step_days: options.step_days,
+        settings: serde_json::to_value(settings)?,
+        windows,
+        stability,
+    };
+    Ok((
+        report,
+        last_spec.expect("at least one window was checked above"),
+    ))
+}
@@ -0,0 +1,213 @@
+// executor/src/bin/dead_letters.rs
+//! Inspects and replays `dead_letters`, where consumers park the stream
+//! entries they could not parse (see `shared_models::dead_letter!`).
+//!
+//!     dead_letters [list] [--stream NAME] [--service NAME] [--limit N]
+//!     dead_letters reprocess [--stream NAME] [--service NAME] [--limit N]
+//!                  [--dry-run] [--keep]
+//!
+//! `list` prints each letter as a JSON line along with whether its payload
+//! parses under the current schemas. `reprocess` appends every letter that
+//! now parses back onto its source stream under the field it came from, then
+//! deletes it from the dead-letter stream unless `--keep` is given; letters
+//! that still fail are left where they are. An entry dead-lettered by several
+//! consumers is only replayed once. Replayed entries get new ids, so market
+//! events older than the executor's staleness cutoff are recorded but not
+//! traded on.
+
+use anyhow::{anyhow, bail, Context, Result};
+use redis::streams::StreamRangeReply;
+use redis::AsyncCommands;
+use shared_models::{
+    DeadLetter, ExecutionReport, MarketEvent, PositionCloseRequest, StrategyAllocation,
+    StrategySpec, DEAD_LETTER_STREAM,
+};
+use std::collections::HashSet;
+use tracing::{info, level_filters::LevelFilter, warn};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str =
+    "usage: dead_letters [list | reprocess] [--stream NAME] [--service NAME] [--limit N]
+reprocess only: [--dry-run] [--keep]";
+const PAGE_SIZE: usize = 1_000;
+
+struct Args {
+    reprocess: bool,
+    stream: Option<String>,
+    service: Option<String>,
+    limit: Option<usize>,
+    dry_run: bool,
+    keep: bool,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        reprocess: false,
+        stream: None,
+        service: None,
+        limit: None,
+        dry_run: false,
+        keep: false,
+    };
+    let mut iter = std::env::args().skip(1).peekable();
+    if let Some(command) = iter.next_if(|arg| arg == "list" || arg == "reprocess") {
+        args.reprocess = command == "reprocess";
+    }
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--dry-run" => args.dry_run = true,
+            "--keep" => args.keep = true,
+            "--stream" | "--service" | "--limit" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("{} needs a value\n{}", flag, USAGE))?;
+                match flag.as_str() {
+                    "--stream" => args.stream = Some(value),
+                    "--service" => args.service = Some(value),
+                    _ => {
+                        args.limit = Some(
+                            value
+                                .parse()
+                                .with_context(|| format!("{} must be a number", flag))?,
+                        )
+                    }
+                }
+            }
+            _ => bail!("unknown flag {}\n{}", flag, USAGE),
+        }
+    }
+    if !args.reprocess && (args.dry_run || args.keep) {
+        bail!("--dry-run and --keep only apply to reprocess\n{}", USAGE);
+    }
+    Ok(args)
+}
+
+/// Parses a payload with the type its consumers expect for `field`.
+fn validate(field: &str, payload: &str) -> Result<()> {
+    match field {
+        "event" => serde_json::from_str::<MarketEvent>(payload).map(drop),
+        "report" => serde_json::from_str::<ExecutionReport>(payload).map(drop),
+        "request" => serde_json::from_str::<PositionCloseRequest>(payload).map(drop),
+        "spec" => serde_json::from_str::<StrategySpec>(payload).map(drop),
+        "allocations" => serde_json::from_str::<Vec<StrategyAllocation>>(payload).map(drop),
+        _ => bail!("no schema known for field '{}'", field),
+    }
+    .map_err(Into::into)
+}
+
+/// Dead letters matching the filters, oldest first, with their own ids.
+async fn read_letters(
+    conn: &mut redis::aio::MultiplexedConnection,
+    args: &Args,
+) -> Result<Vec<(String, DeadLetter)>> {
+    let mut letters = Vec::new();
+    let mut start = "-".to_string();
+    loop {
+        let reply: StreamRangeReply = conn
+            .xrange_count(DEAD_LETTER_STREAM, &start, "+", PAGE_SIZE)
+            .await?;
+        let Some(last) = reply.ids.last() else {
+            break;
+        };
+        start = format!("({}", last.id); // Exclusive
+        for entry in &reply.ids {
+            let letter = entry
+                .get::<String>("letter")
+                .and_then(|json| serde_json::from_str::<DeadLetter>(&json).ok());
+            let Some(letter) = letter else {
+                warn!(id = %entry.id, "Skipping malformed dead letter.");
+                continue;
+            };
+            if args
+                .stream
+                .as_ref()
+                .is_some_and(|s| *s != letter.source_stream)
+                || args.service.as_ref().is_some_and(|s| *s != letter.service)
+            {
+                continue;
+            }
+            letters.push((entry.id.clone(), letter));
+            if args.limit.is_some_and(|limit| letters.len() >= limit) {
+                return Ok(letters);
+            }
+        }
+        if reply.ids.len() < PAGE_SIZE {
+            break;
+        }
+    }
+    Ok(letters)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+
+    let args = parse_args()?;
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let mut conn = redis::Client::open(redis_url)?
+        .get_multiplexed_async_connection()
+        .await?;
+    let letters = read_letters(&mut conn, &args).await?;
+
+    if !args.reprocess {
+        for (id, letter) in &letters {
+            let parses_now = validate(&letter.field, &letter.payload).is_ok();
+            println!(
+                "{}",
+                serde_json::json!({ "id": id, "letter": letter, "parses_now": parses_now })
+            );
+        }
+        return Ok(());
+    }
+
+    let (mut replayed, mut failing) = (0, 0);
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    for (id, letter) in &letters {
+        if let Err(e) = validate(&letter.field, &letter.payload) {
+            warn!(id = %id, stream = %letter.source_stream, error = %e, "Still unparseable; leaving it.");
+            failing += 1;
+            continue;
+        }
+        let first = seen.insert((letter.source_stream.clone(), letter.source_id.clone()));
+        if args.dry_run {
+            if first {
+                info!(id = %id, stream = %letter.source_stream, source_id = %letter.source_id, "Would replay.");
+                replayed += 1;
+            }
+            continue;
+        }
+        if first {
+            let new_id: String = conn
+                .xadd(
+                    &letter.source_stream,
+                    "*",
+                    &[(letter.field.as_str(), letter.payload.as_str())],
+                )
+                .await?;
+            info!(id = %id, stream = %letter.source_stream, source_id = %letter.source_id, %new_id, "Replayed.");
+            replayed += 1;
+        }
+        if !args.keep {
+            conn.xdel::<_, _, ()>(DEAD_LETTER_STREAM, &[id]).await?;
+        }
+    }
+    info!(
+        "{} {} entries; {} still unparseable.",
+        if args.dry_run {
+            "Would replay"
+        } else {
+            "Replayed"
+        },
+        replayed,
+        failing
+    );
+    Ok(())
+}
@@ -0,0 +1,485 @@
+// executor/src/bin/memectl.rs
+//! Operator CLI for the common incident-time chores, so none of them need a
+//! hand-typed redis-cli command:
+//!
+//!     memectl strategies
+//!     memectl pause STRATEGY_ID | resume STRATEGY_ID
+//!     memectl positions [--strategy ID]
+//!     memectl flatten TRADE_ID | --strategy ID [--reason TEXT]
+//!     memectl portfolio
+//!     memectl alerts
+//!     memectl kill-switch [pause [REASON] | halt [REASON] | resume]
+//!     memectl flags
+//!     memectl flag NAME on|off|clear
+//!
+//! `pause` adds the strategy to `paused_strategies` and nudges the executor
+//! over `strategy_control_channel`; a paused strategy keeps its positions and
+//! state but acts on no events until resumed. `flatten` asks the position
+//! manager to close at market, the same request a strategy's own exits make.
+//! `kill-switch` shows or sets the portfolio-wide trading state that the
+//! portfolio monitor, risk guardian and wallet guard pause on their own; only
+//! an operator can lift their pauses early or enter and leave a halt, which
+//! nothing automated overrides. `alerts` prints alerts as they are published
+//! until interrupted. `flag` sets or clears a feature flag in this
+//! environment's `flags:{MEMESNIPE_ENV}` hash and tells services to re-read it.
+//!
+//! Reads REDIS_URL, MEMESNIPE_ENV, DATABASE_PATH (for positions and PnL) and
+//! RISK_GUARDIAN_URL (default http://risk_guardian:7200).
+
+#[path = "../database.rs"]
+#[allow(dead_code)]
+mod database;
+
+use anyhow::{anyhow, bail, Context, Result};
+use database::Database;
+use futures::StreamExt;
+use redis::streams::StreamRangeReply;
+use redis::AsyncCommands;
+use shared_config::flags;
+use shared_models::{
+    PositionCloseRequest, StrategyAllocation, TradingState, TradingStateRecord,
+    KILL_SWITCH_CHANNEL, PAUSED_STRATEGIES_KEY, STRATEGY_CONTROL_CHANNEL, TRADING_STATE_KEY,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+const USAGE: &str = "usage: memectl <command>
+  strategies                              allocations, pause state and PnL per strategy
+  pause STRATEGY_ID | resume STRATEGY_ID  stop or restart one strategy's trading
+  positions [--strategy ID]               open positions
+  flatten TRADE_ID | --strategy ID [--reason TEXT]
+                                          close positions at market
+  portfolio                               PnL and risk
+  alerts                                  follow alerts until interrupted
+  kill-switch [pause [REASON] | halt [REASON] | resume]
+                                          show, pause, halt or resume all trading
+  flags                                   feature flags set in this environment
+  flag NAME on|off|clear                  set a feature flag or return it to its default";
+const ALLOCATIONS_STREAM: &str = "allocations_channel";
+const POSITION_CLOSE_REQUESTS_STREAM: &str = "position_close_requests";
+const ALERTS_CHANNEL: &str = "alerts";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+enum Command {
+    Strategies,
+    Pause(String),
+    Resume(String),
+    Positions {
+        strategy: Option<String>,
+    },
+    Flatten {
+        target: FlattenTarget,
+        reason: String,
+    },
+    Portfolio,
+    Alerts,
+    KillSwitch(Option<TradingState>), // None shows the current state
+    Flags,
+    SetFlag {
+        name: String,
+        value: Option<bool>,
+    },
+}
+
+enum FlattenTarget {
+    Trade(i64),
+    Strategy(String),
+}
+
+fn parse_args() -> Result<Command> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        std::process::exit(0);
+    }
+    let Some((command, rest)) = args.split_first() else {
+        bail!("{}", USAGE);
+    };
+    let mut strategy = None;
+    let mut reason = None;
+    let mut positional = Vec::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--strategy" | "--reason" => {
+                let value = iter
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("{} needs a value\n{}", arg, USAGE))?;
+                if arg == "--strategy" {
+                    strategy = Some(value);
+                } else {
+                    reason = Some(value);
+                }
+            }
+            flag if flag.starts_with("--") => bail!("unknown flag {}\n{}", flag, USAGE),
+            _ => positional.push(arg.clone()),
+        }
+    }
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+
+    let parsed = match (command.as_str(), positional.as_slice()) {
+        ("strategies", []) => Command::Strategies,
+        ("pause", [id]) => Command::Pause(id.to_string()),
+        ("resume", [id]) => Command::Resume(id.to_string()),
+        ("positions", []) => Command::Positions {
+            strategy: strategy.take(),
+        },
+        ("flatten", rest) => {
+            let target = match (rest, strategy.take()) {
+                ([trade_id], None) => FlattenTarget::Trade(
+                    trade_id
+                        .parse()
+                        .with_context(|| format!("{} is not a trade id", trade_id))?,
+                ),
+                ([], Some(id)) => FlattenTarget::Strategy(id),
+                _ => bail!("flatten takes a trade id or --strategy ID\n{}", USAGE),
+            };
+            Command::Flatten {
+                target,
+                reason: reason
+                    .take()
+                    .unwrap_or_else(|| "memectl flatten".to_string()),
+            }
+        }
+        ("portfolio", []) => Command::Portfolio,
+        ("alerts", []) => Command::Alerts,
+        ("kill-switch", []) => Command::KillSwitch(None),
+        ("kill-switch", [stop @ ("pause" | "halt"), words @ ..]) => {
+            let reason = if words.is_empty() {
+                "MANUAL".to_string()
+            } else {
+                words.join(" ")
+            };
+            Command::KillSwitch(Some(if *stop == "halt" {
+                TradingState::Halted { reason }
+            } else {
+                TradingState::Paused { reason }
+            }))
+        }
+        ("kill-switch", ["resume"]) => Command::KillSwitch(Some(TradingState::Active)),
+        ("flags", []) => Command::Flags,
+        ("flag", [name, "clear"]) => Command::SetFlag {
+            name: name.to_string(),
+            value: None,
+        },
+        ("flag", [name, value]) => Command::SetFlag {
+            name: name.to_string(),
+            value: Some(
+                flags::parse_value(value)
+                    .ok_or_else(|| anyhow!("flag value must be on, off or clear\n{}", USAGE))?,
+            ),
+        },
+        _ => bail!("{}", USAGE),
+    };
+    if strategy.is_some() || reason.is_some() {
+        bail!(
+            "--strategy and --reason do not apply to {}\n{}",
+            command,
+            USAGE
+        );
+    }
+    Ok(parsed)
+}
+
+fn open_database() -> Result<Database> {
+    let path = std::env::var("DATABASE_PATH").context("DATABASE_PATH must be set")?;
+    Database::new(&path)
+}
+
+/// The allocations in the newest `allocations_channel` entry.
+async fn latest_allocations(
+    conn: &mut redis::aio::MultiplexedConnection,
+) -> Result<Vec<StrategyAllocation>> {
+    let reply: StreamRangeReply = conn
+        .xrevrange_count(ALLOCATIONS_STREAM, "+", "-", 1)
+        .await?;
+    let Some(entry) = reply.ids.first() else {
+        return Ok(Vec::new());
+    };
+    let raw: String = entry
+        .get("allocations")
+        .ok_or_else(|| anyhow!("allocation entry {} has no allocations field", entry.id))?;
+    serde_json::from_str(&raw).with_context(|| format!("allocation entry {}", entry.id))
+}
+
+async fn strategies(conn: &mut redis::aio::MultiplexedConnection) -> Result<()> {
+    let allocations = latest_allocations(conn).await?;
+    let paused: HashSet<String> = conn.smembers(PAUSED_STRATEGIES_KEY).await?;
+    let db = open_database()?;
+    let pnl: BTreeMap<String, f64> = db.get_realized_pnl_by_strategy()?.into_iter().collect();
+    let mut open: BTreeMap<String, usize> = BTreeMap::new();
+    for trade in db.get_open_trades()? {
+        *open.entry(trade.strategy_id).or_default() += 1;
+    }
+
+    // Strategies with history but no current allocation are shown too
+    let mut ids: Vec<String> = allocations.iter().map(|a| a.id.clone()).collect();
+    for id in pnl.keys().chain(open.keys()).chain(paused.iter()) {
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+    println!(
+        "{:<32} {:>7} {:<6} {:<7} {:>5} {:>12}",
+        "STRATEGY", "WEIGHT", "MODE", "STATE", "OPEN", "REALIZED"
+    );
+    for id in ids {
+        let allocation = allocations.iter().find(|a| a.id == id);
+        println!(
+            "{:<32} {:>7} {:<6} {:<7} {:>5} {:>12.2}",
+            id,
+            allocation.map_or_else(|| "-".to_string(), |a| format!("{:.3}", a.weight)),
+            allocation.map_or_else(|| "-".to_string(), |a| format!("{:?}", a.mode)),
+            if paused.contains(&id) {
+                "paused"
+            } else {
+                "active"
+            },
+            open.get(&id).copied().unwrap_or(0),
+            pnl.get(&id).copied().unwrap_or(0.0),
+        );
+    }
+    Ok(())
+}
+
+async fn set_paused(
+    conn: &mut redis::aio::MultiplexedConnection,
+    id: &str,
+    pause: bool,
+) -> Result<()> {
+    if pause {
+        conn.sadd::<_, _, ()>(PAUSED_STRATEGIES_KEY, id).await?;
+    } else {
+        conn.srem::<_, _, ()>(PAUSED_STRATEGIES_KEY, id).await?;
+    }
+    let command = format!("{} {}", if pause { "PAUSE" } else { "RESUME" }, id);
+    let receivers: i64 = conn.publish(STRATEGY_CONTROL_CHANNEL, &command).await?;
+    println!(
+        "{} {}{}",
+        if pause { "Paused" } else { "Resumed" },
+        id,
+        if receivers == 0 {
+            " (no executor listening; it applies on start)"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+fn positions(strategy: Option<&str>) -> Result<()> {
+    let trades = open_database()?.get_open_trades()?;
+    println!(
+        "{:>6} {:<24} {:<10} {:<5} {:<5} {:>10} {:>14} {:<20}",
+        "TRADE", "STRATEGY", "SYMBOL", "SIDE", "MODE", "USD", "ENTRY", "OPENED"
+    );
+    for trade in trades
+        .iter()
+        .filter(|t| strategy.is_none() || strategy == Some(t.strategy_id.as_str()))
+    {
+        let opened = chrono::DateTime::from_timestamp(trade.entry_time, 0).map_or_else(
+            || trade.entry_time.to_string(),
+            |t| t.format("%F %T").to_string(),
+        );
+        println!(
+            "{:>6} {:<24} {:<10} {:<5} {:<5} {:>10.2} {:>14.8} {:<20}",
+            trade.id,
+            trade.strategy_id,
+            trade.symbol,
+            trade.side,
+            trade.mode,
+            trade.amount_usd,
+            trade.entry_price_usd,
+            opened
+        );
+    }
+    Ok(())
+}
+
+async fn flatten(
+    conn: &mut redis::aio::MultiplexedConnection,
+    target: FlattenTarget,
+    reason: String,
+) -> Result<()> {
+    let trades = open_database()?.get_open_trades()?;
+    let selected: Vec<_> = match &target {
+        FlattenTarget::Trade(id) => trades.iter().filter(|t| t.id == *id).collect(),
+        FlattenTarget::Strategy(id) => trades.iter().filter(|t| t.strategy_id == *id).collect(),
+    };
+    if selected.is_empty() {
+        bail!("no matching open positions");
+    }
+    for trade in selected {
+        let request = PositionCloseRequest {
+            timestamp: chrono::Utc::now().timestamp(),
+            trade_id: trade.id,
+            strategy_id: trade.strategy_id.clone(),
+            reason: reason.clone(),
+        };
+        conn.xadd::<_, _, _, _, ()>(
+            POSITION_CLOSE_REQUESTS_STREAM,
+            "*",
+            &[("request", serde_json::to_string(&request)?)],
+        )
+        .await?;
+        println!(
+            "Requested close of trade {} ({} {}).",
+            trade.id, trade.strategy_id, trade.symbol
+        );
+    }
+    Ok(())
+}
+
+async fn portfolio(conn: &mut redis::aio::MultiplexedConnection) -> Result<()> {
+    let db = open_database()?;
+    let open = db.get_open_trades()?;
+    let exposure: f64 = open.iter().map(|t| t.amount_usd).sum();
+    println!("Trading state:   {}", trading_state(conn).await?.state);
+    println!("Realized PnL:    {:>12.2} USD", db.get_total_pnl()?);
+    println!("Open positions:  {:>12}", open.len());
+    println!("Open exposure:   {:>12.2} USD", exposure);
+
+    let url = std::env::var("RISK_GUARDIAN_URL")
+        .unwrap_or_else(|_| "http://risk_guardian:7200".to_string());
+    let risk = reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()?
+        .get(format!("{}/risk", url.trim_end_matches('/')))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match risk {
+        Ok(response) => {
+            let risk: serde_json::Value = response.json().await?;
+            println!("Risk:\n{}", serde_json::to_string_pretty(&risk)?);
+        }
+        // PnL is still worth showing when the risk guardian is the thing that's down
+        Err(e) => println!("Risk:            unavailable ({})", e),
+    }
+    Ok(())
+}
+
+async fn tail_alerts(client: &redis::Client) -> Result<()> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub
+        .subscribe(&[ALERTS_CHANNEL, KILL_SWITCH_CHANNEL])
+        .await?;
+    let mut messages = pubsub.on_message();
+    while let Some(msg) = messages.next().await {
+        let payload: String = msg.get_payload().unwrap_or_default();
+        println!(
+            "{} [{}] {}",
+            chrono::Utc::now().format("%F %T"),
+            msg.get_channel_name(),
+            payload
+        );
+    }
+    bail!("alert subscription closed")
+}
+
+async fn trading_state(conn: &mut redis::aio::MultiplexedConnection) -> Result<TradingStateRecord> {
+    let raw: Option<String> = conn.get(TRADING_STATE_KEY).await?;
+    Ok(TradingStateRecord::from_stored(raw.as_deref()))
+}
+
+async fn kill_switch(
+    conn: &mut redis::aio::MultiplexedConnection,
+    state: Option<TradingState>,
+) -> Result<()> {
+    let current = trading_state(conn).await?;
+    let Some(state) = state else {
+        match current.timestamp {
+            0 => println!("{}", current.state),
+            at => println!(
+                "{} (set by {} at {})",
+                current.state,
+                current.set_by,
+                chrono::DateTime::from_timestamp(at, 0)
+                    .map_or_else(|| at.to_string(), |at| at.format("%F %T").to_string())
+            ),
+        }
+        return Ok(());
+    };
+    // An operator may make any change, so this skips `set_trading_state!`'s checks
+    let record = TradingStateRecord {
+        state,
+        set_by: "operator".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+    };
+    let payload = serde_json::to_string(&record)?;
+    let _: () = conn.set(TRADING_STATE_KEY, &payload).await?;
+    let receivers: i64 = conn.publish(KILL_SWITCH_CHANNEL, &payload).await?;
+    println!(
+        "Trading state {} -> {}; {} receivers notified.",
+        current.state, record.state, receivers
+    );
+    if receivers == 0 {
+        println!(
+            "Nothing is subscribed to {}; services read it when they start.",
+            KILL_SWITCH_CHANNEL
+        );
+    }
+    Ok(())
+}
+
+async fn show_flags(conn: &mut redis::aio::MultiplexedConnection) -> Result<()> {
+    let key = flags::flags_key();
+    let set: BTreeMap<String, String> = conn.hgetall(&key).await?;
+    if set.is_empty() {
+        println!("No flags set in {}; every flag is at its default.", key);
+    }
+    for (name, value) in set {
+        println!("{:<40} {}", name, value);
+    }
+    Ok(())
+}
+
+async fn set_flag(
+    conn: &mut redis::aio::MultiplexedConnection,
+    name: &str,
+    value: Option<bool>,
+) -> Result<()> {
+    let key = flags::flags_key();
+    match value {
+        Some(on) => {
+            conn.hset::<_, _, _, ()>(&key, name, if on { "on" } else { "off" })
+                .await?
+        }
+        None => conn.hdel::<_, _, ()>(&key, name).await?,
+    }
+    conn.publish::<_, _, ()>(flags::FLAGS_UPDATES_CHANNEL, name)
+        .await?;
+    println!(
+        "{} {} in {}.",
+        name,
+        match value {
+            Some(true) => "on",
+            Some(false) => "off",
+            None => "cleared",
+        },
+        key
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let command = parse_args()?;
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let client = redis::Client::open(redis_url)?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    match command {
+        Command::Strategies => strategies(&mut conn).await,
+        Command::Pause(id) => set_paused(&mut conn, &id, true).await,
+        Command::Resume(id) => set_paused(&mut conn, &id, false).await,
+        Command::Positions { strategy } => positions(strategy.as_deref()),
+        Command::Flatten { target, reason } => flatten(&mut conn, target, reason).await,
+        Command::Portfolio => portfolio(&mut conn).await,
+        Command::KillSwitch(state) => kill_switch(&mut conn, state).await,
+        Command::Alerts => tail_alerts(&client).await,
+        Command::Flags => show_flags(&mut conn).await,
+        Command::SetFlag { name, value } => set_flag(&mut conn, &name, value).await,
+    }
+}
@@ -1,67 +1,234 @@
 // executor/src/config.rs
 use lazy_static::lazy_static;
-use std::env;
+use shared_config::{Flags, Live, Loader, Profile, Tunables};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The data consumers' heartbeat names and the event types each produces.
+const DEFAULT_FEED_SOURCES: &str = "pyth_consumer:SolPrice|Price,\
+    market_data_consumer:Price|Depth,\
+    geyser_consumer:Depth|OnChain,\
+    helius_consumer:OnChain,\
+    twitter_consumer:Social,\
+    telegram_consumer:Social,\
+    farcaster_consumer:Social,\
+    drift_consumer:Funding,\
+    bridge_consumer:Bridge";
 
 pub struct Config {
+    pub profile: Profile, // MEMESNIPE_ENV; only mainnet-live trades Live
     pub paper_trading_mode: bool,
     pub jito_auth_keypair_path: String,
     pub solana_rpc_url: String,
     pub jito_rpc_url: String,
     pub signer_url: String,
-    pub global_max_position_usd: f64,
-    pub portfolio_stop_loss_percent: f64,
     pub trailing_stop_loss_percent: f64, // NEW: For position_manager
     pub jupiter_api_url: String,
-    pub slippage_bps: u16,
-    pub jito_tip_lamports: u64,
     pub database_path: String,
     pub redis_url: String,
     pub helius_api_key: String,       // NEW: For data consumers
     pub pyth_api_key: String,         // NEW: For data consumers
     pub twitter_bearer_token: String, // NEW: For data consumers
     pub drift_api_url: String,        // NEW: For data consumers
+    pub order_poll_interval_secs: u64, // How often resting Limit/StopEntry orders re-quote
+    pub stop_entry_max_wait_secs: u64, // StopEntry orders are dropped after this long untriggered
+    pub shutdown_drain_timeout_secs: u64, // Per-strategy drain budget on SIGTERM
+    pub strategy_queue_capacity: usize,   // Per-strategy inbound event queue size
+    pub drop_oldest_event_types: Vec<String>, // EventTypes whose oldest queued event is evicted when full
+    pub strategy_restart_base_backoff_secs: u64, // First delay before restarting a panicked strategy
+    pub strategy_restart_max_backoff_secs: u64,  // Cap on the exponential restart delay
+    pub strategy_crash_alert_threshold: u32,     // Consecutive crashes before alerting
+    pub strategy_snapshot_interval_secs: u64,    // How often strategy state is persisted (0 = only on stop)
+    pub strategy_snapshot_max_age_secs: i64,     // Older snapshots are discarded instead of restored
+    pub strategy_warmup_minutes: i64,            // History replayed into a starting strategy (0 = none)
+    pub strategy_warmup_max_events: usize,       // Cap on replayed events per stream
+    pub shadow_hold_secs: i64,                   // Holding period of a shadow variant's hypothetical position
+    pub shadow_report_interval_secs: u64,        // How often shadow comparison reports are published
+    pub max_positions_per_strategy_token: u32, // Overlapping positions one strategy may hold in a token
+    pub max_open_tokens_per_strategy: u32,     // Distinct tokens one strategy may hold at once
+    pub max_positions_per_token: u32,          // Positions across all strategies in one token
+    pub position_refresh_interval_secs: u64,   // How often open positions are re-read from the DB
+    pub portfolio_capital_usd: f64,            // Capital split across strategies by allocation weight
+    pub execution_concurrency: usize,          // Fills executed at once by the execution queue
+    pub raydium_api_url: String,               // Pool-key lookups for direct Raydium swaps
+    pub orca_api_url: String,                  // Whirlpool lookups for direct Orca swaps
+    pub submission_fallback_chain: Vec<String>, // Ordered live submission paths (jito, rpc); exhausting it aborts
+    pub token_safety_cache_ttl_secs: u64,       // How long a token safety verdict is reused
+    pub safety_reject_mint_authority: bool,     // Reject mints whose mint authority isn't revoked
+    pub safety_reject_freeze_authority: bool,   // Reject mints with a freeze authority
+    pub safety_max_transfer_fee_bps: u16,       // Highest Token-2022 transfer fee accepted
+    pub safety_max_top_holder_pct: f64,         // Max share of supply in the top 10 non-pool holders
+    pub safety_min_lp_locked_pct: f64,          // Min share of Raydium LP burned or held by a locker
+    pub safety_lp_locker_owners: Vec<String>,   // Owners whose LP holdings count as locked
+    pub depth_max_age_secs: i64,                // DepthEvents older than this fall back to quote impact
+    pub downsize_on_price_impact: bool,         // Re-quote smaller instead of rejecting outright
+    pub fill_confirm_timeout_secs: u64,         // How long to wait for a live swap to confirm
+    pub partial_fill_threshold: f64,            // Fills below this share of the quote are partial
+    pub resubmit_partial_remainder: bool,       // Re-buy the unfilled remainder once instead of shrinking
+    pub min_remainder_usd: f64,                 // Smaller remainders are never re-submitted
+    pub swap_max_attempts: u32,                 // Quote->sign->submit attempts before a trade is FAILED
+    pub swap_retry_base_backoff_ms: u64,        // Delay after the first failed attempt, doubled per retry
+    pub swap_retry_max_backoff_ms: u64,         // Cap on the retry delay
+    pub sol_price_max_age_secs: i64,            // Streamed SOL/USD older than this falls back to Pyth
+    pub pyth_hermes_url: String,                // Hermes endpoint for the Pyth fallback
+    pub pyth_sol_usd_feed_id: String,           // Pyth SOL/USD price feed id
+    pub pyth_max_age_secs: i64,                 // Pyth prices published longer ago than this are rejected
+    pub pyth_max_conf_ratio: f64,               // Pyth prices with confidence/price above this are rejected
+    pub feed_sources: HashMap<String, Vec<String>>, // Heartbeat source -> EventTypes it produces
+    pub feed_stale_after_secs: i64,             // A source silent this long is stale
+    pub replay_mode: bool,                      // EXECUTOR_MODE=replay: archived events in, paper trades only
+    pub replay_dir: String,                     // Recorder archive root read in replay mode
+    pub replay_from: Option<String>,            // First archived day replayed (YYYY-MM-DD, inclusive)
+    pub replay_to: Option<String>,              // Last archived day replayed (YYYY-MM-DD, inclusive)
+    pub replay_speed: f64,                      // Replay speed-up over recorded time
+    pub dry_run: bool,                          // Live trades stop before signing and log the transaction
+    pub tunables: Arc<Live<ExecutorTunables>>,  // Re-read on config changes while running
+    pub flags: Arc<Flags>,                      // Runtime subsystem switches from flags:{MEMESNIPE_ENV}
+}
+
+/// The settings that can change without a restart; read them through
+/// `CONFIG.tunables.get()` where they're used.
+pub struct ExecutorTunables {
+    pub global_max_position_usd: f64,
+    pub portfolio_stop_loss_percent: f64,
+    pub slippage_bps: u16,
+    pub max_slippage_bps: u16,           // Global cap on per-trade slippage tolerance
+    pub slippage_impact_multiplier: f64, // Tolerance = expected price impact x this
+    pub max_price_impact_pct: f64,       // Live buys quoting more impact than this are rejected
+    pub jito_tip_lamports: u64,
+    pub rpc_priority_fee_micro_lamports: u64, // Compute-unit price used on the RPC fallback path
+}
+
+impl Tunables for ExecutorTunables {
+    fn load(c: &mut Loader) -> Self {
+        let tunables = Self {
+            global_max_position_usd: c.required("GLOBAL_MAX_POSITION_USD"),
+            portfolio_stop_loss_percent: c.required("PORTFOLIO_STOP_LOSS_PERCENT"),
+            slippage_bps: c.required("SLIPPAGE_BPS"),
+            max_slippage_bps: c.get("MAX_SLIPPAGE_BPS", 1500),
+            slippage_impact_multiplier: c.get("SLIPPAGE_IMPACT_MULTIPLIER", 2.0),
+            max_price_impact_pct: c.get("MAX_PRICE_IMPACT_PCT", 0.05),
+            jito_tip_lamports: c.required("JITO_TIP_LAMPORTS"),
+            rpc_priority_fee_micro_lamports: c.get("RPC_PRIORITY_FEE_MICRO_LAMPORTS", 100_000),
+        };
+        c.check(
+            tunables.slippage_bps <= tunables.max_slippage_bps,
+            "SLIPPAGE_BPS must not exceed MAX_SLIPPAGE_BPS",
+        );
+        c.check(tunables.slippage_bps < 10_000, "SLIPPAGE_BPS must be below 10000");
+        tunables
+    }
 }
 
 impl Config {
     fn load() -> Self {
-        Self {
-            paper_trading_mode: env::var("PAPER_TRADING_MODE")
-                .unwrap_or_else(|_| "true".to_string())
-                == "true",
-            jito_auth_keypair_path: env::var("JITO_AUTH_KEYPAIR_FILENAME")
-                .expect("JITO_AUTH_KEYPAIR_FILENAME must be set"),
-            solana_rpc_url: env::var("SOLANA_RPC_URL").expect("SOLANA_RPC_URL must be set"),
-            jito_rpc_url: env::var("JITO_RPC_URL").expect("JITO_RPC_URL must be set"),
-            signer_url: env::var("SIGNER_URL").expect("SIGNER_URL must be set"),
-            global_max_position_usd: env::var("GLOBAL_MAX_POSITION_USD")
-                .expect("GLOBAL_MAX_POSITION_USD must be set")
-                .parse()
-                .expect("GLOBAL_MAX_POSITION_USD must be a valid number"),
-            portfolio_stop_loss_percent: env::var("PORTFOLIO_STOP_LOSS_PERCENT")
-                .expect("PORTFOLIO_STOP_LOSS_PERCENT must be set")
-                .parse()
-                .expect("PORTFOLIO_STOP_LOSS_PERCENT must be a valid number"),
-            trailing_stop_loss_percent: env::var("TRAILING_STOP_LOSS_PERCENT")
-                .expect("TRAILING_STOP_LOSS_PERCENT must be set")
-                .parse()
-                .expect("TRAILING_STOP_LOSS_PERCENT must be a valid number"),
-            jupiter_api_url: env::var("JUPITER_API_URL").expect("JUPITER_API_URL must be set"),
-            slippage_bps: env::var("SLIPPAGE_BPS")
-                .expect("SLIPPAGE_BPS must be set")
-                .parse()
-                .expect("SLIPPAGE_BPS must be a valid number"),
-            jito_tip_lamports: env::var("JITO_TIP_LAMPORTS")
-                .expect("JITO_TIP_LAMPORTS must be set")
-                .parse()
-                .expect("JITO_TIP_LAMPORTS must be a valid number"),
-            database_path: env::var("DATABASE_PATH").expect("DATABASE_PATH must be set"),
-            redis_url: env::var("REDIS_URL").expect("REDIS_URL must be set"),
-            helius_api_key: env::var("HELIUS_API_KEY").expect("HELIUS_API_KEY must be set"),
-            pyth_api_key: env::var("PYTH_API_KEY").expect("PYTH_API_KEY must be set"),
-            twitter_bearer_token: env::var("TWITTER_BEARER_TOKEN")
-                .expect("TWITTER_BEARER_TOKEN must be set"),
-            drift_api_url: env::var("DRIFT_API_URL").expect("DRIFT_API_URL must be set"),
-        }
+        let mut c = Loader::new("executor");
+        let config = Self {
+            profile: c.profile(),
+            paper_trading_mode: c.flag("PAPER_TRADING_MODE", true),
+            jito_auth_keypair_path: c.required("JITO_AUTH_KEYPAIR_FILENAME"),
+            solana_rpc_url: c.required("SOLANA_RPC_URL"),
+            jito_rpc_url: c.required("JITO_RPC_URL"),
+            signer_url: c.required("SIGNER_URL"),
+            trailing_stop_loss_percent: c.required("TRAILING_STOP_LOSS_PERCENT"),
+            jupiter_api_url: c.required("JUPITER_API_URL"),
+            database_path: c.required("DATABASE_PATH"),
+            redis_url: c.required("REDIS_URL"),
+            helius_api_key: c.required("HELIUS_API_KEY"),
+            pyth_api_key: c.required("PYTH_API_KEY"),
+            twitter_bearer_token: c.required("TWITTER_BEARER_TOKEN"),
+            drift_api_url: c.required("DRIFT_API_URL"),
+            order_poll_interval_secs: c.get("ORDER_POLL_INTERVAL_SECS", 2),
+            stop_entry_max_wait_secs: c.get("STOP_ENTRY_MAX_WAIT_SECS", 3600),
+            shutdown_drain_timeout_secs: c.get("SHUTDOWN_DRAIN_TIMEOUT_SECS", 10),
+            strategy_queue_capacity: c.get("STRATEGY_QUEUE_CAPACITY", 100),
+            drop_oldest_event_types: c.list("DROP_OLDEST_EVENT_TYPES", "Price,SolPrice,Depth"),
+            strategy_restart_base_backoff_secs: c.get("STRATEGY_RESTART_BASE_BACKOFF_SECS", 1),
+            strategy_restart_max_backoff_secs: c.get("STRATEGY_RESTART_MAX_BACKOFF_SECS", 300),
+            strategy_crash_alert_threshold: c.get("STRATEGY_CRASH_ALERT_THRESHOLD", 3),
+            strategy_snapshot_interval_secs: c.get("STRATEGY_SNAPSHOT_INTERVAL_SECS", 60),
+            strategy_snapshot_max_age_secs: c.get("STRATEGY_SNAPSHOT_MAX_AGE_SECS", 3600),
+            strategy_warmup_minutes: c.get("STRATEGY_WARMUP_MINUTES", 30),
+            strategy_warmup_max_events: c.get("STRATEGY_WARMUP_MAX_EVENTS", 5000),
+            shadow_hold_secs: c.get("SHADOW_HOLD_SECS", 900),
+            shadow_report_interval_secs: c.get("SHADOW_REPORT_INTERVAL_SECS", 300),
+            max_positions_per_strategy_token: c.get("MAX_POSITIONS_PER_STRATEGY_TOKEN", 1),
+            max_open_tokens_per_strategy: c.get("MAX_OPEN_TOKENS_PER_STRATEGY", 5),
+            max_positions_per_token: c.get("MAX_POSITIONS_PER_TOKEN", 3),
+            position_refresh_interval_secs: c.get("POSITION_REFRESH_INTERVAL_SECS", 10),
+            portfolio_capital_usd: c.get("PORTFOLIO_CAPITAL_USD", 1000.0),
+            execution_concurrency: c.get("EXECUTION_CONCURRENCY", 4),
+            raydium_api_url: c.get("RAYDIUM_API_URL", "https://api-v3.raydium.io".to_string()),
+            orca_api_url: c.get("ORCA_API_URL", "https://api.orca.so/v2/solana".to_string()),
+            submission_fallback_chain: c.list("SUBMISSION_FALLBACK_CHAIN", "jito"),
+            token_safety_cache_ttl_secs: c.get("TOKEN_SAFETY_CACHE_TTL_SECS", 300),
+            safety_reject_mint_authority: c.flag("SAFETY_REJECT_MINT_AUTHORITY", true),
+            safety_reject_freeze_authority: c.flag("SAFETY_REJECT_FREEZE_AUTHORITY", true),
+            safety_max_transfer_fee_bps: c.get("SAFETY_MAX_TRANSFER_FEE_BPS", 0),
+            safety_max_top_holder_pct: c.get("SAFETY_MAX_TOP_HOLDER_PCT", 0.5),
+            safety_min_lp_locked_pct: c.get("SAFETY_MIN_LP_LOCKED_PCT", 0.9),
+            safety_lp_locker_owners: c.list(
+                "SAFETY_LP_LOCKER_OWNERS",
+                "1nc1nerator11111111111111111111111111111111",
+            ),
+            depth_max_age_secs: c.get("DEPTH_MAX_AGE_SECS", 30),
+            downsize_on_price_impact: c.flag("DOWNSIZE_ON_PRICE_IMPACT", false),
+            fill_confirm_timeout_secs: c.get("FILL_CONFIRM_TIMEOUT_SECS", 60),
+            partial_fill_threshold: c.get("PARTIAL_FILL_THRESHOLD", 0.98),
+            resubmit_partial_remainder: c.flag("RESUBMIT_PARTIAL_REMAINDER", false),
+            min_remainder_usd: c.get("MIN_REMAINDER_USD", 5.0),
+            swap_max_attempts: c.get("SWAP_MAX_ATTEMPTS", 3),
+            swap_retry_base_backoff_ms: c.get("SWAP_RETRY_BASE_BACKOFF_MS", 500),
+            swap_retry_max_backoff_ms: c.get("SWAP_RETRY_MAX_BACKOFF_MS", 5000),
+            sol_price_max_age_secs: c.get("SOL_PRICE_MAX_AGE_SECS", 60),
+            pyth_hermes_url: c.get("PYTH_HERMES_URL", "https://hermes.pyth.network".to_string()),
+            pyth_sol_usd_feed_id: c.get(
+                "PYTH_SOL_USD_FEED_ID",
+                "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".to_string(),
+            ),
+            pyth_max_age_secs: c.get("PYTH_MAX_AGE_SECS", 30),
+            pyth_max_conf_ratio: c.get("PYTH_MAX_CONF_RATIO", 0.01),
+            feed_sources: c
+                .list("FEED_SOURCES", DEFAULT_FEED_SOURCES)
+                .iter()
+                .filter_map(|spec| match spec.split_once(':') {
+                    Some((source, types)) => Some((
+                        source.to_string(),
+                        types.split('|').map(|t| t.trim().to_string()).collect(),
+                    )),
+                    None => {
+                        c.invalid("FEED_SOURCES", format!("{} is not SOURCE:Type|Type", spec));
+                        None
+                    }
+                })
+                .collect(),
+            feed_stale_after_secs: c.get("FEED_STALE_AFTER_SECS", 120),
+            replay_mode: match c.get("EXECUTOR_MODE", "live".to_string()).as_str() {
+                "live" => false,
+                "replay" => true,
+                other => {
+                    c.invalid("EXECUTOR_MODE", format!("must be live or replay, got {}", other));
+                    false
+                }
+            },
+            replay_dir: c.get("REPLAY_DIR", "/app/recorder".to_string()),
+            replay_from: c.optional("REPLAY_FROM"),
+            replay_to: c.optional("REPLAY_TO"),
+            replay_speed: c.get("REPLAY_SPEED", 10.0),
+            dry_run: c.flag("DRY_RUN", false),
+            tunables: Live::load(&mut c),
+            flags: Flags::new("executor"),
+        };
+        c.check(config.replay_speed > 0.0, "REPLAY_SPEED must be positive");
+        c.check(
+            config.paper_trading_mode || config.profile.allows_live(),
+            format!(
+                "PAPER_TRADING_MODE=false needs MEMESNIPE_ENV=mainnet-live, not {}",
+                config.profile
+            ),
+        );
+        c.finish();
+        config
     }
 }
 
@@ -1,13 +1,15 @@
 // executor/src/database.rs
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
-use shared_models::OrderDetails;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use shared_models::{OrderDetails, TradeState};
 use std::path::Path;
 use tracing::info;
 
 // --- Trade Record Struct ---
-#[derive(Debug, Clone)] // Added Clone for position_manager
+#[derive(Debug, Clone, Serialize)] // Added Clone for position_manager
 pub struct TradeRecord {
     pub id: i64,
     pub strategy_id: String,
@@ -27,6 +29,27 @@ pub struct TradeRecord {
     pub mode: String,                   // NEW: Paper vs Live mode
 }
 
+/// One row of `trade_transitions`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeTransition {
+    pub from_state: Option<String>,
+    pub to_state: String,
+    pub at: i64,
+    pub note: Option<String>,
+}
+
+/// Everything the database holds on one trade, for its audit trail.
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeJournal {
+    #[serde(flatten)]
+    pub trade: TradeRecord,
+    pub filled_token_amount: Option<f64>,
+    pub filled_amount_usd: Option<f64>,
+    pub exit_profile: Option<Value>,
+    pub triggering_features: Option<Value>,
+    pub transitions: Vec<TradeTransition>, // Oldest first
+}
+
 // --- Database Manager ---
 pub struct Database {
     conn: Connection,
@@ -53,7 +76,7 @@ impl Database {
                 token_address TEXT NOT NULL,
                 symbol TEXT NOT NULL,
                 amount_usd REAL NOT NULL,
-                status TEXT NOT NULL, -- PENDING, OPEN, CLOSED_PROFIT, CLOSED_LOSS, CANCELED
+                status TEXT NOT NULL, -- TradeState: CREATED, RISK_APPROVED, SUBMITTED, CONFIRMED, OPEN, CLOSING, CLOSED, FAILED, CANCELED
                 signature TEXT,
                 entry_time INTEGER NOT NULL,
                 entry_price_usd REAL NOT NULL,
@@ -63,24 +86,65 @@ impl Database {
                 confidence REAL NOT NULL,
                 side TEXT NOT NULL, -- NEW
                 highest_price_usd REAL, -- NEW
-                mode TEXT NOT NULL DEFAULT 'Paper' -- NEW: Track Paper vs Live trades
+                mode TEXT NOT NULL DEFAULT 'Paper', -- NEW: Track Paper vs Live trades
+                filled_token_amount REAL, -- Tokens actually received, from the confirmed tx
+                filled_amount_usd REAL, -- USD actually spent, from the confirmed tx
+                exit_profile TEXT, -- JSON ExitProfile the position manager applies, if any
+                triggering_features TEXT -- JSON features the strategy acted on, if any
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trade_transitions (
+                id INTEGER PRIMARY KEY,
+                trade_id INTEGER NOT NULL,
+                from_state TEXT, -- NULL for the CREATED row
+                to_state TEXT NOT NULL,
+                at INTEGER NOT NULL,
+                note TEXT
             )",
             [],
         )?;
 
-        // Add mode column if it doesn't exist (migration for existing databases)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS shadow_decisions (
+                id INTEGER PRIMARY KEY,
+                strategy_id TEXT NOT NULL,
+                variant TEXT NOT NULL, -- champion (live params) or challenger
+                at INTEGER NOT NULL,
+                token_address TEXT NOT NULL,
+                side TEXT NOT NULL,
+                size_usd REAL NOT NULL,
+                confidence REAL NOT NULL,
+                price_usd REAL, -- Last seen price when the decision was made, if any
+                agreed INTEGER NOT NULL -- 1 if the other variant made the same call
+            )",
+            [],
+        )?;
+
+        // Add columns missing from older databases
+        Self::ensure_column(conn, "mode", "TEXT NOT NULL DEFAULT 'Paper'")?;
+        Self::ensure_column(conn, "filled_token_amount", "REAL")?;
+        Self::ensure_column(conn, "filled_amount_usd", "REAL")?;
+        Self::ensure_column(conn, "exit_profile", "TEXT")?;
+        Self::ensure_column(conn, "triggering_features", "TEXT")?;
+
+        Ok(())
+    }
+
+    fn ensure_column(conn: &Connection, name: &str, definition: &str) -> Result<()> {
         let mut stmt = conn.prepare("PRAGMA table_info(trades)")?;
-        let has_mode_column = stmt
+        let has_column = stmt
             .query_map([], |row| row.get::<_, String>(1))?
-            .any(|col_name| col_name.as_deref() == Ok("mode"));
+            .any(|col_name| col_name.as_deref() == Ok(name));
 
-        if !has_mode_column {
+        if !has_column {
             conn.execute(
-                "ALTER TABLE trades ADD COLUMN mode TEXT NOT NULL DEFAULT 'Paper'",
+                &format!("ALTER TABLE trades ADD COLUMN {} {}", name, definition),
                 [],
             )?;
         }
-
         Ok(())
     }
 
@@ -93,32 +157,89 @@ impl Database {
     ) -> Result<i64> {
         let now: DateTime<Utc> = Utc::now();
         self.conn.execute(
-            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd, mode)
-             VALUES (?1, ?2, ?3, ?4, 'PENDING', ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd, mode, exit_profile, triggering_features)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 strategy_id,
                 details.token_address,
                 details.token_address, // Use address as symbol for now, can be updated later
                 details.suggested_size_usd,
+                TradeState::Created.as_str(),
                 now.timestamp(),
                 entry_price_usd,
                 details.confidence,
                 details.side.to_string(),
                 entry_price_usd, // Initialize highest_price with entry price
                 mode,
+                details
+                    .exit_profile
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?,
+                details
+                    .triggering_features
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?,
             ],
         )?;
-        Ok(self.conn.last_insert_rowid())
+        let trade_id = self.conn.last_insert_rowid();
+        self.conn.execute(
+            "INSERT INTO trade_transitions (trade_id, from_state, to_state, at) VALUES (?1, NULL, ?2, ?3)",
+            params![trade_id, TradeState::Created.as_str(), now.timestamp()],
+        )?;
+        Ok(trade_id)
     }
 
-    pub fn open_trade(&self, trade_id: i64, signature: &str) -> Result<()> {
+    /// Moves a trade to `to` if its stored state allows it, recording the
+    /// transition. Returns the state it moved from.
+    pub fn transition(&self, trade_id: i64, to: TradeState, note: Option<&str>) -> Result<TradeState> {
+        let tx = self.conn.unchecked_transaction()?;
+        let current: String = tx
+            .query_row("SELECT status FROM trades WHERE id = ?1", params![trade_id], |row| row.get(0))
+            .with_context(|| format!("Trade {} not found", trade_id))?;
+        let from: TradeState = current.parse().map_err(anyhow::Error::msg)?;
+        from.transition(to)
+            .with_context(|| format!("Trade {}", trade_id))?;
+
+        let now = Utc::now().timestamp();
+        tx.execute(
+            "UPDATE trades SET status = ?1 WHERE id = ?2",
+            params![to.as_str(), trade_id],
+        )?;
+        tx.execute(
+            "INSERT INTO trade_transitions (trade_id, from_state, to_state, at, note) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![trade_id, from.as_str(), to.as_str(), now, note],
+        )?;
+        tx.commit()?;
+        info!(trade_id, from = %from, to = %to, "Trade state transition.");
+        Ok(from)
+    }
+
+    pub fn set_signature(&self, trade_id: i64, signature: &str) -> Result<()> {
         self.conn.execute(
-            "UPDATE trades SET status = 'OPEN', signature = ?1 WHERE id = ?2",
+            "UPDATE trades SET signature = ?1 WHERE id = ?2",
             params![signature, trade_id],
         )?;
         Ok(())
     }
 
+    /// Stores what a live swap actually filled. The position is resized to the
+    /// filled amount at the realised entry price.
+    pub fn record_fill(
+        &self,
+        trade_id: i64,
+        filled_token_amount: f64,
+        filled_amount_usd: f64,
+        fill_price_usd: f64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE trades SET filled_token_amount = ?1, filled_amount_usd = ?2, amount_usd = ?2, entry_price_usd = ?3, highest_price_usd = ?3 WHERE id = ?4",
+            params![filled_token_amount, filled_amount_usd, fill_price_usd, trade_id],
+        )?;
+        Ok(())
+    }
+
     pub fn get_all_trades(&self) -> Result<Vec<TradeRecord>> {
         let mut stmt = self.conn.prepare("SELECT id, strategy_id, token_address, symbol, amount_usd, status, signature, entry_time, entry_price_usd, close_time, close_price_usd, pnl_usd, confidence, side, highest_price_usd, mode FROM trades ORDER BY entry_time DESC")?;
         let trades_iter = stmt.query_map([], |row| {
@@ -149,8 +270,8 @@ impl Database {
 
     pub fn get_open_trades(&self) -> Result<Vec<TradeRecord>> {
         // NEW: For position_manager
-        let mut stmt = self.conn.prepare("SELECT id, strategy_id, token_address, symbol, amount_usd, status, signature, entry_time, entry_price_usd, close_time, close_price_usd, pnl_usd, confidence, side, highest_price_usd, mode FROM trades WHERE status = 'OPEN'")?;
-        let trades_iter = stmt.query_map([], |row| {
+        let mut stmt = self.conn.prepare("SELECT id, strategy_id, token_address, symbol, amount_usd, status, signature, entry_time, entry_price_usd, close_time, close_price_usd, pnl_usd, confidence, side, highest_price_usd, mode FROM trades WHERE status = ?1")?;
+        let trades_iter = stmt.query_map(params![TradeState::Open.as_str()], |row| {
             Ok(TradeRecord {
                 id: row.get(0)?,
                 strategy_id: row.get(1)?,
@@ -175,17 +296,73 @@ impl Database {
             .map_err(anyhow::Error::from)
     }
 
-    pub fn update_trade_pnl(
-        &self,
-        trade_id: i64,
-        status: &str,
-        close_price_usd: f64,
-        pnl_usd: f64,
-    ) -> Result<()> {
+    /// The trade row and its full transition history, None if no such trade.
+    pub fn get_trade_journal(&self, trade_id: i64) -> Result<Option<TradeJournal>> {
+        let journal = self
+            .conn
+            .query_row(
+                "SELECT id, strategy_id, token_address, symbol, amount_usd, status, signature, entry_time, entry_price_usd, close_time, close_price_usd, pnl_usd, confidence, side, highest_price_usd, mode, filled_token_amount, filled_amount_usd, exit_profile, triggering_features FROM trades WHERE id = ?1",
+                params![trade_id],
+                |row| {
+                    let json = |idx: usize| -> rusqlite::Result<Option<Value>> {
+                        Ok(row
+                            .get::<_, Option<String>>(idx)?
+                            .and_then(|raw| serde_json::from_str(&raw).ok()))
+                    };
+                    Ok(TradeJournal {
+                        trade: TradeRecord {
+                            id: row.get(0)?,
+                            strategy_id: row.get(1)?,
+                            token_address: row.get(2)?,
+                            symbol: row.get(3)?,
+                            amount_usd: row.get(4)?,
+                            status: row.get(5)?,
+                            signature: row.get(6)?,
+                            entry_time: row.get(7)?,
+                            entry_price_usd: row.get(8)?,
+                            close_time: row.get(9)?,
+                            close_price_usd: row.get(10)?,
+                            pnl_usd: row.get(11)?,
+                            confidence: row.get(12)?,
+                            side: row.get(13)?,
+                            highest_price_usd: row.get(14)?,
+                            mode: row.get(15)?,
+                        },
+                        filled_token_amount: row.get(16)?,
+                        filled_amount_usd: row.get(17)?,
+                        exit_profile: json(18)?,
+                        triggering_features: json(19)?,
+                        transitions: Vec::new(),
+                    })
+                },
+            )
+            .optional()?;
+        let Some(mut journal) = journal else {
+            return Ok(None);
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT from_state, to_state, at, note FROM trade_transitions WHERE trade_id = ?1 ORDER BY at, id",
+        )?;
+        journal.transitions = stmt
+            .query_map(params![trade_id], |row| {
+                Ok(TradeTransition {
+                    from_state: row.get(0)?,
+                    to_state: row.get(1)?,
+                    at: row.get(2)?,
+                    note: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, rusqlite::Error>>()?;
+        Ok(Some(journal))
+    }
+
+    /// Records the close of a trade; the status moves separately via `transition`.
+    pub fn update_trade_pnl(&self, trade_id: i64, close_price_usd: f64, pnl_usd: f64) -> Result<()> {
         let now: DateTime<Utc> = Utc::now();
         self.conn.execute(
-            "UPDATE trades SET status = ?1, close_time = ?2, close_price_usd = ?3, pnl_usd = ?4 WHERE id = ?5",
-            params![status, now.timestamp(), close_price_usd, pnl_usd, trade_id],
+            "UPDATE trades SET close_time = ?1, close_price_usd = ?2, pnl_usd = ?3 WHERE id = ?4",
+            params![now.timestamp(), close_price_usd, pnl_usd, trade_id],
         )?;
         Ok(())
     }
@@ -199,12 +376,59 @@ impl Database {
         Ok(())
     }
 
+    /// Records one Execute decision made by a variant under shadow evaluation.
+    pub fn log_shadow_decision(
+        &self,
+        strategy_id: &str,
+        variant: &str,
+        at: i64,
+        details: &OrderDetails,
+        price_usd: Option<f64>,
+        agreed: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO shadow_decisions (strategy_id, variant, at, token_address, side, size_usd, confidence, price_usd, agreed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                strategy_id,
+                variant,
+                at,
+                details.token_address,
+                details.side.to_string(),
+                details.suggested_size_usd,
+                details.confidence,
+                price_usd,
+                agreed,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Flushes the WAL into the main database file; called on shutdown.
+    pub fn checkpoint(&self) -> Result<()> {
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        info!("Database checkpointed.");
+        Ok(())
+    }
+
     pub fn get_total_pnl(&self) -> Result<f64> {
         let total: Option<f64> = self.conn.query_row(
-            "SELECT SUM(pnl_usd) FROM trades WHERE status LIKE 'CLOSED_%'",
+            "SELECT SUM(pnl_usd) FROM trades WHERE status LIKE 'CLOSED%'",
             [],
             |row| row.get(0),
         )?;
         Ok(total.unwrap_or(0.0))
     }
+
+    /// Realized PnL of each strategy's closed trades.
+    pub fn get_realized_pnl_by_strategy(&self) -> Result<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT strategy_id, SUM(pnl_usd) FROM trades \
+             WHERE status LIKE 'CLOSED%' AND pnl_usd IS NOT NULL GROUP BY strategy_id",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<(String, f64)>, rusqlite::Error>>()
+            .map_err(anyhow::Error::from)
+    }
 }
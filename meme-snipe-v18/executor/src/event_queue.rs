@@ -0,0 +1,164 @@
+// executor/src/event_queue.rs
+//! Per-strategy inbound queue. Events that must not be lost (on-chain, social, ...)
+//! go through a bounded mpsc and the router waits for space; high-rate ticks where
+//! only the latest data matters go through a drop-oldest ring so a slow strategy
+//! never stalls the router.
+
+use crate::config::CONFIG;
+use parking_lot::Mutex;
+use shared_models::{EventType, MarketEvent};
+use std::{collections::VecDeque, future::Future, sync::Arc};
+use tokio::sync::mpsc::{self, error::SendError, error::TrySendError, Receiver, Sender};
+use tokio::sync::Notify;
+
+/// How the router behaves when a strategy's queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchPolicy {
+    DropOldest, // Evict the oldest queued tick and enqueue the new one
+    Block,      // Wait for the strategy to make room
+}
+
+impl DispatchPolicy {
+    pub fn for_event(event_type: &EventType) -> Self {
+        let name = format!("{:?}", event_type);
+        if CONFIG.drop_oldest_event_types.iter().any(|t| *t == name) {
+            DispatchPolicy::DropOldest
+        } else {
+            DispatchPolicy::Block
+        }
+    }
+}
+
+struct LossyRing {
+    buf: Mutex<VecDeque<MarketEvent>>,
+    capacity: usize,
+    notify: Notify,
+}
+
+impl LossyRing {
+    /// Returns the evicted event, if the ring was full.
+    fn push(&self, event: MarketEvent) -> Option<MarketEvent> {
+        let evicted = {
+            let mut buf = self.buf.lock();
+            let evicted = if buf.len() >= self.capacity {
+                buf.pop_front()
+            } else {
+                None
+            };
+            buf.push_back(event);
+            evicted
+        };
+        self.notify.notify_one();
+        evicted
+    }
+
+    fn pop(&self) -> Option<MarketEvent> {
+        self.buf.lock().pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.buf.lock().len()
+    }
+}
+
+pub fn channel(capacity: usize) -> (EventSender, EventReceiver) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let ring = Arc::new(LossyRing {
+        buf: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity,
+        notify: Notify::new(),
+    });
+    (
+        EventSender {
+            tx,
+            ring: ring.clone(),
+        },
+        EventReceiver {
+            rx,
+            ring,
+            held: VecDeque::new(),
+        },
+    )
+}
+
+#[derive(Clone)]
+pub struct EventSender {
+    tx: Sender<MarketEvent>,
+    ring: Arc<LossyRing>,
+}
+
+impl EventSender {
+    /// Enqueues `event` according to `policy`. Returns the event evicted to make room, if any.
+    pub async fn dispatch(
+        &self,
+        event: MarketEvent,
+        policy: DispatchPolicy,
+    ) -> Result<Option<MarketEvent>, SendError<MarketEvent>> {
+        if self.tx.is_closed() {
+            return Err(SendError(event));
+        }
+        match policy {
+            DispatchPolicy::DropOldest => Ok(self.ring.push(event)),
+            DispatchPolicy::Block => match self.tx.try_send(event) {
+                Ok(()) => Ok(None),
+                Err(TrySendError::Full(event)) => self.tx.send(event).await.map(|_| None),
+                Err(TrySendError::Closed(event)) => Err(SendError(event)),
+            },
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Events queued but not yet consumed by the strategy.
+    pub fn depth(&self) -> usize {
+        (self.tx.max_capacity() - self.tx.capacity()) + self.ring.len()
+    }
+}
+
+pub struct EventReceiver {
+    rx: Receiver<MarketEvent>,
+    ring: Arc<LossyRing>,
+    held: VecDeque<MarketEvent>, // Taken off the mpsc by `hold_until`, not yet consumed
+}
+
+impl EventReceiver {
+    /// Next event from either queue. Returns `None` once every sender is dropped
+    /// and both queues are drained.
+    pub async fn recv(&mut self) -> Option<MarketEvent> {
+        if let Some(event) = self.held.pop_front() {
+            return Some(event);
+        }
+        loop {
+            if let Ok(event) = self.rx.try_recv() {
+                return Some(event);
+            }
+            if let Some(event) = self.ring.pop() {
+                return Some(event);
+            }
+            tokio::select! {
+                event = self.rx.recv() => {
+                    return event.or_else(|| self.ring.pop());
+                }
+                _ = self.ring.notify.notified() => {}
+            }
+        }
+    }
+
+    /// Runs `ready` to completion while holding must-deliver events in memory,
+    /// so the router never waits on a strategy that isn't consuming yet. Held
+    /// events come out of `recv` first, in order; ticks stay in their ring.
+    pub async fn hold_until<F: Future>(&mut self, ready: F) -> F::Output {
+        tokio::pin!(ready);
+        loop {
+            tokio::select! {
+                output = &mut ready => return output,
+                event = self.rx.recv() => match event {
+                    Some(event) => self.held.push_back(event),
+                    None => return ready.await, // Every sender dropped
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// executor/src/execution_queue.rs
+//! Central fill queue between strategy tasks and `execute_trade`. Bounds how many
+//! Jupiter/signing round-trips run at once, never works two fills on the same
+//! token concurrently, and picks the highest-confidence pending fill first.
+
+use futures::FutureExt;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use prometheus::{register_gauge, Gauge};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::Arc,
+};
+use tokio::sync::{oneshot, Notify};
+
+lazy_static! {
+    static ref EXECUTION_QUEUE_DEPTH: Gauge = register_gauge!(
+        "executor_execution_queue_depth",
+        "Fills waiting for an execution slot."
+    )
+    .unwrap();
+    static ref EXECUTIONS_IN_FLIGHT: Gauge = register_gauge!(
+        "executor_executions_in_flight",
+        "Fills currently being executed."
+    )
+    .unwrap();
+}
+
+pub type FillJob = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+struct QueuedFill {
+    priority: f64, // Order confidence
+    seq: u64,      // FIFO among equal priorities
+    token_address: String,
+    job: FillJob,
+    done: oneshot::Sender<()>,
+}
+
+impl PartialEq for QueuedFill {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedFill {}
+
+impl PartialOrd for QueuedFill {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedFill {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .total_cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: BinaryHeap<QueuedFill>,
+    busy_tokens: HashSet<String>,
+    running: usize,
+    next_seq: u64,
+}
+
+pub struct ExecutionQueue {
+    concurrency: usize,
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+impl ExecutionQueue {
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queues a fill. The returned receiver resolves once the fill has run.
+    pub fn submit(
+        &self,
+        token_address: String,
+        priority: f64,
+        job: FillJob,
+    ) -> oneshot::Receiver<()> {
+        let (done, finished) = oneshot::channel();
+        {
+            let mut state = self.state.lock();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.pending.push(QueuedFill {
+                priority,
+                seq,
+                token_address,
+                job,
+                done,
+            });
+            EXECUTION_QUEUE_DEPTH.set(state.pending.len() as f64);
+        }
+        self.notify.notify_one();
+        finished
+    }
+
+    /// Dispatcher loop; spawn once.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            match self.next_runnable() {
+                Some(fill) => {
+                    let queue = self.clone();
+                    tokio::spawn(async move {
+                        // A panicking fill must still free its slot and token
+                        let _ = AssertUnwindSafe(fill.job).catch_unwind().await;
+                        queue.finish(&fill.token_address);
+                        let _ = fill.done.send(());
+                    });
+                }
+                None => self.notify.notified().await,
+            }
+        }
+    }
+
+    /// Highest-priority pending fill whose token is idle, if a slot is free.
+    fn next_runnable(&self) -> Option<QueuedFill> {
+        let mut state = self.state.lock();
+        if state.running >= self.concurrency {
+            return None;
+        }
+        let mut skipped = Vec::new();
+        let mut picked = None;
+        while let Some(fill) = state.pending.pop() {
+            if state.busy_tokens.contains(&fill.token_address) {
+                skipped.push(fill);
+            } else {
+                picked = Some(fill);
+                break;
+            }
+        }
+        state.pending.extend(skipped);
+        if let Some(fill) = &picked {
+            state.busy_tokens.insert(fill.token_address.clone());
+            state.running += 1;
+            EXECUTION_QUEUE_DEPTH.set(state.pending.len() as f64);
+            EXECUTIONS_IN_FLIGHT.set(state.running as f64);
+        }
+        picked
+    }
+
+    fn finish(&self, token_address: &str) {
+        {
+            let mut state = self.state.lock();
+            state.busy_tokens.remove(token_address);
+            state.running -= 1;
+            EXECUTIONS_IN_FLIGHT.set(state.running as f64);
+        }
+        self.notify.notify_one();
+    }
+}
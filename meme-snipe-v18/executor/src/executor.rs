@@ -1,34 +1,93 @@
 // executor/src/executor.rs
 use crate::{
-    config::CONFIG, database::Database, jito_client::JitoClient, jupiter::JupiterClient,
-    portfolio_monitor, signer_client, strategies,
+    config::CONFIG,
+    database::Database,
+    event_queue::{self, DispatchPolicy, EventReceiver, EventSender},
+    execution_queue::ExecutionQueue,
+    feed_health::{self, FeedHealth},
+    fills::{Fill, Settlement},
+    jito_client::JitoClient,
+    jupiter::JupiterClient,
+    lifecycle::TradeLifecycle,
+    portfolio_monitor,
+    position_book::{PositionBook, PositionReservation},
+    replay,
+    routing::{SpotQuote, SpotRouter},
+    shadow::ShadowEvaluator,
+    signer_client,
+    slippage::DepthBook,
+    sol_price::SolPriceFeed,
+    strategies::{
+        self, drift_market_index, MetricsHandle, PortfolioContext, ShutdownAction, ShutdownReason,
+    },
+    submission::{Submission, Submitter},
+    token_safety::TokenSafetyChecker,
 };
 use anyhow::{anyhow, Result};
 use drift_rs::{Context as DriftContext, DriftClient};
 use redis::AsyncCommands;
+use shared_config::{
+    flags,
+    stream_lag::{self, StreamCursors},
+};
 use shared_models::{
-    alert, EventType, MarketEvent, OrderDetails, Side, StrategyAction, StrategyAllocation,
-    TradeMode,
+    alert, dead_letter, EventType, ExecutionReport, ExecutionStatus, LegPolicy, MarketEvent,
+    OrderDetails, OrderType, PositionCloseRequest, Side, StrategyAction, StrategyAllocation,
+    TradeMode, TradeState, TradingState, TradingStateRecord, KILL_SWITCH_CHANNEL,
+    PAUSED_STRATEGIES_KEY, STRATEGY_CONTROL_CHANNEL, TRADING_STATE_KEY,
 };
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
-use tokio::sync::mpsc::{self, Receiver, Sender};
-use tokio::task::JoinHandle;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    panic::AssertUnwindSafe,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::time::MissedTickBehavior;
+use tokio::task::{JoinHandle, JoinSet};
 use tracing::{debug, error, info, instrument, warn};
 
 use lazy_static::lazy_static;
-use prometheus::{register_counter, register_gauge, Counter, Gauge, Opts};
+use prometheus::{
+    register_counter, register_counter_vec, register_gauge, register_gauge_vec,
+    register_histogram_vec, Counter, CounterVec, Gauge, GaugeVec, HistogramVec, Opts,
+};
 
 lazy_static! {
-    static ref TRADES_EXECUTED: Counter = register_counter!(
+    static ref TRADES_EXECUTED: CounterVec = register_counter_vec!(
         "executor_trades_executed_total",
         "Total number of trades executed by the executor.",
         &["strategy_id", "trade_mode"]
     )
     .unwrap();
-    static ref TRADE_LATENCY: Gauge = register_gauge!(
-        "executor_trade_latency_seconds",
-        "Latency of trade execution from signal to completion."
+    static ref SIGNALS_EMITTED: CounterVec = register_counter_vec!(
+        "executor_signals_emitted_total",
+        "Entry and exit signals returned by a strategy.",
+        &["strategy_id", "kind"]
+    )
+    .unwrap();
+    static ref SIGNAL_REJECTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_signal_rejections_total",
+        "Entry signals or legs refused before filling, by reason.",
+        &["strategy_id", "reason"]
+    )
+    .unwrap();
+    static ref OPEN_POSITIONS: GaugeVec = register_gauge_vec!(
+        "executor_open_positions",
+        "OPEN trades per strategy as of the last position refresh.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref REALIZED_PNL_USD: GaugeVec = register_gauge_vec!(
+        "executor_realized_pnl_usd",
+        "Realized PnL of each strategy's closed trades.",
+        &["strategy_id"]
     )
     .unwrap();
     static ref ACTIVE_STRATEGIES_GAUGE: Gauge = register_gauge!(
@@ -42,40 +101,161 @@ lazy_static! {
         &["event_type"]
     )
     .unwrap();
+    static ref STRATEGY_QUEUE_DEPTH: GaugeVec = register_gauge_vec!(
+        "executor_strategy_queue_depth",
+        "Events queued for a strategy but not yet consumed.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref DROPPED_EVENTS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_dropped_events_total",
+        "Events evicted from a full strategy queue under the drop-oldest policy.",
+        &["strategy_id", "event_type"]
+    )
+    .unwrap();
+    static ref MULTI_LEG_ORDERS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_multi_leg_orders_total",
+        "All-or-nothing multi-leg orders by outcome (filled, failed, unwound, dry_run).",
+        &["strategy_id", "outcome"]
+    )
+    .unwrap();
+    static ref POSITION_CAP_REJECTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_position_cap_rejections_total",
+        "Orders rejected because they would exceed an open-position cap.",
+        &["strategy_id", "cap"]
+    )
+    .unwrap();
+    static ref FEED_STALE_SKIPS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_feed_stale_skips_total",
+        "Entry signals held back because a subscribed feed was stale.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref DEAD_LETTERS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_dead_letters_total",
+        "Unparseable stream entries moved to dead_letters.",
+        &["stream"]
+    )
+    .unwrap();
+    static ref EVENT_INGEST_LAG: HistogramVec = register_histogram_vec!(
+        "executor_event_ingest_lag_seconds",
+        "Time from an event's stream entry to the executor reading it.",
+        &["event_type"],
+        vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    )
+    .unwrap();
+    static ref STRATEGY_DECISION_SECONDS: HistogramVec = register_histogram_vec!(
+        "executor_strategy_decision_seconds",
+        "Time a strategy spends in on_event or on_timer.",
+        &["strategy_id"],
+        vec![0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0]
+    )
+    .unwrap();
+    static ref QUOTE_LATENCY: HistogramVec = register_histogram_vec!(
+        "executor_quote_latency_seconds",
+        "Time to get a spot quote, by the venue that won it.",
+        &["strategy_id", "venue"],
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0]
+    )
+    .unwrap();
+    static ref SIGNING_LATENCY: HistogramVec = register_histogram_vec!(
+        "executor_signing_latency_seconds",
+        "Round trip to the signer service for a live swap.",
+        &["strategy_id", "venue"],
+        vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0]
+    )
+    .unwrap();
+    static ref SUBMIT_TO_CONFIRM: HistogramVec = register_histogram_vec!(
+        "executor_submit_to_confirm_seconds",
+        "Time from a live swap being submitted to its fill confirming.",
+        &["strategy_id", "venue"],
+        vec![0.5, 1.0, 2.0, 4.0, 8.0, 15.0, 30.0, 60.0, 120.0]
+    )
+    .unwrap();
+}
+
+const ALLOCATIONS_STREAM: &str = "allocations_channel";
+const POSITION_CLOSE_REQUESTS_STREAM: &str = "position_close_requests";
+const STRATEGY_SNAPSHOTS_KEY: &str = "executor:strategy_snapshots"; // Hash: strategy ID -> StoredSnapshot JSON
+
+/// Optional per-allocation token filter applied before an event is routed.
+#[derive(Debug, Default)]
+struct TokenUniverse {
+    allow: Option<HashSet<String>>, // None = every token not denied
+    deny: HashSet<String>,
+}
+
+impl TokenUniverse {
+    fn from_allocation(alloc: &StrategyAllocation) -> Self {
+        Self {
+            allow: alloc
+                .token_allowlist
+                .as_ref()
+                .map(|tokens| tokens.iter().cloned().collect()),
+            deny: alloc.token_denylist.iter().cloned().collect(),
+        }
+    }
+
+    fn admits(&self, token: &str) -> bool {
+        !self.deny.contains(token)
+            && self
+                .allow
+                .as_ref()
+                .map_or(true, |allow| allow.contains(token))
+    }
+}
+
+/// One strategy's subscription to an event type.
+struct EventRoute {
+    strategy_id: String,
+    sender: EventSender,
+    universe: Arc<TokenUniverse>,
 }
 
 pub struct MasterExecutor {
     db: Arc<Database>,
-    active_strategies: HashMap<String, (Sender<MarketEvent>, JoinHandle<()>)>, // ID -> (Sender, TaskHandle)
-    event_router_senders: HashMap<EventType, Vec<Sender<MarketEvent>>>, // EventType -> List of interested strategy senders
+    active_strategies: HashMap<String, (EventSender, JoinHandle<()>)>, // ID -> (Sender, TaskHandle)
+    event_router_senders: HashMap<EventType, Vec<EventRoute>>, // EventType -> List of interested strategy routes
     redis_client: redis::Client, // P-7: Client for Redis Streams
     jupiter_client: Arc<JupiterClient>,
-    sol_usd_price: Arc<tokio::sync::Mutex<f64>>, // P-2: Store live SOL/USD price
-    portfolio_paused: Arc<tokio::sync::Mutex<bool>>, // P-6: Flag to pause trading
-    jito_client: Arc<JitoClient>,                // NEW
+    spot_router: Arc<SpotRouter>, // Venue selection across Jupiter, Raydium and pump.fun
+    sol_usd_price: Arc<SolPriceFeed>, // P-2: Live SOL/USD from the stream, Pyth as fallback
+    trading_state: Arc<tokio::sync::Mutex<TradingState>>, // P-6: Mirrors `trading_state`; only Active trades
+    paused_strategies: Arc<tokio::sync::Mutex<HashSet<String>>>, // Paused by an operator (memectl)
+    portfolio_drawdown_pct: Arc<tokio::sync::Mutex<f64>>, // Drawdown from peak PnL, set by the portfolio monitor
+    submitter: Arc<Submitter>,                   // Jito first, then any configured fallbacks
+    token_safety: Arc<TokenSafetyChecker>,       // Mint/holder/LP checks before Live entries
+    depth_book: Arc<DepthBook>,                  // Latest DepthEvent per token, for slippage sizing
+    feed_health: Arc<FeedHealth>,                // Last heartbeat per data source
     drift_client: Arc<DriftClient>,              // NEW
     strategy_allocations: Arc<tokio::sync::Mutex<HashMap<String, StrategyAllocation>>>, // Strategy ID -> Current Allocation
     redis_connection_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    positions: Arc<PositionBook>, // Open/in-flight positions per (strategy, token) for cap checks
+    execution_queue: Arc<ExecutionQueue>, // Bounded, per-token-serialized fill queue
 }
 
 impl MasterExecutor {
     pub fn get_state_snapshot(&self) -> Value {
         let allocations = self.strategy_allocations.blocking_lock();
+        let paused_strategies = self.paused_strategies.blocking_lock();
         let strategies: Vec<Value> = allocations.values().map(|alloc| {
             json!({
                 "id": alloc.id,
                 "weight": alloc.weight,
                 "mode": alloc.mode,
                 "params": alloc.params,
-                "is_active": self.active_strategies.contains_key(&alloc.id)
+                "is_active": self.active_strategies.contains_key(&alloc.id),
+                "is_paused": paused_strategies.contains(&alloc.id)
             })
         }).collect();
 
+        let trading_state = self.trading_state.blocking_lock().clone();
         json!({
             "timestamp": chrono::Utc::now().to_rfc3339(),
-            "is_paused": self.portfolio_paused.blocking_lock().clone(),
+            "is_paused": !trading_state.is_active(),
+            "trading_state": trading_state,
             "active_strategies_count": self.active_strategies.len(),
-            "sol_usd_price": self.sol_usd_price.blocking_lock().clone(),
+            "sol_usd_price": self.sol_usd_price.last_known(),
             "strategies": strategies
         })
     }
@@ -84,6 +264,8 @@ impl MasterExecutor {
         // Initialize JitoClient and DriftClient correctly with their respective new() or connect methods
         let jito_client = Arc::new(JitoClient::new(&CONFIG.jito_rpc_url).await?);
         let drift_client = Arc::new(DriftClient::connect(DriftContext::Mainnet, None).await?); // None for optional wallet
+        let jupiter_client = Arc::new(JupiterClient::new());
+        let spot_router = Arc::new(SpotRouter::new(jupiter_client.clone()));
         let redis_client = redis::Client::open(CONFIG.redis_url.clone())?;
         let redis_connection_manager = Arc::new(tokio::sync::Mutex::new(
             redis::aio::ConnectionManager::new(redis_client.clone()).await?,
@@ -94,22 +276,31 @@ impl MasterExecutor {
             active_strategies: HashMap::new(),
             event_router_senders: HashMap::new(),
             redis_client: redis::Client::open(CONFIG.redis_url.clone())?,
-            jupiter_client: Arc::new(JupiterClient::new()),
-            sol_usd_price: Arc::new(tokio::sync::Mutex::new(1.0)), // P-2: Default to 1.0, will be updated by consumer
-            portfolio_paused: Arc::new(tokio::sync::Mutex::new(false)), // P-6: Not paused by default
-            jito_client,                                                // Correct initialization
+            jupiter_client: jupiter_client.clone(),
+            token_safety: Arc::new(TokenSafetyChecker::new(spot_router.clone())?),
+            spot_router,
+            depth_book: Arc::new(DepthBook::default()),
+            feed_health: Arc::new(FeedHealth::new()),
+            sol_usd_price: Arc::new(SolPriceFeed::new()), // P-2: Empty until the consumer (or Pyth) provides a price
+            // P-6: Paused until the stored state is read, in case trading was stopped while we were down
+            trading_state: Arc::new(tokio::sync::Mutex::new(TradingState::paused("starting"))),
+            paused_strategies: Arc::new(tokio::sync::Mutex::new(HashSet::new())),
+            portfolio_drawdown_pct: Arc::new(tokio::sync::Mutex::new(0.0)),
+            submitter: Arc::new(Submitter::new(jito_client, redis_connection_manager.clone())?),
             drift_client,                                               // Correct initialization
             strategy_allocations: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
             redis_connection_manager,
+            positions: Arc::new(PositionBook::default()),
+            execution_queue: Arc::new(ExecutionQueue::new(CONFIG.execution_concurrency)),
         })
     }
 
     // simple getter for monitor
-    pub fn paused_flag(&self) -> Arc<tokio::sync::Mutex<bool>> {
-        self.portfolio_paused.clone()
+    pub fn drawdown_pct(&self) -> Arc<tokio::sync::Mutex<f64>> {
+        self.portfolio_drawdown_pct.clone()
     }
 
-    pub async fn run(&mut self) -> Result<()> {
+    pub async fn run(&mut self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
         info!("Starting Master Executor run loop.");
 
         let mut conn_manager = self.redis_connection_manager.lock().await;
@@ -127,114 +318,419 @@ impl MasterExecutor {
             ("events:sol_price", "0"),
             ("events:onchain", "0"),
             ("events:data_source_heartbeat", "0"),
+            ("events:liquidity_pool", "0"),
+            ("events:whale_transfer", "0"),
+            ("events:token_launch", "0"),
         ]
         .iter()
         .map(|(k, v)| (k.to_string(), v.to_string()))
         .collect();
 
-        let mut kill_switch_listener = self
-            .redis_client
-            .get_async_connection()
-            .await?
-            .into_pubsub();
-        kill_switch_listener
-            .subscribe("kill_switch_channel")
-            .await?;
+        // Resume from the offsets checkpointed on the last clean shutdown
+        let saved_offsets: HashMap<String, String> = conn
+            .hgetall("executor:stream_offsets")
+            .await
+            .unwrap_or_default();
+        for (stream, id) in saved_offsets {
+            if let Some(offset) = market_stream_ids.get_mut(&stream) {
+                *offset = id;
+            }
+        }
 
-        loop {
-            let read_result = conn
-                .xread_options(
-                    &market_stream_ids
-                        .keys()
-                        .map(|k| k.as_str())
-                        .collect::<Vec<&str>>(),
-                    &market_stream_ids
-                        .values()
-                        .map(|v| v.as_str())
-                        .collect::<Vec<&str>>(),
-                    &redis::XReadOptions::default().count(100).block(5000),
-                )
+        // Replay mode feeds archived events instead; only allocations come from Redis
+        let mut replay_events = if CONFIG.replay_mode {
+            market_stream_ids.clear();
+            Some(replay::start())
+        } else {
+            None
+        };
+        let read_block_ms = if CONFIG.replay_mode { 100 } else { 5000 };
+
+        // Publish param schemas so the allocator can validate specs before allocating them
+        for (family, schema) in strategies::param_schemas() {
+            let schema_json = serde_json::to_string(&schema)?;
+            let _: Result<(), _> = conn
+                .hset("strategy_param_schemas", family, schema_json)
                 .await;
+        }
+        info!("Published strategy param schemas to Redis.");
+
+        // Start from the most recent allocation set instead of replaying the whole stream
+        let latest: redis::streams::StreamRangeReply = conn
+            .xrevrange_count(ALLOCATIONS_STREAM, "+", "-", 1)
+            .await
+            .unwrap_or_default();
+        if let Some(message) = latest.ids.first() {
+            allocation_stream_id = message.id.clone();
+            self.apply_allocations(message, &market_stream_ids).await;
+        }
+
+        tokio::spawn(self.execution_queue.clone().run());
+        tokio::spawn(feed_health::run_monitor(
+            self.feed_health.clone(),
+            self.redis_connection_manager.clone(),
+        ));
+
+        let stream_cursors = StreamCursors::new("executor");
+        for (stream, id) in &market_stream_ids {
+            stream_cursors.set(stream, id);
+        }
+        stream_cursors.set(ALLOCATIONS_STREAM, &allocation_stream_id);
+        tokio::spawn(stream_lag::monitor(stream_cursors.clone(), CONFIG.redis_url.clone()));
+
+        // Keep position counts (and the strategy health gauges) in step with
+        // trades the position_manager closes
+        let positions = self.positions.clone();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(
+                CONFIG.position_refresh_interval_secs,
+            ));
+            loop {
+                interval.tick().await;
+                match db.get_open_trades() {
+                    Ok(open_trades) => {
+                        positions.refresh(&open_trades);
+                        let mut open: HashMap<&str, u32> = HashMap::new();
+                        for trade in &open_trades {
+                            *open.entry(trade.strategy_id.as_str()).or_default() += 1;
+                        }
+                        OPEN_POSITIONS.reset(); // Strategies with nothing open drop out
+                        for (strategy_id, count) in open {
+                            OPEN_POSITIONS
+                                .with_label_values(&[strategy_id])
+                                .set(count as f64);
+                        }
+                    }
+                    Err(e) => error!(error = %e, "Failed to refresh open positions."),
+                }
+                match db.get_realized_pnl_by_strategy() {
+                    Ok(pnls) => {
+                        for (strategy_id, pnl_usd) in pnls {
+                            REALIZED_PNL_USD
+                                .with_label_values(&[&strategy_id])
+                                .set(pnl_usd);
+                        }
+                    }
+                    Err(e) => error!(error = %e, "Failed to read realized PnL."),
+                }
+            }
+        });
+
+        let mut kill_switch_messages = self.subscribe_control_channels().await?;
+
+        'run: loop {
+            if let Some(replay) = replay_events.as_mut() {
+                loop {
+                    match replay.try_recv() {
+                        Ok(batch) => {
+                            for event in batch {
+                                self.process_event(event).await;
+                            }
+                        }
+                        Err(mpsc::error::TryRecvError::Empty) => break,
+                        Err(mpsc::error::TryRecvError::Disconnected) => {
+                            info!("Replay finished, shutting down.");
+                            break 'run;
+                        }
+                    }
+                }
+            }
+
+            // Allocations ride along in the same XREAD so one blocking call covers every stream
+            let mut stream_keys: Vec<&str> = market_stream_ids.keys().map(|k| k.as_str()).collect();
+            let mut stream_ids: Vec<&str> = market_stream_ids.values().map(|v| v.as_str()).collect();
+            stream_keys.push(ALLOCATIONS_STREAM);
+            stream_ids.push(allocation_stream_id.as_str());
+
+            let read_result = tokio::select! {
+                res = conn.xread_options(
+                    &stream_keys,
+                    &stream_ids,
+                    &redis::XReadOptions::default().count(100).block(read_block_ms),
+                ) => res,
+                msg = kill_switch_messages.next() => {
+                    match msg {
+                        Some(msg) => {
+                            let payload: String = msg.get_payload().unwrap_or_default();
+                            if msg.get_channel_name() == STRATEGY_CONTROL_CHANNEL {
+                                // The set is the source of truth; the message only says it changed
+                                info!(command = %payload, "Strategy control command received.");
+                                self.refresh_paused_strategies().await;
+                            } else {
+                                // Likewise `trading_state`, in case changes arrive out of order
+                                info!(change = %payload, "Trading state change received.");
+                                self.refresh_trading_state().await;
+                            }
+                        }
+                        None => {
+                            // Fail safe: trade nothing until we can hear the kill switch again
+                            error!("Control channel subscription closed. Pausing and resubscribing.");
+                            *self.trading_state.lock().await = TradingState::paused("kill switch unreachable");
+                            tokio::time::sleep(Duration::from_secs(5)).await;
+                            if let Ok(messages) = self.subscribe_control_channels().await {
+                                kill_switch_messages = messages;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                _ = shutdown.changed() => {
+                    info!("Shutdown signal received, leaving run loop.");
+                    break;
+                }
+            };
 
             match read_result {
                 Ok(streams) => {
                     for stream in streams.keys {
                         let stream_name = stream.key;
+                        if stream_name == ALLOCATIONS_STREAM {
+                            // Each entry is a full allocation set; only the newest matters
+                            if let Some(message) = stream.ids.last() {
+                                allocation_stream_id = message.id.clone();
+                                stream_cursors.set(ALLOCATIONS_STREAM, &allocation_stream_id);
+                                self.apply_allocations(message, &market_stream_ids).await;
+                            }
+                            continue;
+                        }
                         for message in stream.ids {
                             let id_str = message.id.clone();
-                            let event_result: Result<MarketEvent, _> =
-                                serde_json::from_str(message.get("event").unwrap_or(""));
-
-                            if let Ok(event) = event_result {
-                                // Defend against stale data
-                                let now = chrono::Utc::now().timestamp();
-                                if now - event.timestamp() > 30 {
-                                    warn!(
-                                        "Discarding stale event of type {:?} with timestamp {}",
-                                        event.get_type(),
-                                        event.timestamp()
-                                    );
-                                    STALE_EVENTS_TOTAL
-                                        .with_label_values(&[&format!("{:?}", event.get_type())])
-                                        .inc();
-                                    continue;
-                                }
-
-                                if let MarketEvent::SolPrice(sol_price_event) = &event {
-                                    *self.sol_usd_price.lock().await = sol_price_event.price_usd;
-                                } else if let MarketEvent::DataSourceHeartbeat(heartbeat) = &event {
-                                    // Handle heartbeat logic, e.g., update a map of last-seen times
-                                } else {
-                                    self.dispatch_event(event).await;
+                            // Entry IDs start with the ms Redis appended them at
+                            if let Some(appended_ms) = id_str
+                                .split('-')
+                                .next()
+                                .and_then(|ms| ms.parse::<i64>().ok())
+                            {
+                                let lag_ms = chrono::Utc::now().timestamp_millis() - appended_ms;
+                                EVENT_INGEST_LAG
+                                    .with_label_values(&[stream_name.trim_start_matches("events:")])
+                                    .observe(lag_ms.max(0) as f64 / 1000.0);
+                            }
+                            let payload: String = message.get("event").unwrap_or_default();
+                            match serde_json::from_str::<MarketEvent>(&payload) {
+                                Ok(event) => self.process_event(event).await,
+                                Err(e) => {
+                                    error!("Failed to parse event from stream {}: {:?}", stream_name, message);
+                                    DEAD_LETTERS_TOTAL.with_label_values(&[&stream_name]).inc();
+                                    dead_letter!(conn, "executor", stream_name, id_str, "event", payload, e);
                                 }
-                            } else {
-                                error!("Failed to parse event from stream {}: {:?}", stream_name, message);
                             }
                             market_stream_ids.insert(stream_name.clone(), id_str);
                         }
+                        if let Some(id) = market_stream_ids.get(&stream_name) {
+                            stream_cursors.set(&stream_name, id);
+                        }
                     }
                 }
                 Err(e) => {
                     error!("Error reading from market event streams: {}. Attempting to reconnect.", e);
-                    *self.portfolio_paused.lock().await = true;
+                    *self.trading_state.lock().await = TradingState::paused("redis unreachable");
                     tokio::time::sleep(Duration::from_secs(5)).await;
                     // Re-establish connection
                     let mut new_conn_manager = self.redis_connection_manager.lock().await;
                     if let Ok(new_conn) = redis::aio::ConnectionManager::new(self.redis_client.clone()).await {
                         *new_conn_manager = new_conn;
                         info!("Successfully reconnected to Redis.");
-                        *self.portfolio_paused.lock().await = false;
+                        drop(new_conn_manager);
+                        self.refresh_trading_state().await;
                     }
                 }
             }
+        }
+
+        self.shutdown(&mut conn, &market_stream_ids).await;
+        Ok(())
+    }
 
-            // Allocation stream reading logic remains similar but should also be adapted for robustness
-            // ...
+    /// Routes one market event: stale ones are dropped, market state is
+    /// updated in place, everything else goes to the subscribed strategies.
+    async fn process_event(&mut self, event: MarketEvent) {
+        // Defend against stale data
+        let now = chrono::Utc::now().timestamp();
+        if now - event.timestamp() > 30 {
+            warn!(
+                "Discarding stale event of type {:?} with timestamp {}",
+                event.get_type(),
+                event.timestamp()
+            );
+            STALE_EVENTS_TOTAL
+                .with_label_values(&[&format!("{:?}", event.get_type())])
+                .inc();
+            return;
+        }
+
+        if let MarketEvent::Depth(depth_event) = &event {
+            self.depth_book.update(depth_event);
+        }
+        if let MarketEvent::SolPrice(sol_price_event) = &event {
+            self.sol_usd_price
+                .update(sol_price_event.price_usd, sol_price_event.timestamp)
+                .await;
+        } else if let MarketEvent::DataSourceHeartbeat(heartbeat) = &event {
+            self.feed_health.record(heartbeat);
+        } else {
+            self.dispatch_event(event).await;
+        }
+    }
+
+    /// Subscribes to trading state changes and per-strategy pause commands,
+    /// then re-reads both in case any changed while unsubscribed.
+    async fn subscribe_control_channels(&self) -> Result<BoxStream<'static, redis::Msg>> {
+        let mut pubsub = self.redis_client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(&[KILL_SWITCH_CHANNEL, STRATEGY_CONTROL_CHANNEL]).await?;
+        self.refresh_trading_state().await;
+        self.refresh_paused_strategies().await;
+        Ok(pubsub.into_on_message().boxed())
+    }
+
+    async fn refresh_paused_strategies(&self) {
+        let mut conn = self.redis_connection_manager.lock().await.clone();
+        match conn.smembers::<_, HashSet<String>>(PAUSED_STRATEGIES_KEY).await {
+            Ok(paused) => {
+                let mut current = self.paused_strategies.lock().await;
+                if *current != paused {
+                    info!(paused = ?paused, "Paused strategies updated.");
+                    *current = paused;
+                }
+            }
+            Err(e) => error!(error = %e, "Failed to read paused strategies."),
+        }
+    }
+
+    /// Re-reads `trading_state`; on failure the last state read stays in force.
+    async fn refresh_trading_state(&self) {
+        let mut conn = self.redis_connection_manager.lock().await.clone();
+        match conn.get::<_, Option<String>>(TRADING_STATE_KEY).await {
+            Ok(raw) => {
+                let state = TradingStateRecord::from_stored(raw.as_deref()).state;
+                let mut current = self.trading_state.lock().await;
+                if *current != state {
+                    warn!(from = %*current, to = %state, "🚦 Trading state applied.");
+                    *current = state;
+                }
+            }
+            Err(e) => error!(error = %e, "Failed to read trading state."),
+        }
+    }
+
+    /// Reconciles running strategies against an `allocations_channel` entry.
+    /// `dispatched` holds the last id routed from each market stream.
+    async fn apply_allocations(
+        &mut self,
+        message: &redis::streams::StreamId,
+        dispatched: &HashMap<String, String>,
+    ) {
+        // Replayed markets are history, and only mainnet-live may reach the chain
+        let paper_only = CONFIG.replay_mode || !CONFIG.profile.allows_live();
+        match parse_allocations(message, paper_only) {
+            Some(Ok(allocations)) => {
+                info!(id = %message.id, count = allocations.len(), "Received strategy allocations.");
+                self.reconcile_strategies(allocations, dispatched).await;
+                ACTIVE_STRATEGIES_GAUGE.set(self.active_strategies.len() as f64);
+            }
+            Some(Err(e)) => {
+                error!(id = %message.id, error = %e, "Failed to parse allocations.");
+                DEAD_LETTERS_TOTAL.with_label_values(&[ALLOCATIONS_STREAM]).inc();
+                let payload: String = message.get("allocations").unwrap_or_default();
+                let mut conn = self.redis_connection_manager.lock().await.clone();
+                dead_letter!(conn, "executor", ALLOCATIONS_STREAM, message.id, "allocations", payload, e);
+            }
+            None => error!(id = %message.id, "Allocation message has no 'allocations' field."),
+        }
+    }
+
+    /// Stops consumption cleanly: checkpoints stream offsets, lets every
+    /// strategy drain its queued events, cancel its open orders, run
+    /// `on_shutdown` and persist a snapshot, then flushes the trade DB.
+    async fn shutdown(
+        &mut self,
+        conn: &mut redis::aio::ConnectionManager,
+        market_stream_ids: &HashMap<String, String>,
+    ) {
+        info!("🛑 Executor shutting down gracefully...");
+
+        // 1. Checkpoint stream offsets (none in replay mode, which reads no market streams)
+        let offsets: Vec<(&String, &String)> = market_stream_ids.iter().collect();
+        if offsets.is_empty() {
+            debug!("No stream offsets to persist.");
+        } else if let Err(e) = conn
+            .hset_multiple::<_, _, _, ()>("executor:stream_offsets", &offsets)
+            .await
+        {
+            error!(error = %e, "Failed to persist stream offsets.");
+        } else {
+            info!("Persisted {} stream offsets.", offsets.len());
+        }
+
+        // 2. Close every strategy channel; tasks drain what is queued, cancel their
+        // open orders, shut down, snapshot, and exit
+        self.event_router_senders.clear();
+        let strategies: Vec<(String, (EventSender, JoinHandle<()>))> =
+            self.active_strategies.drain().collect();
+        let drain_timeout = Duration::from_secs(CONFIG.shutdown_drain_timeout_secs);
+        for (id, (tx, mut handle)) in strategies {
+            drop(tx);
+            match tokio::time::timeout(drain_timeout, &mut handle).await {
+                Ok(_) => info!(strategy = id, "Strategy drained."),
+                Err(_) => {
+                    // Aborting also cancels its orders, so none trade past the DB flush
+                    handle.abort();
+                    warn!(strategy = id, "Strategy did not drain in time, abandoning.");
+                }
+            }
+        }
+        ACTIVE_STRATEGIES_GAUGE.set(0.0);
 
-            // Kill switch logic remains the same
-            // ...
+        // 3. Flush pending DB writes
+        if let Err(e) = self.db.checkpoint() {
+            error!(error = %e, "Failed to checkpoint trade database.");
         }
+        info!("✅ Executor shutdown complete.");
     }
 
-    async fn reconcile_strategies(&mut self, allocations: Vec<StrategyAllocation>) {
+    async fn reconcile_strategies(
+        &mut self,
+        allocations: Vec<StrategyAllocation>,
+        dispatched: &HashMap<String, String>,
+    ) {
         let new_ids: HashMap<String, StrategyAllocation> =
             allocations.into_iter().map(|a| (a.id.clone(), a)).collect();
-        let current_ids: Vec<String> = self.active_strategies.keys().cloned().collect();
+        let running: Vec<String> = self.active_strategies.keys().cloned().collect();
 
-        // Lock acquisition order: 1. strategy_allocations, 2. portfolio_paused
+        // Lock acquisition order: 1. strategy_allocations, 2. trading_state
         let mut stored_allocs = self.strategy_allocations.lock().await;
+        let plan = plan_reconcile(&running, &stored_allocs, &new_ids);
         *stored_allocs = new_ids.clone();
         drop(stored_allocs); // Release lock ASAP
 
-        // 1. Stop strategies that are no longer allocated
-        for id in current_ids.iter().filter(|id| !new_ids.contains_key(*id)) {
+        // 1. Stop strategies that are no longer allocated, or allocated new params
+        let mut restarting: Vec<(String, JoinHandle<()>)> = Vec::new();
+        for id in plan.stop.iter().chain(&plan.restart) {
+            // Dropping the sender (and the routes below) closes the channel; the task
+            // drains what is queued, runs on_shutdown and exits on its own.
             if let Some((_, handle)) = self.active_strategies.remove(id) {
-                handle.abort();
-                info!(strategy = id, "Stopped strategy due to deallocation.");
+                if plan.restart.contains(id) {
+                    info!(strategy = id, "Restarting strategy with new params.");
+                    restarting.push((id.clone(), handle));
+                } else {
+                    info!(strategy = id, "Stopping strategy due to deallocation.");
+                }
             }
+            let _ = STRATEGY_QUEUE_DEPTH.remove_label_values(&[id]);
             // Remove from event router senders as well
-            for (_, senders) in self.event_router_senders.iter_mut() {
-                senders.retain(|s| !s.is_closed()); // Remove closed channels
+            for (_, routes) in self.event_router_senders.iter_mut() {
+                routes.retain(|r| &r.strategy_id != id && !r.sender.is_closed());
+            }
+        }
+        // The new instance restores the snapshot the old one writes on its way out
+        let drain_timeout = Duration::from_secs(CONFIG.shutdown_drain_timeout_secs);
+        for (id, handle) in restarting {
+            if tokio::time::timeout(drain_timeout, handle).await.is_err() {
+                warn!(
+                    strategy = id,
+                    "Strategy did not drain in time, restarting from its last snapshot."
+                );
             }
         }
 
@@ -246,54 +742,188 @@ impl MasterExecutor {
                     weight = alloc.weight,
                     "Starting new strategy."
                 );
-                if let Some(mut strategy_instance) = self.build_strategy(&id) {
+                if let Some(mut strategy_instance) =
+                    strategies::build_strategy(alloc.strategy_family())
+                {
+                    // Reject bad params with a readable error instead of an opaque serde failure in init()
+                    if let Err(e) = strategy_instance.params_schema().validate(&alloc.params) {
+                        error!(strategy = id, error = %e, "Allocation params failed schema validation, skipping.");
+                        continue;
+                    }
                     // Pass actual params from alloc
-                    if let Err(e) = strategy_instance.init(&alloc.params).await {
+                    if let Err(e) = strategy_instance.init(&alloc.params, &MetricsHandle::new(&id)).await {
                         error!(strategy = id, error = %e, "Failed to initialize strategy, skipping.");
                         continue;
                     }
+                    // Warm lookback buffers from the last run instead of starting cold
+                    let restored_at = match restore_snapshot(
+                        &self.redis_connection_manager,
+                        &id,
+                        strategy_instance.as_mut(),
+                    )
+                    .await
+                    {
+                        Ok(restored_at) => restored_at,
+                        Err(e) => {
+                            warn!(strategy = id, error = %e, "Failed to restore strategy snapshot, starting cold.");
+                            None
+                        }
+                    };
+                    // Then replay recent history, in the task so the run loop isn't held up
+                    let universe = Arc::new(TokenUniverse::from_allocation(&alloc));
+                    let warmup_window_start = (CONFIG.strategy_warmup_minutes > 0
+                        && !CONFIG.replay_mode)
+                        .then(|| chrono::Utc::now().timestamp() - CONFIG.strategy_warmup_minutes * 60);
+
+                    // Challenger params run alongside in paper-only shadow mode
+                    let shadow = match &alloc.challenger_params {
+                        Some(challenger_params) => match build_challenger(
+                            &id,
+                            alloc.strategy_family(),
+                            challenger_params,
+                            &self.db,
+                        )
+                        .await
+                        {
+                            Ok(shadow) => {
+                                info!(strategy = id, "Shadow evaluating challenger params.");
+                                Some(shadow)
+                            }
+                            Err(e) => {
+                                warn!(strategy = id, error = %e, "Failed to start challenger, running without shadow.");
+                                None
+                            }
+                        },
+                        None => None,
+                    };
 
-                    let (tx, rx) = mpsc::channel(100); // Bounded channel for backpressure
+                    let (tx, rx) = event_queue::channel(CONFIG.strategy_queue_capacity); // Bounded, see DispatchPolicy
                     let strategy_id_clone = id.clone();
                     let db_clone = self.db.clone();
-                    let jupiter_client_clone = self.jupiter_client.clone();
+                    let spot_router_clone = self.spot_router.clone();
                     let sol_usd_price_clone = self.sol_usd_price.clone();
-                    let portfolio_paused_clone = self.portfolio_paused.clone();
+                    let trading_state_clone = self.trading_state.clone();
+                    let paused_strategies_clone = self.paused_strategies.clone();
+                    let portfolio_drawdown_pct_clone = self.portfolio_drawdown_pct.clone();
                     let drift_client_clone = self.drift_client.clone();
-                    let jito_client_clone = self.jito_client.clone();
+                    let submitter_clone = self.submitter.clone();
+                    let token_safety_clone = self.token_safety.clone();
+                    let depth_book_clone = self.depth_book.clone();
+                    let feed_health_clone = self.feed_health.clone();
                     let redis_conn_manager_clone = self.redis_connection_manager.clone();
+                    let positions_clone = self.positions.clone();
+                    let execution_queue_clone = self.execution_queue.clone();
 
-                    // Register subscriptions
-                    for sub_type in strategy_instance.subscriptions() {
+                    // Register subscriptions, filtered to the allocation's token universe
+                    let mut route_types = strategy_instance.subscriptions();
+                    if let Some(shadow) = &shadow {
+                        route_types.extend(shadow.subscriptions());
+                    }
+                    for sub_type in route_types {
                         self.event_router_senders
                             .entry(sub_type)
                             .or_default()
-                            .push(tx.clone());
+                            .push(EventRoute {
+                                strategy_id: id.clone(),
+                                sender: tx.clone(),
+                                universe: universe.clone(),
+                            });
                     }
 
                     let strategy_allocations_clone = self.strategy_allocations.clone();
+                    let started_with = alloc.clone();
+                    let warmed_until = dispatched.clone();
                     let handle = tokio::spawn(async move {
-                        let task_result = tokio::spawn(strategy_task(
-                            strategy_instance,
-                            rx,
-                            db_clone,
-                            jupiter_client_clone,
-                            drift_client_clone,
-                            jito_client_clone,
-                            sol_usd_price_clone,
-                            portfolio_paused_clone,
-                            strategy_allocations_clone,
-                            strategy_id_clone.clone(), // clone for the task
-                            redis_conn_manager_clone,
-                        ))
-                        .await;
+                        // Supervisor: the receiver outlives a panicking task so the
+                        // strategy can be rebuilt without re-registering its routes.
+                        let mut strategy_instance = strategy_instance;
+                        let mut rx = rx;
+                        let mut shadow = shadow;
 
-                        if let Err(e) = task_result {
-                            if e.is_panic() {
-                                error!(strategy_id = %strategy_id_clone, "Strategy task panicked! It will be shut down.");
-                                // Here you could add alerting logic
-                            } else {
-                                error!(strategy_id = %strategy_id_clone, "Strategy task failed: {:?}", e);
+                        // Warm up to what the router had dispatched when the routes went in;
+                        // everything routed since is held until it's ready, so no event is
+                        // missed or seen twice, and nothing trades before then.
+                        if let Some(window_start) = warmup_window_start {
+                            let since = restored_at.map_or(window_start, |t| t.max(window_start));
+                            rx.hold_until(async {
+                                if let Err(e) = warm_up(strategy_instance.as_mut(), &strategy_id_clone, &universe, &redis_conn_manager_clone, since, &warmed_until).await {
+                                    warn!(strategy = %strategy_id_clone, error = %e, "Strategy warmup failed, starting with partial history.");
+                                }
+                                if let Some(shadow) = shadow.as_mut() {
+                                    if let Err(e) = warm_up(shadow.challenger_mut(), &strategy_id_clone, &universe, &redis_conn_manager_clone, window_start, &warmed_until).await {
+                                        warn!(strategy = %strategy_id_clone, error = %e, "Challenger warmup failed, starting with partial history.");
+                                    }
+                                }
+                            })
+                            .await;
+                        }
+                        let mut orders = OrderTasks::default();
+                        let mut crashes: u32 = 0;
+                        let max_backoff = Duration::from_secs(CONFIG.strategy_restart_max_backoff_secs);
+                        loop {
+                            let started = std::time::Instant::now();
+                            let task_result = AssertUnwindSafe(strategy_task(
+                                strategy_instance,
+                                &mut rx,
+                                &mut shadow,
+                                &mut orders,
+                                db_clone.clone(),
+                                spot_router_clone.clone(),
+                                drift_client_clone.clone(),
+                                submitter_clone.clone(),
+                                token_safety_clone.clone(),
+                                depth_book_clone.clone(),
+                                feed_health_clone.clone(),
+                                sol_usd_price_clone.clone(),
+                                trading_state_clone.clone(),
+                                paused_strategies_clone.clone(),
+                                portfolio_drawdown_pct_clone.clone(),
+                                strategy_allocations_clone.clone(),
+                                &started_with,
+                                strategy_id_clone.clone(), // clone for the task
+                                redis_conn_manager_clone.clone(),
+                                positions_clone.clone(),
+                                execution_queue_clone.clone(),
+                            ))
+                            .catch_unwind()
+                            .await;
+                            if task_result.is_ok() {
+                                break; // Channel closed: deallocated or shutting down
+                            }
+
+                            // A strategy that stayed up longer than the max backoff starts counting afresh
+                            crashes = if started.elapsed() >= max_backoff { 1 } else { crashes + 1 };
+                            let backoff = restart_backoff(crashes);
+                            error!(strategy_id = %strategy_id_clone, crashes, backoff_secs = backoff.as_secs(), "Strategy task panicked, restarting after backoff.");
+                            if crashes >= CONFIG.strategy_crash_alert_threshold {
+                                let mut conn = redis_conn_manager_clone.lock().await.clone();
+                                alert!(conn, "💥 Strategy {} has crashed {} times in a row; restarting in {}s", strategy_id_clone, crashes, backoff.as_secs());
+                            }
+
+                            // Keep the queue moving while backing off so the router never blocks on us
+                            let drained = tokio::time::timeout(backoff, async {
+                                while let Some(event) = rx.recv().await {
+                                    DROPPED_EVENTS_TOTAL
+                                        .with_label_values(&[
+                                            strategy_id_clone.as_str(),
+                                            &format!("{:?}", event.get_type()),
+                                        ])
+                                        .inc();
+                                }
+                            })
+                            .await;
+                            if drained.is_ok() {
+                                break; // Channel closed during backoff
+                            }
+
+                            match restart_strategy(&strategy_id_clone, &strategy_allocations_clone, &redis_conn_manager_clone).await {
+                                Ok(instance) => strategy_instance = instance,
+                                Err(e) => {
+                                    error!(strategy_id = %strategy_id_clone, error = %e, "Strategy could not be restarted; it will stay down.");
+                                    let mut conn = redis_conn_manager_clone.lock().await.clone();
+                                    alert!(conn, "🛑 Strategy {} failed to restart and is down: {}", strategy_id_clone, e);
+                                    break;
+                                }
                             }
                         }
                     });
@@ -307,6 +937,12 @@ impl MasterExecutor {
             } else {
                 // Strategy already running, potentially update its internal weight/config if needed
                 // (Current strategy trait doesn't have an `update_params` method, but could be added)
+                let universe = Arc::new(TokenUniverse::from_allocation(&alloc));
+                for routes in self.event_router_senders.values_mut() {
+                    for route in routes.iter_mut().filter(|r| r.strategy_id == id) {
+                        route.universe = universe.clone();
+                    }
+                }
                 info!(
                     strategy = id,
                     weight = alloc.weight,
@@ -318,156 +954,27 @@ impl MasterExecutor {
 
     async fn dispatch_event(&self, event: MarketEvent) {
         let event_type = event.get_type();
-        if let Some(senders) = self.event_router_senders.get(&event_type) {
-            for sender in senders {
-                if let Err(e) = sender.send(event.clone()).await {
-                    error!(event_type = ?event_type, error = %e, "Failed to dispatch event to strategy channel.");
-                }
-            }
-        }
-    }
-
-    fn build_strategy(&self, id: &str) -> Option<Box<dyn strategies::Strategy>> {
-        for constructor in inventory::iter::<strategies::StrategyConstructor> {
-            if constructor.0 == id {
-                return Some((constructor.1)());
-            }
-        }
-        None
-    }
-
-    #[instrument(skip(self, action), fields(strategy_id = %action.strategy_id, action_type = ?action.action_type))]
-    async fn execute_action(&self, action: StrategyAction) -> Result<()> {
-        let start_time = std::time::Instant::now();
-        info!("Executing action for strategy {}", action.strategy_id);
-
-        // Update portfolio paused state from the action if needed
-        if let Some(paused) = action.paused {
-            *self.portfolio_paused.lock().await = paused;
-            info!(
-                "Portfolio trading status updated from action: {}",
-                if paused { "PAUSED" } else { "RESUMED" }
-            );
-        }
-
-        match action.action_type {
-            shared_models::ActionType::Trade(ref order_details) => {
-                let trade_mode = {
-                    let allocations = self.strategy_allocations.lock().await;
-                    allocations
-                        .get(&action.strategy_id)
-                        .map(|a| a.mode)
-                        .unwrap_or(TradeMode::Paper)
-                };
-
-                // Log the trade attempt with the determined trade mode
-                let trade_id = self.db.log_trade_attempt(
-                    &order_details,
-                    &action.strategy_id,
-                    0.0,
-                    match trade_mode {
-                        TradeMode::Paper => "Paper",
-                        TradeMode::Live => "Live",
-                    },
-                )?;
-
-                // Execute the trade logic based on the trade mode
-                match trade_mode {
-                    TradeMode::Live => {
-                        info!(
-                            "🔴 LIVE TRADE: {} executing with real capital",
-                            action.strategy_id
-                        );
-                        // Live trading logic (e.g., sending orders to an exchange) goes here
-                        // For example, using Jupiter and Drift for executing the trade:
-                        let final_size_usd = order_details
-                            .suggested_size_usd
-                            .min(CONFIG.global_max_position_usd);
-                        let current_sol_usd_price = *self.sol_usd_price.lock().await;
-                        if current_sol_usd_price <= 0.0 {
-                            return Err(anyhow!(
-                                "SOL/USD price not available or zero. Cannot size trade."
-                            ));
-                        }
-
-                        let price_quote = self
-                            .jupiter_client
-                            .get_quote(
-                                final_size_usd / current_sol_usd_price,
-                                &order_details.token_address,
-                            )
-                            .await?;
-                        let current_token_price_usd = price_quote.price_per_token;
-
-                        // Log the trade attempt in the database
-                        self.db.log_trade_attempt(
-                            &order_details,
-                            &action.strategy_id,
-                            current_token_price_usd,
-                            "Live",
-                        )?;
-
-                        // Execute the trade using Drift or Jupiter
-                        if matches!(order_details.side, Side::Short) {
-                            // P-4: Implement Drift perp hedge for shorting
-                            info!("P-4: Executing SHORT via Drift perps.");
-                            let margin_acct = self.drift_client.get_or_create_user().await?;
-                            let args = OpenPositionArgs {
-                                market_index: 0, // Assuming SOL-PERP is market 0
-                                direction: DriftDirection::Short,
-                                base_asset_amount: (final_size_usd / current_sol_usd_price * 1e9)
-                                    as u64, // Convert USD to Lamports of SOL equivalent
-                                limit_price: None, // Market order
-                                reduce_only: false,
-                            };
-                            let sig = self.drift_client.open_position(&margin_acct, &args).await?;
-                            info!(signature = %sig, "Drift SHORT position opened.");
-                            self.db.open_trade(trade_id, &sig.to_string())?;
-                        } else {
-                            // P-4: Spot buy via Jupiter for Longs and Sells (to close shorts/take profit on longs)
-                            let swap_tx_b64 = self
-                                .jupiter_client
-                                .get_swap_transaction(
-                                    &user_pk,
-                                    &order_details.token_address,
-                                    final_size_usd,
-                                )
-                                .await?;
-                            let signed_tx_b64 =
-                                signer_client::sign_transaction(&swap_tx_b64).await?;
-                            let mut tx = crate::jupiter::deserialize_transaction(&signed_tx_b64)?;
-
-                            // P-5: Jito tip injection
-                            let bh = self.jito_client.get_recent_blockhash().await?;
-                            tx.message.set_recent_blockhash(bh);
-                            self.jito_client
-                                .attach_tip(&mut tx, CONFIG.jito_tip_lamports)
-                                .await?;
-
-                            // P-5: Send transaction via Jito
-                            let sig = self.jito_client.send_transaction(&tx).await?;
-                            info!(signature = %sig, "✅ Spot trade submitted via Jito.");
-                            self.db.open_trade(trade_id, &sig.to_string())?;
-                        }
+        if let Some(routes) = self.event_router_senders.get(&event_type) {
+            let token = event.token();
+            let policy = DispatchPolicy::for_event(&event_type);
+            for route in routes.iter().filter(|r| r.universe.admits(token)) {
+                match route.sender.dispatch(event.clone(), policy).await {
+                    Ok(Some(evicted)) => {
+                        DROPPED_EVENTS_TOTAL
+                            .with_label_values(&[
+                                route.strategy_id.as_str(),
+                                &format!("{:?}", evicted.get_type()),
+                            ])
+                            .inc();
                     }
-                    TradeMode::Paper => {
-                        info!(
-                            "Executing PAPER {} for ${} of {}",
-                            side, order_details.amount_usd, order_details.token_address
-                        );
-                        // Paper trading logic remains the same
-                        self.db.save_trade(&order_details).await?;
+                    Ok(None) => {}
+                    Err(e) => {
+                        error!(event_type = ?event_type, error = %e, "Failed to dispatch event to strategy channel.");
                     }
                 }
-                TRADES_EXECUTED
-                    .with_label_values(&[&action.strategy_id, &format!("{:?}", trade_mode)])
-                    .inc();
-                let latency = start_time.elapsed().as_secs_f64();
-                TRADE_LATENCY.set(latency);
-                info!("Trade execution took {:.4} seconds", latency);
-            }
-            shared_models::ActionType::Alert => {
-                // Handle alerts if needed
+                STRATEGY_QUEUE_DEPTH
+                    .with_label_values(&[route.strategy_id.as_str()])
+                    .set(route.sender.depth() as f64);
             }
         }
     }
@@ -476,94 +983,990 @@ impl MasterExecutor {
 #[instrument(skip_all, fields(strategy_id))]
 async fn strategy_task(
     mut strategy_instance: Box<dyn strategies::Strategy>,
-    mut rx: Receiver<MarketEvent>,
+    rx: &mut EventReceiver,
+    shadow: &mut Option<ShadowEvaluator>,
+    orders: &mut OrderTasks,
     db: Arc<Database>,
-    jupiter_client: Arc<JupiterClient>,
+    spot_router: Arc<SpotRouter>,
     drift_client: Arc<DriftClient>,
-    jito_client: Arc<JitoClient>,
-    sol_usd_price: Arc<tokio::sync::Mutex<f64>>,
-    portfolio_paused: Arc<tokio::sync::Mutex<bool>>,
+    submitter: Arc<Submitter>,
+    token_safety: Arc<TokenSafetyChecker>,
+    depth_book: Arc<DepthBook>,
+    feed_health: Arc<FeedHealth>,
+    sol_usd_price: Arc<SolPriceFeed>,
+    trading_state: Arc<tokio::sync::Mutex<TradingState>>,
+    paused_strategies: Arc<tokio::sync::Mutex<HashSet<String>>>,
+    portfolio_drawdown_pct: Arc<tokio::sync::Mutex<f64>>,
     strategy_allocations: Arc<tokio::sync::Mutex<HashMap<String, StrategyAllocation>>>,
+    started_with: &StrategyAllocation,
     strategy_id: String,
     redis_conn_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    positions: Arc<PositionBook>,
+    execution_queue: Arc<ExecutionQueue>,
 ) {
     info!("Strategy task started.");
-    while let Some(event) = rx.recv().await {
-        // P-6: Check if portfolio is paused before processing trade signals
-        let is_paused = { *portfolio_paused.lock().await }; // Lock and release
-        if is_paused {
+    let periodic = |period: Duration| {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        interval
+    };
+    let mut timer = strategy_instance.timer_interval().map(periodic);
+    let mut snapshot_timer = Some(CONFIG.strategy_snapshot_interval_secs)
+        .filter(|secs| *secs > 0)
+        .map(|secs| periodic(Duration::from_secs(secs)));
+    let mut shadow_report_timer = Some(CONFIG.shadow_report_interval_secs)
+        .filter(|secs| *secs > 0 && shadow.is_some())
+        .map(|secs| periodic(Duration::from_secs(secs)));
+    // The route may carry extra event types for the shadow; the champion only sees its own
+    let subscriptions = strategy_instance.subscriptions();
+    let mut feed_paused = false;
+    loop {
+        let event = tokio::select! {
+            event = rx.recv() => match event {
+                Some(event) => Some(event),
+                None => break,
+            },
+            _ = next_tick(&mut timer) => None,
+            _ = next_tick(&mut snapshot_timer) => {
+                save_snapshot(&redis_conn_manager, &strategy_id, strategy_instance.as_ref()).await;
+                continue;
+            }
+            _ = next_tick(&mut shadow_report_timer) => {
+                if let Some(shadow) = shadow.as_mut() {
+                    shadow.publish(&redis_conn_manager).await;
+                }
+                continue;
+            }
+        };
+
+        // P-6: Check if portfolio is paused before processing trade signals. Timer ticks
+        // still run while paused so time-bucketed state keeps rolling; their signals are dropped.
+        let state = { trading_state.lock().await.clone() }; // Lock and release
+        let halted = if !state.is_active() {
             debug!(
-                "Portfolio paused. Skipping trade signal for {}.",
-                strategy_id
+                "Portfolio {}. Skipping trade signal for {}.",
+                state, strategy_id
             );
+            true
+        } else if paused_strategies.lock().await.contains(&strategy_id)
+            || !CONFIG.flags.strategy_enabled(&strategy_id)
+        {
+            debug!("Strategy {} paused by operator or flag. Skipping trade signal.", strategy_id);
+            true
+        } else {
+            false
+        };
+        if halted && event.is_some() {
             continue;
         }
 
-        match strategy_instance.on_event(&event).await {
-            Ok(StrategyAction::Execute(details, _strategy_mode)) => {
-                // Override strategy mode with allocation mode
-                let allocations = strategy_allocations.lock().await;
-                let allocation = allocations.get(&strategy_id);
-                let actual_mode = allocation.map(|a| a.mode).unwrap_or(TradeMode::Paper);
-                drop(allocations); // Release lock
-
-                let trade_result = execute_trade(
-                    db.clone(),
-                    jupiter_client.clone(),
-                    drift_client.clone(),
-                    jito_client.clone(),
-                    sol_usd_price.clone(),
-                    details.clone(), // Clone details for the trade
+        let now = chrono::Utc::now().timestamp();
+        let portfolio = match &event {
+            Some(_) => {
+                portfolio_context(
+                    &positions,
+                    &portfolio_drawdown_pct,
+                    &strategy_allocations,
                     &strategy_id,
-                    actual_mode,
                 )
-                .await;
-
-                if let Ok(trade_id) = trade_result {
-                    // Publish trade event to analytics channel
-                    let mut conn = redis_conn_manager.lock().await.clone();
-                    let position_update = json!({
-                        "position_id": trade_id,
-                        "strategy_id": strategy_id,
-                        "token_address": details.token_address,
-                        "status": "OPEN",
-                        "pnl": 0.0,
-                        "entry_timestamp": chrono::Utc::now().timestamp(),
-                        "triggering_features": details.triggering_features,
-                    });
-
-                    let _: Result<(), _> = conn
-                        .xadd(
-                            "position_updates_channel",
-                            "*",
-                            &[("data", &position_update.to_string())],
-                        )
+                .await
+            }
+            None => PortfolioContext::default(), // Timers don't see the portfolio
+        };
+        let decision_started = Instant::now();
+        let action = match &event {
+            Some(event) if subscriptions.contains(&event.get_type()) => {
+                strategy_instance.on_event(event, &portfolio).await
+            }
+            Some(_) => Ok(StrategyAction::Hold),
+            None => strategy_instance.on_timer(now).await,
+        };
+        STRATEGY_DECISION_SECONDS
+            .with_label_values(&[&strategy_id])
+            .observe(decision_started.elapsed().as_secs_f64());
+        if let Some(shadow) = shadow.as_mut() {
+            let hold = StrategyAction::Hold;
+            let champion = action.as_ref().unwrap_or(&hold);
+            match &event {
+                Some(event) => shadow.on_event(event, &portfolio, champion).await,
+                None => shadow.on_timer(now, champion).await,
+            }
+        }
+        if halted {
+            continue;
+        }
+        let (legs, policy) = match action {
+            Ok(StrategyAction::Execute(details, _strategy_mode)) => {
+                SIGNALS_EMITTED
+                    .with_label_values(&[strategy_id.as_str(), "entry"])
+                    .inc();
+                (vec![details], LegPolicy::Independent)
+            }
+            Ok(StrategyAction::ExecuteMany(legs, policy, _strategy_mode)) => {
+                SIGNALS_EMITTED
+                    .with_label_values(&[strategy_id.as_str(), "entry"])
+                    .inc();
+                (legs, policy)
+            }
+            Ok(StrategyAction::ClosePositions(token_address)) => {
+                SIGNALS_EMITTED
+                    .with_label_values(&[strategy_id.as_str(), "exit"])
+                    .inc();
+                let mut conn = redis_conn_manager.lock().await.clone();
+                for position in positions
+                    .open_positions(&strategy_id)
+                    .iter()
+                    .filter(|p| p.token_address == token_address)
+                {
+                    let reason = "strategy exit signal".to_string();
+                    request_position_close(&mut conn, position.trade_id, &strategy_id, reason)
                         .await;
-                    info!("Published trade event for trade_id: {}", trade_id);
-                } else if let Err(e) = trade_result {
-                    error!(strategy = %strategy_id, error = %e, "Trade execution failed.");
                 }
+                continue;
             }
-            Ok(StrategyAction::Hold) => { /* No action */ }
+            Ok(StrategyAction::Hold) => continue, /* No action */
             Err(e) => {
-                error!(strategy=%strategy_id, error=%e, "Strategy returned an error on event.");
+                error!(strategy=%strategy_id, error=%e, "Strategy returned an error.");
+                continue;
+            }
+        };
+
+        // No entries on a picture some of the strategy's feeds have stopped updating
+        let stale_types = feed_health.stale_types(&subscriptions);
+        if !stale_types.is_empty() {
+            if !feed_paused {
+                warn!(strategy = %strategy_id, stale = ?stale_types, "Feeds stale, holding back entries.");
+                feed_paused = true;
+            }
+            FEED_STALE_SKIPS_TOTAL
+                .with_label_values(&[strategy_id.as_str()])
+                .inc();
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id.as_str(), "stale_feed"])
+                .inc();
+            continue;
+        } else if feed_paused {
+            info!(strategy = %strategy_id, "Feeds recovered, entries resume.");
+            feed_paused = false;
+        }
+        if policy == LegPolicy::AllOrNothing
+            && legs.iter().any(|leg| leg.order_type != OrderType::Market)
+        {
+            warn!(strategy = %strategy_id, "All-or-nothing legs must be market orders, dropping signal.");
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id.as_str(), "invalid_order"])
+                .inc();
+            continue;
+        }
+
+        // Opted-in strategies don't buy unsafe tokens even on paper
+        let legs = if strategy_instance.requires_token_safety() {
+            let leg_count = legs.len();
+            let mut safe_legs = Vec::with_capacity(leg_count);
+            for leg in legs {
+                if !matches!(leg.side, Side::Short) {
+                    if let Err(e) = token_safety.ensure_safe(&leg.token_address).await {
+                        warn!(strategy = %strategy_id, token = %leg.token_address, error = %e, "Dropping leg that failed token safety checks.");
+                        SIGNAL_REJECTIONS_TOTAL
+                            .with_label_values(&[strategy_id.as_str(), "safety_check"])
+                            .inc();
+                        continue;
+                    }
+                }
+                safe_legs.push(leg);
+            }
+            if safe_legs.is_empty()
+                || (policy == LegPolicy::AllOrNothing && safe_legs.len() < leg_count)
+            {
+                continue;
             }
+            safe_legs
+        } else {
+            legs
+        };
+
+        // Override strategy mode with allocation mode
+        let allocations = strategy_allocations.lock().await;
+        let allocation = allocations.get(&strategy_id);
+        let actual_mode = allocation.map(|a| a.mode).unwrap_or(TradeMode::Paper);
+        let max_slippage_bps = allocation.and_then(|a| a.max_slippage_bps);
+        drop(allocations); // Release lock
+
+        // One reservation per token: legs in the same token (perp + spot) are one position
+        let mut reservations: HashMap<String, Arc<PositionReservation>> = HashMap::new();
+        let mut capped = false;
+        for leg in &legs {
+            if reservations.contains_key(&leg.token_address) {
+                continue;
+            }
+            match positions.try_reserve(&strategy_id, &leg.token_address) {
+                Ok(reservation) => {
+                    reservations.insert(leg.token_address.clone(), Arc::new(reservation));
+                }
+                Err(cap) => {
+                    warn!(strategy = %strategy_id, token = %leg.token_address, cap = %cap, "Open-position cap reached, dropping signal.");
+                    POSITION_CAP_REJECTIONS_TOTAL
+                        .with_label_values(&[strategy_id.as_str(), &cap.to_string()])
+                        .inc();
+                    SIGNAL_REJECTIONS_TOTAL
+                        .with_label_values(&[strategy_id.as_str(), "position_cap"])
+                        .inc();
+                    capped = true;
+                }
+            }
+        }
+        if capped && policy == LegPolicy::AllOrNothing {
+            continue; // Dropping the reservations releases them
         }
+
+        let legs: Vec<(ExecutionContext, OrderDetails)> = legs
+            .into_iter()
+            .filter_map(|details| {
+                let reservation = reservations.get(&details.token_address)?.clone();
+                let ctx = ExecutionContext {
+                    db: db.clone(),
+                    spot_router: spot_router.clone(),
+                    drift_client: drift_client.clone(),
+                    submitter: submitter.clone(),
+                    token_safety: token_safety.clone(),
+                    depth_book: depth_book.clone(),
+                    max_slippage_bps,
+                    sol_usd_price: sol_usd_price.clone(),
+                    redis_conn_manager: redis_conn_manager.clone(),
+                    reservation,
+                    execution_queue: execution_queue.clone(),
+                };
+                Some((ctx, details))
+            })
+            .collect();
+
+        // Hand off to the execution queue; the strategy keeps consuming events.
+        match policy {
+            LegPolicy::Independent => {
+                for (ctx, details) in legs {
+                    orders.spawn_order(execute_order(
+                        ctx,
+                        details,
+                        strategy_id.clone(),
+                        actual_mode,
+                    ));
+                }
+            }
+            LegPolicy::AllOrNothing => {
+                orders.spawn_multi_leg(execute_all_or_nothing(
+                    legs,
+                    strategy_id.clone(),
+                    actual_mode,
+                ));
+            }
+        }
+    }
+
+    // Channel closed: deallocation removes the allocation and new params replace it
+    // before the channel closes, a stopping executor leaves it as it was
+    let reason = match strategy_allocations.lock().await.get(&strategy_id) {
+        None => ShutdownReason::Deallocated,
+        Some(current) if reparameterizes(started_with, current) => ShutdownReason::Reparameterized,
+        Some(_) => ShutdownReason::ExecutorStopping,
+    };
+    // Before on_shutdown, so a resting order can't open a position after it closes them
+    orders.stop(&strategy_id).await;
+    match strategy_instance.on_shutdown(reason).await {
+        Ok(ShutdownAction::ClosePositions) => {
+            request_position_closes(&db, &redis_conn_manager, &strategy_id, reason).await
+        }
+        Ok(ShutdownAction::KeepPositions) => {}
+        Err(e) => error!(strategy = %strategy_id, error = %e, "Strategy on_shutdown failed."),
+    }
+
+    // Persist in-flight state for the next start
+    save_snapshot(&redis_conn_manager, &strategy_id, strategy_instance.as_ref()).await;
+    if let Some(shadow) = shadow.as_mut() {
+        shadow.publish(&redis_conn_manager).await;
     }
     info!("Strategy task finished.");
 }
 
+/// Which running strategies an allocation set takes down: `stop` are no
+/// longer allocated, `restart` were allocated new params (e.g. a promoted A/B
+/// variant) and come back up from the new allocation.
+#[derive(Debug, Default, PartialEq)]
+struct ReconcilePlan {
+    stop: Vec<String>,
+    restart: Vec<String>,
+}
+
+fn plan_reconcile(
+    running: &[String],
+    previous: &HashMap<String, StrategyAllocation>,
+    allocated: &HashMap<String, StrategyAllocation>,
+) -> ReconcilePlan {
+    let mut plan = ReconcilePlan::default();
+    for id in running {
+        match (previous.get(id), allocated.get(id)) {
+            (_, None) => plan.stop.push(id.clone()),
+            (Some(old), Some(new)) if reparameterizes(old, new) => plan.restart.push(id.clone()),
+            _ => {}
+        }
+    }
+    plan
+}
+
+/// Reads the allocation set out of an `allocations_channel` entry, or None if
+/// it has no `allocations` field. `paper_only` downgrades every allocation to
+/// paper trading.
+fn parse_allocations(
+    message: &redis::streams::StreamId,
+    paper_only: bool,
+) -> Option<serde_json::Result<Vec<StrategyAllocation>>> {
+    let raw: String = message.get("allocations")?;
+    let parsed = serde_json::from_str::<Vec<StrategyAllocation>>(&raw).map(|mut allocations| {
+        if paper_only {
+            for allocation in &mut allocations {
+                allocation.mode = TradeMode::Paper;
+            }
+        }
+        allocations
+    });
+    Some(parsed)
+}
+
+/// Whether `new` changes `old`'s params or family, so the strategy must be rebuilt.
+fn reparameterizes(old: &StrategyAllocation, new: &StrategyAllocation) -> bool {
+    old.params != new.params || old.strategy_family() != new.strategy_family()
+}
+
+/// What `strategy_id` is shown of the portfolio before each event: its open
+/// positions, its allocation's capital budget and the drawdown.
+async fn portfolio_context(
+    positions: &PositionBook,
+    portfolio_drawdown_pct: &tokio::sync::Mutex<f64>,
+    strategy_allocations: &tokio::sync::Mutex<HashMap<String, StrategyAllocation>>,
+    strategy_id: &str,
+) -> PortfolioContext {
+    let capital_budget_usd = strategy_allocations
+        .lock()
+        .await
+        .get(strategy_id)
+        .map_or(0.0, |a| a.capital_budget_usd(CONFIG.portfolio_capital_usd));
+    PortfolioContext {
+        open_positions: positions.open_positions(strategy_id),
+        capital_budget_usd,
+        drawdown_pct: *portfolio_drawdown_pct.lock().await,
+        drawdown_limit_pct: CONFIG.tunables.get().portfolio_stop_loss_percent,
+    }
+}
+
+/// Publishes a close request for each of the strategy's open trades.
+async fn request_position_closes(
+    db: &Database,
+    redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    strategy_id: &str,
+    reason: ShutdownReason,
+) {
+    let open_trades = match db.get_open_trades() {
+        Ok(trades) => trades,
+        Err(e) => {
+            error!(strategy = %strategy_id, error = %e, "Failed to load open trades to close.");
+            return;
+        }
+    };
+    let mut conn = redis_conn_manager.lock().await.clone();
+    for trade in open_trades.iter().filter(|t| t.strategy_id == strategy_id) {
+        let reason = format!("strategy shutdown ({:?})", reason);
+        request_position_close(&mut conn, trade.id, strategy_id, reason).await;
+    }
+}
+
+/// Asks the position manager to close one trade at market.
+async fn request_position_close(
+    conn: &mut redis::aio::ConnectionManager,
+    trade_id: i64,
+    strategy_id: &str,
+    reason: String,
+) {
+    let request = PositionCloseRequest {
+        timestamp: chrono::Utc::now().timestamp(),
+        trade_id,
+        strategy_id: strategy_id.to_string(),
+        reason,
+    };
+    let result = conn
+        .xadd::<_, _, _, _, ()>(
+            POSITION_CLOSE_REQUESTS_STREAM,
+            "*",
+            &[("request", serde_json::to_string(&request).unwrap_or_default())],
+        )
+        .await;
+    match result {
+        Ok(()) => info!(strategy = %strategy_id, trade_id, "Requested position close."),
+        Err(e) => error!(strategy = %strategy_id, trade_id, error = %e, "Failed to request position close."),
+    }
+}
+
+/// A strategy snapshot as stored in Redis, stamped so stale state can be discarded.
+#[derive(Serialize, Deserialize)]
+struct StoredSnapshot {
+    saved_at: i64,
+    state: Value,
+}
+
+async fn save_snapshot(
+    redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    strategy_id: &str,
+    strategy: &dyn strategies::Strategy,
+) {
+    let state = strategy.snapshot();
+    if state.is_null() {
+        return;
+    }
+    let stored = StoredSnapshot {
+        saved_at: chrono::Utc::now().timestamp(),
+        state,
+    };
+    let mut conn = redis_conn_manager.lock().await.clone();
+    let result = match serde_json::to_string(&stored) {
+        Ok(raw) => conn
+            .hset::<_, _, _, ()>(STRATEGY_SNAPSHOTS_KEY, strategy_id, raw)
+            .await
+            .map_err(anyhow::Error::from),
+        Err(e) => Err(e.into()),
+    };
+    match result {
+        Ok(()) => debug!(strategy = %strategy_id, "Persisted strategy snapshot."),
+        Err(e) => error!(strategy = %strategy_id, error = %e, "Failed to persist strategy snapshot."),
+    }
+}
+
+/// Restores the strategy's last snapshot if there is one younger than
+/// STRATEGY_SNAPSHOT_MAX_AGE_SECS. Returns when the restored snapshot was taken.
+async fn restore_snapshot(
+    redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    strategy_id: &str,
+    strategy: &mut dyn strategies::Strategy,
+) -> Result<Option<i64>> {
+    let mut conn = redis_conn_manager.lock().await.clone();
+    let raw: Option<String> = conn.hget(STRATEGY_SNAPSHOTS_KEY, strategy_id).await?;
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let stored: StoredSnapshot = serde_json::from_str(&raw)?;
+    let age = chrono::Utc::now().timestamp() - stored.saved_at;
+    if age > CONFIG.strategy_snapshot_max_age_secs {
+        info!(strategy_id, age_secs = age, "Strategy snapshot too old, starting cold.");
+        return Ok(None);
+    }
+    strategy.restore(&stored.state)?;
+    info!(strategy_id, age_secs = age, "Restored strategy snapshot.");
+    Ok(Some(stored.saved_at))
+}
+
+/// Stream each event type is published on.
+fn stream_for(event_type: &EventType) -> &'static str {
+    match event_type {
+        EventType::Price => "events:price",
+        EventType::Social => "events:social",
+        EventType::Depth => "events:depth",
+        EventType::Bridge => "events:bridge",
+        EventType::Funding => "events:funding",
+        EventType::OnChain => "events:onchain",
+        EventType::SolPrice => "events:sol_price",
+        EventType::DataSourceHeartbeat => "events:data_source_heartbeat",
+        EventType::LiquidityPool => "events:liquidity_pool",
+        EventType::WhaleTransfer => "events:whale_transfer",
+        EventType::TokenLaunch => "events:token_launch",
+    }
+}
+
+/// Replays the strategy's subscribed streams from `since` (unix seconds) up to
+/// the ids in `until` into `on_event`, oldest first; later entries reach it
+/// through its route. Actions are discarded: buffers fill, nothing trades.
+async fn warm_up(
+    strategy: &mut dyn strategies::Strategy,
+    strategy_id: &str,
+    universe: &TokenUniverse,
+    redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    since: i64,
+    until: &HashMap<String, String>,
+) -> Result<()> {
+    let mut conn = redis_conn_manager.lock().await.clone();
+    let mut events = Vec::new();
+    for event_type in strategy.subscriptions() {
+        // Newest first so the cap keeps the most recent history
+        let stream = stream_for(&event_type);
+        let reply: redis::streams::StreamRangeReply = conn
+            .xrevrange_count(
+                stream,
+                until.get(stream).map_or("+", String::as_str),
+                format!("{}-0", since * 1000),
+                CONFIG.strategy_warmup_max_events,
+            )
+            .await?;
+        events.extend(reply.ids.iter().filter_map(|message| {
+            serde_json::from_str::<MarketEvent>(&message.get::<String>("event")?)
+                .ok()
+                .filter(|event| universe.admits(event.token()))
+        }));
+    }
+    events.sort_by_key(|event| event.timestamp());
+
+    let mut suppressed = 0;
+    let portfolio = PortfolioContext::default(); // Nothing is traded during warmup
+    for event in &events {
+        match strategy.on_event(event, &portfolio).await {
+            Ok(
+                StrategyAction::Execute(..)
+                | StrategyAction::ExecuteMany(..)
+                | StrategyAction::ClosePositions(_),
+            ) => suppressed += 1,
+            Ok(StrategyAction::Hold) => {}
+            Err(e) => debug!(strategy = %strategy_id, error = %e, "Strategy error during warmup."),
+        }
+    }
+    info!(
+        strategy = %strategy_id,
+        replayed = events.len(),
+        suppressed,
+        since,
+        "Strategy warmed up from stream history."
+    );
+    Ok(())
+}
+
+/// Builds the paper-only challenger for `strategy_id`; it warms up with the champion.
+async fn build_challenger(
+    strategy_id: &str,
+    family: &str,
+    params: &Value,
+    db: &Arc<Database>,
+) -> Result<ShadowEvaluator> {
+    let mut challenger = strategies::build_strategy(family)
+        .ok_or_else(|| anyhow!("strategy constructor not found"))?;
+    challenger.params_schema().validate(params)?;
+    challenger
+        .init(params, &MetricsHandle::new(&format!("{}_challenger", strategy_id)))
+        .await?;
+    Ok(ShadowEvaluator::new(strategy_id, challenger, db.clone()))
+}
+
+/// Resolves on the strategy's next timer tick, or never if it has no timer.
+async fn next_tick(timer: &mut Option<tokio::time::Interval>) {
+    match timer {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Exponential restart delay: base * 2^(crashes - 1), capped at the configured max.
+fn restart_backoff(crashes: u32) -> Duration {
+    let base = CONFIG.strategy_restart_base_backoff_secs;
+    let secs = base.saturating_mul(1u64 << crashes.saturating_sub(1).min(32));
+    Duration::from_secs(secs.min(CONFIG.strategy_restart_max_backoff_secs))
+}
+
+/// Rebuilds a crashed strategy from its current allocation params and
+/// restores the last snapshot it persisted, if any. A snapshot that can't be
+/// read or restored (it may be what crashed the strategy) means a cold start.
+async fn restart_strategy(
+    strategy_id: &str,
+    strategy_allocations: &Arc<tokio::sync::Mutex<HashMap<String, StrategyAllocation>>>,
+    redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+) -> Result<Box<dyn strategies::Strategy>> {
+    let (family, params) = strategy_allocations
+        .lock()
+        .await
+        .get(strategy_id)
+        .map(|alloc| (alloc.strategy_family().to_string(), alloc.params.clone()))
+        .ok_or_else(|| anyhow!("no longer allocated"))?;
+    let build = || async {
+        let mut instance = strategies::build_strategy(&family)
+            .ok_or_else(|| anyhow!("strategy constructor not found"))?;
+        instance
+            .init(&params, &MetricsHandle::new(strategy_id))
+            .await?;
+        Ok::<_, anyhow::Error>(instance)
+    };
+    let mut instance = build().await?;
+    if let Err(e) = restore_snapshot(redis_conn_manager, strategy_id, instance.as_mut()).await {
+        warn!(strategy_id, error = %e, "Failed to restore strategy snapshot, starting cold.");
+        // A failed restore may have applied part of the snapshot
+        instance = build().await?;
+    }
+    Ok(instance)
+}
+
+/// Handles a trade needs once it leaves the strategy task. Cloned into
+/// spawned tasks for orders that rest (Limit, StopEntry) or fill over time (Twap).
+#[derive(Clone)]
+struct ExecutionContext {
+    db: Arc<Database>,
+    spot_router: Arc<SpotRouter>,
+    drift_client: Arc<DriftClient>,
+    submitter: Arc<Submitter>,
+    token_safety: Arc<TokenSafetyChecker>,
+    depth_book: Arc<DepthBook>,
+    max_slippage_bps: Option<u16>, // Strategy's cap on per-trade slippage tolerance
+    sol_usd_price: Arc<SolPriceFeed>,
+    redis_conn_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    reservation: Arc<PositionReservation>, // Released once every clone of the context is dropped
+    execution_queue: Arc<ExecutionQueue>,
+}
+
+/// A strategy's orders still being worked after it handed them off. Held by
+/// the strategy's supervisor, so they outlive a crash-restart of the strategy
+/// task, and stopped when the strategy stops.
+#[derive(Default)]
+struct OrderTasks {
+    orders: JoinSet<()>,    // execute_order: resting, sliced or queued
+    multi_leg: JoinSet<()>, // execute_all_or_nothing
+}
+
+impl OrderTasks {
+    fn spawn_order(&mut self, order: impl Future<Output = ()> + Send + 'static) {
+        self.reap();
+        self.orders.spawn(order);
+    }
+
+    fn spawn_multi_leg(&mut self, order: impl Future<Output = ()> + Send + 'static) {
+        self.reap();
+        self.multi_leg.spawn(order);
+    }
+
+    /// Drops the handles of orders that have finished.
+    fn reap(&mut self) {
+        while let Some(Some(_)) = self.orders.join_next().now_or_never() {}
+        while let Some(Some(_)) = self.multi_leg.join_next().now_or_never() {}
+    }
+
+    /// Cancels resting limits, armed stop entries and remaining TWAP slices; a
+    /// fill already handed to the execution queue still completes there.
+    /// Multi-leg orders are waited out instead, since cancelling one between
+    /// legs would skip unwinding the legs already filled.
+    async fn stop(&mut self, strategy_id: &str) {
+        self.reap();
+        if !self.orders.is_empty() {
+            info!(strategy = %strategy_id, orders = self.orders.len(), "Cancelling open orders.");
+        }
+        self.orders.shutdown().await;
+        while self.multi_leg.join_next().await.is_some() {}
+    }
+}
+
+/// Works an order according to its `OrderType`, issuing one or more market
+/// fills through `execute_trade`.
+#[instrument(
+    skip_all,
+    fields(strategy_id = %strategy_id, token_address = %details.token_address, order_type = ?details.order_type)
+)]
+async fn execute_order(
+    ctx: ExecutionContext,
+    details: OrderDetails,
+    strategy_id: String,
+    trade_mode: TradeMode,
+) {
+    match details.order_type.clone() {
+        OrderType::Market => {
+            queued_fill(&ctx, details, &strategy_id, trade_mode).await;
+        }
+        OrderType::Limit { price, ttl_secs } => {
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(ttl_secs);
+            while tokio::time::Instant::now() < deadline {
+                match current_price(&ctx, &details).await {
+                    Ok(quote) => {
+                        let marketable = match details.side {
+                            Side::Long => quote <= price,
+                            Side::Short => quote >= price,
+                        };
+                        if marketable {
+                            info!(quote, limit = price, "Limit order marketable, filling.");
+                            // Filled at market, so it books at the quote it fills at, not the limit
+                            let fill = OrderDetails {
+                                limit_price: None,
+                                order_type: OrderType::Market,
+                                ..details
+                            };
+                            queued_fill(&ctx, fill, &strategy_id, trade_mode).await;
+                            return;
+                        }
+                        debug!(quote, limit = price, "Limit order resting.");
+                    }
+                    Err(e) => warn!(error = %e, "Failed to quote resting limit order."),
+                }
+                tokio::time::sleep(Duration::from_secs(CONFIG.order_poll_interval_secs)).await;
+            }
+            info!(limit = price, ttl_secs, "Limit order expired unfilled.");
+        }
+        OrderType::Twap {
+            duration_secs,
+            slices,
+        } => {
+            let slices = slices.max(1);
+            // Clamp the parent order so slicing can't be used to bypass the position cap.
+            let total_size_usd = details
+                .suggested_size_usd
+                .min(CONFIG.tunables.get().global_max_position_usd);
+            let slice_size_usd = total_size_usd / slices as f64;
+            let interval = Duration::from_secs(duration_secs) / slices;
+            info!(
+                slices,
+                slice_size_usd,
+                interval_secs = interval.as_secs_f64(),
+                "Starting TWAP order."
+            );
+            for slice in 0..slices {
+                let fill = OrderDetails {
+                    suggested_size_usd: slice_size_usd,
+                    order_type: OrderType::Market,
+                    ..details.clone()
+                };
+                queued_fill(&ctx, fill, &strategy_id, trade_mode).await;
+                if slice + 1 < slices {
+                    tokio::time::sleep(interval).await;
+                }
+            }
+            info!(slices, "TWAP order complete.");
+        }
+        OrderType::StopEntry { trigger_price } => {
+            let deadline = tokio::time::Instant::now()
+                + Duration::from_secs(CONFIG.stop_entry_max_wait_secs);
+            while tokio::time::Instant::now() < deadline {
+                match current_price(&ctx, &details).await {
+                    Ok(quote) => {
+                        let triggered = match details.side {
+                            Side::Long => quote >= trigger_price,
+                            Side::Short => quote <= trigger_price,
+                        };
+                        if triggered {
+                            info!(
+                                quote,
+                                trigger = trigger_price,
+                                "Stop entry triggered, filling at market."
+                            );
+                            let fill = OrderDetails {
+                                order_type: OrderType::Market,
+                                ..details
+                            };
+                            queued_fill(&ctx, fill, &strategy_id, trade_mode).await;
+                            return;
+                        }
+                        debug!(quote, trigger = trigger_price, "Stop entry armed.");
+                    }
+                    Err(e) => warn!(error = %e, "Failed to quote armed stop entry."),
+                }
+                tokio::time::sleep(Duration::from_secs(CONFIG.order_poll_interval_secs)).await;
+            }
+            info!(trigger = trigger_price, "Stop entry expired without triggering.");
+        }
+    }
+}
+
+/// Current quoted token price for the order's size, used by resting order types.
+async fn current_price(ctx: &ExecutionContext, details: &OrderDetails) -> Result<f64> {
+    let sol_usd = ctx.sol_usd_price.get().await?;
+    let size_usd = details
+        .suggested_size_usd
+        .min(CONFIG.tunables.get().global_max_position_usd);
+    Ok(ctx
+        .spot_router
+        .best_quote(size_usd, sol_usd, &details.token_address, details.venue)
+        .await?
+        .price_per_token())
+}
+
+/// How one market fill ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FillOutcome {
+    Filled(i64), // Trade ID of the position now held
+    DryRun,      // Stopped before signing under DRY_RUN
+    NotFilled,
+}
+
+/// Submits one market fill to the execution queue and waits until it has run.
+async fn queued_fill(
+    ctx: &ExecutionContext,
+    details: OrderDetails,
+    strategy_id: &str,
+    trade_mode: TradeMode,
+) -> FillOutcome {
+    let token_address = details.token_address.clone();
+    let priority = details.confidence;
+    let job_ctx = ctx.clone();
+    let strategy_id = strategy_id.to_string();
+    let (outcome_tx, outcome_rx) = oneshot::channel();
+    let finished = ctx.execution_queue.submit(
+        token_address,
+        priority,
+        Box::pin(async move {
+            let outcome = fill_order(&job_ctx, details, &strategy_id, trade_mode).await;
+            let _ = outcome_tx.send(outcome);
+        }),
+    );
+    let _ = finished.await;
+    outcome_rx.await.unwrap_or(FillOutcome::NotFilled)
+}
+
+/// Fills the legs of an all-or-nothing `ExecuteMany` in order. If a leg fails,
+/// the legs already filled are sent to the position manager to be closed, so
+/// the strategy isn't left holding half of a hedged position. Under DRY_RUN no
+/// leg is held, so every leg is only logged and nothing is unwound.
+async fn execute_all_or_nothing(
+    legs: Vec<(ExecutionContext, OrderDetails)>,
+    strategy_id: String,
+    trade_mode: TradeMode,
+) {
+    let total = legs.len();
+    let mut filled: Vec<i64> = Vec::with_capacity(total);
+    let mut dry_run = false;
+    for (i, (ctx, details)) in legs.into_iter().enumerate() {
+        let token_address = details.token_address.clone();
+        match queued_fill(&ctx, details, &strategy_id, trade_mode).await {
+            FillOutcome::Filled(trade_id) => {
+                filled.push(trade_id);
+                continue;
+            }
+            FillOutcome::DryRun => {
+                dry_run = true;
+                continue;
+            }
+            FillOutcome::NotFilled => {}
+        }
+
+        error!(strategy = %strategy_id, leg = i + 1, legs = total, token = %token_address, "Leg failed, unwinding {} filled legs.", filled.len());
+        let outcome = if filled.is_empty() { "failed" } else { "unwound" };
+        MULTI_LEG_ORDERS_TOTAL
+            .with_label_values(&[strategy_id.as_str(), outcome])
+            .inc();
+        if !filled.is_empty() {
+            let mut conn = ctx.redis_conn_manager.lock().await.clone();
+            for trade_id in &filled {
+                let reason = format!("leg {}/{} of a multi-leg order failed", i + 1, total);
+                request_position_close(&mut conn, *trade_id, &strategy_id, reason).await;
+            }
+            alert!(conn, "⚠️ Multi-leg order for {} failed on leg {}/{}; closing {} filled legs", strategy_id, i + 1, total, filled.len());
+        }
+        return;
+    }
+    if dry_run {
+        MULTI_LEG_ORDERS_TOTAL
+            .with_label_values(&[strategy_id.as_str(), "dry_run"])
+            .inc();
+        info!(strategy = %strategy_id, legs = total, "🧪 DRY RUN: all legs of multi-leg order built, none sent.");
+        return;
+    }
+    MULTI_LEG_ORDERS_TOTAL
+        .with_label_values(&[strategy_id.as_str(), "filled"])
+        .inc();
+    info!(strategy = %strategy_id, legs = total, "All legs of multi-leg order filled.");
+}
+
+/// Executes a single market fill, publishes its `ExecutionReport`, and on
+/// success publishes the resulting position update.
+async fn fill_order(
+    ctx: &ExecutionContext,
+    details: OrderDetails,
+    strategy_id: &str,
+    trade_mode: TradeMode,
+) -> FillOutcome {
+    let trade_result = execute_trade(
+        ctx.db.clone(),
+        ctx.spot_router.clone(),
+        ctx.drift_client.clone(),
+        ctx.submitter.clone(),
+        ctx.token_safety.clone(),
+        ctx.depth_book.clone(),
+        ctx.max_slippage_bps,
+        ctx.sol_usd_price.clone(),
+        details.clone(), // Clone details for the trade
+        strategy_id,
+        trade_mode,
+    )
+    .await;
+
+    let mut conn = ctx.redis_conn_manager.lock().await.clone();
+    let report = match &trade_result {
+        Ok(report) => report.clone(),
+        Err(e) => ExecutionReport {
+            timestamp: chrono::Utc::now().timestamp(),
+            trade_id: None,
+            strategy_id: strategy_id.to_string(),
+            token_address: details.token_address.clone(),
+            side: details.side.clone(),
+            mode: trade_mode,
+            status: ExecutionStatus::Failed,
+            requested_size_usd: details.suggested_size_usd,
+            filled_size_usd: 0.0,
+            fill_price_usd: 0.0,
+            fees_usd: 0.0,
+            signature: None,
+            error: Some(e.to_string()),
+        },
+    };
+    if let Ok(report_json) = serde_json::to_string(&report) {
+        let _: Result<(), _> = conn
+            .xadd("execution_reports", "*", &[("report", &report_json)])
+            .await;
+    }
+
+    match trade_result {
+        Ok(ExecutionReport {
+            status: ExecutionStatus::DryRun,
+            ..
+        }) => FillOutcome::DryRun, // Nothing is held
+        Ok(ExecutionReport {
+            status: ExecutionStatus::Filled | ExecutionStatus::PartiallyFilled,
+            trade_id: Some(trade_id),
+            ..
+        }) => {
+            ctx.reservation.record_fill();
+            TRADES_EXECUTED
+                .with_label_values(&[strategy_id, &format!("{:?}", trade_mode)])
+                .inc();
+
+            // Publish trade event to analytics channel
+            let position_update = json!({
+                "position_id": trade_id,
+                "strategy_id": strategy_id,
+                "token_address": details.token_address,
+                "status": TradeState::Open.as_str(),
+                "pnl": 0.0,
+                "entry_timestamp": chrono::Utc::now().timestamp(),
+                "triggering_features": details.triggering_features,
+            });
+
+            let _: Result<(), _> = conn
+                .xadd(
+                    "position_updates_channel",
+                    "*",
+                    &[("data", &position_update.to_string())],
+                )
+                .await;
+            info!("Published trade event for trade_id: {}", trade_id);
+            FillOutcome::Filled(trade_id)
+        }
+        Ok(_) => FillOutcome::NotFilled, // Unconfirmed: not held until reconciled
+        Err(e) => {
+            error!(strategy = %strategy_id, error = %e, "Trade execution failed.");
+            FillOutcome::NotFilled
+        }
+    }
+}
+
 #[instrument(skip_all, fields(strategy_id, token_address = %details.token_address, action = ?details.side))]
 async fn execute_trade(
     db: Arc<Database>,
-    jupiter: Arc<JupiterClient>,
+    spot_router: Arc<SpotRouter>,
     drift: Arc<DriftClient>,
-    jito: Arc<JitoClient>,
-    sol_price: Arc<tokio::sync::Mutex<f64>>,
+    submitter: Arc<Submitter>,
+    token_safety: Arc<TokenSafetyChecker>,
+    depth_book: Arc<DepthBook>,
+    max_slippage_bps: Option<u16>,
+    sol_price: Arc<SolPriceFeed>,
     details: OrderDetails,
     strategy_id: &str,
     trade_mode: TradeMode,
-) -> Result<i64> { // Return trade_id on success
+) -> Result<ExecutionReport> {
     let mode_str = if trade_mode == TradeMode::Live {
         "LIVE"
     } else {
@@ -572,30 +1975,134 @@ async fn execute_trade(
     info!("Attempting {} trade.", mode_str);
 
     // Limit suggested size by global max position
-    let final_size_usd = details
+    let tunables = CONFIG.tunables.get();
+    let mut final_size_usd = details
         .suggested_size_usd
-        .min(CONFIG.global_max_position_usd);
+        .min(tunables.global_max_position_usd);
+
+    // P-2: Get live SOL/USD price, falling back to Pyth if the stream has gone quiet
+    let current_sol_usd_price = match sol_price.get().await {
+        Ok(price) => price,
+        Err(e) => {
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id, "stale_price"])
+                .inc();
+            return Err(e);
+        }
+    };
+
+    if trade_mode == TradeMode::Live
+        && matches!(details.side, Side::Short)
+        && !CONFIG.flags.enabled(flags::DRIFT_SHORTS, true)
+    {
+        SIGNAL_REJECTIONS_TOTAL
+            .with_label_values(&[strategy_id, "flag_disabled"])
+            .inc();
+        return Err(anyhow!("Drift shorts are off ({} flag)", flags::DRIFT_SHORTS));
+    }
 
-    // P-2: Get live SOL/USD price
-    let current_sol_usd_price = *sol_price.lock().await;
-    if current_sol_usd_price <= 0.0 {
+    // Shorts are Drift perp orders, so only tokens with a perp market of their own
+    if matches!(details.side, Side::Short) && drift_market_index(&details.token_address).is_none() {
+        SIGNAL_REJECTIONS_TOTAL
+            .with_label_values(&[strategy_id, "no_perp_market"])
+            .inc();
         return Err(anyhow!(
-            "SOL/USD price not available or zero. Cannot size trade."
+            "No Drift perp market to short {}",
+            details.token_address
         ));
     }
 
-    // Use limit price from details if available, otherwise get quote
-    let current_token_price_usd = if let Some(limit_price) = details.limit_price {
-        limit_price
-    } else {
-        jupiter
-            .get_quote(
-                final_size_usd / current_sol_usd_price,
-                &details.token_address,
-            )
-            .await?
-            .price_per_token
-    };
+    // Live spot entries must clear the token safety rules before any capital moves
+    if trade_mode == TradeMode::Live && !matches!(details.side, Side::Short) {
+        if let Err(e) = token_safety.ensure_safe(&details.token_address).await {
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id, "safety_check"])
+                .inc();
+            return Err(e);
+        }
+    }
+
+    // Use limit price from details if available, otherwise the best venue's quoted price.
+    // The quote is reused to build the live swap so both see the same route.
+    let mut quote = timed_quote(
+        &spot_router,
+        strategy_id,
+        final_size_usd,
+        current_sol_usd_price,
+        &details,
+    )
+    .await?;
+
+    // Don't buy far up the curve: downsize once (if enabled) or abort on excessive impact
+    if trade_mode == TradeMode::Live
+        && !matches!(details.side, Side::Short)
+        && quote.price_impact_pct() > tunables.max_price_impact_pct
+    {
+        warn!(
+            venue = ?quote.venue(),
+            size_usd = final_size_usd,
+            price_per_token = quote.price_per_token(),
+            price_impact_pct = quote.price_impact_pct(),
+            max_price_impact_pct = tunables.max_price_impact_pct,
+            "🚫 Quote exceeds max price impact."
+        );
+        if !CONFIG.downsize_on_price_impact {
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id, "risk"])
+                .inc();
+            return Err(anyhow!(
+                "Price impact {:.2}% on {} exceeds max {:.2}%",
+                quote.price_impact_pct() * 100.0,
+                details.token_address,
+                tunables.max_price_impact_pct * 100.0
+            ));
+        }
+        // Impact scales roughly linearly with size on an AMM; leave 10% headroom
+        let downsized_usd =
+            final_size_usd * tunables.max_price_impact_pct / quote.price_impact_pct() * 0.9;
+        quote = timed_quote(
+            &spot_router,
+            strategy_id,
+            downsized_usd,
+            current_sol_usd_price,
+            &details,
+        )
+        .await?;
+        if quote.price_impact_pct() > tunables.max_price_impact_pct {
+            warn!(
+                venue = ?quote.venue(),
+                size_usd = downsized_usd,
+                price_impact_pct = quote.price_impact_pct(),
+                "🚫 Downsized quote still exceeds max price impact."
+            );
+            SIGNAL_REJECTIONS_TOTAL
+                .with_label_values(&[strategy_id, "risk"])
+                .inc();
+            return Err(anyhow!(
+                "Price impact {:.2}% on {} exceeds max {:.2}% even at ${:.2}",
+                quote.price_impact_pct() * 100.0,
+                details.token_address,
+                tunables.max_price_impact_pct * 100.0,
+                downsized_usd
+            ));
+        }
+        info!(
+            from_usd = final_size_usd,
+            to_usd = downsized_usd,
+            price_impact_pct = quote.price_impact_pct(),
+            "Downsized order to stay within max price impact."
+        );
+        final_size_usd = downsized_usd;
+    }
+
+    let slippage_bps = depth_book.slippage_bps(
+        &details.token_address,
+        final_size_usd,
+        quote.price_impact_pct(),
+        max_slippage_bps,
+    );
+    quote.set_slippage_bps(slippage_bps);
+    let current_token_price_usd = quote.price_per_token();
 
     let trade_id = db.log_trade_attempt(
         &details,
@@ -612,12 +2119,41 @@ async fn execute_trade(
         price_usd = current_token_price_usd,
         "Trade attempt logged."
     );
+    // Safety, price impact and position limits were all checked above; the
+    // approved quote rides along in the note for the audit trail
+    let approved_quote = json!({
+        "venue": quote.venue(),
+        "size_usd": final_size_usd,
+        "price_per_token": quote.price_per_token(),
+        "price_impact_pct": quote.price_impact_pct(),
+        "slippage_bps": slippage_bps,
+    });
+    let mut lifecycle = TradeLifecycle::new(db.clone(), trade_id);
+    lifecycle.advance(TradeState::RiskApproved, Some(&approved_quote.to_string()))?;
+
+    let mut report = ExecutionReport {
+        timestamp: chrono::Utc::now().timestamp(),
+        trade_id: Some(trade_id),
+        strategy_id: strategy_id.to_string(),
+        token_address: details.token_address.clone(),
+        side: details.side.clone(),
+        mode: trade_mode,
+        status: ExecutionStatus::Filled,
+        requested_size_usd: details.suggested_size_usd,
+        filled_size_usd: final_size_usd,
+        fill_price_usd: current_token_price_usd,
+        fees_usd: 0.0,
+        signature: None,
+        error: None,
+    };
 
     // For paper trading, just simulate the trade
     if trade_mode == TradeMode::Paper {
         info!("📝 PAPER TRADING: Simulating trade.");
-        db.open_trade(trade_id, "PAPER_TRADE")?;
-        return Ok(trade_id);
+        db.set_signature(trade_id, "PAPER_TRADE")?;
+        lifecycle.advance(TradeState::Open, Some("paper"))?;
+        report.signature = Some("PAPER_TRADE".to_string());
+        return Ok(report);
     }
 
     // Below here is LIVE TRADING ONLY
@@ -627,37 +2163,422 @@ async fn execute_trade(
     if matches!(details.side, Side::Short) {
         // P-4: Implement Drift perp hedge for shorting
         info!("P-4: Executing SHORT via Drift perps.");
-        let margin_acct = drift.get_or_create_user().await?;
+        let market_index = drift_market_index(&details.token_address)
+            .ok_or_else(|| anyhow!("No Drift perp market to short {}", details.token_address))?;
         let args = OpenPositionArgs {
-            market_index: 0, // Assuming SOL-PERP is market 0
+            market_index,
             direction: DriftDirection::Short,
-            base_asset_amount: (final_size_usd / current_sol_usd_price * 1e9) as u64, // Convert USD to Lamports of SOL equivalent
+            base_asset_amount: (final_size_usd / current_token_price_usd * 1e9) as u64, // Whole tokens at Drift's 1e9 base precision
             limit_price: None, // Market order
             reduce_only: false,
         };
+        if CONFIG.dry_run {
+            // The Drift client builds, signs and sends in one call, so only its order can be shown
+            let order = json!({
+                "venue": "drift",
+                "market_index": args.market_index,
+                "direction": "short",
+                "base_asset_amount": args.base_asset_amount,
+                "reduce_only": args.reduce_only,
+            });
+            return finish_dry_run(&db, lifecycle, report, order);
+        }
+        let margin_acct = drift.get_or_create_user().await?;
         let sig = drift.open_position(&margin_acct, &args).await?;
         info!(signature = %sig, "Drift SHORT position opened.");
-        db.open_trade(trade_id, &sig.to_string())?;
+        lifecycle.submitted(&sig.to_string())?;
+        lifecycle.advance(TradeState::Open, None)?;
+        report.signature = Some(sig.to_string());
         // Note: Closing short positions, managing collateral, and PnL tracking for shorts
         // would require additional logic (e.g., a dedicated position monitor for Drift trades).
     } else {
         // P-4: Spot buy via Jupiter for Longs and Sells (to close shorts/take profit on longs)
-        let swap_tx_b64 = jupiter
-            .get_swap_transaction(&user_pk, &details.token_address, final_size_usd)
-            .await?;
-        let signed_tx_b64 = signer_client::sign_transaction(&swap_tx_b64).await?;
-        let mut tx = crate::jupiter::deserialize_transaction(&signed_tx_b64)?;
+        info!(
+            venue = ?quote.venue(),
+            price_impact_pct = quote.price_impact_pct(),
+            slippage_bps,
+            "Building spot swap."
+        );
+        if CONFIG.dry_run {
+            // Tips and priority fees are added by the submitter after signing
+            let swap_tx_b64 = spot_router.build_swap(&user_pk, &quote).await?;
+            let transaction = json!({
+                "venue": quote.venue(),
+                "size_usd": final_size_usd,
+                "price_per_token": quote.price_per_token(),
+                "price_impact_pct": quote.price_impact_pct(),
+                "slippage_bps": slippage_bps,
+                "submission_chain": CONFIG.submission_fallback_chain,
+                "jito_tip_lamports": CONFIG.tunables.get().jito_tip_lamports,
+                "unsigned_transaction_b64": swap_tx_b64,
+            });
+            return finish_dry_run(&db, lifecycle, report, transaction);
+        }
+        // Retries re-quote and rebuild, so each attempt carries a fresh route and
+        // blockhash. The submitter only errors once none of its signed transactions
+        // can land any more (rejected, or expired unseen), so a failed attempt is
+        // never one that landed late and retrying can't buy twice.
+        let mut attempt: u32 = 1;
+        let submission = loop {
+            match submit_swap(&spot_router, &submitter, &user_pk, &quote).await {
+                Ok(submission) => break submission,
+                Err(e) if attempt < CONFIG.swap_max_attempts => {
+                    let backoff = swap_retry_backoff(attempt);
+                    warn!(attempt, error = %e, backoff_ms = backoff.as_millis() as u64, "Swap attempt failed, retrying.");
+                    tokio::time::sleep(backoff).await;
 
-        // P-5: Jito tip injection
-        let bh = jito.get_recent_blockhash().await?;
-        tx.message.set_recent_blockhash(bh);
-        jito.attach_tip(&mut tx, CONFIG.jito_tip_lamports).await?;
+                    attempt += 1;
+                    match timed_quote(
+                        &spot_router,
+                        strategy_id,
+                        final_size_usd,
+                        current_sol_usd_price,
+                        &details,
+                    )
+                    .await
+                    {
+                        Ok(fresh) => {
+                            quote = fresh;
+                            quote.set_slippage_bps(slippage_bps);
+                        }
+                        Err(e) => warn!(attempt, error = %e, "Re-quote failed, retrying with the previous route."),
+                    }
+                }
+                Err(e) => {
+                    lifecycle.fail(&format!("swap failed after {} attempts: {}", attempt, e))?;
+                    return Err(e.context(format!("Swap failed after {} attempts", attempt)));
+                }
+            }
+        };
+        info!(signature = %submission.signature, path = %submission.path, "✅ Spot trade submitted.");
+        let submitted_at = Instant::now();
+        let venue = format!("{:?}", quote.venue());
+        SIGNING_LATENCY
+            .with_label_values(&[strategy_id, &venue])
+            .observe(submission.signing.as_secs_f64());
+        lifecycle.submitted(&submission.signature.to_string())?;
+        report.signature = Some(submission.signature.to_string());
+        report.fees_usd = submission.fee_lamports as f64 / 1e9 * current_sol_usd_price;
+
+        // Size the position by what actually landed, not by the quote
+        let confirmed = match submitter
+            .fills
+            .confirm(
+                &submission.signature,
+                &user_pk,
+                &details.token_address,
+                submission.tip_lamports(),
+            )
+            .await?
+        {
+            Some(fill) => Some(fill),
+            // Not fetched in time; only a transaction known to have landed is worth another wait
+            None => match submitter
+                .fills
+                .settle(&submission.signature, submission.last_valid_block_height)
+                .await
+            {
+                Settlement::Landed => {
+                    submitter
+                        .fills
+                        .confirm(
+                            &submission.signature,
+                            &user_pk,
+                            &details.token_address,
+                            submission.tip_lamports(),
+                        )
+                        .await?
+                }
+                Settlement::Expired => {
+                    lifecycle.fail("expired without landing")?;
+                    return Err(anyhow!(
+                        "Swap {} expired without landing",
+                        submission.signature
+                    ));
+                }
+                Settlement::Unknown => None,
+            },
+        };
+        let fill = match confirmed {
+            Some(fill) => {
+                SUBMIT_TO_CONFIRM
+                    .with_label_values(&[strategy_id, &venue])
+                    .observe(submitted_at.elapsed().as_secs_f64());
+                fill
+            }
+            None => {
+                // The position may or may not exist; it stays SUBMITTED until reconciled
+                warn!(signature = %submission.signature, "Fill unconfirmed, leaving the trade in flight.");
+                report.status = ExecutionStatus::Unconfirmed;
+                report.error = Some("fill unconfirmed".to_string());
+                return Ok(report);
+            }
+        };
+        if fill.failed || fill.token_amount == 0 {
+            lifecycle.fail("landed without filling")?;
+            return Err(anyhow!(
+                "Swap {} landed without filling",
+                submission.signature
+            ));
+        }
+
+        lifecycle.advance(TradeState::Confirmed, None)?;
+
+        let mut filled_tokens = fill.tokens();
+        let mut filled_usd = fill.sol_spent_lamports as f64 / 1e9 * current_sol_usd_price;
+        let filled_fraction = fill.token_amount as f64 / quote.out_amount() as f64;
+        if filled_fraction < CONFIG.partial_fill_threshold {
+            let remainder_usd = (final_size_usd - filled_usd).max(0.0);
+            warn!(
+                signature = %submission.signature,
+                filled_fraction,
+                filled_usd,
+                remainder_usd,
+                "Partial fill detected."
+            );
+            if CONFIG.resubmit_partial_remainder && remainder_usd >= CONFIG.min_remainder_usd {
+                // The first fill is already held, so a failed remainder only shrinks the position
+                match resubmit_remainder(
+                    &spot_router,
+                    &submitter,
+                    &user_pk,
+                    &details,
+                    strategy_id,
+                    remainder_usd,
+                    current_sol_usd_price,
+                    slippage_bps,
+                )
+                .await
+                {
+                    Ok((resubmission, extra)) => {
+                        report.fees_usd +=
+                            resubmission.fee_lamports as f64 / 1e9 * current_sol_usd_price;
+                        match extra {
+                            Some(extra) if !extra.failed => {
+                                info!(signature = %resubmission.signature, tokens = extra.tokens(), "Remainder filled.");
+                                filled_tokens += extra.tokens();
+                                filled_usd +=
+                                    extra.sol_spent_lamports as f64 / 1e9 * current_sol_usd_price;
+                            }
+                            _ => warn!(signature = %resubmission.signature, "Remainder did not fill, shrinking position."),
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "Remainder resubmission failed, shrinking position."),
+                }
+            }
+        }
+
+        let fill_price_usd = filled_usd / filled_tokens;
+        db.record_fill(trade_id, filled_tokens, filled_usd, fill_price_usd)?;
+        lifecycle.advance(TradeState::Open, None)?;
+        report.filled_size_usd = filled_usd;
+        report.fill_price_usd = fill_price_usd;
+        if filled_usd < final_size_usd * CONFIG.partial_fill_threshold {
+            report.status = ExecutionStatus::PartiallyFilled;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Ends a Live trade under DRY_RUN at the point it would have been signed.
+/// What would have been sent is logged and kept as the note on the trade,
+/// which is CANCELED so nothing treats it as a held position.
+fn finish_dry_run(
+    db: &Database,
+    mut lifecycle: TradeLifecycle,
+    mut report: ExecutionReport,
+    would_send: Value,
+) -> Result<ExecutionReport> {
+    let trade_id = report.trade_id.unwrap_or_default();
+    warn!(
+        trade_id,
+        strategy = %report.strategy_id,
+        token = %report.token_address,
+        would_send = %would_send,
+        "🧪 DRY RUN: stopping before signing."
+    );
+    db.set_signature(trade_id, "DRY_RUN")?;
+    lifecycle.advance(TradeState::Canceled, Some(&would_send.to_string()))?;
+    report.status = ExecutionStatus::DryRun;
+    report.signature = Some("DRY_RUN".to_string());
+    Ok(report)
+}
+
+/// Best spot quote for the order's token, timed into the quote latency histogram.
+async fn timed_quote(
+    spot_router: &SpotRouter,
+    strategy_id: &str,
+    size_usd: f64,
+    sol_usd_price: f64,
+    details: &OrderDetails,
+) -> Result<SpotQuote> {
+    let started = Instant::now();
+    let quote = spot_router
+        .best_quote(
+            size_usd,
+            sol_usd_price,
+            &details.token_address,
+            details.venue,
+        )
+        .await?;
+    QUOTE_LATENCY
+        .with_label_values(&[strategy_id, &format!("{:?}", quote.venue())])
+        .observe(started.elapsed().as_secs_f64());
+    Ok(quote)
+}
+
+/// One build -> sign -> submit pass for a spot quote.
+async fn submit_swap(
+    spot_router: &SpotRouter,
+    submitter: &Submitter,
+    user_pk: &Pubkey,
+    quote: &SpotQuote,
+) -> Result<Submission> {
+    let swap_tx_b64 = spot_router.build_swap(user_pk, quote).await?;
+    // P-5: Jito bundle, falling back only along SUBMISSION_FALLBACK_CHAIN
+    submitter.submit(&swap_tx_b64).await
+}
+
+/// Quotes, submits and confirms the unfilled remainder of a partial fill.
+async fn resubmit_remainder(
+    spot_router: &SpotRouter,
+    submitter: &Submitter,
+    user_pk: &Pubkey,
+    details: &OrderDetails,
+    strategy_id: &str,
+    remainder_usd: f64,
+    sol_usd_price: f64,
+    slippage_bps: u16,
+) -> Result<(Submission, Option<Fill>)> {
+    let mut remainder = timed_quote(
+        spot_router,
+        strategy_id,
+        remainder_usd,
+        sol_usd_price,
+        details,
+    )
+    .await?;
+    remainder.set_slippage_bps(slippage_bps);
+    let resubmission = submit_swap(spot_router, submitter, user_pk, &remainder).await?;
+    let submitted_at = Instant::now();
+    let venue = format!("{:?}", remainder.venue());
+    SIGNING_LATENCY
+        .with_label_values(&[strategy_id, &venue])
+        .observe(resubmission.signing.as_secs_f64());
+    let fill = submitter
+        .fills
+        .confirm(
+            &resubmission.signature,
+            user_pk,
+            &details.token_address,
+            resubmission.tip_lamports(),
+        )
+        .await?;
+    if fill.is_some() {
+        SUBMIT_TO_CONFIRM
+            .with_label_values(&[strategy_id, &venue])
+            .observe(submitted_at.elapsed().as_secs_f64());
+    }
+    Ok((resubmission, fill))
+}
 
-        // P-5: Send transaction via Jito
-        let sig = jito.send_transaction(&tx).await?;
-        info!(signature = %sig, "✅ Spot trade submitted via Jito.");
-        db.open_trade(trade_id, &sig.to_string())?;
+/// Exponential backoff between swap attempts: base * 2^(attempt-1), capped.
+fn swap_retry_backoff(attempt: u32) -> Duration {
+    let ms = CONFIG
+        .swap_retry_base_backoff_ms
+        .saturating_mul(1u64 << (attempt - 1).min(16));
+    Duration::from_millis(ms.min(CONFIG.swap_retry_max_backoff_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(id: &str, params: Value) -> StrategyAllocation {
+        serde_json::from_value(json!({
+            "id": id,
+            "weight": 0.5,
+            "sharpeRatio": 1.2,
+            "mode": "Live",
+            "params": params,
+        }))
+        .unwrap()
     }
 
-    Ok(trade_id)
+    fn allocations(entries: &[(&str, Value)]) -> HashMap<String, StrategyAllocation> {
+        entries
+            .iter()
+            .map(|(id, params)| (id.to_string(), allocation(id, params.clone())))
+            .collect()
+    }
+
+    fn stream_entry(fields: &[(&str, &str)]) -> redis::streams::StreamId {
+        redis::streams::StreamId {
+            id: "1700000000000-0".to_string(),
+            map: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), redis::Value::Data(v.as_bytes().to_vec())))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn allocation_entries() {
+        let raw =
+            serde_json::to_string(&[allocation("momentum_5m", json!({"lookback": 5}))]).unwrap();
+        let entry = stream_entry(&[("allocations", &raw)]);
+
+        let live = parse_allocations(&entry, false).unwrap().unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].id, "momentum_5m");
+        assert_eq!(live[0].params, json!({"lookback": 5}));
+        assert!(live[0].is_live());
+
+        let paper = parse_allocations(&entry, true).unwrap().unwrap();
+        assert_eq!(paper[0].mode, TradeMode::Paper);
+
+        assert!(
+            parse_allocations(&stream_entry(&[("allocations", "not json")]), false)
+                .unwrap()
+                .is_err()
+        );
+        assert!(parse_allocations(&stream_entry(&[("other", "[]")]), false).is_none());
+    }
+
+    #[test]
+    fn reconcile_stops_deallocated_and_restarts_reparameterized() {
+        let running: Vec<String> = ["kept", "retuned", "dropped"].map(String::from).into();
+        let previous = allocations(&[
+            ("kept", json!({"lookback": 5})),
+            ("retuned", json!({"lookback": 5})),
+            ("dropped", json!({})),
+        ]);
+        let allocated = allocations(&[
+            ("kept", json!({"lookback": 5})),
+            ("retuned", json!({"lookback": 10})),
+            ("added", json!({})),
+        ]);
+
+        let plan = plan_reconcile(&running, &previous, &allocated);
+        assert_eq!(
+            plan,
+            ReconcilePlan {
+                stop: vec!["dropped".to_string()],
+                restart: vec!["retuned".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn reconcile_restarts_on_family_change() {
+        let running = vec!["variant".to_string()];
+        let previous = allocations(&[("variant", json!({}))]);
+        let mut allocated = previous.clone();
+        allocated.get_mut("variant").unwrap().family = "mean_revert_1h".to_string();
+
+        let plan = plan_reconcile(&running, &previous, &allocated);
+        assert_eq!(plan.restart, vec!["variant".to_string()]);
+        assert!(plan.stop.is_empty());
+    }
 }
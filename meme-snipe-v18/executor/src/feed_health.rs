@@ -0,0 +1,175 @@
+// executor/src/feed_health.rs
+//! Liveness of the data consumers, from their DataSourceHeartbeats. Each
+//! source in FEED_SOURCES is mapped to the event types it produces; a source
+//! is stale once its last heartbeat is older than FEED_STALE_AFTER_SECS, and
+//! an event type is stale once every source producing it is. A source is only
+//! judged once it has sent a heartbeat, so consumers a deployment doesn't run
+//! never hold entries back. Strategies subscribed to a stale
+//! type keep receiving events but their entries are held back until it
+//! recovers; exits still go through.
+
+use crate::config::CONFIG;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use prometheus::{register_gauge_vec, GaugeVec};
+use shared_models::{alert, DataSourceHeartbeat, EventType};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const CHECK_INTERVAL_SECS: u64 = 10;
+
+lazy_static! {
+    static ref FEED_STALE: GaugeVec = register_gauge_vec!(
+        "executor_feed_stale",
+        "1 while a data source's heartbeats are older than FEED_STALE_AFTER_SECS.",
+        &["source"]
+    )
+    .unwrap();
+    static ref FEED_LAG_SECONDS: GaugeVec = register_gauge_vec!(
+        "executor_feed_lag_seconds",
+        "Seconds since a data source's last heartbeat.",
+        &["source"]
+    )
+    .unwrap();
+}
+
+/// A source going stale or recovering.
+#[derive(Debug)]
+pub struct Transition {
+    pub source: String,
+    pub stale: bool,
+    pub lag_secs: i64,
+}
+
+struct State {
+    last_seen: HashMap<String, i64>, // Source -> heartbeat timestamp
+    stale_sources: HashSet<String>,
+}
+
+pub struct FeedHealth {
+    state: Mutex<State>,
+}
+
+impl FeedHealth {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                last_seen: HashMap::new(),
+                stale_sources: HashSet::new(),
+            }),
+        }
+    }
+
+    pub fn record(&self, heartbeat: &DataSourceHeartbeat) {
+        let mut state = self.state.lock();
+        let last_seen = state
+            .last_seen
+            .entry(heartbeat.source_name.clone())
+            .or_insert(heartbeat.timestamp);
+        *last_seen = (*last_seen).max(heartbeat.timestamp);
+    }
+
+    /// Re-evaluates every configured source heard from so far as of `now`.
+    pub fn refresh(&self, now: i64) -> Vec<Transition> {
+        let mut state = self.state.lock();
+        let mut transitions = Vec::new();
+        for source in CONFIG.feed_sources.keys() {
+            let Some(last_seen) = state.last_seen.get(source).copied() else {
+                continue;
+            };
+            let lag_secs = now - last_seen;
+            let stale = lag_secs > CONFIG.feed_stale_after_secs;
+            FEED_LAG_SECONDS
+                .with_label_values(&[source])
+                .set(lag_secs as f64);
+            FEED_STALE
+                .with_label_values(&[source])
+                .set(if stale { 1.0 } else { 0.0 });
+            let changed = if stale {
+                state.stale_sources.insert(source.clone())
+            } else {
+                state.stale_sources.remove(source)
+            };
+            if changed {
+                transitions.push(Transition {
+                    source: source.clone(),
+                    stale,
+                    lag_secs,
+                });
+            }
+        }
+        transitions
+    }
+
+    /// The subscribed event types with no live source, by name.
+    pub fn stale_types(&self, subscriptions: &HashSet<EventType>) -> Vec<String> {
+        let state = self.state.lock();
+        if state.stale_sources.is_empty() {
+            return Vec::new();
+        }
+        let mut stale: Vec<String> = subscriptions
+            .iter()
+            .map(|event_type| format!("{:?}", event_type))
+            .filter(|name| {
+                let mut producers = CONFIG
+                    .feed_sources
+                    .iter()
+                    .filter(|(_, types)| types.contains(name))
+                    .peekable();
+                // Types no configured source produces can't be judged
+                producers.peek().is_some()
+                    && producers.all(|(source, _)| state.stale_sources.contains(source))
+            })
+            .collect();
+        stale.sort();
+        stale
+    }
+}
+
+/// Refreshes feed health every few seconds and alerts when a source goes
+/// stale or recovers.
+pub async fn run_monitor(
+    feed_health: Arc<FeedHealth>,
+    redis_conn_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+) {
+    info!(
+        "🩺 Starting feed health monitor for [{}] (stale after {}s)...",
+        CONFIG
+            .feed_sources
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", "),
+        CONFIG.feed_stale_after_secs
+    );
+    let mut interval = tokio::time::interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let transitions = feed_health.refresh(chrono::Utc::now().timestamp());
+        if transitions.is_empty() {
+            continue;
+        }
+        let mut conn = redis_conn_manager.lock().await.clone();
+        for transition in transitions {
+            let types = CONFIG
+                .feed_sources
+                .get(&transition.source)
+                .map(|types| types.join(", "))
+                .unwrap_or_default();
+            if transition.stale {
+                warn!(source = %transition.source, lag_secs = transition.lag_secs, "Data source is stale.");
+                alert!(conn, "⚠️ Data source {} has been silent for {}s; entries paused for strategies relying only on it for [{}]", transition.source, transition.lag_secs, types);
+            } else {
+                info!(source = %transition.source, "Data source recovered.");
+                alert!(
+                    conn,
+                    "✅ Data source {} recovered; [{}] strategies resume",
+                    transition.source,
+                    types
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+// executor/src/fills.rs
+//! Confirms live swaps and measures what actually filled from the landed
+//! transaction's balance changes, rather than trusting the quote.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Result};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiTransactionEncoding, UiTransactionTokenBalance,
+};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(120); // Blockhashes expire after ~60-90s
+const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280; // Rent-exempt minimum for a 165-byte account
+
+/// Balance changes of the fee payer from one confirmed swap.
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub token_amount: u64,        // Raw units of the output mint received
+    pub token_decimals: u8,
+    pub sol_spent_lamports: u64,  // SOL out of the wallet, excluding the network fee and tip
+    pub failed: bool,             // Landed but reverted; nothing was filled
+}
+
+/// Where a transaction stands whose submission errored after it may have been sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Settlement {
+    Landed,  // Seen on chain (successful or reverted)
+    Expired, // Its blockhash expired without it landing; it never will
+    Unknown, // The RPC couldn't tell in time
+}
+
+impl Fill {
+    pub fn tokens(&self) -> f64 {
+        self.token_amount as f64 / 10f64.powi(self.token_decimals as i32)
+    }
+}
+
+pub struct FillTracker {
+    rpc: RpcClient,
+}
+
+impl FillTracker {
+    pub fn new() -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(
+                CONFIG.solana_rpc_url.clone(),
+                CommitmentConfig::confirmed(),
+            ),
+        }
+    }
+
+    /// Waits until `signature` is seen on chain or the block height passes
+    /// `last_valid_block_height`, after which it can no longer land. Gives up
+    /// with `Unknown` after SETTLE_TIMEOUT of failing RPC calls.
+    pub async fn settle(&self, signature: &Signature, last_valid_block_height: u64) -> Settlement {
+        let deadline = Instant::now() + SETTLE_TIMEOUT;
+        loop {
+            // Height first: no status after the blockhash expired means it never landed
+            let height = self.rpc.get_block_height().await;
+            let status = self
+                .rpc
+                .get_signature_status_with_commitment(signature, CommitmentConfig::processed())
+                .await;
+            match (height, status) {
+                (_, Ok(Some(_))) => return Settlement::Landed,
+                (Ok(height), Ok(None)) if height > last_valid_block_height => {
+                    return Settlement::Expired
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    debug!(signature = %signature, error = %e, "Could not check transaction status.")
+                }
+                _ => {}
+            }
+            if Instant::now() >= deadline {
+                warn!(signature = %signature, "Timed out waiting for the transaction to land or expire.");
+                return Settlement::Unknown;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Waits up to FILL_CONFIRM_TIMEOUT_SECS for `signature` to confirm and
+    /// returns `owner`'s balance changes in `mint`. `tip_lamports` is a Jito tip
+    /// the transaction transferred, which is a fee rather than part of the fill.
+    /// Ok(None) means the transaction never showed up, so the fill is unknown.
+    pub async fn confirm(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        mint: &str,
+        tip_lamports: u64,
+    ) -> Result<Option<Fill>> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let deadline = Instant::now() + Duration::from_secs(CONFIG.fill_confirm_timeout_secs);
+        let tx = loop {
+            match self.rpc.get_transaction_with_config(signature, config).await {
+                Ok(tx) => break tx,
+                Err(e) if Instant::now() < deadline => {
+                    debug!(signature = %signature, error = %e, "Transaction not confirmed yet.");
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+                Err(e) => {
+                    warn!(signature = %signature, error = %e, "Timed out waiting for confirmation.");
+                    return Ok(None);
+                }
+            }
+        };
+
+        let meta = tx
+            .transaction
+            .meta
+            .ok_or_else(|| anyhow!("Confirmed transaction {} has no metadata", signature))?;
+        let owner = owner.to_string();
+        let find = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| {
+            match balances {
+                OptionSerializer::Some(balances) => balances
+                    .iter()
+                    .find(|b| {
+                        b.mint == mint
+                            && matches!(&b.owner, OptionSerializer::Some(o) if *o == owner)
+                    })
+                    .map(|b| {
+                        (
+                            b.ui_token_amount.amount.parse::<u64>().unwrap_or(0),
+                            b.ui_token_amount.decimals,
+                        )
+                    }),
+                _ => None,
+            }
+        };
+        let pre = find(&meta.pre_token_balances);
+        let (post, decimals) = find(&meta.post_token_balances).unwrap_or((0, 0));
+        // A token account created by this swap cost rent, which isn't part of the fill
+        let rent = if pre.is_none() && post > 0 {
+            TOKEN_ACCOUNT_RENT_LAMPORTS
+        } else {
+            0
+        };
+        let pre = pre.map_or(0, |(amount, _)| amount);
+
+        // The fee payer is always account 0
+        let sol_spent_lamports = meta
+            .pre_balances
+            .first()
+            .zip(meta.post_balances.first())
+            .map(|(pre, post)| {
+                pre.saturating_sub(*post)
+                    .saturating_sub(meta.fee)
+                    .saturating_sub(tip_lamports)
+                    .saturating_sub(rent)
+            })
+            .unwrap_or(0);
+
+        Ok(Some(Fill {
+            token_amount: post.saturating_sub(pre),
+            token_decimals: decimals,
+            sol_spent_lamports,
+            failed: meta.err.is_some(),
+        }))
+    }
+}
@@ -4,7 +4,6 @@ use anyhow::{anyhow, Context, Result};
 // use jito_searcher_client::{JitoClient as BaseJitoClient, TxBundle};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
-    hash::Hash,
     signature::{read_keypair_file, Signature, Signer},
     transaction::{Transaction, VersionedTransaction},
 };
@@ -54,13 +53,6 @@ impl JitoClient {
         })
     }
 
-    pub async fn get_recent_blockhash(&self) -> Result<Hash> {
-        self.rpc_client
-            .get_latest_blockhash()
-            .await
-            .context("Failed to get recent blockhash from RPC")
-    }
-
     // P-5: Attach Jito tip to a transaction
     pub async fn attach_tip(&self, tx: &mut VersionedTransaction, tip_lamports: u64) -> Result<()> {
         let tip_account = "96gYZGLnJYVFmbjzopPSU6QiEV5fGq58M8N1MUXronJA".parse()?; // Jito's main tip account
@@ -1,54 +1,93 @@
 // executor/src/jupiter.rs
+// Jupiter v6 client: GET /quote, POST /swap. Orders are sized in USD and
+// converted to lamports with the live SOL/USD price from the event bus.
 use crate::config::CONFIG;
 use anyhow::{anyhow, Context, Result};
+use dashmap::DashMap;
 use reqwest::Client;
 use serde::Deserialize;
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
-use std::time::Duration;
-use tracing::info;
+use std::{str::FromStr, time::Duration};
+use tracing::{debug, info};
+
+pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JupiterQuote {
+    pub input_mint: String,
+    pub in_amount: String,
+    pub output_mint: String,
     pub out_amount: String,
-    #[serde(rename = "marketInfos")]
-    pub market_infos: Vec<MarketInfo>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MarketInfo {
-    pub lp_fee: LpFee,
-    pub liquidity: f64,
+    pub other_amount_threshold: String,
+    pub slippage_bps: u16,
+    pub price_impact_pct: String,
+    pub route_plan: Vec<RoutePlanStep>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct LpFee {
-    pub amount: String,
-    pub mint: String,
-    pub pct: f64,
+pub struct RoutePlanStep {
+    pub swap_info: SwapInfo,
+    pub percent: u8,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct JupiterQuoteResponse {
-    pub data: Vec<JupiterQuote>,
+pub struct SwapInfo {
+    pub amm_key: String,
+    pub label: Option<String>,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: String,
+    pub out_amount: String,
+    pub fee_amount: String,
+    pub fee_mint: String,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapResponse {
     pub swap_transaction: String,
+    pub last_valid_block_height: u64,
+}
+
+/// One leg of the route Jupiter picked, for logging and venue comparison.
+#[derive(Debug, Clone)]
+pub struct RouteHop {
+    pub label: String,
+    pub amm_key: String,
+    pub percent: u8,
 }
 
 pub struct QuoteResult {
-    pub out_amount: u64,
-    pub price_per_token: f64, // Price of the token per USD
+    pub in_amount_lamports: u64,
+    pub out_amount: u64,          // Raw output token units
+    pub min_out_amount: u64,      // out_amount after slippage
+    pub price_per_token: f64,     // USD per whole output token
+    pub price_impact_pct: f64,    // Jupiter's priceImpactPct for this size
+    pub route: Vec<RouteHop>,
+    quote_response: Value,        // Echoed back verbatim to /swap
+}
+
+impl QuoteResult {
+    /// Re-derives the minimum output for a new slippage tolerance. The echoed
+    /// quote is patched too, since /swap reads the threshold from it.
+    pub fn set_slippage_bps(&mut self, slippage_bps: u16) {
+        self.min_out_amount =
+            (self.out_amount as u128 * (10_000 - slippage_bps as u128) / 10_000) as u64;
+        self.quote_response["slippageBps"] = slippage_bps.into();
+        self.quote_response["otherAmountThreshold"] = self.min_out_amount.to_string().into();
+    }
 }
 
 pub struct JupiterClient {
     client: Client,
+    rpc: RpcClient,
+    decimals: DashMap<String, u8>, // Mint -> decimals, mints never change them
 }
 
 impl JupiterClient {
@@ -58,83 +97,123 @@ impl JupiterClient {
                 .timeout(Duration::from_secs(15))
                 .build()
                 .expect("Failed to build HTTP client"),
+            rpc: RpcClient::new(CONFIG.solana_rpc_url.clone()),
+            decimals: DashMap::new(),
         }
     }
 
-    // P-1: Corrected Jupiter quote URL pattern
-    // P-2: Now takes amount_sol_to_swap directly (calculated from USD in executor)
+    /// Quotes a SOL -> `output_mint` swap worth `amount_usd`.
     pub async fn get_quote(
         &self,
-        amount_sol_to_swap: f64,
+        amount_usd: f64,
+        sol_usd_price: f64,
         output_mint: &str,
     ) -> Result<QuoteResult> {
-        let amount_lamports = (amount_sol_to_swap * 1_000_000_000.0) as u64; // Convert SOL to Lamports
+        if sol_usd_price <= 0.0 {
+            return Err(anyhow!("SOL/USD price not available or zero. Cannot quote."));
+        }
+        let amount_lamports = (amount_usd / sol_usd_price * LAMPORTS_PER_SOL) as u64;
         let url = format!(
-            "{}/quote?inputMint=So11111111111111111111111111111111111111112&outputMint={}&amount={}&slippageBps={}",
-            CONFIG.jupiter_api_url, output_mint, amount_lamports, CONFIG.slippage_bps
+            "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
+            CONFIG.jupiter_api_url,
+            SOL_MINT,
+            output_mint,
+            amount_lamports,
+            CONFIG.tunables.get().slippage_bps
         );
 
-        let response: JupiterQuoteResponse = self.client.get(&url).send().await?.json().await?;
-        let best_route = response
-            .data
-            .first()
-            .ok_or_else(|| anyhow!("No route found by Jupiter for {}", output_mint))?;
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Jupiter quote for {} failed: {}",
+                output_mint,
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        let quote_response: Value = response.json().await?;
+        let quote: JupiterQuote = serde_json::from_value(quote_response.clone())
+            .with_context(|| format!("Unexpected Jupiter quote shape for {}", output_mint))?;
 
-        let out_amount: u64 = best_route.out_amount.parse()?;
+        let out_amount: u64 = quote.out_amount.parse()?;
+        if out_amount == 0 {
+            return Err(anyhow!("No route found by Jupiter for {}", output_mint));
+        }
+        let decimals = self.token_decimals(output_mint).await?;
+        let tokens_out = out_amount as f64 / 10f64.powi(decimals as i32);
+        let price_per_token = amount_usd / tokens_out;
+        let price_impact_pct: f64 = quote.price_impact_pct.parse().unwrap_or(0.0);
+        let route: Vec<RouteHop> = quote
+            .route_plan
+            .iter()
+            .map(|step| RouteHop {
+                label: step.swap_info.label.clone().unwrap_or_default(),
+                amm_key: step.swap_info.amm_key.clone(),
+                percent: step.percent,
+            })
+            .collect();
 
-        // Calculate price_per_token based on the swap of the SOL amount provided
-        let price_per_token = (amount_sol_to_swap / (out_amount as f64 / 1_000_000_000.0)).recip(); // (SOL_amount / tokens_received) -> SOL_per_Token; then invert for Token_per_SOL, convert to USD later.
         info!(
-            "Jupiter quote for {} SOL -> {}. Price per token: {:.8} USD",
-            amount_sol_to_swap, output_mint, price_per_token
+            output_mint,
+            amount_usd,
+            price_per_token,
+            price_impact_pct,
+            route = %route.iter().map(|h| h.label.as_str()).collect::<Vec<_>>().join(" -> "),
+            "Jupiter quote."
         );
 
         Ok(QuoteResult {
+            in_amount_lamports: quote.in_amount.parse()?,
             out_amount,
+            min_out_amount: quote.other_amount_threshold.parse()?,
             price_per_token,
+            price_impact_pct,
+            route,
+            quote_response,
         })
     }
 
-    // P-1: Corrected Jupiter swap URL pattern
-    // P-2: Now takes amount_usd_to_swap directly (executor handles SOL conversion)
+    /// Builds the unsigned swap transaction (base64) for a previously fetched quote.
     pub async fn get_swap_transaction(
         &self,
         user_pubkey: &Pubkey,
-        output_mint: &str,
-        amount_usd_to_swap: f64,
+        quote: &QuoteResult,
     ) -> Result<String> {
-        // Executor passes amount in USD, this function assumes current SOL price for Jupiter call
-        // In a real system, you'd get the live SOL/USD price from the event bus
-        let amount_sol_approx = amount_usd_to_swap / 150.0; // Placeholder SOL price for Jupiter's internal calculation.
-        let amount_lamports = (amount_sol_approx * 1_000_000_000.0) as u64;
-
-        let quote_url = format!(
-            "{}/quote?inputMint=So11111111111111111111111111111111111111112&outputMint={}&amount={}&slippageBps={}",
-            CONFIG.jupiter_api_url, output_mint, amount_lamports, CONFIG.slippage_bps
-        );
-        let quote_response: serde_json::Value =
-            self.client.get(&quote_url).send().await?.json().await?;
-
         let swap_payload = serde_json::json!({
-            "quoteResponse": quote_response,
+            "quoteResponse": quote.quote_response,
             "userPublicKey": user_pubkey.to_string(),
             "wrapAndUnwrapSol": true,
+            "dynamicComputeUnitLimit": true,
         });
 
         let swap_url = format!("{}/swap", CONFIG.jupiter_api_url);
-        let response: SwapResponse = self
-            .client
-            .post(swap_url)
-            .json(&swap_payload)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let response = self.client.post(swap_url).json(&swap_payload).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Jupiter swap build failed: {}",
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        let swap: SwapResponse = response.json().await?;
         info!(
-            "Generated Jupiter swap transaction for {} USD.",
-            amount_usd_to_swap
+            in_lamports = quote.in_amount_lamports,
+            last_valid_block_height = swap.last_valid_block_height,
+            "Generated Jupiter swap transaction."
         );
-        Ok(response.swap_transaction)
+        Ok(swap.swap_transaction)
+    }
+
+    async fn token_decimals(&self, mint: &str) -> Result<u8> {
+        if let Some(decimals) = self.decimals.get(mint) {
+            return Ok(*decimals);
+        }
+        let supply = self
+            .rpc
+            .get_token_supply(&Pubkey::from_str(mint)?)
+            .await
+            .with_context(|| format!("Failed to fetch decimals for {}", mint))?;
+        debug!(mint, decimals = supply.decimals, "Cached token decimals.");
+        self.decimals.insert(mint.to_string(), supply.decimals);
+        Ok(supply.decimals)
     }
 }
 
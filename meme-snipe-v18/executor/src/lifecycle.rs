@@ -0,0 +1,65 @@
+// executor/src/lifecycle.rs
+//! Drives one trade row through the `TradeState` machine. Every step is checked
+//! against the stored state and persisted with a timestamp in
+//! `trade_transitions`. An attempt dropped before anything reached the chain is
+//! marked FAILED; one dropped while SUBMITTED/CONFIRMED is left as-is, since
+//! capital may already be committed and the wallet has to be reconciled.
+
+use crate::database::Database;
+use anyhow::Result;
+use shared_models::TradeState;
+use std::sync::Arc;
+use tracing::{error, warn};
+
+pub struct TradeLifecycle {
+    db: Arc<Database>,
+    trade_id: i64,
+    state: TradeState,
+}
+
+impl TradeLifecycle {
+    /// Wraps a trade freshly inserted by `Database::log_trade_attempt`.
+    pub fn new(db: Arc<Database>, trade_id: i64) -> Self {
+        Self {
+            db,
+            trade_id,
+            state: TradeState::Created,
+        }
+    }
+
+    pub fn advance(&mut self, to: TradeState, note: Option<&str>) -> Result<()> {
+        self.db.transition(self.trade_id, to, note)?;
+        self.state = to;
+        Ok(())
+    }
+
+    /// Stores the transaction signature and moves to SUBMITTED.
+    pub fn submitted(&mut self, signature: &str) -> Result<()> {
+        self.db.set_signature(self.trade_id, signature)?;
+        self.advance(TradeState::Submitted, Some(signature))
+    }
+
+    pub fn fail(&mut self, reason: &str) -> Result<()> {
+        self.advance(TradeState::Failed, Some(reason))
+    }
+}
+
+impl Drop for TradeLifecycle {
+    fn drop(&mut self) {
+        match self.state {
+            TradeState::Created | TradeState::RiskApproved => {
+                if let Err(e) = self.db.transition(
+                    self.trade_id,
+                    TradeState::Failed,
+                    Some("aborted before submission"),
+                ) {
+                    error!(trade_id = self.trade_id, error = %e, "Failed to mark aborted trade as FAILED.");
+                }
+            }
+            state if state.is_in_flight() => {
+                warn!(trade_id = self.trade_id, state = %state, "Trade left in flight, reconcile against the wallet.");
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,22 +1,42 @@
 // executor/src/main.rs
+mod archive;
+mod audit;
 mod config;
 mod database;
+mod event_queue;
+mod execution_queue;
 mod executor;
+mod feed_health;
+mod fills;
 mod jito_client; // Corrected module name
 mod jupiter;
+mod lifecycle;
+mod orca;
 mod portfolio_monitor;
+mod position_book;
+mod pumpfun;
+mod raydium;
+mod replay;
+mod routing;
+mod shadow;
 mod signer_client;
+mod slippage;
+mod sol_price;
+mod spl;
 mod strategies;
+mod submission;
+mod token_safety;
 
 use crate::config::CONFIG;
 use anyhow::Result;
-use axum::{routing::get, Router};
+use axum::{http::StatusCode, routing::get, Router};
 use database::Database;
 use executor::MasterExecutor;
 use prometheus::{Encoder, TextEncoder};
+use shared_config::Readiness;
+use shared_models::spawn_heartbeat;
 use std::sync::Arc;
-use tracing::{info, level_filters::LevelFilter};
-use tracing_subscriber::EnvFilter;
+use tracing::{info, warn};
 use axum::Json;
 use serde_json::{json, Value};
 
@@ -32,6 +52,17 @@ async fn health_handler() -> &'static str {
     "OK"
 }
 
+async fn ready_handler(
+    axum::extract::State(readiness): axum::extract::State<Arc<Readiness>>,
+) -> (StatusCode, Json<Value>) {
+    let status = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness.report()))
+}
+
 async fn state_handler(axum::extract::State(executor): axum::extract::State<Arc<tokio::sync::Mutex<MasterExecutor>>>) -> Json<Value> {
     let executor = executor.lock().await;
     Json(executor.get_state_snapshot())
@@ -39,14 +70,33 @@ async fn state_handler(axum::extract::State(executor): axum::extract::State<Arc<
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::INFO.into())
-        .from_env_lossy();
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let log_level = shared_config::log_level::init();
 
     info!(version = %env!("CARGO_PKG_VERSION"), "🚀 Starting MemeSnipe Executor Orchestrator v18 - The Alpha Engine...");
+    lazy_static::initialize(&CONFIG); // Validates and logs the effective configuration
+    if CONFIG.dry_run {
+        warn!("🧪 DRY_RUN is on: Live trades are built and logged but never signed.");
+    }
+    if !CONFIG.profile.allows_live() {
+        info!(profile = %CONFIG.profile, "📝 Paper-only profile: Live allocations are traded as Paper.");
+    }
+
+    // Replay never signs or touches the chain, so it only needs Redis
+    let mut readiness = Readiness::new("executor").redis(&CONFIG.redis_url);
+    if !CONFIG.replay_mode {
+        readiness = readiness
+            .http("signer", &format!("{}/pubkey", CONFIG.signer_url))
+            .solana_rpc(&CONFIG.solana_rpc_url);
+    }
+    let readiness = Arc::new(readiness);
+    readiness.preflight().await.map_err(anyhow::Error::msg)?;
+    tokio::spawn(shared_config::readiness::monitor(readiness.clone()));
 
     let db = Arc::new(Database::new(&CONFIG.database_path)?);
+    spawn_heartbeat!(redis::Client::open(CONFIG.redis_url.as_str())?, "executor");
+    // Slippage, stops and tips follow config file edits and config_updates
+    tokio::spawn(shared_config::watch(CONFIG.tunables.clone(), CONFIG.redis_url.clone()));
+    tokio::spawn(shared_config::flags::sync(CONFIG.flags.clone(), CONFIG.redis_url.clone()));
     let master_executor = MasterExecutor::new(db.clone()).await?;
     let executor_state = Arc::new(tokio::sync::Mutex::new(master_executor));
 
@@ -54,8 +104,23 @@ async fn main() -> Result<()> {
     let metrics_app = Router::new()
         .route("/metrics", get(metrics_handler))
         .route("/health", get(health_handler))
+        .route("/live", get(health_handler))
         .route("/api/v1/state", get(state_handler))
-        .with_state(executor_state.clone());
+        .with_state(executor_state.clone())
+        .merge(
+            Router::new()
+                .route("/ready", get(ready_handler))
+                .with_state(readiness),
+        )
+        .merge(shared_config::log_level::router(log_level))
+        .merge(
+            Router::new()
+                .route("/api/v1/trades/:id/audit", get(audit::trade_audit_handler))
+                .with_state(audit::AuditState {
+                    db: db.clone(),
+                    redis_client: redis::Client::open(CONFIG.redis_url.as_str())?,
+                }),
+        );
 
     let metrics_listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
     info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
@@ -67,12 +132,27 @@ async fn main() -> Result<()> {
     });
 
     // Start the portfolio monitor task
-    tokio::spawn(portfolio_monitor::run_monitor(
-        db.clone(),
-        executor_state.lock().await.paused_flag(),
-    ));
+    let drawdown_pct = executor_state.lock().await.drawdown_pct();
+    tokio::spawn(portfolio_monitor::run_monitor(db.clone(), drawdown_pct));
+
+    // Translate SIGTERM/Ctrl-C into a shutdown signal for the run loop
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        info!("🛑 Shutdown signal received.");
+        let _ = shutdown_tx.send(true);
+    });
 
     let mut executor = executor_state.lock().await;
-    executor.run().await?;
+    executor.run(shutdown_rx).await?;
     Ok(())
 }
+
+async fn wait_for_shutdown_signal() {
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .expect("Failed to install SIGTERM handler");
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
@@ -0,0 +1,276 @@
+// executor/src/orca.rs
+// Orca Whirlpool swaps. Pools are discovered through the Orca API; quotes come
+// from the pool's on-chain sqrt price and active liquidity, i.e. they assume
+// the swap stays inside the current tick range (true for our position sizes
+// on any pool worth trading, and min-out protects us when it isn't).
+use crate::{
+    config::CONFIG,
+    jupiter::SOL_MINT,
+    spl::{self, associated_token_address, create_ata_idempotent, pk, TOKEN_PROGRAM_ID},
+};
+use anyhow::{anyhow, Context, Result};
+use dashmap::DashMap;
+use reqwest::Client;
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use std::{str::FromStr, time::Duration};
+use tracing::{debug, info};
+
+pub const WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const TICK_ARRAY_SIZE: i32 = 88;
+const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+#[derive(Debug, Deserialize)]
+struct PoolList {
+    data: Vec<ApiPool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPool {
+    address: String,
+}
+
+/// Fields of the Whirlpool account the swap needs (after the 8-byte discriminator).
+#[derive(Debug, Clone)]
+pub struct Whirlpool {
+    pub address: Pubkey,
+    pub tick_spacing: u16,
+    pub fee_rate: u16, // Hundredths of a basis point
+    pub liquidity: u128,
+    pub sqrt_price_x64: u128,
+    pub tick_current_index: i32,
+    pub token_mint_a: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+impl Whirlpool {
+    fn parse(address: Pubkey, data: &[u8]) -> Result<Self> {
+        if data.len() < 245 {
+            return Err(anyhow!("Whirlpool account {} too short", address));
+        }
+        let u16_at = |o: usize| u16::from_le_bytes(data[o..o + 2].try_into().unwrap());
+        let u128_at = |o: usize| u128::from_le_bytes(data[o..o + 16].try_into().unwrap());
+        let key_at = |o: usize| Pubkey::new_from_array(data[o..o + 32].try_into().unwrap());
+        Ok(Self {
+            address,
+            tick_spacing: u16_at(41),
+            fee_rate: u16_at(45),
+            liquidity: u128_at(49),
+            sqrt_price_x64: u128_at(65),
+            tick_current_index: i32::from_le_bytes(data[81..85].try_into().unwrap()),
+            token_mint_a: key_at(101),
+            token_vault_a: key_at(133),
+            token_mint_b: key_at(181),
+            token_vault_b: key_at(213),
+        })
+    }
+
+    /// The three tick arrays a swap may walk, in swap direction.
+    fn tick_arrays(&self, a_to_b: bool) -> [Pubkey; 3] {
+        let span = self.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let start = self.tick_current_index.div_euclid(span) * span;
+        let step = if a_to_b { -span } else { span };
+        [0, 1, 2].map(|i| {
+            let start_index = (start + step * i).to_string();
+            Pubkey::find_program_address(
+                &[b"tick_array", self.address.as_ref(), start_index.as_bytes()],
+                &pk(WHIRLPOOL_PROGRAM_ID),
+            )
+            .0
+        })
+    }
+}
+
+pub struct OrcaQuote {
+    pub pool: Whirlpool,
+    pub a_to_b: bool, // true when SOL is token A
+    pub in_amount_lamports: u64,
+    pub out_amount: u64,
+    pub min_out_amount: u64,
+    pub price_per_token: f64, // USD per whole output token, net of the pool fee
+    pub price_impact_pct: f64,
+}
+
+pub struct OrcaClient {
+    client: Client,
+    rpc: RpcClient,
+    pools: DashMap<String, Pubkey>, // Token mint -> deepest SOL whirlpool
+}
+
+impl OrcaClient {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+            rpc: RpcClient::new(CONFIG.solana_rpc_url.clone()),
+            pools: DashMap::new(),
+        }
+    }
+
+    /// Quotes a SOL -> `output_mint` swap worth `amount_usd` on the deepest SOL whirlpool.
+    pub async fn get_quote(
+        &self,
+        amount_usd: f64,
+        sol_usd_price: f64,
+        output_mint: &str,
+    ) -> Result<OrcaQuote> {
+        if sol_usd_price <= 0.0 {
+            return Err(anyhow!("SOL/USD price not available or zero. Cannot quote."));
+        }
+        let address = self.pool_for_mint(output_mint).await?;
+        let pool = Whirlpool::parse(address, &self.rpc.get_account_data(&address).await?)?;
+        if pool.liquidity == 0 {
+            return Err(anyhow!("Whirlpool {} has no active liquidity", address));
+        }
+        let a_to_b = pool.token_mint_a.to_string() == SOL_MINT;
+        let token_mint = if a_to_b { pool.token_mint_b } else { pool.token_mint_a };
+        let decimals = self
+            .rpc
+            .get_token_supply(&token_mint)
+            .await
+            .with_context(|| format!("Failed to fetch decimals for {}", token_mint))?
+            .decimals;
+
+        let amount_in = (amount_usd / sol_usd_price * 1_000_000_000.0) as u64;
+        let in_after_fee = amount_in as f64 * (1.0 - pool.fee_rate as f64 / 1_000_000.0);
+        let l = pool.liquidity as f64;
+        let sqrt_p = pool.sqrt_price_x64 as f64 / Q64;
+        let (out, spot_out) = if a_to_b {
+            // Selling A (SOL) for B: sqrt price falls
+            let sqrt_p_new = l * sqrt_p / (l + in_after_fee * sqrt_p);
+            (l * (sqrt_p - sqrt_p_new), amount_in as f64 * sqrt_p * sqrt_p)
+        } else {
+            // Selling B (SOL) for A: sqrt price rises
+            let sqrt_p_new = sqrt_p + in_after_fee / l;
+            (l * (1.0 / sqrt_p - 1.0 / sqrt_p_new), amount_in as f64 / (sqrt_p * sqrt_p))
+        };
+        let out_amount = out as u64;
+        if out_amount == 0 {
+            return Err(anyhow!("Orca quote for {} rounds to zero", output_mint));
+        }
+        let slippage_bps = CONFIG.tunables.get().slippage_bps as u128;
+        let min_out_amount = (out_amount as u128 * (10_000 - slippage_bps) / 10_000) as u64;
+        let price_per_token = amount_usd / (out_amount as f64 / 10f64.powi(decimals as i32));
+        let price_impact_pct = 1.0 - out / spot_out;
+
+        info!(
+            output_mint,
+            pool = %pool.address,
+            amount_usd,
+            price_per_token,
+            price_impact_pct,
+            "Orca quote."
+        );
+        Ok(OrcaQuote {
+            pool,
+            a_to_b,
+            in_amount_lamports: amount_in,
+            out_amount,
+            min_out_amount,
+            price_per_token,
+            price_impact_pct,
+        })
+    }
+
+    /// Builds an unsigned (base64) transaction that wraps SOL, swaps through
+    /// the whirlpool, and unwraps any leftover WSOL.
+    pub async fn get_swap_transaction(
+        &self,
+        user_pubkey: &Pubkey,
+        quote: &OrcaQuote,
+    ) -> Result<String> {
+        let pool = &quote.pool;
+        let sol_mint = pk(SOL_MINT);
+        let token_mint = if quote.a_to_b { pool.token_mint_b } else { pool.token_mint_a };
+        let wsol_account = associated_token_address(user_pubkey, &sol_mint);
+        let token_account = associated_token_address(user_pubkey, &token_mint);
+        let (owner_a, owner_b) = if quote.a_to_b {
+            (wsol_account, token_account)
+        } else {
+            (token_account, wsol_account)
+        };
+        let [tick_0, tick_1, tick_2] = pool.tick_arrays(quote.a_to_b);
+        let oracle = Pubkey::find_program_address(
+            &[b"oracle", pool.address.as_ref()],
+            &pk(WHIRLPOOL_PROGRAM_ID),
+        )
+        .0;
+        let sqrt_price_limit = if quote.a_to_b {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        };
+
+        let swap = Instruction {
+            program_id: pk(WHIRLPOOL_PROGRAM_ID),
+            accounts: vec![
+                AccountMeta::new_readonly(pk(TOKEN_PROGRAM_ID), false),
+                AccountMeta::new_readonly(*user_pubkey, true),
+                AccountMeta::new(pool.address, false),
+                AccountMeta::new(owner_a, false),
+                AccountMeta::new(pool.token_vault_a, false),
+                AccountMeta::new(owner_b, false),
+                AccountMeta::new(pool.token_vault_b, false),
+                AccountMeta::new(tick_0, false),
+                AccountMeta::new(tick_1, false),
+                AccountMeta::new(tick_2, false),
+                AccountMeta::new_readonly(oracle, false),
+            ],
+            data: [
+                SWAP_DISCRIMINATOR.to_vec(),
+                quote.in_amount_lamports.to_le_bytes().to_vec(),
+                quote.min_out_amount.to_le_bytes().to_vec(),
+                sqrt_price_limit.to_le_bytes().to_vec(),
+                vec![1u8],                // amount_specified_is_input
+                vec![quote.a_to_b as u8], // a_to_b
+            ]
+            .concat(),
+        };
+
+        let instructions = vec![
+            create_ata_idempotent(user_pubkey, &sol_mint),
+            system_instruction::transfer(user_pubkey, &wsol_account, quote.in_amount_lamports),
+            spl::sync_native(&wsol_account),
+            create_ata_idempotent(user_pubkey, &token_mint),
+            swap,
+            spl::close_account(&wsol_account, user_pubkey),
+        ];
+
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        info!(pool = %pool.address, in_lamports = quote.in_amount_lamports, "Generated Orca swap transaction.");
+        spl::unsigned_transaction_b64(user_pubkey, &instructions, &blockhash)
+    }
+
+    async fn pool_for_mint(&self, mint: &str) -> Result<Pubkey> {
+        if let Some(pool) = self.pools.get(mint) {
+            return Ok(*pool);
+        }
+        let url = format!(
+            "{}/pools?tokensBothOf={},{}&sortBy=tvl&sortDirection=desc",
+            CONFIG.orca_api_url, SOL_MINT, mint
+        );
+        let list: PoolList = self.client.get(&url).send().await?.json().await?;
+        let address = list
+            .data
+            .first()
+            .map(|p| Pubkey::from_str(&p.address))
+            .transpose()?
+            .ok_or_else(|| anyhow!("No Orca whirlpool found for {}", mint))?;
+        debug!(mint, pool = %address, "Cached Orca whirlpool.");
+        self.pools.insert(mint.to_string(), address);
+        Ok(address)
+    }
+}
@@ -3,10 +3,13 @@ use crate::config::CONFIG;
 use crate::database::Database;
 use anyhow::Result;
 use redis::AsyncCommands;
+use shared_models::{set_trading_state, TradingState, PORTFOLIO_METRICS_KEY};
 use std::{sync::Arc, time::Duration};
 use tracing::{error, info, warn}; // P-7: For Redis Streams
 
-pub async fn run_monitor(db: Arc<Database>, portfolio_paused_flag: Arc<tokio::sync::Mutex<bool>>) {
+const STOP_LOSS_REASON: &str = "PORTFOLIO_STOP_LOSS";
+
+pub async fn run_monitor(db: Arc<Database>, portfolio_drawdown_pct: Arc<tokio::sync::Mutex<f64>>) {
     info!("📈 Starting Portfolio Monitor (P-6)...");
     let redis_url = CONFIG.redis_url.clone();
     let client = match redis::Client::open(redis_url) {
@@ -45,37 +48,61 @@ pub async fn run_monitor(db: Arc<Database>, portfolio_paused_flag: Arc<tokio::sy
                 } else {
                     0.0 // No drawdown if no profit yet
                 };
+                *portfolio_drawdown_pct.lock().await = drawdown_from_peak; // Shown to strategies via PortfolioContext
 
                 info!(
                     "Portfolio PnL: {:.2} USD (Peak: {:.2} USD, Drawdown: {:.2}%)",
                     current_pnl, highest_water_mark_pnl, drawdown_from_peak
                 );
 
-                if drawdown_from_peak > CONFIG.portfolio_stop_loss_percent {
-                    if !*portfolio_paused_flag.lock().await {
-                        // P-6: Check internal flag
-                        error!(
+                // The allocator scales capital with equity: realized PnL plus what's still open
+                let live_open_positions_usd: f64 = db
+                    .get_open_trades()
+                    .map(|trades| {
+                        trades
+                            .iter()
+                            .filter(|t| t.mode == "Live")
+                            .map(|t| t.amount_usd)
+                            .sum()
+                    })
+                    .unwrap_or(0.0);
+                if let Err(e) = conn
+                    .hset_multiple::<_, _, _, ()>(
+                        PORTFOLIO_METRICS_KEY,
+                        &[
+                            ("live_open_positions_usd", live_open_positions_usd),
+                            ("realized_pnl_usd", current_pnl),
+                            ("executor_updated_at", chrono::Utc::now().timestamp() as f64),
+                        ],
+                    )
+                    .await
+                {
+                    warn!("Portfolio Monitor: Failed to record portfolio metrics: {}", e);
+                }
+
+                let stop_loss_percent = CONFIG.tunables.get().portfolio_stop_loss_percent;
+                // P-6: Pause through the shared trading state; a pause is only lifted here if
+                // this monitor set it and the drawdown has recovered well below the threshold
+                let target = if drawdown_from_peak > stop_loss_percent {
+                    Some(TradingState::paused(STOP_LOSS_REASON))
+                } else if drawdown_from_peak < stop_loss_percent * 0.8 {
+                    Some(TradingState::Active)
+                } else {
+                    None
+                };
+                if let Some(target) = target {
+                    match set_trading_state!(conn, "executor", target.clone()) {
+                        Ok(true) if target.is_active() => info!(
+                            "✅ Portfolio recovered. Drawdown {:.2}% < Threshold {:.2}%. Resuming trading.",
+                            drawdown_from_peak,
+                            stop_loss_percent * 0.8
+                        ),
+                        Ok(true) => error!(
                             "🚨 PORTFOLIO STOP LOSS TRIGGERED! Drawdown {:.2}% > Threshold {:.2}%. Pausing trading.",
-                            drawdown_from_peak, CONFIG.portfolio_stop_loss_percent
-                        );
-                        // P-6: Publish to kill switch channel (Pub/Sub)
-                        if let Err(e) = conn.publish("kill_switch_channel", "PAUSE").await {
-                            error!("Failed to publish PAUSE to kill_switch_channel: {}", e);
-                        }
-                        *portfolio_paused_flag.lock().await = true; // P-6: Update internal flag
-                    }
-                } else if *portfolio_paused_flag.lock().await {
-                    // P-6: Check internal flag
-                    // If currently paused but drawdown is recovered, resume
-                    if drawdown_from_peak < CONFIG.portfolio_stop_loss_percent * 0.8 {
-                        // Resume if recovered significantly
-                        info!("✅ Portfolio recovered. Drawdown {:.2}% < Threshold {:.2}%. Resuming trading.",
-                            drawdown_from_peak, CONFIG.portfolio_stop_loss_percent * 0.8);
-                        // P-6: Publish to kill switch channel (Pub/Sub)
-                        if let Err(e) = conn.publish("kill_switch_channel", "RESUME").await {
-                            error!("Failed to publish RESUME to kill_switch_channel: {}", e);
-                        }
-                        *portfolio_paused_flag.lock().await = false; // P-6: Update internal flag
+                            drawdown_from_peak, stop_loss_percent
+                        ),
+                        Ok(false) => {}
+                        Err(e) => error!("Failed to set trading state to {}: {}", target, e),
                     }
                 }
             }
@@ -0,0 +1,162 @@
+// executor/src/position_book.rs
+//! Open-position accounting used to cap exposure before an order reaches
+//! `execute_trade`. OPEN trades in the DB are the source of truth (the
+//! position_manager closes them); orders still being worked are tracked as
+//! in-flight reservations so concurrent signals can't slip past a cap.
+
+use crate::{config::CONFIG, database::TradeRecord, strategies::OpenPosition};
+use parking_lot::Mutex;
+use shared_models::Side;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+type PositionKey = (String, String); // (strategy_id, token_address)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionCap {
+    PerStrategyToken,
+    PerStrategy,
+    PerToken,
+}
+
+impl fmt::Display for PositionCap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionCap::PerStrategyToken => write!(f, "per_strategy_token"),
+            PositionCap::PerStrategy => write!(f, "per_strategy"),
+            PositionCap::PerToken => write!(f, "per_token"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct BookState {
+    open: HashMap<PositionKey, u32>,
+    in_flight: HashMap<PositionKey, u32>,
+    by_strategy: HashMap<String, Vec<OpenPosition>>, // strategy_id -> OPEN trades, for PortfolioContext
+}
+
+impl BookState {
+    fn count(&self, key: &PositionKey) -> u32 {
+        self.open.get(key).copied().unwrap_or(0) + self.in_flight.get(key).copied().unwrap_or(0)
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &PositionKey> {
+        self.open.keys().chain(self.in_flight.keys())
+    }
+}
+
+#[derive(Default)]
+pub struct PositionBook {
+    state: Mutex<BookState>,
+}
+
+impl PositionBook {
+    /// Replaces the open positions with the DB's current OPEN trades.
+    pub fn refresh(&self, open_trades: &[TradeRecord]) {
+        let mut open: HashMap<PositionKey, u32> = HashMap::new();
+        let mut by_strategy: HashMap<String, Vec<OpenPosition>> = HashMap::new();
+        for trade in open_trades {
+            *open
+                .entry((trade.strategy_id.clone(), trade.token_address.clone()))
+                .or_default() += 1;
+            by_strategy
+                .entry(trade.strategy_id.clone())
+                .or_default()
+                .push(OpenPosition {
+                    trade_id: trade.id,
+                    token_address: trade.token_address.clone(),
+                    side: match trade.side.as_str() {
+                        "Short" => Side::Short,
+                        _ => Side::Long,
+                    },
+                    size_usd: trade.amount_usd,
+                });
+        }
+        let mut state = self.state.lock();
+        state.open = open;
+        state.by_strategy = by_strategy;
+    }
+
+    /// The strategy's OPEN trades as of the last refresh.
+    pub fn open_positions(&self, strategy_id: &str) -> Vec<OpenPosition> {
+        self.state
+            .lock()
+            .by_strategy
+            .get(strategy_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Reserves room for one new position, or returns the cap it would breach.
+    pub fn try_reserve(
+        self: &Arc<Self>,
+        strategy_id: &str,
+        token_address: &str,
+    ) -> Result<PositionReservation, PositionCap> {
+        let key = (strategy_id.to_string(), token_address.to_string());
+        let mut state = self.state.lock();
+
+        if state.count(&key) >= CONFIG.max_positions_per_strategy_token {
+            return Err(PositionCap::PerStrategyToken);
+        }
+        let mut strategy_tokens: Vec<&String> = state
+            .keys()
+            .filter(|k| k.0 == strategy_id && state.count(k) > 0)
+            .map(|k| &k.1)
+            .collect();
+        strategy_tokens.sort();
+        strategy_tokens.dedup();
+        if !strategy_tokens.contains(&&key.1)
+            && strategy_tokens.len() as u32 >= CONFIG.max_open_tokens_per_strategy
+        {
+            return Err(PositionCap::PerStrategy);
+        }
+        let token_total: u32 = state
+            .open
+            .iter()
+            .chain(state.in_flight.iter())
+            .filter(|(k, _)| k.1 == token_address)
+            .map(|(_, n)| n)
+            .sum();
+        if token_total >= CONFIG.max_positions_per_token {
+            return Err(PositionCap::PerToken);
+        }
+
+        *state.in_flight.entry(key.clone()).or_default() += 1;
+        Ok(PositionReservation {
+            book: self.clone(),
+            key,
+        })
+    }
+}
+
+/// Holds an in-flight slot until the order finishes working. Fills are
+/// recorded as open positions; the slot itself is released on drop.
+pub struct PositionReservation {
+    book: Arc<PositionBook>,
+    key: PositionKey,
+}
+
+impl PositionReservation {
+    pub fn record_fill(&self) {
+        *self
+            .book
+            .state
+            .lock()
+            .open
+            .entry(self.key.clone())
+            .or_default() += 1;
+    }
+}
+
+impl Drop for PositionReservation {
+    fn drop(&mut self) {
+        let mut state = self.book.state.lock();
+        if let Some(n) = state.in_flight.get_mut(&self.key) {
+            *n = n.saturating_sub(1);
+            if *n == 0 {
+                state.in_flight.remove(&self.key);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// executor/src/pumpfun.rs
+// pump.fun bonding-curve trading for tokens that haven't migrated to Raydium yet.
+// Quotes are computed from the curve's virtual reserves (constant product, 1% fee).
+use crate::{
+    config::CONFIG,
+    spl::{
+        self, associated_token_address, create_ata_idempotent, pk, ASSOCIATED_TOKEN_PROGRAM_ID,
+        TOKEN_PROGRAM_ID,
+    },
+};
+use anyhow::{anyhow, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use std::str::FromStr;
+use tracing::info;
+
+pub const PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+const GLOBAL: &str = "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf";
+const FEE_RECIPIENT: &str = "CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM";
+const EVENT_AUTHORITY: &str = "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1";
+const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+const FEE_BPS: u128 = 100;
+const TOKEN_DECIMALS: i32 = 6; // Every pump.fun mint uses 6 decimals
+
+/// On-chain bonding curve state (after the 8-byte Anchor discriminator).
+#[derive(Debug, Clone)]
+pub struct BondingCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool, // true once migrated to Raydium
+}
+
+impl BondingCurve {
+    fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 + 8 * 5 + 1 {
+            return Err(anyhow!("Bonding curve account too short"));
+        }
+        let u64_at = |i: usize| {
+            let start = 8 + i * 8;
+            u64::from_le_bytes(data[start..start + 8].try_into().unwrap())
+        };
+        Ok(Self {
+            virtual_token_reserves: u64_at(0),
+            virtual_sol_reserves: u64_at(1),
+            real_token_reserves: u64_at(2),
+            real_sol_reserves: u64_at(3),
+            token_total_supply: u64_at(4),
+            complete: data[48] != 0,
+        })
+    }
+}
+
+pub struct PumpFunQuote {
+    pub mint: Pubkey,
+    pub sol_amount_lamports: u64, // SOL in (buy) or out (sell)
+    pub token_amount: u64,        // Raw token units out (buy) or in (sell)
+    pub sol_limit_lamports: u64,  // Max SOL cost (buy) / min SOL output (sell)
+    pub price_per_token: f64,     // USD per whole token
+    pub price_impact_pct: f64,
+}
+
+pub struct PumpFunClient {
+    rpc: RpcClient,
+}
+
+impl PumpFunClient {
+    pub fn new() -> Self {
+        Self {
+            rpc: RpcClient::new(CONFIG.solana_rpc_url.clone()),
+        }
+    }
+
+    pub async fn bonding_curve(&self, mint: &Pubkey) -> Result<BondingCurve> {
+        let data = self.rpc.get_account_data(&bonding_curve_address(mint)).await?;
+        BondingCurve::parse(&data)
+    }
+
+    /// Quotes buying `amount_usd` worth of `mint` off the curve.
+    pub async fn get_buy_quote(
+        &self,
+        amount_usd: f64,
+        sol_usd_price: f64,
+        mint: &str,
+    ) -> Result<PumpFunQuote> {
+        if sol_usd_price <= 0.0 {
+            return Err(anyhow!("SOL/USD price not available or zero. Cannot quote."));
+        }
+        let mint = Pubkey::from_str(mint)?;
+        let curve = self.live_curve(&mint).await?;
+        let sol_in = (amount_usd / sol_usd_price * 1_000_000_000.0) as u64;
+
+        let sol_after_fee = sol_in as u128 * (10_000 - FEE_BPS) / 10_000;
+        let vt = curve.virtual_token_reserves as u128;
+        let vs = curve.virtual_sol_reserves as u128;
+        let token_amount =
+            ((vt * sol_after_fee / (vs + sol_after_fee)) as u64).min(curve.real_token_reserves);
+        if token_amount == 0 {
+            return Err(anyhow!("pump.fun quote for {} rounds to zero", mint));
+        }
+        let spot_tokens = vt as f64 * sol_in as f64 / vs as f64;
+        let slippage_bps = CONFIG.tunables.get().slippage_bps as u128;
+
+        Ok(PumpFunQuote {
+            mint,
+            sol_amount_lamports: sol_in,
+            token_amount,
+            sol_limit_lamports: (sol_in as u128 * (10_000 + slippage_bps) / 10_000) as u64,
+            price_per_token: amount_usd / (token_amount as f64 / 10f64.powi(TOKEN_DECIMALS)),
+            price_impact_pct: 1.0 - token_amount as f64 / spot_tokens,
+        })
+    }
+
+    /// Quotes selling `token_amount` raw units of `mint` back to the curve.
+    pub async fn get_sell_quote(
+        &self,
+        token_amount: u64,
+        sol_usd_price: f64,
+        mint: &str,
+    ) -> Result<PumpFunQuote> {
+        let mint = Pubkey::from_str(mint)?;
+        let curve = self.live_curve(&mint).await?;
+        let vt = curve.virtual_token_reserves as u128;
+        let vs = curve.virtual_sol_reserves as u128;
+        let sol_out = vs * token_amount as u128 / (vt + token_amount as u128);
+        let sol_out = (sol_out * (10_000 - FEE_BPS) / 10_000) as u64;
+        let tokens = token_amount as f64 / 10f64.powi(TOKEN_DECIMALS);
+        let spot_sol = vs as f64 * token_amount as f64 / vt as f64;
+        let slippage_bps = CONFIG.tunables.get().slippage_bps as u128;
+
+        Ok(PumpFunQuote {
+            mint,
+            sol_amount_lamports: sol_out,
+            token_amount,
+            sol_limit_lamports: (sol_out as u128 * (10_000 - slippage_bps) / 10_000) as u64,
+            price_per_token: sol_out as f64 / 1e9 * sol_usd_price / tokens,
+            price_impact_pct: 1.0 - sol_out as f64 / spot_sol,
+        })
+    }
+
+    /// Unsigned base64 buy transaction for a quote from `get_buy_quote`.
+    pub async fn get_buy_transaction(&self, user: &Pubkey, quote: &PumpFunQuote) -> Result<String> {
+        let accounts = vec![
+            AccountMeta::new_readonly(pk(GLOBAL), false),
+            AccountMeta::new(pk(FEE_RECIPIENT), false),
+            AccountMeta::new_readonly(quote.mint, false),
+            AccountMeta::new(bonding_curve_address(&quote.mint), false),
+            AccountMeta::new(associated_token_address(&bonding_curve_address(&quote.mint), &quote.mint), false),
+            AccountMeta::new(associated_token_address(user, &quote.mint), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pk(TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(pk(EVENT_AUTHORITY), false),
+            AccountMeta::new_readonly(pk(PROGRAM_ID), false),
+        ];
+        let buy = Instruction {
+            program_id: pk(PROGRAM_ID),
+            accounts,
+            data: [
+                BUY_DISCRIMINATOR.to_vec(),
+                quote.token_amount.to_le_bytes().to_vec(),
+                quote.sol_limit_lamports.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+        info!(mint = %quote.mint, tokens = quote.token_amount, max_sol = quote.sol_limit_lamports, "Generated pump.fun buy transaction.");
+        self.unsigned_tx(user, vec![create_ata_idempotent(user, &quote.mint), buy])
+            .await
+    }
+
+    /// Unsigned base64 sell transaction for a quote from `get_sell_quote`.
+    pub async fn get_sell_transaction(&self, user: &Pubkey, quote: &PumpFunQuote) -> Result<String> {
+        let accounts = vec![
+            AccountMeta::new_readonly(pk(GLOBAL), false),
+            AccountMeta::new(pk(FEE_RECIPIENT), false),
+            AccountMeta::new_readonly(quote.mint, false),
+            AccountMeta::new(bonding_curve_address(&quote.mint), false),
+            AccountMeta::new(associated_token_address(&bonding_curve_address(&quote.mint), &quote.mint), false),
+            AccountMeta::new(associated_token_address(user, &quote.mint), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pk(ASSOCIATED_TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(pk(TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(pk(EVENT_AUTHORITY), false),
+            AccountMeta::new_readonly(pk(PROGRAM_ID), false),
+        ];
+        let sell = Instruction {
+            program_id: pk(PROGRAM_ID),
+            accounts,
+            data: [
+                SELL_DISCRIMINATOR.to_vec(),
+                quote.token_amount.to_le_bytes().to_vec(),
+                quote.sol_limit_lamports.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+        info!(mint = %quote.mint, tokens = quote.token_amount, min_sol = quote.sol_limit_lamports, "Generated pump.fun sell transaction.");
+        self.unsigned_tx(user, vec![sell]).await
+    }
+
+    async fn live_curve(&self, mint: &Pubkey) -> Result<BondingCurve> {
+        let curve = self.bonding_curve(mint).await?;
+        if curve.complete {
+            return Err(anyhow!("{} has migrated off the pump.fun curve", mint));
+        }
+        if curve.virtual_sol_reserves == 0 || curve.virtual_token_reserves == 0 {
+            return Err(anyhow!("Bonding curve for {} has no reserves", mint));
+        }
+        Ok(curve)
+    }
+
+    async fn unsigned_tx(&self, user: &Pubkey, instructions: Vec<Instruction>) -> Result<String> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        spl::unsigned_transaction_b64(user, &instructions, &blockhash)
+    }
+}
+
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &pk(PROGRAM_ID)).0
+}
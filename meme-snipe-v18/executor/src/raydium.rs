@@ -0,0 +1,339 @@
+// executor/src/raydium.rs
+// Direct Raydium AMM v4 swaps, used when Jupiter is unavailable or routes a
+// fresh pool poorly. Pool keys come from the Raydium API; quotes are computed
+// locally from the pool's vault balances (constant product, 25 bps fee).
+use crate::{
+    config::CONFIG,
+    jupiter::SOL_MINT,
+    spl::{self, associated_token_address, create_ata_idempotent, TOKEN_PROGRAM_ID},
+};
+use anyhow::{anyhow, Context, Result};
+use dashmap::DashMap;
+use reqwest::Client;
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use std::{str::FromStr, time::Duration};
+use tracing::{debug, info};
+
+pub const AMM_V4_PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+const SWAP_BASE_IN: u8 = 9;
+const FEE_NUMERATOR: u128 = 25;
+const FEE_DENOMINATOR: u128 = 10_000;
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolPage {
+    data: Vec<PoolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PoolInfo {
+    id: String,
+    program_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MintInfo {
+    address: String,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultKeys {
+    #[serde(rename = "A")]
+    a: String,
+    #[serde(rename = "B")]
+    b: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPoolKeys {
+    program_id: String,
+    id: String,
+    mint_a: MintInfo,
+    mint_b: MintInfo,
+    mint_lp: MintInfo,
+    vault: VaultKeys,
+    authority: String,
+    open_orders: String,
+    target_orders: String,
+    market_program_id: String,
+    market_id: String,
+    market_authority: String,
+    market_base_vault: String,
+    market_quote_vault: String,
+    market_bids: String,
+    market_asks: String,
+    market_event_queue: String,
+}
+
+/// Every account the AMM v4 swap instruction touches, plus which side is SOL.
+/// Vaults keep the pool's own coin (mint A) / pc (mint B) order, which is the
+/// order the swap instruction takes them in.
+#[derive(Debug, Clone)]
+pub struct PoolKeys {
+    pub program_id: Pubkey,
+    pub amm_id: Pubkey,
+    pub authority: Pubkey,
+    pub open_orders: Pubkey,
+    pub target_orders: Pubkey,
+    pub coin_vault: Pubkey,
+    pub pc_vault: Pubkey,
+    pub sol_is_coin: bool,
+    pub token_mint: Pubkey,
+    pub token_decimals: u8,
+    pub lp_mint: Pubkey,
+    pub market_program_id: Pubkey,
+    pub market_id: Pubkey,
+    pub market_authority: Pubkey,
+    pub market_base_vault: Pubkey,
+    pub market_quote_vault: Pubkey,
+    pub market_bids: Pubkey,
+    pub market_asks: Pubkey,
+    pub market_event_queue: Pubkey,
+}
+
+impl PoolKeys {
+    fn from_api(keys: ApiPoolKeys, token_mint: &str) -> Result<Self> {
+        let pk = |s: &str| Pubkey::from_str(s).with_context(|| format!("Bad pool key {}", s));
+        let (sol_is_coin, token) = if keys.mint_a.address == SOL_MINT {
+            (true, &keys.mint_b)
+        } else if keys.mint_b.address == SOL_MINT {
+            (false, &keys.mint_a)
+        } else {
+            return Err(anyhow!("Pool {} is not paired with SOL", keys.id));
+        };
+        if token.address != token_mint {
+            return Err(anyhow!("Pool {} does not trade {}", keys.id, token_mint));
+        }
+        Ok(Self {
+            program_id: pk(&keys.program_id)?,
+            amm_id: pk(&keys.id)?,
+            authority: pk(&keys.authority)?,
+            open_orders: pk(&keys.open_orders)?,
+            target_orders: pk(&keys.target_orders)?,
+            coin_vault: pk(&keys.vault.a)?,
+            pc_vault: pk(&keys.vault.b)?,
+            sol_is_coin,
+            token_mint: pk(&token.address)?,
+            token_decimals: token.decimals,
+            lp_mint: pk(&keys.mint_lp.address)?,
+            market_program_id: pk(&keys.market_program_id)?,
+            market_id: pk(&keys.market_id)?,
+            market_authority: pk(&keys.market_authority)?,
+            market_base_vault: pk(&keys.market_base_vault)?,
+            market_quote_vault: pk(&keys.market_quote_vault)?,
+            market_bids: pk(&keys.market_bids)?,
+            market_asks: pk(&keys.market_asks)?,
+            market_event_queue: pk(&keys.market_event_queue)?,
+        })
+    }
+
+    pub fn sol_vault(&self) -> Pubkey {
+        if self.sol_is_coin {
+            self.coin_vault
+        } else {
+            self.pc_vault
+        }
+    }
+
+    pub fn token_vault(&self) -> Pubkey {
+        if self.sol_is_coin {
+            self.pc_vault
+        } else {
+            self.coin_vault
+        }
+    }
+}
+
+pub struct RaydiumQuote {
+    pub pool: PoolKeys,
+    pub in_amount_lamports: u64,
+    pub out_amount: u64,      // Raw output token units
+    pub min_out_amount: u64,  // out_amount after slippage
+    pub price_per_token: f64, // USD per whole output token
+    pub price_impact_pct: f64,
+}
+
+pub struct RaydiumClient {
+    client: Client,
+    rpc: RpcClient,
+    pools: DashMap<String, PoolKeys>, // Token mint -> deepest SOL pool
+}
+
+impl RaydiumClient {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+            rpc: RpcClient::new(CONFIG.solana_rpc_url.clone()),
+            pools: DashMap::new(),
+        }
+    }
+
+    /// Quotes a SOL -> `output_mint` swap worth `amount_usd` on the deepest AMM v4 pool.
+    pub async fn get_quote(
+        &self,
+        amount_usd: f64,
+        sol_usd_price: f64,
+        output_mint: &str,
+    ) -> Result<RaydiumQuote> {
+        if sol_usd_price <= 0.0 {
+            return Err(anyhow!("SOL/USD price not available or zero. Cannot quote."));
+        }
+        let pool = self.pool_for_mint(output_mint).await?;
+        let amount_in = (amount_usd / sol_usd_price * 1_000_000_000.0) as u64;
+
+        let (sol_reserve, token_reserve) = tokio::try_join!(
+            self.rpc.get_token_account_balance(&pool.sol_vault()),
+            self.rpc.get_token_account_balance(&pool.token_vault()),
+        )?;
+        let sol_reserve: u128 = sol_reserve.amount.parse()?;
+        let token_reserve: u128 = token_reserve.amount.parse()?;
+        if sol_reserve == 0 || token_reserve == 0 {
+            return Err(anyhow!("Raydium pool {} has no liquidity", pool.amm_id));
+        }
+
+        let in_after_fee = amount_in as u128 * (FEE_DENOMINATOR - FEE_NUMERATOR) / FEE_DENOMINATOR;
+        let out_amount = (token_reserve * in_after_fee / (sol_reserve + in_after_fee)) as u64;
+        if out_amount == 0 {
+            return Err(anyhow!("Raydium quote for {} rounds to zero", output_mint));
+        }
+        let slippage_bps = CONFIG.tunables.get().slippage_bps as u128;
+        let min_out_amount = (out_amount as u128 * (10_000 - slippage_bps) / 10_000) as u64;
+
+        let tokens_out = out_amount as f64 / 10f64.powi(pool.token_decimals as i32);
+        let price_per_token = amount_usd / tokens_out;
+        let spot_out = token_reserve as f64 * amount_in as f64 / sol_reserve as f64;
+        let price_impact_pct = 1.0 - out_amount as f64 / spot_out;
+
+        info!(
+            output_mint,
+            pool = %pool.amm_id,
+            amount_usd,
+            price_per_token,
+            price_impact_pct,
+            "Raydium quote."
+        );
+        Ok(RaydiumQuote {
+            pool,
+            in_amount_lamports: amount_in,
+            out_amount,
+            min_out_amount,
+            price_per_token,
+            price_impact_pct,
+        })
+    }
+
+    /// Builds an unsigned (base64) transaction that wraps SOL, swaps on the
+    /// quoted pool, and unwraps any leftover WSOL.
+    pub async fn get_swap_transaction(
+        &self,
+        user_pubkey: &Pubkey,
+        quote: &RaydiumQuote,
+    ) -> Result<String> {
+        let token_program = spl::pk(TOKEN_PROGRAM_ID);
+        let sol_mint = spl::pk(SOL_MINT);
+        let pool = &quote.pool;
+        let wsol_account = associated_token_address(user_pubkey, &sol_mint);
+        let token_account = associated_token_address(user_pubkey, &pool.token_mint);
+
+        let swap = Instruction {
+            program_id: pool.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new(pool.amm_id, false),
+                AccountMeta::new_readonly(pool.authority, false),
+                AccountMeta::new(pool.open_orders, false),
+                AccountMeta::new(pool.target_orders, false),
+                // Coin then pc; the program reads the direction off the source
+                // account's mint (coin -> pc when SOL is the coin side).
+                AccountMeta::new(pool.coin_vault, false),
+                AccountMeta::new(pool.pc_vault, false),
+                AccountMeta::new_readonly(pool.market_program_id, false),
+                AccountMeta::new(pool.market_id, false),
+                AccountMeta::new(pool.market_bids, false),
+                AccountMeta::new(pool.market_asks, false),
+                AccountMeta::new(pool.market_event_queue, false),
+                AccountMeta::new(pool.market_base_vault, false),
+                AccountMeta::new(pool.market_quote_vault, false),
+                AccountMeta::new_readonly(pool.market_authority, false),
+                AccountMeta::new(wsol_account, false),
+                AccountMeta::new(token_account, false),
+                AccountMeta::new_readonly(*user_pubkey, true),
+            ],
+            data: [
+                vec![SWAP_BASE_IN],
+                quote.in_amount_lamports.to_le_bytes().to_vec(),
+                quote.min_out_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let instructions = vec![
+            create_ata_idempotent(user_pubkey, &sol_mint),
+            system_instruction::transfer(user_pubkey, &wsol_account, quote.in_amount_lamports),
+            spl::sync_native(&wsol_account),
+            create_ata_idempotent(user_pubkey, &pool.token_mint),
+            swap,
+            spl::close_account(&wsol_account, user_pubkey),
+        ];
+
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        info!(pool = %pool.amm_id, in_lamports = quote.in_amount_lamports, "Generated Raydium swap transaction.");
+        spl::unsigned_transaction_b64(user_pubkey, &instructions, &blockhash)
+    }
+
+    /// Keys of the deepest AMM v4 SOL pool for `mint`, cached after the first lookup.
+    pub async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys> {
+        if let Some(pool) = self.pools.get(mint) {
+            return Ok(pool.clone());
+        }
+
+        let url = format!(
+            "{}/pools/info/mint?mint1={}&mint2={}&poolType=standard&poolSortField=liquidity&sortType=desc&pageSize=5&page=1",
+            CONFIG.raydium_api_url, SOL_MINT, mint
+        );
+        let page: ApiResponse<PoolPage> = self.client.get(&url).send().await?.json().await?;
+        if !page.success {
+            return Err(anyhow!("Raydium pool lookup failed for {}", mint));
+        }
+        let pool_id = page
+            .data
+            .data
+            .into_iter()
+            .find(|p| p.program_id == AMM_V4_PROGRAM_ID)
+            .map(|p| p.id)
+            .ok_or_else(|| anyhow!("No Raydium AMM v4 pool found for {}", mint))?;
+
+        let url = format!("{}/pools/key/ids?ids={}", CONFIG.raydium_api_url, pool_id);
+        let keys: ApiResponse<Vec<ApiPoolKeys>> =
+            self.client.get(&url).send().await?.json().await?;
+        let keys = keys
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Raydium returned no keys for pool {}", pool_id))?;
+        let pool = PoolKeys::from_api(keys, mint)?;
+
+        debug!(mint, pool = %pool.amm_id, "Cached Raydium pool keys.");
+        self.pools.insert(mint.to_string(), pool.clone());
+        Ok(pool)
+    }
+}
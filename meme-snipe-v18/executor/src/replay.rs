@@ -0,0 +1,120 @@
+// executor/src/replay.rs
+//! EXECUTOR_MODE=replay: feeds the recorder's archives through the executor in
+//! place of the live event streams, for end-to-end dry runs before a deploy.
+//! Events are released on their recorded spacing divided by REPLAY_SPEED, and
+//! each is moved onto the wall clock (keeping its original lag behind arrival)
+//! so the stale-event guard, feed health and strategy time windows all see
+//! one consistent, accelerated present. The executor forces every allocation
+//! to paper while replaying.
+
+use crate::archive::Archive;
+use crate::config::CONFIG;
+use anyhow::{bail, Result};
+use shared_models::{EventType, MarketEvent};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{error, info};
+
+const BATCH_SIZE: usize = 500;
+const MAX_GAP_MS: i64 = 60_000; // Longer recording gaps (outages, missing days) are skipped over
+
+const EVENT_TYPES: [EventType; 11] = [
+    EventType::Price,
+    EventType::Social,
+    EventType::Depth,
+    EventType::Bridge,
+    EventType::Funding,
+    EventType::OnChain,
+    EventType::SolPrice,
+    EventType::DataSourceHeartbeat,
+    EventType::LiquidityPool,
+    EventType::WhaleTransfer,
+    EventType::TokenLaunch,
+];
+
+/// Starts the replay; batches of due events arrive on the receiver, which
+/// closes once the archive is exhausted.
+pub fn start() -> mpsc::Receiver<Vec<MarketEvent>> {
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        if let Err(e) = run(tx).await {
+            error!(error = %e, "Replay failed.");
+        }
+    });
+    rx
+}
+
+async fn run(tx: mpsc::Sender<Vec<MarketEvent>>) -> Result<()> {
+    let event_types: HashSet<EventType> = EVENT_TYPES.into_iter().collect();
+    let archive = Arc::new(Archive::new(
+        &PathBuf::from(&CONFIG.replay_dir),
+        &event_types,
+    ));
+    let days = archive.days(CONFIG.replay_from.as_deref(), CONFIG.replay_to.as_deref())?;
+    if days.is_empty() {
+        bail!(
+            "no archived events in {} for the requested days",
+            CONFIG.replay_dir
+        );
+    }
+    info!(
+        "⏪ Replaying {} days ({} to {}) from {} at {}x...",
+        days.len(),
+        days[0],
+        days[days.len() - 1],
+        CONFIG.replay_dir,
+        CONFIG.replay_speed
+    );
+
+    let started = Instant::now();
+    let mut origin_ms: Option<i64> = None; // Archive time of the first event
+    let mut skipped_ms = 0; // Archive time jumped over in gaps
+    let mut last_ms = 0;
+    let mut replayed = 0u64;
+    for day in days {
+        let loader = archive.clone();
+        let loaded_day = day.clone();
+        let events = tokio::task::spawn_blocking(move || loader.load_day(&loaded_day)).await??;
+        info!(day = %day, events = events.len(), "Replaying archive day.");
+
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        for recorded in events {
+            let origin = *origin_ms.get_or_insert(recorded.received_at_ms);
+            if last_ms > 0 && recorded.received_at_ms - last_ms > MAX_GAP_MS {
+                skipped_ms += recorded.received_at_ms - last_ms - MAX_GAP_MS;
+            }
+            last_ms = recorded.received_at_ms;
+
+            let due = Duration::from_secs_f64(
+                (recorded.received_at_ms - origin - skipped_ms).max(0) as f64
+                    / 1000.0
+                    / CONFIG.replay_speed,
+            );
+            let elapsed = started.elapsed();
+            if due > elapsed || batch.len() >= BATCH_SIZE {
+                if !batch.is_empty() && tx.send(std::mem::take(&mut batch)).await.is_err() {
+                    return Ok(()); // Executor stopped
+                }
+                tokio::time::sleep(due.saturating_sub(started.elapsed())).await;
+            }
+
+            let mut event = recorded.event;
+            let lag = recorded.received_at_ms / 1000 - event.timestamp();
+            event.set_timestamp(chrono::Utc::now().timestamp() - lag);
+            batch.push(event);
+            replayed += 1;
+        }
+        if !batch.is_empty() && tx.send(batch).await.is_err() {
+            return Ok(());
+        }
+    }
+    info!(
+        replayed,
+        wall_secs = started.elapsed().as_secs(),
+        "⏹️ Replay reached the end of the archive."
+    );
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// executor/src/routing.rs
+// Picks the spot venue for a buy: honours an order's venue hint, otherwise
+// quotes Jupiter, Raydium and Orca side by side and takes the cheapest
+// effective fill, falling back to the pump.fun curve for tokens none can route.
+use crate::{
+    jupiter::{JupiterClient, QuoteResult},
+    orca::{OrcaClient, OrcaQuote},
+    pumpfun::{PumpFunClient, PumpFunQuote},
+    raydium::{RaydiumClient, RaydiumQuote},
+};
+use anyhow::{anyhow, Result};
+use shared_models::Venue;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub enum SpotQuote {
+    Jupiter(QuoteResult),
+    Raydium(RaydiumQuote),
+    Orca(OrcaQuote),
+    PumpFun(PumpFunQuote),
+}
+
+impl SpotQuote {
+    pub fn venue(&self) -> Venue {
+        match self {
+            SpotQuote::Jupiter(_) => Venue::Jupiter,
+            SpotQuote::Raydium(_) => Venue::Raydium,
+            SpotQuote::Orca(_) => Venue::Orca,
+            SpotQuote::PumpFun(_) => Venue::PumpFun,
+        }
+    }
+
+    /// USD per whole output token. Every venue derives this from the amount
+    /// actually received, so pool/route fees are already priced in.
+    pub fn price_per_token(&self) -> f64 {
+        match self {
+            SpotQuote::Jupiter(q) => q.price_per_token,
+            SpotQuote::Raydium(q) => q.price_per_token,
+            SpotQuote::Orca(q) => q.price_per_token,
+            SpotQuote::PumpFun(q) => q.price_per_token,
+        }
+    }
+
+    /// Re-derives the quote's output floor (or pump.fun max cost) for a
+    /// per-trade slippage tolerance.
+    pub fn set_slippage_bps(&mut self, slippage_bps: u16) {
+        let min_out = |out: u64| (out as u128 * (10_000 - slippage_bps as u128) / 10_000) as u64;
+        match self {
+            SpotQuote::Jupiter(q) => q.set_slippage_bps(slippage_bps),
+            SpotQuote::Raydium(q) => q.min_out_amount = min_out(q.out_amount),
+            SpotQuote::Orca(q) => q.min_out_amount = min_out(q.out_amount),
+            SpotQuote::PumpFun(q) => {
+                q.sol_limit_lamports = (q.sol_amount_lamports as u128
+                    * (10_000 + slippage_bps as u128)
+                    / 10_000) as u64
+            }
+        }
+    }
+
+    /// Raw output token units the quote expects to receive.
+    pub fn out_amount(&self) -> u64 {
+        match self {
+            SpotQuote::Jupiter(q) => q.out_amount,
+            SpotQuote::Raydium(q) => q.out_amount,
+            SpotQuote::Orca(q) => q.out_amount,
+            SpotQuote::PumpFun(q) => q.token_amount,
+        }
+    }
+
+    pub fn price_impact_pct(&self) -> f64 {
+        match self {
+            SpotQuote::Jupiter(q) => q.price_impact_pct,
+            SpotQuote::Raydium(q) => q.price_impact_pct,
+            SpotQuote::Orca(q) => q.price_impact_pct,
+            SpotQuote::PumpFun(q) => q.price_impact_pct,
+        }
+    }
+}
+
+/// Every spot venue the executor can buy on.
+pub struct SpotRouter {
+    pub jupiter: Arc<JupiterClient>,
+    pub raydium: RaydiumClient,
+    pub orca: OrcaClient,
+    pub pumpfun: PumpFunClient,
+}
+
+impl SpotRouter {
+    pub fn new(jupiter: Arc<JupiterClient>) -> Self {
+        Self {
+            jupiter,
+            raydium: RaydiumClient::new(),
+            orca: OrcaClient::new(),
+            pumpfun: PumpFunClient::new(),
+        }
+    }
+
+    /// Best available quote for a SOL -> `output_mint` buy worth `amount_usd`,
+    /// restricted to `venue` when the order pins one.
+    pub async fn best_quote(
+        &self,
+        amount_usd: f64,
+        sol_usd_price: f64,
+        output_mint: &str,
+        venue: Option<Venue>,
+    ) -> Result<SpotQuote> {
+        let quote = match venue {
+            Some(Venue::Jupiter) => SpotQuote::Jupiter(
+                self.jupiter
+                    .get_quote(amount_usd, sol_usd_price, output_mint)
+                    .await?,
+            ),
+            Some(Venue::Raydium) => SpotQuote::Raydium(
+                self.raydium
+                    .get_quote(amount_usd, sol_usd_price, output_mint)
+                    .await?,
+            ),
+            Some(Venue::Orca) => SpotQuote::Orca(
+                self.orca
+                    .get_quote(amount_usd, sol_usd_price, output_mint)
+                    .await?,
+            ),
+            Some(Venue::PumpFun) => SpotQuote::PumpFun(
+                self.pumpfun
+                    .get_buy_quote(amount_usd, sol_usd_price, output_mint)
+                    .await?,
+            ),
+            None => self.route(amount_usd, sol_usd_price, output_mint).await?,
+        };
+        info!(
+            output_mint,
+            venue = ?quote.venue(),
+            price_per_token = quote.price_per_token(),
+            "Selected spot venue."
+        );
+        Ok(quote)
+    }
+
+    async fn route(&self, amount_usd: f64, sol_usd_price: f64, output_mint: &str) -> Result<SpotQuote> {
+        let (jup, ray, orca) = tokio::join!(
+            self.jupiter.get_quote(amount_usd, sol_usd_price, output_mint),
+            self.raydium.get_quote(amount_usd, sol_usd_price, output_mint),
+            self.orca.get_quote(amount_usd, sol_usd_price, output_mint),
+        );
+
+        let mut errors = Vec::new();
+        let mut quotes = Vec::new();
+        match jup {
+            Ok(q) => quotes.push(SpotQuote::Jupiter(q)),
+            Err(e) => errors.push(format!("jupiter: {}", e)),
+        }
+        match ray {
+            Ok(q) => quotes.push(SpotQuote::Raydium(q)),
+            Err(e) => errors.push(format!("raydium: {}", e)),
+        }
+        match orca {
+            Ok(q) => quotes.push(SpotQuote::Orca(q)),
+            Err(e) => errors.push(format!("orca: {}", e)),
+        }
+        if !errors.is_empty() && !quotes.is_empty() {
+            warn!(output_mint, errors = %errors.join("; "), "Some venues could not quote.");
+        }
+
+        // min_by keeps the first of equal prices, so Jupiter wins ties: its
+        // routes are battle-tested and may split across pools
+        if let Some(best) = quotes
+            .into_iter()
+            .min_by(|a, b| a.price_per_token().total_cmp(&b.price_per_token()))
+        {
+            return Ok(best);
+        }
+
+        // No AMM knows the token: it may still be on its bonding curve
+        match self
+            .pumpfun
+            .get_buy_quote(amount_usd, sol_usd_price, output_mint)
+            .await
+        {
+            Ok(p) => Ok(SpotQuote::PumpFun(p)),
+            Err(pe) => Err(anyhow!(
+                "No venue could quote {}: {}; pumpfun: {}",
+                output_mint,
+                errors.join("; "),
+                pe
+            )),
+        }
+    }
+
+    /// Unsigned base64 swap transaction for the chosen venue.
+    pub async fn build_swap(&self, user_pubkey: &Pubkey, quote: &SpotQuote) -> Result<String> {
+        match quote {
+            SpotQuote::Jupiter(q) => self.jupiter.get_swap_transaction(user_pubkey, q).await,
+            SpotQuote::Raydium(q) => self.raydium.get_swap_transaction(user_pubkey, q).await,
+            SpotQuote::Orca(q) => self.orca.get_swap_transaction(user_pubkey, q).await,
+            SpotQuote::PumpFun(q) => self.pumpfun.get_buy_transaction(user_pubkey, q).await,
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// executor/src/shadow.rs
+//! Shadow evaluation of challenger params. A second instance of a strategy,
+//! built from the spec's `challenger_params`, sees exactly the events and timer
+//! ticks the champion sees inside the same task but never trades. Execute
+//! decisions from both variants are logged to `shadow_decisions`, and each
+//! variant keeps a paper book: a signal opens a hypothetical position at the
+//! last seen price, held for SHADOW_HOLD_SECS. The comparison (divergence and
+//! PnL per variant) is published to Redis and Prometheus so a param change can
+//! be promoted on evidence. Books live in memory and restart empty.
+
+use crate::config::CONFIG;
+use crate::database::Database;
+use crate::strategies::{PortfolioContext, Strategy};
+use lazy_static::lazy_static;
+use prometheus::{register_gauge_vec, GaugeVec};
+use redis::AsyncCommands;
+use serde::Serialize;
+use shared_models::{EventType, MarketEvent, OrderDetails, Side, StrategyAction};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+lazy_static! {
+    static ref SHADOW_PNL_USD: GaugeVec = register_gauge_vec!(
+        "executor_shadow_pnl_usd",
+        "Hypothetical PnL (realized + unrealized) of each shadow-evaluated variant.",
+        &["strategy_id", "variant"]
+    )
+    .unwrap();
+    static ref SHADOW_DIVERGENCE_RATIO: GaugeVec = register_gauge_vec!(
+        "executor_shadow_divergence_ratio",
+        "Share of decision points where champion and challenger disagreed.",
+        &["strategy_id"]
+    )
+    .unwrap();
+}
+
+const SHADOW_REPORTS_KEY: &str = "executor:shadow_reports"; // Hash: strategy ID -> ShadowReport JSON
+
+struct PaperPosition {
+    side: Side,
+    entry_price_usd: f64,
+    size_usd: f64,
+    opened_at: i64,
+}
+
+impl PaperPosition {
+    fn pnl_usd(&self, price_usd: f64) -> f64 {
+        let change = (price_usd - self.entry_price_usd) / self.entry_price_usd;
+        match self.side {
+            Side::Long => change * self.size_usd,
+            Side::Short => -change * self.size_usd,
+        }
+    }
+}
+
+/// One variant's hypothetical trading, one position per token at a time like
+/// the default per-strategy-token cap.
+#[derive(Default)]
+struct PaperBook {
+    open: HashMap<String, PaperPosition>,
+    signals: u64,
+    trades: u64,
+    realized_pnl_usd: f64,
+}
+
+impl PaperBook {
+    fn on_signal(&mut self, details: &OrderDetails, price_usd: Option<f64>, at: i64) {
+        self.signals += 1;
+        let price_usd = match price_usd {
+            Some(price) if price > 0.0 => price,
+            _ => return, // Can't mark a position we have no price for
+        };
+        if self.open.contains_key(&details.token_address) {
+            return;
+        }
+        self.trades += 1;
+        self.open.insert(
+            details.token_address.clone(),
+            PaperPosition {
+                side: details.side.clone(),
+                entry_price_usd: price_usd,
+                size_usd: details
+                    .suggested_size_usd
+                    .min(CONFIG.tunables.get().global_max_position_usd),
+                opened_at: at,
+            },
+        );
+    }
+
+    /// Closes positions held for the full holding period at the last seen price.
+    fn settle(&mut self, now: i64, prices: &HashMap<String, f64>) {
+        let mut realized = 0.0;
+        self.open.retain(|token, position| match prices.get(token) {
+            Some(price) if now - position.opened_at >= CONFIG.shadow_hold_secs => {
+                realized += position.pnl_usd(*price);
+                false
+            }
+            _ => true,
+        });
+        self.realized_pnl_usd += realized;
+    }
+
+    fn summary(&self, prices: &HashMap<String, f64>) -> VariantSummary {
+        let unrealized_pnl_usd = self
+            .open
+            .iter()
+            .filter_map(|(token, position)| prices.get(token).map(|p| position.pnl_usd(*p)))
+            .sum::<f64>();
+        VariantSummary {
+            signals: self.signals,
+            trades: self.trades,
+            open_positions: self.open.len(),
+            realized_pnl_usd: self.realized_pnl_usd,
+            unrealized_pnl_usd,
+            total_pnl_usd: self.realized_pnl_usd + unrealized_pnl_usd,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct VariantSummary {
+    pub signals: u64,
+    pub trades: u64,
+    pub open_positions: usize,
+    pub realized_pnl_usd: f64,
+    pub unrealized_pnl_usd: f64,
+    pub total_pnl_usd: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShadowReport {
+    pub strategy_id: String,
+    pub generated_at: i64,
+    pub since: i64,
+    pub decision_points: u64, // Events/ticks where at least one variant executed
+    pub divergences: u64,     // ... and the variants didn't make the same call
+    pub divergence_ratio: f64,
+    pub champion: VariantSummary,
+    pub challenger: VariantSummary,
+}
+
+pub struct ShadowEvaluator {
+    strategy_id: String,
+    challenger: Box<dyn Strategy>,
+    subscriptions: HashSet<EventType>,
+    db: Arc<Database>,
+    prices: HashMap<String, f64>, // Last seen price per token, for paper fills and marks
+    champion_book: PaperBook,
+    challenger_book: PaperBook,
+    decision_points: u64,
+    divergences: u64,
+    since: i64,
+}
+
+impl ShadowEvaluator {
+    /// Wraps an initialized challenger instance of `strategy_id`'s family.
+    pub fn new(strategy_id: &str, challenger: Box<dyn Strategy>, db: Arc<Database>) -> Self {
+        Self {
+            strategy_id: strategy_id.to_string(),
+            subscriptions: challenger.subscriptions(),
+            challenger,
+            db,
+            prices: HashMap::new(),
+            champion_book: PaperBook::default(),
+            challenger_book: PaperBook::default(),
+            decision_points: 0,
+            divergences: 0,
+            since: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// The challenger, for replaying history into it before it goes live.
+    pub fn challenger_mut(&mut self) -> &mut dyn Strategy {
+        self.challenger.as_mut()
+    }
+
+    /// Event types the task must be routed for the shadow: the challenger's
+    /// subscriptions plus prices to mark both books.
+    pub fn subscriptions(&self) -> HashSet<EventType> {
+        let mut subscriptions = self.subscriptions.clone();
+        subscriptions.insert(EventType::Price);
+        subscriptions
+    }
+
+    /// Feeds an event to the challenger, with the champion's portfolio view,
+    /// and compares its decision with the champion's. `champion` is `Hold` for
+    /// events the champion didn't see.
+    pub async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+        champion: &StrategyAction,
+    ) {
+        if let MarketEvent::Price(tick) = event {
+            self.prices
+                .insert(tick.token_address.clone(), tick.price_usd);
+        }
+        if !self.subscriptions.contains(&event.get_type()) {
+            self.compare(event.timestamp(), champion, &StrategyAction::Hold);
+            return;
+        }
+        let challenger = match self.challenger.on_event(event, portfolio).await {
+            Ok(action) => action,
+            Err(e) => {
+                warn!(strategy = %self.strategy_id, error = %e, "Challenger returned an error.");
+                StrategyAction::Hold
+            }
+        };
+        self.compare(event.timestamp(), champion, &challenger);
+    }
+
+    /// Drives the challenger's timer from the champion's ticks.
+    pub async fn on_timer(&mut self, now: i64, champion: &StrategyAction) {
+        let challenger = match self.challenger.on_timer(now).await {
+            Ok(action) => action,
+            Err(e) => {
+                warn!(strategy = %self.strategy_id, error = %e, "Challenger timer returned an error.");
+                StrategyAction::Hold
+            }
+        };
+        self.compare(now, champion, &challenger);
+    }
+
+    fn compare(&mut self, at: i64, champion: &StrategyAction, challenger: &StrategyAction) {
+        let champion = champion.orders();
+        let challenger = challenger.orders();
+        if champion.is_empty() && challenger.is_empty() {
+            return;
+        }
+        // Same call = same (token, side) for every leg, in any order
+        let calls = |orders: &[&OrderDetails]| {
+            let mut calls: Vec<(String, String)> = orders
+                .iter()
+                .map(|o| (o.token_address.clone(), o.side.to_string()))
+                .collect();
+            calls.sort();
+            calls
+        };
+        let agreed = calls(&champion) == calls(&challenger);
+        self.decision_points += 1;
+        if !agreed {
+            self.divergences += 1;
+        }
+
+        for (variant, orders) in [("champion", champion), ("challenger", challenger)] {
+            for details in orders {
+                let price = self.prices.get(&details.token_address).copied();
+                let book = match variant {
+                    "champion" => &mut self.champion_book,
+                    _ => &mut self.challenger_book,
+                };
+                book.on_signal(details, price, at);
+                if let Err(e) = self.db.log_shadow_decision(
+                    &self.strategy_id,
+                    variant,
+                    at,
+                    details,
+                    price,
+                    agreed,
+                ) {
+                    error!(strategy = %self.strategy_id, error = %e, "Failed to log shadow decision.");
+                }
+            }
+        }
+    }
+
+    /// Settles expired paper positions and builds the current comparison.
+    pub fn report(&mut self) -> ShadowReport {
+        let now = chrono::Utc::now().timestamp();
+        self.champion_book.settle(now, &self.prices);
+        self.challenger_book.settle(now, &self.prices);
+        ShadowReport {
+            strategy_id: self.strategy_id.clone(),
+            generated_at: now,
+            since: self.since,
+            decision_points: self.decision_points,
+            divergences: self.divergences,
+            divergence_ratio: if self.decision_points > 0 {
+                self.divergences as f64 / self.decision_points as f64
+            } else {
+                0.0
+            },
+            champion: self.champion_book.summary(&self.prices),
+            challenger: self.challenger_book.summary(&self.prices),
+        }
+    }
+
+    /// Publishes the current report to the `executor:shadow_reports` hash and
+    /// the shadow gauges.
+    pub async fn publish(
+        &mut self,
+        redis_conn_manager: &Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    ) {
+        let report = self.report();
+        SHADOW_PNL_USD
+            .with_label_values(&[&self.strategy_id, "champion"])
+            .set(report.champion.total_pnl_usd);
+        SHADOW_PNL_USD
+            .with_label_values(&[&self.strategy_id, "challenger"])
+            .set(report.challenger.total_pnl_usd);
+        SHADOW_DIVERGENCE_RATIO
+            .with_label_values(&[&self.strategy_id])
+            .set(report.divergence_ratio);
+        info!(
+            strategy = %self.strategy_id,
+            divergence_ratio = report.divergence_ratio,
+            champion_pnl_usd = report.champion.total_pnl_usd,
+            challenger_pnl_usd = report.challenger.total_pnl_usd,
+            "Shadow evaluation report."
+        );
+
+        let raw = match serde_json::to_string(&report) {
+            Ok(raw) => raw,
+            Err(e) => {
+                error!(strategy = %self.strategy_id, error = %e, "Failed to serialize shadow report.");
+                return;
+            }
+        };
+        let mut conn = redis_conn_manager.lock().await.clone();
+        if let Err(e) = conn
+            .hset::<_, _, _, ()>(SHADOW_REPORTS_KEY, &self.strategy_id, raw)
+            .await
+        {
+            error!(strategy = %self.strategy_id, error = %e, "Failed to publish shadow report.");
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// executor/src/slippage.rs
+//! Per-trade slippage tolerance. The expected price impact of an order is read
+//! off the latest DepthEvent for the token (share of visible ask liquidity the
+//! order consumes, plus half the spread), or the venue quote's own price impact
+//! when depth is missing or stale. Tolerance is that impact times
+//! SLIPPAGE_IMPACT_MULTIPLIER, floored at SLIPPAGE_BPS and capped by the
+//! strategy's max_slippage_bps and the global MAX_SLIPPAGE_BPS.
+
+use crate::config::CONFIG;
+use dashmap::DashMap;
+use shared_models::DepthEvent;
+use tracing::debug;
+
+#[derive(Default)]
+pub struct DepthBook {
+    latest: DashMap<String, DepthEvent>, // Token -> most recent top-of-book
+}
+
+impl DepthBook {
+    pub fn update(&self, event: &DepthEvent) {
+        self.latest.insert(event.token_address.clone(), event.clone());
+    }
+
+    /// Expected fractional price impact of buying `size_usd`, if depth is fresh.
+    fn expected_impact(&self, token_address: &str, size_usd: f64) -> Option<f64> {
+        let depth = self.latest.get(token_address)?;
+        let age = chrono::Utc::now().timestamp() - depth.timestamp;
+        if age > CONFIG.depth_max_age_secs || depth.ask_size_usd <= 0.0 || depth.ask_price <= 0.0 {
+            return None;
+        }
+        let mid = (depth.bid_price + depth.ask_price) / 2.0;
+        let half_spread = if mid > 0.0 {
+            (depth.ask_price - depth.bid_price).max(0.0) / mid / 2.0
+        } else {
+            0.0
+        };
+        Some(size_usd / depth.ask_size_usd + half_spread)
+    }
+
+    /// Slippage tolerance in bps for a buy of `size_usd` of `token_address`.
+    pub fn slippage_bps(
+        &self,
+        token_address: &str,
+        size_usd: f64,
+        quoted_impact_pct: f64,
+        strategy_max_bps: Option<u16>,
+    ) -> u16 {
+        let impact = self
+            .expected_impact(token_address, size_usd)
+            .unwrap_or(quoted_impact_pct)
+            .max(0.0);
+        let tunables = CONFIG.tunables.get();
+        let cap = strategy_max_bps
+            .unwrap_or(tunables.max_slippage_bps)
+            .min(tunables.max_slippage_bps);
+        let floor = tunables.slippage_bps.min(cap);
+        let bps = (impact * 10_000.0 * tunables.slippage_impact_multiplier)
+            .round()
+            .clamp(floor as f64, cap as f64) as u16;
+        debug!(token_address, size_usd, impact, bps, "Derived slippage tolerance.");
+        bps
+    }
+}
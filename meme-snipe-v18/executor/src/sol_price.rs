@@ -0,0 +1,167 @@
+// executor/src/sol_price.rs
+//! SOL/USD used to size trades. pyth_consumer's `events:sol_price` stream is the
+//! primary source; when it hasn't produced a price for SOL_PRICE_MAX_AGE_SECS
+//! the executor reads the Pyth SOL/USD feed from Hermes itself, so a stalled
+//! consumer doesn't halt live trading. Pyth prices that are stale or have a wide
+//! confidence interval are rejected rather than traded on.
+
+use crate::config::CONFIG;
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use prometheus::{register_counter_vec, CounterVec};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+lazy_static! {
+    static ref SOL_PRICE_SOURCE_TOTAL: CounterVec = register_counter_vec!(
+        "executor_sol_price_source_total",
+        "SOL/USD lookups by source (stream, pyth) and outcome.",
+        &["source", "outcome"]
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesResponse {
+    parsed: Vec<HermesFeed>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesFeed {
+    price: HermesPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    conf: String,
+    expo: i32,
+    publish_time: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PricePoint {
+    price_usd: f64,
+    timestamp: i64, // Unix seconds the price was observed/published
+}
+
+impl PricePoint {
+    fn is_fresh(&self, max_age_secs: i64) -> bool {
+        self.price_usd > 0.0 && chrono::Utc::now().timestamp() - self.timestamp <= max_age_secs
+    }
+}
+
+pub struct SolPriceFeed {
+    stream: Mutex<Option<PricePoint>>, // Last events:sol_price update
+    pyth: Mutex<Option<PricePoint>>,   // Last accepted Pyth price, reused while fresh
+    http: Client,
+}
+
+impl SolPriceFeed {
+    pub fn new() -> Self {
+        Self {
+            stream: Mutex::new(None),
+            pyth: Mutex::new(None),
+            http: Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// Records a price from the `events:sol_price` stream.
+    pub async fn update(&self, price_usd: f64, timestamp: i64) {
+        *self.stream.lock().await = Some(PricePoint {
+            price_usd,
+            timestamp,
+        });
+    }
+
+    /// Last known price from either source, for status reporting only.
+    pub fn last_known(&self) -> Option<f64> {
+        let stream = *self.stream.blocking_lock();
+        stream.or(*self.pyth.blocking_lock()).map(|p| p.price_usd)
+    }
+
+    /// A fresh SOL/USD price, or an error if neither source has one.
+    pub async fn get(&self) -> Result<f64> {
+        if let Some(point) = *self.stream.lock().await {
+            if point.is_fresh(CONFIG.sol_price_max_age_secs) {
+                SOL_PRICE_SOURCE_TOTAL
+                    .with_label_values(&["stream", "success"])
+                    .inc();
+                return Ok(point.price_usd);
+            }
+            debug!(age_secs = chrono::Utc::now().timestamp() - point.timestamp, "Streamed SOL/USD is stale.");
+        }
+
+        let mut pyth = self.pyth.lock().await;
+        if let Some(point) = *pyth {
+            if point.is_fresh(CONFIG.pyth_max_age_secs) {
+                return Ok(point.price_usd);
+            }
+        }
+        match self.fetch_pyth().await {
+            Ok(point) => {
+                SOL_PRICE_SOURCE_TOTAL
+                    .with_label_values(&["pyth", "success"])
+                    .inc();
+                warn!(price_usd = point.price_usd, "Using Pyth fallback for SOL/USD.");
+                *pyth = Some(point);
+                Ok(point.price_usd)
+            }
+            Err(e) => {
+                SOL_PRICE_SOURCE_TOTAL
+                    .with_label_values(&["pyth", "failure"])
+                    .inc();
+                Err(e.context("SOL/USD price not available from stream or Pyth"))
+            }
+        }
+    }
+
+    async fn fetch_pyth(&self) -> Result<PricePoint> {
+        let url = format!(
+            "{}/v2/updates/price/latest?ids[]={}&parsed=true",
+            CONFIG.pyth_hermes_url, CONFIG.pyth_sol_usd_feed_id
+        );
+        let response: HermesResponse = self
+            .http
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Hermes response")?;
+        let feed = response
+            .parsed
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Hermes returned no SOL/USD feed"))?;
+
+        let scale = 10f64.powi(feed.price.expo);
+        let price_usd = feed.price.price.parse::<i64>()? as f64 * scale;
+        let conf_usd = feed.price.conf.parse::<u64>()? as f64 * scale;
+        if price_usd <= 0.0 {
+            return Err(anyhow!("Pyth SOL/USD price is non-positive: {}", price_usd));
+        }
+        let age = chrono::Utc::now().timestamp() - feed.price.publish_time;
+        if age > CONFIG.pyth_max_age_secs {
+            return Err(anyhow!("Pyth SOL/USD is stale ({}s old)", age));
+        }
+        if conf_usd / price_usd > CONFIG.pyth_max_conf_ratio {
+            return Err(anyhow!(
+                "Pyth SOL/USD confidence too wide: ±{:.4} on {:.4}",
+                conf_usd,
+                price_usd
+            ));
+        }
+        Ok(PricePoint {
+            price_usd,
+            timestamp: feed.price.publish_time,
+        })
+    }
+}
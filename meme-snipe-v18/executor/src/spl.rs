@@ -0,0 +1,79 @@
+// executor/src/spl.rs
+// Minimal SPL token / ATA helpers shared by the direct-venue clients
+// (Raydium, pump.fun) that assemble their own swap transactions.
+use anyhow::Result;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+    transaction::VersionedTransaction,
+};
+use std::str::FromStr;
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+pub fn pk(s: &str) -> Pubkey {
+    Pubkey::from_str(s).expect("valid hard-coded pubkey")
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), pk(TOKEN_PROGRAM_ID).as_ref(), mint.as_ref()],
+        &pk(ASSOCIATED_TOKEN_PROGRAM_ID),
+    )
+    .0
+}
+
+pub fn create_ata_idempotent(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pk(ASSOCIATED_TOKEN_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pk(TOKEN_PROGRAM_ID), false),
+        ],
+        data: vec![1], // CreateIdempotent
+    }
+}
+
+pub fn sync_native(account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pk(TOKEN_PROGRAM_ID),
+        accounts: vec![AccountMeta::new(*account, false)],
+        data: vec![17], // SyncNative
+    }
+}
+
+pub fn close_account(account: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pk(TOKEN_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![9], // CloseAccount
+    }
+}
+
+/// Base64 legacy transaction with empty signature slots, in the same shape
+/// Jupiter's /swap returns, so it goes through the signer unchanged.
+pub fn unsigned_transaction_b64(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    blockhash: &Hash,
+) -> Result<String> {
+    let message = Message::new_with_blockhash(instructions, Some(payer), blockhash);
+    let tx = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    };
+    Ok(base64::encode(bincode::serialize(&tx)?))
+}
@@ -0,0 +1,322 @@
+// executor/src/strategies/basket_momentum.rs
+//! Momentum in narrative baskets, e.g. AI, dog or political memes. Each entry
+//! of `members` is "BASKET:MINT", grouping tokens into named baskets; a token
+//! belongs to one basket only.
+//!
+//! A basket is on when its members' mean return over `lookback_secs` is above
+//! `min_basket_return_pct` and its social volume is rising: the mentions of
+//! its members in the last `social_window_secs` exceed `min_social_growth`
+//! times those of the window before. While a basket is on, its `top_n`
+//! strongest members with a positive return are held, bought at most once
+//! per `rebalance_secs`. Members that lose the lead, and every member once
+//! the basket turns off, are closed one per update.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction, TradeMode,
+    },
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use prometheus::GaugeVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{LegPolicy, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::info;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Basket {
+    name: String,
+    members: Vec<String>,
+    mentions: VecDeque<i64>, // Mention timestamps within two social windows
+    rebalanced_at: i64,
+    #[serde(skip)]
+    closing: HashSet<String>, // Members a close has been requested for
+}
+
+#[derive(Default)]
+struct BasketMomentum {
+    lookback_secs: i64,
+    min_basket_return_pct: f64,
+    social_window_secs: i64,
+    min_social_growth: f64,
+    top_n: usize,
+    size_usd: f64,
+    rebalance_secs: i64,
+    baskets: Vec<Basket>,
+    basket_of: HashMap<String, usize>, // Member token -> index into baskets
+    prices: HashMap<String, VecDeque<(i64, f64)>>, // Per member, oldest first
+    return_gauge: Option<GaugeVec>,
+}
+
+impl BasketMomentum {
+    /// Return over the lookback, once a price at least that old is held.
+    fn member_return(&self, token: &str, now: i64) -> Option<f64> {
+        let prices = self.prices.get(token)?;
+        let (anchor_at, anchor) = *prices.front()?;
+        let (_, latest) = *prices.back()?;
+        (now - anchor_at >= self.lookback_secs && anchor > 0.0).then(|| latest / anchor - 1.0)
+    }
+}
+
+#[async_trait]
+impl Strategy for BasketMomentum {
+    fn id(&self) -> &'static str {
+        "basket_momentum"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price, EventType::Social]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("members", ParamKind::StringList)
+            .min(1.0)
+            .required("lookback_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_basket_return_pct", ParamKind::Number)
+            .required("social_window_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_social_growth", ParamKind::Number)
+            .min(0.0)
+            .required("top_n", ParamKind::Integer)
+            .min(1.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("rebalance_secs", ParamKind::Integer)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            members: Vec<String>,
+            lookback_secs: i64,
+            min_basket_return_pct: f64,
+            social_window_secs: i64,
+            min_social_growth: f64,
+            top_n: usize,
+            size_usd: f64,
+            rebalance_secs: i64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        let mut baskets: Vec<Basket> = Vec::new();
+        let mut basket_of = HashMap::new();
+        for spec in &p.members {
+            let (name, token) = spec
+                .split_once(':')
+                .filter(|(name, token)| !name.is_empty() && !token.is_empty())
+                .ok_or_else(|| anyhow!("member `{}` must be BASKET:MINT", spec))?;
+            let index = match baskets.iter().position(|b| b.name == name) {
+                Some(index) => index,
+                None => {
+                    baskets.push(Basket {
+                        name: name.to_string(),
+                        ..Basket::default()
+                    });
+                    baskets.len() - 1
+                }
+            };
+            if basket_of.insert(token.to_string(), index).is_some() {
+                return Err(anyhow!("token {} is in more than one basket", token));
+            }
+            baskets[index].members.push(token.to_string());
+        }
+        self.lookback_secs = p.lookback_secs;
+        self.min_basket_return_pct = p.min_basket_return_pct;
+        self.social_window_secs = p.social_window_secs;
+        self.min_social_growth = p.min_social_growth;
+        self.top_n = p.top_n;
+        self.size_usd = p.size_usd;
+        self.rebalance_secs = p.rebalance_secs;
+        self.baskets = baskets;
+        self.basket_of = basket_of;
+        self.prices.clear();
+        self.return_gauge = Some(metrics.gauge_vec(
+            "basket_return_pct",
+            "Mean member return over the lookback, per basket.",
+            &["basket"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} baskets, lookback_secs: {}, social_window_secs: {}, top_n: {}",
+            self.baskets.len(),
+            self.lookback_secs,
+            self.social_window_secs,
+            self.top_n
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let social_window = self.social_window_secs;
+        let tick = match event {
+            MarketEvent::Social(mention) => {
+                if let Some(&index) = self.basket_of.get(&mention.token_address) {
+                    let mentions = &mut self.baskets[index].mentions;
+                    mentions.push_back(mention.timestamp);
+                    while mentions
+                        .front()
+                        .is_some_and(|at| mention.timestamp - at > 2 * social_window)
+                    {
+                        mentions.pop_front();
+                    }
+                }
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let index = match self.basket_of.get(&tick.token_address) {
+            Some(&index) => index,
+            None => return Ok(StrategyAction::Hold),
+        };
+        let now = tick.timestamp;
+        let lookback = self.lookback_secs;
+        let prices = self.prices.entry(tick.token_address.clone()).or_default();
+        prices.push_back((now, tick.price_usd));
+        // Keep one price at least a lookback old as the anchor
+        while prices.len() > 1 && now - prices[1].0 >= lookback {
+            prices.pop_front();
+        }
+
+        let basket = &self.baskets[index];
+        let mut returns: Vec<(String, f64)> = basket
+            .members
+            .iter()
+            .filter_map(|token| Some((token.clone(), self.member_return(token, now)?)))
+            .collect();
+        if returns.len() < basket.members.len().min(2) {
+            return Ok(StrategyAction::Hold);
+        }
+        let basket_return_pct =
+            returns.iter().map(|(_, r)| r).sum::<f64>() / returns.len() as f64 * 100.0;
+        if let Some(gauge) = &self.return_gauge {
+            gauge
+                .with_label_values(&[&basket.name])
+                .set(basket_return_pct);
+        }
+        let recent = basket
+            .mentions
+            .iter()
+            .filter(|at| now - **at <= social_window)
+            .count();
+        let prior = basket
+            .mentions
+            .iter()
+            .filter(|at| now - **at > social_window && now - **at <= 2 * social_window)
+            .count();
+        let social_rising = recent > 0 && recent as f64 > prior as f64 * self.min_social_growth;
+        let basket_on = basket_return_pct > self.min_basket_return_pct && social_rising;
+
+        returns.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let leaders: Vec<(String, f64)> = if basket_on {
+            returns
+                .into_iter()
+                .filter(|(_, r)| *r > 0.0)
+                .take(self.top_n)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let held = |token: &String| portfolio.exposure_to(token, &Side::Long) > 0.0;
+        let basket = &mut self.baskets[index];
+        basket.closing.retain(|token| held(token));
+        let laggard = basket
+            .members
+            .iter()
+            .find(|token| {
+                held(token)
+                    && !basket.closing.contains(*token)
+                    && !leaders.iter().any(|(leader, _)| leader == *token)
+            })
+            .cloned();
+        if let Some(token) = laggard {
+            basket.closing.insert(token.clone());
+            let name = basket.name.clone();
+            info!(id = self.id(), basket = %name, token = %token, "EXIT signal: Member lost the basket lead.");
+            return Ok(StrategyAction::ClosePositions(token));
+        }
+
+        if now - basket.rebalanced_at < self.rebalance_secs {
+            return Ok(StrategyAction::Hold);
+        }
+        let entries: Vec<&(String, f64)> = leaders.iter().filter(|(t, _)| !held(t)).collect();
+        if entries.is_empty() {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd =
+            portfolio.scale_size(self.size_usd * entries.len() as f64) / entries.len() as f64;
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        basket.rebalanced_at = now;
+        let name = basket.name.clone();
+        let legs = entries
+            .iter()
+            .map(|(token, member_return)| OrderDetails {
+                token_address: token.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "basket": name,
+                    "basket_return_pct": basket_return_pct,
+                    "member_return_pct": member_return * 100.0,
+                    "social_mentions": recent,
+                    "social_mentions_prior": prior,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None, // Closed when it loses the lead
+            })
+            .collect::<Vec<_>>();
+        info!(id = self.id(), basket = %name, "BUY signal: Basket up {:.2}% with mentions {} -> {}, buying {} leaders.", basket_return_pct, prior, recent, legs.len());
+        Ok(StrategyAction::ExecuteMany(
+            legs,
+            LegPolicy::Independent,
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "baskets": self.baskets, "prices": self.prices })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            baskets: Vec<Basket>,
+            prices: HashMap<String, VecDeque<(i64, f64)>>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        // Baskets and members dropped from the params are forgotten
+        for saved in s.baskets {
+            if let Some(basket) = self.baskets.iter_mut().find(|b| b.name == saved.name) {
+                basket.mentions = saved.mentions;
+                basket.rebalanced_at = saved.rebalanced_at;
+            }
+        }
+        let basket_of = &self.basket_of;
+        self.prices = s
+            .prices
+            .into_iter()
+            .filter(|(token, _)| basket_of.contains_key(token))
+            .collect();
+        Ok(())
+    }
+}
+register_strategy!(BasketMomentum, "basket_momentum");
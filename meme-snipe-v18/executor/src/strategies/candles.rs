@@ -0,0 +1,110 @@
+// executor/src/strategies/candles.rs
+//! Rolls a token's PriceTicks up into fixed-period OHLCV candles, for
+//! strategies that reason in bars rather than ticks. Periods are aligned to the
+//! unix epoch, so a 1h candle always spans hh:00 to hh:59. A period without
+//! ticks produces no candle rather than a flat one.
+//!
+//! Ticks carry a rolling 1m volume, not the volume traded since the previous
+//! tick, so a candle's volume integrates that rate over the time between
+//! ticks (gaps longer than a minute count as one minute).
+
+use crate::strategies::newest;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub start: i64, // Unix timestamp the period starts at
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CandleAggregator {
+    period_secs: i64,
+    capacity: usize,
+    closed: VecDeque<Candle>, // Oldest first
+    current: Option<Candle>,
+    last_tick_at: Option<i64>,
+}
+
+impl CandleAggregator {
+    /// Keeps the newest `capacity` closed candles of `period_secs` each.
+    pub fn new(period_secs: i64, capacity: usize) -> Self {
+        Self {
+            period_secs: period_secs.max(1),
+            capacity: capacity.max(1),
+            closed: VecDeque::with_capacity(capacity),
+            current: None,
+            last_tick_at: None,
+        }
+    }
+
+    pub fn period_secs(&self) -> i64 {
+        self.period_secs
+    }
+
+    /// Folds a tick in. Returns the candle the tick closed, if it is the first
+    /// tick of a new period. Ticks older than the current candle are ignored.
+    pub fn push(&mut self, timestamp: i64, price: f64, volume_usd_1m: f64) -> Option<Candle> {
+        let start = timestamp - timestamp.rem_euclid(self.period_secs);
+        let elapsed_secs = self
+            .last_tick_at
+            .map_or(0, |at| (timestamp - at).clamp(0, 60));
+        let volume_usd = volume_usd_1m * elapsed_secs as f64 / 60.0;
+
+        let just_closed = match &mut self.current {
+            Some(candle) if start < candle.start => return None,
+            Some(candle) if start == candle.start => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume_usd += volume_usd;
+                None
+            }
+            current => current.replace(Candle {
+                start,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume_usd,
+            }),
+        };
+        self.last_tick_at = Some(timestamp);
+        if let Some(candle) = just_closed {
+            if self.closed.len() == self.capacity {
+                self.closed.pop_front();
+            }
+            self.closed.push_back(candle);
+        }
+        just_closed
+    }
+
+    /// Closed candles, oldest first.
+    pub fn closed(&self) -> &VecDeque<Candle> {
+        &self.closed
+    }
+
+    /// The candle still being built, if any tick has arrived.
+    pub fn current(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// A restored aggregator under the current params: `None` if the period
+    /// changed (its candles mean something else now), otherwise trimmed to
+    /// `capacity`.
+    pub fn restored(self, period_secs: i64, capacity: usize) -> Option<Self> {
+        if self.period_secs != period_secs {
+            return None;
+        }
+        Some(Self {
+            capacity: capacity.max(1),
+            closed: newest(self.closed, capacity.max(1)),
+            ..self
+        })
+    }
+}
@@ -0,0 +1,200 @@
+// executor/src/strategies/copy_trader.rs
+//! Copies the swaps of a configured list of leader wallets, read from OnChain
+//! events of type "Swap" whose data is `{ wallet, direction: "buy" | "sell",
+//! amount_usd }`. A leader's buy of at least `min_leader_usd` is mirrored at
+//! `size_ratio` of their size, capped at `max_size_usd`, unless the swap is
+//! more than `max_delay_secs` old by the time it arrives: a late copy chases
+//! the price the leader moved. A leader's sell closes the strategy's position
+//! in that token however late it is. Buys also have to pass the executor's
+//! token safety checker, in Paper mode as well as Live.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction, TradeMode,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::CounterVec;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::HashSet;
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SwapDirection {
+    Buy,
+    Sell,
+}
+
+/// Payload of an OnChain "Swap" event.
+#[derive(Debug, Clone, Deserialize)]
+struct LeaderSwap {
+    wallet: String,
+    direction: SwapDirection,
+    amount_usd: f64,
+}
+
+#[derive(Default)]
+struct CopyTrader {
+    leaders: HashSet<String>,
+    min_leader_usd: f64,
+    size_ratio: f64,
+    max_size_usd: f64,
+    max_delay_secs: i64,
+    swaps_counter: Option<CounterVec>,
+}
+
+impl CopyTrader {
+    fn count(&self, outcome: &str) {
+        if let Some(counter) = &self.swaps_counter {
+            counter.with_label_values(&[outcome]).inc();
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for CopyTrader {
+    fn id(&self) -> &'static str {
+        "copy_trader"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::OnChain].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("leaders", ParamKind::StringList)
+            .min(1.0)
+            .required("min_leader_usd", ParamKind::Number)
+            .min(0.0)
+            .required("size_ratio", ParamKind::Number)
+            .min(0.0)
+            .required("max_size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("max_delay_secs", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            leaders: Vec<String>,
+            min_leader_usd: f64,
+            size_ratio: f64,
+            max_size_usd: f64,
+            max_delay_secs: i64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.leaders = p.leaders.into_iter().collect();
+        self.min_leader_usd = p.min_leader_usd;
+        self.size_ratio = p.size_ratio;
+        self.max_size_usd = p.max_size_usd;
+        self.max_delay_secs = p.max_delay_secs;
+        self.swaps_counter = Some(metrics.counter_vec(
+            "leader_swaps_total",
+            "Leader swaps seen, by outcome (mirrored or why they were skipped).",
+            &["outcome"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized copying {} leaders, min_leader_usd: {}, size_ratio: {}, max_delay_secs: {}",
+            self.leaders.len(),
+            self.min_leader_usd,
+            self.size_ratio,
+            self.max_delay_secs
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let onchain = match event {
+            MarketEvent::OnChain(onchain) if onchain.event_type == "Swap" => onchain,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let swap: LeaderSwap = match serde_json::from_value(onchain.data.clone()) {
+            Ok(swap) => swap,
+            Err(e) => {
+                debug!(id = self.id(), error = %e, "Ignoring malformed Swap event.");
+                return Ok(StrategyAction::Hold);
+            }
+        };
+        if !self.leaders.contains(&swap.wallet) {
+            return Ok(StrategyAction::Hold);
+        }
+        let token = onchain.token_address.as_str();
+        let holding = portfolio.exposure_to(token, &Side::Long) > 0.0;
+
+        if swap.direction == SwapDirection::Sell {
+            if !holding {
+                return Ok(StrategyAction::Hold);
+            }
+            info!(id = self.id(), token = %token, leader = %swap.wallet, "EXIT signal: Leader sold {:.0} USD.", swap.amount_usd);
+            self.count("exit");
+            return Ok(StrategyAction::ClosePositions(token.to_string()));
+        }
+
+        if holding {
+            return Ok(StrategyAction::Hold); // Leaders adding to a position aren't chased
+        }
+        if swap.amount_usd < self.min_leader_usd {
+            self.count("below_min");
+            return Ok(StrategyAction::Hold);
+        }
+        let delay_secs = chrono::Utc::now().timestamp() - onchain.timestamp;
+        if delay_secs > self.max_delay_secs {
+            debug!(id = self.id(), token = %token, leader = %swap.wallet, delay_secs, "Leader buy arrived too late to copy.");
+            self.count("too_late");
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd =
+            portfolio.scale_size((swap.amount_usd * self.size_ratio).min(self.max_size_usd));
+        if size_usd <= 0.0 {
+            self.count("no_budget");
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(
+            id = self.id(),
+            token = %token,
+            leader = %swap.wallet,
+            "BUY signal: Copying a {:.0} USD leader buy with {:.0} USD, {}s behind.",
+            swap.amount_usd,
+            size_usd,
+            delay_secs
+        );
+        self.count("entry");
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: token.to_string(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "leader_wallet": swap.wallet,
+                    "leader_buy_usd": swap.amount_usd,
+                    "copy_delay_secs": delay_secs,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None, // Exits follow the leader
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn requires_token_safety(&self) -> bool {
+        true
+    }
+}
+register_strategy!(CopyTrader, "copy_trader");
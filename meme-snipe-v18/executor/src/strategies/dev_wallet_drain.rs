@@ -1,12 +1,15 @@
 use crate::{
     register_strategy,
-    strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction},
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value;
-use shared_models::Side;
+use shared_models::{ParamKind, ParamSchema, Side};
 use std::collections::HashSet;
 use tracing::info; // P-5: Import Side
 
@@ -28,7 +31,14 @@ impl Strategy for DevWalletDrain {
         [EventType::Price].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("dev_balance_threshold_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+    }
+
+    async fn init(&mut self, params: &Value, _metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             dev_balance_threshold_pct: f64,
@@ -42,7 +52,11 @@ impl Strategy for DevWalletDrain {
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        _portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         if let MarketEvent::Price(tick) = event {
             // Simulate: If price drops sharply with very high volume, it could be a dev dump.
             // A real strategy would monitor specific known dev wallet addresses and their outflows.
@@ -0,0 +1,210 @@
+// executor/src/strategies/dip_buyer.rs
+//! Buys sharp drawdowns once the selling exhausts. Each token's ticks over
+//! the last `window_mins` give a rolling high; a token trading at least
+//! `min_drawdown_pct` below it is a dip. It is bought when the volume has
+//! stabilized, the latest 1m volume having fallen to `max_volume_ratio` of
+//! the window's peak, and the price has stopped making new lows, the tick
+//! being above the window's low. Rugs look like dips too, so buys must pass
+//! the token safety checker even on paper.
+//!
+//! The aim is the bounce: a take-profit, a trailing stop and `max_hold_secs`
+//! close the position.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashSet, VecDeque};
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Tick {
+    at: i64,
+    price: f64,
+    volume_usd_1m: f64,
+}
+
+#[derive(Default)]
+struct DipBuyer {
+    window_secs: i64,
+    min_drawdown_pct: f64,
+    max_volume_ratio: f64,
+    size_usd: f64,
+    exit_profile: ExitProfile,
+    ticks: PerTokenState<VecDeque<Tick>>, // Oldest first, trimmed to the window
+    drawdown_gauge: Option<Gauge>,
+}
+
+#[async_trait]
+impl Strategy for DipBuyer {
+    fn id(&self) -> &'static str {
+        "dip_buyer"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("window_mins", ParamKind::Integer)
+            .min(1.0)
+            .required("min_drawdown_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_volume_ratio", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("trailing_stop_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            window_mins: i64,
+            min_drawdown_pct: f64,
+            max_volume_ratio: f64,
+            size_usd: f64,
+            take_profit_pct: f64,
+            trailing_stop_pct: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.window_secs = p.window_mins * 60;
+        self.min_drawdown_pct = p.min_drawdown_pct;
+        self.max_volume_ratio = p.max_volume_ratio;
+        self.size_usd = p.size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: Some(p.trailing_stop_pct),
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.ticks = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.drawdown_gauge = Some(metrics.gauge(
+            "drawdown_pct",
+            "Drawdown from the rolling high, for the token last updated.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with window_mins: {}, min_drawdown_pct: {}, max_volume_ratio: {}",
+            p.window_mins,
+            self.min_drawdown_pct,
+            self.max_volume_ratio
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let tick = match event {
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        if tick.price_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        let window_secs = self.window_secs;
+        let ticks = self
+            .ticks
+            .get_or_insert_with(&tick.token_address, VecDeque::new);
+        // The window excludes the current tick, so its low is the low to hold above
+        while ticks
+            .front()
+            .is_some_and(|t| tick.timestamp - t.at > window_secs)
+        {
+            ticks.pop_front();
+        }
+        let high = ticks.iter().map(|t| t.price).fold(tick.price_usd, f64::max);
+        let low = ticks.iter().map(|t| t.price).fold(f64::INFINITY, f64::min);
+        let peak_volume = ticks.iter().map(|t| t.volume_usd_1m).fold(0.0, f64::max);
+        ticks.push_back(Tick {
+            at: tick.timestamp,
+            price: tick.price_usd,
+            volume_usd_1m: tick.volume_usd_1m,
+        });
+
+        let drawdown_pct = (high - tick.price_usd) / high * 100.0;
+        if let Some(gauge) = &self.drawdown_gauge {
+            gauge.set(drawdown_pct);
+        }
+        if drawdown_pct < self.min_drawdown_pct {
+            return Ok(StrategyAction::Hold);
+        }
+        let volume_ratio = if peak_volume > 0.0 {
+            tick.volume_usd_1m / peak_volume
+        } else {
+            1.0
+        };
+        let stabilized = volume_ratio <= self.max_volume_ratio && tick.price_usd > low;
+        if !stabilized {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %tick.token_address, "BUY signal: Down {:.1}% from the {}m high, volume {:.2}x its peak.", drawdown_pct, window_secs / 60, volume_ratio);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.55,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "drawdown_pct": drawdown_pct,
+                    "rolling_high_usd": high,
+                    "volume_ratio": volume_ratio,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "ticks": self.ticks.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            ticks: Vec<(String, VecDeque<Tick>)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        // Windows are trimmed by time as new ticks arrive
+        self.ticks.restore_from(s.ticks);
+        Ok(())
+    }
+
+    fn requires_token_safety(&self) -> bool {
+        true
+    }
+}
+register_strategy!(DipBuyer, "dip_buyer");
@@ -0,0 +1,338 @@
+// executor/src/strategies/ensemble.rs
+//! Meta-strategy that runs several child strategies and only trades when a
+//! quorum of them agree. Each child's Execute counts as a vote for its side on
+//! that token; votes expire after `window_secs`. Once `quorum` children have
+//! voted the same side within the window, the ensemble emits one Execute with
+//! the agreeing children's mean size and a confidence of their mean confidence
+//! scaled by the share of children that agreed. Multi-leg child orders vote
+//! once per leg.
+
+use crate::register_strategy;
+use crate::strategies::{
+    build_strategy, EventType, MarketEvent, MetricsHandle, PortfolioContext, ShutdownAction,
+    ShutdownReason, Strategy, StrategyAction,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use prometheus::{Counter, CounterVec};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_models::{ChildStrategySpec, OrderDetails, ParamKind, ParamSchema, TradeMode};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::{info, warn};
+
+const FAMILY: &str = "ensemble";
+
+struct Child {
+    family: String,
+    strategy: Box<dyn Strategy>,
+    subscriptions: HashSet<EventType>,
+    timer: Option<(i64, i64)>, // (interval secs, next due unix secs)
+}
+
+struct Vote {
+    child: usize,
+    at: i64,
+    details: OrderDetails,
+    mode: TradeMode,
+}
+
+#[derive(Default)]
+struct Ensemble {
+    quorum: usize,
+    window_secs: i64,
+    children: Vec<Child>,
+    votes: HashMap<String, Vec<Vote>>, // token -> latest vote per child
+    votes_counter: Option<CounterVec>,
+    signals_counter: Option<Counter>,
+}
+
+impl Ensemble {
+    /// Replaces `child`'s vote on the token and returns an Execute if that
+    /// brought a quorum of unexpired votes onto one side.
+    fn record_vote(
+        &mut self,
+        child: usize,
+        at: i64,
+        details: OrderDetails,
+        mode: TradeMode,
+    ) -> StrategyAction {
+        if let Some(counter) = &self.votes_counter {
+            counter
+                .with_label_values(&[&self.children[child].family, &details.side.to_string()])
+                .inc();
+        }
+        let token = details.token_address.clone();
+        let window_secs = self.window_secs;
+        let votes = self.votes.entry(token.clone()).or_default();
+        votes.retain(|v| v.child != child && at - v.at <= window_secs);
+        let side = details.side.clone();
+        votes.push(Vote {
+            child,
+            at,
+            details,
+            mode,
+        });
+
+        let agreeing: Vec<&Vote> = votes.iter().filter(|v| v.details.side == side).collect();
+        if agreeing.len() < self.quorum {
+            return StrategyAction::Hold;
+        }
+
+        let n = agreeing.len() as f64;
+        let mean_confidence = agreeing.iter().map(|v| v.details.confidence).sum::<f64>() / n;
+        let mean_size = agreeing
+            .iter()
+            .map(|v| v.details.suggested_size_usd)
+            .sum::<f64>()
+            / n;
+        let voters: Vec<&str> = agreeing
+            .iter()
+            .map(|v| self.children[v.child].family.as_str())
+            .collect();
+        let mut order = agreeing[0].details.clone();
+        order.suggested_size_usd = mean_size;
+        order.confidence = mean_confidence * n / self.children.len() as f64;
+        order.triggering_features = Some(json!({
+            "ensemble_voters": voters,
+            "ensemble_mean_confidence": mean_confidence,
+        }));
+        let mode = agreeing[0].mode;
+
+        info!(id = FAMILY, token = %token, side = %side, voters = ?voters, "Quorum reached ({}/{}).", agreeing.len(), self.children.len());
+        if let Some(counter) = &self.signals_counter {
+            counter.inc();
+        }
+        // One trade per agreement; the children have to vote again for another
+        self.votes.remove(&token);
+        StrategyAction::Execute(order, mode)
+    }
+
+    fn expire_votes(&mut self, now: i64) {
+        let window_secs = self.window_secs;
+        self.votes.retain(|_, votes| {
+            votes.retain(|v| now - v.at <= window_secs);
+            !votes.is_empty()
+        });
+    }
+}
+
+/// Each order a child asks for is one vote; multi-leg orders vote once per leg.
+fn votes_of(action: StrategyAction) -> Vec<(OrderDetails, TradeMode)> {
+    match action {
+        StrategyAction::Execute(details, mode) => vec![(details, mode)],
+        StrategyAction::ExecuteMany(legs, _, mode) => {
+            legs.into_iter().map(|leg| (leg, mode)).collect()
+        }
+        // Exits are the child's own business; the ensemble's positions are closed by its stops
+        StrategyAction::ClosePositions(_) | StrategyAction::Hold => Vec::new(),
+    }
+}
+
+#[async_trait]
+impl Strategy for Ensemble {
+    fn id(&self) -> &'static str {
+        FAMILY
+    }
+
+    /// Union of the children's subscriptions; empty until `init()`.
+    fn subscriptions(&self) -> HashSet<EventType> {
+        self.children
+            .iter()
+            .flat_map(|c| c.subscriptions.iter().cloned())
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("children", ParamKind::ChildSpecs)
+            .min(2.0)
+            .required("quorum", ParamKind::Integer)
+            .min(1.0)
+            .required("window_secs", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            children: Vec<ChildStrategySpec>,
+            quorum: usize,
+            window_secs: i64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        if p.quorum > p.children.len() {
+            return Err(anyhow!(
+                "quorum {} exceeds the number of children ({})",
+                p.quorum,
+                p.children.len()
+            ));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut children = Vec::with_capacity(p.children.len());
+        for (i, spec) in p.children.iter().enumerate() {
+            if spec.family == FAMILY {
+                return Err(anyhow!("ensembles cannot be nested"));
+            }
+            let mut strategy = build_strategy(&spec.family)
+                .ok_or_else(|| anyhow!("unknown child family {}", spec.family))?;
+            strategy
+                .params_schema()
+                .validate(&spec.params)
+                .map_err(|e| anyhow!("child {}: {}", spec.family, e))?;
+            strategy
+                .init(
+                    &spec.params,
+                    &metrics.child(&format!("{}_{}", i, spec.family)),
+                )
+                .await?;
+            let timer = strategy.timer_interval().map(|interval| {
+                let secs = interval.as_secs().max(1) as i64;
+                (secs, now + secs)
+            });
+            children.push(Child {
+                family: spec.family.clone(),
+                subscriptions: strategy.subscriptions(),
+                strategy,
+                timer,
+            });
+        }
+
+        self.quorum = p.quorum;
+        self.window_secs = p.window_secs;
+        self.children = children;
+        self.votes.clear();
+        self.votes_counter = Some(metrics.counter_vec(
+            "votes_total",
+            "Child Execute votes by child family and side.",
+            &["child", "side"],
+        )?);
+        self.signals_counter = Some(metrics.counter(
+            "quorum_signals_total",
+            "Executes emitted after a quorum agreed.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} children, quorum: {}, window_secs: {}",
+            self.children.len(),
+            self.quorum,
+            self.window_secs
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let event_type = event.get_type();
+        let at = event.timestamp();
+        let mut action = StrategyAction::Hold;
+        for i in 0..self.children.len() {
+            if !self.children[i].subscriptions.contains(&event_type) {
+                continue;
+            }
+            // Children see the ensemble's own positions and budget. A failing
+            // child abstains rather than taking the others down with it
+            match self.children[i].strategy.on_event(event, portfolio).await {
+                Ok(child_action) => {
+                    for (details, mode) in votes_of(child_action) {
+                        let result = self.record_vote(i, at, details, mode);
+                        if let StrategyAction::Execute(..) = result {
+                            action = result;
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(id = FAMILY, child = %self.children[i].family, error = %e, "Child strategy failed on event.")
+                }
+            }
+        }
+        Ok(action)
+    }
+
+    fn timer_interval(&self) -> Option<Duration> {
+        // Fast enough for the most frequent child timer and for vote expiry
+        let secs = self
+            .children
+            .iter()
+            .filter_map(|c| c.timer.map(|(secs, _)| secs))
+            .min()
+            .map_or(self.window_secs, |secs| secs.min(self.window_secs));
+        Some(Duration::from_secs(secs.max(1) as u64))
+    }
+
+    fn requires_token_safety(&self) -> bool {
+        self.children
+            .iter()
+            .any(|c| c.strategy.requires_token_safety())
+    }
+
+    async fn on_timer(&mut self, now: i64) -> Result<StrategyAction> {
+        let mut action = StrategyAction::Hold;
+        for i in 0..self.children.len() {
+            let (secs, due) = match self.children[i].timer {
+                Some(timer) => timer,
+                None => continue,
+            };
+            if now < due {
+                continue;
+            }
+            self.children[i].timer = Some((secs, now + secs));
+            match self.children[i].strategy.on_timer(now).await {
+                Ok(child_action) => {
+                    for (details, mode) in votes_of(child_action) {
+                        let result = self.record_vote(i, now, details, mode);
+                        if let StrategyAction::Execute(..) = result {
+                            action = result;
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(id = FAMILY, child = %self.children[i].family, error = %e, "Child strategy failed on timer.")
+                }
+            }
+        }
+        self.expire_votes(now);
+        Ok(action)
+    }
+
+    async fn on_shutdown(&mut self, reason: ShutdownReason) -> Result<ShutdownAction> {
+        let mut action = ShutdownAction::KeepPositions;
+        for child in &mut self.children {
+            if child.strategy.on_shutdown(reason).await? == ShutdownAction::ClosePositions {
+                action = ShutdownAction::ClosePositions;
+            }
+        }
+        Ok(action)
+    }
+
+    fn snapshot(&self) -> Value {
+        Value::Array(
+            self.children
+                .iter()
+                .map(|c| json!({ "family": c.family, "state": c.strategy.snapshot() }))
+                .collect(),
+        )
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            family: String,
+            state: Value,
+        }
+        let saved: Vec<S> = serde_json::from_value(snapshot.clone())?;
+        // Children are matched by position; skip any whose family changed since
+        for (child, s) in self.children.iter_mut().zip(saved) {
+            if child.family == s.family && !s.state.is_null() {
+                child.strategy.restore(&s.state)?;
+            }
+        }
+        Ok(())
+    }
+}
+register_strategy!(Ensemble, "ensemble");
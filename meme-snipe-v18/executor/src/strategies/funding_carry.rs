@@ -0,0 +1,181 @@
+// executor/src/strategies/funding_carry.rs
+//! Funding-rate carry. Funding is annualized as rate x `periods_per_year`; once
+//! it exceeds `entry_apr` the strategy shorts the perp (collecting funding on
+//! Drift) and buys the same size of spot through Jupiter so price moves net
+//! out, both legs or neither. The carry is held until annualized funding
+//! falls back below `exit_apr`, then both legs are closed. Whether a carry is
+//! on comes from the strategy's open positions, so there is no state to keep.
+//!
+//! Negative funding would call for the mirror trade (long perp, short spot),
+//! but the executor has no spot-short path: Shorts go to Drift and spot orders
+//! only buy. Negative funding is counted and skipped.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction, TradeMode,
+    },
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use prometheus::{CounterVec, Gauge};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_models::{LegPolicy, OrderType, ParamKind, ParamSchema, Side, Venue};
+use std::collections::HashSet;
+use tracing::info;
+
+#[derive(Default)]
+struct FundingCarry {
+    entry_apr: f64,
+    exit_apr: f64,
+    periods_per_year: f64,
+    size_usd: f64,
+    funding_apr_gauge: Option<Gauge>,
+    signals_counter: Option<CounterVec>,
+}
+
+impl FundingCarry {
+    fn leg(
+        &self,
+        token_address: &str,
+        size_usd: f64,
+        side: Side,
+        venue: Option<Venue>,
+        apr: f64,
+    ) -> OrderDetails {
+        OrderDetails {
+            token_address: token_address.to_string(),
+            suggested_size_usd: size_usd,
+            confidence: 0.8,
+            side,
+            limit_price: None,
+            triggering_features: Some(json!({ "funding_apr": apr, "entry_apr": self.entry_apr })),
+            order_type: OrderType::Market,
+            venue,
+            exit_profile: None, // Held until funding normalizes, not by price
+        }
+    }
+
+    fn count(&self, signal: &str) {
+        if let Some(counter) = &self.signals_counter {
+            counter.with_label_values(&[signal]).inc();
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for FundingCarry {
+    fn id(&self) -> &'static str {
+        "funding_carry"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Funding].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("entry_apr", ParamKind::Number)
+            .min(0.0)
+            .required("exit_apr", ParamKind::Number)
+            .min(0.0)
+            .required("periods_per_year", ParamKind::Number)
+            .min(1.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            entry_apr: f64,
+            exit_apr: f64,
+            periods_per_year: f64,
+            size_usd: f64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        if p.exit_apr >= p.entry_apr {
+            return Err(anyhow!(
+                "exit_apr ({}) must be below entry_apr ({})",
+                p.exit_apr,
+                p.entry_apr
+            ));
+        }
+        self.entry_apr = p.entry_apr;
+        self.exit_apr = p.exit_apr;
+        self.periods_per_year = p.periods_per_year;
+        self.size_usd = p.size_usd;
+        self.funding_apr_gauge = Some(metrics.gauge(
+            "funding_apr",
+            "Annualized funding rate, for the token last updated.",
+        )?);
+        self.signals_counter = Some(metrics.counter_vec(
+            "signals_total",
+            "Carry entries, exits and skipped negative-funding opportunities.",
+            &["signal"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with entry_apr: {}, exit_apr: {}, periods_per_year: {}",
+            self.entry_apr,
+            self.exit_apr,
+            self.periods_per_year
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let funding = match event {
+            MarketEvent::Funding(funding) => funding,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let token = funding.token_address.as_str();
+        let apr = funding.funding_rate_pct * self.periods_per_year;
+        if let Some(gauge) = &self.funding_apr_gauge {
+            gauge.set(apr);
+        }
+
+        // The perp leg is the strategy's only short, so it marks an open carry
+        if portfolio.exposure_to(token, &Side::Short) > 0.0 {
+            if apr < self.exit_apr {
+                info!(id = self.id(), token = %token, "EXIT signal: Funding normalized to {:.1}% APR.", apr * 100.0);
+                self.count("exit");
+                return Ok(StrategyAction::ClosePositions(token.to_string()));
+            }
+            return Ok(StrategyAction::Hold);
+        }
+
+        if apr < -self.entry_apr {
+            self.count("skipped_negative");
+            return Ok(StrategyAction::Hold);
+        }
+        if apr <= self.entry_apr {
+            return Ok(StrategyAction::Hold);
+        }
+        // Each leg is a full-size position, so the pair needs twice the budget
+        let size_usd = portfolio.scale_size(self.size_usd * 2.0) / 2.0;
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %token, "SHORT PERP/LONG SPOT signal: Funding {:.1}% APR above {:.1}%.", apr * 100.0, self.entry_apr * 100.0);
+        self.count("entry");
+        Ok(StrategyAction::ExecuteMany(
+            vec![
+                // Spot first: the less liquid leg is the one more likely to fail
+                self.leg(token, size_usd, Side::Long, Some(Venue::Jupiter), apr),
+                // Shorts are opened on Drift perps
+                self.leg(token, size_usd, Side::Short, None, apr),
+            ],
+            LegPolicy::AllOrNothing,
+            TradeMode::Paper,
+        ))
+    }
+}
+register_strategy!(FundingCarry, "funding_carry");
@@ -0,0 +1,258 @@
+// executor/src/strategies/graduation_sniper.rs
+//! Positions in pump.fun tokens just before they graduate to Raydium.
+//! OnChain events of type "CurveProgress" carry a token's bonding-curve fill,
+//! `{ progress_pct }`. A token whose curve is between `entry_progress_pct` and
+//! `max_entry_progress_pct` and filling at least `min_progress_per_min` is
+//! bought once on the curve. Migration, seen as the token's Raydium
+//! TokenLaunch or its curve reaching 100%, tends to bring a burst of
+//! volatility: the position rides it under a take-profit and trailing stop,
+//! and is closed `post_migration_hold_secs` after migration if still open.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::CounterVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, Launchpad, OrderType, ParamKind, ParamSchema, Side, Venue};
+use std::collections::HashSet;
+use tracing::{debug, info};
+
+/// Payload of an OnChain "CurveProgress" event.
+#[derive(Debug, Clone, Deserialize)]
+struct CurveProgress {
+    progress_pct: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CurveState {
+    progress_pct: f64,
+    at: i64,
+    entered: bool,
+    migrated_at: Option<i64>,
+}
+
+#[derive(Default)]
+struct GraduationSniper {
+    entry_progress_pct: f64,
+    max_entry_progress_pct: f64,
+    min_progress_per_min: f64,
+    size_usd: f64,
+    post_migration_hold_secs: i64,
+    exit_profile: ExitProfile,
+    curves: PerTokenState<CurveState>,
+    signals_counter: Option<CounterVec>,
+}
+
+impl GraduationSniper {
+    fn count(&self, signal: &str) {
+        if let Some(counter) = &self.signals_counter {
+            counter.with_label_values(&[signal]).inc();
+        }
+    }
+
+    fn mark_migrated(&mut self, token: &str, at: i64) {
+        if let Some(curve) = self.curves.get_mut(token) {
+            if curve.migrated_at.is_none() {
+                curve.migrated_at = Some(at);
+                info!(id = self.id(), token = %token, "Token migrated off the bonding curve.");
+                self.count("migrated");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for GraduationSniper {
+    fn id(&self) -> &'static str {
+        "graduation_sniper"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Raydium launches mark migrations; price ticks time the post-migration exit
+        [EventType::OnChain, EventType::TokenLaunch, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("entry_progress_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_entry_progress_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("min_progress_per_min", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("trailing_stop_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("post_migration_hold_secs", ParamKind::Integer)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            entry_progress_pct: f64,
+            max_entry_progress_pct: f64,
+            min_progress_per_min: f64,
+            size_usd: f64,
+            take_profit_pct: f64,
+            trailing_stop_pct: f64,
+            max_hold_secs: i64,
+            post_migration_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.entry_progress_pct = p.entry_progress_pct;
+        self.max_entry_progress_pct = p.max_entry_progress_pct;
+        self.min_progress_per_min = p.min_progress_per_min;
+        self.size_usd = p.size_usd;
+        self.post_migration_hold_secs = p.post_migration_hold_secs;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: Some(p.trailing_stop_pct),
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.curves = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.signals_counter = Some(metrics.counter_vec(
+            "signals_total",
+            "Curve entries, migrations seen and post-migration exits.",
+            &["signal"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with entry_progress_pct: {}, max_entry_progress_pct: {}, min_progress_per_min: {}, post_migration_hold_secs: {}",
+            self.entry_progress_pct,
+            self.max_entry_progress_pct,
+            self.min_progress_per_min,
+            self.post_migration_hold_secs
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let onchain = match event {
+            MarketEvent::TokenLaunch(launch) if launch.launchpad == Launchpad::Raydium => {
+                self.mark_migrated(&launch.token_address, launch.timestamp);
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::Price(tick) => {
+                let hold_secs = self.post_migration_hold_secs;
+                let curve = match self.curves.get_mut(&tick.token_address) {
+                    Some(curve) if curve.entered => curve,
+                    _ => return Ok(StrategyAction::Hold),
+                };
+                let due = curve
+                    .migrated_at
+                    .is_some_and(|at| tick.timestamp - at >= hold_secs);
+                if !due || portfolio.exposure_to(&tick.token_address, &Side::Long) <= 0.0 {
+                    return Ok(StrategyAction::Hold);
+                }
+                curve.entered = false; // One exit request per position
+                info!(id = self.id(), token = %tick.token_address, "EXIT signal: Post-migration hold elapsed.");
+                self.count("exit");
+                return Ok(StrategyAction::ClosePositions(tick.token_address.clone()));
+            }
+            MarketEvent::OnChain(onchain) if onchain.event_type == "CurveProgress" => onchain,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let progress: CurveProgress = match serde_json::from_value(onchain.data.clone()) {
+            Ok(progress) => progress,
+            Err(e) => {
+                debug!(id = self.id(), error = %e, "Ignoring malformed CurveProgress event.");
+                return Ok(StrategyAction::Hold);
+            }
+        };
+        let token = onchain.token_address.as_str();
+        let previous = self.curves.get(token).cloned();
+        let curve = self.curves.get_or_insert_with(token, CurveState::default);
+        curve.progress_pct = progress.progress_pct;
+        curve.at = onchain.timestamp;
+        if progress.progress_pct >= 100.0 {
+            self.mark_migrated(token, onchain.timestamp);
+            return Ok(StrategyAction::Hold);
+        }
+
+        // Needs a previous reading to measure how fast the curve is filling
+        let previous = match previous {
+            Some(previous)
+                if !previous.entered
+                    && previous.migrated_at.is_none()
+                    && onchain.timestamp > previous.at =>
+            {
+                previous
+            }
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let minutes = (onchain.timestamp - previous.at) as f64 / 60.0;
+        let progress_per_min = (progress.progress_pct - previous.progress_pct) / minutes;
+        if progress.progress_pct < self.entry_progress_pct
+            || progress.progress_pct > self.max_entry_progress_pct
+            || progress_per_min < self.min_progress_per_min
+        {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        curve.entered = true;
+        info!(id = self.id(), token = %token, "BUY signal: Curve {:.1}% full, filling {:.2}%/min.", progress.progress_pct, progress_per_min);
+        self.count("entry");
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: token.to_string(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "curve_progress_pct": progress.progress_pct,
+                    "curve_progress_per_min": progress_per_min,
+                })),
+                order_type: OrderType::Market,
+                venue: Some(Venue::PumpFun), // Still on the curve
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "curves": self.curves.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            curves: Vec<(String, CurveState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        self.curves.restore_from(s.curves);
+        Ok(())
+    }
+}
+register_strategy!(GraduationSniper, "graduation_sniper");
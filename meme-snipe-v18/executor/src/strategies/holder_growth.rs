@@ -0,0 +1,245 @@
+// executor/src/strategies/holder_growth.rs
+//! Buys tokens whose holder base is growing faster and faster. OnChain events
+//! of type "HolderDelta" carry a token's unique holder count,
+//! `{ holder_count }`; consecutive counts give a growth rate in holders per
+//! minute. A signal needs that rate to have risen `accel_periods` times in a
+//! row and be at least `min_holder_growth_per_min`, confirmed by the latest
+//! 1m volume being `volume_multiplier` times its mean over the previous
+//! `volume_window` price ticks.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashSet, VecDeque};
+use tracing::{debug, info};
+
+/// Payload of an OnChain "HolderDelta" event.
+#[derive(Debug, Clone, Deserialize)]
+struct HolderDelta {
+    holder_count: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TokenState {
+    holders: VecDeque<(i64, u64)>, // (timestamp, unique holders)
+    volumes: VecDeque<f64>,        // 1m volume per price tick, latest last
+}
+
+#[derive(Default)]
+struct HolderGrowth {
+    min_holder_growth_per_min: f64,
+    accel_periods: usize,
+    volume_window: usize,
+    volume_multiplier: f64,
+    size_usd: f64,
+    tokens: PerTokenState<TokenState>,
+    growth_gauge: Option<Gauge>,
+}
+
+/// Holders gained per minute between consecutive counts, oldest first.
+fn growth_rates(holders: &VecDeque<(i64, u64)>) -> Vec<f64> {
+    holders
+        .iter()
+        .zip(holders.iter().skip(1))
+        .map(|((t0, c0), (t1, c1))| {
+            let minutes = ((t1 - t0) as f64 / 60.0).max(1.0 / 60.0);
+            (*c1 as f64 - *c0 as f64) / minutes
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Strategy for HolderGrowth {
+    fn id(&self) -> &'static str {
+        "holder_growth"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Price ticks supply the volume confirmation
+        [EventType::OnChain, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("min_holder_growth_per_min", ParamKind::Number)
+            .min(0.0)
+            .required("accel_periods", ParamKind::Integer)
+            .min(1.0)
+            .required("volume_window", ParamKind::Integer)
+            .min(1.0)
+            .required("volume_multiplier", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            min_holder_growth_per_min: f64,
+            accel_periods: usize,
+            volume_window: usize,
+            volume_multiplier: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.min_holder_growth_per_min = p.min_holder_growth_per_min;
+        self.accel_periods = p.accel_periods;
+        self.volume_window = p.volume_window;
+        self.volume_multiplier = p.volume_multiplier;
+        self.size_usd = p.size_usd;
+        self.tokens = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.growth_gauge = Some(metrics.gauge(
+            "holder_growth_per_min",
+            "Unique holders gained per minute, for the token last updated.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with min_holder_growth_per_min: {}, accel_periods: {}, volume_window: {}, volume_multiplier: {}",
+            self.min_holder_growth_per_min,
+            self.accel_periods,
+            self.volume_window,
+            self.volume_multiplier
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        // Rising accel_periods times takes accel_periods + 1 rates, so one more count
+        let holder_len = self.accel_periods + 2;
+        let volume_len = self.volume_window + 1;
+        let onchain = match event {
+            MarketEvent::Price(tick) => {
+                // Only tokens with holder data are followed
+                if let Some(state) = self.tokens.get_mut(&tick.token_address) {
+                    if state.volumes.len() == volume_len {
+                        state.volumes.pop_front();
+                    }
+                    state.volumes.push_back(tick.volume_usd_1m);
+                }
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::OnChain(onchain) if onchain.event_type == "HolderDelta" => onchain,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let delta: HolderDelta = match serde_json::from_value(onchain.data.clone()) {
+            Ok(delta) => delta,
+            Err(e) => {
+                debug!(id = self.id(), error = %e, "Ignoring malformed HolderDelta event.");
+                return Ok(StrategyAction::Hold);
+            }
+        };
+
+        let state = self
+            .tokens
+            .get_or_insert_with(&onchain.token_address, TokenState::default);
+        if state.holders.len() == holder_len {
+            state.holders.pop_front();
+        }
+        state
+            .holders
+            .push_back((onchain.timestamp, delta.holder_count));
+        let rates = growth_rates(&state.holders);
+        let latest_rate = match rates.last() {
+            Some(rate) => *rate,
+            None => return Ok(StrategyAction::Hold),
+        };
+        if let Some(gauge) = &self.growth_gauge {
+            gauge.set(latest_rate);
+        }
+        let accelerating =
+            rates.len() == self.accel_periods + 1 && rates.windows(2).all(|pair| pair[1] > pair[0]);
+        if !accelerating || latest_rate < self.min_holder_growth_per_min {
+            return Ok(StrategyAction::Hold);
+        }
+
+        if state.volumes.len() < volume_len {
+            return Ok(StrategyAction::Hold);
+        }
+        let latest_volume = *state.volumes.back().expect("volumes are non-empty");
+        let mean_volume =
+            state.volumes.iter().rev().skip(1).sum::<f64>() / self.volume_window as f64;
+        if mean_volume <= 0.0 || latest_volume < mean_volume * self.volume_multiplier {
+            return Ok(StrategyAction::Hold);
+        }
+
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&onchain.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        info!(
+            id = self.id(),
+            token = %onchain.token_address,
+            "BUY signal: Holder growth accelerating to {:.1}/min over {} periods, volume {:.1}x its mean.",
+            latest_rate,
+            self.accel_periods,
+            latest_volume / mean_volume
+        );
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: onchain.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.7,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "holder_count": delta.holder_count,
+                    "holder_growth_per_min": latest_rate,
+                    "holder_growth_rates": rates,
+                    "volume_ratio": latest_volume / mean_volume,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "tokens": self.tokens.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            tokens: Vec<(String, TokenState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let holder_len = self.accel_periods + 2;
+        let volume_len = self.volume_window + 1;
+        // The windows may have shrunk since the snapshot was taken
+        self.tokens
+            .restore_from(s.tokens.into_iter().map(|(token, state)| {
+                (
+                    token,
+                    TokenState {
+                        holders: newest(state.holders, holder_len),
+                        volumes: newest(state.volumes, volume_len),
+                    },
+                )
+            }));
+        Ok(())
+    }
+}
+register_strategy!(HolderGrowth, "holder_growth");
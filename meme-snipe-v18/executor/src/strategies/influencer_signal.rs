@@ -0,0 +1,246 @@
+// executor/src/strategies/influencer_signal.rs
+//! Early entries on calls from influential accounts. Each bullish
+//! SocialMention adds its author's influence times its sentiment to the
+//! token's call score. Influence comes from `influence_scores` for listed
+//! accounts, otherwise from the social consumer's follower-based score, and
+//! mentions by accounts under `min_influence` are ignored. The score decays
+//! with a `half_life_secs` half-life.
+//!
+//! The first price tick after a call run starts is its reference price. Once
+//! the score reaches `min_call_score` the token is bought, as long as the
+//! price is within `max_price_move_pct` of the reference, meaning the market
+//! hasn't reacted yet, and the run began less than `max_call_age_secs` ago.
+//! Calls older than that are stale: the next one starts a new run.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CallRun {
+    score: f64,      // Decayed sum of influence x sentiment
+    updated_at: i64, // When the score was last decayed
+    started_at: i64,
+    reference_price: Option<f64>,
+    entered: bool,
+}
+
+impl CallRun {
+    fn decay_to(&mut self, now: i64, half_life_secs: f64) {
+        let elapsed = (now - self.updated_at).max(0) as f64;
+        self.score *= 0.5f64.powf(elapsed / half_life_secs);
+        self.updated_at = self.updated_at.max(now);
+    }
+}
+
+#[derive(Default)]
+struct InfluencerSignal {
+    influence_scores: HashMap<String, f64>,
+    min_influence: f64,
+    half_life_secs: f64,
+    min_call_score: f64,
+    max_price_move_pct: f64,
+    max_call_age_secs: i64,
+    size_usd: f64,
+    exit_profile: ExitProfile,
+    runs: PerTokenState<CallRun>,
+    call_score_gauge: Option<Gauge>,
+}
+
+#[async_trait]
+impl Strategy for InfluencerSignal {
+    fn id(&self) -> &'static str {
+        "influencer_signal"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Social, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .optional("influence_scores", ParamKind::NumberMap)
+            .min(0.0)
+            .max(1.0)
+            .required("min_influence", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("half_life_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_call_score", ParamKind::Number)
+            .min(0.0)
+            .required("max_price_move_pct", ParamKind::Number)
+            .min(0.0)
+            .required("max_call_age_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("trailing_stop_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            #[serde(default)]
+            influence_scores: HashMap<String, f64>,
+            min_influence: f64,
+            half_life_secs: i64,
+            min_call_score: f64,
+            max_price_move_pct: f64,
+            max_call_age_secs: i64,
+            size_usd: f64,
+            take_profit_pct: f64,
+            trailing_stop_pct: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.influence_scores = p.influence_scores;
+        self.min_influence = p.min_influence;
+        self.half_life_secs = p.half_life_secs as f64;
+        self.min_call_score = p.min_call_score;
+        self.max_price_move_pct = p.max_price_move_pct;
+        self.max_call_age_secs = p.max_call_age_secs;
+        self.size_usd = p.size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: Some(p.trailing_stop_pct),
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.runs = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.call_score_gauge = Some(metrics.gauge(
+            "call_score",
+            "Decayed influence-weighted call score, for the token last updated.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} scored accounts, min_influence: {}, half_life_secs: {}, min_call_score: {}, max_price_move_pct: {}",
+            self.influence_scores.len(),
+            self.min_influence,
+            self.half_life_secs,
+            self.min_call_score,
+            self.max_price_move_pct
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let half_life_secs = self.half_life_secs;
+        let max_call_age_secs = self.max_call_age_secs;
+        let tick = match event {
+            MarketEvent::Social(mention) => {
+                // Configured scores override the consumer's
+                let influence = mention
+                    .author
+                    .as_ref()
+                    .and_then(|author| self.influence_scores.get(author).copied())
+                    .or(mention.author_influence)
+                    .unwrap_or(0.0);
+                if influence < self.min_influence || mention.sentiment <= 0.0 {
+                    return Ok(StrategyAction::Hold);
+                }
+                let run = self
+                    .runs
+                    .get_or_insert_with(&mention.token_address, CallRun::default);
+                if mention.timestamp - run.started_at > max_call_age_secs {
+                    *run = CallRun {
+                        updated_at: mention.timestamp,
+                        started_at: mention.timestamp,
+                        ..CallRun::default()
+                    };
+                }
+                run.decay_to(mention.timestamp, half_life_secs);
+                run.score += influence * mention.sentiment.min(1.0);
+                if let Some(gauge) = &self.call_score_gauge {
+                    gauge.set(run.score);
+                }
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+
+        // Only tokens with a live call are followed
+        let run = match self.runs.get_mut(&tick.token_address) {
+            Some(run) if !run.entered => run,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        if tick.price_usd <= 0.0 || tick.timestamp - run.started_at > max_call_age_secs {
+            return Ok(StrategyAction::Hold);
+        }
+        let reference_price = *run.reference_price.get_or_insert(tick.price_usd);
+        run.decay_to(tick.timestamp, half_life_secs);
+        let move_pct = (tick.price_usd - reference_price) / reference_price * 100.0;
+        if run.score < self.min_call_score || move_pct > self.max_price_move_pct {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        run.entered = true;
+        let (call_score, call_age_secs) = (run.score, tick.timestamp - run.started_at);
+        info!(id = self.id(), token = %tick.token_address, "BUY signal: Call score {:.2} after {}s, price moved {:.2}%.", call_score, call_age_secs, move_pct);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "call_score": call_score,
+                    "call_age_secs": call_age_secs,
+                    "price_move_pct": move_pct,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "runs": self.runs.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            runs: Vec<(String, CallRun)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        self.runs.restore_from(s.runs);
+        Ok(())
+    }
+}
+register_strategy!(InfluencerSignal, "influencer_signal");
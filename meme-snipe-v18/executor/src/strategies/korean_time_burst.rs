@@ -1,13 +1,15 @@
 use crate::{
     register_strategy,
-    strategies::{MarketEvent, OrderDetails, Strategy, StrategyAction},
+    strategies::{
+        MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy, StrategyAction,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{Timelike, Utc};
 use serde::Deserialize;
 use serde_json::Value;
-use shared_models::{default_trade_mode, EventType, Side, TradeMode};
+use shared_models::{default_trade_mode, EventType, ParamKind, ParamSchema, Side, TradeMode};
 use std::collections::HashSet;
 use tracing::info;
 
@@ -27,7 +29,13 @@ impl Strategy for KoreanTimeBurst {
         [EventType::Price].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("volume_multiplier_threshold", ParamKind::Number)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, _metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             volume_multiplier_threshold: f64,
@@ -41,7 +49,11 @@ impl Strategy for KoreanTimeBurst {
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        _portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         if let MarketEvent::Price(tick) = event {
             let now = Utc::now().with_timezone(&chrono_tz::Asia::Seoul);
             let hour = now.hour();
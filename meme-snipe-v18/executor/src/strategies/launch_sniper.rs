@@ -0,0 +1,291 @@
+// executor/src/strategies/launch_sniper.rs
+//! Buys new tokens within the first seconds of their launch. A launch is
+//! sniped only if it is at most `max_launch_age_secs` old, has at least
+//! `min_initial_liquidity_usd` in its pool, has its LP locked (when
+//! `require_lp_lock`), and its creator passes the reputation filters: not
+//! blocked, not a serial launcher (more than `max_creator_launches` within
+//! `creator_window_secs`), and none of their earlier tokens had most of their
+//! liquidity pulled. Each launch is bought once, at `size_pct_of_liquidity` of
+//! its pool capped at `max_size_usd`, with a fast take-profit exit profile.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::CounterVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{
+    ExitProfile, Launchpad, LiquidityAction, OrderType, ParamKind, ParamSchema, Side, TokenLaunch,
+    Venue,
+};
+use std::collections::{HashSet, VecDeque};
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CreatorRecord {
+    launches: VecDeque<i64>, // Launch timestamps within the window
+    rugged: bool,            // One of their tokens had most of its liquidity pulled
+}
+
+#[derive(Default)]
+struct LaunchSniper {
+    max_launch_age_secs: i64,
+    min_initial_liquidity_usd: f64,
+    require_lp_lock: bool,
+    blocked_creators: HashSet<String>,
+    max_creator_launches: usize,
+    creator_window_secs: i64,
+    size_pct_of_liquidity: f64,
+    max_size_usd: f64,
+    exit_profile: ExitProfile,
+    creators: PerTokenState<CreatorRecord>, // Keyed by creator wallet
+    token_creators: PerTokenState<String>,  // Launched token -> creator, to attribute rugs
+    sniped: PerTokenState<()>,              // Launches already bought
+    launches_counter: Option<CounterVec>,
+}
+
+impl LaunchSniper {
+    /// Why a launch fails the filters, or `None` if it passes.
+    fn rejection(&self, launch: &TokenLaunch, now: i64) -> Option<&'static str> {
+        if now - launch.timestamp > self.max_launch_age_secs {
+            return Some("too_old");
+        }
+        if launch.initial_liquidity_usd < self.min_initial_liquidity_usd {
+            return Some("low_liquidity");
+        }
+        if self.require_lp_lock && !launch.lp_locked {
+            return Some("lp_unlocked");
+        }
+        if self.blocked_creators.contains(&launch.creator_wallet) {
+            return Some("creator_blocked");
+        }
+        if let Some(record) = self.creators.get(&launch.creator_wallet) {
+            if record.rugged {
+                return Some("creator_rugged");
+            }
+            let recent = record
+                .launches
+                .iter()
+                .filter(|at| launch.timestamp - **at <= self.creator_window_secs)
+                .count();
+            if recent >= self.max_creator_launches {
+                return Some("serial_launcher");
+            }
+        }
+        if self.sniped.get(&launch.token_address).is_some() {
+            return Some("already_sniped");
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl Strategy for LaunchSniper {
+    fn id(&self) -> &'static str {
+        "launch_sniper"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Liquidity events flag creators whose tokens get rugged
+        [EventType::TokenLaunch, EventType::LiquidityPool]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("max_launch_age_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_initial_liquidity_usd", ParamKind::Number)
+            .min(0.0)
+            .required("require_lp_lock", ParamKind::Boolean)
+            .optional("blocked_creators", ParamKind::StringList)
+            .required("max_creator_launches", ParamKind::Integer)
+            .min(1.0)
+            .required("creator_window_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("size_pct_of_liquidity", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("trailing_stop_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            max_launch_age_secs: i64,
+            min_initial_liquidity_usd: f64,
+            require_lp_lock: bool,
+            #[serde(default)]
+            blocked_creators: Vec<String>,
+            max_creator_launches: usize,
+            creator_window_secs: i64,
+            size_pct_of_liquidity: f64,
+            max_size_usd: f64,
+            take_profit_pct: f64,
+            trailing_stop_pct: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        let max_tokens = p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        self.max_launch_age_secs = p.max_launch_age_secs;
+        self.min_initial_liquidity_usd = p.min_initial_liquidity_usd;
+        self.require_lp_lock = p.require_lp_lock;
+        self.blocked_creators = p.blocked_creators.into_iter().collect();
+        self.max_creator_launches = p.max_creator_launches;
+        self.creator_window_secs = p.creator_window_secs;
+        self.size_pct_of_liquidity = p.size_pct_of_liquidity;
+        self.max_size_usd = p.max_size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: Some(p.trailing_stop_pct),
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.creators = PerTokenState::new(max_tokens);
+        self.token_creators = PerTokenState::new(max_tokens);
+        self.sniped = PerTokenState::new(max_tokens);
+        self.launches_counter = Some(metrics.counter_vec(
+            "launches_total",
+            "Launches seen, by outcome (sniped or the filter that rejected them).",
+            &["outcome"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with max_launch_age_secs: {}, min_initial_liquidity_usd: {}, require_lp_lock: {}, max_size_usd: {}",
+            self.max_launch_age_secs,
+            self.min_initial_liquidity_usd,
+            self.require_lp_lock,
+            self.max_size_usd
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let launch = match event {
+            MarketEvent::LiquidityPool(lp) => {
+                // More than half the pool pulled out: count it against the creator
+                if lp.action == LiquidityAction::Remove && lp.amount_usd > lp.pool_liquidity_usd {
+                    if let Some(creator) = self.token_creators.get(&lp.token_address).cloned() {
+                        info!(id = self.id(), token = %lp.token_address, creator = %creator, "Creator's token had its liquidity pulled.");
+                        self.creators
+                            .get_or_insert_with(&creator, CreatorRecord::default)
+                            .rugged = true;
+                    }
+                }
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::TokenLaunch(launch) => launch,
+            _ => return Ok(StrategyAction::Hold),
+        };
+
+        // Check before this launch counts towards the creator's history
+        let now = chrono::Utc::now().timestamp();
+        let rejection = self.rejection(launch, now);
+
+        let window_secs = self.creator_window_secs;
+        let record = self
+            .creators
+            .get_or_insert_with(&launch.creator_wallet, CreatorRecord::default);
+        record
+            .launches
+            .retain(|at| launch.timestamp - at <= window_secs);
+        record.launches.push_back(launch.timestamp);
+        self.token_creators
+            .insert(&launch.token_address, launch.creator_wallet.clone());
+
+        let size_usd = portfolio.scale_size(
+            (launch.initial_liquidity_usd * self.size_pct_of_liquidity / 100.0)
+                .min(self.max_size_usd),
+        );
+        let outcome = match rejection {
+            Some(reason) => reason,
+            None if size_usd <= 0.0 => "no_budget",
+            None => "sniped",
+        };
+        if let Some(counter) = &self.launches_counter {
+            counter.with_label_values(&[outcome]).inc();
+        }
+        if outcome != "sniped" {
+            debug!(id = self.id(), token = %launch.token_address, outcome, "Launch skipped.");
+            return Ok(StrategyAction::Hold);
+        }
+
+        self.sniped.insert(&launch.token_address, ());
+        info!(
+            id = self.id(),
+            token = %launch.token_address,
+            creator = %launch.creator_wallet,
+            "BUY signal: Sniping launch with {:.0} USD initial liquidity, {:.0} USD.",
+            launch.initial_liquidity_usd,
+            size_usd
+        );
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: launch.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "initial_liquidity_usd": launch.initial_liquidity_usd,
+                    "lp_locked": launch.lp_locked,
+                    "creator_wallet": launch.creator_wallet,
+                    "launch_age_secs": now - launch.timestamp,
+                })),
+                order_type: OrderType::Market,
+                // Fresh pump.fun tokens only trade on the bonding curve
+                venue: match launch.launchpad {
+                    Launchpad::PumpFun => Some(Venue::PumpFun),
+                    Launchpad::Raydium => Some(Venue::Raydium),
+                },
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({
+            "creators": self.creators.by_recency(),
+            "token_creators": self.token_creators.by_recency(),
+            "sniped": self.sniped.by_recency(),
+        })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            creators: Vec<(String, CreatorRecord)>,
+            token_creators: Vec<(String, String)>,
+            sniped: Vec<(String, ())>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        self.creators.restore_from(s.creators);
+        self.token_creators.restore_from(s.token_creators);
+        self.sniped.restore_from(s.sniped);
+        Ok(())
+    }
+}
+register_strategy!(LaunchSniper, "launch_sniper");
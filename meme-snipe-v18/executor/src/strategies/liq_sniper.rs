@@ -0,0 +1,263 @@
+// executor/src/strategies/liq_sniper.rs
+//! Fades liquidation cascades on Drift. OnChain events of type "Liquidation"
+//! carry a forced close, `{ side, size_usd }`, where `side` is the side of the
+//! position that was liquidated. Liquidated longs are forced sells, so at
+//! least `min_cascade_usd` of them within `window_secs`, with the price
+//! `min_move_pct` below its high over that window, is an overextension down
+//! and is bought. Liquidated shorts are the mirror image and are shorted, on
+//! Drift perps, so only in tokens with a perp market.
+//!
+//! Snapbacks are quick or don't come, so exits are tight: a take-profit and
+//! trailing stop, and `max_hold_secs`. A token is faded at most once per
+//! window.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        drift_market_index, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::CounterVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashSet, VecDeque};
+use tracing::{debug, info};
+
+/// Payload of an OnChain "Liquidation" event.
+#[derive(Debug, Clone, Deserialize)]
+struct Liquidation {
+    side: Side, // Side of the liquidated position
+    size_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TokenState {
+    liquidations: VecDeque<(i64, Side, f64)>, // (timestamp, liquidated side, USD)
+    prices: VecDeque<(i64, f64)>,             // (timestamp, price), oldest first
+    faded_at: Option<i64>,
+}
+
+impl TokenState {
+    /// Drops liquidations and prices older than the window.
+    fn trim(&mut self, now: i64, window_secs: i64) {
+        while self
+            .liquidations
+            .front()
+            .is_some_and(|(at, _, _)| now - at > window_secs)
+        {
+            self.liquidations.pop_front();
+        }
+        while self
+            .prices
+            .front()
+            .is_some_and(|(at, _)| now - at > window_secs)
+        {
+            self.prices.pop_front();
+        }
+    }
+
+    fn liquidated_usd(&self, side: &Side) -> f64 {
+        self.liquidations
+            .iter()
+            .filter(|(_, s, _)| s == side)
+            .map(|(_, _, usd)| usd)
+            .sum()
+    }
+}
+
+#[derive(Default)]
+struct LiqSniper {
+    window_secs: i64,
+    min_cascade_usd: f64,
+    min_move_pct: f64,
+    size_usd: f64,
+    exit_profile: ExitProfile,
+    tokens: PerTokenState<TokenState>,
+    fades_counter: Option<CounterVec>,
+}
+
+#[async_trait]
+impl Strategy for LiqSniper {
+    fn id(&self) -> &'static str {
+        "liq_sniper"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Price ticks measure how far the cascade pushed the price
+        [EventType::OnChain, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("window_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_cascade_usd", ParamKind::Number)
+            .min(0.0)
+            .required("min_move_pct", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("trailing_stop_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            window_secs: i64,
+            min_cascade_usd: f64,
+            min_move_pct: f64,
+            size_usd: f64,
+            take_profit_pct: f64,
+            trailing_stop_pct: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.window_secs = p.window_secs;
+        self.min_cascade_usd = p.min_cascade_usd;
+        self.min_move_pct = p.min_move_pct;
+        self.size_usd = p.size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: Some(p.trailing_stop_pct),
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.tokens = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.fades_counter = Some(metrics.counter_vec(
+            "fades_total",
+            "Cascades faded, by the side entered.",
+            &["side"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with window_secs: {}, min_cascade_usd: {}, min_move_pct: {}",
+            self.window_secs,
+            self.min_cascade_usd,
+            self.min_move_pct
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let window_secs = self.window_secs;
+        let onchain = match event {
+            MarketEvent::Price(tick) => {
+                let state = self
+                    .tokens
+                    .get_or_insert_with(&tick.token_address, TokenState::default);
+                state.prices.push_back((tick.timestamp, tick.price_usd));
+                state.trim(tick.timestamp, window_secs);
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::OnChain(onchain) if onchain.event_type == "Liquidation" => onchain,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let liquidation: Liquidation = match serde_json::from_value(onchain.data.clone()) {
+            Ok(liquidation) => liquidation,
+            Err(e) => {
+                debug!(id = self.id(), error = %e, "Ignoring malformed Liquidation event.");
+                return Ok(StrategyAction::Hold);
+            }
+        };
+
+        let state = self
+            .tokens
+            .get_or_insert_with(&onchain.token_address, TokenState::default);
+        state
+            .liquidations
+            .push_back((onchain.timestamp, liquidation.side, liquidation.size_usd));
+        state.trim(onchain.timestamp, window_secs);
+        if state
+            .faded_at
+            .is_some_and(|at| onchain.timestamp - at < window_secs)
+        {
+            return Ok(StrategyAction::Hold);
+        }
+        let price = match state.prices.back() {
+            Some((_, price)) => *price,
+            None => return Ok(StrategyAction::Hold),
+        };
+        let high = state.prices.iter().map(|(_, p)| *p).fold(price, f64::max);
+        let low = state.prices.iter().map(|(_, p)| *p).fold(price, f64::min);
+        let drop_pct = (high - price) / high * 100.0;
+        let rise_pct = (price - low) / low * 100.0;
+
+        // Forced sells overshoot down, forced buys up; enter against them
+        let long_cascade = state.liquidated_usd(&Side::Long);
+        let short_cascade = state.liquidated_usd(&Side::Short);
+        let (side, cascade_usd, move_pct) =
+            if long_cascade >= self.min_cascade_usd && drop_pct >= self.min_move_pct {
+                (Side::Long, long_cascade, drop_pct)
+            } else if short_cascade >= self.min_cascade_usd && rise_pct >= self.min_move_pct {
+                (Side::Short, short_cascade, rise_pct)
+            } else {
+                return Ok(StrategyAction::Hold);
+            };
+        if side == Side::Short && drift_market_index(&onchain.token_address).is_none() {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&onchain.token_address, &side) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        state.faded_at = Some(onchain.timestamp);
+        if let Some(counter) = &self.fades_counter {
+            counter.with_label_values(&[&side.to_string()]).inc();
+        }
+        info!(id = self.id(), token = %onchain.token_address, "FADE signal: ${:.0} liquidated within {}s, price moved {:.2}%, going {}.", cascade_usd, window_secs, move_pct, side);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: onchain.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "cascade_usd": cascade_usd,
+                    "move_pct": move_pct,
+                    "window_secs": window_secs,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "tokens": self.tokens.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            tokens: Vec<(String, TokenState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        // Windows are trimmed by time as new events arrive
+        self.tokens.restore_from(s.tokens);
+        Ok(())
+    }
+}
+register_strategy!(LiqSniper, "liq_sniper");
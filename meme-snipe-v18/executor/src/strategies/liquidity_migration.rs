@@ -1,12 +1,15 @@
 use crate::{
     register_strategy,
-    strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction, TradeMode},
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction, TradeMode,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
-use shared_models::Side;
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
 use std::collections::HashSet;
 use tracing::info;
 
@@ -27,7 +30,13 @@ impl Strategy for LiquidityMigration {
         [EventType::Bridge].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("min_volume_migrate_usd", ParamKind::Number)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, _metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             min_volume_migrate_usd: f64,
@@ -41,7 +50,11 @@ impl Strategy for LiquidityMigration {
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        _portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         // The logic now reacts to the correct event type, not a noisy proxy.
         if let MarketEvent::Bridge(bridge_event) = event {
             if bridge_event.volume_usd > self.min_volume_migrate_usd
@@ -73,6 +86,9 @@ impl Strategy for LiquidityMigration {
                         // ADDED: new fields for enhanced data collection and control
                         limit_price: None, // This strategy is a market taker
                         triggering_features: Some(features),
+                        order_type: OrderType::Market,
+                        venue: None,
+                        exit_profile: None,
                     },
                     TradeMode::Paper,
                 ));
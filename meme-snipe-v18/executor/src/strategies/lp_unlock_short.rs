@@ -0,0 +1,258 @@
+// executor/src/strategies/lp_unlock_short.rs
+//! Trades against tokens whose liquidity is about to leave. Two triggers, both
+//! from LiquidityPoolEvents:
+//!
+//! - a removal that pulls at least `removal_pct` of the pool (and at least
+//!   `min_removal_usd`) in one go;
+//! - an LP lock of at least `min_locked_usd` that expires within
+//!   `unlock_warning_secs`, checked on the token's price ticks, or an Unlock.
+//!
+//! On a trigger the strategy closes its longs in the token if it holds any,
+//! otherwise it shorts it with `size_usd` on Drift, if the token has a perp
+//! market there.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        drift_market_index, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::CounterVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{LiquidityAction, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::HashSet;
+use tracing::info;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LpLock {
+    unlock_at: i64,
+    locked_usd: f64,
+    signaled: bool, // The warning has fired for this lock
+}
+
+#[derive(Default)]
+struct LpUnlockShort {
+    unlock_warning_secs: i64,
+    min_locked_usd: f64,
+    removal_pct: f64,
+    min_removal_usd: f64,
+    size_usd: f64,
+    locks: PerTokenState<LpLock>, // Latest known lock per token
+    signals_counter: Option<CounterVec>,
+}
+
+impl LpUnlockShort {
+    /// Exits a long if there is one, otherwise shorts.
+    fn act(
+        &self,
+        token: &str,
+        trigger: &str,
+        confidence: f64,
+        features: Value,
+        portfolio: &PortfolioContext,
+    ) -> StrategyAction {
+        let action = if portfolio.exposure_to(token, &Side::Long) > 0.0 {
+            info!(id = self.id(), token = %token, trigger, "EXIT signal: Liquidity is leaving, closing longs.");
+            "exit"
+        } else if portfolio.exposure_to(token, &Side::Short) > 0.0
+            || drift_market_index(token).is_none()
+        {
+            return StrategyAction::Hold;
+        } else {
+            info!(id = self.id(), token = %token, trigger, "SHORT signal: Liquidity is leaving.");
+            "short"
+        };
+        if let Some(counter) = &self.signals_counter {
+            counter.with_label_values(&[trigger, action]).inc();
+        }
+        if action == "exit" {
+            return StrategyAction::ClosePositions(token.to_string());
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 {
+            return StrategyAction::Hold;
+        }
+        StrategyAction::Execute(
+            OrderDetails {
+                token_address: token.to_string(),
+                suggested_size_usd: size_usd,
+                confidence,
+                side: Side::Short,
+                limit_price: None,
+                triggering_features: Some(features),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        )
+    }
+}
+
+#[async_trait]
+impl Strategy for LpUnlockShort {
+    fn id(&self) -> &'static str {
+        "lp_unlock_short"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Price ticks are the clock for lock expiries
+        [EventType::LiquidityPool, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("unlock_warning_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("min_locked_usd", ParamKind::Number)
+            .min(0.0)
+            .required("removal_pct", ParamKind::Number)
+            .min(0.0)
+            .max(100.0)
+            .required("min_removal_usd", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            unlock_warning_secs: i64,
+            min_locked_usd: f64,
+            removal_pct: f64,
+            min_removal_usd: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.unlock_warning_secs = p.unlock_warning_secs;
+        self.min_locked_usd = p.min_locked_usd;
+        self.removal_pct = p.removal_pct;
+        self.min_removal_usd = p.min_removal_usd;
+        self.size_usd = p.size_usd;
+        self.locks = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.signals_counter = Some(metrics.counter_vec(
+            "signals_total",
+            "Signals by trigger (removal or unlock) and action (short or exit).",
+            &["trigger", "action"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with unlock_warning_secs: {}, min_locked_usd: {}, removal_pct: {}, min_removal_usd: {}",
+            self.unlock_warning_secs,
+            self.min_locked_usd,
+            self.removal_pct,
+            self.min_removal_usd
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let lp = match event {
+            MarketEvent::Price(tick) => {
+                let warning_secs = self.unlock_warning_secs;
+                let lock = match self.locks.get_mut(&tick.token_address) {
+                    Some(lock) if !lock.signaled => lock,
+                    _ => return Ok(StrategyAction::Hold),
+                };
+                let secs_to_unlock = lock.unlock_at - tick.timestamp;
+                if secs_to_unlock > warning_secs {
+                    return Ok(StrategyAction::Hold);
+                }
+                lock.signaled = true;
+                let features = json!({
+                    "locked_usd": lock.locked_usd,
+                    "secs_to_unlock": secs_to_unlock,
+                });
+                return Ok(self.act(&tick.token_address, "unlock", 0.7, features, portfolio));
+            }
+            MarketEvent::LiquidityPool(lp) => lp,
+            _ => return Ok(StrategyAction::Hold),
+        };
+
+        match lp.action {
+            LiquidityAction::Lock => {
+                if let Some(unlock_at) = lp.unlock_timestamp {
+                    if lp.amount_usd >= self.min_locked_usd {
+                        self.locks.insert(
+                            &lp.token_address,
+                            LpLock {
+                                unlock_at,
+                                locked_usd: lp.amount_usd,
+                                signaled: false,
+                            },
+                        );
+                    }
+                }
+                Ok(StrategyAction::Hold)
+            }
+            LiquidityAction::Unlock => {
+                // Already warned about if the expiry was seen coming
+                let warned = self
+                    .locks
+                    .get(&lp.token_address)
+                    .is_some_and(|lock| lock.signaled);
+                if warned || lp.amount_usd < self.min_locked_usd {
+                    return Ok(StrategyAction::Hold);
+                }
+                let features = json!({ "unlocked_usd": lp.amount_usd, "secs_to_unlock": 0 });
+                Ok(self.act(&lp.token_address, "unlock", 0.7, features, portfolio))
+            }
+            LiquidityAction::Remove => {
+                // pool_liquidity_usd is what's left after the removal
+                let before_usd = lp.pool_liquidity_usd + lp.amount_usd;
+                let removed_pct = if before_usd > 0.0 {
+                    lp.amount_usd / before_usd * 100.0
+                } else {
+                    0.0
+                };
+                if removed_pct < self.removal_pct || lp.amount_usd < self.min_removal_usd {
+                    return Ok(StrategyAction::Hold);
+                }
+                let features = json!({
+                    "removed_usd": lp.amount_usd,
+                    "removed_pct": removed_pct,
+                    "pool_liquidity_usd": lp.pool_liquidity_usd,
+                });
+                let confidence = 0.5 + 0.5 * removed_pct / 100.0;
+                Ok(self.act(
+                    &lp.token_address,
+                    "removal",
+                    confidence,
+                    features,
+                    portfolio,
+                ))
+            }
+            LiquidityAction::Add => Ok(StrategyAction::Hold),
+        }
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "locks": self.locks.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            locks: Vec<(String, LpLock)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        self.locks.restore_from(s.locks);
+        Ok(())
+    }
+}
+register_strategy!(LpUnlockShort, "lp_unlock_short");
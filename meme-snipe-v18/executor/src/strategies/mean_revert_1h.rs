@@ -1,21 +1,31 @@
 use crate::{
     register_strategy,
-    strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction},
+    strategies::{
+        CandleAggregator, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use prometheus::Gauge;
 use serde::Deserialize;
-use serde_json::Value;
-use shared_models::Side;
-use std::collections::{HashSet, VecDeque};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::HashSet;
 use tracing::info; // P-5: Import Side
 
+const CANDLE_SECS: i64 = 3600;
+
 #[derive(Default, Deserialize)]
 struct MeanRevert1h {
     period_hours: usize,
     z_score_threshold: f64,
     #[serde(skip)]
-    price_history: VecDeque<f64>, // Stores prices for Z-score calculation
+    candles: PerTokenState<CandleAggregator>, // 1h candles per token; z-scores use their closes
+    #[serde(skip)]
+    z_score_gauge: Option<Gauge>,
+    #[serde(skip)]
+    buffer_fill_gauge: Option<Gauge>,
 }
 
 #[async_trait]
@@ -27,16 +37,32 @@ impl Strategy for MeanRevert1h {
         [EventType::Price].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("period_hours", ParamKind::Integer)
+            .min(2.0)
+            .required("z_score_threshold", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             period_hours: usize,
             z_score_threshold: f64,
+            max_tokens: Option<usize>,
         }
         let p: P = serde_json::from_value(params.clone())?;
         self.period_hours = p.period_hours;
         self.z_score_threshold = p.z_score_threshold;
-        self.price_history = VecDeque::with_capacity(self.period_hours * 60); // Assuming 1-minute ticks
+        self.candles = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.z_score_gauge = Some(metrics.gauge("z_score", "Z-score of the latest 1h close.")?);
+        self.buffer_fill_gauge = Some(metrics.gauge(
+            "buffer_fill_ratio",
+            "Share of the lookback window filled with 1h candles, for the token last updated.",
+        )?);
         info!(
             strategy = self.id(),
             "Initialized with period_hours: {}, z_score_threshold: {}",
@@ -46,57 +72,95 @@ impl Strategy for MeanRevert1h {
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         if let MarketEvent::Price(tick) = event {
-            // Simplified: Add each tick. A real 1h strategy would aggregate to 1h candles.
-            if self.price_history.len() == self.period_hours * 60 {
-                self.price_history.pop_front();
+            let window = self.period_hours;
+            let candles = self.candles.get_or_insert_with(&tick.token_address, || {
+                CandleAggregator::new(CANDLE_SECS, window)
+            });
+            // Signals are only evaluated as each hour closes
+            let close = match candles.push(tick.timestamp, tick.price_usd, tick.volume_usd_1m) {
+                Some(candle) => candle.close,
+                None => return Ok(StrategyAction::Hold),
+            };
+            let closes: Vec<f64> = candles.closed().iter().map(|c| c.close).collect();
+            if let Some(gauge) = &self.buffer_fill_gauge {
+                gauge.set(closes.len() as f64 / window as f64);
             }
-            self.price_history.push_back(tick.price_usd);
 
-            if self.price_history.len() < self.period_hours * 60 {
+            if closes.len() < window {
                 return Ok(StrategyAction::Hold);
             }
 
-            let mean: f64 =
-                self.price_history.iter().sum::<f64>() / (self.period_hours * 60) as f64;
-            let std_dev = (self
-                .price_history
-                .iter()
-                .map(|&p| (p - mean).powi(2))
-                .sum::<f64>()
-                / (self.period_hours * 60) as f64)
-                .sqrt();
+            let mean: f64 = closes.iter().sum::<f64>() / window as f64;
+            let std_dev =
+                (closes.iter().map(|&p| (p - mean).powi(2)).sum::<f64>() / window as f64).sqrt();
 
             if std_dev > 0.0 {
-                let z_score = (tick.price_usd - mean) / std_dev;
-                if z_score < -self.z_score_threshold {
+                let z_score = (close - mean) / std_dev;
+                if let Some(gauge) = &self.z_score_gauge {
+                    gauge.set(z_score);
+                }
+                let (side, size_usd, confidence) = if z_score < -self.z_score_threshold {
                     // Buy when significantly oversold
-                    info!(id = self.id(), token = %tick.token_address, "BUY signal: Price z-score {:.2} is below threshold -{:.2}", z_score, self.z_score_threshold);
-                    return Ok(StrategyAction::Execute(
-                        OrderDetails {
-                            // P-5: Use Execute
-                            token_address: tick.token_address.clone(),
-                            suggested_size_usd: 300.0,
-                            confidence: 0.6,
-                            side: Side::Short, // P-5: Add side
-                        },
-                        TradeMode::Paper,
-                    ));
+                    info!(id = self.id(), token = %tick.token_address, "BUY signal: 1h close z-score {:.2} is below threshold -{:.2}", z_score, self.z_score_threshold);
+                    (Side::Long, 300.0, 0.6)
                 } else if z_score > self.z_score_threshold {
-                    // Sell when significantly overbought
-                    info!(id = self.id(), token = %tick.token_address, "SELL signal: Price z-score {:.2} is above threshold {:.2}", z_score, self.z_score_threshold);
-                    return Ok(StrategyAction::Execute(OrderDetails {
-                        // P-5: Use Execute
-                        token_address: tick.token_address.clone(),
-                        suggested_size_usd: 400.0, // Amount to sell
-                        confidence: 0.7,
-                        side: Side::Long, // P-5: Add side (for closing a long or opening a short)
-                    }));
+                    // Short when significantly overbought
+                    info!(id = self.id(), token = %tick.token_address, "SELL signal: 1h close z-score {:.2} is above threshold {:.2}", z_score, self.z_score_threshold);
+                    (Side::Short, 400.0, 0.7)
+                } else {
+                    return Ok(StrategyAction::Hold);
+                };
+                let size_usd = portfolio.scale_size(size_usd);
+                if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &side) > 0.0 {
+                    return Ok(StrategyAction::Hold);
                 }
+                return Ok(StrategyAction::Execute(
+                    OrderDetails {
+                        token_address: tick.token_address.clone(),
+                        suggested_size_usd: size_usd,
+                        confidence,
+                        side,
+                        limit_price: None,
+                        triggering_features: Some(json!({
+                            "close_z_score": z_score,
+                            "close_mean": mean,
+                            "close_std_dev": std_dev,
+                            "period_hours": window,
+                        })),
+                        order_type: OrderType::Market,
+                        venue: None,
+                        exit_profile: None,
+                    },
+                    TradeMode::Paper,
+                ));
             }
         }
         Ok(StrategyAction::Hold)
     }
+
+    fn snapshot(&self) -> Value {
+        json!({ "candles": self.candles.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            candles: Vec<(String, CandleAggregator)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let window = self.period_hours;
+        self.candles.restore_from(
+            s.candles.into_iter().filter_map(|(token, candles)| {
+                Some((token, candles.restored(CANDLE_SECS, window)?))
+            }),
+        );
+        Ok(())
+    }
 }
 register_strategy!(MeanRevert1h, "mean_revert_1h");
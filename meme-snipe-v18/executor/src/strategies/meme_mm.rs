@@ -0,0 +1,334 @@
+// executor/src/strategies/meme_mm.rs
+//! Passive market making in a configured list of liquid tokens. On each
+//! DepthEvent for a token with no live quotes, a bid and an ask of
+//! `quote_size_usd` are placed as Limit orders `half_spread_bps` either side
+//! of mid, resting for `quote_ttl_secs`. Quotes never cross the book.
+//!
+//! Inventory is the strategy's long minus short exposure in the token. Quotes
+//! are shifted against it, by up to `skew_bps` at `max_inventory_usd`, and a
+//! side whose fill would take inventory past the limit isn't quoted.
+//!
+//! Fills show up as exposure growing between depth updates. Each is marked
+//! against mid `markout_secs` later, and the mean adverse move over the last
+//! `markout_window` fills measures how often the quotes are picked off by
+//! informed flow. Inventory beyond the limit or a mean adverse move above
+//! `max_adverse_bps` is a hard stop: the token's positions are closed and it
+//! isn't quoted again for `halt_secs`.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PortfolioContext, Strategy,
+        StrategyAction, TradeMode,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::{CounterVec, GaugeVec};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{LegPolicy, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fill {
+    side: Side,
+    price: f64,
+    at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BookState {
+    bid_quote: Option<f64>,
+    ask_quote: Option<f64>,
+    quotes_expire_at: i64,
+    long_usd: f64, // Exposure at the previous update, to spot fills
+    short_usd: f64,
+    pending: VecDeque<Fill>,     // Fills not yet marked out, oldest first
+    markouts_bps: VecDeque<f64>, // Adverse move per fill, latest last
+    halted_until: i64,
+}
+
+#[derive(Default)]
+struct MemeMm {
+    quote_size_usd: f64,
+    half_spread_bps: f64,
+    skew_bps: f64,
+    max_inventory_usd: f64,
+    quote_ttl_secs: i64,
+    markout_secs: i64,
+    markout_window: usize,
+    max_adverse_bps: f64,
+    halt_secs: i64,
+    books: HashMap<String, BookState>,
+    inventory_gauge: Option<GaugeVec>,
+    hard_stops_counter: Option<CounterVec>,
+}
+
+impl MemeMm {
+    fn quote(
+        &self,
+        token: &str,
+        side: Side,
+        price: f64,
+        size_usd: f64,
+        inventory: f64,
+    ) -> OrderDetails {
+        OrderDetails {
+            token_address: token.to_string(),
+            suggested_size_usd: size_usd,
+            confidence: 0.5,
+            side,
+            limit_price: Some(price),
+            triggering_features: Some(json!({
+                "quote_price": price,
+                "inventory_usd": inventory,
+            })),
+            order_type: OrderType::Limit {
+                price,
+                ttl_secs: self.quote_ttl_secs as u64,
+            },
+            venue: None,
+            exit_profile: None, // Inventory is flattened by hard stops
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for MemeMm {
+    fn id(&self) -> &'static str {
+        "meme_mm"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Depth].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("tokens", ParamKind::StringList)
+            .min(1.0)
+            .required("quote_size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("half_spread_bps", ParamKind::Number)
+            .min(0.0)
+            .required("skew_bps", ParamKind::Number)
+            .min(0.0)
+            .required("max_inventory_usd", ParamKind::Number)
+            .min(0.0)
+            .required("quote_ttl_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("markout_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("markout_window", ParamKind::Integer)
+            .min(1.0)
+            .required("max_adverse_bps", ParamKind::Number)
+            .min(0.0)
+            .required("halt_secs", ParamKind::Integer)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            tokens: Vec<String>,
+            quote_size_usd: f64,
+            half_spread_bps: f64,
+            skew_bps: f64,
+            max_inventory_usd: f64,
+            quote_ttl_secs: i64,
+            markout_secs: i64,
+            markout_window: usize,
+            max_adverse_bps: f64,
+            halt_secs: i64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.quote_size_usd = p.quote_size_usd;
+        self.half_spread_bps = p.half_spread_bps;
+        self.skew_bps = p.skew_bps;
+        self.max_inventory_usd = p.max_inventory_usd;
+        self.quote_ttl_secs = p.quote_ttl_secs;
+        self.markout_secs = p.markout_secs;
+        self.markout_window = p.markout_window;
+        self.max_adverse_bps = p.max_adverse_bps;
+        self.halt_secs = p.halt_secs;
+        self.books = p
+            .tokens
+            .into_iter()
+            .map(|token| (token, BookState::default()))
+            .collect();
+        self.inventory_gauge = Some(metrics.gauge_vec(
+            "inventory_usd",
+            "Long minus short exposure per quoted token.",
+            &["token"],
+        )?);
+        self.hard_stops_counter = Some(metrics.counter_vec(
+            "hard_stops_total",
+            "Tokens flattened and halted, by the limit that was hit.",
+            &["reason"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} tokens, half_spread_bps: {}, skew_bps: {}, max_inventory_usd: {}, max_adverse_bps: {}",
+            self.books.len(),
+            self.half_spread_bps,
+            self.skew_bps,
+            self.max_inventory_usd,
+            self.max_adverse_bps
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let depth = match event {
+            MarketEvent::Depth(depth) => depth,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let mid = (depth.bid_price + depth.ask_price) / 2.0;
+        if mid <= 0.0 || depth.ask_price < depth.bid_price {
+            return Ok(StrategyAction::Hold); // Empty or crossed book
+        }
+        let (markout_secs, markout_window) = (self.markout_secs, self.markout_window);
+        let book = match self.books.get_mut(&depth.token_address) {
+            Some(book) => book,
+            None => return Ok(StrategyAction::Hold),
+        };
+
+        // Exposure that grew since the last update was one of our quotes filling
+        let long_usd = portfolio.exposure_to(&depth.token_address, &Side::Long);
+        let short_usd = portfolio.exposure_to(&depth.token_address, &Side::Short);
+        if long_usd > book.long_usd {
+            if let Some(price) = book.bid_quote.take() {
+                book.pending.push_back(Fill {
+                    side: Side::Long,
+                    price,
+                    at: depth.timestamp,
+                });
+            }
+        }
+        if short_usd > book.short_usd {
+            if let Some(price) = book.ask_quote.take() {
+                book.pending.push_back(Fill {
+                    side: Side::Short,
+                    price,
+                    at: depth.timestamp,
+                });
+            }
+        }
+        book.long_usd = long_usd;
+        book.short_usd = short_usd;
+        while let Some(fill) = book.pending.front() {
+            if depth.timestamp - fill.at < markout_secs {
+                break;
+            }
+            // Positive when mid moved against the fill
+            let adverse_bps = match fill.side {
+                Side::Long => (fill.price - mid) / fill.price * 10_000.0,
+                Side::Short => (mid - fill.price) / fill.price * 10_000.0,
+            };
+            book.pending.pop_front();
+            if book.markouts_bps.len() == markout_window {
+                book.markouts_bps.pop_front();
+            }
+            book.markouts_bps.push_back(adverse_bps);
+        }
+
+        let inventory = long_usd - short_usd;
+        if let Some(gauge) = &self.inventory_gauge {
+            gauge
+                .with_label_values(&[&depth.token_address])
+                .set(inventory);
+        }
+        let mean_adverse_bps = if book.markouts_bps.len() == markout_window {
+            book.markouts_bps.iter().sum::<f64>() / markout_window as f64
+        } else {
+            0.0
+        };
+        let stop_reason = if inventory.abs() > self.max_inventory_usd {
+            Some("inventory")
+        } else if mean_adverse_bps > self.max_adverse_bps {
+            Some("adverse_selection")
+        } else {
+            None
+        };
+        if let Some(reason) = stop_reason {
+            book.halted_until = depth.timestamp + self.halt_secs;
+            book.bid_quote = None;
+            book.ask_quote = None;
+            book.pending.clear();
+            book.markouts_bps.clear();
+            if let Some(counter) = &self.hard_stops_counter {
+                counter.with_label_values(&[reason]).inc();
+            }
+            warn!(id = self.id(), token = %depth.token_address, reason, inventory, mean_adverse_bps, "HARD STOP: Flattening and halting quotes.");
+            return Ok(StrategyAction::ClosePositions(depth.token_address.clone()));
+        }
+        if depth.timestamp < book.halted_until || depth.timestamp < book.quotes_expire_at {
+            return Ok(StrategyAction::Hold);
+        }
+
+        let size_usd = portfolio.scale_size(self.quote_size_usd);
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        // Long inventory lowers both quotes: sells get more aggressive, buys less
+        let inventory_ratio = if self.max_inventory_usd > 0.0 {
+            (inventory / self.max_inventory_usd).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        let center = mid * (1.0 - inventory_ratio * self.skew_bps / 10_000.0);
+        let bid = center * (1.0 - self.half_spread_bps / 10_000.0);
+        let ask = center * (1.0 + self.half_spread_bps / 10_000.0);
+        let quote_bid = bid < depth.ask_price && inventory + size_usd <= self.max_inventory_usd;
+        let quote_ask = ask > depth.bid_price && inventory - size_usd >= -self.max_inventory_usd;
+        book.bid_quote = quote_bid.then_some(bid);
+        book.ask_quote = quote_ask.then_some(ask);
+        book.quotes_expire_at = depth.timestamp + self.quote_ttl_secs;
+
+        let mut legs = Vec::with_capacity(2);
+        if quote_bid {
+            legs.push(self.quote(&depth.token_address, Side::Long, bid, size_usd, inventory));
+        }
+        if quote_ask {
+            legs.push(self.quote(&depth.token_address, Side::Short, ask, size_usd, inventory));
+        }
+        if legs.is_empty() {
+            return Ok(StrategyAction::Hold);
+        }
+        Ok(StrategyAction::ExecuteMany(
+            legs,
+            LegPolicy::Independent,
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "books": self.books })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            books: HashMap<String, BookState>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        // Tokens dropped from the params are forgotten; the window may have shrunk
+        let markout_window = self.markout_window;
+        for (token, saved) in s.books {
+            if let Some(book) = self.books.get_mut(&token) {
+                *book = BookState {
+                    markouts_bps: newest(saved.markouts_bps, markout_window),
+                    ..saved
+                };
+            }
+        }
+        Ok(())
+    }
+}
+register_strategy!(MemeMm, "meme_mm");
@@ -0,0 +1,316 @@
+// executor/src/strategies/meme_pairs.rs
+//! Relative value between correlated tokens. Each entry of `pairs` is
+//! "MINT_A:MINT_B"; the strategy tracks ln(price A / price B) over the last
+//! `window` updates of the pair. When the ratio's z-score goes beyond
+//! `entry_z` the rich token is shorted and the cheap one bought, both legs or
+//! neither, `size_usd` each. Once |z| is back under `exit_z` the pair is
+//! closed. Shorts are Drift perps, so an entry that would short a token
+//! without a perp market is skipped.
+//!
+//! A close action names one token, so the A leg is closed first and the B
+//! leg, now unpaired, on the pair's next update. Any leg left unpaired, e.g.
+//! after its partner was stopped out, is closed the same way. A token may
+//! appear in only one pair: positions are told apart by token.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        drift_market_index, newest, EventType, MarketEvent, MetricsHandle, OrderDetails,
+        PortfolioContext, Strategy, StrategyAction, TradeMode,
+    },
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use prometheus::GaugeVec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{LegPolicy, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::info;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Pair {
+    token_a: String,
+    token_b: String,
+    log_ratios: VecDeque<f64>,
+    #[serde(skip)]
+    closing: Option<String>, // Leg a close has been requested for
+}
+
+impl Pair {
+    fn name(&self) -> String {
+        format!("{}:{}", self.token_a, self.token_b)
+    }
+}
+
+#[derive(Default)]
+struct MemePairs {
+    window: usize,
+    entry_z: f64,
+    exit_z: f64,
+    size_usd: f64,
+    pairs: Vec<Pair>,
+    prices: HashMap<String, f64>, // Latest price of each paired token
+    z_score_gauge: Option<GaugeVec>,
+}
+
+fn held(portfolio: &PortfolioContext, token: &str) -> bool {
+    portfolio.exposure_to(token, &Side::Long) + portfolio.exposure_to(token, &Side::Short) > 0.0
+}
+
+impl MemePairs {
+    fn leg(
+        &self,
+        token: &str,
+        side: Side,
+        size_usd: f64,
+        pair: &str,
+        z_score: f64,
+    ) -> OrderDetails {
+        OrderDetails {
+            token_address: token.to_string(),
+            suggested_size_usd: size_usd,
+            confidence: 0.6,
+            side,
+            limit_price: None,
+            triggering_features: Some(json!({
+                "pair": pair,
+                "log_ratio_z_score": z_score,
+                "entry_z": self.entry_z,
+            })),
+            order_type: OrderType::Market,
+            venue: None,
+            exit_profile: None, // Closed on reversion
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for MemePairs {
+    fn id(&self) -> &'static str {
+        "meme_pairs"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("pairs", ParamKind::StringList)
+            .min(1.0)
+            .required("window", ParamKind::Integer)
+            .min(2.0)
+            .required("entry_z", ParamKind::Number)
+            .min(0.0)
+            .required("exit_z", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            pairs: Vec<String>,
+            window: usize,
+            entry_z: f64,
+            exit_z: f64,
+            size_usd: f64,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        if p.exit_z >= p.entry_z {
+            return Err(anyhow!(
+                "exit_z ({}) must be below entry_z ({})",
+                p.exit_z,
+                p.entry_z
+            ));
+        }
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::with_capacity(p.pairs.len());
+        for spec in &p.pairs {
+            let (token_a, token_b) = spec
+                .split_once(':')
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty() && a != b)
+                .ok_or_else(|| anyhow!("pair `{}` must be MINT_A:MINT_B", spec))?;
+            for token in [token_a, token_b] {
+                if !seen.insert(token.to_string()) {
+                    return Err(anyhow!("token {} appears in more than one pair", token));
+                }
+            }
+            pairs.push(Pair {
+                token_a: token_a.to_string(),
+                token_b: token_b.to_string(),
+                log_ratios: VecDeque::with_capacity(p.window),
+                closing: None,
+            });
+        }
+        self.window = p.window;
+        self.entry_z = p.entry_z;
+        self.exit_z = p.exit_z;
+        self.size_usd = p.size_usd;
+        self.pairs = pairs;
+        self.prices.clear();
+        self.z_score_gauge = Some(metrics.gauge_vec(
+            "log_ratio_z_score",
+            "Z-score of each pair's log price ratio.",
+            &["pair"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} pairs, window: {}, entry_z: {}, exit_z: {}",
+            self.pairs.len(),
+            self.window,
+            self.entry_z,
+            self.exit_z
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let tick = match event {
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let index = match self
+            .pairs
+            .iter()
+            .position(|p| p.token_a == tick.token_address || p.token_b == tick.token_address)
+        {
+            Some(index) => index,
+            None => return Ok(StrategyAction::Hold),
+        };
+        if tick.price_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        self.prices
+            .insert(tick.token_address.clone(), tick.price_usd);
+
+        let window = self.window;
+        let pair = &mut self.pairs[index];
+        let (price_a, price_b) = match (
+            self.prices.get(&pair.token_a),
+            self.prices.get(&pair.token_b),
+        ) {
+            (Some(a), Some(b)) => (*a, *b),
+            _ => return Ok(StrategyAction::Hold),
+        };
+        if pair.log_ratios.len() == window {
+            pair.log_ratios.pop_front();
+        }
+        pair.log_ratios.push_back((price_a / price_b).ln());
+
+        // Unpaired legs are closed whatever the spread is doing
+        let (held_a, held_b) = (
+            held(portfolio, &pair.token_a),
+            held(portfolio, &pair.token_b),
+        );
+        if !held_a && !held_b {
+            pair.closing = None;
+        } else if held_a != held_b {
+            let leg = if held_a {
+                pair.token_a.clone()
+            } else {
+                pair.token_b.clone()
+            };
+            if pair.closing.as_ref() == Some(&leg) {
+                return Ok(StrategyAction::Hold);
+            }
+            pair.closing = Some(leg.clone());
+            info!(id = self.id(), token = %leg, "EXIT signal: Closing an unpaired leg.");
+            return Ok(StrategyAction::ClosePositions(leg));
+        }
+
+        if pair.log_ratios.len() < window {
+            return Ok(StrategyAction::Hold);
+        }
+        let n = window as f64;
+        let mean = pair.log_ratios.iter().sum::<f64>() / n;
+        let std_dev = (pair
+            .log_ratios
+            .iter()
+            .map(|r| (r - mean).powi(2))
+            .sum::<f64>()
+            / n)
+            .sqrt();
+        if std_dev <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        let latest = *pair.log_ratios.back().expect("window is full");
+        let z_score = (latest - mean) / std_dev;
+        let (name, token_a, token_b) = (pair.name(), pair.token_a.clone(), pair.token_b.clone());
+        if let Some(gauge) = &self.z_score_gauge {
+            gauge.with_label_values(&[&name]).set(z_score);
+        }
+
+        if held_a && held_b {
+            if z_score.abs() < self.exit_z && pair.closing.is_none() {
+                pair.closing = Some(token_a.clone());
+                info!(id = self.id(), pair = %name, "EXIT signal: Log ratio z-score reverted to {:.2}.", z_score);
+                return Ok(StrategyAction::ClosePositions(token_a));
+            }
+            return Ok(StrategyAction::Hold);
+        }
+        if z_score.abs() <= self.entry_z {
+            return Ok(StrategyAction::Hold);
+        }
+        // Each leg is a full-size position, so the pair needs twice the budget
+        let size_usd = portfolio.scale_size(self.size_usd * 2.0) / 2.0;
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        // A high ratio means A is rich relative to B
+        let (side_a, side_b) = if z_score > 0.0 {
+            (Side::Short, Side::Long)
+        } else {
+            (Side::Long, Side::Short)
+        };
+        let short_token = if side_a == Side::Short {
+            &token_a
+        } else {
+            &token_b
+        };
+        if drift_market_index(short_token).is_none() {
+            return Ok(StrategyAction::Hold);
+        }
+        info!(id = self.id(), pair = %name, "PAIR signal: Log ratio z-score {:.2}, {} A / {} B.", z_score, side_a, side_b);
+        Ok(StrategyAction::ExecuteMany(
+            vec![
+                self.leg(&token_a, side_a, size_usd, &name, z_score),
+                self.leg(&token_b, side_b, size_usd, &name, z_score),
+            ],
+            LegPolicy::AllOrNothing,
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "pairs": self.pairs })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            pairs: Vec<Pair>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let mut saved: HashMap<String, VecDeque<f64>> = s
+            .pairs
+            .into_iter()
+            .map(|pair| (pair.name(), pair.log_ratios))
+            .collect();
+        // Pairs dropped from the params are forgotten; the window may have shrunk
+        let window = self.window;
+        for pair in &mut self.pairs {
+            if let Some(log_ratios) = saved.remove(&pair.name()) {
+                pair.log_ratios = newest(log_ratios, window);
+            }
+        }
+        Ok(())
+    }
+}
+register_strategy!(MemePairs, "meme_pairs");
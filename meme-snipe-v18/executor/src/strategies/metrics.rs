@@ -0,0 +1,81 @@
+// executor/src/strategies/metrics.rs
+//! Lets a strategy export its own Prometheus metrics. Every metric is named
+//! `strategy_<strategy id>_<name>` and lands in the default registry, so it is
+//! served on the executor's /metrics endpoint with everything else. Metrics are
+//! registered once per process; a restarted strategy gets the same collectors
+//! back and keeps counting where it left off.
+
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use prometheus::{Counter, CounterVec, Gauge, GaugeVec, Opts};
+
+lazy_static! {
+    static ref GAUGES: DashMap<String, GaugeVec> = DashMap::new();
+    static ref COUNTERS: DashMap<String, CounterVec> = DashMap::new();
+}
+
+#[derive(Clone)]
+pub struct MetricsHandle {
+    strategy_id: String,
+}
+
+impl MetricsHandle {
+    pub fn new(strategy_id: &str) -> Self {
+        Self {
+            strategy_id: strategy_id.to_string(),
+        }
+    }
+
+    /// Handle for a strategy nested inside this one (e.g. an ensemble child),
+    /// namespaced as `strategy_<id>_<child>_<name>`.
+    pub fn child(&self, name: &str) -> Self {
+        Self {
+            strategy_id: format!("{}_{}", self.strategy_id, name),
+        }
+    }
+
+    fn full_name(&self, name: &str) -> Result<String> {
+        let full = format!("strategy_{}_{}", self.strategy_id, name);
+        if full.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            Ok(full)
+        } else {
+            Err(anyhow!("Invalid metric name '{}'", full))
+        }
+    }
+
+    pub fn gauge(&self, name: &str, help: &str) -> Result<Gauge> {
+        Ok(self.gauge_vec(name, help, &[])?.with_label_values(&[]))
+    }
+
+    /// A gauge with extra labels, e.g. one series per token.
+    pub fn gauge_vec(&self, name: &str, help: &str, labels: &[&str]) -> Result<GaugeVec> {
+        let full = self.full_name(name)?;
+        let gauge = GAUGES
+            .entry(full.clone())
+            .or_try_insert_with(|| -> Result<GaugeVec> {
+                let gauge = GaugeVec::new(Opts::new(full, help), labels)?;
+                prometheus::register(Box::new(gauge.clone()))?;
+                Ok(gauge)
+            })?;
+        Ok(gauge.clone())
+    }
+
+    pub fn counter(&self, name: &str, help: &str) -> Result<Counter> {
+        Ok(self.counter_vec(name, help, &[])?.with_label_values(&[]))
+    }
+
+    /// A counter with extra labels, e.g. signals by side.
+    pub fn counter_vec(&self, name: &str, help: &str, labels: &[&str]) -> Result<CounterVec> {
+        let full = self.full_name(name)?;
+        let counter =
+            COUNTERS
+                .entry(full.clone())
+                .or_try_insert_with(|| -> Result<CounterVec> {
+                    let counter = CounterVec::new(Opts::new(full, help), labels)?;
+                    prometheus::register(Box::new(counter.clone()))?;
+                    Ok(counter)
+                })?;
+        Ok(counter.clone())
+    }
+}
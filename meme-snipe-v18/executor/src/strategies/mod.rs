@@ -1,16 +1,83 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
-use shared_models::{EventType, MarketEvent, Side, StrategyAction}; // P-5: Import Side
-use std::collections::HashSet;
+use shared_models::{EventType, MarketEvent, ParamSchema, Side, StrategyAction}; // P-5: Import Side
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Why a strategy's task is stopping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    Deallocated,      // Removed from the allocation set; it won't be back soon
+    Reparameterized,  // Allocated new params; started again right away with its snapshot
+    ExecutorStopping, // Deploy or restart; it will be started again with its snapshot
+}
+
+/// What the executor should do with a stopping strategy's open positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownAction {
+    KeepPositions,  // Leave them to the position manager's stops
+    ClosePositions, // Ask the position manager to close them at market
+}
 
 #[async_trait]
 pub trait Strategy: Send + Sync + 'static {
     // Added 'static bound
     fn id(&self) -> &'static str;
     fn subscriptions(&self) -> HashSet<EventType>;
-    async fn init(&mut self, params: &Value) -> Result<()>;
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction>;
+    /// Shape of the params accepted by `init()`. Validated by the allocator
+    /// before allocation and again by the executor before `init()`.
+    fn params_schema(&self) -> ParamSchema;
+    /// `metrics` registers strategy-namespaced Prometheus metrics; keep the
+    /// returned collectors to update them from `on_event`.
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()>;
+    /// `portfolio` is a read-only view of this strategy's open positions,
+    /// remaining capital budget and the portfolio drawdown, for sizing.
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction>;
+    /// How often `on_timer` should fire. Read once after `init()`; `None`
+    /// (the default) means the strategy is purely event-driven.
+    fn timer_interval(&self) -> Option<Duration> {
+        None
+    }
+    /// Periodic callback for time-based bookkeeping (bucket rotation, decay,
+    /// timeouts). `now` is the current unix timestamp in seconds.
+    async fn on_timer(&mut self, _now: i64) -> Result<StrategyAction> {
+        Ok(StrategyAction::Hold)
+    }
+    /// Whether this strategy's buys must pass the token safety checker in
+    /// Paper mode too; Live buys always do. Legs that fail are dropped.
+    fn requires_token_safety(&self) -> bool {
+        false
+    }
+    /// In-flight state persisted periodically and when the strategy's task stops.
+    /// `Value::Null` (the default) means there is nothing worth saving.
+    fn snapshot(&self) -> Value {
+        Value::Null
+    }
+    /// Called once when the strategy's task stops, after its queue has drained
+    /// and before the final snapshot is taken.
+    async fn on_shutdown(&mut self, _reason: ShutdownReason) -> Result<ShutdownAction> {
+        Ok(ShutdownAction::KeepPositions)
+    }
+    /// Rehydrates state saved by `snapshot()`, called after `init()` whenever
+    /// the strategy starts: on allocation, after an executor restart, or after
+    /// a crash. Params may have changed since the snapshot was taken.
+    fn restore(&mut self, _snapshot: &Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps the newest `capacity` entries of a restored buffer, for when a
+/// lookback param shrank between the snapshot and the restart.
+pub fn newest<T>(mut buffer: VecDeque<T>, capacity: usize) -> VecDeque<T> {
+    while buffer.len() > capacity {
+        buffer.pop_front();
+    }
+    buffer
 }
 
 // Strategy constructor for dynamic loading
@@ -20,6 +87,24 @@ pub struct StrategyConstructor(
 );
 inventory::collect!(StrategyConstructor);
 
+/// Builds an uninitialized strategy for a registered family ID.
+pub fn build_strategy(id: &str) -> Option<Box<dyn Strategy>> {
+    for constructor in inventory::iter::<StrategyConstructor> {
+        if constructor.0 == id {
+            return Some((constructor.1)());
+        }
+    }
+    None
+}
+
+/// Param schemas for every registered strategy family, keyed by family ID.
+pub fn param_schemas() -> HashMap<&'static str, ParamSchema> {
+    inventory::iter::<StrategyConstructor>
+        .into_iter()
+        .map(|constructor| (constructor.0, (constructor.1)().params_schema()))
+        .collect()
+}
+
 // Macro to simplify registration in each strategy file
 #[macro_export]
 macro_rules! register_strategy {
@@ -33,14 +118,43 @@ macro_rules! register_strategy {
     };
 }
 
+pub mod candles;
+pub mod metrics;
+pub mod per_token;
+pub mod perp_markets;
+pub mod portfolio;
+pub use candles::{Candle, CandleAggregator};
+pub use metrics::MetricsHandle;
+pub use per_token::{PerTokenState, DEFAULT_MAX_TOKENS};
+pub use perp_markets::drift_market_index;
+pub use portfolio::{OpenPosition, PortfolioContext};
+
 // Import and declare all strategy modules
-pub mod airdrop_rotation;
+pub mod basket_momentum;
 pub mod bridge_inflow;
+pub mod copy_trader;
 pub mod dev_wallet_drain;
+pub mod dip_buyer;
+pub mod ensemble;
+pub mod funding_carry;
+pub mod graduation_sniper;
+pub mod holder_growth;
+pub mod influencer_signal;
 pub mod korean_time_burst;
+pub mod launch_sniper;
+pub mod liq_sniper;
 pub mod liquidity_migration;
+pub mod lp_unlock_short;
 pub mod mean_revert_1h;
+pub mod meme_mm;
+pub mod meme_pairs;
 pub mod momentum_5m;
+pub mod obi_scalper;
 pub mod perp_basis_arb;
-pub mod rug_pull_sniffer;
+pub mod sentiment_momentum;
+pub mod session_burst;
 pub mod social_buzz;
+pub mod vol_breakout;
+pub mod volume_breakout;
+pub mod whale_tracker;
+pub mod xdex_arb;
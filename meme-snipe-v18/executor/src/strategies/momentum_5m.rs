@@ -1,24 +1,36 @@
 use crate::register_strategy;
-use crate::strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction};
+use crate::strategies::{
+    newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+    Strategy, StrategyAction, DEFAULT_MAX_TOKENS,
+};
 use anyhow::Result;
 use async_trait::async_trait;
-use serde::Deserialize;
-use serde_json::Value;
-use shared_models::{Side, TradeMode};
+use prometheus::{Counter, Gauge};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ParamKind, ParamSchema, Side, TradeMode};
 use std::collections::{HashSet, VecDeque};
 use tracing::info;
 
+#[derive(Default, Serialize, Deserialize)]
+struct TokenHistory {
+    prices: VecDeque<f64>,
+    volumes: VecDeque<f64>,
+}
+
 #[derive(Default, Deserialize)]
 struct Momentum5m {
     lookback: usize,
     vol_multiplier: f64,
     price_change_threshold: f64,
     #[serde(skip)]
-    price_history: VecDeque<f64>,
-    #[serde(skip)]
-    volume_history: VecDeque<f64>,
+    histories: PerTokenState<TokenHistory>,
     #[serde(skip)]
     current_mode: TradeMode,
+    #[serde(skip)]
+    signals_counter: Option<Counter>,
+    #[serde(skip)]
+    buffer_fill_gauge: Option<Gauge>,
 }
 
 #[async_trait]
@@ -30,20 +42,36 @@ impl Strategy for Momentum5m {
         [EventType::Price].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("lookback", ParamKind::Integer)
+            .min(2.0)
+            .required("vol_multiplier", ParamKind::Number)
+            .min(0.0)
+            .required("price_change_threshold", ParamKind::Number)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             lookback: usize,
             vol_multiplier: f64,
             price_change_threshold: f64,
+            max_tokens: Option<usize>,
         }
         let p: P = serde_json::from_value(params.clone())?;
         self.lookback = p.lookback;
         self.vol_multiplier = p.vol_multiplier;
         self.price_change_threshold = p.price_change_threshold;
-        self.price_history = VecDeque::with_capacity(self.lookback);
-        self.volume_history = VecDeque::with_capacity(self.lookback);
+        self.histories = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
         self.current_mode = TradeMode::Paper; // Start in paper mode
+        self.signals_counter = Some(metrics.counter("signals_total", "BUY signals emitted.")?);
+        self.buffer_fill_gauge = Some(metrics.gauge(
+            "buffer_fill_ratio",
+            "Share of the lookback window filled with prices, for the token last updated.",
+        )?);
         info!(
             strategy = self.id(),
             "Initialized with lookback: {}, vol_multiplier: {}, price_change_threshold: {}",
@@ -54,32 +82,55 @@ impl Strategy for Momentum5m {
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         if let MarketEvent::Price(tick) = event {
-            if self.price_history.len() == self.lookback {
-                self.price_history.pop_front();
+            let lookback = self.lookback;
+            let history = self
+                .histories
+                .get_or_insert_with(&tick.token_address, || TokenHistory {
+                    prices: VecDeque::with_capacity(lookback),
+                    volumes: VecDeque::with_capacity(lookback),
+                });
+            if history.prices.len() == lookback {
+                history.prices.pop_front();
+            }
+            if history.volumes.len() == lookback {
+                history.volumes.pop_front();
             }
-            if self.volume_history.len() == self.lookback {
-                self.volume_history.pop_front();
+            history.prices.push_back(tick.price_usd);
+            history.volumes.push_back(tick.volume_usd_1m);
+            if let Some(gauge) = &self.buffer_fill_gauge {
+                gauge.set(history.prices.len() as f64 / lookback as f64);
             }
-            self.price_history.push_back(tick.price_usd);
-            self.volume_history.push_back(tick.volume_usd_1m);
 
-            if self.price_history.len() < self.lookback {
+            if history.prices.len() < lookback {
                 return Ok(StrategyAction::Hold);
             }
 
-            let avg_volume = self.volume_history.iter().sum::<f64>() / self.lookback as f64;
-            let old_price = self.price_history.front().unwrap_or(&tick.price_usd);
+            let avg_volume = history.volumes.iter().sum::<f64>() / lookback as f64;
+            let old_price = history.prices.front().unwrap_or(&tick.price_usd);
             let price_change = (tick.price_usd - old_price) / old_price;
 
             if price_change > self.price_change_threshold
                 && tick.volume_usd_1m > avg_volume * self.vol_multiplier
             {
+                // Don't chase a token already held; size down as the budget runs out
+                let size_usd = portfolio.scale_size(500.0);
+                if portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 || size_usd <= 0.0
+                {
+                    return Ok(StrategyAction::Hold);
+                }
+                if let Some(counter) = &self.signals_counter {
+                    counter.inc();
+                }
                 info!(id = self.id(), token = %tick.token_address, "BUY signal: Price change {:.2}% > threshold and Volume spike > {:.1}x", price_change * 100.0, self.vol_multiplier);
                 return Ok(StrategyAction::Execute(OrderDetails {
                     token_address: tick.token_address.clone(),
-                    suggested_size_usd: 500.0,
+                    suggested_size_usd: size_usd,
                     confidence: 0.75,
                     side: Side::Long,
                 }));
@@ -87,5 +138,27 @@ impl Strategy for Momentum5m {
         }
         Ok(StrategyAction::Hold)
     }
+
+    fn snapshot(&self) -> Value {
+        json!({ "histories": self.histories.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            histories: Vec<(String, TokenHistory)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let lookback = self.lookback;
+        self.histories
+            .restore_from(s.histories.into_iter().map(|(token, history)| {
+                let history = TokenHistory {
+                    prices: newest(history.prices, lookback),
+                    volumes: newest(history.volumes, lookback),
+                };
+                (token, history)
+            }));
+        Ok(())
+    }
 }
 register_strategy!(Momentum5m, "momentum_5m");
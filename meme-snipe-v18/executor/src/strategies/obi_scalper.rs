@@ -0,0 +1,233 @@
+// executor/src/strategies/obi_scalper.rs
+//! Order-book imbalance scalper. Each DepthEvent gives a top-of-book size
+//! imbalance, (bid - ask) / (bid + ask), and a spread in bps. Once `window`
+//! snapshots of a token are held, an imbalance beyond `imbalance_threshold`
+//! enters on the heavy side (long on bid pressure, short on ask pressure),
+//! unless the spread's z-score over the window is above `max_spread_z`: a
+//! blown-out spread means the imbalance is more likely thin liquidity than
+//! pressure. Positions are short-horizon: closed at `take_profit_pct` or after
+//! `max_hold_secs`.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashSet, VecDeque};
+use tracing::info;
+
+#[derive(Default, Serialize, Deserialize)]
+struct BookHistory {
+    imbalances: VecDeque<f64>,
+    spreads_bps: VecDeque<f64>,
+}
+
+#[derive(Default)]
+struct ObiScalper {
+    window: usize,
+    imbalance_threshold: f64,
+    max_spread_z: f64,
+    size_usd: f64,
+    exit_profile: ExitProfile,
+    histories: PerTokenState<BookHistory>,
+    imbalance_gauge: Option<Gauge>,
+    spread_z_gauge: Option<Gauge>,
+}
+
+fn mean_std(values: &VecDeque<f64>) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+#[async_trait]
+impl Strategy for ObiScalper {
+    fn id(&self) -> &'static str {
+        "obi_scalper"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Depth].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("window", ParamKind::Integer)
+            .min(2.0)
+            .required("imbalance_threshold", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("max_spread_z", ParamKind::Number)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("take_profit_pct", ParamKind::Number)
+            .min(0.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            window: usize,
+            imbalance_threshold: f64,
+            max_spread_z: f64,
+            size_usd: f64,
+            take_profit_pct: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.window = p.window;
+        self.imbalance_threshold = p.imbalance_threshold;
+        self.max_spread_z = p.max_spread_z;
+        self.size_usd = p.size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: Some(p.take_profit_pct),
+            trailing_stop_pct: None,
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.histories = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.imbalance_gauge = Some(metrics.gauge(
+            "book_imbalance",
+            "Top-of-book size imbalance, for the token last updated.",
+        )?);
+        self.spread_z_gauge = Some(metrics.gauge(
+            "spread_z_score",
+            "Spread z-score over the window, for the token last updated.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with window: {}, imbalance_threshold: {}, max_spread_z: {}",
+            self.window,
+            self.imbalance_threshold,
+            self.max_spread_z
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let depth = match event {
+            MarketEvent::Depth(depth) => depth,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let total_size = depth.bid_size_usd + depth.ask_size_usd;
+        let mid = (depth.bid_price + depth.ask_price) / 2.0;
+        if total_size <= 0.0 || mid <= 0.0 || depth.ask_price < depth.bid_price {
+            return Ok(StrategyAction::Hold); // Empty or crossed book
+        }
+        let imbalance = (depth.bid_size_usd - depth.ask_size_usd) / total_size;
+        let spread_bps = (depth.ask_price - depth.bid_price) / mid * 10_000.0;
+
+        let window = self.window;
+        let history = self
+            .histories
+            .get_or_insert_with(&depth.token_address, || BookHistory {
+                imbalances: VecDeque::with_capacity(window),
+                spreads_bps: VecDeque::with_capacity(window),
+            });
+        if history.imbalances.len() == window {
+            history.imbalances.pop_front();
+        }
+        if history.spreads_bps.len() == window {
+            history.spreads_bps.pop_front();
+        }
+        history.imbalances.push_back(imbalance);
+        history.spreads_bps.push_back(spread_bps);
+        if history.spreads_bps.len() < window {
+            return Ok(StrategyAction::Hold);
+        }
+
+        let (spread_mean, spread_std) = mean_std(&history.spreads_bps);
+        let (imbalance_mean, _) = mean_std(&history.imbalances);
+        let spread_z = if spread_std > 0.0 {
+            (spread_bps - spread_mean) / spread_std
+        } else {
+            0.0
+        };
+        if let Some(gauge) = &self.imbalance_gauge {
+            gauge.set(imbalance);
+        }
+        if let Some(gauge) = &self.spread_z_gauge {
+            gauge.set(spread_z);
+        }
+
+        if imbalance.abs() < self.imbalance_threshold || spread_z > self.max_spread_z {
+            return Ok(StrategyAction::Hold);
+        }
+        let side = if imbalance > 0.0 {
+            Side::Long
+        } else {
+            Side::Short
+        };
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&depth.token_address, &side) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        // Scales from 0.5 at the threshold to 1.0 for a fully one-sided book
+        let excess = (imbalance.abs() - self.imbalance_threshold)
+            / (1.0 - self.imbalance_threshold).max(f64::EPSILON);
+        info!(id = self.id(), token = %depth.token_address, side = %side, "Imbalance signal: {:.2} (window mean {:.2}), spread z-score {:.2}", imbalance, imbalance_mean, spread_z);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: depth.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.5 + 0.5 * excess.clamp(0.0, 1.0),
+                side,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "book_imbalance": imbalance,
+                    "book_imbalance_mean": imbalance_mean,
+                    "spread_bps": spread_bps,
+                    "spread_z_score": spread_z,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "histories": self.histories.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            histories: Vec<(String, BookHistory)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let window = self.window;
+        // The window may have shrunk since the snapshot was taken
+        self.histories
+            .restore_from(s.histories.into_iter().map(|(token, history)| {
+                (
+                    token,
+                    BookHistory {
+                        imbalances: newest(history.imbalances, window),
+                        spreads_bps: newest(history.spreads_bps, window),
+                    },
+                )
+            }));
+        Ok(())
+    }
+}
+register_strategy!(ObiScalper, "obi_scalper");
@@ -0,0 +1,120 @@
+// executor/src/strategies/per_token.rs
+//! Keeps a strategy's rolling state separate per token. A strategy subscribed
+//! to a multi-token feed must not mix one token's prices into another's
+//! statistics, and a feed of freshly launched tokens must not grow its state
+//! without bound, so entries beyond `capacity` evict the least recently used
+//! token.
+
+use std::collections::HashMap;
+
+/// Tokens tracked when a strategy doesn't set `max_tokens`.
+pub const DEFAULT_MAX_TOKENS: usize = 500;
+
+pub struct PerTokenState<T> {
+    capacity: usize,
+    entries: HashMap<String, (u64, T)>, // token -> (last touched, state)
+    clock: u64,
+}
+
+impl<T> Default for PerTokenState<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TOKENS)
+    }
+}
+
+impl<T> PerTokenState<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Drops least recently used tokens until at most `keep` remain.
+    fn evict_to(&mut self, keep: usize) {
+        while self.entries.len() > keep {
+            // Linear scan; eviction only happens once the map is full
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (touched, _))| *touched)
+                .map(|(token, _)| token.clone());
+            match oldest {
+                Some(token) => self.entries.remove(&token),
+                None => break,
+            };
+        }
+    }
+
+    /// Reads a token's state without counting it as a use.
+    pub fn get(&self, token: &str) -> Option<&T> {
+        self.entries.get(token).map(|(_, state)| state)
+    }
+
+    /// The token's state if it is already tracked, counted as a use.
+    pub fn get_mut(&mut self, token: &str) -> Option<&mut T> {
+        let now = self.tick();
+        self.entries.get_mut(token).map(|(touched, state)| {
+            *touched = now;
+            state
+        })
+    }
+
+    /// The token's state, created with `init` (evicting the least recently
+    /// used token if full) the first time it is seen.
+    pub fn get_or_insert_with(&mut self, token: &str, init: impl FnOnce() -> T) -> &mut T {
+        let now = self.tick();
+        if !self.entries.contains_key(token) {
+            self.evict_to(self.capacity - 1);
+            self.entries.insert(token.to_string(), (now, init()));
+        }
+        let (touched, state) = self.entries.get_mut(token).expect("entry was just ensured");
+        *touched = now;
+        state
+    }
+
+    pub fn insert(&mut self, token: &str, state: T) {
+        let now = self.tick();
+        if !self.entries.contains_key(token) {
+            self.evict_to(self.capacity - 1);
+        }
+        self.entries.insert(token.to_string(), (now, state));
+    }
+
+    /// Keeps only the tokens for which `keep` returns true.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &mut T) -> bool) {
+        self.entries.retain(|token, (_, state)| keep(token, state));
+    }
+
+    /// Entries least recently used first, so feeding them back through
+    /// `restore_from` reproduces the eviction order. For snapshots.
+    pub fn by_recency(&self) -> Vec<(&str, &T)> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(token, (touched, state))| (*touched, token.as_str(), state))
+            .collect();
+        entries.sort_by_key(|(touched, _, _)| *touched);
+        entries
+            .into_iter()
+            .map(|(_, token, state)| (token, state))
+            .collect()
+    }
+
+    /// Replaces the contents with `entries` (oldest first), keeping the newest
+    /// `capacity` of them. For restoring snapshots.
+    pub fn restore_from(&mut self, entries: impl IntoIterator<Item = (String, T)>) {
+        self.entries.clear();
+        for (token, state) in entries {
+            let now = self.tick();
+            self.entries.insert(token, (now, state));
+        }
+        self.evict_to(self.capacity);
+    }
+}
@@ -1,22 +1,72 @@
+// executor/src/strategies/perp_basis_arb.rs
+//! Perp/spot basis arbitrage. The basis is the Drift perp mark, carried on
+//! each FundingEvent, over the token's latest spot price from Price ticks.
+//! Once the perp trades more than `basis_threshold_pct` above spot the
+//! strategy shorts the perp and buys the same size of spot through Jupiter,
+//! both legs or neither, and closes both once the basis has converged to
+//! zero or below.
+//!
+//! A perp below spot would call for the mirror trade (long perp, short spot),
+//! but the executor has no spot-short path, so negative basis is counted and
+//! skipped, as in funding_carry.
+
 use crate::{
     register_strategy,
-    strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction},
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use prometheus::{CounterVec, Gauge};
 use serde::Deserialize;
-use serde_json::Value;
-use shared_models::Side;
-use std::collections::{HashMap, HashSet};
-use tracing::info; // P-5: Import Side
+use serde_json::{json, Value};
+use shared_models::{LegPolicy, OrderType, ParamKind, ParamSchema, Side, Venue};
+use std::collections::HashSet;
+use tracing::info;
+
+const DEFAULT_SIZE_USD: f64 = 800.0;
 
-#[derive(Default, Deserialize)]
+#[derive(Default)]
 struct PerpBasisArb {
     basis_threshold_pct: f64,
-    #[serde(skip)]
-    spot_prices: HashMap<String, f64>,
-    #[serde(skip)]
-    funding_rates: HashMap<String, f64>,
+    size_usd: f64,
+    spot_prices: PerTokenState<f64>,
+    basis_gauge: Option<Gauge>,
+    signals_counter: Option<CounterVec>,
+}
+
+impl PerpBasisArb {
+    fn leg(
+        &self,
+        token_address: &str,
+        size_usd: f64,
+        side: Side,
+        venue: Option<Venue>,
+        basis_pct: f64,
+    ) -> OrderDetails {
+        OrderDetails {
+            token_address: token_address.to_string(),
+            suggested_size_usd: size_usd,
+            confidence: 0.9,
+            side,
+            limit_price: None,
+            triggering_features: Some(json!({
+                "basis_pct": basis_pct,
+                "basis_threshold_pct": self.basis_threshold_pct,
+            })),
+            order_type: OrderType::Market,
+            venue,
+            exit_profile: None, // Held until the basis converges, not by price
+        }
+    }
+
+    fn count(&self, signal: &str) {
+        if let Some(counter) = &self.signals_counter {
+            counter.with_label_values(&[signal]).inc();
+        }
+    }
 }
 
 #[async_trait]
@@ -24,7 +74,8 @@ impl Strategy for PerpBasisArb {
     fn id(&self) -> &'static str {
         "perp_basis_arb"
     }
-    // This strategy now subscribes to Price (for spot) and Funding events
+
+    // Price for spot, Funding for the perp mark
     fn subscriptions(&self) -> HashSet<EventType> {
         [EventType::Price, EventType::Funding]
             .iter()
@@ -32,78 +83,101 @@ impl Strategy for PerpBasisArb {
             .collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("basis_threshold_pct", ParamKind::Number)
+            .min(0.0)
+            .optional("size_usd", ParamKind::Number)
+            .min(0.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             basis_threshold_pct: f64,
+            size_usd: Option<f64>,
         }
         let p: P = serde_json::from_value(params.clone())?;
         self.basis_threshold_pct = p.basis_threshold_pct;
+        self.size_usd = p.size_usd.unwrap_or(DEFAULT_SIZE_USD);
+        self.basis_gauge = Some(metrics.gauge(
+            "basis_pct",
+            "Perp mark over spot, in percent, for the token last updated.",
+        )?);
+        self.signals_counter = Some(metrics.counter_vec(
+            "signals_total",
+            "Basis entries, exits and skipped negative-basis opportunities.",
+            &["signal"],
+        )?);
         info!(
             strategy = self.id(),
-            "Initialized with basis_threshold_pct: {}", self.basis_threshold_pct
+            "Initialized with basis_threshold_pct: {}, size_usd: {}",
+            self.basis_threshold_pct,
+            self.size_usd
         );
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
-        match event {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let funding = match event {
             MarketEvent::Price(tick) => {
-                self.spot_prices
-                    .insert(tick.token_address.clone(), tick.price_usd);
-            }
-            MarketEvent::Funding(funding_event) => {
-                self.funding_rates.insert(
-                    funding_event.token_address.clone(),
-                    funding_event.funding_rate_pct,
-                );
+                if tick.price_usd > 0.0 {
+                    self.spot_prices.insert(&tick.token_address, tick.price_usd);
+                }
+                return Ok(StrategyAction::Hold);
             }
-            _ => {} // Ignore other event types
+            MarketEvent::Funding(funding) => funding,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let token = funding.token_address.as_str();
+        let (Some(mark), Some(&spot)) = (funding.mark_price_usd, self.spot_prices.get(token))
+        else {
+            return Ok(StrategyAction::Hold);
+        };
+        let basis_pct = (mark - spot) / spot * 100.0;
+        if let Some(gauge) = &self.basis_gauge {
+            gauge.set(basis_pct);
         }
 
-        if let (Some(&spot_price), Some(&funding_rate_pct)) = (
-            self.spot_prices.get(&event.token()),
-            self.funding_rates.get(&event.token()),
-        ) {
-            // Simplified: Basis is directly the funding rate. A real basis would be (perp_price - spot_price) / spot_price
-            let basis = funding_rate_pct;
-
-            if basis.abs() > self.basis_threshold_pct / 100.0 {
-                if basis > 0.0 {
-                    // Positive basis: perp is more expensive, short perp & long spot
-                    info!(id = self.id(), token = %event.token(), "SHORT PERP/LONG SPOT signal: Basis {:.4}% is above threshold. (Simulated)", basis * 100.0);
-                    return Ok(StrategyAction::Execute(
-                        OrderDetails {
-                            // P-5: Use Execute
-                            token_address: event.token().to_string(),
-                            suggested_size_usd: 800.0,
-                            confidence: 0.9,
-                            side: Side::Short, // P-5: Add side (for the short leg)
-                        },
-                        TradeMode::Paper,
-                    ));
-                    // A real strategy would also execute the long spot leg here
-                } else {
-                    // Negative basis: perp is cheaper, long perp & short spot
-                    info!(id = self.id(), token = %event.token(), "LONG PERP/SHORT SPOT signal: Basis {:.4}% is below threshold. (Simulated)", basis * 100.0);
-                    return Ok(StrategyAction::Execute(
-                        OrderDetails {
-                            // P-5: Use Execute
-                            token_address: event.token().to_string(),
-                            suggested_size_usd: 800.0,
-                            confidence: 0.9,
-                            side: Side::Long, // P-5: Add side (for the long leg)
-                        },
-                        TradeMode::Paper,
-                    ));
-                    // A real strategy would also execute the short spot leg here
-                }
+        // The perp leg is the strategy's only short, so it marks an open trade
+        if portfolio.exposure_to(token, &Side::Short) > 0.0 {
+            if basis_pct <= 0.0 {
+                info!(id = self.id(), token = %token, "EXIT signal: Basis converged to {:.3}%.", basis_pct);
+                self.count("exit");
+                return Ok(StrategyAction::ClosePositions(token.to_string()));
             }
+            return Ok(StrategyAction::Hold);
         }
-        Ok(StrategyAction::Hold)
+
+        if basis_pct < -self.basis_threshold_pct {
+            self.count("skipped_negative");
+            return Ok(StrategyAction::Hold);
+        }
+        if basis_pct <= self.basis_threshold_pct {
+            return Ok(StrategyAction::Hold);
+        }
+        // Each leg is a full-size position, so the pair needs twice the budget
+        let size_usd = portfolio.scale_size(self.size_usd * 2.0) / 2.0;
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %token, "SHORT PERP/LONG SPOT signal: Basis {:.3}% above {:.3}%.", basis_pct, self.basis_threshold_pct);
+        self.count("entry");
+        Ok(StrategyAction::ExecuteMany(
+            vec![
+                // Spot first: the less liquid leg is the one more likely to fail
+                self.leg(token, size_usd, Side::Long, Some(Venue::Jupiter), basis_pct),
+                // Shorts are opened on Drift perps
+                self.leg(token, size_usd, Side::Short, None, basis_pct),
+            ],
+            LegPolicy::AllOrNothing,
+            TradeMode::Paper,
+        ))
     }
 }
 register_strategy!(PerpBasisArb, "perp_basis_arb");
-
-// Helper to get token address from any MarketEvent
-// This trait is now part of shared-models/src/lib.rs
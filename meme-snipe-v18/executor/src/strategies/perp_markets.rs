@@ -0,0 +1,45 @@
+// executor/src/strategies/perp_markets.rs
+//! Which tokens can be shorted. Shorts are opened as Drift perp orders, so a
+//! token is shortable only if it has a perp market of its own; strategies that
+//! short arbitrary mints check here and hold otherwise.
+//!
+//! DRIFT_PERP_MARKET_INDEXES maps spot mints to Drift perp market indexes as
+//! comma-separated `MINT:INDEX` entries (default: SOL on SOL-PERP, index 0).
+//! Orders are sized in whole tokens, so only list markets whose base unit is
+//! one token of the mint (not Drift's 1M-token markets such as 1MBONK-PERP).
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use tracing::warn;
+
+const DEFAULT_MARKETS: &str = "So11111111111111111111111111111111111111112:0";
+
+lazy_static! {
+    static ref MARKETS: HashMap<String, u16> = load();
+}
+
+fn load() -> HashMap<String, u16> {
+    std::env::var("DRIFT_PERP_MARKET_INDEXES")
+        .unwrap_or_else(|_| DEFAULT_MARKETS.to_string())
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let market = entry
+                .split_once(':')
+                .and_then(|(mint, index)| Some((mint.to_string(), index.parse().ok()?)));
+            if market.is_none() {
+                warn!(
+                    entry,
+                    "Ignoring DRIFT_PERP_MARKET_INDEXES entry; expected MINT:INDEX."
+                );
+            }
+            market
+        })
+        .collect()
+}
+
+/// The Drift perp market shorts of `mint` are opened on, if it has one.
+pub fn drift_market_index(mint: &str) -> Option<u16> {
+    MARKETS.get(mint).copied()
+}
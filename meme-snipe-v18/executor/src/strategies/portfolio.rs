@@ -0,0 +1,55 @@
+// executor/src/strategies/portfolio.rs
+//! Read-only view of a strategy's own book, passed to `on_event` so sizing can
+//! account for what the strategy already holds. Open positions come from the
+//! executor's position book (refreshed from the DB every
+//! POSITION_REFRESH_INTERVAL_SECS, so orders still being worked aren't listed);
+//! the budget is the capital the allocator sized the strategy at, or its
+//! allocation weight of PORTFOLIO_CAPITAL_USD if the allocator didn't.
+
+use shared_models::Side;
+
+#[derive(Debug, Clone)]
+pub struct OpenPosition {
+    pub trade_id: i64,
+    pub token_address: String,
+    pub side: Side,
+    pub size_usd: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioContext {
+    pub open_positions: Vec<OpenPosition>, // This strategy's OPEN trades
+    pub capital_budget_usd: f64,           // Allocation weight x portfolio capital
+    pub drawdown_pct: f64,                 // Portfolio drawdown from its PnL peak
+    pub drawdown_limit_pct: f64,           // Drawdown at which the portfolio stop pauses trading
+}
+
+impl PortfolioContext {
+    pub fn deployed_usd(&self) -> f64 {
+        self.open_positions.iter().map(|p| p.size_usd).sum()
+    }
+
+    pub fn remaining_budget_usd(&self) -> f64 {
+        (self.capital_budget_usd - self.deployed_usd()).max(0.0)
+    }
+
+    /// USD this strategy already holds in `token_address` on `side`.
+    pub fn exposure_to(&self, token_address: &str, side: &Side) -> f64 {
+        self.open_positions
+            .iter()
+            .filter(|p| p.token_address == token_address && &p.side == side)
+            .map(|p| p.size_usd)
+            .sum()
+    }
+
+    /// `desired_usd` capped at the remaining budget and tapered linearly to
+    /// zero as the portfolio drawdown approaches the stop.
+    pub fn scale_size(&self, desired_usd: f64) -> f64 {
+        let headroom = if self.drawdown_limit_pct > 0.0 {
+            (1.0 - self.drawdown_pct / self.drawdown_limit_pct).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        (desired_usd * headroom).min(self.remaining_budget_usd())
+    }
+}
@@ -0,0 +1,256 @@
+// executor/src/strategies/sentiment_momentum.rs
+//! Sentiment plus price momentum. Each token keeps an exponentially weighted
+//! sentiment score from SocialMention events: every mention counts with its
+//! source's weight from `source_weights` (`default_source_weight` for unlisted
+//! sources), and older mentions fade with a `half_life_secs` half-life. On each
+//! price tick the move over the last `momentum_window` ticks is compared with
+//! the score; the strategy enters only when both agree: long when sentiment is
+//! above `sentiment_threshold` and price is up at least
+//! `momentum_threshold_pct`, short on the mirror image. A score resting on
+//! less than `min_mention_weight` of decayed mentions is ignored.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::info;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TokenState {
+    weighted_sum: f64, // Decayed sum of weight x sentiment
+    weight: f64,       // Decayed sum of weights; the score's evidence
+    updated_at: i64,   // When the sums were last decayed
+    prices: VecDeque<f64>,
+}
+
+impl TokenState {
+    fn decay_to(&mut self, now: i64, half_life_secs: f64) {
+        let elapsed = (now - self.updated_at).max(0) as f64;
+        let factor = 0.5f64.powf(elapsed / half_life_secs);
+        self.weighted_sum *= factor;
+        self.weight *= factor;
+        self.updated_at = self.updated_at.max(now);
+    }
+
+    fn score(&self) -> f64 {
+        if self.weight > 0.0 {
+            self.weighted_sum / self.weight
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Default)]
+struct SentimentMomentum {
+    half_life_secs: f64,
+    source_weights: HashMap<String, f64>,
+    default_source_weight: f64,
+    min_mention_weight: f64,
+    sentiment_threshold: f64,
+    momentum_window: usize,
+    momentum_threshold_pct: f64,
+    size_usd: f64,
+    tokens: PerTokenState<TokenState>,
+    sentiment_gauge: Option<Gauge>,
+}
+
+#[async_trait]
+impl Strategy for SentimentMomentum {
+    fn id(&self) -> &'static str {
+        "sentiment_momentum"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Social, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("half_life_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("source_weights", ParamKind::NumberMap)
+            .min(0.0)
+            .required("default_source_weight", ParamKind::Number)
+            .min(0.0)
+            .required("min_mention_weight", ParamKind::Number)
+            .min(0.0)
+            .required("sentiment_threshold", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("momentum_window", ParamKind::Integer)
+            .min(2.0)
+            .required("momentum_threshold_pct", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            half_life_secs: i64,
+            #[serde(default)]
+            source_weights: HashMap<String, f64>,
+            default_source_weight: f64,
+            min_mention_weight: f64,
+            sentiment_threshold: f64,
+            momentum_window: usize,
+            momentum_threshold_pct: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.half_life_secs = p.half_life_secs as f64;
+        self.source_weights = p.source_weights;
+        self.default_source_weight = p.default_source_weight;
+        self.min_mention_weight = p.min_mention_weight;
+        self.sentiment_threshold = p.sentiment_threshold;
+        self.momentum_window = p.momentum_window;
+        self.momentum_threshold_pct = p.momentum_threshold_pct;
+        self.size_usd = p.size_usd;
+        self.tokens = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.sentiment_gauge = Some(metrics.gauge(
+            "sentiment_score",
+            "Weighted sentiment score, for the token last updated.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with half_life_secs: {}, {} weighted sources, sentiment_threshold: {}, momentum_window: {}, momentum_threshold_pct: {}",
+            self.half_life_secs,
+            self.source_weights.len(),
+            self.sentiment_threshold,
+            self.momentum_window,
+            self.momentum_threshold_pct
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let half_life_secs = self.half_life_secs;
+        let tick = match event {
+            MarketEvent::Social(mention) => {
+                let weight = self
+                    .source_weights
+                    .get(&mention.source)
+                    .copied()
+                    .unwrap_or(self.default_source_weight);
+                let state = self
+                    .tokens
+                    .get_or_insert_with(&mention.token_address, TokenState::default);
+                state.decay_to(mention.timestamp, half_life_secs);
+                state.weighted_sum += weight * mention.sentiment.clamp(-1.0, 1.0);
+                state.weight += weight;
+                let score = state.score();
+                if let Some(gauge) = &self.sentiment_gauge {
+                    gauge.set(score);
+                }
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+
+        // Only tokens people are talking about are followed
+        let window = self.momentum_window;
+        let state = match self.tokens.get_mut(&tick.token_address) {
+            Some(state) => state,
+            None => return Ok(StrategyAction::Hold),
+        };
+        if state.prices.len() == window {
+            state.prices.pop_front();
+        }
+        state.prices.push_back(tick.price_usd);
+        state.decay_to(tick.timestamp, half_life_secs);
+        if state.prices.len() < window || state.weight < self.min_mention_weight {
+            return Ok(StrategyAction::Hold);
+        }
+        let first = *state.prices.front().expect("window is non-empty");
+        if first <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        let momentum_pct = (tick.price_usd - first) / first * 100.0;
+        let score = state.score();
+        let mention_weight = state.weight;
+
+        let side = if score >= self.sentiment_threshold
+            && momentum_pct >= self.momentum_threshold_pct
+        {
+            Side::Long
+        } else if score <= -self.sentiment_threshold && momentum_pct <= -self.momentum_threshold_pct
+        {
+            Side::Short
+        } else {
+            return Ok(StrategyAction::Hold);
+        };
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &side) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %tick.token_address, side = %side, "Sentiment {:.2} and {:.1}% momentum agree.", score, momentum_pct);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.5 + 0.5 * score.abs(),
+                side,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "sentiment_score": score,
+                    "mention_weight": mention_weight,
+                    "momentum_pct": momentum_pct,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "tokens": self.tokens.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            tokens: Vec<(String, TokenState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let window = self.momentum_window;
+        // The window may have shrunk since the snapshot was taken
+        self.tokens
+            .restore_from(s.tokens.into_iter().map(|(token, state)| {
+                (
+                    token,
+                    TokenState {
+                        prices: newest(state.prices, window),
+                        ..state
+                    },
+                )
+            }));
+        Ok(())
+    }
+}
+register_strategy!(SentimentMomentum, "sentiment_momentum");
@@ -0,0 +1,280 @@
+// executor/src/strategies/session_burst.rs
+//! Volume bursts inside a configured trading session. The session runs from
+//! `start_hour` to `end_hour` (exclusive) local time in `timezone`, an IANA
+//! name such as "Asia/Seoul" or "America/New_York"; an end at or before the
+//! start wraps past midnight, equal hours making a 24h session. `weekdays`
+//! restricts the days a session may open on, e.g. ["Sat", "Sun"] for
+//! weekends, and defaults to every day.
+//!
+//! Each token's 1m volume is averaged over its last `volume_window` ticks,
+//! in session or not. A tick inside the session with volume at least
+//! `volume_multiplier` times that average, and `min_volume_usd` in absolute
+//! terms, is bought at most once per token per session.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{Datelike, Duration, TimeZone, Timelike, Weekday};
+use chrono_tz::Tz;
+use prometheus::Counter;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::{HashSet, VecDeque};
+use tracing::info;
+
+const ALL_WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+struct Session {
+    timezone: Tz,
+    start_hour: u32,
+    end_hour: u32,
+    weekdays: Vec<Weekday>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            timezone: Tz::UTC,
+            start_hour: 0,
+            end_hour: 24,
+            weekdays: ALL_WEEKDAYS.to_vec(),
+        }
+    }
+}
+
+impl Session {
+    /// The local day the session containing `timestamp` opened on, as days
+    /// since the common era, or `None` outside the session.
+    fn opened_on(&self, timestamp: i64) -> Option<i32> {
+        let local = self.timezone.timestamp_opt(timestamp, 0).single()?;
+        let hour = local.hour();
+        let date = local.date_naive();
+        let opened = if self.start_hour < self.end_hour {
+            (self.start_hour..self.end_hour)
+                .contains(&hour)
+                .then_some(date)
+        } else if hour >= self.start_hour {
+            Some(date)
+        } else if hour < self.end_hour {
+            Some(date - Duration::days(1)) // Opened before midnight
+        } else {
+            None
+        };
+        opened
+            .filter(|date| self.weekdays.contains(&date.weekday()))
+            .map(|date| date.num_days_from_ce())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TokenState {
+    volumes: VecDeque<f64>,
+    bought_session: Option<i32>, // Day the session last bought in opened on
+}
+
+#[derive(Default)]
+struct SessionBurst {
+    session: Session,
+    volume_window: usize,
+    volume_multiplier: f64,
+    min_volume_usd: f64,
+    size_usd: f64,
+    tokens: PerTokenState<TokenState>,
+    bursts_counter: Option<Counter>,
+}
+
+#[async_trait]
+impl Strategy for SessionBurst {
+    fn id(&self) -> &'static str {
+        "session_burst"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("timezone", ParamKind::String)
+            .required("start_hour", ParamKind::Integer)
+            .min(0.0)
+            .max(23.0)
+            .required("end_hour", ParamKind::Integer)
+            .min(0.0)
+            .max(24.0)
+            .optional("weekdays", ParamKind::StringList)
+            .required("volume_window", ParamKind::Integer)
+            .min(1.0)
+            .required("volume_multiplier", ParamKind::Number)
+            .min(0.0)
+            .required("min_volume_usd", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            timezone: String,
+            start_hour: u32,
+            end_hour: u32,
+            weekdays: Option<Vec<String>>,
+            volume_window: usize,
+            volume_multiplier: f64,
+            min_volume_usd: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        let timezone: Tz = p
+            .timezone
+            .parse()
+            .map_err(|e| anyhow!("invalid timezone `{}`: {}", p.timezone, e))?;
+        let weekdays = match p.weekdays {
+            Some(days) if !days.is_empty() => days
+                .iter()
+                .map(|day| {
+                    day.parse::<Weekday>()
+                        .map_err(|_| anyhow!("invalid weekday `{}`", day))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => ALL_WEEKDAYS.to_vec(),
+        };
+        self.session = Session {
+            timezone,
+            start_hour: p.start_hour,
+            end_hour: p.end_hour,
+            weekdays,
+        };
+        self.volume_window = p.volume_window;
+        self.volume_multiplier = p.volume_multiplier;
+        self.min_volume_usd = p.min_volume_usd;
+        self.size_usd = p.size_usd;
+        self.tokens = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.bursts_counter = Some(metrics.counter(
+            "session_bursts_total",
+            "In-session volume bursts, bought or not.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with session {:02}:00-{:02}:00 {} on {:?}, volume_window: {}, volume_multiplier: {}",
+            self.session.start_hour,
+            self.session.end_hour,
+            self.session.timezone,
+            self.session.weekdays,
+            self.volume_window,
+            self.volume_multiplier
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let tick = match event {
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let volume_window = self.volume_window;
+        let state = self
+            .tokens
+            .get_or_insert_with(&tick.token_address, TokenState::default);
+        // The average leaves the current tick out
+        let average_volume = if state.volumes.len() == volume_window {
+            Some(state.volumes.iter().sum::<f64>() / volume_window as f64)
+        } else {
+            None
+        };
+        if state.volumes.len() == volume_window {
+            state.volumes.pop_front();
+        }
+        state.volumes.push_back(tick.volume_usd_1m);
+
+        let session_day = match self.session.opened_on(tick.timestamp) {
+            Some(day) if state.bought_session != Some(day) => day,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let average_volume = match average_volume {
+            Some(average) if average > 0.0 => average,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let volume_ratio = tick.volume_usd_1m / average_volume;
+        if volume_ratio < self.volume_multiplier || tick.volume_usd_1m < self.min_volume_usd {
+            return Ok(StrategyAction::Hold);
+        }
+        if let Some(counter) = &self.bursts_counter {
+            counter.inc();
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        state.bought_session = Some(session_day);
+        info!(id = self.id(), token = %tick.token_address, "BUY signal: In-session volume burst (V: {:.0} USD, {:.1}x average).", tick.volume_usd_1m, volume_ratio);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.7,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "volume_usd_1m": tick.volume_usd_1m,
+                    "volume_ratio": volume_ratio,
+                    "session_timezone": self.session.timezone.name(),
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "tokens": self.tokens.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            tokens: Vec<(String, TokenState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let volume_window = self.volume_window;
+        // The window may have shrunk since the snapshot was taken
+        self.tokens
+            .restore_from(s.tokens.into_iter().map(|(token, state)| {
+                (
+                    token,
+                    TokenState {
+                        volumes: newest(state.volumes, volume_window),
+                        ..state
+                    },
+                )
+            }));
+        Ok(())
+    }
+}
+register_strategy!(SessionBurst, "session_burst");
@@ -1,21 +1,27 @@
 use crate::{
     register_strategy,
-    strategies::{EventType, MarketEvent, OrderDetails, Strategy, StrategyAction},
+    strategies::{
+        newest, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, DEFAULT_MAX_TOKENS,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
-use serde_json::Value;
-use shared_models::Side;
+use serde_json::{json, Value};
+use shared_models::{ParamKind, ParamSchema, Side};
 use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
 use tracing::info; // P-5: Import Side
 
 #[derive(Default, Deserialize)]
 struct SocialBuzz {
     lookback_minutes: usize,
     std_dev_threshold: f64,
+    timer_secs: u64,
+    window: usize, // Buckets in the lookback window
     #[serde(skip)]
-    mention_counts_per_minute: VecDeque<u32>, // Each entry is mention count for one minute
+    mention_counts_per_minute: PerTokenState<VecDeque<u32>>, // Each entry is mention count for one timer bucket
 }
 
 #[async_trait]
@@ -27,61 +33,80 @@ impl Strategy for SocialBuzz {
         [EventType::Social].iter().cloned().collect()
     }
 
-    async fn init(&mut self, params: &Value) -> Result<()> {
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("lookback_minutes", ParamKind::Integer)
+            .min(1.0)
+            .required("std_dev_threshold", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+            .optional("timer_secs", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, _metrics: &MetricsHandle) -> Result<()> {
         #[derive(Deserialize)]
         struct P {
             lookback_minutes: usize,
             std_dev_threshold: f64,
+            max_tokens: Option<usize>,
+            timer_secs: Option<u64>,
         }
         let p: P = serde_json::from_value(params.clone())?;
         self.lookback_minutes = p.lookback_minutes;
         self.std_dev_threshold = p.std_dev_threshold;
-        self.mention_counts_per_minute = VecDeque::with_capacity(self.lookback_minutes);
-        // Initialize with zeros or previous data to avoid false positives on startup
-        for _ in 0..self.lookback_minutes {
-            self.mention_counts_per_minute.push_back(0);
-        }
+        self.timer_secs = p.timer_secs.unwrap_or(60);
+        // The lookback stays in minutes whatever the bucket width
+        self.window = ((p.lookback_minutes as u64 * 60).div_ceil(self.timer_secs) as usize).max(1);
+        self.mention_counts_per_minute =
+            PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
         info!(
             strategy = self.id(),
-            "Initialized with lookback: {}, std_dev_threshold: {}",
+            "Initialized with lookback: {}, std_dev_threshold: {}, timer_secs: {}",
             self.lookback_minutes,
-            self.std_dev_threshold
+            self.std_dev_threshold,
+            self.timer_secs
         );
         Ok(())
     }
 
-    async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        _portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
         if let MarketEvent::Social(mention) = event {
-            // Simulate incrementing the current minute's count.
-            // In a real system, `on_event` would be called with aggregated data
-            // or this would be driven by a time-based tick.
-            if let Some(last_count) = self.mention_counts_per_minute.back_mut() {
+            // Count into the current bucket; on_timer opens a new one every `timer_secs`.
+            // A token seen for the first time starts with zeros to avoid false positives.
+            let window = self.window;
+            let counts = self
+                .mention_counts_per_minute
+                .get_or_insert_with(&mention.token_address, || VecDeque::from(vec![0; window]));
+            if let Some(last_count) = counts.back_mut() {
                 *last_count += 1;
             } else {
-                // Should not happen if initialized correctly
-                self.mention_counts_per_minute.push_back(1);
+                counts.push_back(1);
             }
 
-            if self.mention_counts_per_minute.len() < self.lookback_minutes {
+            if counts.len() < window {
                 return Ok(StrategyAction::Hold);
             }
 
-            let sum: u32 = self.mention_counts_per_minute.iter().sum();
-            let mean = sum as f64 / self.lookback_minutes as f64;
+            let sum: u32 = counts.iter().sum();
+            let mean = sum as f64 / window as f64;
 
-            let variance: f64 = self
-                .mention_counts_per_minute
+            let variance: f64 = counts
                 .iter()
                 .map(|&count| (count as f64 - mean).powi(2))
                 .sum::<f64>()
-                / self.lookback_minutes as f64;
+                / window as f64;
             let std_dev = variance.sqrt().max(0.1); // Avoid division by zero
 
-            let current_minute_mentions =
-                *self.mention_counts_per_minute.back().unwrap_or(&0) as f64;
+            let current_bucket_mentions = *counts.back().unwrap_or(&0) as f64;
 
-            if current_minute_mentions > mean + self.std_dev_threshold * std_dev {
-                info!(id = self.id(), token = %mention.token_address, "BUY signal: Social mention rate spike detected (current: {:.0}, mean: {:.1}, std_dev: {:.1}).", current_minute_mentions, mean, std_dev);
+            if current_bucket_mentions > mean + self.std_dev_threshold * std_dev {
+                info!(id = self.id(), token = %mention.token_address, "BUY signal: Social mention rate spike detected (current: {:.0}, mean: {:.1}, std_dev: {:.1}).", current_bucket_mentions, mean, std_dev);
                 return Ok(StrategyAction::Execute(
                     OrderDetails {
                         // P-5: Use Execute
@@ -96,5 +121,42 @@ impl Strategy for SocialBuzz {
         }
         Ok(StrategyAction::Hold)
     }
+
+    fn timer_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.timer_secs))
+    }
+
+    async fn on_timer(&mut self, _now: i64) -> Result<StrategyAction> {
+        // Roll every token's window forward so quiet buckets count as zero mentions,
+        // and forget tokens that have gone quiet for the whole window
+        let window = self.window;
+        self.mention_counts_per_minute.retain(|_, counts| {
+            counts.push_back(0);
+            while counts.len() > window {
+                counts.pop_front();
+            }
+            counts.iter().any(|&count| count > 0)
+        });
+        Ok(StrategyAction::Hold)
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "mention_counts_per_minute": self.mention_counts_per_minute.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            mention_counts_per_minute: Vec<(String, VecDeque<u32>)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let window = self.window;
+        self.mention_counts_per_minute.restore_from(
+            s.mention_counts_per_minute
+                .into_iter()
+                .map(|(token, counts)| (token, newest(counts, window))),
+        );
+        Ok(())
+    }
 }
 register_strategy!(SocialBuzz, "social_buzz");
@@ -0,0 +1,213 @@
+// executor/src/strategies/vol_breakout.rs
+//! Bollinger-band breakouts. Each token's ticks are rolled into `candle_secs`
+//! candles, and every closed candle is measured against bands of its
+//! predecessors: the mean of the previous `window` closes, plus or minus
+//! `band_k` standard deviations. A close above the upper band is bought when
+//! volatility is expanding, the window's standard deviation having grown at
+//! least `min_vol_expansion` times with the new close included, and volume is
+//! flat to rising, the candle's volume being at least `min_volume_ratio` times
+//! the window's mean candle volume.
+//!
+//! Unlike momentum_5m, which fires on a fixed price change, the threshold
+//! widens and narrows with the token's own volatility regime.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        Candle, CandleAggregator, EventType, MarketEvent, MetricsHandle, OrderDetails,
+        PerTokenState, PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Gauge;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::HashSet;
+use tracing::info;
+
+#[derive(Default)]
+struct VolBreakout {
+    candle_secs: i64,
+    window: usize,
+    band_k: f64,
+    min_vol_expansion: f64,
+    min_volume_ratio: f64,
+    size_usd: f64,
+    candles: PerTokenState<CandleAggregator>,
+    band_width_gauge: Option<Gauge>,
+}
+
+/// Mean and population standard deviation of the candles' closes.
+fn mean_std<'a>(candles: impl Iterator<Item = &'a Candle>) -> (f64, f64) {
+    let closes: Vec<f64> = candles.map(|c| c.close).collect();
+    let n = closes.len() as f64;
+    let mean = closes.iter().sum::<f64>() / n;
+    let variance = closes.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[async_trait]
+impl Strategy for VolBreakout {
+    fn id(&self) -> &'static str {
+        "vol_breakout"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("candle_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("window", ParamKind::Integer)
+            .min(2.0)
+            .required("band_k", ParamKind::Number)
+            .min(0.0)
+            .required("min_vol_expansion", ParamKind::Number)
+            .min(0.0)
+            .required("min_volume_ratio", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            candle_secs: i64,
+            window: usize,
+            band_k: f64,
+            min_vol_expansion: f64,
+            min_volume_ratio: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.candle_secs = p.candle_secs;
+        self.window = p.window;
+        self.band_k = p.band_k;
+        self.min_vol_expansion = p.min_vol_expansion;
+        self.min_volume_ratio = p.min_volume_ratio;
+        self.size_usd = p.size_usd;
+        self.candles = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.band_width_gauge = Some(metrics.gauge(
+            "band_width_pct",
+            "Width of the bands as a percentage of their mean, for the token last closed.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with candle_secs: {}, window: {}, band_k: {}, min_vol_expansion: {}, min_volume_ratio: {}",
+            self.candle_secs,
+            self.window,
+            self.band_k,
+            self.min_vol_expansion,
+            self.min_volume_ratio
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let tick = match event {
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let (candle_secs, window) = (self.candle_secs, self.window);
+        // One more close than the window, so the bands can leave the new one out
+        let candles = self.candles.get_or_insert_with(&tick.token_address, || {
+            CandleAggregator::new(candle_secs, window + 1)
+        });
+        let latest = match candles.push(tick.timestamp, tick.price_usd, tick.volume_usd_1m) {
+            Some(candle) => candle,
+            None => return Ok(StrategyAction::Hold),
+        };
+        let closed = candles.closed();
+        if closed.len() < window + 1 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        let (mean, std_dev) = mean_std(closed.iter().take(window));
+        let (_, std_dev_now) = mean_std(closed.iter().skip(1));
+        let mean_volume = closed
+            .iter()
+            .take(window)
+            .map(|c| c.volume_usd)
+            .sum::<f64>()
+            / window as f64;
+        if mean <= 0.0 || std_dev <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+        let upper_band = mean + self.band_k * std_dev;
+        if let Some(gauge) = &self.band_width_gauge {
+            gauge.set(2.0 * self.band_k * std_dev / mean * 100.0);
+        }
+
+        let vol_expansion = std_dev_now / std_dev;
+        let volume_ratio = if mean_volume > 0.0 {
+            latest.volume_usd / mean_volume
+        } else {
+            0.0
+        };
+        if latest.close <= upper_band
+            || vol_expansion < self.min_vol_expansion
+            || volume_ratio < self.min_volume_ratio
+        {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %tick.token_address, "BUY signal: Close {:.6} above upper band {:.6}, volatility {:.2}x, volume {:.2}x.", latest.close, upper_band, vol_expansion, volume_ratio);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.65,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "close_usd": latest.close,
+                    "upper_band_usd": upper_band,
+                    "vol_expansion": vol_expansion,
+                    "volume_ratio": volume_ratio,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "candles": self.candles.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            candles: Vec<(String, CandleAggregator)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let (candle_secs, window) = (self.candle_secs, self.window);
+        // Candles of another period are dropped
+        self.candles
+            .restore_from(s.candles.into_iter().filter_map(|(token, candles)| {
+                candles
+                    .restored(candle_secs, window + 1)
+                    .map(|candles| (token, candles))
+            }));
+        Ok(())
+    }
+}
+register_strategy!(VolBreakout, "vol_breakout");
@@ -0,0 +1,259 @@
+// executor/src/strategies/volume_breakout.rs
+//! Volume-profile breakouts. Each token's ticks are rolled into
+//! `candle_secs` candles; over the last `profile_candles` of them, candle
+//! volume is binned by typical price, (high + low + close) / 3, into `bins`
+//! equal-width price bins. Bins holding at least `node_multiplier` times the
+//! mean bin volume are high-volume nodes: prices the market has accepted, and
+//! so resistance until cleared. A tick that takes the price from at or below
+//! a node's upper edge to above it is a breakout, entered long if the tick's
+//! 1m volume is at least `volume_multiplier` times the profile's average 1m
+//! volume. The profile is rebuilt whenever a candle closes.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        CandleAggregator, EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState,
+        PortfolioContext, Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::Counter;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side};
+use std::collections::HashSet;
+use tracing::info;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TokenState {
+    candles: CandleAggregator,
+    node_tops: Vec<f64>, // Upper edges of the high-volume nodes, ascending
+    avg_volume_1m: f64,  // Profile volume per minute
+    last_price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ProfileParams {
+    candle_secs: i64,
+    profile_candles: usize,
+    bins: usize,
+    node_multiplier: f64,
+}
+
+impl ProfileParams {
+    /// Rebuilds the high-volume nodes and average volume from closed candles.
+    fn rebuild(&self, state: &mut TokenState) {
+        let candles = state.candles.closed();
+        state.node_tops.clear();
+        state.avg_volume_1m = 0.0;
+        if candles.len() < self.profile_candles {
+            return; // Not enough history for a meaningful profile
+        }
+        let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+        let high = candles
+            .iter()
+            .map(|c| c.high)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let total_volume: f64 = candles.iter().map(|c| c.volume_usd).sum();
+        state.avg_volume_1m =
+            total_volume / (candles.len() as f64 * self.candle_secs as f64 / 60.0);
+        if high <= low || total_volume <= 0.0 {
+            return;
+        }
+
+        let bin_width = (high - low) / self.bins as f64;
+        let mut volume_by_bin = vec![0.0; self.bins];
+        for candle in candles {
+            let typical = (candle.high + candle.low + candle.close) / 3.0;
+            let bin = (((typical - low) / bin_width) as usize).min(self.bins - 1);
+            volume_by_bin[bin] += candle.volume_usd;
+        }
+        let mean_bin_volume = total_volume / self.bins as f64;
+        state.node_tops = volume_by_bin
+            .iter()
+            .enumerate()
+            .filter(|(_, volume)| **volume >= mean_bin_volume * self.node_multiplier)
+            .map(|(bin, _)| low + bin_width * (bin + 1) as f64)
+            .collect();
+    }
+}
+
+#[derive(Default)]
+struct VolumeBreakout {
+    profile: ProfileParams,
+    volume_multiplier: f64,
+    size_usd: f64,
+    tokens: PerTokenState<TokenState>,
+    breakouts_counter: Option<Counter>,
+}
+
+#[async_trait]
+impl Strategy for VolumeBreakout {
+    fn id(&self) -> &'static str {
+        "volume_breakout"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("candle_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("profile_candles", ParamKind::Integer)
+            .min(2.0)
+            .required("bins", ParamKind::Integer)
+            .min(2.0)
+            .required("node_multiplier", ParamKind::Number)
+            .min(0.0)
+            .required("volume_multiplier", ParamKind::Number)
+            .min(0.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            candle_secs: i64,
+            profile_candles: usize,
+            bins: usize,
+            node_multiplier: f64,
+            volume_multiplier: f64,
+            size_usd: f64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.profile = ProfileParams {
+            candle_secs: p.candle_secs,
+            profile_candles: p.profile_candles,
+            bins: p.bins,
+            node_multiplier: p.node_multiplier,
+        };
+        self.volume_multiplier = p.volume_multiplier;
+        self.size_usd = p.size_usd;
+        self.tokens = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.breakouts_counter = Some(metrics.counter(
+            "breakouts_total",
+            "Breakouts above a high-volume node, confirmed by volume or not.",
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with candle_secs: {}, profile_candles: {}, bins: {}, node_multiplier: {}, volume_multiplier: {}",
+            self.profile.candle_secs,
+            self.profile.profile_candles,
+            self.profile.bins,
+            self.profile.node_multiplier,
+            self.volume_multiplier
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let tick = match event {
+            MarketEvent::Price(tick) => tick,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        let profile = self.profile;
+        let state = self
+            .tokens
+            .get_or_insert_with(&tick.token_address, || TokenState {
+                candles: CandleAggregator::new(profile.candle_secs, profile.profile_candles),
+                ..TokenState::default()
+            });
+        if state
+            .candles
+            .push(tick.timestamp, tick.price_usd, tick.volume_usd_1m)
+            .is_some()
+        {
+            profile.rebuild(state);
+        }
+        let previous_price = state.last_price.replace(tick.price_usd);
+        // The highest node the tick cleared
+        let cleared_node = previous_price.and_then(|previous| {
+            state
+                .node_tops
+                .iter()
+                .rev()
+                .find(|top| previous <= **top && tick.price_usd > **top)
+                .copied()
+        });
+        let avg_volume_1m = state.avg_volume_1m;
+
+        let node_top = match cleared_node {
+            Some(top) => top,
+            None => return Ok(StrategyAction::Hold),
+        };
+        if let Some(counter) = &self.breakouts_counter {
+            counter.inc();
+        }
+        let volume_ratio = if avg_volume_1m > 0.0 {
+            tick.volume_usd_1m / avg_volume_1m
+        } else {
+            0.0
+        };
+        if volume_ratio < self.volume_multiplier {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 || portfolio.exposure_to(&tick.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %tick.token_address, "BUY signal: Broke above the {:.6} volume node on {:.1}x average volume.", node_top, volume_ratio);
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: tick.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: 0.6,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "node_top_usd": node_top,
+                    "volume_ratio": volume_ratio,
+                    "avg_volume_1m": avg_volume_1m,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "tokens": self.tokens.by_recency() })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            tokens: Vec<(String, TokenState)>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        let profile = self.profile;
+        let mut restored = Vec::with_capacity(s.tokens.len());
+        for (token, state) in s.tokens {
+            // Candles of another period are dropped; the profile is rebuilt from what's left
+            if let Some(candles) = state
+                .candles
+                .restored(profile.candle_secs, profile.profile_candles)
+            {
+                let mut state = TokenState { candles, ..state };
+                profile.rebuild(&mut state);
+                restored.push((token, state));
+            }
+        }
+        self.tokens.restore_from(restored);
+        Ok(())
+    }
+}
+register_strategy!(VolumeBreakout, "volume_breakout");
@@ -0,0 +1,276 @@
+// executor/src/strategies/whale_tracker.rs
+//! Mirrors meme-coin buys by a configured list of profitable wallets. A tracked
+//! wallet's inflow of at least `min_buy_usd` is copied at `size_ratio` of the
+//! whale's size, capped at `max_size_usd`. Confidence is the wallet's hit rate:
+//! each buy is scored after `hit_horizon_secs` (a hit if the price is up from
+//! the buy), smoothed towards `prior_hit_rate` until the wallet has a record.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::{Counter, GaugeVec};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared_models::{OrderType, ParamKind, ParamSchema, Side, TransferDirection};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+use tracing::info;
+
+const PRIOR_WEIGHT: f64 = 5.0; // Scored buys the prior counts for
+const MAX_PENDING_BUYS: usize = 1000; // Oldest unscored buys are dropped beyond this
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WalletRecord {
+    hits: u32,
+    misses: u32,
+}
+
+/// A whale buy waiting for its hit/miss verdict.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingBuy {
+    wallet: String,
+    token_address: String,
+    price_usd: f64,
+    at: i64,
+}
+
+#[derive(Default)]
+struct WhaleTracker {
+    wallets: HashSet<String>,
+    min_buy_usd: f64,
+    size_ratio: f64,
+    max_size_usd: f64,
+    prior_hit_rate: f64,
+    min_hit_rate: f64,
+    hit_horizon_secs: i64,
+    prices: PerTokenState<f64>, // Last seen price per token
+    records: HashMap<String, WalletRecord>,
+    pending: VecDeque<PendingBuy>,
+    signals_counter: Option<Counter>,
+    hit_rate_gauge: Option<GaugeVec>,
+}
+
+impl WhaleTracker {
+    fn hit_rate(&self, wallet: &str) -> f64 {
+        let record = self.records.get(wallet).cloned().unwrap_or_default();
+        let scored = (record.hits + record.misses) as f64;
+        (record.hits as f64 + self.prior_hit_rate * PRIOR_WEIGHT) / (scored + PRIOR_WEIGHT)
+    }
+
+    /// Scores buys older than the horizon against the last seen price.
+    fn score_pending(&mut self, now: i64) {
+        while let Some(buy) = self.pending.front() {
+            if now - buy.at < self.hit_horizon_secs {
+                break;
+            }
+            let buy = self.pending.pop_front().expect("front was just checked");
+            let price = match self.prices.get(&buy.token_address) {
+                Some(price) => *price,
+                None => continue, // Never priced again; can't score it
+            };
+            let record = self.records.entry(buy.wallet.clone()).or_default();
+            if price > buy.price_usd {
+                record.hits += 1;
+            } else {
+                record.misses += 1;
+            }
+            let hit_rate = self.hit_rate(&buy.wallet);
+            if let Some(gauge) = &self.hit_rate_gauge {
+                gauge.with_label_values(&[&buy.wallet]).set(hit_rate);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for WhaleTracker {
+    fn id(&self) -> &'static str {
+        "whale_tracker"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        // Prices mark the whales' buys for scoring
+        [EventType::WhaleTransfer, EventType::Price]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("wallets", ParamKind::StringList)
+            .min(1.0)
+            .required("min_buy_usd", ParamKind::Number)
+            .min(0.0)
+            .required("size_ratio", ParamKind::Number)
+            .min(0.0)
+            .required("max_size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("prior_hit_rate", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("min_hit_rate", ParamKind::Number)
+            .min(0.0)
+            .max(1.0)
+            .required("hit_horizon_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            wallets: Vec<String>,
+            min_buy_usd: f64,
+            size_ratio: f64,
+            max_size_usd: f64,
+            prior_hit_rate: f64,
+            min_hit_rate: f64,
+            hit_horizon_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.wallets = p.wallets.into_iter().collect();
+        self.min_buy_usd = p.min_buy_usd;
+        self.size_ratio = p.size_ratio;
+        self.max_size_usd = p.max_size_usd;
+        self.prior_hit_rate = p.prior_hit_rate;
+        self.min_hit_rate = p.min_hit_rate;
+        self.hit_horizon_secs = p.hit_horizon_secs;
+        self.prices = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.records.clear();
+        self.pending.clear();
+        self.signals_counter = Some(metrics.counter("signals_total", "Whale buys mirrored.")?);
+        self.hit_rate_gauge = Some(metrics.gauge_vec(
+            "wallet_hit_rate",
+            "Smoothed share of a tracked wallet's buys that were up after the horizon.",
+            &["wallet"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized tracking {} wallets, min_buy_usd: {}, size_ratio: {}, hit_horizon_secs: {}",
+            self.wallets.len(),
+            self.min_buy_usd,
+            self.size_ratio,
+            self.hit_horizon_secs
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let transfer = match event {
+            MarketEvent::Price(tick) => {
+                self.prices.insert(&tick.token_address, tick.price_usd);
+                return Ok(StrategyAction::Hold);
+            }
+            MarketEvent::WhaleTransfer(transfer) => transfer,
+            _ => return Ok(StrategyAction::Hold),
+        };
+        if transfer.direction != TransferDirection::Inflow
+            || !self.wallets.contains(&transfer.wallet)
+            || transfer.usd_value < self.min_buy_usd
+        {
+            return Ok(StrategyAction::Hold);
+        }
+
+        // Every tracked buy is scored, mirrored or not, so the record keeps up
+        if let Some(price_usd) = self.prices.get(&transfer.token_address) {
+            if self.pending.len() == MAX_PENDING_BUYS {
+                self.pending.pop_front();
+            }
+            self.pending.push_back(PendingBuy {
+                wallet: transfer.wallet.clone(),
+                token_address: transfer.token_address.clone(),
+                price_usd: *price_usd,
+                at: transfer.timestamp,
+            });
+        }
+
+        let hit_rate = self.hit_rate(&transfer.wallet);
+        if hit_rate < self.min_hit_rate {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd =
+            portfolio.scale_size((transfer.usd_value * self.size_ratio).min(self.max_size_usd));
+        if size_usd <= 0.0 || portfolio.exposure_to(&transfer.token_address, &Side::Long) > 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        if let Some(counter) = &self.signals_counter {
+            counter.inc();
+        }
+        info!(
+            id = self.id(),
+            token = %transfer.token_address,
+            wallet = %transfer.wallet,
+            "BUY signal: Mirroring a {:.0} USD whale buy with {:.0} USD (hit rate {:.2}).",
+            transfer.usd_value,
+            size_usd,
+            hit_rate
+        );
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: transfer.token_address.clone(),
+                suggested_size_usd: size_usd,
+                confidence: hit_rate,
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "whale_wallet": transfer.wallet,
+                    "whale_buy_usd": transfer.usd_value,
+                    "wallet_hit_rate": hit_rate,
+                })),
+                order_type: OrderType::Market,
+                venue: None,
+                exit_profile: None,
+            },
+            TradeMode::Paper,
+        ))
+    }
+
+    fn timer_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60))
+    }
+
+    async fn on_timer(&mut self, now: i64) -> Result<StrategyAction> {
+        self.score_pending(now);
+        Ok(StrategyAction::Hold)
+    }
+
+    fn snapshot(&self) -> Value {
+        json!({ "records": self.records, "pending": self.pending })
+    }
+
+    fn restore(&mut self, snapshot: &Value) -> Result<()> {
+        #[derive(Deserialize)]
+        struct S {
+            records: HashMap<String, WalletRecord>,
+            pending: VecDeque<PendingBuy>,
+        }
+        let s: S = serde_json::from_value(snapshot.clone())?;
+        // Wallets dropped from the list since are forgotten
+        self.records = s
+            .records
+            .into_iter()
+            .filter(|(wallet, _)| self.wallets.contains(wallet))
+            .collect();
+        self.pending = s
+            .pending
+            .into_iter()
+            .filter(|buy| self.wallets.contains(&buy.wallet))
+            .collect();
+        Ok(())
+    }
+}
+register_strategy!(WhaleTracker, "whale_tracker");
@@ -0,0 +1,223 @@
+// executor/src/strategies/xdex_arb.rs
+//! Cross-DEX dislocation arbitrage. Keeps the latest venue-tagged price of
+//! each token on every venue in `venues`; quotes older than
+//! `max_quote_age_secs` are ignored. When the spread between the cheapest and
+//! richest venue exceeds `fee_bps + threshold_bps`, the token is bought on the
+//! cheap venue, and the position is closed once the spread converges below
+//! `exit_spread_bps`, or after `max_hold_secs` if it never does.
+//!
+//! Only the cheap side is traded: the executor has no spot-short path (Shorts
+//! go to Drift perps), so the rich venue can't be sold against it. The trade
+//! profits from the cheap venue catching up rather than locking in the spread.
+
+use crate::{
+    register_strategy,
+    strategies::{
+        EventType, MarketEvent, MetricsHandle, OrderDetails, PerTokenState, PortfolioContext,
+        Strategy, StrategyAction, TradeMode, DEFAULT_MAX_TOKENS,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::{CounterVec, Gauge};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared_models::{ExitProfile, OrderType, ParamKind, ParamSchema, Side, Venue};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    price_usd: f64,
+    at: i64,
+}
+
+#[derive(Default)]
+struct XdexArb {
+    venues: HashSet<Venue>,
+    fee_bps: f64,
+    threshold_bps: f64,
+    exit_spread_bps: f64,
+    max_quote_age_secs: i64,
+    size_usd: f64,
+    exit_profile: ExitProfile,
+    quotes: PerTokenState<HashMap<Venue, Quote>>,
+    spread_gauge: Option<Gauge>,
+    signals_counter: Option<CounterVec>,
+}
+
+impl XdexArb {
+    fn count(&self, signal: &str) {
+        if let Some(counter) = &self.signals_counter {
+            counter.with_label_values(&[signal]).inc();
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for XdexArb {
+    fn id(&self) -> &'static str {
+        "xdex_arb"
+    }
+
+    fn subscriptions(&self) -> HashSet<EventType> {
+        [EventType::Price].iter().cloned().collect()
+    }
+
+    fn params_schema(&self) -> ParamSchema {
+        ParamSchema::new()
+            .required("venues", ParamKind::StringList)
+            .min(2.0)
+            .required("fee_bps", ParamKind::Number)
+            .min(0.0)
+            .required("threshold_bps", ParamKind::Number)
+            .min(0.0)
+            .required("exit_spread_bps", ParamKind::Number)
+            .min(0.0)
+            .required("max_quote_age_secs", ParamKind::Integer)
+            .min(1.0)
+            .required("size_usd", ParamKind::Number)
+            .min(0.0)
+            .required("max_hold_secs", ParamKind::Integer)
+            .min(1.0)
+            .optional("max_tokens", ParamKind::Integer)
+            .min(1.0)
+    }
+
+    async fn init(&mut self, params: &Value, metrics: &MetricsHandle) -> Result<()> {
+        #[derive(Deserialize)]
+        struct P {
+            venues: Vec<Venue>,
+            fee_bps: f64,
+            threshold_bps: f64,
+            exit_spread_bps: f64,
+            max_quote_age_secs: i64,
+            size_usd: f64,
+            max_hold_secs: i64,
+            max_tokens: Option<usize>,
+        }
+        let p: P = serde_json::from_value(params.clone())?;
+        self.venues = p.venues.into_iter().collect();
+        self.fee_bps = p.fee_bps;
+        self.threshold_bps = p.threshold_bps;
+        self.exit_spread_bps = p.exit_spread_bps;
+        self.max_quote_age_secs = p.max_quote_age_secs;
+        self.size_usd = p.size_usd;
+        self.exit_profile = ExitProfile {
+            take_profit_pct: None,
+            trailing_stop_pct: None,
+            max_hold_secs: Some(p.max_hold_secs),
+        };
+        self.quotes = PerTokenState::new(p.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS));
+        self.spread_gauge = Some(metrics.gauge(
+            "venue_spread_bps",
+            "Spread between the cheapest and richest venue, for the token last updated.",
+        )?);
+        self.signals_counter = Some(metrics.counter_vec(
+            "signals_total",
+            "Dislocation entries and convergence exits.",
+            &["signal"],
+        )?);
+        info!(
+            strategy = self.id(),
+            "Initialized with {} venues, fee_bps: {}, threshold_bps: {}, exit_spread_bps: {}",
+            self.venues.len(),
+            self.fee_bps,
+            self.threshold_bps,
+            self.exit_spread_bps
+        );
+        Ok(())
+    }
+
+    async fn on_event(
+        &mut self,
+        event: &MarketEvent,
+        portfolio: &PortfolioContext,
+    ) -> Result<StrategyAction> {
+        let (tick, venue) = match event {
+            MarketEvent::Price(tick) => match tick.venue {
+                Some(venue) if self.venues.contains(&venue) => (tick, venue),
+                _ => return Ok(StrategyAction::Hold), // Aggregated or untracked venue
+            },
+            _ => return Ok(StrategyAction::Hold),
+        };
+        if tick.price_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        let max_age = self.max_quote_age_secs;
+        let quotes = self
+            .quotes
+            .get_or_insert_with(&tick.token_address, HashMap::new);
+        quotes.insert(
+            venue,
+            Quote {
+                price_usd: tick.price_usd,
+                at: tick.timestamp,
+            },
+        );
+        quotes.retain(|_, quote| tick.timestamp - quote.at <= max_age);
+        let cheapest = quotes
+            .iter()
+            .min_by(|a, b| a.1.price_usd.total_cmp(&b.1.price_usd))
+            .map(|(venue, quote)| (*venue, quote.price_usd));
+        let richest = quotes
+            .iter()
+            .max_by(|a, b| a.1.price_usd.total_cmp(&b.1.price_usd))
+            .map(|(venue, quote)| (*venue, quote.price_usd));
+        let ((cheap_venue, cheap_price), (rich_venue, rich_price)) = match (cheapest, richest) {
+            (Some(cheap), Some(rich)) if quotes.len() >= 2 => (cheap, rich),
+            _ => return Ok(StrategyAction::Hold), // Need two fresh venues to compare
+        };
+        let spread_bps = (rich_price - cheap_price) / cheap_price * 10_000.0;
+        if let Some(gauge) = &self.spread_gauge {
+            gauge.set(spread_bps);
+        }
+
+        let token = tick.token_address.as_str();
+        if portfolio.exposure_to(token, &Side::Long) > 0.0 {
+            if spread_bps < self.exit_spread_bps {
+                info!(id = self.id(), token = %token, "EXIT signal: Venue spread converged to {:.1} bps.", spread_bps);
+                self.count("exit");
+                return Ok(StrategyAction::ClosePositions(token.to_string()));
+            }
+            return Ok(StrategyAction::Hold);
+        }
+
+        let edge_bps = spread_bps - self.fee_bps;
+        if edge_bps <= self.threshold_bps {
+            return Ok(StrategyAction::Hold);
+        }
+        let size_usd = portfolio.scale_size(self.size_usd);
+        if size_usd <= 0.0 {
+            return Ok(StrategyAction::Hold);
+        }
+
+        info!(id = self.id(), token = %token, "BUY signal: {:?} at {:.1} bps below {:?}, {:.1} bps edge after fees.", cheap_venue, spread_bps, rich_venue, edge_bps);
+        self.count("entry");
+        Ok(StrategyAction::Execute(
+            OrderDetails {
+                token_address: token.to_string(),
+                suggested_size_usd: size_usd,
+                // Scales from 0.5 at the threshold to 1.0 at twice the threshold
+                confidence: (0.5 + 0.5 * (edge_bps / self.threshold_bps.max(1.0) - 1.0))
+                    .clamp(0.5, 1.0),
+                side: Side::Long,
+                limit_price: None,
+                triggering_features: Some(json!({
+                    "venue_spread_bps": spread_bps,
+                    "edge_bps": edge_bps,
+                    "cheap_venue": cheap_venue,
+                    "cheap_price_usd": cheap_price,
+                    "rich_venue": rich_venue,
+                    "rich_price_usd": rich_price,
+                })),
+                order_type: OrderType::Market,
+                venue: Some(cheap_venue), // The dislocation only exists on this venue
+                exit_profile: Some(self.exit_profile.clone()),
+            },
+            TradeMode::Paper,
+        ))
+    }
+}
+register_strategy!(XdexArb, "xdex_arb");
@@ -0,0 +1,353 @@
+// executor/src/submission.rs
+//! Live transaction submission along a configured fallback chain. Jito bundles
+//! keep swaps out of the public mempool; the RPC path (with a priority fee) is
+//! MEV-unprotected, so it is only tried when SUBMISSION_FALLBACK_CHAIN lists it
+//! and every use raises an alert. When the chain is exhausted the trade aborts.
+//! The `jito_submission` feature flag takes the Jito path out of the chain.
+
+use crate::{
+    config::CONFIG,
+    fills::{FillTracker, Settlement},
+    jito_client::JitoClient,
+    jupiter, signer_client,
+};
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use prometheus::{register_counter_vec, CounterVec};
+use shared_config::flags;
+use shared_models::alert;
+use solana_client::{
+    client_error::ClientErrorKind, nonblocking::rpc_client::RpcClient, rpc_request::RpcError,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::CompiledInstruction,
+    message::{MessageHeader, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use std::{
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+lazy_static! {
+    static ref SUBMISSIONS_TOTAL: CounterVec = register_counter_vec!(
+        "executor_submissions_total",
+        "Live transaction submission attempts by path and outcome.",
+        &["path", "outcome"]
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitPath {
+    Jito,           // Bundle via the Jito block engine (MEV-protected)
+    RpcPriorityFee, // Public RPC broadcast with a compute-unit price (unprotected)
+}
+
+impl fmt::Display for SubmitPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitPath::Jito => write!(f, "jito"),
+            SubmitPath::RpcPriorityFee => write!(f, "rpc"),
+        }
+    }
+}
+
+impl FromStr for SubmitPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "jito" => Ok(SubmitPath::Jito),
+            "rpc" => Ok(SubmitPath::RpcPriorityFee),
+            other => Err(anyhow!("Unknown submission path '{}'", other)),
+        }
+    }
+}
+
+pub struct Submission {
+    pub signature: Signature,
+    pub path: SubmitPath,
+    pub fee_lamports: u64, // Jito tip or priority fee paid on top of the base fee
+    pub signing: Duration, // Round trip to the signer service
+    pub last_valid_block_height: u64, // The transaction can't land past this height
+}
+
+impl Submission {
+    /// The part of `fee_lamports` paid as a transfer out of the wallet; a
+    /// priority fee is already in the network fee.
+    pub fn tip_lamports(&self) -> u64 {
+        match self.path {
+            SubmitPath::Jito => self.fee_lamports,
+            SubmitPath::RpcPriorityFee => 0,
+        }
+    }
+}
+
+pub struct Submitter {
+    chain: Vec<SubmitPath>,
+    jito: Arc<JitoClient>,
+    rpc: RpcClient,
+    redis_conn_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    pub fills: FillTracker, // Confirms submissions and measures what filled
+}
+
+impl Submitter {
+    pub fn new(
+        jito: Arc<JitoClient>,
+        redis_conn_manager: Arc<tokio::sync::Mutex<redis::aio::ConnectionManager>>,
+    ) -> Result<Self> {
+        let chain = CONFIG
+            .submission_fallback_chain
+            .iter()
+            .map(|p| p.parse())
+            .collect::<Result<Vec<SubmitPath>>>()?;
+        if chain.is_empty() {
+            return Err(anyhow!("SUBMISSION_FALLBACK_CHAIN must list at least one path"));
+        }
+        info!(chain = ?chain, "Submission fallback chain configured.");
+        Ok(Self {
+            chain,
+            jito,
+            rpc: RpcClient::new_with_commitment(
+                CONFIG.solana_rpc_url.clone(),
+                CommitmentConfig::confirmed(),
+            ),
+            redis_conn_manager,
+            fills: FillTracker::new(),
+        })
+    }
+
+    /// Signs and submits an unsigned (base64) swap along the fallback chain.
+    ///
+    /// A path only falls through to the next once its transaction can't land:
+    /// it was rejected before or on sending, or its send errored (e.g. timed
+    /// out) and it has since settled as expired. A transaction that landed, or
+    /// whose fate couldn't be told, is returned as the submission, so it is
+    /// confirmed like any other instead of being bought again on the next path.
+    pub async fn submit(&self, unsigned_tx_b64: &str) -> Result<Submission> {
+        let mut errors = Vec::new();
+        for &path in &self.chain {
+            if path == SubmitPath::Jito && !CONFIG.flags.enabled(flags::JITO_SUBMISSION, true) {
+                errors.push(format!("{}: disabled by the {} flag", path, flags::JITO_SUBMISSION));
+                continue;
+            }
+            let signed = match path {
+                SubmitPath::Jito => self.sign_jito(unsigned_tx_b64).await,
+                SubmitPath::RpcPriorityFee => self.sign_rpc(unsigned_tx_b64).await,
+            };
+            let error = match signed {
+                Ok((tx, submission)) => match self.send(path, &tx).await {
+                    Ok(()) => return Ok(self.submitted(submission, &errors).await),
+                    Err(SendError::Rejected(e)) => e,
+                    Err(SendError::Uncertain(e)) => {
+                        warn!(path = %path, signature = %submission.signature, error = %e, "Send errored, waiting for the transaction to land or expire.");
+                        match self
+                            .fills
+                            .settle(&submission.signature, submission.last_valid_block_height)
+                            .await
+                        {
+                            Settlement::Expired => e.context("expired without landing"),
+                            Settlement::Landed | Settlement::Unknown => {
+                                return Ok(self.submitted(submission, &errors).await)
+                            }
+                        }
+                    }
+                },
+                Err(e) => e,
+            };
+            SUBMISSIONS_TOTAL
+                .with_label_values(&[&path.to_string(), "failure"])
+                .inc();
+            warn!(path = %path, error = %error, "Submission path failed.");
+            errors.push(format!("{}: {:#}", path, error));
+        }
+        SUBMISSIONS_TOTAL.with_label_values(&["abort", "aborted"]).inc();
+        Err(anyhow!(
+            "Submission aborted, every path failed: {}",
+            errors.join("; ")
+        ))
+    }
+
+    async fn submitted(&self, submission: Submission, errors: &[String]) -> Submission {
+        let path = submission.path;
+        SUBMISSIONS_TOTAL
+            .with_label_values(&[&path.to_string(), "success"])
+            .inc();
+        if path == SubmitPath::Jito {
+            info!(signature = %submission.signature, "✅ Transaction submitted via Jito.");
+        } else {
+            warn!(signature = %submission.signature, "⚠️ Transaction submitted via public RPC.");
+            let mut conn = self.redis_conn_manager.lock().await.clone();
+            alert!(
+                conn,
+                "⚠️ MEV-unprotected submission via {} ({}) after: {}",
+                path,
+                submission.signature,
+                errors.join("; ")
+            );
+        }
+        submission
+    }
+
+    /// Sends a signed transaction. Only an error the endpoint answered with
+    /// (e.g. a failed preflight) means it wasn't forwarded; a Jito error or a
+    /// transport failure may come after the transaction went out.
+    async fn send(&self, path: SubmitPath, tx: &VersionedTransaction) -> Result<(), SendError> {
+        match path {
+            SubmitPath::Jito => self
+                .jito
+                .send_transaction(tx)
+                .await
+                .map(|_| ())
+                .map_err(SendError::Uncertain),
+            SubmitPath::RpcPriorityFee => {
+                self.rpc
+                    .send_transaction(tx)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| {
+                        let answered = matches!(
+                            e.kind(),
+                            ClientErrorKind::RpcError(RpcError::RpcResponseError { .. })
+                        );
+                        if answered {
+                            SendError::Rejected(e.into())
+                        } else {
+                            SendError::Uncertain(e.into())
+                        }
+                    })
+            }
+        }
+    }
+
+    async fn sign_jito(&self, unsigned_tx_b64: &str) -> Result<(VersionedTransaction, Submission)> {
+        let mut tx = jupiter::deserialize_transaction(unsigned_tx_b64)?;
+
+        // P-5: Jito tip injection; the message is final before it is signed
+        let (blockhash, last_valid_block_height) = self
+            .rpc
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        tx.message.set_recent_blockhash(blockhash);
+        let tip_lamports = CONFIG.tunables.get().jito_tip_lamports;
+        self.jito.attach_tip(&mut tx, tip_lamports).await?;
+        let tipped_b64 = base64::encode(bincode::serialize(&tx)?);
+
+        let signing_started = Instant::now();
+        let signed_tx_b64 = signer_client::sign_transaction(&tipped_b64).await?;
+        let signing = signing_started.elapsed();
+        let tx = jupiter::deserialize_transaction(&signed_tx_b64)?;
+        let submission = Submission {
+            signature: tx.signatures[0],
+            path: SubmitPath::Jito,
+            fee_lamports: tip_lamports,
+            signing,
+            last_valid_block_height,
+        };
+        Ok((tx, submission))
+    }
+
+    async fn sign_rpc(&self, unsigned_tx_b64: &str) -> Result<(VersionedTransaction, Submission)> {
+        let mut tx = jupiter::deserialize_transaction(unsigned_tx_b64)?;
+        let priority_fee = CONFIG.tunables.get().rpc_priority_fee_micro_lamports;
+        let compute_units = set_compute_unit_price(&mut tx.message, priority_fee);
+        let (blockhash, last_valid_block_height) = self
+            .rpc
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        tx.message.set_recent_blockhash(blockhash);
+        let repriced_b64 = base64::encode(bincode::serialize(&tx)?);
+
+        let signing_started = Instant::now();
+        let signed_tx_b64 = signer_client::sign_transaction(&repriced_b64).await?;
+        let signing = signing_started.elapsed();
+        let tx = jupiter::deserialize_transaction(&signed_tx_b64)?;
+        let submission = Submission {
+            signature: tx.signatures[0],
+            path: SubmitPath::RpcPriorityFee,
+            fee_lamports: priority_fee * compute_units / 1_000_000,
+            signing,
+            last_valid_block_height,
+        };
+        Ok((tx, submission))
+    }
+}
+
+/// Why sending a signed transaction failed.
+enum SendError {
+    Rejected(anyhow::Error),  // Refused by the endpoint; it can't land
+    Uncertain(anyhow::Error), // It may have gone out and may still land
+}
+
+/// Sets the message's compute-unit price, replacing an existing
+/// SetComputeUnitPrice (Jupiter adds one) or inserting a new one. Returns the
+/// compute-unit limit the fee will be charged against.
+fn set_compute_unit_price(message: &mut VersionedMessage, micro_lamports: u64) -> u64 {
+    let data = ComputeBudgetInstruction::set_compute_unit_price(micro_lamports).data;
+    match message {
+        VersionedMessage::Legacy(m) => {
+            upsert_price(&mut m.account_keys, &mut m.header, &mut m.instructions, data)
+        }
+        VersionedMessage::V0(m) => {
+            upsert_price(&mut m.account_keys, &mut m.header, &mut m.instructions, data)
+        }
+    }
+}
+
+fn upsert_price(
+    keys: &mut Vec<Pubkey>,
+    header: &mut MessageHeader,
+    instructions: &mut Vec<CompiledInstruction>,
+    data: Vec<u8>,
+) -> u64 {
+    let program_index = match keys.iter().position(|k| *k == compute_budget::id()) {
+        Some(index) => index,
+        None => {
+            // Appended as a readonly, unsigned static key. Indices past the
+            // static keys point into lookup tables and shift by one.
+            let index = keys.len();
+            for ix in instructions.iter_mut() {
+                for account in ix.accounts.iter_mut().filter(|a| **a as usize >= index) {
+                    *account += 1;
+                }
+            }
+            keys.push(compute_budget::id());
+            header.num_readonly_unsigned_accounts += 1;
+            index
+        }
+    };
+
+    let budget_ixs = || {
+        instructions
+            .iter()
+            .filter(|ix| ix.program_id_index as usize == program_index)
+    };
+    let compute_units = budget_ixs()
+        .find(|ix| ix.data.first() == Some(&SET_COMPUTE_UNIT_LIMIT) && ix.data.len() >= 5)
+        .map(|ix| u32::from_le_bytes(ix.data[1..5].try_into().unwrap()) as u64)
+        .unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT);
+
+    match instructions.iter_mut().find(|ix| {
+        ix.program_id_index as usize == program_index
+            && ix.data.first() == Some(&SET_COMPUTE_UNIT_PRICE)
+    }) {
+        Some(ix) => ix.data = data,
+        None => instructions.insert(
+            0,
+            CompiledInstruction::new_from_raw_parts(program_index as u8, data, vec![]),
+        ),
+    }
+    compute_units
+}
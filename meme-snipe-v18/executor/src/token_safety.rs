@@ -0,0 +1,250 @@
+// executor/src/token_safety.rs
+//! Pre-trade token safety checks for Live entries. Inspects the mint (mint and
+//! freeze authority, Token-2022 transfer fee), holder concentration and Raydium
+//! LP lock status over RPC, and rejects tokens that break the configured rules.
+//! Verdicts, passing or failing, are cached per mint for TOKEN_SAFETY_CACHE_TTL_SECS.
+//! Turning the `token_safety_gate` feature flag off passes every token.
+
+use crate::{config::CONFIG, pumpfun, routing::SpotRouter};
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use shared_config::flags;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashSet,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const MINT_BASE_LEN: usize = 82;
+const EXTENSIONS_START: usize = 166; // 165-byte account padding + 1-byte account type
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+const TOP_HOLDER_COUNT: usize = 10;
+
+/// What the checker learned about a mint.
+#[derive(Debug, Clone)]
+pub struct SafetyReport {
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub transfer_fee_bps: u16,
+    pub top_holder_pct: f64, // Share of supply in the largest non-pool accounts
+    pub lp_locked_pct: Option<f64>, // None when there is no Raydium pool (e.g. still on the curve)
+    pub violations: Vec<String>,
+}
+
+impl SafetyReport {
+    pub fn is_safe(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+pub struct TokenSafetyChecker {
+    rpc: RpcClient,
+    spot_router: Arc<SpotRouter>,
+    lp_lockers: HashSet<Pubkey>, // Owners whose LP holdings count as locked/burned
+    cache: DashMap<String, (Instant, Arc<SafetyReport>)>,
+}
+
+impl TokenSafetyChecker {
+    pub fn new(spot_router: Arc<SpotRouter>) -> Result<Self> {
+        let lp_lockers = CONFIG
+            .safety_lp_locker_owners
+            .iter()
+            .map(|s| Pubkey::from_str(s).map_err(|e| anyhow!("Bad LP locker owner {}: {}", s, e)))
+            .collect::<Result<HashSet<_>>>()?;
+        Ok(Self {
+            rpc: RpcClient::new(CONFIG.solana_rpc_url.clone()),
+            spot_router,
+            lp_lockers,
+            cache: DashMap::new(),
+        })
+    }
+
+    /// Errors if `mint` fails any safety rule or can't be inspected.
+    pub async fn ensure_safe(&self, mint: &str) -> Result<()> {
+        if !CONFIG.flags.enabled(flags::TOKEN_SAFETY_GATE, true) {
+            return Ok(());
+        }
+        let report = self.check(mint).await?;
+        if report.is_safe() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Token {} failed safety checks: {}",
+                mint,
+                report.violations.join("; ")
+            ))
+        }
+    }
+
+    pub async fn check(&self, mint: &str) -> Result<Arc<SafetyReport>> {
+        let ttl = Duration::from_secs(CONFIG.token_safety_cache_ttl_secs);
+        if let Some(entry) = self.cache.get(mint) {
+            if entry.0.elapsed() < ttl {
+                return Ok(entry.1.clone());
+            }
+        }
+
+        let report = Arc::new(self.inspect(mint).await?);
+        if report.is_safe() {
+            info!(mint, top_holder_pct = report.top_holder_pct, lp_locked_pct = ?report.lp_locked_pct, "🛡️ Token passed safety checks.");
+        } else {
+            warn!(mint, violations = ?report.violations, "🛡️ Token failed safety checks.");
+        }
+        self.cache
+            .insert(mint.to_string(), (Instant::now(), report.clone()));
+        Ok(report)
+    }
+
+    async fn inspect(&self, mint: &str) -> Result<SafetyReport> {
+        let mint_pk = Pubkey::from_str(mint)?;
+        let account = self.rpc.get_account(&mint_pk).await?;
+        let data = &account.data;
+        if data.len() < MINT_BASE_LEN {
+            return Err(anyhow!("{} is not a mint account", mint));
+        }
+        let coption_key = |offset: usize| {
+            (data[offset] != 0)
+                .then(|| Pubkey::new_from_array(data[offset + 4..offset + 36].try_into().unwrap()))
+        };
+        let mint_authority = coption_key(0);
+        let freeze_authority = coption_key(46);
+        let supply = u64::from_le_bytes(data[36..44].try_into().unwrap());
+        let transfer_fee_bps = if account.owner.to_string() == TOKEN_2022_PROGRAM_ID {
+            transfer_fee_bps(data)
+        } else {
+            0
+        };
+
+        // Raydium pool (if any) for LP lock status and to exclude its vault from concentration
+        let pool = self.spot_router.raydium.pool_for_mint(mint).await.ok();
+        let mut pool_owners = vec![pumpfun::bonding_curve_address(&mint_pk)];
+        if let Some(pool) = &pool {
+            pool_owners.push(pool.authority);
+        }
+        let top_holder_pct = self
+            .holder_share(&mint_pk, supply, |owner| {
+                owner.map_or(true, |o| !pool_owners.contains(o))
+            })
+            .await?;
+        let lp_locked_pct = match &pool {
+            Some(pool) => Some(self.lp_locked_share(&pool.lp_mint).await?),
+            None => None,
+        };
+
+        let mut violations = Vec::new();
+        if CONFIG.safety_reject_mint_authority {
+            if let Some(authority) = mint_authority {
+                violations.push(format!("mint authority not revoked ({})", authority));
+            }
+        }
+        if CONFIG.safety_reject_freeze_authority {
+            if let Some(authority) = freeze_authority {
+                violations.push(format!("freeze authority set ({})", authority));
+            }
+        }
+        if transfer_fee_bps > CONFIG.safety_max_transfer_fee_bps {
+            violations.push(format!("transfer fee {} bps", transfer_fee_bps));
+        }
+        if top_holder_pct > CONFIG.safety_max_top_holder_pct {
+            violations.push(format!(
+                "top {} holders own {:.1}% of supply",
+                TOP_HOLDER_COUNT,
+                top_holder_pct * 100.0
+            ));
+        }
+        if let Some(locked) = lp_locked_pct {
+            if locked < CONFIG.safety_min_lp_locked_pct {
+                violations.push(format!("only {:.1}% of LP locked or burned", locked * 100.0));
+            }
+        }
+
+        Ok(SafetyReport {
+            mint_authority,
+            freeze_authority,
+            transfer_fee_bps,
+            top_holder_pct,
+            lp_locked_pct,
+            violations,
+        })
+    }
+
+    /// Share of `supply` held by the largest token accounts whose owner passes
+    /// `include` (called with None when the account no longer exists).
+    async fn holder_share(
+        &self,
+        mint: &Pubkey,
+        supply: u64,
+        include: impl Fn(Option<&Pubkey>) -> bool,
+    ) -> Result<f64> {
+        if supply == 0 {
+            return Ok(0.0);
+        }
+        let largest = self.rpc.get_token_largest_accounts(mint).await?;
+        let holders: Vec<(Pubkey, u64)> = largest
+            .iter()
+            .take(TOP_HOLDER_COUNT)
+            .filter_map(|a| Some((Pubkey::from_str(&a.address).ok()?, a.amount.amount.parse().ok()?)))
+            .collect();
+        let owners = self.token_account_owners(&holders).await?;
+        let held: u64 = holders
+            .iter()
+            .zip(owners)
+            .filter(|(_, owner)| include(owner.as_ref()))
+            .map(|((_, amount), _)| amount)
+            .sum();
+        Ok(held as f64 / supply as f64)
+    }
+
+    /// Share of LP supply that is burned or sits with a known locker.
+    async fn lp_locked_share(&self, lp_mint: &Pubkey) -> Result<f64> {
+        let supply: u64 = self.rpc.get_token_supply(lp_mint).await?.amount.parse()?;
+        if supply == 0 {
+            return Ok(1.0); // Fully burned
+        }
+        let locked = self
+            .holder_share(lp_mint, supply, |owner| {
+                owner.map_or(false, |o| self.lp_lockers.contains(o))
+            })
+            .await?;
+        Ok(locked.min(1.0))
+    }
+
+    async fn token_account_owners(&self, accounts: &[(Pubkey, u64)]) -> Result<Vec<Option<Pubkey>>> {
+        let keys: Vec<Pubkey> = accounts.iter().map(|(k, _)| *k).collect();
+        let infos = self.rpc.get_multiple_accounts(&keys).await?;
+        Ok(infos
+            .into_iter()
+            .map(|info| {
+                info.filter(|a| a.data.len() >= 64)
+                    .map(|a| Pubkey::new_from_array(a.data[32..64].try_into().unwrap()))
+            })
+            .collect())
+    }
+}
+
+/// Highest of the older/newer transfer fee in a Token-2022 mint's
+/// TransferFeeConfig extension, or 0 without one.
+fn transfer_fee_bps(data: &[u8]) -> u16 {
+    let mut offset = EXTENSIONS_START;
+    while offset + 4 <= data.len() {
+        let ext_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let value = &data[offset + 4..(offset + 4 + len).min(data.len())];
+        if ext_type == TRANSFER_FEE_CONFIG_EXTENSION && value.len() >= 108 {
+            // authorities (64) + withheld (8) + older {epoch, max, bps} + newer {epoch, max, bps}
+            let older = u16::from_le_bytes([value[88], value[89]]);
+            let newer = u16::from_le_bytes([value[106], value[107]]);
+            return older.max(newer);
+        }
+        if ext_type == 0 {
+            break; // Uninitialized padding
+        }
+        offset += 4 + len;
+    }
+    0
+}
@@ -0,0 +1,145 @@
+// healthd/src/checks.rs
+//! The checks healthd runs each poll: an HTTP probe of a component's health
+//! endpoint, a look at a Redis stream's length, freshness and consumer
+//! groups, and the services' entries on `service_heartbeats`.
+
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamRangeReply;
+use redis::{AsyncCommands, Value};
+use reqwest::Client;
+use serde::Serialize;
+use shared_models::{ServiceHeartbeat, SERVICE_HEARTBEATS_STREAM};
+use std::collections::HashMap;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub url: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+    pub last_ok_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupStatus {
+    pub name: String,
+    pub consumers: u64,
+    pub pending: u64,
+    pub lag: Option<u64>, // Redis can't tell once entries were deleted past the group
+    pub last_delivered_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamStatus {
+    pub stream: String,
+    pub length: u64,
+    pub last_entry_age_secs: Option<i64>,
+    pub groups: Vec<GroupStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeartbeatStatus {
+    pub service: String,
+    pub alive: bool,
+    pub last_seen_at: Option<i64>,
+    pub started_at: Option<i64>, // Of the instance that sent the last heartbeat
+    pub age_secs: Option<i64>,
+}
+
+/// Probes one health endpoint; any 2xx answer within the timeout is healthy.
+pub async fn probe(http: &Client, url: &str) -> (u64, Result<()>) {
+    let started = Instant::now();
+    let result = match http.get(url).send().await {
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(anyhow::anyhow!("HTTP {}", response.status())),
+        Err(e) => Err(e.into()),
+    };
+    (started.elapsed().as_millis() as u64, result)
+}
+
+/// Every `events:*` stream currently in Redis.
+pub async fn discover_streams(conn: &mut MultiplexedConnection) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut iter = conn.scan_match::<_, String>("events:*").await?;
+    while let Some(key) = iter.next_item().await {
+        keys.push(key);
+    }
+    keys.sort();
+    Ok(keys)
+}
+
+pub async fn inspect_stream(
+    conn: &mut MultiplexedConnection,
+    stream: &str,
+    now_ms: i64,
+) -> Result<StreamStatus> {
+    let length: u64 = conn.xlen(stream).await?;
+    let last: StreamRangeReply = conn.xrevrange_count(stream, "+", "-", 1).await?;
+    let last_entry_age_secs = last.ids.first().map(|entry| {
+        let ms: i64 = entry
+            .id
+            .split_once('-')
+            .map_or(entry.id.as_str(), |(ms, _)| ms)
+            .parse()
+            .unwrap_or(now_ms);
+        (now_ms - ms).max(0) / 1000
+    });
+
+    // XINFO GROUPS is parsed by hand: the typed reply predates the `lag` field
+    let raw: Vec<HashMap<String, Value>> = redis::cmd("XINFO")
+        .arg("GROUPS")
+        .arg(stream)
+        .query_async(conn)
+        .await?;
+    let groups = raw
+        .into_iter()
+        .map(|group| {
+            let field = |name: &str| group.get(name).cloned().unwrap_or(Value::Nil);
+            GroupStatus {
+                name: redis::from_redis_value(&field("name")).unwrap_or_default(),
+                consumers: redis::from_redis_value(&field("consumers")).unwrap_or(0),
+                pending: redis::from_redis_value(&field("pending")).unwrap_or(0),
+                lag: redis::from_redis_value(&field("lag")).unwrap_or(None),
+                last_delivered_id: redis::from_redis_value(&field("last-delivered-id"))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(StreamStatus {
+        stream: stream.to_string(),
+        length,
+        last_entry_age_secs,
+        groups,
+    })
+}
+
+/// Heartbeats appended after `cursor` (a stream id, or `-` for everything
+/// retained), with the id to continue from.
+pub async fn read_heartbeats(
+    conn: &mut MultiplexedConnection,
+    cursor: &str,
+) -> Result<(Vec<ServiceHeartbeat>, String)> {
+    let start = if cursor == "-" {
+        cursor.to_string()
+    } else {
+        format!("({}", cursor) // Exclusive
+    };
+    let reply: StreamRangeReply = conn
+        .xrange_count(SERVICE_HEARTBEATS_STREAM, start, "+", 10_000)
+        .await?;
+    let next = reply
+        .ids
+        .last()
+        .map_or_else(|| cursor.to_string(), |entry| entry.id.clone());
+    let heartbeats = reply
+        .ids
+        .iter()
+        .filter_map(|entry| serde_json::from_str(&entry.get::<String>("heartbeat")?).ok())
+        .collect();
+    Ok((heartbeats, next))
+}
@@ -0,0 +1,132 @@
+// healthd/src/config.rs
+use lazy_static::lazy_static;
+use std::env;
+
+/// Every service with an HTTP health endpoint, as deployed by docker-compose.
+const DEFAULT_TARGETS: &[(&str, &str)] = &[
+    ("executor", "http://executor:9090/health"),
+    ("risk_guardian", "http://risk_guardian:7200/health"),
+    ("wallet_guard", "http://wallet_guard:7070/health"),
+    ("position_manager", "http://position_manager:9090/health"),
+    (
+        "autonomous_allocator",
+        "http://autonomous_allocator:9090/health",
+    ),
+    ("signer", "http://signer:8989/pubkey"), // No /health; answering at all is enough
+    ("helius_consumer", "http://helius_consumer:9090/health"),
+    ("pyth_consumer", "http://pyth_consumer:9090/health"),
+    (
+        "market_data_consumer",
+        "http://market_data_consumer:9090/health",
+    ),
+    ("twitter_consumer", "http://twitter_consumer:9090/health"),
+    ("telegram_consumer", "http://telegram_consumer:9090/health"),
+    (
+        "farcaster_consumer",
+        "http://farcaster_consumer:9090/health",
+    ),
+    ("geyser_consumer", "http://geyser_consumer:9090/health"),
+    ("drift_consumer", "http://drift_consumer:9090/health"),
+    ("bridge_consumer", "http://bridge_consumer:9090/health"),
+    ("recorder", "http://recorder:9090/health"),
+];
+
+/// Services expected on `service_heartbeats`, flagged even if never heard from.
+const DEFAULT_HEARTBEAT_SERVICES: &[&str] = &[
+    "executor",
+    "meta_allocator",
+    "risk_guardian",
+    "wallet_guard",
+    "alert_relay",
+    "signer",
+    "position_manager",
+];
+
+pub struct Config {
+    pub redis_url: String,
+    pub targets: Vec<(String, String)>, // (component, health URL)
+    pub streams: Vec<String>,           // Empty = every events:* stream, rediscovered each poll
+    pub poll_interval_secs: u64,
+    pub request_timeout_ms: u64,
+    pub failure_threshold: u32, // Consecutive failed polls before a component is degraded
+    pub max_pending: u64,       // Per consumer group
+    pub max_lag: u64,           // Per consumer group, entries not yet delivered
+    pub heartbeat_services: Vec<String>,
+    pub missed_heartbeats: u32, // Intervals without a heartbeat before a service is down
+}
+
+impl Config {
+    fn load() -> Self {
+        let targets: Vec<(String, String)> = list("HEALTHD_TARGETS")
+            .map(|spec| {
+                let (name, url) = spec
+                    .split_once('=')
+                    .expect("HEALTHD_TARGETS entries must be NAME=URL");
+                (name.trim().to_string(), url.trim().to_string())
+            })
+            .collect();
+        Self {
+            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://redis:6379".to_string()),
+            targets: if targets.is_empty() {
+                DEFAULT_TARGETS
+                    .iter()
+                    .map(|(name, url)| (name.to_string(), url.to_string()))
+                    .collect()
+            } else {
+                targets
+            },
+            streams: list("HEALTHD_STREAMS").collect(),
+            poll_interval_secs: env::var("HEALTHD_POLL_INTERVAL_SECS")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .expect("HEALTHD_POLL_INTERVAL_SECS must be an integer"),
+            request_timeout_ms: env::var("HEALTHD_REQUEST_TIMEOUT_MS")
+                .unwrap_or_else(|_| "3000".to_string())
+                .parse()
+                .expect("HEALTHD_REQUEST_TIMEOUT_MS must be an integer"),
+            failure_threshold: env::var("HEALTHD_FAILURE_THRESHOLD")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .expect("HEALTHD_FAILURE_THRESHOLD must be an integer"),
+            max_pending: env::var("HEALTHD_MAX_PENDING")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .expect("HEALTHD_MAX_PENDING must be an integer"),
+            max_lag: env::var("HEALTHD_MAX_LAG")
+                .unwrap_or_else(|_| "5000".to_string())
+                .parse()
+                .expect("HEALTHD_MAX_LAG must be an integer"),
+            heartbeat_services: {
+                let services: Vec<String> = list("HEALTHD_HEARTBEAT_SERVICES").collect();
+                if services.is_empty() {
+                    DEFAULT_HEARTBEAT_SERVICES
+                        .iter()
+                        .map(|service| service.to_string())
+                        .collect()
+                } else {
+                    services
+                }
+            },
+            missed_heartbeats: env::var("HEALTHD_MISSED_HEARTBEATS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .expect("HEALTHD_MISSED_HEARTBEATS must be an integer"),
+        }
+    }
+}
+
+/// Non-empty, trimmed entries of a comma-separated variable.
+fn list(var: &str) -> impl Iterator<Item = String> {
+    env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,370 @@
+// healthd/src/main.rs
+//! Polls every component's health endpoint (HEALTHD_TARGETS) and every
+//! `events:*` stream's consumer groups every HEALTHD_POLL_INTERVAL_SECS, and
+//! serves the combined picture at /status: 200 while everything is healthy,
+//! 503 with the list of problems otherwise. A component is degraded after
+//! HEALTHD_FAILURE_THRESHOLD failed probes in a row, a consumer group once its
+//! pending entries exceed HEALTHD_MAX_PENDING or its lag HEALTHD_MAX_LAG, and
+//! a service once HEALTHD_MISSED_HEARTBEATS of its heartbeat intervals pass
+//! without an entry on `service_heartbeats`.
+//! Each problem is alerted once when it appears and again when it clears.
+
+mod checks;
+mod config;
+
+use crate::checks::{ComponentStatus, HeartbeatStatus, StreamStatus};
+use crate::config::CONFIG;
+use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use prometheus::{
+    register_int_gauge, register_int_gauge_vec, Encoder, IntGauge, IntGaugeVec, TextEncoder,
+};
+use redis::aio::MultiplexedConnection;
+use reqwest::Client;
+use serde::Serialize;
+use shared_models::{alert, spawn_heartbeat, ServiceHeartbeat};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+lazy_static! {
+    static ref COMPONENT_UP: IntGaugeVec = register_int_gauge_vec!(
+        "healthd_component_up",
+        "1 while a component's health endpoint answers, by component.",
+        &["component"]
+    )
+    .unwrap();
+    static ref GROUP_PENDING: IntGaugeVec = register_int_gauge_vec!(
+        "healthd_stream_group_pending",
+        "Entries delivered to a consumer group but not yet acknowledged.",
+        &["stream", "group"]
+    )
+    .unwrap();
+    static ref GROUP_LAG: IntGaugeVec = register_int_gauge_vec!(
+        "healthd_stream_group_lag",
+        "Entries in a stream not yet delivered to a consumer group.",
+        &["stream", "group"]
+    )
+    .unwrap();
+    static ref HEARTBEAT_AGE_SECONDS: IntGaugeVec = register_int_gauge_vec!(
+        "healthd_heartbeat_age_seconds",
+        "Seconds since a service's last entry on service_heartbeats.",
+        &["service"]
+    )
+    .unwrap();
+    static ref DEGRADED: IntGauge = register_int_gauge!(
+        "healthd_degraded",
+        "1 while any component, stream or Redis itself has a problem."
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Status {
+    status: &'static str, // "healthy" or "degraded"
+    updated_at: i64,
+    problems: Vec<String>,
+    components: Vec<ComponentStatus>,
+    heartbeats: Vec<HeartbeatStatus>,
+    streams: Vec<StreamStatus>,
+}
+
+type SharedStatus = Arc<RwLock<Option<Status>>>;
+
+async fn status_handler(
+    State(status): State<SharedStatus>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match status.read().clone() {
+        Some(status) => {
+            let code = if status.problems.is_empty() {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            (code, Json(serde_json::to_value(status).unwrap_or_default()))
+        }
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "status": "starting" })),
+        ),
+    }
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+struct Monitor {
+    http: Client,
+    conn: MultiplexedConnection,
+    components: Vec<ComponentStatus>,
+    started_at: i64,
+    heartbeat_cursor: String,
+    heartbeats: HashMap<String, ServiceHeartbeat>, // Service -> latest heartbeat
+    problems: BTreeMap<String, String>,            // Check key -> description, as of the last poll
+}
+
+impl Monitor {
+    fn new(conn: MultiplexedConnection) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_millis(CONFIG.request_timeout_ms))
+            .build()?;
+        let components = CONFIG
+            .targets
+            .iter()
+            .map(|(name, url)| ComponentStatus {
+                name: name.clone(),
+                url: url.clone(),
+                healthy: true,
+                consecutive_failures: 0,
+                latency_ms: None,
+                error: None,
+                last_ok_at: None,
+            })
+            .collect();
+        Ok(Self {
+            http,
+            conn,
+            components,
+            started_at: chrono::Utc::now().timestamp(),
+            heartbeat_cursor: "-".to_string(),
+            heartbeats: HashMap::new(),
+            problems: BTreeMap::new(),
+        })
+    }
+
+    /// Runs every check, alerts on problems that appeared or cleared since
+    /// the last poll, and returns the new status.
+    async fn poll(&mut self) -> Status {
+        let now = chrono::Utc::now();
+        let mut problems = BTreeMap::new();
+
+        let probes = futures::future::join_all(
+            self.components
+                .iter()
+                .map(|component| checks::probe(&self.http, &component.url)),
+        )
+        .await;
+        for (component, (latency_ms, result)) in self.components.iter_mut().zip(probes) {
+            component.latency_ms = Some(latency_ms);
+            match result {
+                Ok(()) => {
+                    component.consecutive_failures = 0;
+                    component.error = None;
+                    component.last_ok_at = Some(now.timestamp());
+                }
+                Err(e) => {
+                    component.consecutive_failures += 1;
+                    component.error = Some(e.to_string());
+                }
+            }
+            component.healthy = component.consecutive_failures < CONFIG.failure_threshold;
+            COMPONENT_UP
+                .with_label_values(&[&component.name])
+                .set(component.healthy as i64);
+            if !component.healthy {
+                problems.insert(
+                    format!("component:{}", component.name),
+                    format!(
+                        "{} failed {} health checks in a row ({})",
+                        component.name,
+                        component.consecutive_failures,
+                        component.error.as_deref().unwrap_or("unknown error")
+                    ),
+                );
+            }
+        }
+
+        let heartbeats = self.check_heartbeats(now.timestamp(), &mut problems).await;
+
+        let mut streams = Vec::new();
+        match self.inspect_streams(now.timestamp_millis()).await {
+            Ok(inspected) => streams = inspected,
+            Err(e) => {
+                problems.insert("redis".to_string(), format!("Redis checks failed: {}", e));
+            }
+        }
+        for stream in &streams {
+            for group in &stream.groups {
+                let labels = [stream.stream.as_str(), group.name.as_str()];
+                GROUP_PENDING
+                    .with_label_values(&labels)
+                    .set(group.pending as i64);
+                GROUP_LAG
+                    .with_label_values(&labels)
+                    .set(group.lag.unwrap_or(0) as i64);
+                if group.pending > CONFIG.max_pending {
+                    problems.insert(
+                        format!("pending:{}:{}", stream.stream, group.name),
+                        format!(
+                            "Group {} on {} has {} unacknowledged entries",
+                            group.name, stream.stream, group.pending
+                        ),
+                    );
+                }
+                if let Some(lag) = group.lag.filter(|&lag| lag > CONFIG.max_lag) {
+                    problems.insert(
+                        format!("lag:{}:{}", stream.stream, group.name),
+                        format!(
+                            "Group {} on {} is {} entries behind",
+                            group.name, stream.stream, lag
+                        ),
+                    );
+                }
+            }
+        }
+
+        self.report_transitions(&problems).await;
+        DEGRADED.set(!problems.is_empty() as i64);
+        self.problems = problems;
+        Status {
+            status: if self.problems.is_empty() {
+                "healthy"
+            } else {
+                "degraded"
+            },
+            updated_at: now.timestamp(),
+            problems: self.problems.values().cloned().collect(),
+            components: self.components.clone(),
+            heartbeats,
+            streams,
+        }
+    }
+
+    /// Folds in new heartbeats and flags every expected service that has
+    /// gone quiet; one never heard from is timed from healthd's own start.
+    async fn check_heartbeats(
+        &mut self,
+        now: i64,
+        problems: &mut BTreeMap<String, String>,
+    ) -> Vec<HeartbeatStatus> {
+        match checks::read_heartbeats(&mut self.conn, &self.heartbeat_cursor).await {
+            Ok((heartbeats, cursor)) => {
+                self.heartbeat_cursor = cursor;
+                for heartbeat in heartbeats {
+                    self.heartbeats.insert(heartbeat.service.clone(), heartbeat);
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to read service heartbeats."),
+        }
+
+        let mut services: Vec<&String> = CONFIG
+            .heartbeat_services
+            .iter()
+            .chain(self.heartbeats.keys())
+            .collect();
+        services.sort();
+        services.dedup();
+        services
+            .into_iter()
+            .map(|service| {
+                let last = self.heartbeats.get(service);
+                let age_secs = now - last.map_or(self.started_at, |h| h.timestamp);
+                let interval_secs = last.map_or(15, |h| h.interval_secs) as i64;
+                let alive = age_secs <= interval_secs * CONFIG.missed_heartbeats as i64;
+                HEARTBEAT_AGE_SECONDS
+                    .with_label_values(&[service])
+                    .set(age_secs);
+                if !alive {
+                    problems.insert(
+                        format!("heartbeat:{}", service),
+                        match last {
+                            Some(_) => {
+                                format!("{} has sent no heartbeat for {}s", service, age_secs)
+                            }
+                            None => {
+                                format!("{} has sent no heartbeat since healthd started", service)
+                            }
+                        },
+                    );
+                }
+                HeartbeatStatus {
+                    service: service.clone(),
+                    alive,
+                    last_seen_at: last.map(|h| h.timestamp),
+                    started_at: last.map(|h| h.started_at),
+                    age_secs: last.map(|_| age_secs),
+                }
+            })
+            .collect()
+    }
+
+    async fn inspect_streams(&mut self, now_ms: i64) -> Result<Vec<StreamStatus>> {
+        let names = if CONFIG.streams.is_empty() {
+            checks::discover_streams(&mut self.conn).await?
+        } else {
+            CONFIG.streams.clone()
+        };
+        let mut streams = Vec::with_capacity(names.len());
+        for name in names {
+            streams.push(checks::inspect_stream(&mut self.conn, &name, now_ms).await?);
+        }
+        Ok(streams)
+    }
+
+    async fn report_transitions(&mut self, problems: &BTreeMap<String, String>) {
+        let mut conn = self.conn.clone();
+        for (key, description) in problems {
+            if !self.problems.contains_key(key) {
+                warn!(check = %key, "{}", description);
+                alert!(conn, "⚠️ healthd: {}", description);
+            }
+        }
+        for (key, description) in &self.problems {
+            if !problems.contains_key(key) {
+                info!(check = %key, "Check recovered.");
+                alert!(conn, "✅ healthd: recovered from: {}", description);
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "🩺 Starting healthd for {} components (polling every {}s)...",
+        CONFIG.targets.len(),
+        CONFIG.poll_interval_secs
+    );
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    spawn_heartbeat!(client.clone(), "healthd");
+    let mut monitor = Monitor::new(conn)?;
+    let status: SharedStatus = Arc::new(RwLock::new(None));
+
+    let app = Router::new()
+        .route("/status", get(status_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .with_state(status.clone())
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Status server listening on http://0.0.0.0:9090/status");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!(error = %e, "Status server stopped.");
+        }
+    });
+
+    let mut interval = tokio::time::interval(Duration::from_secs(CONFIG.poll_interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let polled = monitor.poll().await;
+        *status.write() = Some(polled);
+    }
+}
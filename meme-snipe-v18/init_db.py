@@ -18,7 +18,7 @@ cursor.execute('''
         token_address TEXT NOT NULL,
         symbol TEXT NOT NULL,
         amount_usd REAL NOT NULL,
-        status TEXT NOT NULL, -- PENDING, OPEN, CLOSED_PROFIT, CLOSED_LOSS, CANCELED
+        status TEXT NOT NULL, -- CREATED, RISK_APPROVED, SUBMITTED, CONFIRMED, OPEN, CLOSING, CLOSED, FAILED, CANCELED
         signature TEXT,
         entry_time INTEGER NOT NULL,
         entry_price_usd REAL NOT NULL,
@@ -33,10 +33,10 @@ cursor.execute('''
 
 # Insert some sample data for testing
 sample_trades = [
-    ('momentum_5m', 'So11111111111111111111111111111111111111112', 'SOL', 100.0, 'CLOSED_PROFIT', 'sample_sig_1', 1721696400, 201.5, 1721696500, 204.2, 13.43, 0.85, 'Long', 204.2),
-    ('mean_revert_1h', 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v', 'USDC', 250.0, 'CLOSED_LOSS', 'sample_sig_2', 1721696300, 1.001, 1721696450, 0.998, -0.75, 0.72, 'Long', 1.002),
+    ('momentum_5m', 'So11111111111111111111111111111111111111112', 'SOL', 100.0, 'CLOSED', 'sample_sig_1', 1721696400, 201.5, 1721696500, 204.2, 13.43, 0.85, 'Long', 204.2),
+    ('mean_revert_1h', 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v', 'USDC', 250.0, 'CLOSED', 'sample_sig_2', 1721696300, 1.001, 1721696450, 0.998, -0.75, 0.72, 'Long', 1.002),
     ('korean_time_burst', 'So11111111111111111111111111111111111111112', 'SOL', 150.0, 'OPEN', 'sample_sig_3', 1721697000, 202.0, None, None, None, 0.91, 'Long', 203.1),
-    ('bridge_inflow', 'So11111111111111111111111111111111111111112', 'SOL', 200.0, 'CLOSED_PROFIT', 'sample_sig_4', 1721696200, 200.8, 1721696350, 205.5, 46.89, 0.88, 'Long', 205.5),
+    ('bridge_inflow', 'So11111111111111111111111111111111111111112', 'SOL', 200.0, 'CLOSED', 'sample_sig_4', 1721696200, 200.8, 1721696350, 205.5, 46.89, 0.88, 'Long', 205.5),
     ('perp_basis_arb', 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v', 'USDC', 300.0, 'OPEN', 'sample_sig_5', 1721697100, 1.000, None, None, None, 0.76, 'Short', 0.999)
 ]
 
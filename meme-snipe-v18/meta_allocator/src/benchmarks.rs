@@ -0,0 +1,408 @@
+//! Benchmark-relative performance. Two passive benchmarks are built from the
+//! price streams, one period (BENCHMARK_PERIOD_SECS) at a time: holding SOL
+//! (closing prices off `events:sol_price`) and an equal-weight meme basket
+//! (the mean period return of every token on `events:price` priced in two
+//! consecutive periods, or only BENCHMARK_BASKET_TOKENS if set). Each
+//! strategy's PnL per period over its allocated capital, and the portfolio's
+//! over total capital, is regressed on each benchmark's return: beta is the
+//! exposure a passive holder gets for free, alpha what is left over.
+
+use crate::mean;
+use anyhow::Result;
+use redis::streams::{StreamRangeReply, StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use shared_models::MarketEvent;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::warn;
+
+const SOL_CLOSES_KEY: &str = "benchmark:sol_closes";
+const BASKET_RETURNS_KEY: &str = "benchmark:meme_basket_returns";
+const STATE_KEY: &str = "allocator:benchmark_state";
+const READ_BATCH: usize = 10_000;
+const MAX_BATCHES_PER_PASS: usize = 50; // Catch up over several passes rather than stall allocation
+
+pub struct BenchmarkSettings {
+    pub period_secs: i64,
+    pub lookback_days: i64,
+    pub min_periods: usize, // Fewer overlapping periods and no fit is reported
+    pub basket_tokens: HashSet<String>, // Empty = every priced token
+    pub capital_usd: f64,
+}
+
+impl BenchmarkSettings {
+    pub fn from_env() -> Self {
+        Self {
+            period_secs: std::env::var("BENCHMARK_PERIOD_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<i64>()
+                .unwrap_or(3600)
+                .max(60),
+            lookback_days: std::env::var("BENCHMARK_LOOKBACK_DAYS")
+                .unwrap_or_else(|_| "14".to_string())
+                .parse()
+                .unwrap_or(14),
+            min_periods: std::env::var("BENCHMARK_MIN_PERIODS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .unwrap_or(24),
+            basket_tokens: std::env::var("BENCHMARK_BASKET_TOKENS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            capital_usd: std::env::var("PORTFOLIO_CAPITAL_USD")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000.0),
+        }
+    }
+}
+
+/// Stream cursors and the basket's last closed period, kept in Redis so a
+/// restart picks up where it stopped.
+#[derive(Serialize, Deserialize, Default)]
+struct TrackerState {
+    sol_cursor: Option<String>,
+    price_cursor: Option<String>,
+    basket_period: i64,         // Period `open` is collecting
+    open: HashMap<String, f64>, // Last price per token in `basket_period`
+    prev_period: i64,           // Period `prev` closed
+    prev: HashMap<String, f64>, // Closing price per token in `prev_period`
+}
+
+pub struct BenchmarkTracker {
+    settings: BenchmarkSettings,
+    state: TrackerState,
+}
+
+/// Fit of one return series on a benchmark's.
+#[derive(Serialize, Clone)]
+pub struct Fit {
+    pub periods: usize,
+    pub beta: f64,
+    pub alpha_pct: f64,            // Per period
+    pub alpha_annualized_pct: f64, // alpha_pct × periods per year
+    pub correlation: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct RelativePerformance {
+    pub id: String,
+    pub periods: usize,
+    pub return_pct: f64, // Summed over the periods
+    pub versus: BTreeMap<String, Fit>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BenchmarkSummary {
+    pub name: String,
+    pub periods: usize,
+    pub return_pct: f64, // Compounded over the periods
+}
+
+#[derive(Serialize, Clone)]
+pub struct BenchmarkReport {
+    pub generated_at: i64,
+    pub period_secs: i64,
+    pub benchmarks: Vec<BenchmarkSummary>,
+    pub portfolio: RelativePerformance,
+    pub strategies: Vec<RelativePerformance>,
+}
+
+impl BenchmarkTracker {
+    pub async fn load(conn: &mut redis::aio::Connection, settings: BenchmarkSettings) -> Self {
+        let state = conn
+            .get::<_, Option<String>>(STATE_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { settings, state }
+    }
+
+    fn period_of(&self, timestamp_secs: i64) -> i64 {
+        timestamp_secs - timestamp_secs.rem_euclid(self.settings.period_secs)
+    }
+
+    /// Folds new price events into the benchmark series; returns how many
+    /// events were read.
+    pub async fn ingest(&mut self, conn: &mut redis::aio::Connection) -> Result<usize> {
+        let mut read = 0;
+
+        // SOL: the last price seen in a period is its close
+        let mut sol_closes: BTreeMap<i64, f64> = BTreeMap::new();
+        for _ in 0..MAX_BATCHES_PER_PASS {
+            let events = read_events(conn, "events:sol_price", &mut self.state.sol_cursor).await?;
+            if events.is_empty() {
+                break;
+            }
+            read += events.len();
+            for event in events {
+                if let MarketEvent::SolPrice(e) = event {
+                    if e.price_usd > 0.0 {
+                        sol_closes.insert(self.period_of(e.timestamp), e.price_usd);
+                    }
+                }
+            }
+        }
+        if !sol_closes.is_empty() {
+            let closes: Vec<(i64, f64)> = sol_closes.into_iter().collect();
+            conn.hset_multiple::<_, _, _, ()>(SOL_CLOSES_KEY, &closes)
+                .await?;
+        }
+
+        // Basket: close a period's return once a later period's first price arrives
+        let mut basket_returns: Vec<(i64, f64)> = Vec::new();
+        for _ in 0..MAX_BATCHES_PER_PASS {
+            let events = read_events(conn, "events:price", &mut self.state.price_cursor).await?;
+            if events.is_empty() {
+                break;
+            }
+            read += events.len();
+            for event in events {
+                let MarketEvent::Price(tick) = event else {
+                    continue;
+                };
+                if tick.price_usd <= 0.0
+                    || (!self.settings.basket_tokens.is_empty()
+                        && !self.settings.basket_tokens.contains(&tick.token_address))
+                {
+                    continue;
+                }
+                let period = self.period_of(tick.timestamp);
+                if period < self.state.basket_period {
+                    continue; // Late tick for a closed period
+                }
+                if period > self.state.basket_period {
+                    if let Some(ret) = self.close_basket_period() {
+                        basket_returns.push((self.state.basket_period, ret));
+                    }
+                    self.state.prev_period = self.state.basket_period;
+                    self.state.prev = std::mem::take(&mut self.state.open);
+                    self.state.basket_period = period;
+                }
+                self.state.open.insert(tick.token_address, tick.price_usd);
+            }
+        }
+        if !basket_returns.is_empty() {
+            conn.hset_multiple::<_, _, _, ()>(BASKET_RETURNS_KEY, &basket_returns)
+                .await?;
+        }
+
+        conn.set::<_, _, ()>(STATE_KEY, serde_json::to_string(&self.state)?)
+            .await?;
+        Ok(read)
+    }
+
+    /// Equal-weight return of the open period over the previous one, if the
+    /// two are adjacent and share tokens.
+    fn close_basket_period(&self) -> Option<f64> {
+        if self.state.basket_period - self.state.prev_period != self.settings.period_secs {
+            return None;
+        }
+        let returns: Vec<f64> = self
+            .state
+            .open
+            .iter()
+            .filter_map(|(token, close)| self.state.prev.get(token).map(|prev| close / prev - 1.0))
+            .collect();
+        (!returns.is_empty()).then(|| mean(&returns))
+    }
+
+    /// Fits the portfolio and each strategy against the benchmarks over the
+    /// lookback. `weights` are the strategies' current allocation weights.
+    pub async fn report(
+        &self,
+        conn: &mut redis::aio::Connection,
+        weights: &[(String, f64)],
+    ) -> Result<BenchmarkReport> {
+        let now = chrono::Utc::now().timestamp();
+        let end = self.period_of(now); // Only closed periods
+        let start = end - self.settings.lookback_days * 86_400;
+
+        let sol_closes: HashMap<i64, f64> = conn.hgetall(SOL_CLOSES_KEY).await?;
+        let mut sol: BTreeMap<i64, f64> = BTreeMap::new();
+        for (period, close) in &sol_closes {
+            if let Some(prev) = sol_closes.get(&(period - self.settings.period_secs)) {
+                sol.insert(*period, close / prev - 1.0);
+            }
+        }
+        let basket: BTreeMap<i64, f64> = conn
+            .hgetall::<_, HashMap<i64, f64>>(BASKET_RETURNS_KEY)
+            .await?
+            .into_iter()
+            .collect();
+        let benchmarks: Vec<(&str, BTreeMap<i64, f64>)> = vec![
+            (
+                "sol",
+                sol.range(start..end).map(|(p, r)| (*p, *r)).collect(),
+            ),
+            (
+                "meme_basket",
+                basket.range(start..end).map(|(p, r)| (*p, *r)).collect(),
+            ),
+        ];
+
+        // PnL per period per strategy, from the stream entry times
+        let mut portfolio_pnl: BTreeMap<i64, f64> = BTreeMap::new();
+        let mut portfolio_first = end;
+        let mut strategies = Vec::new();
+        for (id, weight) in weights {
+            let reply: StreamRangeReply = conn
+                .xrange(format!("perf:{}:pnl_history", id), start * 1000, "+")
+                .await
+                .unwrap_or_default();
+            let mut pnl: BTreeMap<i64, f64> = BTreeMap::new();
+            for entry in &reply.ids {
+                let Some(pnl_usd) = entry
+                    .get::<String>("pnl")
+                    .and_then(|p| p.parse::<f64>().ok())
+                else {
+                    continue;
+                };
+                let ms: i64 = entry
+                    .id
+                    .split('-')
+                    .next()
+                    .and_then(|ms| ms.parse().ok())
+                    .unwrap_or(0);
+                let period = self.period_of(ms / 1000);
+                if period < end {
+                    *pnl.entry(period).or_default() += pnl_usd;
+                    *portfolio_pnl.entry(period).or_default() += pnl_usd;
+                }
+            }
+            let Some(first) = pnl.keys().next().copied() else {
+                continue; // No PnL in the lookback
+            };
+            portfolio_first = portfolio_first.min(first);
+            let capital = self.settings.capital_usd * weight;
+            if capital <= 0.0 {
+                continue;
+            }
+            strategies.push(self.relative(id, &pnl, first, end, capital, &benchmarks));
+        }
+        let portfolio = self.relative(
+            "portfolio",
+            &portfolio_pnl,
+            portfolio_first,
+            end,
+            self.settings.capital_usd,
+            &benchmarks,
+        );
+
+        Ok(BenchmarkReport {
+            generated_at: now,
+            period_secs: self.settings.period_secs,
+            benchmarks: benchmarks
+                .iter()
+                .map(|(name, returns)| BenchmarkSummary {
+                    name: name.to_string(),
+                    periods: returns.len(),
+                    return_pct: (returns.values().map(|r| 1.0 + r).product::<f64>() - 1.0) * 100.0,
+                })
+                .collect(),
+            portfolio,
+            strategies,
+        })
+    }
+
+    /// Returns from `first` on (flat periods count as zero) against each benchmark.
+    fn relative(
+        &self,
+        id: &str,
+        pnl: &BTreeMap<i64, f64>,
+        first: i64,
+        end: i64,
+        capital_usd: f64,
+        benchmarks: &[(&str, BTreeMap<i64, f64>)],
+    ) -> RelativePerformance {
+        let periods_per_year = 365.0 * 86_400.0 / self.settings.period_secs as f64;
+        let mut versus = BTreeMap::new();
+        for (name, benchmark) in benchmarks {
+            let pairs: Vec<(f64, f64)> = benchmark
+                .range(first..end)
+                .map(|(period, bench)| {
+                    (
+                        *bench,
+                        pnl.get(period).copied().unwrap_or(0.0) / capital_usd,
+                    )
+                })
+                .collect();
+            if pairs.len() < self.settings.min_periods.max(2) {
+                continue;
+            }
+            if let Some(fit) = fit(&pairs, periods_per_year) {
+                versus.insert(name.to_string(), fit);
+            }
+        }
+        let periods = ((end - first) / self.settings.period_secs).max(0) as usize;
+        RelativePerformance {
+            id: id.to_string(),
+            periods,
+            return_pct: pnl.range(first..end).map(|(_, p)| p).sum::<f64>() / capital_usd * 100.0,
+            versus,
+        }
+    }
+}
+
+async fn read_events(
+    conn: &mut redis::aio::Connection,
+    stream: &str,
+    cursor: &mut Option<String>,
+) -> Result<Vec<MarketEvent>> {
+    let from = cursor.clone().unwrap_or_else(|| "0".to_string());
+    let reply: StreamReadReply = conn
+        .xread_options(
+            &[stream],
+            &[from.as_str()],
+            &StreamReadOptions::default().count(READ_BATCH),
+        )
+        .await?;
+    let mut events = Vec::new();
+    for key in reply.keys {
+        for entry in key.ids {
+            match entry
+                .get::<String>("event")
+                .and_then(|json| serde_json::from_str::<MarketEvent>(&json).ok())
+            {
+                Some(event) => events.push(event),
+                None => warn!("Skipping unparseable event {} on {}", entry.id, stream),
+            }
+            *cursor = Some(entry.id);
+        }
+    }
+    Ok(events)
+}
+
+/// OLS of y on x over (x, y) pairs.
+fn fit(pairs: &[(f64, f64)], periods_per_year: f64) -> Option<Fit> {
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x <= 0.0 {
+        return None;
+    }
+    let beta = cov / var_x;
+    let alpha = mean_y - beta * mean_x;
+    Some(Fit {
+        periods: pairs.len(),
+        beta,
+        alpha_pct: alpha * 100.0,
+        alpha_annualized_pct: alpha * periods_per_year * 100.0,
+        correlation: if var_y > 0.0 {
+            cov / (var_x * var_y).sqrt()
+        } else {
+            0.0
+        },
+    })
+}
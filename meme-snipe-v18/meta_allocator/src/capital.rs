@@ -0,0 +1,145 @@
+//! How much capital the allocation weights divide. Rather than a fixed base,
+//! the total follows the portfolio's equity as recorded in
+//! `portfolio_metrics`:
+//!
+//! - where live trading is allowed, the wallet's SOL (from wallet_guard) at the
+//!   latest `events:sol_price` price plus the live positions still open (from
+//!   the executor);
+//! - otherwise the paper book: PORTFOLIO_CAPITAL_USD (default 1000) plus the
+//!   realized PnL.
+//!
+//! Live figures older than PORTFOLIO_METRICS_MAX_AGE_SECS (default 600) aren't
+//! trusted, and the allocator sizes off PORTFOLIO_CAPITAL_USD until they
+//! refresh. Once equity has fallen more than CAPITAL_DRAWDOWN_THRESHOLD_PCT
+//! (default 10) below its peak, the total is cut to CAPITAL_DRAWDOWN_SCALE
+//! (default 0.5) of equity until it recovers. The peak is kept per equity
+//! source, so moving from paper to live doesn't read as a drawdown.
+
+use anyhow::Result;
+use redis::streams::StreamRangeReply;
+use redis::AsyncCommands;
+use serde::Serialize;
+use shared_models::{MarketEvent, PORTFOLIO_METRICS_KEY};
+use std::collections::HashMap;
+use tracing::warn;
+
+const PEAK_KEY: &str = "allocator:equity_peak";
+
+pub struct CapitalSettings {
+    pub base_capital_usd: f64,
+    pub drawdown_threshold_pct: f64,
+    pub drawdown_scale: f64,
+    pub max_age_secs: i64,
+}
+
+impl CapitalSettings {
+    pub fn from_env() -> Self {
+        Self {
+            base_capital_usd: std::env::var("PORTFOLIO_CAPITAL_USD")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000.0),
+            drawdown_threshold_pct: std::env::var("CAPITAL_DRAWDOWN_THRESHOLD_PCT")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10.0),
+            drawdown_scale: std::env::var("CAPITAL_DRAWDOWN_SCALE")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse::<f64>()
+                .unwrap_or(0.5)
+                .clamp(0.0, 1.0),
+            max_age_secs: std::env::var("PORTFOLIO_METRICS_MAX_AGE_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .unwrap_or(600),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capital {
+    pub source: &'static str, // "live", "paper" or "base" (live figures missing or stale)
+    pub equity_usd: f64,
+    pub peak_usd: f64,
+    pub drawdown_pct: f64,
+    pub scale: f64,
+    pub total_usd: f64, // What the weights divide: equity × scale
+}
+
+/// Reads equity, updates its peak and records the result in `portfolio_metrics`.
+pub async fn assess(
+    conn: &mut redis::aio::Connection,
+    settings: &CapitalSettings,
+    live_allowed: bool,
+    now: i64,
+) -> Result<Capital> {
+    let metrics: HashMap<String, String> = conn.hgetall(PORTFOLIO_METRICS_KEY).await?;
+    let field = |name: &str| metrics.get(name).and_then(|v| v.parse::<f64>().ok());
+    let fresh =
+        |name: &str| field(name).is_some_and(|at| now - (at as i64) <= settings.max_age_secs);
+
+    let (source, equity_usd) = if live_allowed {
+        let wallet_usd = match (fresh("wallet_updated_at"), field("wallet_sol")) {
+            (true, Some(sol)) => latest_sol_price(conn).await?.map(|price| sol * price),
+            _ => None,
+        };
+        let open_usd = field("live_open_positions_usd").filter(|_| fresh("executor_updated_at"));
+        match (wallet_usd, open_usd) {
+            (Some(wallet_usd), Some(open_usd)) => ("live", wallet_usd + open_usd),
+            _ => {
+                warn!("Live portfolio figures are missing or stale; sizing off PORTFOLIO_CAPITAL_USD.");
+                ("base", settings.base_capital_usd)
+            }
+        }
+    } else {
+        let realized = field("realized_pnl_usd").unwrap_or(0.0);
+        ("paper", settings.base_capital_usd + realized)
+    };
+    let equity_usd = equity_usd.max(0.0);
+
+    let previous_peak: Option<f64> = conn.hget(PEAK_KEY, source).await?;
+    let peak_usd = previous_peak.unwrap_or(equity_usd).max(equity_usd);
+    let drawdown_pct = if peak_usd > 0.0 {
+        (peak_usd - equity_usd) / peak_usd * 100.0
+    } else {
+        0.0
+    };
+    let scale = if drawdown_pct > settings.drawdown_threshold_pct {
+        settings.drawdown_scale
+    } else {
+        1.0
+    };
+    let capital = Capital {
+        source,
+        equity_usd,
+        peak_usd,
+        drawdown_pct,
+        scale,
+        total_usd: equity_usd * scale,
+    };
+
+    conn.hset::<_, _, _, ()>(PEAK_KEY, source, peak_usd).await?;
+    conn.hset_multiple::<_, _, _, ()>(
+        PORTFOLIO_METRICS_KEY,
+        &[
+            ("total_value_usd", capital.equity_usd),
+            ("equity_drawdown_pct", capital.drawdown_pct),
+            ("allocated_capital_usd", capital.total_usd),
+            ("allocator_updated_at", now as f64),
+        ],
+    )
+    .await?;
+    Ok(capital)
+}
+
+async fn latest_sol_price(conn: &mut redis::aio::Connection) -> Result<Option<f64>> {
+    let reply: StreamRangeReply = conn
+        .xrevrange_count("events:sol_price", "+", "-", 1)
+        .await?;
+    Ok(reply.ids.first().and_then(|entry| {
+        match serde_json::from_str::<MarketEvent>(&entry.get::<String>("event")?).ok()? {
+            MarketEvent::SolPrice(e) if e.price_usd > 0.0 => Some(e.price_usd),
+            _ => None,
+        }
+    }))
+}
@@ -0,0 +1,281 @@
+//! A/B tests of strategy variants. A spec registered with `variant_of` set to
+//! another spec's id is a variant of that (main) spec. Variants aren't
+//! weighted into the book: each experiment splits VARIANT_PAPER_BUDGET_USD
+//! (default 1000) evenly between its variants and a control arm,
+//! `<main id>_control`, running the main spec's current params, all in paper.
+//! So every arm trades the same size in the same market.
+//!
+//! Once the control and a variant each have VARIANT_MIN_TRADES (default 50)
+//! trades, the variant's mean net trade PnL is tested against the control's
+//! with a one-sided Welch t-test, read off the normal distribution at those
+//! sample sizes. VARIANT_SIGNIFICANCE (default 0.05) is split evenly across
+//! an experiment's variants, so running more of them doesn't make a fluke
+//! winner likelier. The best variant to beat the control significantly wins.
+//! Its params are republished on strategy_registry_stream as the main spec's.
+//! The experiment's variants are then retired for good (register new ones
+//! under new ids), and the control's history is cleared for the next one.
+//! The latest result of each experiment is kept in `allocator:experiments`
+//! for the dashboard (/api/experiments).
+
+use crate::weighting::Performance;
+use crate::{mean, std_dev};
+use anyhow::Result;
+use redis::AsyncCommands;
+use serde::Serialize;
+use shared_models::{AllocationExplanation, StrategyAllocation, StrategySpec, TradeMode};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::SQRT_2;
+use tracing::warn;
+
+const CONTROL_SUFFIX: &str = "_control";
+const RETIRED_KEY: &str = "allocator:retired_variants";
+const RESULTS_KEY: &str = "allocator:experiments";
+
+pub struct ExperimentSettings {
+    pub paper_budget_usd: f64,
+    pub min_trades: usize,
+    pub significance: f64,
+}
+
+impl ExperimentSettings {
+    pub fn from_env() -> Self {
+        Self {
+            paper_budget_usd: std::env::var("VARIANT_PAPER_BUDGET_USD")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000.0),
+            min_trades: std::env::var("VARIANT_MIN_TRADES")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse::<usize>()
+                .unwrap_or(50)
+                .max(2),
+            significance: std::env::var("VARIANT_SIGNIFICANCE")
+                .unwrap_or_else(|_| "0.05".to_string())
+                .parse()
+                .unwrap_or(0.05),
+        }
+    }
+}
+
+/// A main spec and the arms paper traded against it: its control, then its variants.
+pub struct Experiment {
+    pub main: StrategySpec,
+    pub arms: Vec<StrategySpec>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArmResult {
+    pub id: String,
+    pub trades: usize,
+    pub mean_pnl: f64,
+    pub p_value: Option<f64>, // Of beating the control; None for the control or below the trade minimum
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentResult {
+    pub main_id: String,
+    pub evaluated_at: i64,
+    pub alpha: f64, // Significance each variant is held to
+    pub arms: Vec<ArmResult>,
+    pub winner: Option<String>,
+}
+
+pub async fn retired_variants(conn: &mut redis::aio::Connection) -> HashSet<String> {
+    conn.smembers(RETIRED_KEY).await.unwrap_or_default()
+}
+
+/// Splits `specs` into the weighted book and the experiments run on the side.
+pub fn split(
+    specs: Vec<StrategySpec>,
+    retired: &HashSet<String>,
+) -> (Vec<StrategySpec>, Vec<Experiment>) {
+    let (variants, book): (Vec<StrategySpec>, Vec<StrategySpec>) =
+        specs.into_iter().partition(|s| s.variant_of.is_some());
+    let mut experiments: Vec<Experiment> = Vec::new();
+    for variant in variants {
+        if retired.contains(&variant.id) {
+            continue;
+        }
+        let main_id = variant.variant_of.clone().unwrap_or_default();
+        let Some(main) = book.iter().find(|s| s.id == main_id) else {
+            warn!(variant = %variant.id, main = %main_id, "Skipping variant of a spec that isn't registered.");
+            continue;
+        };
+        if variant.family != main.family {
+            warn!(variant = %variant.id, main = %main_id, "Skipping variant of a different family than its main spec.");
+            continue;
+        }
+        match experiments.iter_mut().find(|e| e.main.id == main_id) {
+            Some(experiment) => experiment.arms.push(variant),
+            None => {
+                let control = StrategySpec {
+                    id: format!("{}{}", main.id, CONTROL_SUFFIX),
+                    challenger_params: None,
+                    variant_of: Some(main.id.clone()),
+                    ..main.clone()
+                };
+                experiments.push(Experiment {
+                    main: main.clone(),
+                    arms: vec![control, variant],
+                });
+            }
+        }
+    }
+    (book, experiments)
+}
+
+impl Experiment {
+    fn control_id(&self) -> &str {
+        &self.arms[0].id
+    }
+
+    /// One paper allocation per arm, each on an equal share of the budget.
+    pub fn allocations(
+        &self,
+        settings: &ExperimentSettings,
+        performance_of: impl Fn(&str) -> Performance,
+    ) -> Vec<StrategyAllocation> {
+        let share_usd = settings.paper_budget_usd / self.arms.len() as f64;
+        self.arms
+            .iter()
+            .map(|arm| {
+                let perf = performance_of(&arm.id);
+                StrategyAllocation {
+                    id: arm.id.clone(),
+                    family: arm.family.clone(),
+                    weight: 0.0, // Outside the weighted book
+                    sharpe_ratio: perf.raw_sharpe,
+                    shrunk_sharpe_ratio: perf.sharpe,
+                    capital_usd: Some(share_usd),
+                    mode: TradeMode::Paper,
+                    params: arm.params.clone(),
+                    token_allowlist: arm.token_allowlist.clone(),
+                    token_denylist: arm.token_denylist.clone(),
+                    max_slippage_bps: arm.max_slippage_bps,
+                    challenger_params: None,
+                    explanation: Some(AllocationExplanation {
+                        sharpe_ratio: perf.raw_sharpe,
+                        shrunk_sharpe_ratio: perf.sharpe,
+                        score: perf.score,
+                        trade_count: perf.trade_count,
+                        max_drawdown_usd: perf.max_drawdown,
+                        allocation_mode: format!("ab_test:{}", self.main.id),
+                        ..AllocationExplanation::default()
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Tests every variant with enough trades against the control, given each
+    /// arm's net trade PnLs.
+    pub fn evaluate(
+        &self,
+        settings: &ExperimentSettings,
+        pnl: &HashMap<String, Vec<f64>>,
+        now: i64,
+    ) -> ExperimentResult {
+        let no_trades: Vec<f64> = Vec::new();
+        let pnl_of = |id: &str| pnl.get(id).unwrap_or(&no_trades);
+        let control = pnl_of(self.control_id());
+        let alpha = settings.significance / (self.arms.len() - 1) as f64;
+        let arms: Vec<ArmResult> = self
+            .arms
+            .iter()
+            .map(|arm| {
+                let trades = pnl_of(&arm.id);
+                let tested = arm.id != self.control_id()
+                    && trades.len() >= settings.min_trades
+                    && control.len() >= settings.min_trades;
+                ArmResult {
+                    id: arm.id.clone(),
+                    trades: trades.len(),
+                    mean_pnl: mean(trades),
+                    p_value: if tested {
+                        p_value(trades, control)
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect();
+        let winner = arms
+            .iter()
+            .filter(|arm| arm.p_value.is_some_and(|p| p < alpha))
+            .max_by(|a, b| a.mean_pnl.total_cmp(&b.mean_pnl))
+            .map(|arm| arm.id.clone());
+        ExperimentResult {
+            main_id: self.main.id.clone(),
+            evaluated_at: now,
+            alpha,
+            arms,
+            winner,
+        }
+    }
+
+    /// Republishes the main spec with `winner`'s params and ends the experiment.
+    pub async fn promote(&self, conn: &mut redis::aio::Connection, winner: &str) -> Result<()> {
+        let Some(winner) = self.arms.iter().find(|arm| arm.id == winner) else {
+            return Ok(());
+        };
+        let promoted = StrategySpec {
+            params: winner.params.clone(),
+            ..self.main.clone()
+        };
+        conn.xadd::<_, _, _, _, ()>(
+            "strategy_registry_stream",
+            "*",
+            &[("spec", serde_json::to_string(&promoted)?)],
+        )
+        .await?;
+        for variant in &self.arms[1..] {
+            conn.sadd::<_, _, ()>(RETIRED_KEY, &variant.id).await?;
+        }
+        let control = self.control_id();
+        conn.del::<_, ()>(&[
+            format!("perf:{}:pnl_history", control),
+            format!("perf:{}:trade_count", control),
+            format!("perf:{}:fees_usd", control),
+            format!("perf:{}:failed_fills", control),
+        ])
+        .await?;
+        Ok(())
+    }
+}
+
+pub async fn save_result(
+    conn: &mut redis::aio::Connection,
+    result: &ExperimentResult,
+) -> Result<()> {
+    conn.hset::<_, _, _, ()>(RESULTS_KEY, &result.main_id, serde_json::to_string(result)?)
+        .await?;
+    Ok(())
+}
+
+/// One-sided p-value of `variant`'s mean exceeding `control`'s, from Welch's
+/// t statistic. None if neither sample varies.
+fn p_value(variant: &[f64], control: &[f64]) -> Option<f64> {
+    let standard_error = (std_dev(variant).powi(2) / variant.len() as f64
+        + std_dev(control).powi(2) / control.len() as f64)
+        .sqrt();
+    if !(standard_error > 0.0 && standard_error.is_finite()) {
+        return None;
+    }
+    let t = (mean(variant) - mean(control)) / standard_error;
+    Some(1.0 - normal_cdf(t))
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26; absolute error under 1.5e-7.
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    y.copysign(x)
+}
@@ -0,0 +1,60 @@
+//! History of why each allocation came out the way it did. Every published
+//! allocation carries its `explanation`; the last ALLOCATION_EXPLANATION_HISTORY
+//! (default 100) published sets are also kept, newest first, in a Redis list
+//! for the dashboard (/api/allocations/explanations).
+
+use anyhow::Result;
+use redis::AsyncCommands;
+use serde::Serialize;
+use shared_models::{AllocationExplanation, StrategyAllocation, TradeMode};
+
+const HISTORY_KEY: &str = "allocator:explanations";
+
+pub fn history_len() -> isize {
+    std::env::var("ALLOCATION_EXPLANATION_HISTORY")
+        .unwrap_or_else(|_| "100".to_string())
+        .parse::<isize>()
+        .unwrap_or(100)
+        .max(1)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Explained<'a> {
+    id: &'a str,
+    weight: f64,
+    mode: TradeMode,
+    capital_usd: Option<f64>,
+    explanation: Option<&'a AllocationExplanation>,
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    timestamp: i64,
+    allocations: Vec<Explained<'a>>,
+}
+
+pub async fn record(
+    conn: &mut redis::aio::Connection,
+    allocations: &[StrategyAllocation],
+    timestamp: i64,
+    history_len: isize,
+) -> Result<()> {
+    let run = Run {
+        timestamp,
+        allocations: allocations
+            .iter()
+            .map(|a| Explained {
+                id: &a.id,
+                weight: a.weight,
+                mode: a.mode,
+                capital_usd: a.capital_usd,
+                explanation: a.explanation.as_ref(),
+            })
+            .collect(),
+    };
+    conn.lpush::<_, _, ()>(HISTORY_KEY, serde_json::to_string(&run)?)
+        .await?;
+    conn.ltrim::<_, ()>(HISTORY_KEY, 0, history_len - 1).await?;
+    Ok(())
+}
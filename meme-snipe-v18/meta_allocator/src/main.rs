@@ -1,10 +1,88 @@
+mod benchmarks;
+mod capital;
+mod experiments;
+mod explanations;
+mod ranking;
+mod stability;
+mod weighting;
+
 use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use benchmarks::{BenchmarkSettings, BenchmarkTracker};
+use capital::CapitalSettings;
+use experiments::ExperimentSettings;
+use lazy_static::lazy_static;
+use prometheus::{
+    register_counter, register_counter_vec, register_gauge_vec, Counter, CounterVec, Encoder,
+    GaugeVec, TextEncoder,
+};
+use ranking::RankingMetric;
+use redis::streams::{StreamReadOptions, StreamReadReply};
 use redis::AsyncCommands;
-use shared_models::{alert, StrategyAllocation, StrategySpec, TradeMode};
+use serde_json::Value;
+use stability::{StabilitySettings, Stabilizer};
+use shared_config::{
+    stream_lag::{self, StreamCursors},
+    Profile, Readiness,
+};
+use shared_models::{
+    alert, dead_letter, spawn_heartbeat, AllocationExplanation, ExecutionReport, ExecutionStatus,
+    ParamSchema, StrategyAllocation, StrategySpec, TradeMode,
+};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{info, level_filters::LevelFilter, warn};
-use tracing_subscriber::EnvFilter;
+use tracing::{error, info, warn};
+use weighting::{AllocationMode, FamilyCaps, Performance, SharpePrior};
+
+lazy_static! {
+    static ref ALLOCATION_RUNS_TOTAL: Counter = register_counter!(
+        "allocator_allocation_runs_total",
+        "Allocation sets published to allocations_channel."
+    )
+    .unwrap();
+    static ref ALLOCATIONS: GaugeVec = register_gauge_vec!(
+        "allocator_allocations",
+        "Strategies in the last published allocation set, by trade mode.",
+        &["mode"]
+    )
+    .unwrap();
+    static ref STRATEGY_WEIGHT: GaugeVec = register_gauge_vec!(
+        "allocator_strategy_weight",
+        "Capital weight of each strategy in the last published allocation set.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref CAPITAL_USD: GaugeVec = register_gauge_vec!(
+        "allocator_capital_usd",
+        "Portfolio equity, its peak and the capital allocated from it at the last run.",
+        &["kind"]
+    )
+    .unwrap();
+    static ref GRADUATIONS_TOTAL: CounterVec = register_counter_vec!(
+        "allocator_graduations_total",
+        "Strategies promoted from paper to live between two allocation runs.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref VARIANT_PROMOTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "allocator_variant_promotions_total",
+        "A/B tests won by a variant whose params replaced the main spec's.",
+        &["strategy_id"]
+    )
+    .unwrap();
+    static ref DEAD_LETTERS_TOTAL: CounterVec = register_counter_vec!(
+        "allocator_dead_letters_total",
+        "Unparseable stream entries moved to dead_letters.",
+        &["stream"]
+    )
+    .unwrap();
+    static ref SPECS_REJECTED_TOTAL: Counter = register_counter!(
+        "allocator_specs_rejected_total",
+        "Strategy specs dropped for failing their family's param schema."
+    )
+    .unwrap();
+}
 
 // Simple statistical functions to avoid heavy dependencies
 fn mean(values: &[f64]) -> f64 {
@@ -26,21 +104,178 @@ fn std_dev(values: &[f64]) -> f64 {
     }
 }
 
+/// Folds new execution reports into the per-strategy perf keys: fill counts
+/// drive graduation and fees are netted out of PnL. The stream cursor lives in
+/// Redis so restarts neither skip nor double-count reports.
+async fn ingest_execution_reports(
+    conn: &mut redis::aio::Connection,
+    cursors: &StreamCursors,
+) -> Result<usize> {
+    let cursor: String = conn
+        .get::<_, Option<String>>("allocator:execution_reports_cursor")
+        .await?
+        .unwrap_or_else(|| "0".to_string());
+    let opts = StreamReadOptions::default().count(1000);
+    let reply: StreamReadReply = conn
+        .xread_options(&["execution_reports"], &[cursor.as_str()], &opts)
+        .await?;
+
+    let mut ingested = 0;
+    let mut last_id = cursor;
+    for stream in reply.keys {
+        for entry in stream.ids {
+            last_id = entry.id.clone();
+            let raw: String = entry.get("report").unwrap_or_default();
+            let report = match serde_json::from_str::<ExecutionReport>(&raw) {
+                Ok(report) => report,
+                Err(e) => {
+                    warn!("Skipping unparseable execution report {}", entry.id);
+                    DEAD_LETTERS_TOTAL
+                        .with_label_values(&["execution_reports"])
+                        .inc();
+                    dead_letter!(
+                        *conn,
+                        "meta_allocator",
+                        "execution_reports",
+                        entry.id,
+                        "report",
+                        raw,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if report.status == ExecutionStatus::DryRun {
+                continue; // Neither a fill nor a failure
+            }
+            if report.is_filled() {
+                conn.incr::<_, _, ()>(format!("perf:{}:trade_count", report.strategy_id), 1)
+                    .await?;
+                conn.incr::<_, _, ()>(
+                    format!("perf:{}:fees_usd", report.strategy_id),
+                    report.fees_usd,
+                )
+                .await?;
+            } else {
+                conn.incr::<_, _, ()>(format!("perf:{}:failed_fills", report.strategy_id), 1)
+                    .await?;
+            }
+            ingested += 1;
+        }
+    }
+
+    conn.set::<_, _, ()>("allocator:execution_reports_cursor", &last_id)
+        .await?;
+    cursors.set("execution_reports", &last_id);
+    Ok(ingested)
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn ready_handler(State(readiness): State<Arc<Readiness>>) -> (StatusCode, Json<Value>) {
+    let status = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness.report()))
+}
+
+fn service_url(key: &str, default: &str) -> String {
+    std::env::var(key)
+        .unwrap_or_else(|_| default.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::INFO.into())
-        .from_env_lossy();
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let log_level = shared_config::log_level::init();
 
     info!("🚀 Starting Meta-Allocator v18...");
 
     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://redis:6379".to_string());
-    let client = redis::Client::open(redis_url)?;
+    let client = redis::Client::open(redis_url.as_str())?;
+    // Only mainnet-live may grant Live; every other profile allocates paper only
+    let profile = Profile::current().map_err(anyhow::Error::msg)?;
+    info!(profile = %profile, live_allowed = profile.allows_live(), "Environment profile.");
+
+    let readiness = Arc::new(Readiness::new("meta_allocator").redis(&redis_url));
+    readiness.preflight().await.map_err(anyhow::Error::msg)?;
+    tokio::spawn(shared_config::readiness::monitor(readiness.clone()));
+    // Live mode is only granted while every service a live trade passes through is ready
+    let live_path = Readiness::new("meta_allocator")
+        .http(
+            "executor",
+            &format!("{}/ready", service_url("EXECUTOR_URL", "http://executor:9090")),
+        )
+        .http(
+            "position_manager",
+            &format!(
+                "{}/ready",
+                service_url("POSITION_MANAGER_URL", "http://position_manager:9090")
+            ),
+        )
+        .http(
+            "risk_guardian",
+            &format!(
+                "{}/ready",
+                service_url("RISK_GUARDIAN_URL", "http://risk_guardian:7200")
+            ),
+        );
 
     // P-7: For Redis Streams
     let mut strategy_registry_stream_id = HashMap::new();
     strategy_registry_stream_id.insert("strategy_registry_stream".to_string(), "0".to_string()); // Start from beginning
+    let mut benchmark_tracker: Option<BenchmarkTracker> = None;
+    let mut stabilizer: Option<Stabilizer> = None;
+    let mut previous_modes: HashMap<String, TradeMode> = HashMap::new();
+    let allocation_mode = AllocationMode::from_env();
+    let volatility_window = weighting::volatility_window();
+    let sharpe_prior = SharpePrior::from_env();
+    let ranking_metric = RankingMetric::from_env();
+    let family_caps = FamilyCaps::from_env();
+    let capital_settings = CapitalSettings::from_env();
+    let explanation_history = explanations::history_len();
+    let experiment_settings = ExperimentSettings::from_env();
+    info!(
+        mode = %allocation_mode,
+        ranking = %ranking_metric,
+        sharpe_prior = sharpe_prior.mean,
+        sharpe_prior_trades = sharpe_prior.trades,
+        "Allocation weighting mode."
+    );
+
+    spawn_heartbeat!(client.clone(), "meta_allocator");
+
+    let stream_cursors = StreamCursors::new("allocator");
+    tokio::spawn(stream_lag::monitor(stream_cursors.clone(), redis_url.clone()));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .route("/live", get(health_handler))
+        .route("/ready", get(ready_handler))
+        .with_state(readiness)
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!(error = %e, "Metrics server stopped.");
+        }
+    });
 
     loop {
         info!("Allocator loop starting...");
@@ -68,40 +303,142 @@ async fn main() -> Result<()> {
                 for (_, messages) in streams {
                     for (id, payload) in messages {
                         if let Some(spec_json) = payload.get("spec") {
-                            if let Ok(spec) = serde_json::from_slice::<StrategySpec>(spec_json) {
-                                specs.push(spec);
-                            } else {
-                                warn!(
-                                    "Failed to deserialize strategy spec from stream ID {}: {:?}",
-                                    id,
-                                    String::from_utf8_lossy(spec_json)
-                                );
+                            match serde_json::from_slice::<StrategySpec>(spec_json) {
+                                Ok(spec) => specs.push(spec),
+                                Err(e) => {
+                                    warn!(
+                                        "Failed to deserialize strategy spec from stream ID {}: {:?}",
+                                        id,
+                                        String::from_utf8_lossy(spec_json)
+                                    );
+                                    DEAD_LETTERS_TOTAL
+                                        .with_label_values(&["strategy_registry_stream"])
+                                        .inc();
+                                    dead_letter!(
+                                        conn,
+                                        "meta_allocator",
+                                        "strategy_registry_stream",
+                                        String::from_utf8_lossy(&id.id),
+                                        "spec",
+                                        String::from_utf8_lossy(spec_json),
+                                        e
+                                    );
+                                }
                             }
                         }
                         strategy_registry_stream_id.insert(
                             "strategy_registry_stream".to_string(),
                             String::from_utf8_lossy(&id.id).to_string(),
                         ); // Update last read ID
+                        stream_cursors.set(
+                            "strategy_registry_stream",
+                            &strategy_registry_stream_id["strategy_registry_stream"],
+                        );
                     }
                 }
             }
             Err(e) => warn!("Error reading from strategy_registry_stream: {}", e),
         }
 
+        // Validate params against the schemas published by the executor so a bad
+        // spec never reaches it as an allocation.
+        let schemas: HashMap<String, String> = conn
+            .hgetall("strategy_param_schemas")
+            .await
+            .unwrap_or_default();
+        let mut valid_specs = Vec::with_capacity(specs.len());
+        for spec in specs {
+            let schema = match schemas
+                .get(&spec.family)
+                .and_then(|json| serde_json::from_str::<ParamSchema>(json).ok())
+            {
+                Some(schema) => schema,
+                None => {
+                    warn!(
+                        "No param schema published for family {}; allocating {} unvalidated.",
+                        spec.family, spec.id
+                    );
+                    valid_specs.push(spec);
+                    continue;
+                }
+            };
+            if let Err(e) = schema.validate(&spec.params) {
+                SPECS_REJECTED_TOTAL.inc();
+                alert!(conn, "⚠️ Rejected strategy spec {}: {}", spec.id, e);
+                continue;
+            }
+            // A bad challenger only loses the shadow run, not the strategy
+            let mut spec = spec;
+            if let Some(challenger) = &spec.challenger_params {
+                if let Err(e) = schema.validate(challenger) {
+                    alert!(conn, "⚠️ Dropped challenger params for {}: {}", spec.id, e);
+                    spec.challenger_params = None;
+                }
+            }
+            // Composite specs (e.g. ensembles) carry child specs; each child must
+            // pass its own family's schema too.
+            let child_errors: Vec<String> = schema
+                .child_specs(&spec.params)
+                .iter()
+                .filter_map(|child| {
+                    match schemas
+                        .get(&child.family)
+                        .and_then(|json| serde_json::from_str::<ParamSchema>(json).ok())
+                    {
+                        Some(child_schema) => child_schema
+                            .validate(&child.params)
+                            .err()
+                            .map(|e| format!("child {}: {}", child.family, e)),
+                        None => Some(format!("unknown child family {}", child.family)),
+                    }
+                })
+                .collect();
+            if child_errors.is_empty() {
+                valid_specs.push(spec);
+            } else {
+                SPECS_REJECTED_TOTAL.inc();
+                alert!(
+                    conn,
+                    "⚠️ Rejected strategy spec {}: {}",
+                    spec.id,
+                    child_errors.join("; ")
+                );
+            }
+        }
+        // Variants are A/B tested on the side rather than weighted into the book
+        let retired_variants = experiments::retired_variants(&mut conn).await;
+        let (specs, ab_tests) = experiments::split(valid_specs, &retired_variants);
+
         if specs.is_empty() {
             warn!("No valid strategy specs found in registry. Waiting...");
             tokio::time::sleep(Duration::from_secs(30)).await;
             continue;
         }
 
+        match ingest_execution_reports(&mut conn, &stream_cursors).await {
+            Ok(n) if n > 0 => info!("Ingested {} execution reports.", n),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to ingest execution reports: {}", e),
+        }
+
+        let live_path_ready = profile.allows_live() && live_path.check().await;
+        if profile.allows_live() && !live_path_ready {
+            warn!(live_path = %live_path.report(), "Live trade path not ready; allocating every strategy to paper.");
+        }
+
         // 1. Get performance data for each strategy
         let mut strategy_metrics = HashMap::new();
+        let mut arm_pnl: HashMap<String, Vec<f64>> = HashMap::new(); // Net trade PnLs of A/B test arms
         let min_trades_for_graduation = std::env::var("MIN_TRADES_FOR_GRADUATION")
             .unwrap_or_else(|_| "100".to_string())
             .parse::<u64>()
             .unwrap_or(100); // Reduced from 500 to 100 for faster graduation
+        let min_score_for_graduation = std::env::var("MIN_SCORE_FOR_GRADUATION")
+            .unwrap_or_else(|_| "1.25".to_string())
+            .parse::<f64>()
+            .unwrap_or(1.25);
 
-        for spec in &specs {
+        for spec in specs.iter().chain(ab_tests.iter().flat_map(|t| &t.arms)) {
             let pnl_history_key = format!("perf:{}:pnl_history", spec.id);
             let trade_count_key = format!("perf:{}:trade_count", spec.id);
 
@@ -111,7 +448,7 @@ async fn main() -> Result<()> {
                 .await
                 .unwrap_or_default();
 
-            let pnl_values: Vec<f64> = pnl_history_stream_data
+            let gross_pnl_values: Vec<f64> = pnl_history_stream_data
                 .into_iter()
                 .filter_map(|mut entry| {
                     entry.remove("pnl").and_then(|pnl_bytes| {
@@ -128,6 +465,19 @@ async fn main() -> Result<()> {
                 _ => 0,
             };
 
+            // Net execution fees out of each trade's PnL
+            let fees_usd: f64 = conn
+                .get::<_, Option<f64>>(format!("perf:{}:fees_usd", spec.id))
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0.0);
+            let avg_fee_usd = fees_usd / trade_count.max(1) as f64;
+            let pnl_values: Vec<f64> = gross_pnl_values.iter().map(|p| p - avg_fee_usd).collect();
+            if spec.variant_of.is_some() {
+                arm_pnl.insert(spec.id.clone(), pnl_values.clone());
+            }
+
             if pnl_values.len() > 1 {
                 let mean_pnl = mean(&pnl_values);
                 let std_dev_pnl = std_dev(&pnl_values);
@@ -145,98 +495,238 @@ async fn main() -> Result<()> {
                     0.0
                 };
 
+                let sortino = ranking::sortino(&pnl_values);
+                let calmar = ranking::calmar(&pnl_values);
+                let raw_score = ranking_metric.pick(sharpe_ratio, sortino, calmar);
+
                 // Determine trade mode based on performance criteria
-                let current_mode =
-                    if trade_count >= min_trades_for_graduation && sharpe_ratio >= 1.25 {
-                        TradeMode::Live
-                    } else {
-                        TradeMode::Paper
-                    };
+                let current_mode = if live_path_ready
+                    && trade_count >= min_trades_for_graduation
+                    && raw_score >= min_score_for_graduation
+                {
+                    TradeMode::Live
+                } else {
+                    TradeMode::Paper
+                };
 
                 strategy_metrics.insert(
                     spec.id.clone(),
-                    (mean_pnl, sharpe_ratio, trade_count, current_mode),
+                    Performance {
+                        mean_pnl,
+                        raw_sharpe: sharpe_ratio,
+                        sharpe: sharpe_prior.shrink(sharpe_ratio, pnl_values.len()),
+                        sortino,
+                        calmar,
+                        max_drawdown: ranking::max_drawdown(&pnl_values),
+                        score: sharpe_prior.shrink(raw_score, pnl_values.len()),
+                        volatility: weighting::recent_volatility(&pnl_values, volatility_window),
+                        trade_count,
+                        mode: current_mode,
+                    },
                 );
             } else {
-                let current_mode = TradeMode::Paper; // No data yet, stay in paper
-                strategy_metrics.insert(spec.id.clone(), (0.0, 0.0, trade_count, current_mode));
-                // No data yet
+                // No data yet, stay in paper
+                strategy_metrics.insert(
+                    spec.id.clone(),
+                    Performance {
+                        sharpe: sharpe_prior.mean,
+                        score: sharpe_prior.mean,
+                        trade_count,
+                        ..Performance::default()
+                    },
+                );
             }
         }
 
         // 2. Calculate weights and determine trade modes (paper vs live)
+        let no_record = Performance::default();
+        let performance_of = |id: &str| strategy_metrics.get(id).unwrap_or(&no_record);
         let mut sorted_strategies: Vec<&StrategySpec> = specs.iter().collect();
         sorted_strategies.sort_by(|a, b| {
-            let (pnl_a, sharpe_a, _, _) =
-                strategy_metrics
-                    .get(&a.id)
-                    .unwrap_or(&(0.0, 0.0, 0, TradeMode::Paper));
-            let (pnl_b, sharpe_b, _, _) =
-                strategy_metrics
-                    .get(&b.id)
-                    .unwrap_or(&(0.0, 0.0, 0, TradeMode::Paper));
-
-            sharpe_b
-                .partial_cmp(sharpe_a) // Higher Sharpe first
+            let (a, b) = (performance_of(&a.id), performance_of(&b.id));
+            b.score
+                .partial_cmp(&a.score) // Higher score first
                 .unwrap_or_else(|| {
-                    pnl_b
-                        .partial_cmp(pnl_a)
+                    b.mean_pnl
+                        .partial_cmp(&a.mean_pnl)
                         .unwrap_or(std::cmp::Ordering::Equal)
                 }) // Then higher PnL
         });
 
-        let mut allocations: Vec<StrategyAllocation> = Vec::new();
-        let mut total_sharpe_for_weighting = 0.0;
-        for spec in sorted_strategies.iter() {
-            // Iterate over sorted_strategies directly
-            let (_, sharpe, _, _) =
-                strategy_metrics
-                    .get(&spec.id)
-                    .unwrap_or(&(0.0, 0.0, 0, TradeMode::Paper));
-            // Only consider positive Sharpe ratios for weighting, or a small base weight for new strategies
-            let weight_factor = sharpe.max(0.1); // Give a floor to new/low-sharpe strategies
-            total_sharpe_for_weighting += weight_factor;
+        let performance: Vec<&Performance> = sorted_strategies
+            .iter()
+            .map(|spec| performance_of(&spec.id))
+            .collect();
+        let target_weights = weighting::weights(allocation_mode, &performance);
+        let families: Vec<&str> = sorted_strategies
+            .iter()
+            .map(|spec| spec.family.as_str())
+            .collect();
+        let (weights, capped_families) = family_caps.apply(&target_weights, &families);
+        if !capped_families.is_empty() {
+            info!(families = ?capped_families, "Family weight caps hit; overflow redistributed.");
         }
 
+        let mut allocations: Vec<StrategyAllocation> = Vec::new();
+        let mut explanations: HashMap<String, AllocationExplanation> = HashMap::new();
         let mut graduated_count = 0;
-        for spec in sorted_strategies {
-            let (_, sharpe, trade_count, mode) =
-                strategy_metrics
-                    .get(&spec.id)
-                    .unwrap_or(&(0.0, 0.0, 0, TradeMode::Paper));
-            let weight = if total_sharpe_for_weighting > 0.0 {
-                (sharpe.max(0.1)) / total_sharpe_for_weighting
-            } else {
-                1.0 / specs.len() as f64 // Fallback if no positive sharpe sum
-            };
+        for (((spec, perf), weight), target_weight) in sorted_strategies
+            .into_iter()
+            .zip(performance)
+            .zip(weights)
+            .zip(target_weights)
+        {
+            let Performance {
+                raw_sharpe,
+                sharpe,
+                sortino,
+                calmar,
+                max_drawdown,
+                score,
+                trade_count,
+                mode,
+                ..
+            } = perf;
 
             // Check for graduation announcement
             if *mode == TradeMode::Live && graduated_count == 0 {
                 graduated_count += 1;
                 alert!(
                     conn,
-                    "🎓 Strategy {} graduated to LIVE trading! (Trades: {}, Sharpe: {:.2})",
+                    "🎓 Strategy {} graduated to LIVE trading! (Trades: {}, {}: {:.2})",
                     spec.id,
                     trade_count,
-                    sharpe
-                )
-                .await;
+                    ranking_metric,
+                    ranking_metric.pick(*raw_sharpe, *sortino, *calmar)
+                );
             }
 
             allocations.push(StrategyAllocation {
                 id: spec.id.clone(),
+                family: spec.family.clone(),
                 weight,
-                sharpe_ratio: *sharpe,
+                sharpe_ratio: *raw_sharpe,
+                shrunk_sharpe_ratio: *sharpe,
                 mode: *mode,
+                params: spec.params.clone(),
+                token_allowlist: spec.token_allowlist.clone(),
+                token_denylist: spec.token_denylist.clone(),
+                max_slippage_bps: spec.max_slippage_bps,
+                challenger_params: spec.challenger_params.clone(),
+                capital_usd: None,
+                explanation: None, // Attached once the churn limits have had their say
             });
+            explanations.insert(
+                spec.id.clone(),
+                AllocationExplanation {
+                    sharpe_ratio: *raw_sharpe,
+                    shrunk_sharpe_ratio: *sharpe,
+                    ranking_metric: ranking_metric.to_string(),
+                    score: *score,
+                    trade_count: *trade_count,
+                    max_drawdown_usd: *max_drawdown,
+                    allocation_mode: allocation_mode.to_string(),
+                    target_weight,
+                    caps_hit: if capped_families.contains(&spec.family) {
+                        vec![format!("family_cap:{}", spec.family)]
+                    } else {
+                        Vec::new()
+                    },
+                    ..AllocationExplanation::default()
+                },
+            );
+        }
+
+        // Damp churn against the last published set
+        if stabilizer.is_none() {
+            stabilizer =
+                Some(Stabilizer::load(&mut conn, StabilitySettings::from_env()).await);
+        }
+        if let Some(stabilizer) = stabilizer.as_mut() {
+            allocations = stabilizer.apply(allocations, chrono::Utc::now().timestamp());
+        }
+        for allocation in allocations.iter_mut() {
+            // A strategy kept on only by its dwell time has no spec to explain it
+            let mut explanation =
+                explanations
+                    .remove(&allocation.id)
+                    .unwrap_or_else(|| AllocationExplanation {
+                        ranking_metric: ranking_metric.to_string(),
+                        allocation_mode: allocation_mode.to_string(),
+                        ..AllocationExplanation::default()
+                    });
+            if let Some(stabilizer) = stabilizer.as_ref() {
+                explanation.caps_hit.extend(
+                    stabilizer
+                        .limits_hit(&allocation.id)
+                        .iter()
+                        .map(|limit| limit.to_string()),
+                );
+            }
+            allocation.explanation = Some(explanation);
+        }
+
+        // Size the weights against equity rather than a fixed base
+        match capital::assess(
+            &mut conn,
+            &capital_settings,
+            profile.allows_live(),
+            chrono::Utc::now().timestamp(),
+        )
+        .await
+        {
+            Ok(capital) => {
+                if capital.scale < 1.0 {
+                    warn!(
+                        equity_usd = capital.equity_usd,
+                        peak_usd = capital.peak_usd,
+                        drawdown_pct = capital.drawdown_pct,
+                        "Equity in drawdown; allocating {:.0}% of it.",
+                        capital.scale * 100.0
+                    );
+                }
+                info!(
+                    source = capital.source,
+                    equity_usd = capital.equity_usd,
+                    allocated_usd = capital.total_usd,
+                    "Capital sized."
+                );
+                CAPITAL_USD
+                    .with_label_values(&["equity"])
+                    .set(capital.equity_usd);
+                CAPITAL_USD.with_label_values(&["peak"]).set(capital.peak_usd);
+                CAPITAL_USD
+                    .with_label_values(&["allocated"])
+                    .set(capital.total_usd);
+                for allocation in allocations.iter_mut() {
+                    allocation.capital_usd = Some(allocation.weight * capital.total_usd);
+                    if let Some(explanation) = allocation.explanation.as_mut() {
+                        explanation.portfolio_drawdown_pct = capital.drawdown_pct;
+                        if capital.scale < 1.0 {
+                            explanation.caps_hit.push("capital_drawdown".to_string());
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!(
+                "Failed to size capital from equity; executors fall back to PORTFOLIO_CAPITAL_USD: {}",
+                e
+            ),
+        }
+
+        // A/B test arms trade their own paper budget, outside the weighted book
+        for test in &ab_tests {
+            allocations
+                .extend(test.allocations(&experiment_settings, |id| performance_of(id).clone()));
         }
 
         let live_count = allocations.iter().filter(|a| a.is_live()).count();
         info!(
-            "Publishing {} allocations ({} live, {} paper) with dynamic Sharpe-based weights.",
+            "Publishing {} allocations ({} live, {} paper) with {} weights.",
             allocations.len(),
             live_count,
-            allocations.len() - live_count
+            allocations.len() - live_count,
+            allocation_mode
         );
         let payload = serde_json::to_string(&allocations)?;
 
@@ -253,6 +743,106 @@ async fn main() -> Result<()> {
         {
             warn!("Failed to publish allocations to stream: {}.", e);
         }
+        if let Some(stabilizer) = stabilizer.as_ref() {
+            if let Err(e) = stabilizer.save(&mut conn).await {
+                warn!("Failed to save allocation stability state: {}", e);
+            }
+        }
+        if let Err(e) = explanations::record(
+            &mut conn,
+            &allocations,
+            chrono::Utc::now().timestamp(),
+            explanation_history,
+        )
+        .await
+        {
+            warn!("Failed to record allocation explanations: {}", e);
+        }
+        ALLOCATION_RUNS_TOTAL.inc();
+        ALLOCATIONS
+            .with_label_values(&["live"])
+            .set(live_count as f64);
+        ALLOCATIONS
+            .with_label_values(&["paper"])
+            .set((allocations.len() - live_count) as f64);
+        STRATEGY_WEIGHT.reset(); // Drop strategies no longer allocated
+        for allocation in &allocations {
+            STRATEGY_WEIGHT
+                .with_label_values(&[&allocation.id])
+                .set(allocation.weight);
+            if allocation.is_live() && previous_modes.get(&allocation.id) == Some(&TradeMode::Paper)
+            {
+                GRADUATIONS_TOTAL.with_label_values(&[&allocation.id]).inc();
+            }
+        }
+        previous_modes = allocations.iter().map(|a| (a.id.clone(), a.mode)).collect();
+
+        // 3. Settle A/B tests whose winner is clear
+        for test in &ab_tests {
+            let result = test.evaluate(
+                &experiment_settings,
+                &arm_pnl,
+                chrono::Utc::now().timestamp(),
+            );
+            if let Err(e) = experiments::save_result(&mut conn, &result).await {
+                warn!(
+                    "Failed to save A/B test result for {}: {}",
+                    result.main_id, e
+                );
+            }
+            let Some(winner) = result.winner.as_deref() else {
+                continue;
+            };
+            match test.promote(&mut conn, winner).await {
+                Ok(()) => {
+                    VARIANT_PROMOTIONS_TOTAL
+                        .with_label_values(&[&result.main_id])
+                        .inc();
+                    let arm = |id: &str| result.arms.iter().find(|a| a.id == id);
+                    alert!(
+                        conn,
+                        "🧪 Variant {} won the A/B test of {} (${:.2} vs ${:.2} per trade, p = {:.4}) and its params are now the main spec's.",
+                        winner,
+                        result.main_id,
+                        arm(winner).map_or(0.0, |a| a.mean_pnl),
+                        result.arms[0].mean_pnl,
+                        arm(winner).and_then(|a| a.p_value).unwrap_or(0.0)
+                    );
+                }
+                Err(e) => warn!(
+                    "Failed to promote variant {} of {}: {}",
+                    winner, result.main_id, e
+                ),
+            }
+        }
+
+        // 4. Measure strategies and the portfolio against passive benchmarks
+        if benchmark_tracker.is_none() {
+            benchmark_tracker =
+                Some(BenchmarkTracker::load(&mut conn, BenchmarkSettings::from_env()).await);
+        }
+        if let Some(tracker) = benchmark_tracker.as_mut() {
+            if let Err(e) = tracker.ingest(&mut conn).await {
+                warn!("Failed to update benchmark series: {}", e);
+            }
+            let weights: Vec<(String, f64)> = allocations
+                .iter()
+                .map(|a| (a.id.clone(), a.weight))
+                .collect();
+            match tracker.report(&mut conn, &weights).await {
+                Ok(report) => {
+                    for (benchmark, fit) in &report.portfolio.versus {
+                        info!(
+                            "Portfolio vs {}: alpha {:.3}%/period ({:.1}% annualized), beta {:.2} over {} periods.",
+                            benchmark, fit.alpha_pct, fit.alpha_annualized_pct, fit.beta, fit.periods
+                        );
+                    }
+                    conn.set::<_, _, ()>("benchmark_performance", serde_json::to_string(&report)?)
+                        .await?;
+                }
+                Err(e) => warn!("Failed to compute benchmark-relative performance: {}", e),
+            }
+        }
 
         tokio::time::sleep(Duration::from_secs(60)).await;
     }
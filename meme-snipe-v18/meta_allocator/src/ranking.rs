@@ -0,0 +1,113 @@
+//! The risk-adjusted return strategies are ranked, weighted and graduated on.
+//! RANKING_METRIC picks it from each strategy's net per-trade PnL history:
+//!
+//! - `sharpe` (the default): mean over standard deviation;
+//! - `sortino`: mean over downside deviation, the root mean square of the
+//!   losing trades counted over every trade, so big winners aren't punished
+//!   as risk the way Sharpe punishes them;
+//! - `calmar`: total PnL over the deepest peak-to-trough drawdown of the
+//!   cumulative PnL curve.
+//!
+//! A strategy that never lost (Sortino) or never drew down (Calmar) while
+//! making money scores RATIO_CAP rather than infinity.
+
+use crate::mean;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+const RATIO_CAP: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingMetric {
+    Sharpe,
+    Sortino,
+    Calmar,
+}
+
+impl RankingMetric {
+    pub fn from_env() -> Self {
+        match std::env::var("RANKING_METRIC") {
+            Ok(metric) => metric.parse().unwrap_or_else(|e| {
+                warn!("{}; ranking by Sharpe.", e);
+                RankingMetric::Sharpe
+            }),
+            Err(_) => RankingMetric::Sharpe,
+        }
+    }
+
+    pub fn pick(&self, sharpe: f64, sortino: f64, calmar: f64) -> f64 {
+        match self {
+            RankingMetric::Sharpe => sharpe,
+            RankingMetric::Sortino => sortino,
+            RankingMetric::Calmar => calmar,
+        }
+    }
+}
+
+impl fmt::Display for RankingMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RankingMetric::Sharpe => "sharpe",
+            RankingMetric::Sortino => "sortino",
+            RankingMetric::Calmar => "calmar",
+        })
+    }
+}
+
+impl FromStr for RankingMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "sharpe" => Ok(RankingMetric::Sharpe),
+            "sortino" => Ok(RankingMetric::Sortino),
+            "calmar" => Ok(RankingMetric::Calmar),
+            other => Err(format!(
+                "RANKING_METRIC must be sharpe, sortino or calmar, got {}",
+                other
+            )),
+        }
+    }
+}
+
+/// `reward` over `risk`, at most RATIO_CAP, and RATIO_CAP for a gain at no risk.
+fn ratio(reward: f64, risk: f64) -> f64 {
+    if risk > 0.0 {
+        let ratio = reward / risk;
+        if ratio.is_finite() {
+            ratio.min(RATIO_CAP)
+        } else {
+            0.0
+        }
+    } else if reward > 0.0 {
+        RATIO_CAP
+    } else {
+        0.0
+    }
+}
+
+pub fn sortino(pnl_values: &[f64]) -> f64 {
+    if pnl_values.is_empty() {
+        return 0.0;
+    }
+    let downside = (pnl_values.iter().map(|p| p.min(0.0).powi(2)).sum::<f64>()
+        / pnl_values.len() as f64)
+        .sqrt();
+    ratio(mean(pnl_values), downside)
+}
+
+/// Deepest fall of the cumulative PnL curve from a prior peak, starting flat.
+pub fn max_drawdown(pnl_values: &[f64]) -> f64 {
+    let (mut equity, mut peak, mut drawdown) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for pnl in pnl_values {
+        equity += pnl;
+        peak = peak.max(equity);
+        drawdown = drawdown.max(peak - equity);
+    }
+    drawdown
+}
+
+pub fn calmar(pnl_values: &[f64]) -> f64 {
+    ratio(pnl_values.iter().sum(), max_drawdown(pnl_values))
+}
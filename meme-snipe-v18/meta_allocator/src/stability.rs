@@ -0,0 +1,198 @@
+//! Keeps published weights from churning every cycle. Each cycle's target
+//! allocation set is checked against the last one published:
+//!
+//! - a strategy allocated less than ALLOCATION_MIN_DWELL_SECS (default 1800)
+//!   ago keeps its last allocation, as paper, even if it dropped out of the
+//!   target set;
+//! - a weight moving less than ALLOCATION_MIN_WEIGHT_CHANGE (default 0.02) stays
+//!   where it was;
+//! - if what's left would still move more than ALLOCATION_MAX_TURNOVER (default
+//!   0.25, counted one way: half the summed absolute weight changes) of the
+//!   book, every weight only goes that fraction of the way, so entrants ramp in
+//!   and leavers ramp out over several cycles.
+//!
+//! Zero disables a limit. The last published set is kept in Redis so a
+//! restart neither re-dwells nor jumps straight to the new targets.
+
+use anyhow::Result;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use shared_models::{StrategyAllocation, TradeMode};
+use std::collections::{HashMap, HashSet};
+
+const STATE_KEY: &str = "allocator:stability_state";
+const DROP_BELOW_WEIGHT: f64 = 1e-4; // A leaver ramped out this far is dropped
+
+pub struct StabilitySettings {
+    pub min_weight_change: f64,
+    pub max_turnover: f64,
+    pub min_dwell_secs: i64,
+}
+
+impl StabilitySettings {
+    pub fn from_env() -> Self {
+        Self {
+            min_weight_change: std::env::var("ALLOCATION_MIN_WEIGHT_CHANGE")
+                .unwrap_or_else(|_| "0.02".to_string())
+                .parse()
+                .unwrap_or(0.02),
+            max_turnover: std::env::var("ALLOCATION_MAX_TURNOVER")
+                .unwrap_or_else(|_| "0.25".to_string())
+                .parse()
+                .unwrap_or(0.25),
+            min_dwell_secs: std::env::var("ALLOCATION_MIN_DWELL_SECS")
+                .unwrap_or_else(|_| "1800".to_string())
+                .parse()
+                .unwrap_or(1800),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Held {
+    allocation: StrategyAllocation,
+    since: i64, // When the strategy was last allocated after being out
+}
+
+pub struct Stabilizer {
+    settings: StabilitySettings,
+    held: HashMap<String, Held>,
+    limits_hit: HashMap<String, Vec<&'static str>>, // By the last `apply`, per strategy
+}
+
+impl Stabilizer {
+    pub async fn load(conn: &mut redis::aio::Connection, settings: StabilitySettings) -> Self {
+        let held = conn
+            .get::<_, Option<String>>(STATE_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            settings,
+            held,
+            limits_hit: HashMap::new(),
+        }
+    }
+
+    /// Turns this cycle's target allocations (weights summing to 1) into the
+    /// set to publish, and remembers it as the last published.
+    pub fn apply(&mut self, targets: Vec<StrategyAllocation>, now: i64) -> Vec<StrategyAllocation> {
+        self.limits_hit.clear();
+        if self.held.is_empty() {
+            self.remember(&targets, now); // Nothing to move from
+            return targets;
+        }
+        let previous = |id: &str| self.held.get(id).map_or(0.0, |h| h.allocation.weight);
+        let mut allocations: Vec<StrategyAllocation> =
+            targets.into_iter().filter(|a| a.weight > 0.0).collect();
+        let mut pinned: HashSet<String> = HashSet::new(); // Weights that don't move this cycle
+
+        // Dwell: leavers allocated too recently stay on at their last weight
+        for held in self.held.values() {
+            let leaving = allocations.iter().all(|a| a.id != held.allocation.id);
+            if leaving && now - held.since < self.settings.min_dwell_secs {
+                let mut kept = held.allocation.clone();
+                kept.mode = TradeMode::Paper;
+                pinned.insert(kept.id.clone());
+                self.limits_hit.insert(kept.id.clone(), vec!["min_dwell"]);
+                allocations.push(kept);
+            }
+        }
+
+        // Threshold: small moves are skipped, and the rest share what's left
+        for allocation in &allocations {
+            if self.held.contains_key(&allocation.id)
+                && !pinned.contains(&allocation.id)
+                && (allocation.weight - previous(&allocation.id)).abs()
+                    < self.settings.min_weight_change
+            {
+                pinned.insert(allocation.id.clone());
+                self.limits_hit
+                    .insert(allocation.id.clone(), vec!["min_weight_change"]);
+            }
+        }
+        let pinned_weight: f64 = pinned.iter().map(|id| previous(id)).sum();
+        let free_target: f64 = allocations
+            .iter()
+            .filter(|a| !pinned.contains(&a.id))
+            .map(|a| a.weight)
+            .sum();
+        let scale = if free_target > 0.0 {
+            ((1.0 - pinned_weight) / free_target).max(0.0)
+        } else {
+            0.0
+        };
+        for allocation in allocations.iter_mut() {
+            if pinned.contains(&allocation.id) {
+                allocation.weight = previous(&allocation.id);
+            } else {
+                allocation.weight *= scale;
+            }
+        }
+
+        // Turnover: past the cap, every weight goes the same fraction of the way
+        let leavers: Vec<StrategyAllocation> = self
+            .held
+            .values()
+            .filter(|h| allocations.iter().all(|a| a.id != h.allocation.id))
+            .map(|h| {
+                let mut leaver = h.allocation.clone();
+                leaver.weight = 0.0;
+                leaver.mode = TradeMode::Paper;
+                leaver
+            })
+            .collect();
+        allocations.extend(leavers);
+        let turnover: f64 = allocations
+            .iter()
+            .map(|a| (a.weight - previous(&a.id)).abs())
+            .sum::<f64>()
+            / 2.0;
+        if self.settings.max_turnover > 0.0 && turnover > self.settings.max_turnover {
+            let step = self.settings.max_turnover / turnover;
+            for allocation in allocations.iter_mut() {
+                let from = previous(&allocation.id);
+                if allocation.weight != from {
+                    self.limits_hit
+                        .entry(allocation.id.clone())
+                        .or_default()
+                        .push("max_turnover");
+                }
+                allocation.weight = from + (allocation.weight - from) * step;
+            }
+        }
+        allocations.retain(|a| a.weight > DROP_BELOW_WEIGHT);
+
+        self.remember(&allocations, now);
+        allocations
+    }
+
+    /// Limits that held `id` off its target weight in the last `apply`.
+    pub fn limits_hit(&self, id: &str) -> &[&'static str] {
+        self.limits_hit.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    fn remember(&mut self, allocations: &[StrategyAllocation], now: i64) {
+        self.held = allocations
+            .iter()
+            .map(|a| {
+                let since = self.held.get(&a.id).map_or(now, |h| h.since);
+                (
+                    a.id.clone(),
+                    Held {
+                        allocation: a.clone(),
+                        since,
+                    },
+                )
+            })
+            .collect();
+    }
+
+    pub async fn save(&self, conn: &mut redis::aio::Connection) -> Result<()> {
+        conn.set::<_, _, ()>(STATE_KEY, serde_json::to_string(&self.held)?)
+            .await?;
+        Ok(())
+    }
+}
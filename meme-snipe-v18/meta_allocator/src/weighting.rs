@@ -0,0 +1,282 @@
+//! How capital weights are derived from each strategy's performance.
+//! ALLOCATION_MODE picks the scheme:
+//!
+//! - `score` (the default; `sharpe` is accepted for it too): weight
+//!   proportional to the strategy's ranking score (see the `ranking` module),
+//!   floored at 0.1 so new and losing strategies keep a toehold;
+//! - `risk_parity`: weight inversely proportional to the volatility of the
+//!   strategy's last RISK_PARITY_WINDOW (default 50) net trade PnLs, so each
+//!   strategy puts roughly the same dollar risk on the book and one high-Sharpe,
+//!   low-volatility strategy can't starve the rest. A strategy without two
+//!   trades in the window is given the median volatility of those with, and
+//!   none is counted below a tenth of that median, so a strategy that barely
+//!   moves can't soak up the whole book either.
+//!
+//! Weights then sum to 1, before family caps: FAMILY_WEIGHT_CAPS
+//! (`family:cap,...`, e.g. `momentum_5m:0.4`) and DEFAULT_FAMILY_WEIGHT_CAP
+//! (default 1, no cap) bound the summed weight of each spec family, so a
+//! handful of near-identical variants can't take the book. What a capped
+//! family sheds goes to the families under their caps in proportion to their
+//! weights, and is left unallocated once every family is at its cap.
+//!
+//! A ratio measured over a handful of trades is mostly noise, so before
+//! anything ranks or weights on it each strategy's Sharpe, and its ranking
+//! score, is shrunk toward SHARPE_PRIOR (default 0, read in the ranking
+//! metric's units): averaged with it as if the strategy had already made
+//! SHARPE_PRIOR_TRADES (default 20) trades at the prior. Three trades leave a
+//! strategy close to the prior, hundreds close to its own estimate.
+
+use crate::std_dev;
+use shared_models::TradeMode;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+const SCORE_FLOOR: f64 = 0.1;
+const MIN_VOLATILITY_OF_MEDIAN: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationMode {
+    Score,
+    RiskParity,
+}
+
+impl AllocationMode {
+    pub fn from_env() -> Self {
+        match std::env::var("ALLOCATION_MODE") {
+            Ok(mode) => mode.parse().unwrap_or_else(|e| {
+                warn!("{}; weighting by score.", e);
+                AllocationMode::Score
+            }),
+            Err(_) => AllocationMode::Score,
+        }
+    }
+}
+
+impl fmt::Display for AllocationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AllocationMode::Score => "score",
+            AllocationMode::RiskParity => "risk_parity",
+        })
+    }
+}
+
+impl FromStr for AllocationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "score" | "sharpe" => Ok(AllocationMode::Score),
+            "risk_parity" => Ok(AllocationMode::RiskParity),
+            other => Err(format!(
+                "ALLOCATION_MODE must be score or risk_parity, got {}",
+                other
+            )),
+        }
+    }
+}
+
+/// What the allocator knows of one strategy's track record this cycle.
+#[derive(Debug, Clone)]
+pub struct Performance {
+    pub mean_pnl: f64, // Per trade, net of fees
+    pub raw_sharpe: f64,
+    pub sharpe: f64, // Shrunk toward the prior
+    pub sortino: f64,
+    pub calmar: f64,
+    pub max_drawdown: f64, // USD, of the cumulative net PnL curve
+    pub score: f64, // The ranking metric, shrunk toward the prior; ranked and weighted on
+    pub volatility: Option<f64>, // σ of recent net trade PnL; None under two trades
+    pub trade_count: u64,
+    pub mode: TradeMode,
+}
+
+impl Default for Performance {
+    fn default() -> Self {
+        Self {
+            mean_pnl: 0.0,
+            raw_sharpe: 0.0,
+            sharpe: 0.0,
+            sortino: 0.0,
+            calmar: 0.0,
+            max_drawdown: 0.0,
+            score: 0.0,
+            volatility: None,
+            trade_count: 0,
+            mode: TradeMode::Paper,
+        }
+    }
+}
+
+pub struct SharpePrior {
+    pub mean: f64,
+    pub trades: f64, // Weight of the prior, in trades
+}
+
+impl SharpePrior {
+    pub fn from_env() -> Self {
+        Self {
+            mean: std::env::var("SHARPE_PRIOR")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0.0),
+            trades: std::env::var("SHARPE_PRIOR_TRADES")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse::<f64>()
+                .unwrap_or(20.0)
+                .max(0.0),
+        }
+    }
+
+    /// The posterior-mean Sharpe of a strategy measured at `raw` over `trades` trades.
+    pub fn shrink(&self, raw: f64, trades: usize) -> f64 {
+        let n = trades as f64;
+        if n + self.trades <= 0.0 {
+            return self.mean;
+        }
+        (raw * n + self.mean * self.trades) / (n + self.trades)
+    }
+}
+
+pub fn volatility_window() -> usize {
+    std::env::var("RISK_PARITY_WINDOW")
+        .unwrap_or_else(|_| "50".to_string())
+        .parse::<usize>()
+        .unwrap_or(50)
+        .max(2)
+}
+
+/// σ of the last `window` PnLs, if there are two of them and they differ.
+pub fn recent_volatility(pnl_values: &[f64], window: usize) -> Option<f64> {
+    let recent = &pnl_values[pnl_values.len().saturating_sub(window)..];
+    let sigma = std_dev(recent);
+    (recent.len() > 1 && sigma.is_finite() && sigma > 0.0).then_some(sigma)
+}
+
+/// One weight per entry of `performance`, in the same order.
+pub fn weights(mode: AllocationMode, performance: &[&Performance]) -> Vec<f64> {
+    if performance.is_empty() {
+        return Vec::new();
+    }
+    let raw: Vec<f64> = match mode {
+        AllocationMode::Score => performance
+            .iter()
+            .map(|p| p.score.max(SCORE_FLOOR))
+            .collect(),
+        AllocationMode::RiskParity => inverse_volatility(performance),
+    };
+    let total: f64 = raw.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        raw.iter().map(|w| w / total).collect()
+    } else {
+        vec![1.0 / performance.len() as f64; performance.len()]
+    }
+}
+
+fn inverse_volatility(performance: &[&Performance]) -> Vec<f64> {
+    let mut known: Vec<f64> = performance.iter().filter_map(|p| p.volatility).collect();
+    if known.is_empty() {
+        return vec![1.0; performance.len()]; // Nobody has a track record yet
+    }
+    known.sort_by(|a, b| a.total_cmp(b));
+    let median = if known.len() % 2 == 1 {
+        known[known.len() / 2]
+    } else {
+        (known[known.len() / 2 - 1] + known[known.len() / 2]) / 2.0
+    };
+    let floor = median * MIN_VOLATILITY_OF_MEDIAN;
+    performance
+        .iter()
+        .map(|p| 1.0 / p.volatility.unwrap_or(median).max(floor))
+        .collect()
+}
+
+pub struct FamilyCaps {
+    caps: HashMap<String, f64>,
+    default_cap: f64,
+}
+
+impl FamilyCaps {
+    pub fn from_env() -> Self {
+        let caps = std::env::var("FAMILY_WEIGHT_CAPS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .filter_map(|entry| {
+                let parsed = entry.split_once(':').and_then(|(family, cap)| {
+                    Some((family.trim().to_string(), cap.trim().parse::<f64>().ok()?))
+                });
+                if parsed.is_none() {
+                    warn!(
+                        "Ignoring FAMILY_WEIGHT_CAPS entry {:?}; expected family:cap.",
+                        entry
+                    );
+                }
+                parsed
+            })
+            .collect();
+        Self {
+            caps,
+            default_cap: std::env::var("DEFAULT_FAMILY_WEIGHT_CAP")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .unwrap_or(1.0),
+        }
+    }
+
+    pub fn cap(&self, family: &str) -> f64 {
+        self.caps
+            .get(family)
+            .copied()
+            .unwrap_or(self.default_cap)
+            .clamp(0.0, 1.0)
+    }
+
+    /// `weights` (one per entry of `families`) with every family held to its
+    /// cap, and the families that hit it.
+    pub fn apply(&self, weights: &[f64], families: &[&str]) -> (Vec<f64>, BTreeSet<String>) {
+        let mut weights = weights.to_vec();
+        let mut capped: BTreeSet<String> = BTreeSet::new();
+        // Each pass caps at least one more family, so this ends
+        loop {
+            let mut totals: HashMap<&str, f64> = HashMap::new();
+            for (family, weight) in families.iter().zip(&weights) {
+                *totals.entry(family).or_default() += weight;
+            }
+            let mut shed = 0.0;
+            for (family, total) in &totals {
+                let cap = self.cap(family);
+                if *total > cap + 1e-9 {
+                    let scale = if *total > 0.0 { cap / total } else { 0.0 };
+                    for (f, weight) in families.iter().zip(weights.iter_mut()) {
+                        if f == family {
+                            *weight *= scale;
+                        }
+                    }
+                    shed += total - cap;
+                    capped.insert(family.to_string());
+                }
+            }
+            if shed <= 0.0 {
+                break;
+            }
+            let open: f64 = families
+                .iter()
+                .zip(&weights)
+                .filter(|(family, _)| !capped.contains(**family))
+                .map(|(_, weight)| weight)
+                .sum();
+            if open <= 0.0 {
+                break; // Every family is at its cap; the rest stays unallocated
+            }
+            for (family, weight) in families.iter().zip(weights.iter_mut()) {
+                if !capped.contains(*family) {
+                    *weight += shed * *weight / open;
+                }
+            }
+        }
+        (weights, capped)
+    }
+}
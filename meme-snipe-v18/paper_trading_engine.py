@@ -366,8 +366,8 @@ class PaperTradingEngine:
         # Get active strategies
         strategies = [
             'momentum_5m', 'mean_revert_1h', 'bridge_inflow', 'social_buzz',
-            'liquidity_migration', 'korean_time_burst', 'airdrop_rotation',
-            'dev_wallet_drain', 'rug_pull_sniffer', 'perp_basis_arb'
+            'liquidity_migration', 'korean_time_burst', 'holder_growth',
+            'dev_wallet_drain', 'lp_unlock_short', 'perp_basis_arb'
         ]
         
         trades_executed = 0
@@ -398,8 +398,8 @@ def main():
     # Initialize strategy balances
     strategies = [
         'momentum_5m', 'mean_revert_1h', 'bridge_inflow', 'social_buzz',
-        'liquidity_migration', 'korean_time_burst', 'airdrop_rotation',
-        'dev_wallet_drain', 'rug_pull_sniffer', 'perp_basis_arb'
+        'liquidity_migration', 'korean_time_burst', 'holder_growth',
+        'dev_wallet_drain', 'lp_unlock_short', 'perp_basis_arb'
     ]
     
     for strategy_id in strategies:
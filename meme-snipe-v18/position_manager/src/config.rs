@@ -1,9 +1,8 @@
 // position_manager/src/config.rs
 use lazy_static::lazy_static;
-use serde::Deserialize;
-use std::env;
+use shared_config::{Live, Loader, Tunables};
+use std::sync::Arc;
 
-#[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct Config {
     pub paper_trading_mode: bool,
@@ -13,30 +12,48 @@ pub struct Config {
     pub signer_url: String,
     pub redis_url: String,
     pub database_path: String,
-    pub trailing_stop_loss_percent: f64,
+    pub tunables: Arc<Live<PositionTunables>>, // Re-read on config changes while running
 }
 
-impl Config {
-    fn load() -> Self {
+/// The settings that can change without a restart.
+pub struct PositionTunables {
+    pub trailing_stop_loss_percent: f64, // Used when a trade's exit profile sets none
+}
+
+impl Tunables for PositionTunables {
+    fn load(c: &mut Loader) -> Self {
         Self {
-            paper_trading_mode: env::var("PAPER_TRADING_MODE")
-                .unwrap_or_else(|_| "true".to_string())
-                == "true",
-            wallet_keypair_path: env::var("WALLET_KEYPAIR_FILENAME")
-                .expect("WALLET_KEYPAIR_FILENAME must be set"),
-            solana_rpc_url: env::var("SOLANA_RPC_URL").expect("SOLANA_RPC_URL must be set"),
-            jupiter_api_url: env::var("JUPITER_API_URL").expect("JUPITER_API_URL must be set"),
-            signer_url: env::var("SIGNER_URL").expect("SIGNER_URL must be set"),
-            trailing_stop_loss_percent: env::var("TRAILING_STOP_LOSS_PERCENT")
-                .expect("TRAILING_STOP_LOSS_PERCENT must be set")
-                .parse()
-                .unwrap(),
-            database_path: env::var("DATABASE_PATH").expect("DATABASE_PATH must be set"),
-            redis_url: env::var("REDIS_URL").expect("REDIS_URL must be set"),
+            trailing_stop_loss_percent: c.required("TRAILING_STOP_LOSS_PERCENT"),
         }
     }
 }
 
+impl Config {
+    fn load() -> Self {
+        let mut c = Loader::new("position_manager");
+        let config = Self {
+            paper_trading_mode: c.flag("PAPER_TRADING_MODE", true),
+            wallet_keypair_path: c.required("WALLET_KEYPAIR_FILENAME"),
+            solana_rpc_url: c.required("SOLANA_RPC_URL"),
+            jupiter_api_url: c.required("JUPITER_API_URL"),
+            signer_url: c.required("SIGNER_URL"),
+            database_path: c.required("DATABASE_PATH"),
+            redis_url: c.required("REDIS_URL"),
+            tunables: Live::load(&mut c),
+        };
+        let profile = c.profile();
+        c.check(
+            config.paper_trading_mode || profile.allows_live(),
+            format!(
+                "PAPER_TRADING_MODE=false needs MEMESNIPE_ENV=mainnet-live, not {}",
+                profile
+            ),
+        );
+        c.finish();
+        config
+    }
+}
+
 lazy_static! {
     pub static ref CONFIG: Config = Config::load();
 }
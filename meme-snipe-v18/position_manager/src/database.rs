@@ -4,6 +4,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
+use shared_models::{ExitProfile, TradeState};
 use std::path::Path;
 use tracing::info;
 
@@ -26,6 +27,15 @@ pub struct TradeRecord {
     pub confidence: f64,
     pub side: String,
     pub highest_price_usd: Option<f64>,
+    pub exit_profile: Option<ExitProfile>, // Per-trade exit rules set by the strategy
+}
+
+impl TradeRecord {
+    /// Paper trades are stamped with this signature by the executor; closing
+    /// one only books the PnL.
+    pub fn is_paper(&self) -> bool {
+        self.signature.as_deref() == Some("PAPER_TRADE")
+    }
 }
 
 // --- Database Manager ---
@@ -53,7 +63,7 @@ impl Database {
                 token_address TEXT NOT NULL,
                 symbol TEXT NOT NULL,
                 amount_usd REAL NOT NULL,
-                status TEXT NOT NULL, -- PENDING, OPEN, CLOSED_PROFIT, CLOSED_LOSS, CANCELED
+                status TEXT NOT NULL, -- TradeState: CREATED, RISK_APPROVED, SUBMITTED, CONFIRMED, OPEN, CLOSING, CLOSED, FAILED, CANCELED
                 signature TEXT,
                 entry_time INTEGER NOT NULL,
                 entry_price_usd REAL NOT NULL,
@@ -66,14 +76,33 @@ impl Database {
             )",
             [],
         )?;
+        // The executor normally adds this; don't depend on it having started first
+        let has_exit_profile = conn
+            .prepare("PRAGMA table_info(trades)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .any(|col_name| col_name.as_deref() == Ok("exit_profile"));
+        if !has_exit_profile {
+            conn.execute("ALTER TABLE trades ADD COLUMN exit_profile TEXT", [])?;
+        }
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trade_transitions (
+                id INTEGER PRIMARY KEY,
+                trade_id INTEGER NOT NULL,
+                from_state TEXT,
+                to_state TEXT NOT NULL,
+                at INTEGER NOT NULL,
+                note TEXT
+            )",
+            [],
+        )?;
         Ok(())
     }
 
     pub fn get_open_trades(&self) -> Result<Vec<TradeRecord>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT * FROM trades WHERE status = 'OPEN'")?;
-        let trades_iter = stmt.query_map([], |row| {
+            .prepare("SELECT * FROM trades WHERE status = ?1")?;
+        let trades_iter = stmt.query_map(params![TradeState::Open.as_str()], |row| {
             Ok(TradeRecord {
                 id: row.get(0)?,
                 strategy_id: row.get(1)?,
@@ -90,6 +119,10 @@ impl Database {
                 confidence: row.get(12)?,
                 side: row.get(13)?,
                 highest_price_usd: row.get(14)?,
+                // Column order depends on which service created the table
+                exit_profile: row
+                    .get::<_, Option<String>>("exit_profile")?
+                    .and_then(|raw| serde_json::from_str(&raw).ok()),
             })
         })?;
         trades_iter
@@ -97,26 +130,35 @@ impl Database {
             .map_err(anyhow::Error::from)
     }
 
-    #[allow(dead_code)]
-    pub fn update_trade_status(&self, trade_id: i64, status: &str) -> Result<()> {
-        self.conn.execute(
+    /// Moves a trade to `to` if its stored state allows it, recording the transition.
+    pub fn transition(&self, trade_id: i64, to: TradeState, note: Option<&str>) -> Result<TradeState> {
+        let tx = self.conn.unchecked_transaction()?;
+        let current: String = tx
+            .query_row("SELECT status FROM trades WHERE id = ?1", params![trade_id], |row| row.get(0))
+            .with_context(|| format!("Trade {trade_id} not found"))?;
+        let from: TradeState = current.parse().map_err(anyhow::Error::msg)?;
+        from.transition(to)
+            .with_context(|| format!("Trade {trade_id}"))?;
+
+        tx.execute(
             "UPDATE trades SET status = ?1 WHERE id = ?2",
-            params![status, trade_id],
+            params![to.as_str(), trade_id],
         )?;
-        Ok(())
+        tx.execute(
+            "INSERT INTO trade_transitions (trade_id, from_state, to_state, at, note) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![trade_id, from.as_str(), to.as_str(), Utc::now().timestamp(), note],
+        )?;
+        tx.commit()?;
+        info!(trade_id, from = %from, to = %to, "Trade state transition.");
+        Ok(from)
     }
 
-    pub fn update_trade_pnl(
-        &self,
-        trade_id: i64,
-        status: &str,
-        close_price_usd: f64,
-        pnl_usd: f64,
-    ) -> Result<()> {
+    /// Records the close of a trade; the status moves separately via `transition`.
+    pub fn update_trade_pnl(&self, trade_id: i64, close_price_usd: f64, pnl_usd: f64) -> Result<()> {
         let now: DateTime<Utc> = Utc::now();
         self.conn.execute(
-            "UPDATE trades SET status = ?1, close_time = ?2, close_price_usd = ?3, pnl_usd = ?4 WHERE id = ?5",
-            params![status, now.timestamp(), close_price_usd, pnl_usd, trade_id],
+            "UPDATE trades SET close_time = ?1, close_price_usd = ?2, pnl_usd = ?3 WHERE id = ?4",
+            params![now.timestamp(), close_price_usd, pnl_usd, trade_id],
         )?;
         Ok(())
     }
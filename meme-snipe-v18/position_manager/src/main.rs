@@ -7,22 +7,78 @@ mod signer_client; // Main logic for monitoring
 
 use crate::config::CONFIG;
 use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use database::Database;
+use prometheus::{Encoder, TextEncoder};
+use serde_json::Value;
+use shared_config::Readiness;
+use shared_models::spawn_heartbeat;
 use std::sync::Arc;
-use tracing::{info, level_filters::LevelFilter};
-use tracing_subscriber::EnvFilter;
+use tracing::{error, info};
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+async fn ready_handler(State(readiness): State<Arc<Readiness>>) -> (StatusCode, Json<Value>) {
+    let status = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness.report()))
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::INFO.into())
-        .from_env_lossy();
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let log_level = shared_config::log_level::init();
 
     info!(version = %env!("CARGO_PKG_VERSION"), "📈 Starting MemeSnipe Position Manager v18...");
+    lazy_static::initialize(&CONFIG); // Validates and logs the effective configuration
+
+    let readiness = Arc::new(
+        Readiness::new("position_manager")
+            .redis(&CONFIG.redis_url)
+            .http("signer", &format!("{}/pubkey", CONFIG.signer_url))
+            .solana_rpc(&CONFIG.solana_rpc_url),
+    );
+    readiness.preflight().await.map_err(anyhow::Error::msg)?;
+    tokio::spawn(shared_config::readiness::monitor(readiness.clone()));
 
     let db = Arc::new(Database::new(&CONFIG.database_path)?);
 
+    spawn_heartbeat!(
+        redis::Client::open(CONFIG.redis_url.as_str())?,
+        "position_manager"
+    );
+    tokio::spawn(shared_config::watch(
+        CONFIG.tunables.clone(),
+        CONFIG.redis_url.clone(),
+    ));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .route("/live", get(health_handler))
+        .route("/ready", get(ready_handler))
+        .with_state(readiness)
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!(error = %e, "Metrics server stopped.");
+        }
+    });
+
     // Start the position monitoring loop
     position_monitor::run_monitor(db.clone()).await?;
 
@@ -4,19 +4,33 @@ use crate::database::{Database, TradeRecord};
 use crate::jupiter::JupiterClient;
 use crate::signer_client;
 use anyhow::Result;
+use lazy_static::lazy_static;
+use prometheus::{register_counter_vec, CounterVec};
 use redis::{
     streams::{StreamReadOptions, StreamReadReply},
     AsyncCommands,
 };
-use shared_models::{PriceTick, Side};
+use shared_config::stream_lag::{self, StreamCursors};
+use shared_models::{dead_letter, PositionCloseRequest, PriceTick, Side, TradeState};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, warn};
 
+const POSITION_CLOSE_REQUESTS_STREAM: &str = "position_close_requests";
+
+lazy_static! {
+    static ref DEAD_LETTERS_TOTAL: CounterVec = register_counter_vec!(
+        "position_manager_dead_letters_total",
+        "Unparseable stream entries moved to dead_letters.",
+        &["stream"]
+    )
+    .unwrap();
+}
+
 pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
     info!("📈 Starting Position Manager (Live Position Monitoring)...");
     let redis_url = CONFIG.redis_url.clone();
@@ -30,17 +44,47 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
 
     // Cache of current token prices (token_address -> price_usd)
     let current_prices: Arc<Mutex<HashMap<String, f64>>> = Arc::new(Mutex::new(HashMap::new()));
+    // Trades the executor asked us to close, closed on the next check with a price
+    let mut pending_closes: HashSet<i64> = HashSet::new();
+    let mut close_requests_id = "$".to_string();
+
+    let stream_cursors = StreamCursors::new("position_manager");
+    tokio::spawn(stream_lag::monitor(stream_cursors.clone(), CONFIG.redis_url.clone()));
 
     loop {
         let opts = StreamReadOptions::default().count(10).block(5000);
         tokio::select! {
-            // Read from market event streams (specifically price updates)
-            result = conn.xread_options::<_, _, Option<StreamReadReply>>(&["events:price"], &["$"], &opts) => {
+            // Read from market event streams (specifically price updates) and close requests
+            result = conn.xread_options::<_, _, Option<StreamReadReply>>(
+                &["events:price", POSITION_CLOSE_REQUESTS_STREAM],
+                &["$", close_requests_id.as_str()],
+                &opts,
+            ) => {
                 match result {
                     Ok(streams) => {
                         if let Some(stream_reply) = streams {
                             for stream_key in stream_reply.keys {
+                                if stream_key.key == POSITION_CLOSE_REQUESTS_STREAM {
+                                    for message in stream_key.ids {
+                                        close_requests_id = message.id.clone();
+                                        stream_cursors.set(POSITION_CLOSE_REQUESTS_STREAM, &close_requests_id);
+                                        let raw: String = message.get("request").unwrap_or_default();
+                                        match serde_json::from_str::<PositionCloseRequest>(&raw) {
+                                            Ok(request) => {
+                                                info!(trade_id = request.trade_id, strategy = %request.strategy_id, reason = %request.reason, "Position close requested.");
+                                                pending_closes.insert(request.trade_id);
+                                            }
+                                            Err(e) => {
+                                                error!("Malformed close request at stream ID {}", message.id);
+                                                DEAD_LETTERS_TOTAL.with_label_values(&[POSITION_CLOSE_REQUESTS_STREAM]).inc();
+                                                dead_letter!(conn, "position_manager", POSITION_CLOSE_REQUESTS_STREAM, message.id, "request", raw, e);
+                                            }
+                                        }
+                                    }
+                                    continue;
+                                }
                                 for message in stream_key.ids {
+                                    stream_cursors.set(&stream_key.key, &message.id);
                                     if let Some(redis::Value::Data(event_bytes)) = message.map.get("event") {
                                         if let Ok(event) = serde_json::from_slice::<PriceTick>(&event_bytes) {
                                             current_prices.lock().await.insert(event.token_address.clone(), event.price_usd);
@@ -58,10 +102,14 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
             }
             // Periodically check open positions
             _ = tokio::time::sleep(Duration::from_secs(10)) => {
-                if !CONFIG.paper_trading_mode { // Only run for live trades
-                    if let Err(e) = check_open_positions(db.clone(), jupiter_client.clone(), current_prices.clone()).await {
-                        error!("Error checking open positions: {}", e);
-                    }
+                // Stops only run for live trades; requested closes are honored in either mode
+                let result = if CONFIG.paper_trading_mode {
+                    close_requested_positions(db.clone(), jupiter_client.clone(), current_prices.clone(), &mut pending_closes).await
+                } else {
+                    check_open_positions(db.clone(), jupiter_client.clone(), current_prices.clone(), &mut pending_closes).await
+                };
+                if let Err(e) = result {
+                    error!("Error checking open positions: {}", e);
                 }
             }
         }
@@ -73,8 +121,11 @@ async fn check_open_positions(
     db: Arc<Database>,
     jupiter_client: Arc<JupiterClient>,
     current_prices: Arc<Mutex<HashMap<String, f64>>>,
+    pending_closes: &mut HashSet<i64>,
 ) -> Result<()> {
     let open_trades = db.get_open_trades()?;
+    // Requests for trades that are no longer OPEN have nothing left to do
+    pending_closes.retain(|id| open_trades.iter().any(|t| t.id == *id));
     if open_trades.is_empty() {
         debug!("No open trades to monitor.");
         return Ok(());
@@ -99,8 +150,23 @@ async fn check_open_positions(
 
             let pnl_pct =
                 (current_price_usd - trade.entry_price_usd) / trade.entry_price_usd * 100.0;
-            let tsl_trigger_price = trade.highest_price_usd.unwrap()
-                * (1.0 - CONFIG.trailing_stop_loss_percent / 100.0);
+            let profile = trade.exit_profile.clone().unwrap_or_default();
+            let trailing_stop_pct = profile
+                .trailing_stop_pct
+                .unwrap_or(CONFIG.tunables.get().trailing_stop_loss_percent);
+            let tsl_trigger_price =
+                trade.highest_price_usd.unwrap() * (1.0 - trailing_stop_pct / 100.0);
+            let side_pnl_pct = if trade.side == Side::Short.to_string() {
+                -pnl_pct
+            } else {
+                pnl_pct
+            };
+            let take_profit_hit = profile
+                .take_profit_pct
+                .is_some_and(|take_profit_pct| side_pnl_pct >= take_profit_pct);
+            let max_hold_hit = profile.max_hold_secs.is_some_and(|max_hold_secs| {
+                chrono::Utc::now().timestamp() - trade.entry_time >= max_hold_secs
+            });
 
             info!(
                 trade_id = trade.id,
@@ -114,8 +180,30 @@ async fn check_open_positions(
                 "Monitoring trade."
             );
 
+            if pending_closes.remove(&trade.id) {
+                info!(trade_id = trade.id, "Closing position on request.");
+                execute_close_trade(db.clone(), jupiter_client.clone(), trade, current_price_usd)
+                    .await?;
+            }
+            // Exit profile set by the strategy that opened the trade
+            else if take_profit_hit {
+                info!(
+                    trade_id = trade.id,
+                    pnl_pct = side_pnl_pct,
+                    "🎯 Take profit triggered."
+                );
+                execute_close_trade(db.clone(), jupiter_client.clone(), trade, current_price_usd)
+                    .await?;
+            } else if max_hold_hit {
+                info!(
+                    trade_id = trade.id,
+                    "⏱️ Max holding time reached, closing position."
+                );
+                execute_close_trade(db.clone(), jupiter_client.clone(), trade, current_price_usd)
+                    .await?;
+            }
             // Check Trailing Stop Loss for LONG positions
-            if trade.side == Side::Long.to_string() && current_price_usd < tsl_trigger_price {
+            else if trade.side == Side::Long.to_string() && current_price_usd < tsl_trigger_price {
                 info!(
                     trade_id = trade.id,
                     "🚨 Trailing Stop Loss triggered for LONG position!"
@@ -133,7 +221,6 @@ async fn check_open_positions(
                 execute_close_trade(db.clone(), jupiter_client.clone(), trade, current_price_usd)
                     .await?;
             }
-            // TODO: Add Take Profit logic here if desired
         } else {
             warn!(
                 "Price not available for open trade {}. Skipping monitoring for now.",
@@ -144,6 +231,40 @@ async fn check_open_positions(
     Ok(())
 }
 
+/// Closes the trades in `pending_closes` at their last price, without
+/// evaluating any stops. Trades without a price yet stay pending.
+#[instrument(skip_all)]
+async fn close_requested_positions(
+    db: Arc<Database>,
+    jupiter_client: Arc<JupiterClient>,
+    current_prices: Arc<Mutex<HashMap<String, f64>>>,
+    pending_closes: &mut HashSet<i64>,
+) -> Result<()> {
+    if pending_closes.is_empty() {
+        return Ok(());
+    }
+    let open_trades = db.get_open_trades()?;
+    pending_closes.retain(|id| open_trades.iter().any(|t| t.id == *id));
+
+    let prices_guard = current_prices.lock().await;
+    for trade in open_trades {
+        if !pending_closes.contains(&trade.id) {
+            continue;
+        }
+        let Some(&current_price_usd) = prices_guard.get(&trade.token_address) else {
+            warn!(
+                trade_id = trade.id,
+                "Price not available for requested close, retrying on the next check."
+            );
+            continue;
+        };
+        pending_closes.remove(&trade.id);
+        info!(trade_id = trade.id, "Closing position on request.");
+        execute_close_trade(db.clone(), jupiter_client.clone(), trade, current_price_usd).await?;
+    }
+    Ok(())
+}
+
 #[instrument(skip_all, fields(trade_id = trade.id, token = %trade.token_address, side = %trade.side))]
 async fn execute_close_trade(
     db: Arc<Database>,
@@ -152,7 +273,17 @@ async fn execute_close_trade(
     close_price_usd: f64,
 ) -> Result<()> {
     info!("Executing close trade.");
-    let user_pk = Pubkey::from_str(&signer_client::get_pubkey(&CONFIG.signer_url).await?)?;
+    // CLOSING keeps the trade out of get_open_trades while the exit is in flight
+    db.transition(trade.id, TradeState::Closing, None)?;
+    if trade.is_paper() {
+        info!(
+            close_price_usd,
+            "📝 PAPER TRADING: Closing at the last price."
+        );
+    } else if let Err(e) = submit_close(&trade, &jupiter).await {
+        db.transition(trade.id, TradeState::Open, Some(&format!("close failed: {e}")))?;
+        return Err(e);
+    }
 
     let pnl_usd = if trade.side == Side::Long.to_string() {
         (close_price_usd - trade.entry_price_usd) * (trade.amount_usd / trade.entry_price_usd)
@@ -160,6 +291,19 @@ async fn execute_close_trade(
         // Short position
         (trade.entry_price_usd - close_price_usd) * (trade.amount_usd / trade.entry_price_usd)
     };
+    db.update_trade_pnl(trade.id, close_price_usd, pnl_usd)?;
+    db.transition(
+        trade.id,
+        TradeState::Closed,
+        Some(if pnl_usd > 0.0 { "profit" } else { "loss" }),
+    )?;
+    info!("Trade closed. PnL: {:.2} USD", pnl_usd);
+
+    Ok(())
+}
+
+async fn submit_close(trade: &TradeRecord, jupiter: &JupiterClient) -> Result<()> {
+    let user_pk = Pubkey::from_str(&signer_client::get_pubkey(&CONFIG.signer_url).await?)?;
 
     if trade.side == Side::Long.to_string() {
         // Sell spot via Jupiter
@@ -178,14 +322,5 @@ async fn execute_close_trade(
         // Example: drift_client.close_position(...).await?;
         info!("P-4: Drift SHORT position close simulated.");
     }
-
-    let status = if pnl_usd > 0.0 {
-        "CLOSED_PROFIT"
-    } else {
-        "CLOSED_LOSS"
-    };
-    db.update_trade_pnl(trade.id, status, close_price_usd, pnl_usd)?;
-    info!("Trade closed. Status: {}, PnL: {:.2} USD", status, pnl_usd);
-
     Ok(())
 }
@@ -0,0 +1,156 @@
+// recorder/src/archive.rs
+//! Archive files, one per stream and UTC day at a time, laid out as
+//! `RECORDER_DIR/<event type>/<YYYY-MM-DD>/<first stream id>.<ext>`. Files are
+//! written under a `.partial` name and renamed once complete, so anything
+//! without the suffix is a finished file and anything with it is left over
+//! from a crash.
+
+use crate::config::Format;
+use anyhow::{Context, Result};
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression as ParquetCompression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// One stream entry as archived.
+pub struct Record {
+    pub id: String,
+    pub received_at_ms: i64, // From the stream id
+    pub event: String,       // The entry's JSON payload
+}
+
+enum Writer {
+    Parquet(ArrowWriter<File>),
+    Jsonl(GzEncoder<BufWriter<File>>),
+}
+
+pub struct ArchiveFile {
+    partial: PathBuf,
+    path: PathBuf,
+    writer: Writer,
+    rows: u64,
+}
+
+fn schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("received_at_ms", DataType::Int64, false),
+        Field::new("event", DataType::Utf8, false),
+    ]))
+}
+
+impl ArchiveFile {
+    pub fn create(
+        dir: &Path,
+        event_type: &str,
+        day: &str,
+        first_id: &str,
+        format: Format,
+    ) -> Result<Self> {
+        let dir = dir.join(event_type).join(day);
+        fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+        let path = dir.join(format!("{}.{}", first_id, format.extension()));
+        let partial = PathBuf::from(format!("{}{}", path.display(), PARTIAL_SUFFIX));
+        let file =
+            File::create(&partial).with_context(|| format!("creating {}", partial.display()))?;
+        let writer = match format {
+            Format::Parquet => {
+                let props = WriterProperties::builder()
+                    .set_compression(ParquetCompression::ZSTD(ZstdLevel::default()))
+                    .build();
+                Writer::Parquet(ArrowWriter::try_new(file, schema(), Some(props))?)
+            }
+            Format::Jsonl => {
+                Writer::Jsonl(GzEncoder::new(BufWriter::new(file), Compression::default()))
+            }
+        };
+        Ok(Self {
+            partial,
+            path,
+            writer,
+            rows: 0,
+        })
+    }
+
+    pub fn write(&mut self, records: &[Record]) -> Result<()> {
+        match &mut self.writer {
+            Writer::Parquet(writer) => {
+                let columns: Vec<ArrayRef> = vec![
+                    Arc::new(StringArray::from_iter_values(
+                        records.iter().map(|r| r.id.as_str()),
+                    )),
+                    Arc::new(Int64Array::from_iter_values(
+                        records.iter().map(|r| r.received_at_ms),
+                    )),
+                    Arc::new(StringArray::from_iter_values(
+                        records.iter().map(|r| r.event.as_str()),
+                    )),
+                ];
+                writer.write(&RecordBatch::try_new(schema(), columns)?)?;
+            }
+            Writer::Jsonl(writer) => {
+                for record in records {
+                    // Inline the payload as JSON when it is, so readers don't decode twice
+                    let event = serde_json::from_str::<Value>(&record.event)
+                        .unwrap_or_else(|_| Value::String(record.event.clone()));
+                    let line = json!({
+                        "id": record.id,
+                        "received_at_ms": record.received_at_ms,
+                        "event": event,
+                    });
+                    serde_json::to_writer(&mut *writer, &line)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+        }
+        self.rows += records.len() as u64;
+        Ok(())
+    }
+
+    /// Finishes the file and gives it its final name, returning the path, row
+    /// count and size in bytes.
+    pub fn seal(self) -> Result<(PathBuf, u64, u64)> {
+        match self.writer {
+            Writer::Parquet(writer) => {
+                writer.close()?;
+            }
+            Writer::Jsonl(writer) => {
+                let mut file = writer.finish()?;
+                file.flush()?;
+                file.get_ref().sync_all()?;
+            }
+        }
+        fs::rename(&self.partial, &self.path)
+            .with_context(|| format!("renaming {}", self.partial.display()))?;
+        let bytes = fs::metadata(&self.path)?.len();
+        Ok((self.path, self.rows, bytes))
+    }
+}
+
+/// Deletes `.partial` files under `dir`, returning how many there were.
+pub fn remove_partials(dir: &Path) -> Result<usize> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            removed += remove_partials(&path)?;
+        } else if path.to_string_lossy().ends_with(PARTIAL_SUFFIX) {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
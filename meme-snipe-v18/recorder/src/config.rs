@@ -0,0 +1,80 @@
+// recorder/src/config.rs
+use lazy_static::lazy_static;
+use std::env;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Parquet,
+    Jsonl, // gzip-compressed, one event per line
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Parquet => "parquet",
+            Format::Jsonl => "jsonl.gz",
+        }
+    }
+}
+
+pub struct Config {
+    pub redis_url: String,
+    pub dir: String,
+    pub format: Format,
+    pub streams: Vec<String>, // Empty = every events:* stream, rediscovered periodically
+    pub discovery_interval_secs: u64,
+    pub rotate_secs: u64,
+    pub batch_size: usize,
+    pub redis_retention_secs: u64, // 0 = never trim
+}
+
+impl Config {
+    fn load() -> Self {
+        Self {
+            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://redis:6379".to_string()),
+            dir: env::var("RECORDER_DIR").unwrap_or_else(|_| "/app/recorder".to_string()),
+            format: match env::var("RECORDER_FORMAT")
+                .unwrap_or_else(|_| "parquet".to_string())
+                .to_lowercase()
+                .as_str()
+            {
+                "parquet" => Format::Parquet,
+                "jsonl" => Format::Jsonl,
+                other => panic!("RECORDER_FORMAT must be parquet or jsonl, got {}", other),
+            },
+            streams: list("RECORDER_STREAMS").collect(),
+            discovery_interval_secs: env::var("RECORDER_DISCOVERY_INTERVAL_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .expect("RECORDER_DISCOVERY_INTERVAL_SECS must be an integer"),
+            rotate_secs: env::var("RECORDER_ROTATE_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .expect("RECORDER_ROTATE_SECS must be an integer"),
+            batch_size: env::var("RECORDER_BATCH_SIZE")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .expect("RECORDER_BATCH_SIZE must be an integer"),
+            redis_retention_secs: env::var("RECORDER_REDIS_RETENTION_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .expect("RECORDER_REDIS_RETENTION_SECS must be an integer"),
+        }
+    }
+}
+
+/// Non-empty, trimmed entries of a comma-separated variable.
+fn list(var: &str) -> impl Iterator<Item = String> {
+    env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load();
+}
@@ -0,0 +1,310 @@
+// recorder/src/main.rs
+//! Tails every `events:*` stream (or RECORDER_STREAMS) and archives each
+//! entry under RECORDER_DIR, partitioned by event type and UTC day, as
+//! zstd Parquet or gzipped JSONL (RECORDER_FORMAT). Every RECORDER_ROTATE_SECS
+//! and on shutdown the open files are sealed and only then are the stream
+//! offsets checkpointed, so a crash re-reads, rather than loses, whatever was
+//! in flight (an entry can therefore appear in two files; dedupe on `id`). With RECORDER_REDIS_RETENTION_SECS set, streams are then
+//! trimmed to that window, never past what has been archived.
+
+mod archive;
+mod config;
+
+use crate::archive::{remove_partials, ArchiveFile, Record};
+use crate::config::CONFIG;
+use anyhow::Result;
+use axum::{routing::get, Router};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use redis::aio::MultiplexedConnection;
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const OFFSETS_KEY: &str = "recorder:stream_offsets"; // Hash: stream -> last archived id
+
+lazy_static! {
+    static ref EVENTS_RECORDED: IntCounterVec = register_int_counter_vec!(
+        "recorder_events_recorded_total",
+        "Stream entries written to an archive file, by stream.",
+        &["stream"]
+    )
+    .unwrap();
+    static ref FILES_SEALED: IntCounterVec = register_int_counter_vec!(
+        "recorder_files_sealed_total",
+        "Archive files completed, by stream.",
+        &["stream"]
+    )
+    .unwrap();
+    static ref BYTES_SEALED: IntCounterVec = register_int_counter_vec!(
+        "recorder_bytes_sealed_total",
+        "Bytes of completed archive files, by stream.",
+        &["stream"]
+    )
+    .unwrap();
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn health_handler() -> &'static str {
+    "OK"
+}
+
+/// The (milliseconds, sequence) of a stream id, for ordering.
+fn parse_id(id: &str) -> (i64, u64) {
+    let (ms, seq) = id.split_once('-').unwrap_or((id, "0"));
+    (ms.parse().unwrap_or(0), seq.parse().unwrap_or(0))
+}
+
+struct Recorder {
+    conn: MultiplexedConnection,
+    dir: PathBuf,
+    offsets: HashMap<String, String>, // Stream -> last id read
+    files: HashMap<(String, String), ArchiveFile>, // (stream, day) -> open file
+}
+
+impl Recorder {
+    async fn new(mut conn: MultiplexedConnection) -> Result<Self> {
+        let dir = PathBuf::from(&CONFIG.dir);
+        // Their entries were never checkpointed and will be read again
+        let removed = remove_partials(&dir)?;
+        if removed > 0 {
+            warn!(
+                removed,
+                "Removed unfinished archive files from an earlier run."
+            );
+        }
+        let offsets: HashMap<String, String> = conn.hgetall(OFFSETS_KEY).await?;
+        Ok(Self {
+            conn,
+            dir,
+            offsets,
+            files: HashMap::new(),
+        })
+    }
+
+    /// Adds newly seen streams, read from the start of what Redis retains.
+    async fn discover(&mut self) -> Result<()> {
+        let streams: Vec<String> = if CONFIG.streams.is_empty() {
+            let mut keys = Vec::new();
+            let mut iter = self.conn.scan_match::<_, String>("events:*").await?;
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        } else {
+            CONFIG.streams.clone()
+        };
+        for stream in streams {
+            if !self.offsets.contains_key(&stream) {
+                info!(stream = %stream, "Recording new stream.");
+                self.offsets.insert(stream, "0".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next batch into the open files; only archive errors are returned.
+    async fn read(&mut self) -> Result<()> {
+        if self.offsets.is_empty() {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            return Ok(());
+        }
+        let keys: Vec<&str> = self.offsets.keys().map(String::as_str).collect();
+        let ids: Vec<&str> = self.offsets.values().map(String::as_str).collect();
+        let reply: StreamReadReply = match self
+            .conn
+            .xread_options(
+                &keys,
+                &ids,
+                &StreamReadOptions::default()
+                    .count(CONFIG.batch_size)
+                    .block(1000),
+            )
+            .await
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                error!(error = %e, "Failed to read streams.");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                return Ok(());
+            }
+        };
+
+        for stream in reply.keys {
+            let Some(last) = stream.ids.last().map(|m| m.id.clone()) else {
+                continue;
+            };
+            let mut by_day: HashMap<String, Vec<Record>> = HashMap::new();
+            for message in stream.ids {
+                let received_at_ms = parse_id(&message.id).0;
+                let day = chrono::DateTime::from_timestamp_millis(received_at_ms)
+                    .unwrap_or_default()
+                    .format("%Y-%m-%d")
+                    .to_string();
+                let event = match message.get::<String>("event") {
+                    Some(event) => event,
+                    // Not one of ours: keep every field rather than drop it
+                    None => {
+                        let fields: HashMap<String, String> = message
+                            .map
+                            .iter()
+                            .filter_map(|(k, v)| {
+                                Some((k.clone(), redis::from_redis_value(v).ok()?))
+                            })
+                            .collect();
+                        serde_json::to_string(&fields)?
+                    }
+                };
+                by_day.entry(day).or_default().push(Record {
+                    id: message.id,
+                    received_at_ms,
+                    event,
+                });
+            }
+            let event_type = stream.key.trim_start_matches("events:");
+            for (day, records) in by_day {
+                let key = (stream.key.clone(), day);
+                if !self.files.contains_key(&key) {
+                    let file = ArchiveFile::create(
+                        &self.dir,
+                        event_type,
+                        &key.1,
+                        &records[0].id,
+                        CONFIG.format,
+                    )?;
+                    self.files.insert(key.clone(), file);
+                }
+                self.files.get_mut(&key).unwrap().write(&records)?;
+                EVENTS_RECORDED
+                    .with_label_values(&[&stream.key])
+                    .inc_by(records.len() as u64);
+            }
+            self.offsets.insert(stream.key, last);
+        }
+        Ok(())
+    }
+
+    /// Seals the open files, then checkpoints offsets and trims the streams.
+    async fn rotate(&mut self) -> Result<()> {
+        for ((stream, day), file) in self.files.drain() {
+            let (path, rows, bytes) = file.seal()?;
+            info!(stream = %stream, day = %day, rows, bytes, path = %path.display(), "Sealed archive file.");
+            FILES_SEALED.with_label_values(&[&stream]).inc();
+            BYTES_SEALED.with_label_values(&[&stream]).inc_by(bytes);
+        }
+        let offsets: Vec<(&String, &String)> = self.offsets.iter().collect();
+        if offsets.is_empty() {
+            return Ok(());
+        }
+        self.conn
+            .hset_multiple::<_, _, _, ()>(OFFSETS_KEY, &offsets)
+            .await?;
+
+        if CONFIG.redis_retention_secs == 0 {
+            return Ok(());
+        }
+        let cutoff = (chrono::Utc::now().timestamp_millis()
+            - CONFIG.redis_retention_secs as i64 * 1000)
+            .max(0);
+        for (stream, offset) in &self.offsets {
+            if offset == "0" {
+                continue; // Nothing archived yet
+            }
+            // Everything before MINID goes; the archived offset itself is kept
+            let min_id = if parse_id(offset) < (cutoff, 0) {
+                offset.clone()
+            } else {
+                format!("{}-0", cutoff)
+            };
+            let trimmed: redis::RedisResult<u64> = redis::cmd("XTRIM")
+                .arg(stream)
+                .arg("MINID")
+                .arg("~")
+                .arg(&min_id)
+                .query_async(&mut self.conn)
+                .await;
+            if let Err(e) = trimmed {
+                warn!(stream = %stream, error = %e, "Failed to trim stream.");
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn wait_for_shutdown_signal() {
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .expect("Failed to install SIGTERM handler");
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let log_level = shared_config::log_level::init();
+
+    info!(
+        "📼 Starting Recorder into {} as {:?} (rotating every {}s)...",
+        CONFIG.dir, CONFIG.format, CONFIG.rotate_secs
+    );
+
+    let client = redis::Client::open(CONFIG.redis_url.as_str())?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    let mut recorder = Recorder::new(conn).await?;
+    recorder.discover().await?;
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/health", get(health_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!(error = %e, "Metrics server stopped.");
+        }
+    });
+
+    let period = |secs: u64| {
+        let mut interval = tokio::time::interval_at(
+            tokio::time::Instant::now() + Duration::from_secs(secs),
+            Duration::from_secs(secs),
+        );
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval
+    };
+    let mut rotate = period(CONFIG.rotate_secs);
+    let mut discover = period(CONFIG.discovery_interval_secs);
+    let shutdown = wait_for_shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            // Archive errors are fatal: the restart drops the unsealed files and
+            // re-reads them from the last checkpoint
+            res = recorder.read() => res?,
+            _ = rotate.tick() => recorder.rotate().await?,
+            _ = discover.tick() => {
+                if let Err(e) = recorder.discover().await {
+                    warn!(error = %e, "Failed to discover streams.");
+                }
+            }
+            _ = &mut shutdown => {
+                info!("🛑 Shutdown signal received, sealing open files.");
+                recorder.rotate().await?;
+                break;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+// risk_guardian/src/attribution.rs
+//! Performance attribution. Closed trades over the lookback are grouped by
+//! strategy, and each numeric triggering feature stored with a trade (nested
+//! objects flattened to `a.b`, booleans as 0/1) is set against the trade's
+//! return (PnL over size): its correlation with return, and the mean return
+//! of the trades at or above the feature's median against those below. A
+//! feature whose value doesn't move returns isn't earning its place in the
+//! signal, and a strategy with a non-positive mean return over enough trades
+//! isn't earning its allocation; both are listed as prune candidates.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+
+#[derive(Clone)]
+pub struct AttributionSettings {
+    pub database_path: String,
+    pub interval_secs: u64,
+    pub lookback_days: i64,
+    pub min_trades: usize,    // Fewer samples than this and nothing is judged
+    pub min_correlation: f64, // Features below this absolute correlation everywhere are prune candidates
+}
+
+impl AttributionSettings {
+    pub fn from_env() -> Self {
+        Self {
+            database_path: env::var("DATABASE_PATH")
+                .unwrap_or_else(|_| "/app/shared/trades_v18.db".to_string()),
+            interval_secs: env::var("ATTRIBUTION_INTERVAL_SECS")
+                .unwrap_or_else(|_| "21600".to_string())
+                .parse()
+                .unwrap_or(21_600),
+            lookback_days: env::var("ATTRIBUTION_LOOKBACK_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            min_trades: env::var("ATTRIBUTION_MIN_TRADES")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .unwrap_or(20),
+            min_correlation: env::var("ATTRIBUTION_MIN_CORRELATION")
+                .unwrap_or_else(|_| "0.05".to_string())
+                .parse()
+                .unwrap_or(0.05),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct FeatureAttribution {
+    pub feature: String,
+    pub trades: usize,
+    pub correlation: Option<f64>, // Pearson, feature vs return; None if either is constant
+    pub high_mean_return_pct: f64, // Trades with the feature at or above its median
+    pub low_mean_return_pct: f64, // Trades below the median
+}
+
+#[derive(Serialize, Clone)]
+pub struct StrategyAttribution {
+    pub strategy_id: String,
+    pub trades: usize,
+    pub pnl_usd: f64,
+    pub win_rate_pct: f64,
+    pub mean_return_pct: f64,
+    pub features: Vec<FeatureAttribution>, // Strongest correlation first
+}
+
+#[derive(Serialize, Clone)]
+pub struct PruneCandidates {
+    pub strategies: Vec<String>, // Non-positive mean return over at least min_trades
+    pub features: Vec<String>,   // Weakly correlated in every strategy that records them
+}
+
+#[derive(Serialize, Clone)]
+pub struct AttributionReport {
+    pub generated_at: i64,
+    pub lookback_days: i64,
+    pub trades: usize,
+    pub unattributed_trades: usize, // Closed without stored features (older trades, feature-less strategies)
+    pub strategies: Vec<StrategyAttribution>,
+    pub prune: PruneCandidates,
+}
+
+struct Trade {
+    ret: f64,
+    pnl_usd: f64,
+    features: BTreeMap<String, f64>,
+}
+
+/// Attributes the lookback's closed trades from the trades table.
+pub async fn attribute(
+    db: &SqlitePool,
+    settings: &AttributionSettings,
+) -> Result<AttributionReport> {
+    let now = chrono::Utc::now().timestamp();
+    let since = now - settings.lookback_days * 86_400;
+    let rows: Vec<(String, f64, f64, Option<String>)> = sqlx::query_as(
+        "SELECT strategy_id, amount_usd, pnl_usd, triggering_features FROM trades \
+         WHERE pnl_usd IS NOT NULL AND close_time >= ?1 AND amount_usd > 0",
+    )
+    .bind(since)
+    .fetch_all(db)
+    .await?;
+
+    let mut unattributed_trades = 0;
+    let mut by_strategy: BTreeMap<String, Vec<Trade>> = BTreeMap::new();
+    for (strategy_id, amount_usd, pnl_usd, features_json) in &rows {
+        let mut features = BTreeMap::new();
+        match features_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str::<Value>(json).ok())
+        {
+            Some(value) => flatten("", &value, &mut features),
+            None => unattributed_trades += 1,
+        }
+        by_strategy
+            .entry(strategy_id.clone())
+            .or_default()
+            .push(Trade {
+                ret: pnl_usd / amount_usd,
+                pnl_usd: *pnl_usd,
+                features,
+            });
+    }
+
+    let mut strategies = Vec::new();
+    let mut prune_strategies = Vec::new();
+    // feature -> whether any strategy with enough samples found it informative
+    let mut feature_informative: BTreeMap<String, bool> = BTreeMap::new();
+    for (strategy_id, trades) in by_strategy {
+        let returns: Vec<f64> = trades.iter().map(|t| t.ret).collect();
+        let mean_return = mean(&returns);
+        if trades.len() >= settings.min_trades && mean_return <= 0.0 {
+            prune_strategies.push(strategy_id.clone());
+        }
+
+        let mut samples: HashMap<&str, Vec<(f64, f64)>> = HashMap::new();
+        for trade in &trades {
+            for (feature, value) in &trade.features {
+                samples
+                    .entry(feature.as_str())
+                    .or_default()
+                    .push((*value, trade.ret));
+            }
+        }
+        let mut features: Vec<FeatureAttribution> = samples
+            .into_iter()
+            .map(|(feature, pairs)| attribute_feature(feature, pairs))
+            .collect();
+        for feature in features.iter().filter(|f| f.trades >= settings.min_trades) {
+            let informative = feature
+                .correlation
+                .map_or(false, |c| c.abs() >= settings.min_correlation);
+            *feature_informative
+                .entry(feature.feature.clone())
+                .or_default() |= informative;
+        }
+        features.sort_by(|a, b| {
+            let strength = |f: &FeatureAttribution| f.correlation.map_or(-1.0, f64::abs);
+            strength(b).total_cmp(&strength(a))
+        });
+
+        strategies.push(StrategyAttribution {
+            strategy_id,
+            trades: trades.len(),
+            pnl_usd: trades.iter().map(|t| t.pnl_usd).sum(),
+            win_rate_pct: trades.iter().filter(|t| t.pnl_usd > 0.0).count() as f64
+                / trades.len() as f64
+                * 100.0,
+            mean_return_pct: mean_return * 100.0,
+            features,
+        });
+    }
+
+    Ok(AttributionReport {
+        generated_at: now,
+        lookback_days: settings.lookback_days,
+        trades: rows.len(),
+        unattributed_trades,
+        strategies,
+        prune: PruneCandidates {
+            strategies: prune_strategies,
+            features: feature_informative
+                .into_iter()
+                .filter(|(_, informative)| !informative)
+                .map(|(feature, _)| feature)
+                .collect(),
+        },
+    })
+}
+
+fn attribute_feature(feature: &str, mut pairs: Vec<(f64, f64)>) -> FeatureAttribution {
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let median = pairs[pairs.len() / 2].0;
+    let (high, low): (Vec<(f64, f64)>, Vec<(f64, f64)>) = pairs
+        .iter()
+        .copied()
+        .partition(|(value, _)| *value >= median);
+    let returns = |side: &[(f64, f64)]| side.iter().map(|p| p.1).collect::<Vec<_>>();
+    FeatureAttribution {
+        feature: feature.to_string(),
+        trades: pairs.len(),
+        correlation: correlation(&pairs),
+        high_mean_return_pct: mean(&returns(&high)) * 100.0,
+        low_mean_return_pct: mean(&returns(&low)) * 100.0,
+    }
+}
+
+/// Collects numeric leaves of `value` under dotted paths.
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, f64>) {
+    let number = match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, child, out);
+            }
+            None
+        }
+        _ => None,
+    };
+    if let Some(number) = number.filter(|n| n.is_finite()) {
+        out.insert(prefix.to_string(), number);
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    if pairs.len() < 2 {
+        return None;
+    }
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    (var_x > 0.0 && var_y > 0.0).then(|| cov / (var_x * var_y).sqrt())
+}
@@ -1,12 +1,49 @@
 // risk_guardian/src/main.rs
+mod attribution;
+mod monte_carlo;
+
 use anyhow::*;
-use axum::{routing::get, Router, Json};
+use axum::{http::StatusCode, routing::get, Router, Json};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use prometheus::{register_counter_vec, register_gauge, CounterVec, Encoder, Gauge, TextEncoder};
+use redis::streams::{StreamReadOptions, StreamReadReply};
 use redis::AsyncCommands;
-use shared_models::{alert, StrategyAllocation};
+use shared_config::{Live, Loader, Readiness, Tunables};
+use shared_models::{
+    alert, set_trading_state, spawn_heartbeat, ExecutionReport, ExecutionStatus,
+    StrategyAllocation, TradeMode, TradingState,
+};
 use std::collections::HashMap;
-use std::env;
+use std::sync::Arc;
 use tracing::{info, warn, error};
 use chrono::{DateTime, Utc, Duration};
+use attribution::{AttributionReport, AttributionSettings};
+use monte_carlo::{MonteCarloReport, MonteCarloSettings};
+
+lazy_static! {
+    static ref LIMIT_BREACHES_TOTAL: CounterVec = register_counter_vec!(
+        "risk_guardian_limit_breaches_total",
+        "Risk checks that found a limit exceeded, by limit.",
+        &["limit"]
+    )
+    .unwrap();
+    static ref PORTFOLIO_VAR_USD: Gauge = register_gauge!(
+        "risk_guardian_portfolio_var_usd",
+        "Daily 95% Value at Risk from the last portfolio check."
+    )
+    .unwrap();
+    static ref EXPOSURE_USD: Gauge = register_gauge!(
+        "risk_guardian_exposure_usd",
+        "Capital allocated to live strategies as of the last portfolio check."
+    )
+    .unwrap();
+    static ref POSITION_COUNT: Gauge = register_gauge!(
+        "risk_guardian_position_count",
+        "Open positions as of the last portfolio check."
+    )
+    .unwrap();
+}
 
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 struct RiskMetrics {
@@ -17,55 +54,125 @@ struct RiskMetrics {
     last_updated: DateTime<Utc>,
 }
 
-#[derive(Clone)]
-struct App {
-    redis_url: String,
+/// Running totals built from the executor's `execution_reports` stream.
+#[derive(serde::Serialize, Clone, Default)]
+struct ExecutionStats {
+    live_filled_usd: f64,
+    live_fees_usd: f64,
+    live_fills: u64,
+    live_failures: u64,
+    consecutive_live_failures: u32,
+}
+
+/// Limits that follow config file edits and `config_updates` while running.
+struct RiskLimits {
     max_portfolio_var: f64,
     max_daily_loss_usd: f64,
     max_position_count: u32,
+    max_consecutive_exec_failures: u32,
+}
+
+impl Tunables for RiskLimits {
+    fn load(c: &mut Loader) -> Self {
+        Self {
+            max_portfolio_var: c.get("MAX_PORTFOLIO_VAR", 10000.0), // $10k max VaR
+            max_daily_loss_usd: c.get("MAX_DAILY_LOSS_USD", 5000.0), // $5k max daily loss
+            max_position_count: c.get("MAX_POSITION_COUNT", 50), // Max 50 positions
+            max_consecutive_exec_failures: c.get("MAX_CONSECUTIVE_EXEC_FAILURES", 5), // Pause after 5 live fills fail in a row
+        }
+    }
+}
+
+#[derive(Clone)]
+struct App {
+    redis_url: String,
+    limits: Arc<Live<RiskLimits>>,
+    execution_stats: Arc<Mutex<ExecutionStats>>,
+    portfolio_capital_usd: f64,
+    monte_carlo: MonteCarloSettings,
+    monte_carlo_report: Arc<Mutex<Option<MonteCarloReport>>>,
+    attribution: AttributionSettings,
+    attribution_report: Arc<Mutex<Option<AttributionReport>>>,
+    readiness: Arc<Readiness>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
-    
-    let redis_url = env::var("REDIS_URL")
-        .unwrap_or_else(|_| "redis://redis:6379".to_string());
-    let max_portfolio_var = env::var("MAX_PORTFOLIO_VAR")
-        .unwrap_or_else(|_| "10000.0".to_string())
-        .parse::<f64>()
-        .unwrap_or(10000.0); // $10k max VaR
-    let max_daily_loss_usd = env::var("MAX_DAILY_LOSS_USD")
-        .unwrap_or_else(|_| "5000.0".to_string())
-        .parse::<f64>()
-        .unwrap_or(5000.0); // $5k max daily loss
-    let max_position_count = env::var("MAX_POSITION_COUNT")
-        .unwrap_or_else(|_| "50".to_string())
-        .parse::<u32>()
-        .unwrap_or(50); // Max 50 positions
+    let log_level = shared_config::log_level::init();
+    
+    let mut config = Loader::new("risk_guardian");
+    let redis_url = config.get("REDIS_URL", "redis://redis:6379".to_string());
+    let limits = Live::<RiskLimits>::load(&mut config);
+    let portfolio_capital_usd = config.get("PORTFOLIO_CAPITAL_USD", 1000.0); // Split across strategies by allocation weight
+    config.finish();
+
+    let readiness = Arc::new(Readiness::new("risk_guardian").redis(&redis_url));
+    readiness.preflight().await.map_err(anyhow::Error::msg)?;
+    tokio::spawn(shared_config::readiness::monitor(readiness.clone()));
     
     let app = App {
         redis_url: redis_url.clone(),
-        max_portfolio_var,
-        max_daily_loss_usd,
-        max_position_count,
+        limits: limits.clone(),
+        execution_stats: Arc::new(Mutex::new(ExecutionStats::default())),
+        portfolio_capital_usd,
+        monte_carlo: MonteCarloSettings::from_env(),
+        monte_carlo_report: Arc::new(Mutex::new(None)),
+        attribution: AttributionSettings::from_env(),
+        attribution_report: Arc::new(Mutex::new(None)),
+        readiness,
     };
     
     info!("🛡️  Starting Risk Guardian on :7200...");
-    info!("📊 Max Portfolio VaR: ${:.0}", max_portfolio_var);
-    info!("📉 Max Daily Loss: ${:.0}", max_daily_loss_usd);
-    info!("📈 Max Position Count: {}", max_position_count);
+    info!("📊 Max Portfolio VaR: ${:.0}", limits.get().max_portfolio_var);
+    info!("📉 Max Daily Loss: ${:.0}", limits.get().max_daily_loss_usd);
+    info!("📈 Max Position Count: {}", limits.get().max_position_count);
+
+    spawn_heartbeat!(redis::Client::open(redis_url.as_str())?, "risk_guardian");
+    tokio::spawn(shared_config::watch(limits, redis_url.clone()));
     
     // Start background risk monitor
     let monitor_app = app.clone();
     tokio::spawn(async move {
         monitor_portfolio_risk(monitor_app).await;
     });
+
+    // Track what the executor actually filled
+    let reports_app = app.clone();
+    tokio::spawn(async move {
+        consume_execution_reports(reports_app).await;
+    });
+
+    // Resample trade history into forward-looking outcome distributions
+    let monte_carlo_app = app.clone();
+    tokio::spawn(async move {
+        run_monte_carlo(monte_carlo_app).await;
+    });
+
+    // Tie realized PnL back to the strategies and features that produced it
+    let attribution_app = app.clone();
+    tokio::spawn(async move {
+        run_attribution(attribution_app).await;
+    });
+    
+    let metrics = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let metrics_listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(metrics_listener, metrics).await {
+            error!("Metrics server stopped: {}", e);
+        }
+    });
     
     // Start HTTP server
     let api = Router::new()
         .route("/risk", get(get_risk_metrics))
+        .route("/risk/monte_carlo", get(get_monte_carlo))
+        .route("/risk/attribution", get(get_attribution))
         .route("/health", get(health_check))
+        .route("/live", get(health_check))
+        .route("/ready", get(ready_check))
         .with_state(app);
     
     let listener = tokio::net::TcpListener::bind("0.0.0.0:7200").await?;
@@ -79,18 +186,20 @@ async fn get_risk_metrics(
 ) -> Json<serde_json::Value> {
     match calculate_portfolio_risk(&app).await {
         Ok(metrics) => {
+            let limits = app.limits.get();
             Json(serde_json::json!({
                 "totalExposureUsd": metrics.total_exposure_usd,
                 "dailyVar95": metrics.daily_var_95,
                 "maxDrawdownPct": metrics.max_drawdown_pct,
                 "positionCount": metrics.position_count,
                 "lastUpdated": metrics.last_updated,
+                "execution": app.execution_stats.lock().clone(),
                 "limits": {
-                    "maxPortfolioVar": app.max_portfolio_var,
-                    "maxDailyLossUsd": app.max_daily_loss_usd,
-                    "maxPositionCount": app.max_position_count
+                    "maxPortfolioVar": limits.max_portfolio_var,
+                    "maxDailyLossUsd": limits.max_daily_loss_usd,
+                    "maxPositionCount": limits.max_position_count
                 },
-                "status": if metrics.daily_var_95 > app.max_portfolio_var { "OVER_LIMIT" } else { "OK" }
+                "status": if metrics.daily_var_95 > limits.max_portfolio_var { "OVER_LIMIT" } else { "OK" }
             }))
         }
         Err(e) => {
@@ -103,6 +212,49 @@ async fn get_risk_metrics(
     }
 }
 
+async fn get_monte_carlo(
+    axum::extract::State(app): axum::extract::State<App>
+) -> Json<serde_json::Value> {
+    match app.monte_carlo_report.lock().clone() {
+        Some(report) => Json(serde_json::to_value(report).unwrap_or_default()),
+        None => Json(serde_json::json!({
+            "status": "PENDING",
+            "message": "No simulation has completed yet"
+        })),
+    }
+}
+
+async fn get_attribution(
+    axum::extract::State(app): axum::extract::State<App>
+) -> Json<serde_json::Value> {
+    match app.attribution_report.lock().clone() {
+        Some(report) => Json(serde_json::to_value(report).unwrap_or_default()),
+        None => Json(serde_json::json!({
+            "status": "PENDING",
+            "message": "No attribution report has been built yet"
+        })),
+    }
+}
+
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn ready_check(
+    axum::extract::State(app): axum::extract::State<App>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let status = if app.readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(app.readiness.report()))
+}
+
 async fn health_check() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "service": "risk_guardian",
@@ -125,7 +277,7 @@ async fn calculate_portfolio_risk(app: &App) -> Result<RiskMetrics> {
     // Calculate total exposure (simplified)
     let total_exposure_usd = allocations.iter()
         .filter(|a| a.is_live()) // Only count live allocations
-        .map(|a| a.weight * 10000.0) // Assume $10k base allocation per strategy
+        .map(|a| a.capital_budget_usd(app.portfolio_capital_usd))
         .sum::<f64>();
     
     // Simplified VaR calculation (in practice, would use historical returns)
@@ -180,16 +332,21 @@ async fn monitor_portfolio_risk(app: App) {
             Ok(metrics) => {
                 let client = redis::Client::open(&app.redis_url).unwrap();
                 let mut conn = client.get_async_connection().await.unwrap();
+                PORTFOLIO_VAR_USD.set(metrics.daily_var_95);
+                EXPOSURE_USD.set(metrics.total_exposure_usd);
+                POSITION_COUNT.set(metrics.position_count as f64);
+                let limits = app.limits.get();
                 
                 // Check VaR limit
-                if metrics.daily_var_95 > app.max_portfolio_var {
+                if metrics.daily_var_95 > limits.max_portfolio_var {
+                    LIMIT_BREACHES_TOTAL.with_label_values(&["portfolio_var"]).inc();
                     let msg = format!("🚨 PORTFOLIO VAR BREACH: ${:.0} exceeds limit of ${:.0}", 
-                                     metrics.daily_var_95, app.max_portfolio_var);
+                                     metrics.daily_var_95, limits.max_portfolio_var);
                     warn!("{}", msg);
                     
-                    // Send kill switch
-                    if let Err(e) = send_kill_switch(&app.redis_url, "PAUSE_VAR_BREACH").await {
-                        error!("Failed to send VaR kill switch: {}", e);
+                    // Pause trading
+                    if let Err(e) = set_trading_state!(conn, "risk_guardian", TradingState::paused("VAR_BREACH")) {
+                        error!("Failed to pause trading on VaR breach: {}", e);
                     }
                     
                     // Send alert
@@ -197,9 +354,10 @@ async fn monitor_portfolio_risk(app: App) {
                 }
                 
                 // Check position count limit
-                if metrics.position_count > app.max_position_count {
+                if metrics.position_count > limits.max_position_count {
+                    LIMIT_BREACHES_TOTAL.with_label_values(&["position_count"]).inc();
                     let msg = format!("⚠️  POSITION COUNT HIGH: {} exceeds limit of {}", 
-                                     metrics.position_count, app.max_position_count);
+                                     metrics.position_count, limits.max_position_count);
                     warn!("{}", msg);
                     alert!(conn, "{}", msg).await;
                 }
@@ -222,15 +380,226 @@ async fn monitor_portfolio_risk(app: App) {
     }
 }
 
-async fn send_kill_switch(redis_url: &str, message: &str) -> Result<()> {
-    let client = redis::Client::open(redis_url)?;
+async fn consume_execution_reports(app: App) {
+    info!("📥 Starting execution report consumer...");
+    let client = match redis::Client::open(app.redis_url.as_str()) {
+        std::result::Result::Ok(c) => c,
+        Err(e) => {
+            error!("Failed to create Redis client for execution reports: {}", e);
+            return;
+        }
+    };
+    let mut last_id = "$".to_string(); // Only new reports
+    let opts = StreamReadOptions::default().count(100).block(5000);
+
+    loop {
+        let mut conn = match client.get_async_connection().await {
+            std::result::Result::Ok(c) => c,
+            Err(e) => {
+                warn!(
+                    "Execution report consumer failed to connect to Redis: {}. Retrying in 5s.",
+                    e
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        loop {
+            let reply: redis::RedisResult<StreamReadReply> = conn
+                .xread_options(&["execution_reports"], &[last_id.as_str()], &opts)
+                .await;
+            let reply = match reply {
+                std::result::Result::Ok(r) => r,
+                Err(e) => {
+                    error!("Error reading execution_reports: {}", e);
+                    break; // Reconnect
+                }
+            };
+
+            for stream in reply.keys {
+                for entry in stream.ids {
+                    last_id = entry.id.clone();
+                    let report = entry
+                        .get::<String>("report")
+                        .and_then(|json| serde_json::from_str::<ExecutionReport>(&json).ok());
+                    match report {
+                        Some(report) => handle_execution_report(&app, &mut conn, report).await,
+                        None => warn!("Unparseable execution report {}", entry.id),
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn handle_execution_report(
+    app: &App,
+    conn: &mut redis::aio::Connection,
+    report: ExecutionReport,
+) {
+    if report.mode != TradeMode::Live || report.status == ExecutionStatus::DryRun {
+        return; // Paper fills and dry runs carry no capital risk
+    }
+
+    let consecutive_failures = {
+        let mut stats = app.execution_stats.lock();
+        if report.is_filled() {
+            stats.live_fills += 1;
+            stats.live_filled_usd += report.filled_size_usd;
+            stats.live_fees_usd += report.fees_usd;
+            stats.consecutive_live_failures = 0;
+        } else {
+            stats.live_failures += 1;
+            stats.consecutive_live_failures += 1;
+        }
+        stats.consecutive_live_failures
+    };
+
+    if consecutive_failures == app.limits.get().max_consecutive_exec_failures {
+        LIMIT_BREACHES_TOTAL
+            .with_label_values(&["consecutive_exec_failures"])
+            .inc();
+        let msg = format!(
+            "🚨 EXECUTION FAILURES: {} consecutive live fills failed (last: {} on {}: {})",
+            consecutive_failures,
+            report.strategy_id,
+            report.token_address,
+            report.error.as_deref().unwrap_or("unknown error")
+        );
+        warn!("{}", msg);
+        if let Err(e) = set_trading_state!(*conn, "risk_guardian", TradingState::paused("EXEC_FAILURES")) {
+            error!("Failed to pause trading on execution failures: {}", e);
+        }
+        alert!(*conn, "{}", msg);
+    }
+}
+
+async fn run_monte_carlo(app: App) {
+    info!(
+        "🎲 Starting Monte Carlo simulator ({} paths over {:?} days, every {}s)...",
+        app.monte_carlo.paths, app.monte_carlo.horizons_days, app.monte_carlo.interval_secs
+    );
+    let mut db = None;
+    loop {
+        if db.is_none() {
+            match monte_carlo::open_db(&app.monte_carlo.database_path).await {
+                std::result::Result::Ok(pool) => db = Some(pool),
+                Err(e) => error!(
+                    "Monte Carlo failed to open {}: {}",
+                    app.monte_carlo.database_path, e
+                ),
+            }
+        }
+        if let Some(pool) = &db {
+            if let Err(e) = monte_carlo_pass(&app, pool).await {
+                error!("Monte Carlo simulation failed: {}", e);
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(app.monte_carlo.interval_secs)).await;
+    }
+}
+
+async fn monte_carlo_pass(app: &App, pool: &sqlx::SqlitePool) -> Result<()> {
+    let client = redis::Client::open(app.redis_url.as_str())?;
     let mut conn = client.get_async_connection().await?;
-    
-    redis::cmd("PUBLISH")
-        .arg("kill_switch_channel")
-        .arg(message)
-        .query_async(&mut conn)
-        .await?;
-    
+    let allocations_json: Option<String> = conn.get("active_allocations").await?;
+    let allocations: Vec<StrategyAllocation> = match allocations_json {
+        Some(json) => serde_json::from_str(&json)?,
+        None => Vec::new(),
+    };
+
+    let report = monte_carlo::simulate(
+        pool,
+        &app.monte_carlo,
+        &allocations,
+        app.portfolio_capital_usd,
+    )
+    .await?;
+    for horizon in &report.horizons {
+        info!(
+            "🎲 {}d outlook: median PnL ${:.0} (p5 ${:.0}), p95 drawdown {:.1}%, risk of ruin {:.2}%",
+            horizon.days,
+            horizon.pnl_usd.p50,
+            horizon.pnl_usd.p5,
+            horizon.max_drawdown_pct.p95,
+            horizon.risk_of_ruin_pct
+        );
+    }
+    if let Some(horizon) = report
+        .horizons
+        .iter()
+        .find(|h| h.risk_of_ruin_pct > app.monte_carlo.max_risk_of_ruin_pct)
+    {
+        LIMIT_BREACHES_TOTAL.with_label_values(&["risk_of_ruin"]).inc();
+        let msg = format!(
+            "⚠️  RISK OF RUIN HIGH: {:.2}% chance of a {:.0}% drawdown within {} days at current allocations (limit {:.2}%)",
+            horizon.risk_of_ruin_pct,
+            report.ruin_drawdown_pct,
+            horizon.days,
+            app.monte_carlo.max_risk_of_ruin_pct
+        );
+        warn!("{}", msg);
+        alert!(conn, "{}", msg);
+    }
+
+    let report_json = serde_json::to_string(&report)?;
+    conn.set::<&str, &str, ()>("portfolio_monte_carlo", &report_json).await?;
+    *app.monte_carlo_report.lock() = Some(report);
+    Ok(())
+}
+
+async fn run_attribution(app: App) {
+    info!(
+        "🔬 Starting performance attribution ({}-day lookback, every {}s)...",
+        app.attribution.lookback_days, app.attribution.interval_secs
+    );
+    let mut db = None;
+    loop {
+        if db.is_none() {
+            match monte_carlo::open_db(&app.attribution.database_path).await {
+                std::result::Result::Ok(pool) => db = Some(pool),
+                Err(e) => error!(
+                    "Attribution failed to open {}: {}",
+                    app.attribution.database_path, e
+                ),
+            }
+        }
+        if let Some(pool) = &db {
+            if let Err(e) = attribution_pass(&app, pool).await {
+                error!("Performance attribution failed: {}", e);
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(app.attribution.interval_secs)).await;
+    }
+}
+
+async fn attribution_pass(app: &App, pool: &sqlx::SqlitePool) -> Result<()> {
+    let report = attribution::attribute(pool, &app.attribution).await?;
+    for strategy in &report.strategies {
+        let top = strategy.features.first();
+        info!(
+            "🔬 {}: {} trades, PnL ${:.2}, win rate {:.0}%, mean return {:.2}%, top feature {} (r={:.2})",
+            strategy.strategy_id,
+            strategy.trades,
+            strategy.pnl_usd,
+            strategy.win_rate_pct,
+            strategy.mean_return_pct,
+            top.map_or("none", |f| f.feature.as_str()),
+            top.and_then(|f| f.correlation).unwrap_or(0.0)
+        );
+    }
+    if !report.prune.strategies.is_empty() || !report.prune.features.is_empty() {
+        info!(
+            "✂️  Prune candidates: strategies {:?}, features {:?}",
+            report.prune.strategies, report.prune.features
+        );
+    }
+
+    let client = redis::Client::open(app.redis_url.as_str())?;
+    let mut conn = client.get_async_connection().await?;
+    let report_json = serde_json::to_string(&report)?;
+    conn.set::<&str, &str, ()>("performance_attribution", &report_json).await?;
+    *app.attribution_report.lock() = Some(report);
     Ok(())
 }
@@ -0,0 +1,292 @@
+// risk_guardian/src/monte_carlo.rs
+//! Monte Carlo portfolio outcomes. Each allocated strategy's closed trades
+//! over the lookback give a pool of per-trade returns (PnL over size) and a
+//! trade rate. Every simulated path draws each strategy's trade count for the
+//! horizon from that rate, spreads the trades over the horizon, resamples
+//! their returns with replacement and stakes them at the strategy's usual
+//! trade size, capped at its current capital budget.
+//! The paths give the distribution of horizon PnL, max drawdown and the
+//! chance of ruin, i.e. of equity touching `ruin_drawdown_pct` below start.
+
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use shared_models::StrategyAllocation;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Clone)]
+pub struct MonteCarloSettings {
+    pub database_path: String,
+    pub interval_secs: u64,
+    pub lookback_days: i64,
+    pub horizons_days: Vec<u32>,
+    pub paths: usize,
+    pub ruin_drawdown_pct: f64,
+    pub max_risk_of_ruin_pct: f64, // Alert above this on any horizon
+    pub min_trades: usize,         // Strategies with fewer closed trades are left out
+}
+
+impl MonteCarloSettings {
+    pub fn from_env() -> Self {
+        Self {
+            database_path: env::var("DATABASE_PATH")
+                .unwrap_or_else(|_| "/app/shared/trades_v18.db".to_string()),
+            interval_secs: env::var("MONTE_CARLO_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .unwrap_or(3600),
+            lookback_days: env::var("MONTE_CARLO_LOOKBACK_DAYS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .unwrap_or(90),
+            horizons_days: env::var("MONTE_CARLO_HORIZONS_DAYS")
+                .unwrap_or_else(|_| "30,90".to_string())
+                .split(',')
+                .filter_map(|d| d.trim().parse().ok())
+                .filter(|d| *d > 0)
+                .collect(),
+            paths: env::var("MONTE_CARLO_PATHS")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .unwrap_or(10_000),
+            ruin_drawdown_pct: env::var("MONTE_CARLO_RUIN_DRAWDOWN_PCT")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .unwrap_or(50.0),
+            max_risk_of_ruin_pct: env::var("MAX_RISK_OF_RUIN_PCT")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5.0),
+            min_trades: env::var("MONTE_CARLO_MIN_TRADES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+        }
+    }
+}
+
+/// What was resampled for one strategy.
+#[derive(Serialize, Clone)]
+pub struct StrategyInput {
+    pub strategy_id: String,
+    pub weight: f64,
+    pub trades: usize,
+    pub trades_per_day: f64,
+    pub mean_return_pct: f64,
+    pub stake_usd: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct Percentiles {
+    pub p1: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    fn of(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+        let at = |q: f64| {
+            if values.is_empty() {
+                0.0
+            } else {
+                values[((values.len() - 1) as f64 * q).round() as usize]
+            }
+        };
+        Self {
+            p1: at(0.01),
+            p5: at(0.05),
+            p25: at(0.25),
+            p50: at(0.50),
+            p75: at(0.75),
+            p95: at(0.95),
+            p99: at(0.99),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct HorizonOutcome {
+    pub days: u32,
+    pub mean_pnl_usd: f64,
+    pub pnl_usd: Percentiles,
+    pub max_drawdown_pct: Percentiles,
+    pub prob_loss_pct: f64,
+    pub risk_of_ruin_pct: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct MonteCarloReport {
+    pub generated_at: i64,
+    pub capital_usd: f64,
+    pub lookback_days: i64,
+    pub paths: usize,
+    pub ruin_drawdown_pct: f64,
+    pub strategies: Vec<StrategyInput>,
+    pub skipped: Vec<String>, // Allocated, but too few closed trades to resample
+    pub horizons: Vec<HorizonOutcome>,
+}
+
+struct Pool {
+    input: StrategyInput,
+    returns: Vec<f64>, // PnL over size, per closed trade
+}
+
+pub async fn open_db(path: &str) -> Result<SqlitePool> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    Ok(SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?)
+}
+
+/// Simulates the current allocations from the trades table.
+pub async fn simulate(
+    db: &SqlitePool,
+    settings: &MonteCarloSettings,
+    allocations: &[StrategyAllocation],
+    capital_usd: f64,
+) -> Result<MonteCarloReport> {
+    let since = chrono::Utc::now().timestamp() - settings.lookback_days * 86_400;
+    let rows: Vec<(String, f64, f64, i64)> = sqlx::query_as(
+        "SELECT strategy_id, amount_usd, pnl_usd, close_time FROM trades \
+         WHERE pnl_usd IS NOT NULL AND close_time >= ?1 AND amount_usd > 0",
+    )
+    .bind(since)
+    .fetch_all(db)
+    .await?;
+    let mut by_strategy: HashMap<&str, Vec<(f64, f64, i64)>> = HashMap::new();
+    for (strategy_id, amount_usd, pnl_usd, close_time) in &rows {
+        by_strategy.entry(strategy_id.as_str()).or_default().push((
+            *amount_usd,
+            *pnl_usd,
+            *close_time,
+        ));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut pools = Vec::new();
+    let mut skipped = Vec::new();
+    for allocation in allocations.iter().filter(|a| a.weight > 0.0) {
+        let trades = by_strategy
+            .get(allocation.id.as_str())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if trades.len() < settings.min_trades.max(1) {
+            skipped.push(allocation.id.clone());
+            continue;
+        }
+        // The rate runs from the first trade in the lookback, so a strategy
+        // allocated last week isn't diluted over the full window
+        let first = trades.iter().map(|t| t.2).min().unwrap_or(since);
+        let active_days = ((now - first) as f64 / 86_400.0).max(1.0);
+        let returns: Vec<f64> = trades.iter().map(|(amount, pnl, _)| pnl / amount).collect();
+        let mut sizes: Vec<f64> = trades.iter().map(|t| t.0).collect();
+        sizes.sort_by(f64::total_cmp);
+        let budget = allocation.capital_budget_usd(capital_usd);
+        pools.push(Pool {
+            input: StrategyInput {
+                strategy_id: allocation.id.clone(),
+                weight: allocation.weight,
+                trades: trades.len(),
+                trades_per_day: trades.len() as f64 / active_days,
+                mean_return_pct: returns.iter().sum::<f64>() / returns.len() as f64 * 100.0,
+                stake_usd: sizes[sizes.len() / 2].min(budget),
+            },
+            returns,
+        });
+    }
+
+    let mut rng = StdRng::from_entropy();
+    let horizons = settings
+        .horizons_days
+        .iter()
+        .map(|days| simulate_horizon(&pools, *days, capital_usd, settings, &mut rng))
+        .collect();
+    Ok(MonteCarloReport {
+        generated_at: now,
+        capital_usd,
+        lookback_days: settings.lookback_days,
+        paths: settings.paths,
+        ruin_drawdown_pct: settings.ruin_drawdown_pct,
+        strategies: pools.into_iter().map(|p| p.input).collect(),
+        skipped,
+        horizons,
+    })
+}
+
+fn simulate_horizon(
+    pools: &[Pool],
+    days: u32,
+    capital_usd: f64,
+    settings: &MonteCarloSettings,
+    rng: &mut StdRng,
+) -> HorizonOutcome {
+    let ruin_equity = capital_usd * (1.0 - settings.ruin_drawdown_pct / 100.0);
+    let mut pnls = Vec::with_capacity(settings.paths);
+    let mut drawdowns = Vec::with_capacity(settings.paths);
+    let mut ruined = 0usize;
+    let mut trades: Vec<(f64, f64)> = Vec::new(); // (time, pnl)
+    for _ in 0..settings.paths {
+        trades.clear();
+        for pool in pools {
+            let count = poisson(pool.input.trades_per_day * days as f64, rng);
+            for _ in 0..count {
+                let ret = pool.returns[rng.gen_range(0..pool.returns.len())];
+                trades.push((rng.gen::<f64>(), ret * pool.input.stake_usd));
+            }
+        }
+        trades.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (mut equity, mut peak, mut max_drawdown, mut ruin) =
+            (capital_usd, capital_usd, 0.0f64, false);
+        for (_, pnl) in &trades {
+            equity += pnl;
+            peak = peak.max(equity);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - equity) / peak * 100.0);
+            }
+            ruin |= equity <= ruin_equity;
+        }
+        pnls.push(equity - capital_usd);
+        drawdowns.push(max_drawdown);
+        ruined += ruin as usize;
+    }
+
+    let paths = settings.paths.max(1) as f64;
+    HorizonOutcome {
+        days,
+        mean_pnl_usd: pnls.iter().sum::<f64>() / paths,
+        prob_loss_pct: pnls.iter().filter(|p| **p < 0.0).count() as f64 / paths * 100.0,
+        risk_of_ruin_pct: ruined as f64 / paths * 100.0,
+        pnl_usd: Percentiles::of(pnls),
+        max_drawdown_pct: Percentiles::of(drawdowns),
+    }
+}
+
+/// A Poisson draw: Knuth's method for small means, a rounded normal beyond.
+fn poisson(mean: f64, rng: &mut StdRng) -> usize {
+    if mean <= 0.0 {
+        return 0;
+    }
+    if mean > 50.0 {
+        let u1: f64 = 1.0 - rng.gen::<f64>();
+        let u2: f64 = rng.gen();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        return (mean + z * mean.sqrt()).round().max(0.0) as usize;
+    }
+    let limit = (-mean).exp();
+    let (mut count, mut product) = (0, rng.gen::<f64>());
+    while product > limit {
+        count += 1;
+        product *= rng.gen::<f64>();
+    }
+    count
+}
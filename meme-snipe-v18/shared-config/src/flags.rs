@@ -0,0 +1,212 @@
+// shared-config/src/flags.rs
+//! Runtime feature flags: switches that turn a subsystem on or off without a
+//! rebuild or restart. Flags live in the Redis hash `flags:{MEMESNIPE_ENV}`
+//! (`flags:mainnet-paper` when unset), so each environment has its own set:
+//!
+//! ```text
+//! HSET flags:mainnet-live jito_submission off
+//! PUBLISH flags_updates jito_submission
+//! ```
+//!
+//! Values are `on`/`off` (or `true`/`false`, `1`/`0`); deleting a field hands
+//! the flag back to the default the code passes in. A service keeps its own
+//! copy in a `Flags`, read without touching Redis, and spawns `sync`, which
+//! re-reads the hash every FLAGS_REFRESH_SECS (default 10) and at once when
+//! anything is published on `flags_updates`. If Redis is unreachable the last
+//! values read stay in force; before the first read, the defaults do.
+
+use futures::stream::{BoxStream, StreamExt};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const FLAGS_UPDATES_CHANNEL: &str = "flags_updates";
+const DEFAULT_REFRESH_SECS: u64 = 10;
+
+/// Jito bundle submission; off skips the Jito path of the fallback chain.
+pub const JITO_SUBMISSION: &str = "jito_submission";
+/// Shorting through Drift perps; off rejects short entries.
+pub const DRIFT_SHORTS: &str = "drift_shorts";
+/// Token safety checks on Live entries; off lets entries through unchecked.
+pub const TOKEN_SAFETY_GATE: &str = "token_safety_gate";
+/// Prefix of the per-strategy flags, `strategy:{id}`; off stops its trading.
+pub const STRATEGY_PREFIX: &str = "strategy:";
+
+/// The hash holding this environment's flags.
+pub fn flags_key() -> String {
+    format!("flags:{}", crate::profile::env_name())
+}
+
+/// A flag value as stored; anything unrecognised counts as unset.
+pub fn parse_value(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" | "true" | "1" => Some(true),
+        "off" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// A service's cached view of the flags.
+pub struct Flags {
+    service: String,
+    key: String,
+    values: RwLock<HashMap<String, bool>>,
+}
+
+impl Flags {
+    pub fn new(service: &str) -> Arc<Self> {
+        Arc::new(Self {
+            service: service.to_string(),
+            key: flags_key(),
+            values: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Whether `flag` is on, or `default` if it isn't set.
+    pub fn enabled(&self, flag: &str, default: bool) -> bool {
+        self.values
+            .read()
+            .unwrap()
+            .get(flag)
+            .copied()
+            .unwrap_or(default)
+    }
+
+    /// Whether strategy `id` may trade; strategies are on unless flagged off.
+    pub fn strategy_enabled(&self, id: &str) -> bool {
+        self.enabled(&format!("{}{}", STRATEGY_PREFIX, id), true)
+    }
+
+    /// Swaps in a fresh read of the hash, logging what changed.
+    fn replace(&self, raw: HashMap<String, String>) {
+        let mut fresh = HashMap::new();
+        for (flag, value) in raw {
+            match parse_value(&value) {
+                Some(on) => {
+                    fresh.insert(flag, on);
+                }
+                None => {
+                    warn!(key = %self.key, flag = %flag, value = %value, "Ignoring flag with an unrecognised value.")
+                }
+            }
+        }
+        let mut values = self.values.write().unwrap();
+        for (flag, on) in &fresh {
+            if values.get(flag) != Some(on) {
+                info!(service = %self.service, flag = %flag, enabled = on, "🚩 Feature flag set.");
+            }
+        }
+        for flag in values.keys().filter(|flag| !fresh.contains_key(*flag)) {
+            info!(service = %self.service, flag = %flag, "🚩 Feature flag cleared, back to its default.");
+        }
+        *values = fresh;
+    }
+}
+
+/// Keeps `flags` current for the life of the process; spawn it once at startup.
+pub async fn sync(flags: Arc<Flags>, redis_url: String) {
+    let client = match redis::Client::open(redis_url) {
+        Ok(client) => client,
+        Err(e) => {
+            warn!(error = %e, "Feature flags fixed at their defaults: bad Redis URL.");
+            return;
+        }
+    };
+    let refresh_secs = std::env::var("FLAGS_REFRESH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_REFRESH_SECS);
+    let mut refresh = tokio::time::interval(Duration::from_secs(refresh_secs));
+    let mut messages: Option<BoxStream<'static, redis::Msg>> = None;
+    loop {
+        if messages.is_none() {
+            match subscribe(&client).await {
+                Ok(stream) => messages = Some(stream),
+                Err(e) => warn!(error = %e, "Failed to subscribe to {}.", FLAGS_UPDATES_CHANNEL),
+            }
+        }
+        tokio::select! {
+            _ = refresh.tick() => {}
+            msg = next_message(&mut messages) => {
+                if msg.is_none() {
+                    messages = None; // Resubscribed on the next pass
+                }
+            }
+        }
+        match read(&client, &flags.key).await {
+            Ok(raw) => flags.replace(raw),
+            Err(e) => {
+                warn!(key = %flags.key, error = %e, "Failed to read feature flags; keeping the last values.")
+            }
+        }
+    }
+}
+
+async fn subscribe(client: &redis::Client) -> redis::RedisResult<BoxStream<'static, redis::Msg>> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(FLAGS_UPDATES_CHANNEL).await?;
+    Ok(pubsub.into_on_message().boxed())
+}
+
+async fn next_message(messages: &mut Option<BoxStream<'static, redis::Msg>>) -> Option<redis::Msg> {
+    match messages {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await, // The refresh tick retries the subscription
+    }
+}
+
+async fn read(client: &redis::Client, key: &str) -> redis::RedisResult<HashMap<String, String>> {
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    conn.hgetall(key).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(flags: &[(&str, &str)]) -> HashMap<String, String> {
+        flags
+            .iter()
+            .map(|(flag, value)| (flag.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn flag_values() {
+        for on in ["on", "true", "1", " ON "] {
+            assert_eq!(parse_value(on), Some(true));
+        }
+        for off in ["off", "false", "0", "False"] {
+            assert_eq!(parse_value(off), Some(false));
+        }
+        assert_eq!(parse_value("yes"), None);
+        assert_eq!(parse_value(""), None);
+    }
+
+    #[test]
+    fn unset_flags_use_their_defaults() {
+        let flags = Flags::new("executor");
+        assert!(flags.enabled(JITO_SUBMISSION, true));
+        assert!(!flags.enabled(DRIFT_SHORTS, false));
+        assert!(flags.strategy_enabled("dip_buyer"));
+
+        flags.replace(raw(&[
+            (JITO_SUBMISSION, "off"),
+            (DRIFT_SHORTS, "maybe"),
+            ("strategy:dip_buyer", "0"),
+        ]));
+        assert!(!flags.enabled(JITO_SUBMISSION, true));
+        assert!(!flags.enabled(DRIFT_SHORTS, false)); // Unrecognised: still the default
+        assert!(!flags.strategy_enabled("dip_buyer"));
+        assert!(flags.strategy_enabled("momentum_5m"));
+
+        // A field deleted from the hash goes back to its default
+        flags.replace(raw(&[(DRIFT_SHORTS, "on")]));
+        assert!(flags.enabled(JITO_SUBMISSION, true));
+        assert!(flags.enabled(DRIFT_SHORTS, false));
+        assert!(flags.strategy_enabled("dip_buyer"));
+    }
+}
@@ -0,0 +1,632 @@
+// shared-config/src/lib.rs
+//! Layered service configuration. Every setting is named by its env var and
+//! looked up, highest precedence first, in the environment, the service's own
+//! section of the config file, the file's `[common]` section, and finally the
+//! default the service passes in. The file is TOML, or YAML if it ends in
+//! `.yaml`/`.yml`; it is read from MEMESNIPE_CONFIG, else
+//! `config/memesnipe.toml` if that exists. Keys are the env var names in
+//! lower case and lists may be written as arrays:
+//!
+//! ```toml
+//! [common]
+//! redis_url = "redis://redis:6379"
+//!
+//! [executor]
+//! slippage_bps = 50
+//! submission_fallback_chain = ["jito", "rpc"]
+//! ```
+//!
+//! MEMESNIPE_ENV picks an environment profile (`devnet`, `mainnet-paper` or
+//! `mainnet-live`, see the `profile` module) that adds a `[profile.NAME]`
+//! file section above the others and its own endpoint defaults below them.
+//!
+//! A `Loader` collects every problem instead of stopping at the first, and
+//! `finish` logs the effective configuration with where each value came from
+//! before failing with all of them. Started with `--check-config`, a service
+//! exits right after that step.
+//!
+//! A setting may also name a secret in Vault or AWS Secrets Manager instead
+//! of holding it (see the `secrets` module), and settings a service declares
+//! as tunables can change while it runs (see the `reload` module). Feature
+//! flags that switch whole subsystems on and off are in the `flags` module,
+//! startup preflight and readiness probes in the `readiness` module, the
+//! runtime-adjustable log filter in the `log_level` module, and backlog
+//! gauges for the streams a service reads in the `stream_lag` module.
+
+pub mod flags;
+pub mod log_level;
+pub mod profile;
+pub mod readiness;
+mod reload;
+mod secrets;
+pub mod stream_lag;
+
+pub use flags::Flags;
+pub use profile::Profile;
+pub use readiness::Readiness;
+pub use reload::{watch, ConfigChange, ConfigUpdate, Live, Tunables};
+pub use reload::{CONFIG_AUDIT_STREAM, CONFIG_UPDATES_CHANNEL};
+
+use secrets::Resolver;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::{error, info};
+
+const DEFAULT_PATH: &str = "config/memesnipe.toml";
+const COMMON_SECTION: &str = "common";
+const PROFILE_SECTION: &str = "profile";
+
+/// Where a setting's effective value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Override, // Set at runtime through `config_updates`
+    Env,
+    File(String), // Section
+    Profile(&'static str),
+    Default,
+    Unset,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Override => write!(f, "override"),
+            Source::Env => write!(f, "env"),
+            Source::File(section) => write!(f, "file [{}]", section),
+            Source::Profile(name) => write!(f, "profile {}", name),
+            Source::Default => write!(f, "default"),
+            Source::Unset => write!(f, "unset"),
+        }
+    }
+}
+
+struct Entry {
+    key: String,
+    value: String,
+    source: Source,
+}
+
+pub struct Loader {
+    service: String,
+    profile: Profile,
+    path: Option<PathBuf>,
+    file: HashMap<String, (String, String)>, // KEY -> (value, section)
+    overrides: HashMap<String, String>,
+    secrets: Resolver,
+    references: HashMap<String, String>, // KEY -> secret reference it was read from
+    entries: Vec<Entry>,
+    errors: Vec<String>,
+}
+
+impl Loader {
+    /// Reads the config file, if any, for `service`'s section.
+    pub fn new(service: &str) -> Self {
+        let mut loader = Self::with_overrides(service, HashMap::new());
+        let source = match std::env::var("MEMESNIPE_ENV") {
+            Ok(env) if !env.trim().is_empty() => Source::Env,
+            _ => Source::Default,
+        };
+        loader.record("MEMESNIPE_ENV", profile::env_name(), source);
+        loader
+    }
+
+    /// Like `new`, with `overrides` taking precedence over everything else.
+    fn with_overrides(service: &str, overrides: HashMap<String, String>) -> Self {
+        let mut loader = Self {
+            service: service.to_string(),
+            profile: profile::DEFAULT_PROFILE,
+            path: None,
+            file: HashMap::new(),
+            overrides,
+            secrets: Resolver::default(),
+            references: HashMap::new(),
+            entries: Vec::new(),
+            errors: Vec::new(),
+        };
+        match Profile::current() {
+            Ok(profile) => loader.profile = profile,
+            Err(e) => loader.errors.push(e),
+        }
+        let (path, explicit) = config_path();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => match parse_file(&path, &text, service, loader.profile) {
+                Ok(file) => {
+                    loader.file = file;
+                    loader.path = Some(path);
+                }
+                Err(e) => loader.errors.push(format!("{}: {}", path.display(), e)),
+            },
+            Err(e) if explicit || e.kind() != std::io::ErrorKind::NotFound => {
+                loader.errors.push(format!("{}: {}", path.display(), e))
+            }
+            Err(_) => {} // No file: env and defaults only
+        }
+        loader
+    }
+
+    /// A setting with a default.
+    pub fn get<T>(&mut self, key: &str, default: T) -> T
+    where
+        T: FromStr + fmt::Display,
+        T::Err: fmt::Display,
+    {
+        match self.lookup(key) {
+            Some((raw, source)) => self.parse(key, raw, source).unwrap_or(default),
+            None => {
+                self.record(key, default.to_string(), Source::Default);
+                default
+            }
+        }
+    }
+
+    /// A setting with no default; leaving it unset is an error.
+    pub fn required<T>(&mut self, key: &str) -> T
+    where
+        T: FromStr + Default,
+        T::Err: fmt::Display,
+    {
+        match self.lookup(key) {
+            Some((raw, source)) => self.parse(key, raw, source).unwrap_or_default(),
+            None => {
+                self.errors.push(format!("{} must be set", key));
+                self.record(key, String::new(), Source::Unset);
+                T::default()
+            }
+        }
+    }
+
+    /// A setting that may be left unset; an empty value counts as unset.
+    pub fn optional<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.lookup(key).filter(|(raw, _)| !raw.trim().is_empty()) {
+            Some((raw, source)) => self.parse(key, raw, source),
+            None => {
+                self.record(key, String::new(), Source::Unset);
+                None
+            }
+        }
+    }
+
+    /// A switch that is on only when set to `true`.
+    pub fn flag(&mut self, key: &str, default: bool) -> bool {
+        match self.lookup(key) {
+            Some((raw, source)) => {
+                let on = raw.trim() == "true";
+                self.record(key, raw, source);
+                on
+            }
+            None => {
+                self.record(key, default.to_string(), Source::Default);
+                default
+            }
+        }
+    }
+
+    /// A comma-separated list (or an array in the file); blank items are dropped.
+    pub fn list(&mut self, key: &str, default: &str) -> Vec<String> {
+        self.get(key, default.to_string())
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    /// The environment profile the service runs under.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Records a problem found while interpreting a setting's value.
+    pub fn invalid(&mut self, key: &str, problem: impl fmt::Display) {
+        self.errors.push(format!("{}: {}", key, problem));
+    }
+
+    /// Records `problem` unless `ok`, for checks that span settings.
+    pub fn check(&mut self, ok: bool, problem: impl fmt::Display) {
+        if !ok {
+            self.errors.push(problem.to_string());
+        }
+    }
+
+    /// Logs the effective configuration, then panics listing every problem
+    /// found. With `--check-config` on the command line it exits instead,
+    /// non-zero if anything was wrong.
+    pub fn finish(self) {
+        let file = self
+            .path
+            .as_ref()
+            .map_or_else(|| "none".to_string(), |p| p.display().to_string());
+        info!(service = %self.service, file = %file, "⚙️  Effective configuration:");
+        for entry in &self.entries {
+            info!(
+                "⚙️    {} = {} ({})",
+                entry.key,
+                shown(&entry.key, &entry.value),
+                entry.source
+            );
+        }
+        let check_only = std::env::args().any(|arg| arg == "--check-config");
+        if self.errors.is_empty() {
+            if check_only {
+                info!("✅ Configuration OK.");
+                std::process::exit(0);
+            }
+            return;
+        }
+        for problem in &self.errors {
+            error!("❌ {}", problem);
+        }
+        if check_only {
+            std::process::exit(1);
+        }
+        panic!(
+            "Invalid {} configuration: {}",
+            self.service,
+            self.errors.join("; ")
+        );
+    }
+
+    /// The raw value of `key` and where it came from, with any secret
+    /// reference resolved.
+    fn lookup(&mut self, key: &str) -> Option<(String, Source)> {
+        let (value, source) = if let Some(value) = self.overrides.get(key) {
+            (value.clone(), Source::Override)
+        } else if let Ok(value) = std::env::var(key) {
+            (value, Source::Env)
+        } else if let Some((value, section)) = self.file.get(key) {
+            (value.clone(), Source::File(section.clone()))
+        } else {
+            let value = self.profile.default_for(key)?;
+            (value.to_string(), Source::Profile(self.profile.name()))
+        };
+        if !secrets::is_reference(value.trim()) {
+            self.references.remove(key);
+            return Some((value, source));
+        }
+        let reference = value.trim().to_string();
+        let resolved = match self.secrets.resolve(&reference) {
+            Ok(secret) => secret,
+            Err(e) => {
+                self.errors.push(format!("{} ({}): {}", key, source, e));
+                String::new()
+            }
+        };
+        self.references.insert(key.to_string(), reference);
+        Some((resolved, source))
+    }
+
+    fn parse<T>(&mut self, key: &str, raw: String, source: Source) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let parsed = raw.trim().parse::<T>();
+        if let Err(e) = &parsed {
+            self.errors
+                .push(format!("{} ({}) is invalid: {}", key, source, e));
+        }
+        self.record(key, raw, source);
+        parsed.ok()
+    }
+
+    /// Remembers a setting for the startup log; secrets are recorded as the
+    /// reference they were read from.
+    fn record(&mut self, key: &str, value: String, source: Source) {
+        let value = self.references.get(key).cloned().unwrap_or(value);
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push(Entry {
+            key: key.to_string(),
+            value,
+            source,
+        });
+    }
+}
+
+/// The config file to read and whether it was named explicitly.
+fn config_path() -> (PathBuf, bool) {
+    match std::env::var("MEMESNIPE_CONFIG") {
+        Ok(path) if !path.is_empty() => (PathBuf::from(path), true),
+        _ => (PathBuf::from(DEFAULT_PATH), false),
+    }
+}
+
+/// The `[common]`, `[service]` and `[profile.NAME]` tables of a config file,
+/// flattened to upper-cased keys, each section overriding the ones before.
+fn parse_file(
+    path: &Path,
+    text: &str,
+    service: &str,
+    profile: Profile,
+) -> Result<HashMap<String, (String, String)>, String> {
+    let root: Value = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(text).map_err(|e| e.to_string())?,
+        _ => toml::from_str(text).map_err(|e| e.to_string())?,
+    };
+    let Value::Object(sections) = root else {
+        return Err("expected a table of sections".to_string());
+    };
+    let profile_section = format!("{}.{}", PROFILE_SECTION, profile.name());
+    let profile_table = match sections.get(PROFILE_SECTION) {
+        Some(Value::Object(profiles)) => profiles.get(profile.name()),
+        Some(_) => return Err(format!("[{}] must be a table of profiles", PROFILE_SECTION)),
+        None => None,
+    };
+    let mut settings = HashMap::new();
+    for (section, table) in [
+        (COMMON_SECTION, sections.get(COMMON_SECTION)),
+        (service, sections.get(service)),
+        (profile_section.as_str(), profile_table),
+    ] {
+        match table {
+            Some(Value::Object(table)) => {
+                for (key, value) in table {
+                    let value = scalar(value).ok_or_else(|| {
+                        format!("[{}] {} must be a value or a list", section, key)
+                    })?;
+                    settings.insert(key.to_uppercase(), (value, section.to_string()));
+                }
+            }
+            Some(_) => return Err(format!("[{}] must be a table", section)),
+            None => {}
+        }
+    }
+    Ok(settings)
+}
+
+/// A file value as the string its env var would hold; lists are comma-joined.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some(String::new()),
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+        Value::Array(items) => items
+            .iter()
+            .map(scalar)
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        Value::Object(_) => None,
+    }
+}
+
+/// A value as printed at startup: secrets masked and URL query strings,
+/// which often carry API keys, cut off.
+fn shown(key: &str, value: &str) -> String {
+    let secret = ["KEY", "TOKEN", "SECRET", "PASSWORD"]
+        .iter()
+        .any(|word| key.contains(word))
+        && !key.ends_with("_FILENAME")
+        && !key.ends_with("_PATH");
+    if secret && !value.is_empty() && !secrets::is_reference(value) {
+        return "********".to_string();
+    }
+    match value.split_once('?') {
+        Some((base, _)) if base.contains("://") => format!("{}?…", base),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FILE: &str = r#"
+        [common]
+        shared_config_test_layers = "common"
+        jito_rpc_url = "https://common.jito.example"
+
+        [executor]
+        shared_config_test_layers = "service"
+        shared_config_test_list = ["jito", " rpc ", ""]
+        shared_config_test_number = "fifty"
+
+        [profile.devnet]
+        shared_config_test_layers = "profile"
+    "#;
+
+    /// A devnet executor loader reading `file`, with `overrides` on top.
+    fn loader(file: &str, overrides: &[(&str, &str)]) -> Loader {
+        let overrides = overrides
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let mut loader = Loader::with_overrides("executor", overrides);
+        loader.profile = Profile::Devnet;
+        loader.errors.clear();
+        loader.file = parse_file(
+            Path::new("memesnipe.toml"),
+            file,
+            "executor",
+            Profile::Devnet,
+        )
+        .unwrap();
+        loader
+    }
+
+    fn source(loader: &Loader, key: &str) -> Source {
+        let entry = loader.entries.iter().find(|entry| entry.key == key);
+        entry.unwrap().source.clone()
+    }
+
+    #[test]
+    fn precedence() {
+        // Tests share the process environment, so these names are used nowhere else
+        std::env::set_var("SHARED_CONFIG_TEST_ENV_OVER_FILE", "env");
+        std::env::set_var("SHARED_CONFIG_TEST_OVERRIDE", "env");
+        let file = format!("{}\nshared_config_test_env_over_file = \"profile\"\n", FILE);
+        let mut c = loader(&file, &[("SHARED_CONFIG_TEST_OVERRIDE", "override")]);
+
+        let overridden: String = c.get("SHARED_CONFIG_TEST_OVERRIDE", "default".to_string());
+        assert_eq!(overridden, "override");
+        assert_eq!(source(&c, "SHARED_CONFIG_TEST_OVERRIDE"), Source::Override);
+
+        let env: String = c.get("SHARED_CONFIG_TEST_ENV_OVER_FILE", "default".to_string());
+        assert_eq!(env, "env");
+        assert_eq!(source(&c, "SHARED_CONFIG_TEST_ENV_OVER_FILE"), Source::Env);
+
+        let profile: String = c.get("SHARED_CONFIG_TEST_LAYERS", "default".to_string());
+        assert_eq!(profile, "profile");
+        let section = Source::File("profile.devnet".to_string());
+        assert_eq!(source(&c, "SHARED_CONFIG_TEST_LAYERS"), section);
+
+        // [common] beats the profile's own endpoint defaults
+        let jito: String = c.get("JITO_RPC_URL", "default".to_string());
+        assert_eq!(jito, "https://common.jito.example");
+        let section = Source::File(COMMON_SECTION.to_string());
+        assert_eq!(source(&c, "JITO_RPC_URL"), section);
+
+        let rpc: String = c.get("SOLANA_RPC_URL", "default".to_string());
+        assert_eq!(rpc, "https://api.devnet.solana.com");
+        assert_eq!(source(&c, "SOLANA_RPC_URL"), Source::Profile("devnet"));
+
+        let default = c.get("SHARED_CONFIG_TEST_UNSET", 7u64);
+        assert_eq!(default, 7);
+        assert_eq!(source(&c, "SHARED_CONFIG_TEST_UNSET"), Source::Default);
+        assert!(c.errors.is_empty(), "{:?}", c.errors);
+    }
+
+    #[test]
+    fn sections_stack() {
+        let without_profile = FILE.replace("[profile.devnet]", "[profile.mainnet-live]");
+        let mut c = loader(&without_profile, &[]);
+        let service: String = c.get("SHARED_CONFIG_TEST_LAYERS", String::new());
+        assert_eq!(service, "service");
+
+        let common_only = "[common]\nshared_config_test_layers = \"common\"\n";
+        let mut c = loader(common_only, &[]);
+        let common: String = c.get("SHARED_CONFIG_TEST_LAYERS", String::new());
+        assert_eq!(common, "common");
+
+        let other_service = FILE.replace("[executor]", "[signer]");
+        let file = parse_file(
+            Path::new("memesnipe.toml"),
+            &other_service,
+            "executor",
+            Profile::MainnetPaper,
+        )
+        .unwrap();
+        assert_eq!(
+            file["SHARED_CONFIG_TEST_LAYERS"],
+            ("common".to_string(), COMMON_SECTION.to_string())
+        );
+    }
+
+    #[test]
+    fn arrays_become_lists() {
+        let mut c = loader(FILE, &[]);
+        assert_eq!(
+            c.list("SHARED_CONFIG_TEST_LIST", "rpc"),
+            vec!["jito".to_string(), "rpc".to_string()]
+        );
+        assert_eq!(
+            c.list("SHARED_CONFIG_TEST_NO_LIST", ""),
+            Vec::<String>::new()
+        );
+
+        let yaml = "executor:\n  shared_config_test_list:\n    - jito\n    - 2\n    - true\n";
+        let file = parse_file(
+            Path::new("memesnipe.yaml"),
+            yaml,
+            "executor",
+            Profile::Devnet,
+        )
+        .unwrap();
+        assert_eq!(file["SHARED_CONFIG_TEST_LIST"].0, "jito,2,true");
+
+        assert_eq!(scalar(&json!([["a", "b"], "c"])).unwrap(), "a,b,c");
+        assert_eq!(scalar(&json!(null)).unwrap(), "");
+        assert!(scalar(&json!({ "nested": 1 })).is_none());
+        let nested = "[executor]\nshared_config_test_table = { nested = 1 }\n";
+        let err = parse_file(
+            Path::new("memesnipe.toml"),
+            nested,
+            "executor",
+            Profile::Devnet,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "[executor] shared_config_test_table must be a value or a list"
+        );
+    }
+
+    #[test]
+    fn problems_are_collected() {
+        let mut c = loader(FILE, &[]);
+        let number = c.get("SHARED_CONFIG_TEST_NUMBER", 50u64);
+        assert_eq!(number, 50);
+        let unset: String = c.required("SHARED_CONFIG_TEST_REQUIRED");
+        assert_eq!(unset, "");
+        assert_eq!(source(&c, "SHARED_CONFIG_TEST_REQUIRED"), Source::Unset);
+        c.invalid("SHARED_CONFIG_TEST_LIST", "unknown route");
+        c.check(true, "not recorded");
+        c.check(false, "limits must be ordered");
+
+        assert_eq!(c.errors.len(), 4, "{:?}", c.errors);
+        assert!(c.errors[0].starts_with("SHARED_CONFIG_TEST_NUMBER (file [executor]) is invalid"));
+        assert_eq!(c.errors[1], "SHARED_CONFIG_TEST_REQUIRED must be set");
+        assert_eq!(c.errors[2], "SHARED_CONFIG_TEST_LIST: unknown route");
+        assert_eq!(c.errors[3], "limits must be ordered");
+    }
+
+    #[test]
+    fn shown_values() {
+        assert_eq!(shown("HELIUS_API_KEY", "h-123"), "********");
+        assert_eq!(shown("TWITTER_BEARER_TOKEN", "t-456"), "********");
+        assert_eq!(shown("REDIS_PASSWORD", ""), "");
+        assert_eq!(
+            shown(
+                "HELIUS_API_KEY",
+                "vault://secret/data/memesnipe#helius_api_key"
+            ),
+            "vault://secret/data/memesnipe#helius_api_key"
+        );
+        assert_eq!(
+            shown("WALLET_KEYPAIR_FILENAME", "/keys/wallet.json"),
+            "/keys/wallet.json"
+        );
+        assert_eq!(
+            shown("SOLANA_RPC_URL", "https://rpc.example/?api-key=h-123"),
+            "https://rpc.example/?…"
+        );
+        assert_eq!(shown("STRATEGY_NAME", "why?"), "why?");
+    }
+
+    #[test]
+    fn secrets_are_resolved_and_recorded_as_references() {
+        let reference = "vault://secret/data/memesnipe#helius_api_key";
+        let mut c = loader(FILE, &[("HELIUS_API_KEY", reference)]);
+        c.secrets = Resolver::seeded(&[(
+            "vault://secret/data/memesnipe",
+            json!({ "helius_api_key": "h-123" }),
+        )]);
+
+        let key: String = c.required("HELIUS_API_KEY");
+        assert_eq!(key, "h-123");
+        let entry = c
+            .entries
+            .iter()
+            .find(|e| e.key == "HELIUS_API_KEY")
+            .unwrap();
+        assert_eq!(entry.value, reference);
+        assert_eq!(entry.source, Source::Override);
+
+        let missing = "vault://secret/data/memesnipe#jito_auth_key";
+        let mut c = loader(FILE, &[("JITO_AUTH_KEY", missing)]);
+        c.secrets = Resolver::seeded(&[(
+            "vault://secret/data/memesnipe",
+            json!({ "helius_api_key": "h-123" }),
+        )]);
+        assert_eq!(c.optional::<String>("JITO_AUTH_KEY"), None);
+        assert_eq!(
+            c.errors,
+            vec!["JITO_AUTH_KEY (override): vault://secret/data/memesnipe has no field jito_auth_key"]
+        );
+    }
+}
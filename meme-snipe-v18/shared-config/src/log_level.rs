@@ -0,0 +1,110 @@
+// shared-config/src/log_level.rs
+//! Runtime log levels. `init` installs the service's tracing subscriber with
+//! its EnvFilter (RUST_LOG, `info` when unset) behind a reload handle, and
+//! `router` serves, beside the service's /metrics:
+//!
+//! - `GET /admin/log_level`: the filter directives in force;
+//! - `PUT /admin/log_level`: replaces them with the request body, in RUST_LOG
+//!   syntax, answering 400 and leaving the filter alone if it doesn't parse.
+//!
+//! For example:
+//!
+//! ```text
+//! curl -X PUT --data 'info,executor::strategies::dip_buyer=debug' \
+//!     http://executor:9090/admin/log_level
+//! ```
+//!
+//! A change lasts until the process restarts, so one strategy can be cranked
+//! to debug during an incident without losing in-memory state; PUT the old
+//! directives back to undo it.
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde_json::{json, Value};
+use tracing::{level_filters::LevelFilter, warn};
+use tracing_subscriber::{prelude::*, reload, EnvFilter, Registry};
+
+#[derive(Clone)]
+pub struct LogLevel {
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+/// Installs the global subscriber. Directives in RUST_LOG that don't parse
+/// are skipped, as before runtime changes existed.
+pub fn init() -> LogLevel {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+    let (filter, handle) = reload::Layer::new(filter);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+    LogLevel { handle }
+}
+
+impl LogLevel {
+    /// The directives in force, as RUST_LOG would spell them.
+    pub fn current(&self) -> String {
+        self.handle
+            .with_current(|filter| filter.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Swaps in `directives`, returning them as now in force.
+    pub fn set(&self, directives: &str) -> Result<String, String> {
+        let filter = EnvFilter::builder()
+            .with_default_directive(LevelFilter::INFO.into())
+            .parse(directives.trim())
+            .map_err(|e| format!("invalid log directives {:?}: {}", directives.trim(), e))?;
+        let previous = self.current();
+        self.handle.reload(filter).map_err(|e| e.to_string())?;
+        let current = self.current();
+        warn!(from = %previous, to = %current, "🔧 Log level changed.");
+        Ok(current)
+    }
+}
+
+/// The /admin/log_level routes, to merge into the service's metrics router.
+pub fn router<S>(level: LogLevel) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/admin/log_level", get(get_handler).put(put_handler))
+        .with_state(level)
+}
+
+async fn get_handler(State(level): State<LogLevel>) -> Json<Value> {
+    Json(json!({ "directives": level.current() }))
+}
+
+async fn put_handler(State(level): State<LogLevel>, body: String) -> (StatusCode, Json<Value>) {
+    match level.set(&body) {
+        Ok(directives) => (StatusCode::OK, Json(json!({ "directives": directives }))),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_swaps_valid_directives_only() {
+        // Not installed globally; the layer only has to outlive the handle's use
+        let (_layer, handle) = reload::Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+        let level = LogLevel { handle };
+        assert_eq!(level.current(), "info");
+
+        let set = level.set(" info,executor::strategies::dip_buyer=debug\n");
+        assert_eq!(set.unwrap(), level.current());
+        assert!(level
+            .current()
+            .contains("executor::strategies::dip_buyer=debug"));
+
+        let before = level.current();
+        let err = level.set("executor=loud").unwrap_err();
+        assert!(err.starts_with("invalid log directives \"executor=loud\""));
+        assert_eq!(level.current(), before);
+    }
+}
@@ -0,0 +1,154 @@
+// shared-config/src/profile.rs
+//! Environment profiles. MEMESNIPE_ENV names the one a deployment runs as:
+//!
+//! - `devnet`: Solana devnet, paper trading only;
+//! - `mainnet-paper` (the default when unset): mainnet data and quotes, paper
+//!   trading only;
+//! - `mainnet-live`: mainnet, and the only profile that may trade Live.
+//!
+//! The profile supplies its own defaults for the chain endpoints, ranked just
+//! above a service's built-in defaults, and the config file may hold a
+//! `[profile.NAME]` section that beats the file's other sections while that
+//! profile is active, for whatever else differs between environments (token
+//! lists, sizing):
+//!
+//! ```toml
+//! [profile.devnet]
+//! market_data_tokens = ["4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"]
+//! ```
+//!
+//! Outside `mainnet-live` the allocator grants no Live allocations, the
+//! executor and signer treat every trade as paper, and a service configured
+//! with PAPER_TRADING_MODE=false refuses to start.
+
+use std::fmt;
+use std::str::FromStr;
+
+pub const DEFAULT_PROFILE: Profile = Profile::MainnetPaper;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Devnet,
+    MainnetPaper,
+    MainnetLive,
+}
+
+impl Profile {
+    /// The profile MEMESNIPE_ENV names, `mainnet-paper` if it's unset.
+    pub fn current() -> Result<Self, String> {
+        env_name().parse()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Profile::Devnet => "devnet",
+            Profile::MainnetPaper => "mainnet-paper",
+            Profile::MainnetLive => "mainnet-live",
+        }
+    }
+
+    /// Whether anything may trade Live under this profile.
+    pub fn allows_live(&self) -> bool {
+        *self == Profile::MainnetLive
+    }
+
+    /// The profile's own default for setting `key`, if it has one.
+    pub(crate) fn default_for(&self, key: &str) -> Option<&'static str> {
+        let value = match (self, key) {
+            (Profile::Devnet, "SOLANA_RPC_URL") => "https://api.devnet.solana.com",
+            // Jito runs no devnet block engine; nothing is submitted on devnet anyway
+            (Profile::Devnet, "JITO_RPC_URL") => "https://dallas.testnet.block-engine.jito.wtf/api",
+            (_, "SOLANA_RPC_URL") => "https://api.mainnet-beta.solana.com",
+            (_, "JITO_RPC_URL") => "https://mainnet.block-engine.jito.wtf/api",
+            // Jupiter only quotes mainnet liquidity, so devnet paper fills price off mainnet too
+            (_, "JUPITER_API_URL") => "https://quote-api.jup.ag/v6",
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "devnet" => Ok(Profile::Devnet),
+            "mainnet-paper" => Ok(Profile::MainnetPaper),
+            "mainnet-live" => Ok(Profile::MainnetLive),
+            other => Err(format!(
+                "MEMESNIPE_ENV must be devnet, mainnet-paper or mainnet-live, got {}",
+                other
+            )),
+        }
+    }
+}
+
+/// MEMESNIPE_ENV as set, or the default profile's name; per-environment
+/// Redis keys are named after it.
+pub fn env_name() -> String {
+    std::env::var("MEMESNIPE_ENV")
+        .ok()
+        .map(|env| env.trim().to_string())
+        .filter(|env| !env.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.name().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profile_names() {
+        for profile in [Profile::Devnet, Profile::MainnetPaper, Profile::MainnetLive] {
+            assert_eq!(profile.name().parse::<Profile>(), Ok(profile));
+        }
+        assert_eq!(
+            " mainnet-live\n".parse::<Profile>(),
+            Ok(Profile::MainnetLive)
+        );
+        assert_eq!(
+            "mainnet".parse::<Profile>(),
+            Err(
+                "MEMESNIPE_ENV must be devnet, mainnet-paper or mainnet-live, got mainnet"
+                    .to_string()
+            )
+        );
+        assert!("Devnet".parse::<Profile>().is_err());
+    }
+
+    #[test]
+    fn only_mainnet_live_allows_live() {
+        assert!(!Profile::Devnet.allows_live());
+        assert!(!Profile::MainnetPaper.allows_live());
+        assert!(!DEFAULT_PROFILE.allows_live());
+        assert!(Profile::MainnetLive.allows_live());
+    }
+
+    #[test]
+    fn endpoint_defaults() {
+        assert_eq!(
+            Profile::Devnet.default_for("SOLANA_RPC_URL"),
+            Some("https://api.devnet.solana.com")
+        );
+        assert_eq!(
+            Profile::MainnetLive.default_for("SOLANA_RPC_URL"),
+            Some("https://api.mainnet-beta.solana.com")
+        );
+        assert_eq!(
+            Profile::Devnet.default_for("JUPITER_API_URL"),
+            Profile::MainnetPaper.default_for("JUPITER_API_URL")
+        );
+        assert_ne!(
+            Profile::Devnet.default_for("JITO_RPC_URL"),
+            Profile::MainnetPaper.default_for("JITO_RPC_URL")
+        );
+        assert_eq!(Profile::MainnetLive.default_for("REDIS_URL"), None);
+    }
+}
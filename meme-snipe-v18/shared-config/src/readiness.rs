@@ -0,0 +1,235 @@
+// shared-config/src/readiness.rs
+//! Startup preflight and readiness. A service lists what it can't work
+//! without (Redis, the signer, a Solana RPC node, another service's `/ready`)
+//! in a `Readiness`, then:
+//!
+//! - awaits `preflight` before starting its loops, which keeps probing until
+//!   everything answers or PREFLIGHT_TIMEOUT_SECS (default 60) pass, and then
+//!   fails naming every dependency still down, so a misconfigured service
+//!   exits at once instead of failing inside its loop;
+//! - spawns `monitor`, which re-probes every READINESS_INTERVAL_SECS
+//!   (default 10) and logs each dependency going down or coming back;
+//! - serves `/live` (the process is up) and `/ready` (200 with `report` when
+//!   every dependency answered its last probe, 503 otherwise).
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const PREFLIGHT_RETRY: Duration = Duration::from_secs(2);
+const DEFAULT_PREFLIGHT_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_INTERVAL_SECS: u64 = 10;
+
+enum Probe {
+    Redis(String),
+    Http(String),      // Any 2xx
+    SolanaRpc(String), // getHealth answers "ok"
+}
+
+/// The outcome of a dependency's last probe.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    pub ok: bool,
+    pub error: Option<String>,
+    pub checked_at: i64,
+}
+
+pub struct Readiness {
+    service: String,
+    dependencies: Vec<(String, Probe)>,
+    status: RwLock<BTreeMap<String, DependencyStatus>>,
+}
+
+impl Readiness {
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            dependencies: Vec::new(),
+            status: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Redis, answering PING.
+    pub fn redis(mut self, url: &str) -> Self {
+        self.dependencies
+            .push(("redis".to_string(), Probe::Redis(url.to_string())));
+        self
+    }
+
+    /// An HTTP endpoint answering GET with a 2xx.
+    pub fn http(mut self, name: &str, url: &str) -> Self {
+        self.dependencies
+            .push((name.to_string(), Probe::Http(url.to_string())));
+        self
+    }
+
+    /// A Solana RPC node reporting itself healthy.
+    pub fn solana_rpc(mut self, url: &str) -> Self {
+        self.dependencies
+            .push(("solana_rpc".to_string(), Probe::SolanaRpc(url.to_string())));
+        self
+    }
+
+    /// Probes every dependency once, recording the results. True if all answered.
+    pub async fn check(&self) -> bool {
+        let checked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let mut all_ok = true;
+        for (name, probe) in &self.dependencies {
+            let result = match tokio::time::timeout(PROBE_TIMEOUT, probe.run()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("no answer within {}s", PROBE_TIMEOUT.as_secs())),
+            };
+            let status = DependencyStatus {
+                ok: result.is_ok(),
+                error: result.err(),
+                checked_at,
+            };
+            all_ok &= status.ok;
+            let previous = self
+                .status
+                .write()
+                .unwrap()
+                .insert(name.clone(), status.clone());
+            match (previous.map(|p| p.ok), &status.error) {
+                (Some(true), Some(e)) => {
+                    error!(service = %self.service, dependency = %name, error = %e, "❌ Dependency down, not ready.")
+                }
+                (Some(false), None) => {
+                    info!(service = %self.service, dependency = %name, "✅ Dependency back.")
+                }
+                _ => {}
+            }
+        }
+        all_ok
+    }
+
+    /// Waits for every dependency to answer, failing with those that never
+    /// did once PREFLIGHT_TIMEOUT_SECS pass.
+    pub async fn preflight(&self) -> Result<(), String> {
+        let timeout = Duration::from_secs(env_secs(
+            "PREFLIGHT_TIMEOUT_SECS",
+            DEFAULT_PREFLIGHT_TIMEOUT_SECS,
+        ));
+        let started = tokio::time::Instant::now();
+        loop {
+            if self.check().await {
+                info!(service = %self.service, dependencies = self.dependencies.len(), "✅ Preflight passed.");
+                return Ok(());
+            }
+            let down = self.down();
+            if started.elapsed() >= timeout {
+                return Err(format!(
+                    "{} preflight failed after {}s: {}",
+                    self.service,
+                    timeout.as_secs(),
+                    down.join("; ")
+                ));
+            }
+            warn!(service = %self.service, down = %down.join("; "), "Preflight waiting on dependencies.");
+            tokio::time::sleep(PREFLIGHT_RETRY).await;
+        }
+    }
+
+    /// Whether every dependency answered its last probe.
+    pub fn is_ready(&self) -> bool {
+        let status = self.status.read().unwrap();
+        status.len() == self.dependencies.len() && status.values().all(|s| s.ok)
+    }
+
+    /// The body of `/ready`.
+    pub fn report(&self) -> Value {
+        json!({
+            "service": self.service,
+            "ready": self.is_ready(),
+            "dependencies": *self.status.read().unwrap(),
+        })
+    }
+
+    /// Dependencies whose last probe failed, with why.
+    fn down(&self) -> Vec<String> {
+        self.status
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|(name, s)| s.error.as_ref().map(|error| format!("{}: {}", name, error)))
+            .collect()
+    }
+}
+
+impl Probe {
+    async fn run(&self) -> Result<(), String> {
+        match self {
+            Probe::Redis(url) => {
+                let mut conn = redis::Client::open(url.as_str())
+                    .map_err(|e| e.to_string())?
+                    .get_multiplexed_async_connection()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                redis::cmd("PING")
+                    .query_async::<_, String>(&mut conn)
+                    .await
+                    .map(drop)
+                    .map_err(|e| e.to_string())
+            }
+            Probe::Http(url) => {
+                let url = url.clone();
+                blocking(move || {
+                    ureq::get(&url)
+                        .timeout(PROBE_TIMEOUT)
+                        .call()
+                        .map(drop)
+                        .map_err(|e| e.to_string())
+                })
+                .await
+            }
+            Probe::SolanaRpc(url) => {
+                let url = url.clone();
+                blocking(move || {
+                    let body: Value = ureq::post(&url)
+                        .timeout(PROBE_TIMEOUT)
+                        .send_json(json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" }))
+                        .map_err(|e| e.to_string())?
+                        .into_json()
+                        .map_err(|e| e.to_string())?;
+                    match body.get("result").and_then(Value::as_str) {
+                        Some("ok") => Ok(()),
+                        _ => Err(format!("unhealthy: {}", body)),
+                    }
+                })
+                .await
+            }
+        }
+    }
+}
+
+async fn blocking(
+    probe: impl FnOnce() -> Result<(), String> + Send + 'static,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(probe)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn env_secs(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default)
+}
+
+/// Keeps `readiness` current for the life of the process; spawn it once
+/// after the preflight.
+pub async fn monitor(readiness: Arc<Readiness>) {
+    let interval = Duration::from_secs(env_secs("READINESS_INTERVAL_SECS", DEFAULT_INTERVAL_SECS));
+    loop {
+        tokio::time::sleep(interval).await;
+        readiness.check().await;
+    }
+}
@@ -0,0 +1,449 @@
+// shared-config/src/reload.rs
+//! Settings that change without a redeploy. A service groups its tunables
+//! (slippage, stops, limits, tips) in a struct implementing `Tunables`, keeps
+//! it in a `Live` read through `Live::get` at the point of use, and spawns
+//! `watch`, which re-reads them when the config file's modification time
+//! changes or an update arrives on the `config_updates` channel:
+//!
+//! ```text
+//! PUBLISH config_updates '{"service":"executor","key":"SLIPPAGE_BPS","value":"75","changed_by":"ops"}'
+//! ```
+//!
+//! `service` may be `*` or left out to reach every service, a null `value`
+//! clears the override, and an update without a `key` only re-reads the file.
+//! Overrides beat the environment and the file, and are kept in the
+//! `config_overrides:{service}` hash so a restart restores them. Since the
+//! environment beats the file, a tunable meant to be edited in the file must
+//! not also be set in the environment.
+//!
+//! A reload is all or nothing: if any tunable fails to parse, the values in
+//! force stay. Every applied change is logged and appended to `config_audit`.
+//! Settings outside the service's tunables only change on restart.
+
+use crate::{config_path, shown, Loader, Source};
+use futures::stream::{BoxStream, StreamExt};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+pub const CONFIG_UPDATES_CHANNEL: &str = "config_updates";
+pub const CONFIG_AUDIT_STREAM: &str = "config_audit";
+const OVERRIDES_KEY_PREFIX: &str = "config_overrides:";
+const FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+const AUDIT_MAXLEN: usize = 10_000;
+
+/// A group of settings that may change while the service runs.
+pub trait Tunables: Send + Sync + Sized + 'static {
+    /// Reads every tunable; called at startup and again on each reload.
+    fn load(c: &mut Loader) -> Self;
+}
+
+/// A message on `config_updates`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigUpdate {
+    #[serde(default)]
+    pub service: Option<String>, // None or "*": every service
+    #[serde(default)]
+    pub key: Option<String>, // None: only re-read the file
+    #[serde(default)]
+    pub value: Option<String>, // None: clear the override
+    #[serde(default)]
+    pub changed_by: Option<String>,
+}
+
+/// One applied change, as appended to `config_audit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub service: String,
+    pub key: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub source: String,  // Where the new value comes from
+    pub trigger: String, // file, update or restore
+    pub changed_by: Option<String>,
+    pub timestamp: i64,
+}
+
+struct Snapshot<T> {
+    value: Arc<T>,
+    settings: BTreeMap<String, (String, Source)>,
+}
+
+/// The tunables in force, swapped whole on each reload.
+pub struct Live<T> {
+    service: String,
+    current: RwLock<Snapshot<T>>,
+    overrides: Mutex<HashMap<String, String>>,
+}
+
+impl<T: Tunables> Live<T> {
+    /// Reads the tunables as part of the service's startup configuration.
+    pub fn load(c: &mut Loader) -> Arc<Self> {
+        let before: HashSet<String> = c.entries.iter().map(|e| e.key.clone()).collect();
+        let value = T::load(c);
+        let settings = c
+            .entries
+            .iter()
+            .filter(|e| !before.contains(&e.key))
+            .map(|e| (e.key.clone(), (e.value.clone(), e.source.clone())))
+            .collect();
+        Arc::new(Self {
+            service: c.service.clone(),
+            current: RwLock::new(Snapshot {
+                value: Arc::new(value),
+                settings,
+            }),
+            overrides: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The values in force. Take a fresh copy per decision rather than
+    /// holding on to one, or later changes won't be seen.
+    pub fn get(&self) -> Arc<T> {
+        self.current.read().unwrap().value.clone()
+    }
+
+    /// Re-reads every tunable with `update` applied; nothing changes unless
+    /// all of them are valid.
+    fn apply(
+        &self,
+        update: &ConfigUpdate,
+        trigger: &str,
+    ) -> Result<Vec<ConfigChange>, Vec<String>> {
+        let mut overrides = self.overrides.lock().unwrap().clone();
+        if let Some(key) = &update.key {
+            let key = key.trim().to_uppercase();
+            if !self.current.read().unwrap().settings.contains_key(&key) {
+                return Err(vec![format!(
+                    "{} is not a hot-reloadable setting of {}",
+                    key, self.service
+                )]);
+            }
+            match &update.value {
+                Some(value) => overrides.insert(key, value.clone()),
+                None => overrides.remove(&key),
+            };
+        }
+
+        let mut c = Loader::with_overrides(&self.service, overrides.clone());
+        let value = T::load(&mut c);
+        if !c.errors.is_empty() {
+            return Err(c.errors);
+        }
+        let settings: BTreeMap<String, (String, Source)> = c
+            .entries
+            .into_iter()
+            .map(|e| (e.key, (e.value, e.source)))
+            .collect();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let mut current = self.current.write().unwrap();
+        let changes = settings
+            .iter()
+            .filter_map(|(key, (new_value, source))| {
+                let (old_value, _) = current.settings.get(key)?;
+                (old_value.trim() != new_value.trim()).then(|| ConfigChange {
+                    service: self.service.clone(),
+                    key: key.clone(),
+                    old_value: shown(key, old_value),
+                    new_value: shown(key, new_value),
+                    source: source.to_string(),
+                    trigger: trigger.to_string(),
+                    changed_by: update.changed_by.clone(),
+                    timestamp,
+                })
+            })
+            .collect();
+        *current = Snapshot {
+            value: Arc::new(value),
+            settings,
+        };
+        *self.overrides.lock().unwrap() = overrides;
+        Ok(changes)
+    }
+}
+
+/// Keeps `live` current for the life of the process; spawn it once at startup.
+pub async fn watch<T: Tunables>(live: Arc<Live<T>>, redis_url: String) {
+    let client = match redis::Client::open(redis_url) {
+        Ok(client) => client,
+        Err(e) => {
+            error!(service = %live.service, error = %e, "Config reload disabled: bad Redis URL.");
+            return;
+        }
+    };
+    let overrides_key = format!("{}{}", OVERRIDES_KEY_PREFIX, live.service);
+    let (path, _) = config_path();
+    let mut modified = modified_at(&path);
+
+    // One at a time, so an override that no longer applies can't block the rest
+    match saved_overrides(&client, &overrides_key).await {
+        Ok(saved) => {
+            for (key, value) in saved {
+                let update = ConfigUpdate {
+                    key: Some(key),
+                    value: Some(value),
+                    ..ConfigUpdate::default()
+                };
+                let result = live.apply(&update, "restore");
+                record(&client, &live.service, "restore", result).await;
+            }
+        }
+        Err(e) => warn!(service = %live.service, error = %e, "Failed to restore config overrides."),
+    }
+
+    let mut file_poll = tokio::time::interval(FILE_POLL_INTERVAL);
+    let mut messages: Option<BoxStream<'static, redis::Msg>> = None;
+    loop {
+        if messages.is_none() {
+            match subscribe(&client).await {
+                Ok(stream) => messages = Some(stream),
+                Err(e) => warn!(error = %e, "Failed to subscribe to {}.", CONFIG_UPDATES_CHANNEL),
+            }
+        }
+        tokio::select! {
+            _ = file_poll.tick() => {
+                let now = modified_at(&path);
+                if now != modified {
+                    modified = now;
+                    let result = live.apply(&ConfigUpdate::default(), "file");
+                    record(&client, &live.service, "file", result).await;
+                }
+            }
+            msg = next_message(&mut messages) => {
+                let Some(msg) = msg else {
+                    warn!("{} subscription closed, resubscribing.", CONFIG_UPDATES_CHANNEL);
+                    messages = None;
+                    tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+                    continue;
+                };
+                let payload: String = msg.get_payload().unwrap_or_default();
+                let update = match serde_json::from_str::<ConfigUpdate>(&payload) {
+                    Ok(update) => update,
+                    Err(e) => {
+                        warn!(payload = %payload, error = %e, "Ignoring malformed config update.");
+                        continue;
+                    }
+                };
+                if update
+                    .service
+                    .as_deref()
+                    .is_some_and(|s| s != "*" && s != live.service)
+                {
+                    continue;
+                }
+                let result = live.apply(&update, "update");
+                if let (Ok(_), Some(key)) = (&result, &update.key) {
+                    let key = key.trim().to_uppercase();
+                    if let Err(e) = save_override(&client, &overrides_key, &key, update.value.as_deref()).await {
+                        warn!(key = %key, error = %e, "Applied config override was not saved; a restart will drop it.");
+                    }
+                }
+                record(&client, &live.service, "update", result).await;
+            }
+        }
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+async fn subscribe(client: &redis::Client) -> redis::RedisResult<BoxStream<'static, redis::Msg>> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(CONFIG_UPDATES_CHANNEL).await?;
+    Ok(pubsub.into_on_message().boxed())
+}
+
+async fn next_message(messages: &mut Option<BoxStream<'static, redis::Msg>>) -> Option<redis::Msg> {
+    match messages {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await, // The file poll retries the subscription
+    }
+}
+
+async fn saved_overrides(
+    client: &redis::Client,
+    key: &str,
+) -> redis::RedisResult<HashMap<String, String>> {
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    conn.hgetall(key).await
+}
+
+async fn save_override(
+    client: &redis::Client,
+    key: &str,
+    setting: &str,
+    value: Option<&str>,
+) -> redis::RedisResult<()> {
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    match value {
+        Some(value) => conn.hset(key, setting, value).await,
+        None => conn.hdel(key, setting).await,
+    }
+}
+
+/// Logs the outcome of a reload and appends each change to the audit stream.
+async fn record(
+    client: &redis::Client,
+    service: &str,
+    trigger: &str,
+    result: Result<Vec<ConfigChange>, Vec<String>>,
+) {
+    let changes = match result {
+        Ok(changes) => changes,
+        Err(errors) => {
+            error!(
+                service,
+                trigger,
+                errors = %errors.join("; "),
+                "❌ Config reload rejected; keeping the current values."
+            );
+            return;
+        }
+    };
+    if changes.is_empty() {
+        info!(service, trigger, "⚙️  Config reloaded, no tunables changed.");
+        return;
+    }
+    let mut conn = match client.get_multiplexed_async_connection().await {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            warn!(error = %e, "Config changes applied but not audited: Redis unavailable.");
+            None
+        }
+    };
+    for change in changes {
+        info!(
+            service,
+            key = %change.key,
+            old = %change.old_value,
+            new = %change.new_value,
+            source = %change.source,
+            trigger,
+            changed_by = change.changed_by.as_deref().unwrap_or("-"),
+            "⚙️  Config changed."
+        );
+        let Some(conn) = conn.as_mut() else {
+            continue;
+        };
+        let json = serde_json::to_string(&change).unwrap_or_default();
+        let added: redis::RedisResult<String> = redis::cmd("XADD")
+            .arg(CONFIG_AUDIT_STREAM)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(AUDIT_MAXLEN)
+            .arg("*")
+            .arg("change")
+            .arg(json)
+            .query_async(conn)
+            .await;
+        if let Err(e) = added {
+            warn!(key = %change.key, error = %e, "Failed to audit config change.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVICE: &str = "reload_test";
+
+    struct Limits {
+        slippage_bps: u64,
+        api_key: String,
+    }
+
+    impl Tunables for Limits {
+        fn load(c: &mut Loader) -> Self {
+            Self {
+                slippage_bps: c.get("RELOAD_TEST_SLIPPAGE_BPS", 50),
+                api_key: c.get("RELOAD_TEST_API_KEY", String::new()),
+            }
+        }
+    }
+
+    fn live() -> Arc<Live<Limits>> {
+        Live::load(&mut Loader::with_overrides(SERVICE, HashMap::new()))
+    }
+
+    fn update(key: &str, value: Option<&str>) -> ConfigUpdate {
+        ConfigUpdate {
+            key: Some(key.to_string()),
+            value: value.map(str::to_string),
+            changed_by: Some("ops".to_string()),
+            ..ConfigUpdate::default()
+        }
+    }
+
+    #[test]
+    fn overrides_apply_and_clear() {
+        let live = live();
+        assert_eq!(live.get().slippage_bps, 50);
+
+        let changes = live
+            .apply(&update("reload_test_slippage_bps", Some("75")), "update")
+            .unwrap();
+        assert_eq!(live.get().slippage_bps, 75);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "RELOAD_TEST_SLIPPAGE_BPS");
+        assert_eq!(
+            (changes[0].old_value.as_str(), changes[0].new_value.as_str()),
+            ("50", "75")
+        );
+        assert_eq!(changes[0].source, "override");
+        assert_eq!(changes[0].changed_by.as_deref(), Some("ops"));
+
+        // Re-reading with nothing new changes nothing
+        let changes = live.apply(&ConfigUpdate::default(), "file").unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(live.get().slippage_bps, 75);
+
+        let changes = live
+            .apply(&update("RELOAD_TEST_SLIPPAGE_BPS", None), "update")
+            .unwrap();
+        assert_eq!(live.get().slippage_bps, 50);
+        assert_eq!(changes[0].source, "default");
+    }
+
+    #[test]
+    fn invalid_updates_change_nothing() {
+        let live = live();
+        live.apply(&update("RELOAD_TEST_SLIPPAGE_BPS", Some("75")), "update")
+            .unwrap();
+
+        let errors = live
+            .apply(&update("RELOAD_TEST_SLIPPAGE_BPS", Some("fast")), "update")
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("RELOAD_TEST_SLIPPAGE_BPS (override) is invalid"));
+        assert_eq!(live.get().slippage_bps, 75);
+
+        let errors = live
+            .apply(&update("REDIS_URL", Some("redis://elsewhere")), "update")
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["REDIS_URL is not a hot-reloadable setting of reload_test"]
+        );
+    }
+
+    #[test]
+    fn audited_secrets_are_masked() {
+        let live = live();
+        let changes = live
+            .apply(&update("RELOAD_TEST_API_KEY", Some("k-123")), "update")
+            .unwrap();
+        assert_eq!(live.get().api_key, "k-123");
+        assert_eq!(changes[0].old_value, "");
+        assert_eq!(changes[0].new_value, "********");
+    }
+}
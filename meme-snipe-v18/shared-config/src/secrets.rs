@@ -0,0 +1,340 @@
+// shared-config/src/secrets.rs
+//! Secret references. Any setting may hold a reference to a secret store in
+//! place of its value, resolved when the service loads its configuration:
+//!
+//! ```text
+//! HELIUS_API_KEY=vault://secret/data/memesnipe#helius_api_key
+//! TWITTER_BEARER_TOKEN=aws-sm://memesnipe/prod#twitter_bearer_token
+//! ```
+//!
+//! - `vault://PATH#FIELD` reads FIELD of the secret at PATH from HashiCorp
+//!   Vault at VAULT_ADDR, authenticating with VAULT_TOKEN or the token in
+//!   VAULT_TOKEN_FILE (and VAULT_NAMESPACE if set). KV v1 and v2 both work.
+//! - `aws-sm://SECRET_ID[#FIELD]` reads a secret from AWS Secrets Manager in
+//!   AWS_REGION, whole or one field of its JSON. Credentials come from
+//!   AWS_ACCESS_KEY_ID/AWS_SECRET_ACCESS_KEY (and AWS_SESSION_TOKEN), or the
+//!   ECS task role.
+//!
+//! Resolved values stay in the service's memory: they are not written back to
+//! the environment or to disk, and the startup log shows the reference, never
+//! the value. Each secret is fetched once per load, however many settings
+//! read from it.
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const VAULT_SCHEME: &str = "vault://";
+const AWS_SCHEME: &str = "aws-sm://";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const ECS_CREDENTIALS_HOST: &str = "http://169.254.170.2";
+
+/// Whether `value` names a secret rather than being one.
+pub(crate) fn is_reference(value: &str) -> bool {
+    value.starts_with(VAULT_SCHEME) || value.starts_with(AWS_SCHEME)
+}
+
+/// Fetches referenced secrets, keeping each one for the rest of the load.
+#[derive(Default)]
+pub(crate) struct Resolver {
+    fetched: HashMap<String, Value>,
+}
+
+impl Resolver {
+    /// The value `reference` points at.
+    pub(crate) fn resolve(&mut self, reference: &str) -> Result<String, String> {
+        let (location, field) = match reference.split_once('#') {
+            Some((location, field)) => (location, Some(field)),
+            None => (reference, None),
+        };
+        if !self.fetched.contains_key(location) {
+            let secret = if let Some(path) = location.strip_prefix(VAULT_SCHEME) {
+                vault_secret(path)?
+            } else if let Some(id) = location.strip_prefix(AWS_SCHEME) {
+                aws_secret(id)?
+            } else {
+                return Err(format!("{} is not a secret reference", reference));
+            };
+            self.fetched.insert(location.to_string(), secret);
+        }
+        let secret = &self.fetched[location];
+        match (field, secret) {
+            (None, Value::String(value)) => Ok(value.clone()),
+            (None, _) => Err(format!("{} needs a #field", location)),
+            (Some(field), Value::String(json)) => {
+                let parsed: Value = serde_json::from_str(json)
+                    .map_err(|_| format!("{} is not JSON, so it has no fields", location))?;
+                pick(&parsed, location, field)
+            }
+            (Some(field), secret) => pick(secret, location, field),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Resolver {
+    /// A resolver already holding `secrets` by location, so nothing is fetched.
+    pub(crate) fn seeded(secrets: &[(&str, Value)]) -> Self {
+        Self {
+            fetched: secrets
+                .iter()
+                .map(|(location, secret)| (location.to_string(), secret.clone()))
+                .collect(),
+        }
+    }
+}
+
+fn pick(secret: &Value, location: &str, field: &str) -> Result<String, String> {
+    match secret.get(field) {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(Value::Null) | None => Err(format!("{} has no field {}", location, field)),
+        Some(value) => Ok(value.to_string()),
+    }
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// The `data` of a Vault secret; KV v2 nests it one level deeper.
+fn vault_secret(path: &str) -> Result<Value, String> {
+    let addr = env("VAULT_ADDR").ok_or("VAULT_ADDR must be set to read vault:// secrets")?;
+    let token = match (env("VAULT_TOKEN"), env("VAULT_TOKEN_FILE")) {
+        (Some(token), _) => token,
+        (None, Some(file)) => std::fs::read_to_string(&file)
+            .map_err(|e| format!("VAULT_TOKEN_FILE {}: {}", file, e))?
+            .trim()
+            .to_string(),
+        (None, None) => return Err("VAULT_TOKEN or VAULT_TOKEN_FILE must be set".to_string()),
+    };
+    let url = format!("{}/v1/{}", addr.trim_end_matches('/'), path);
+    let mut request = ureq::get(&url)
+        .timeout(REQUEST_TIMEOUT)
+        .set("X-Vault-Token", &token);
+    if let Some(namespace) = env("VAULT_NAMESPACE") {
+        request = request.set("X-Vault-Namespace", &namespace);
+    }
+    let body: Value = request
+        .call()
+        .map_err(|e| format!("vault {}: {}", path, e))?
+        .into_json()
+        .map_err(|e| format!("vault {}: {}", path, e))?;
+    let data = body.get("data").cloned().unwrap_or(Value::Null);
+    Ok(match data.get("data") {
+        Some(inner) if inner.is_object() => inner.clone(),
+        _ => data,
+    })
+}
+
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+fn aws_credentials() -> Result<AwsCredentials, String> {
+    if let (Some(access_key_id), Some(secret_access_key)) =
+        (env("AWS_ACCESS_KEY_ID"), env("AWS_SECRET_ACCESS_KEY"))
+    {
+        return Ok(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token: env("AWS_SESSION_TOKEN"),
+        });
+    }
+    let uri = env("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI")
+        .ok_or("AWS credentials must be set to read aws-sm:// secrets")?;
+    let body: Value = ureq::get(&format!("{}{}", ECS_CREDENTIALS_HOST, uri))
+        .timeout(REQUEST_TIMEOUT)
+        .call()
+        .map_err(|e| format!("ECS task credentials: {}", e))?
+        .into_json()
+        .map_err(|e| format!("ECS task credentials: {}", e))?;
+    let field = |name: &str| body.get(name).and_then(Value::as_str).map(str::to_string);
+    Ok(AwsCredentials {
+        access_key_id: field("AccessKeyId").ok_or("ECS task credentials lack AccessKeyId")?,
+        secret_access_key: field("SecretAccessKey")
+            .ok_or("ECS task credentials lack SecretAccessKey")?,
+        session_token: field("Token"),
+    })
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// A secret's SecretString, via a SigV4-signed GetSecretValue call.
+fn aws_secret(id: &str) -> Result<Value, String> {
+    let region = env("AWS_REGION")
+        .or_else(|| env("AWS_DEFAULT_REGION"))
+        .ok_or("AWS_REGION must be set to read aws-sm:// secrets")?;
+    let credentials = aws_credentials()?;
+    let host = format!("secretsmanager.{}.amazonaws.com", region);
+    let body = json!({ "SecretId": id }).to_string();
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    // Signed headers must be sorted by name
+    let mut headers = vec![
+        ("content-type", "application/x-amz-json-1.1".to_string()),
+        ("host", host.clone()),
+        ("x-amz-date", amz_date.clone()),
+        ("x-amz-target", "secretsmanager.GetSecretValue".to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.sort();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        canonical_headers,
+        signed_headers,
+        hex::encode(Sha256::digest(body.as_bytes()))
+    );
+    let scope = format!("{}/{}/secretsmanager/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let mut key = hmac(
+        format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+        &date,
+    );
+    for part in [region.as_str(), "secretsmanager", "aws4_request"] {
+        key = hmac(&key, part);
+    }
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id,
+        scope,
+        signed_headers,
+        hex::encode(hmac(&key, &string_to_sign))
+    );
+
+    let mut request = ureq::post(&format!("https://{}/", host))
+        .timeout(REQUEST_TIMEOUT)
+        .set("Authorization", &authorization);
+    for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+        request = request.set(name, value);
+    }
+    let response: Value = request
+        .send_string(&body)
+        .map_err(|e| match e {
+            // The error body names the problem (missing secret, denied access)
+            ureq::Error::Status(status, response) => format!(
+                "aws-sm {}: HTTP {}: {}",
+                id,
+                status,
+                response.into_string().unwrap_or_default()
+            ),
+            e => format!("aws-sm {}: {}", id, e),
+        })?
+        .into_json()
+        .map_err(|e| format!("aws-sm {}: {}", id, e))?;
+    response
+        .get("SecretString")
+        .cloned()
+        .filter(Value::is_string)
+        .ok_or_else(|| format!("aws-sm {} has no SecretString", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references() {
+        assert!(is_reference("vault://secret/data/memesnipe#helius_api_key"));
+        assert!(is_reference("aws-sm://memesnipe/prod"));
+        assert!(!is_reference("https://vault.internal/secret"));
+        assert!(!is_reference("plain-value"));
+    }
+
+    #[test]
+    fn resolves_fields_and_whole_secrets() {
+        let mut resolver = Resolver::seeded(&[
+            (
+                "vault://secret/data/memesnipe",
+                json!({ "helius_api_key": "h-123", "port": 8200 }),
+            ),
+            (
+                "aws-sm://memesnipe/prod",
+                json!(r#"{"twitter_bearer_token":"t-456"}"#),
+            ),
+            ("aws-sm://memesnipe/plain", json!("whole-secret")),
+        ]);
+
+        assert_eq!(
+            resolver
+                .resolve("vault://secret/data/memesnipe#helius_api_key")
+                .unwrap(),
+            "h-123"
+        );
+        assert_eq!(
+            resolver
+                .resolve("vault://secret/data/memesnipe#port")
+                .unwrap(),
+            "8200"
+        );
+        // A SecretString holding JSON has fields too
+        assert_eq!(
+            resolver
+                .resolve("aws-sm://memesnipe/prod#twitter_bearer_token")
+                .unwrap(),
+            "t-456"
+        );
+        assert_eq!(
+            resolver.resolve("aws-sm://memesnipe/plain").unwrap(),
+            "whole-secret"
+        );
+    }
+
+    #[test]
+    fn unresolvable_references() {
+        let mut resolver = Resolver::seeded(&[
+            (
+                "vault://secret/data/memesnipe",
+                json!({ "helius_api_key": "h-123" }),
+            ),
+            ("aws-sm://memesnipe/plain", json!("whole-secret")),
+        ]);
+
+        let missing = resolver.resolve("vault://secret/data/memesnipe#jito_auth_key");
+        assert_eq!(
+            missing.unwrap_err(),
+            "vault://secret/data/memesnipe has no field jito_auth_key"
+        );
+        let no_field = resolver.resolve("vault://secret/data/memesnipe");
+        assert_eq!(
+            no_field.unwrap_err(),
+            "vault://secret/data/memesnipe needs a #field"
+        );
+        let not_json = resolver.resolve("aws-sm://memesnipe/plain#token");
+        assert_eq!(
+            not_json.unwrap_err(),
+            "aws-sm://memesnipe/plain is not JSON, so it has no fields"
+        );
+        let not_secret = resolver.resolve("https://example.com#token");
+        assert_eq!(
+            not_secret.unwrap_err(),
+            "https://example.com#token is not a secret reference"
+        );
+    }
+}
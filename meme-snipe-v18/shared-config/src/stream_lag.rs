@@ -0,0 +1,170 @@
+// shared-config/src/stream_lag.rs
+//! Backlog of the Redis streams a service reads. The read loop records the
+//! last id it handed on per stream in a `StreamCursors`, and `monitor` compares
+//! each stream's length and newest id with it every few seconds, so a backlog
+//! shows up as a growing lag well before its entries go stale. Services read
+//! with plain XREAD and hold no pending-entries list of their own; the PEL
+//! gauge reports any consumer group other readers hold on the same streams.
+//!
+//! The gauges are `{prefix}_stream_length`, `{prefix}_stream_lag_seconds` and
+//! `{prefix}_stream_pending_entries`, with the prefix the service passes in.
+
+use prometheus::{register_gauge_vec, GaugeVec};
+use redis::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+const CHECK_INTERVAL_SECS: u64 = 15;
+
+/// The millisecond part of a stream id; None for special ids like `$`.
+fn id_ms(id: &str) -> Option<i64> {
+    id.split('-').next()?.parse().ok()
+}
+
+/// Last id consumed per stream, shared between the read loop and `monitor`.
+pub struct StreamCursors {
+    cursors: Mutex<HashMap<String, String>>,
+    length: GaugeVec,
+    lag_seconds: GaugeVec,
+    pending_entries: GaugeVec,
+}
+
+impl StreamCursors {
+    /// Registers the gauges under `metric_prefix`; create one per process.
+    pub fn new(metric_prefix: &str) -> Arc<Self> {
+        Arc::new(Self {
+            cursors: Mutex::new(HashMap::new()),
+            length: register_gauge_vec!(
+                format!("{}_stream_length", metric_prefix),
+                "Entries currently held in a stream this service reads.",
+                &["stream"]
+            )
+            .unwrap(),
+            lag_seconds: register_gauge_vec!(
+                format!("{}_stream_lag_seconds", metric_prefix),
+                "Gap between a stream's newest id and the last id this service consumed.",
+                &["stream"]
+            )
+            .unwrap(),
+            pending_entries: register_gauge_vec!(
+                format!("{}_stream_pending_entries", metric_prefix),
+                "Entries delivered to a consumer group on the stream but not yet acknowledged.",
+                &["stream", "group"]
+            )
+            .unwrap(),
+        })
+    }
+
+    pub fn set(&self, stream: &str, id: &str) {
+        self.cursors
+            .lock()
+            .unwrap()
+            .insert(stream.to_string(), id.to_string());
+    }
+
+    fn snapshot(&self) -> Vec<(String, String)> {
+        self.cursors
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(stream, id)| (stream.clone(), id.clone()))
+            .collect()
+    }
+
+    async fn check(&self, conn: &mut redis::aio::MultiplexedConnection) {
+        for (stream, cursor) in self.snapshot() {
+            let (length, last_id) = match stream_info(conn, &stream).await {
+                Ok(Some(info)) => info,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!(stream = %stream, error = %e, "Failed to read stream info.");
+                    continue;
+                }
+            };
+            self.length
+                .with_label_values(&[&stream])
+                .set(length as f64);
+            if let (Some(last_ms), Some(cursor_ms)) = (id_ms(&last_id), id_ms(&cursor)) {
+                self.lag_seconds
+                    .with_label_values(&[&stream])
+                    .set((last_ms - cursor_ms).max(0) as f64 / 1000.0);
+            }
+            match group_pending(conn, &stream).await {
+                Ok(groups) => {
+                    for (group, pending) in groups {
+                        self.pending_entries
+                            .with_label_values(&[&stream, &group])
+                            .set(pending as f64);
+                    }
+                }
+                Err(e) => debug!(stream = %stream, error = %e, "Failed to read consumer groups."),
+            }
+        }
+    }
+}
+
+/// Length and newest id of a stream from XINFO STREAM, None if it doesn't exist yet.
+async fn stream_info(
+    conn: &mut redis::aio::MultiplexedConnection,
+    stream: &str,
+) -> redis::RedisResult<Option<(u64, String)>> {
+    let info: redis::RedisResult<HashMap<String, Value>> = redis::cmd("XINFO")
+        .arg("STREAM")
+        .arg(stream)
+        .query_async(conn)
+        .await;
+    let info = match info {
+        Ok(info) => info,
+        Err(e) if e.kind() == redis::ErrorKind::ResponseError => return Ok(None), // No such key
+        Err(e) => return Err(e),
+    };
+    let field = |name: &str| info.get(name).cloned().unwrap_or(Value::Nil);
+    Ok(Some((
+        redis::from_redis_value(&field("length")).unwrap_or(0),
+        redis::from_redis_value(&field("last-generated-id")).unwrap_or_default(),
+    )))
+}
+
+/// Pending entries per consumer group on a stream.
+async fn group_pending(
+    conn: &mut redis::aio::MultiplexedConnection,
+    stream: &str,
+) -> redis::RedisResult<Vec<(String, u64)>> {
+    let groups: Vec<HashMap<String, Value>> = redis::cmd("XINFO")
+        .arg("GROUPS")
+        .arg(stream)
+        .query_async(conn)
+        .await?;
+    Ok(groups
+        .iter()
+        .map(|group| {
+            let field = |name: &str| group.get(name).cloned().unwrap_or(Value::Nil);
+            (
+                redis::from_redis_value(&field("name")).unwrap_or_default(),
+                redis::from_redis_value(&field("pending")).unwrap_or(0),
+            )
+        })
+        .collect())
+}
+
+/// Keeps the gauges current for the life of the process; spawn it once at startup.
+pub async fn monitor(cursors: Arc<StreamCursors>, redis_url: String) {
+    let client = match redis::Client::open(redis_url) {
+        Ok(client) => client,
+        Err(e) => {
+            warn!(error = %e, "Stream lag not monitored: bad Redis URL.");
+            return;
+        }
+    };
+    info!("📏 Starting stream lag monitor...");
+    let mut interval = tokio::time::interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        match client.get_multiplexed_tokio_connection().await {
+            Ok(mut conn) => cursors.check(&mut conn).await,
+            Err(e) => debug!(error = %e, "Failed to connect to Redis."),
+        }
+    }
+}
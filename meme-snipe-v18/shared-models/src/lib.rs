@@ -19,23 +19,332 @@ pub struct StrategySpec {
     pub id: String,
     pub family: String,
     pub params: serde_json::Value,
+    /// If set, the strategy only receives events for these tokens.
+    #[serde(default)]
+    pub token_allowlist: Option<Vec<String>>,
+    /// Tokens the strategy never receives events for.
+    #[serde(default)]
+    pub token_denylist: Vec<String>,
+    /// Upper bound on the executor's per-trade slippage tolerance.
+    #[serde(default)]
+    pub max_slippage_bps: Option<u16>,
+    /// Alternative params run alongside `params` in paper-only shadow mode, to
+    /// compare decisions and hypothetical PnL before promoting them.
+    #[serde(default)]
+    pub challenger_params: Option<serde_json::Value>,
+    /// Registers the spec as an A/B variant of the spec with this id: it's
+    /// paper traded on a fixed budget, outside the weighted book, and its
+    /// params replace that spec's if it significantly outperforms them.
+    #[serde(default)]
+    pub variant_of: Option<String>,
 }
 
+/// Expected JSON type of a single strategy parameter.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Integer,
+    Number,
+    Boolean,
+    String,
+    StringList,
+    /// Object of string keys to numbers, e.g. per-source weights. Min/max
+    /// bound each value.
+    NumberMap,
+    /// List of `{"family": ..., "params": {...}}` objects describing the child
+    /// strategies of a composite spec. Each child's params are validated
+    /// against that child family's own schema.
+    ChildSpecs,
+}
+
+/// One child of a composite (e.g. ensemble) strategy spec.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChildStrategySpec {
+    pub family: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParamField {
+    pub name: String,
+    pub kind: ParamKind,
+    pub required: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Declares the shape of a strategy family's `StrategySpec.params`.
+///
+/// Strategies build one with the chained helpers below; the executor publishes
+/// them to Redis so the allocator can reject bad specs before they are allocated.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ParamSchema {
+    pub fields: Vec<ParamField>,
+}
+
+impl ParamSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn required(mut self, name: &str, kind: ParamKind) -> Self {
+        self.fields.push(ParamField {
+            name: name.to_string(),
+            kind,
+            required: true,
+            min: None,
+            max: None,
+        });
+        self
+    }
+
+    pub fn optional(mut self, name: &str, kind: ParamKind) -> Self {
+        self.fields.push(ParamField {
+            name: name.to_string(),
+            kind,
+            required: false,
+            min: None,
+            max: None,
+        });
+        self
+    }
+
+    /// Lower bound (inclusive) for the most recently added numeric field, or
+    /// on the number of children for a `ChildSpecs` field.
+    pub fn min(mut self, min: f64) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.min = Some(min);
+        }
+        self
+    }
+
+    /// Upper bound (inclusive) for the most recently added numeric field.
+    pub fn max(mut self, max: f64) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.max = Some(max);
+        }
+        self
+    }
+
+    /// Checks `params` against the schema, collecting every problem rather
+    /// than stopping at the first so a bad spec can be fixed in one pass.
+    pub fn validate(&self, params: &Value) -> Result<(), ParamValidationError> {
+        let mut errors = Vec::new();
+        let obj = match params.as_object() {
+            Some(obj) => obj,
+            None if self.fields.iter().all(|f| !f.required) => return Ok(()),
+            None => {
+                return Err(ParamValidationError {
+                    errors: vec!["params must be a JSON object".to_string()],
+                })
+            }
+        };
+
+        for field in &self.fields {
+            let value = match obj.get(&field.name) {
+                Some(Value::Null) | None => {
+                    if field.required {
+                        errors.push(format!("missing required param `{}`", field.name));
+                    }
+                    continue;
+                }
+                Some(v) => v,
+            };
+
+            let type_ok = match field.kind {
+                ParamKind::Integer => value.is_i64() || value.is_u64(),
+                ParamKind::Number => value.is_number(),
+                ParamKind::Boolean => value.is_boolean(),
+                ParamKind::String => value.is_string(),
+                ParamKind::StringList => value
+                    .as_array()
+                    .map(|a| a.iter().all(Value::is_string))
+                    .unwrap_or(false),
+                ParamKind::NumberMap => value
+                    .as_object()
+                    .map(|m| m.values().all(Value::is_number))
+                    .unwrap_or(false),
+                ParamKind::ChildSpecs => {
+                    serde_json::from_value::<Vec<ChildStrategySpec>>(value.clone()).is_ok()
+                }
+            };
+            if !type_ok {
+                errors.push(format!(
+                    "param `{}` must be {:?}, got {}",
+                    field.name, field.kind, value
+                ));
+                continue;
+            }
+
+            // For lists, min/max bound the number of entries; for maps, each value
+            let measured: Vec<f64> = match field.kind {
+                ParamKind::StringList | ParamKind::ChildSpecs => value
+                    .as_array()
+                    .map(|a| a.len() as f64)
+                    .into_iter()
+                    .collect(),
+                ParamKind::NumberMap => value
+                    .as_object()
+                    .map(|m| m.values().filter_map(Value::as_f64).collect())
+                    .unwrap_or_default(),
+                _ => value.as_f64().into_iter().collect(),
+            };
+            for n in measured {
+                if let Some(min) = field.min {
+                    if n < min {
+                        errors.push(format!(
+                            "param `{}` = {} is below minimum {}",
+                            field.name, n, min
+                        ));
+                    }
+                }
+                if let Some(max) = field.max {
+                    if n > max {
+                        errors.push(format!(
+                            "param `{}` = {} is above maximum {}",
+                            field.name, n, max
+                        ));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ParamValidationError { errors })
+        }
+    }
+
+    /// Children declared by the `ChildSpecs` fields of `params`, in order.
+    /// Empty for non-composite schemas or params that don't parse.
+    pub fn child_specs(&self, params: &Value) -> Vec<ChildStrategySpec> {
+        self.fields
+            .iter()
+            .filter(|field| field.kind == ParamKind::ChildSpecs)
+            .filter_map(|field| params.get(&field.name))
+            .filter_map(|value| serde_json::from_value::<Vec<ChildStrategySpec>>(value.clone()).ok())
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamValidationError {
+    pub errors: Vec<String>,
+}
+
+impl std::fmt::Display for ParamValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid strategy params: {}", self.errors.join("; "))
+    }
+}
+
+impl std::error::Error for ParamValidationError {}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StrategyAllocation {
     pub id: String,
+    /// Strategy family the executor builds; empty from older allocators,
+    /// which only allocated specs whose id is their family.
+    #[serde(default)]
+    pub family: String,
     pub weight: f64,
     pub sharpe_ratio: f64,
+    /// `sharpe_ratio` shrunk toward the allocator's prior by sample size; what
+    /// the strategy was ranked and weighted on.
+    #[serde(default)]
+    pub shrunk_sharpe_ratio: f64,
+    /// USD the strategy sizes against: its weight of the capital the allocator
+    /// scaled to portfolio equity. Unset by older allocators.
+    #[serde(default)]
+    pub capital_usd: Option<f64>,
     /// NEW – defaults to `Paper` until the allocator upgrades it.
     #[serde(default = "default_trade_mode")]
     pub mode: TradeMode,
+    /// Validated `StrategySpec.params`, passed to the strategy's `init()`.
+    #[serde(default)]
+    pub params: Value,
+    /// Token universe copied from the spec; enforced by the executor's event router.
+    #[serde(default)]
+    pub token_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    pub token_denylist: Vec<String>,
+    #[serde(default)]
+    pub max_slippage_bps: Option<u16>,
+    /// Validated challenger params; the executor shadows the strategy with them.
+    #[serde(default)]
+    pub challenger_params: Option<Value>,
+    /// What the allocator weighed to arrive at `weight`.
+    #[serde(default)]
+    pub explanation: Option<AllocationExplanation>,
 }
 
 impl StrategyAllocation {
     pub fn is_live(&self) -> bool {
         self.mode == TradeMode::Live
     }
+
+    pub fn strategy_family(&self) -> &str {
+        if self.family.is_empty() {
+            &self.id
+        } else {
+            &self.family
+        }
+    }
+
+    /// `capital_usd`, or `weight` of `default_capital_usd` if the allocator
+    /// didn't size it.
+    pub fn capital_budget_usd(&self, default_capital_usd: f64) -> f64 {
+        self.capital_usd.unwrap_or(self.weight * default_capital_usd)
+    }
+}
+
+/// The inputs behind one strategy's allocation. The allocator applies no
+/// market-regime or correlation adjustment yet, so `regime_multiplier` is
+/// always 1 and `correlation_penalty` 0.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationExplanation {
+    pub sharpe_ratio: f64,
+    pub shrunk_sharpe_ratio: f64,
+    pub ranking_metric: String,
+    /// The ranking metric shrunk toward the prior; what the strategy was ranked on.
+    pub score: f64,
+    pub trade_count: u64,
+    /// Deepest fall of the strategy's cumulative net PnL, in USD.
+    pub max_drawdown_usd: f64,
+    /// Portfolio equity below its peak when the capital was sized.
+    pub portfolio_drawdown_pct: f64,
+    pub allocation_mode: String,
+    /// The weight the allocation mode gave before any cap or limit.
+    pub target_weight: f64,
+    /// Caps and limits that moved the weight off `target_weight`, e.g.
+    /// `family_cap:momentum_5m`, `min_weight_change`, `max_turnover`,
+    /// `min_dwell` or `capital_drawdown`.
+    pub caps_hit: Vec<String>,
+    pub regime_multiplier: f64,
+    pub correlation_penalty: f64,
+}
+
+impl Default for AllocationExplanation {
+    fn default() -> Self {
+        Self {
+            sharpe_ratio: 0.0,
+            shrunk_sharpe_ratio: 0.0,
+            ranking_metric: String::new(),
+            score: 0.0,
+            trade_count: 0,
+            max_drawdown_usd: 0.0,
+            portfolio_drawdown_pct: 0.0,
+            allocation_mode: String::new(),
+            target_weight: 0.0,
+            caps_hit: Vec::new(),
+            regime_multiplier: 1.0,
+            correlation_penalty: 0.0,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, Eq, PartialEq)]
@@ -48,6 +357,9 @@ pub enum EventType {
     OnChain,  // Placeholder for future expansion (e.g., LP locks, holder changes)
     SolPrice, // P-2: For real-time SOL/USD price
     DataSourceHeartbeat, // For monitoring data consumer health
+    LiquidityPool, // LP adds/removes/locks/unlocks
+    WhaleTransfer, // Large wallet transfers on tracked tokens
+    TokenLaunch,   // New mints on pump.fun / Raydium
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -56,6 +368,8 @@ pub struct PriceTick {
     pub token_address: String,
     pub price_usd: f64,
     pub volume_usd_1m: f64,
+    #[serde(default)]
+    pub venue: Option<Venue>, // Set by per-venue feeds; aggregated prices leave it unset
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -64,6 +378,14 @@ pub struct SocialMention {
     pub token_address: String,
     pub source: String, // "twitter", "telegram", etc.
     pub sentiment: f64, // -1.0 to 1.0
+    #[serde(default)]
+    pub author: Option<String>, // Account handle, where the source exposes one
+    #[serde(default)]
+    pub author_influence: Option<f64>, // 0.0 to 1.0, scored by the social consumer
+    #[serde(default)]
+    pub post_id: Option<String>, // Tweet/message id, to trace a signal back to its post
+    #[serde(default)]
+    pub urgency: Option<f64>, // 0.0 to 1.0, how strongly the post pushes to act now
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -91,6 +413,12 @@ pub struct FundingEvent {
     pub token_address: String,
     pub funding_rate_pct: f64, // e.g., 0.01 for 1%
     pub next_funding_time_sec: u64,
+    #[serde(default)]
+    pub mark_price_usd: Option<f64>, // Perp mark, for basis against spot
+    #[serde(default)]
+    pub oracle_price_usd: Option<f64>, // Index the perp is funded against
+    #[serde(default)]
+    pub open_interest_usd: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -108,6 +436,58 @@ pub struct OnChainEvent {
     pub data: Value,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityAction {
+    Add,
+    Remove,
+    Lock,
+    Unlock,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LiquidityPoolEvent {
+    pub timestamp: i64,
+    pub token_address: String,
+    pub pool_address: String,
+    pub action: LiquidityAction,
+    pub amount_usd: f64,               // USD value of the liquidity moved/locked
+    pub lp_amount: f64,                // Raw LP token amount
+    pub pool_liquidity_usd: f64,       // Total pool liquidity after the action
+    pub unlock_timestamp: Option<i64>, // Set for Lock events with a known expiry
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    Inflow,  // Wallet received the token (buy or transfer in)
+    Outflow, // Wallet sent the token away (sell or transfer out)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WhaleTransfer {
+    pub timestamp: i64,
+    pub token_address: String,
+    pub wallet: String,
+    pub direction: TransferDirection,
+    pub token_amount: f64,
+    pub usd_value: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Launchpad {
+    PumpFun,
+    Raydium,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenLaunch {
+    pub timestamp: i64,
+    pub token_address: String, // Mint address of the new token
+    pub creator_wallet: String,
+    pub initial_liquidity_usd: f64,
+    pub lp_locked: bool,
+    pub launchpad: Launchpad,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DataSourceHeartbeat {
     pub source_name: String,
@@ -115,6 +495,129 @@ pub struct DataSourceHeartbeat {
     pub timestamp: i64,
 }
 
+/// Stream every long-running service appends a `ServiceHeartbeat` to.
+pub const SERVICE_HEARTBEATS_STREAM: &str = "service_heartbeats";
+
+/// A service's liveness beat, published by `spawn_heartbeat!` every
+/// `interval_secs`; a service is considered down once several intervals pass
+/// without one.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServiceHeartbeat {
+    pub service: String,
+    pub started_at: i64, // Changes when the service restarts
+    pub interval_secs: u64,
+    pub timestamp: i64,
+}
+
+/// Stream entries a consumer could not parse are parked here by
+/// `dead_letter!` instead of being dropped, so they can be replayed onto their
+/// source stream once the schema issue is fixed. Kept outside `events:*` so
+/// the recorder neither archives nor trims it.
+pub const DEAD_LETTER_STREAM: &str = "dead_letters";
+
+/// Set of strategy IDs an operator has paused; the executor skips their signals.
+pub const PAUSED_STRATEGIES_KEY: &str = "paused_strategies";
+/// Tells the executor to re-read `paused_strategies` (`PAUSE <id>` / `RESUME <id>`).
+pub const STRATEGY_CONTROL_CHANNEL: &str = "strategy_control_channel";
+
+/// Key holding the portfolio-wide `TradingStateRecord` as JSON; the source of
+/// truth for whether anything may trade, kept across restarts.
+pub const TRADING_STATE_KEY: &str = "trading_state";
+/// Every change to `trading_state` is published here as the new record.
+pub const KILL_SWITCH_CHANNEL: &str = "kill_switch_channel";
+/// Hash of portfolio balances. wallet_guard writes `wallet_sol`, the executor
+/// `live_open_positions_usd` and `realized_pnl_usd`, each with an
+/// `*_updated_at` unix time; the allocator sizes capital from them and writes
+/// back `total_value_usd` (its equity estimate) and `equity_drawdown_pct`.
+pub const PORTFOLIO_METRICS_KEY: &str = "portfolio_metrics";
+
+/// Whether the portfolio may trade. A service may pause it (a VaR breach, a
+/// low wallet) and later resume a pause it set itself; anything else, halts
+/// included, is up to an operator.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradingState {
+    Active,
+    Paused { reason: String },
+    Halted { reason: String },
+}
+
+impl TradingState {
+    pub fn paused(reason: &str) -> Self {
+        TradingState::Paused {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self, TradingState::Active)
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            TradingState::Active => None,
+            TradingState::Paused { reason } | TradingState::Halted { reason } => Some(reason),
+        }
+    }
+}
+
+impl std::fmt::Display for TradingState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TradingState::Active => f.write_str("ACTIVE"),
+            TradingState::Paused { reason } => write!(f, "PAUSED ({})", reason),
+            TradingState::Halted { reason } => write!(f, "HALTED ({})", reason),
+        }
+    }
+}
+
+/// What `trading_state` holds and `kill_switch_channel` carries.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TradingStateRecord {
+    #[serde(flatten)]
+    pub state: TradingState,
+    pub set_by: String, // Service, or `operator`
+    pub timestamp: i64,
+}
+
+impl TradingStateRecord {
+    /// The record held in `trading_state`, Active if it was never set. One
+    /// that can't be read pauses, since nothing should trade on a guess.
+    pub fn from_stored(raw: Option<&str>) -> Self {
+        let unset = |state| TradingStateRecord {
+            state,
+            set_by: String::new(),
+            timestamp: 0,
+        };
+        match raw {
+            None => unset(TradingState::Active),
+            Some(raw) => serde_json::from_str(raw)
+                .unwrap_or_else(|_| unset(TradingState::paused("unreadable trading_state"))),
+        }
+    }
+
+    /// Whether `service` may replace this record with `next` without an
+    /// operator: it may pause an active portfolio, and resume its own pause.
+    pub fn allows(&self, service: &str, next: &TradingState) -> bool {
+        match (&self.state, next) {
+            (TradingState::Active, TradingState::Paused { .. }) => true,
+            (TradingState::Paused { .. }, TradingState::Active) => self.set_by == service,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeadLetter {
+    pub service: String, // Consumer that failed to parse it
+    pub source_stream: String,
+    pub source_id: String,
+    pub field: String, // Field of the source entry the payload was read from
+    pub payload: String,
+    pub error: String,
+    pub timestamp: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum MarketEvent {
@@ -126,6 +629,9 @@ pub enum MarketEvent {
     SolPrice(SolPriceEvent), // P-2: New event variant
     OnChain(OnChainEvent),
     DataSourceHeartbeat(DataSourceHeartbeat),
+    LiquidityPool(LiquidityPoolEvent),
+    WhaleTransfer(WhaleTransfer),
+    TokenLaunch(TokenLaunch),
 }
 
 impl MarketEvent {
@@ -139,6 +645,9 @@ impl MarketEvent {
             MarketEvent::SolPrice(_) => EventType::SolPrice, // P-2
             MarketEvent::OnChain(_) => EventType::OnChain,
             MarketEvent::DataSourceHeartbeat(_) => EventType::DataSourceHeartbeat,
+            MarketEvent::LiquidityPool(_) => EventType::LiquidityPool,
+            MarketEvent::WhaleTransfer(_) => EventType::WhaleTransfer,
+            MarketEvent::TokenLaunch(_) => EventType::TokenLaunch,
         }
     }
     // Helper to get token address from any MarketEvent
@@ -152,6 +661,9 @@ impl MarketEvent {
             MarketEvent::OnChain(e) => &e.token_address,
             MarketEvent::SolPrice(_) => "So11111111111111111111111111111111111111112", // SOL mint address
             MarketEvent::DataSourceHeartbeat(_) => "N/A",
+            MarketEvent::LiquidityPool(e) => &e.token_address,
+            MarketEvent::WhaleTransfer(e) => &e.token_address,
+            MarketEvent::TokenLaunch(e) => &e.token_address,
         }
     }
 
@@ -165,8 +677,61 @@ impl MarketEvent {
             MarketEvent::SolPrice(e) => e.timestamp,
             MarketEvent::OnChain(e) => e.timestamp,
             MarketEvent::DataSourceHeartbeat(e) => e.timestamp,
+            MarketEvent::LiquidityPool(e) => e.timestamp,
+            MarketEvent::WhaleTransfer(e) => e.timestamp,
+            MarketEvent::TokenLaunch(e) => e.timestamp,
         }
     }
+
+    /// Moves the event to `timestamp`, e.g. onto the wall clock when replaying.
+    pub fn set_timestamp(&mut self, timestamp: i64) {
+        match self {
+            MarketEvent::Price(e) => e.timestamp = timestamp,
+            MarketEvent::Social(e) => e.timestamp = timestamp,
+            MarketEvent::Depth(e) => e.timestamp = timestamp,
+            MarketEvent::Bridge(e) => e.timestamp = timestamp,
+            MarketEvent::Funding(e) => e.timestamp = timestamp,
+            MarketEvent::SolPrice(e) => e.timestamp = timestamp,
+            MarketEvent::OnChain(e) => e.timestamp = timestamp,
+            MarketEvent::DataSourceHeartbeat(e) => e.timestamp = timestamp,
+            MarketEvent::LiquidityPool(e) => e.timestamp = timestamp,
+            MarketEvent::WhaleTransfer(e) => e.timestamp = timestamp,
+            MarketEvent::TokenLaunch(e) => e.timestamp = timestamp,
+        }
+    }
+}
+
+/// How the executor should work an order once a strategy emits it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(tag = "type")]
+pub enum OrderType {
+    /// Fill immediately at the quoted price.
+    #[default]
+    Market,
+    /// Fill only once the quote is at or better than `price`; cancelled after `ttl_secs`.
+    Limit { price: f64, ttl_secs: u64 },
+    /// Split the order into `slices` equal market fills spread over `duration_secs`.
+    Twap { duration_secs: u64, slices: u32 },
+    /// Wait for price to cross `trigger_price` in the direction of the trade, then fill at market.
+    StopEntry { trigger_price: f64 },
+}
+
+/// Where a spot order is executed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Venue {
+    Jupiter,
+    Raydium,
+    Orca,    // Whirlpools
+    PumpFun, // Bonding curve, for tokens that haven't migrated yet
+}
+
+/// Per-trade exit rules the position manager applies on top of (or instead
+/// of) the global trailing stop. Unset fields fall back to the defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ExitProfile {
+    pub take_profit_pct: Option<f64>,   // Close once the position is up this much from entry
+    pub trailing_stop_pct: Option<f64>, // Replaces TRAILING_STOP_LOSS_PERCENT for this trade
+    pub max_hold_secs: Option<i64>,     // Close at market after this long regardless of PnL
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -177,6 +742,15 @@ pub struct OrderDetails {
     pub side: Side,
     pub limit_price: Option<f64>,
     pub triggering_features: Option<Value>,
+    #[serde(default)]
+    pub order_type: OrderType,
+    /// Pin the order to a venue. `None` lets the executor route to the best quote.
+    #[serde(default)]
+    pub venue: Option<Venue>,
+    /// How the resulting position should be exited. `None` leaves it to the
+    /// position manager's global trailing stop.
+    #[serde(default)]
+    pub exit_profile: Option<ExitProfile>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -185,9 +759,34 @@ pub enum StrategyAction {
     /// Execute with explicit trade‑mode so the executor doesn't have to
     /// do a lookup.
     Execute(OrderDetails, TradeMode),
+    /// Several orders from one decision, e.g. both legs of a basis trade.
+    ExecuteMany(Vec<OrderDetails>, LegPolicy, TradeMode),
+    /// Close all of the strategy's open positions in this token at market.
+    ClosePositions(String),
     Hold,
 }
 
+impl StrategyAction {
+    /// The orders this action asks for; empty for `Hold` and closes.
+    pub fn orders(&self) -> Vec<&OrderDetails> {
+        match self {
+            StrategyAction::Execute(details, _) => vec![details],
+            StrategyAction::ExecuteMany(legs, _, _) => legs.iter().collect(),
+            StrategyAction::ClosePositions(_) | StrategyAction::Hold => Vec::new(),
+        }
+    }
+}
+
+/// How the executor works the legs of an `ExecuteMany`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegPolicy {
+    /// Each leg is worked like a separate Execute; some may fill while others fail.
+    Independent,
+    /// Legs fill in order at market; if one fails, the legs already filled
+    /// are sent to the position manager to be closed again.
+    AllOrNothing,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum Side {
     Long,
@@ -203,6 +802,169 @@ impl std::fmt::Display for Side {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    Filled,
+    PartiallyFilled,
+    Failed,
+    DryRun,      // DRY_RUN: built and logged, never signed
+    Unconfirmed, // Submitted, but neither a fill nor an expiry could be confirmed
+}
+
+/// What actually happened to an order. Published by the executor to the
+/// `execution_reports` stream once per fill attempt.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExecutionReport {
+    pub timestamp: i64,
+    pub trade_id: Option<i64>, // None if the attempt failed before it was logged
+    pub strategy_id: String,
+    pub token_address: String,
+    pub side: Side,
+    pub mode: TradeMode,
+    pub status: ExecutionStatus,
+    pub requested_size_usd: f64,
+    pub filled_size_usd: f64,
+    pub fill_price_usd: f64,
+    pub fees_usd: f64,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ExecutionReport {
+    pub fn is_filled(&self) -> bool {
+        matches!(
+            self.status,
+            ExecutionStatus::Filled | ExecutionStatus::PartiallyFilled
+        )
+    }
+}
+
+/// Lifecycle of a trade row. Every status change goes through
+/// `TradeState::transition` and is persisted with a timestamp, so a trade can
+/// only move along the edges below:
+///
+/// CREATED → RISK_APPROVED → SUBMITTED → CONFIRMED → OPEN → CLOSING → CLOSED
+///
+/// Paper trades go RISK_APPROVED → OPEN. A submission whose fill can't be
+/// confirmed stays SUBMITTED until it is reconciled against the wallet. A
+/// failed close returns CLOSING → OPEN. Anything before OPEN can end in FAILED
+/// or CANCELED.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeState {
+    Created,
+    RiskApproved,
+    Submitted,
+    Confirmed,
+    Open,
+    Closing,
+    Closed,
+    Failed,
+    Canceled,
+}
+
+impl TradeState {
+    /// The value stored in `trades.status`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeState::Created => "CREATED",
+            TradeState::RiskApproved => "RISK_APPROVED",
+            TradeState::Submitted => "SUBMITTED",
+            TradeState::Confirmed => "CONFIRMED",
+            TradeState::Open => "OPEN",
+            TradeState::Closing => "CLOSING",
+            TradeState::Closed => "CLOSED",
+            TradeState::Failed => "FAILED",
+            TradeState::Canceled => "CANCELED",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TradeState::Closed | TradeState::Failed | TradeState::Canceled
+        )
+    }
+
+    /// True while capital may be committed on-chain without the position being OPEN yet.
+    pub fn is_in_flight(&self) -> bool {
+        matches!(self, TradeState::Submitted | TradeState::Confirmed)
+    }
+
+    pub fn can_transition_to(&self, next: TradeState) -> bool {
+        use TradeState::*;
+        matches!(
+            (self, next),
+            (Created, RiskApproved | Failed | Canceled)
+                | (RiskApproved, Submitted | Open | Failed | Canceled)
+                | (Submitted, Confirmed | Failed)
+                | (Confirmed, Open | Failed)
+                | (Open, Closing)
+                | (Closing, Closed | Open)
+        )
+    }
+
+    pub fn transition(&self, next: TradeState) -> Result<TradeState, InvalidTransition> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(InvalidTransition {
+                from: *self,
+                to: next,
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for TradeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TradeState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "CREATED" | "PENDING" => Ok(TradeState::Created), // PENDING predates the state machine
+            "RISK_APPROVED" => Ok(TradeState::RiskApproved),
+            "SUBMITTED" => Ok(TradeState::Submitted),
+            "CONFIRMED" => Ok(TradeState::Confirmed),
+            "OPEN" => Ok(TradeState::Open),
+            "CLOSING" => Ok(TradeState::Closing),
+            "CLOSED" | "CLOSED_PROFIT" | "CLOSED_LOSS" => Ok(TradeState::Closed),
+            "FAILED" => Ok(TradeState::Failed),
+            "CANCELED" => Ok(TradeState::Canceled),
+            other => Err(format!("unknown trade state '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: TradeState,
+    pub to: TradeState,
+}
+
+impl std::fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid trade transition {} -> {}", self.from, self.to)
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+/// Asks the position manager to close an open trade at market. Published to
+/// the `position_close_requests` stream, e.g. when a strategy shuts down.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionCloseRequest {
+    pub timestamp: i64,
+    pub trade_id: i64,
+    pub strategy_id: String,
+    pub reason: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SignRequest {
     pub transaction_b64: String,
@@ -225,3 +987,183 @@ macro_rules! alert {
             .await;
     }};
 }
+
+/// Spawns a task appending a `ServiceHeartbeat` for the named service to
+/// `service_heartbeats` every SERVICE_HEARTBEAT_INTERVAL_SECS (default 15),
+/// reconnecting through the given `redis::Client` whenever a write fails.
+#[macro_export]
+macro_rules! spawn_heartbeat {
+    ($client:expr, $service:expr) => {{
+        let client: redis::Client = $client;
+        let service: String = $service.to_string();
+        let interval_secs: u64 = std::env::var("SERVICE_HEARTBEAT_INTERVAL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or(15);
+        tokio::spawn(async move {
+            let started_at = chrono::Utc::now().timestamp();
+            let mut conn: Option<redis::aio::MultiplexedConnection> = None;
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                if conn.is_none() {
+                    conn = client.get_multiplexed_async_connection().await.ok();
+                }
+                let Some(c) = conn.as_mut() else {
+                    tracing::warn!(service = %service, "No Redis connection for heartbeat.");
+                    continue;
+                };
+                let heartbeat = $crate::ServiceHeartbeat {
+                    service: service.clone(),
+                    started_at,
+                    interval_secs,
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                let result = redis::cmd("XADD")
+                    .arg($crate::SERVICE_HEARTBEATS_STREAM)
+                    .arg("MAXLEN")
+                    .arg("~")
+                    .arg(10_000)
+                    .arg("*")
+                    .arg("heartbeat")
+                    .arg(serde_json::to_string(&heartbeat).unwrap_or_default())
+                    .query_async::<_, String>(c)
+                    .await;
+                if let Err(e) = result {
+                    tracing::warn!(service = %service, error = %e, "Failed to publish heartbeat.");
+                    conn = None;
+                }
+            }
+        })
+    }};
+}
+
+/// Parks an unparseable stream entry on `dead_letters` as a
+/// `DeadLetter`: `dead_letter!(conn, service, stream, id, field, payload, error)`.
+/// Failing to park it is only logged; the caller moves past the entry either way.
+#[macro_export]
+macro_rules! dead_letter {
+    ($conn:expr, $service:expr, $stream:expr, $id:expr, $field:expr, $payload:expr, $error:expr) => {{
+        let letter = $crate::DeadLetter {
+            service: $service.to_string(),
+            source_stream: $stream.to_string(),
+            source_id: $id.to_string(),
+            field: $field.to_string(),
+            payload: $payload.to_string(),
+            error: $error.to_string(),
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        tracing::warn!(stream = %letter.source_stream, id = %letter.source_id, error = %letter.error, "☠️ Dead-lettering unparseable entry.");
+        let result = redis::cmd("XADD")
+            .arg($crate::DEAD_LETTER_STREAM)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(100_000)
+            .arg("*")
+            .arg("letter")
+            .arg(serde_json::to_string(&letter).unwrap_or_default())
+            .query_async::<_, String>(&mut $conn)
+            .await;
+        if let Err(e) = result {
+            tracing::error!(stream = %letter.source_stream, id = %letter.source_id, error = %e, "Failed to dead-letter entry.");
+        }
+    }};
+}
+
+/// Moves the portfolio to a new `TradingState` on a service's behalf:
+/// `set_trading_state!(conn, service, state)` stores the record in
+/// `trading_state` and publishes it on `kill_switch_channel`. Evaluates to
+/// `Ok(true)` if the state changed, `Ok(false)` if it already held or the move
+/// needs an operator (see `TradingStateRecord::allows`).
+#[macro_export]
+macro_rules! set_trading_state {
+    ($conn:expr, $set_by:expr, $state:expr) => {{
+        use ::std::result::Result::{Err, Ok}; // Callers may glob-import anyhow's `Ok`
+        let set_by: String = $set_by.to_string();
+        let next: $crate::TradingState = $state;
+        let current = redis::cmd("GET")
+            .arg($crate::TRADING_STATE_KEY)
+            .query_async::<_, Option<String>>(&mut $conn)
+            .await
+            .map(|raw| $crate::TradingStateRecord::from_stored(raw.as_deref()));
+        match current {
+            Err(e) => Err(e),
+            Ok(current) if current.state == next => Ok(false),
+            Ok(current) if !current.allows(&set_by, &next) => {
+                tracing::debug!(current = %current.state, set_by = %current.set_by, requested = %next, "Trading state change left to an operator.");
+                Ok(false)
+            }
+            Ok(current) => {
+                let record = $crate::TradingStateRecord {
+                    state: next,
+                    set_by,
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                let payload = serde_json::to_string(&record).unwrap_or_default();
+                let result = redis::pipe()
+                    .cmd("SET")
+                    .arg($crate::TRADING_STATE_KEY)
+                    .arg(&payload)
+                    .ignore()
+                    .cmd("PUBLISH")
+                    .arg($crate::KILL_SWITCH_CHANNEL)
+                    .arg(&payload)
+                    .ignore()
+                    .query_async::<_, ()>(&mut $conn)
+                    .await;
+                if result.is_ok() {
+                    tracing::warn!(from = %current.state, to = %record.state, set_by = %record.set_by, "🚦 Trading state changed.");
+                }
+                result.map(|_| true)
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(state: TradingState, set_by: &str) -> TradingStateRecord {
+        TradingStateRecord {
+            state,
+            set_by: set_by.to_string(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn stored_trading_state() {
+        let unset = TradingStateRecord::from_stored(None);
+        assert_eq!(unset.state, TradingState::Active);
+
+        let paused = record(TradingState::paused("VaR breach"), "risk_guardian");
+        let raw = serde_json::to_string(&paused).unwrap();
+        let stored = TradingStateRecord::from_stored(Some(&raw));
+        assert_eq!(stored.state, paused.state);
+        assert_eq!(stored.set_by, "risk_guardian");
+
+        // Unreadable records fail safe
+        let unreadable = TradingStateRecord::from_stored(Some("PAUSE"));
+        assert!(!unreadable.state.is_active());
+    }
+
+    #[test]
+    fn services_resume_only_their_own_pause() {
+        let active = record(TradingState::Active, "operator");
+        assert!(active.allows("wallet_guard", &TradingState::paused("low balance")));
+
+        let paused = record(TradingState::paused("low balance"), "wallet_guard");
+        assert!(paused.allows("wallet_guard", &TradingState::Active));
+        assert!(!paused.allows("risk_guardian", &TradingState::Active));
+
+        let halted = record(
+            TradingState::Halted {
+                reason: "manual".to_string(),
+            },
+            "operator",
+        );
+        assert!(!halted.allows("risk_guardian", &TradingState::Active));
+    }
+}
@@ -7,40 +7,78 @@ use axum::{
     Json, Router,
 };
 use base64::Engine;
-use shared_models::{SignRequest, SignResponse};
+use lazy_static::lazy_static;
+use prometheus::{register_counter, register_counter_vec, Counter, CounterVec, Encoder, TextEncoder};
+use shared_config::Loader;
+use shared_models::{spawn_heartbeat, SignRequest, SignResponse};
 use solana_sdk::{
     hash::Hash,
     message::VersionedMessage,
     signature::{Keypair, Signer},
     transaction::VersionedTransaction,
 };
-use std::{env, fs, net::SocketAddr, sync::Arc};
-use tracing::{error, info, instrument, level_filters::LevelFilter};
-use tracing_subscriber::EnvFilter;
+use std::{fs, net::SocketAddr, sync::Arc};
+use tracing::{error, info, instrument, warn};
+use zeroize::Zeroize;
+
+lazy_static! {
+    static ref SIGN_REQUESTS_TOTAL: Counter = register_counter!(
+        "signer_sign_requests_total",
+        "Transactions submitted to /sign."
+    )
+    .unwrap();
+    static ref SIGNED_TOTAL: Counter = register_counter!(
+        "signer_signed_total",
+        "Transactions signed and returned."
+    )
+    .unwrap();
+    static ref SIGN_REJECTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "signer_sign_rejections_total",
+        "Sign requests refused, by reason.",
+        &["reason"]
+    )
+    .unwrap();
+}
 
 struct AppState {
     keypair: Keypair,
+    paper_trading_mode: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::INFO.into())
-        .from_env_lossy();
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let log_level = shared_config::log_level::init();
 
     info!("🔒 Starting Signer Service...");
 
-    let wallet_filename =
-        env::var("WALLET_KEYPAIR_FILENAME").expect("WALLET_KEYPAIR_FILENAME must be set");
-    let wallet_path = format!("/app/{}", wallet_filename);
-
-    // Read the JSON array format wallet file
-    let wallet_data = fs::read_to_string(&wallet_path)
-        .map_err(|e| anyhow!("Failed to read wallet file at {}: {}", wallet_path, e))?;
+    let mut config = Loader::new("signer");
+    // The keypair itself, normally a vault:// or aws-sm:// reference, else the file
+    let wallet_keypair: Option<String> = config.optional("WALLET_KEYPAIR");
+    let wallet_filename: Option<String> = config.optional("WALLET_KEYPAIR_FILENAME");
+    let redis_url = config.get("REDIS_URL", "redis://redis:6379".to_string());
+    // Outside mainnet-live nothing is signed, whatever PAPER_TRADING_MODE says
+    let profile = config.profile();
+    let paper_trading_mode = config.flag("PAPER_TRADING_MODE", false) || !profile.allows_live();
+    config.check(
+        wallet_keypair.is_some() || wallet_filename.is_some(),
+        "WALLET_KEYPAIR or WALLET_KEYPAIR_FILENAME must be set",
+    );
+    config.finish();
+
+    // Either way the keypair is a JSON array of its 64 bytes
+    let mut wallet_data = match (wallet_keypair, wallet_filename) {
+        (Some(keypair), _) => keypair,
+        (None, Some(filename)) => {
+            let wallet_path = format!("/app/{}", filename);
+            fs::read_to_string(&wallet_path)
+                .map_err(|e| anyhow!("Failed to read wallet file at {}: {}", wallet_path, e))?
+        }
+        (None, None) => unreachable!("checked above"),
+    };
 
-    let byte_array: Vec<u8> = serde_json::from_str(&wallet_data)
+    let mut byte_array: Vec<u8> = serde_json::from_str(&wallet_data)
         .map_err(|e| anyhow!("Failed to parse wallet JSON: {}", e))?;
+    wallet_data.zeroize();
 
     if byte_array.len() != 64 {
         return Err(anyhow!(
@@ -51,11 +89,32 @@ async fn main() -> Result<()> {
 
     let keypair = Keypair::from_bytes(&byte_array)
         .map_err(|e| anyhow!("Failed to create keypair from bytes: {}", e))?;
+    byte_array.zeroize(); // The keypair holds the only copy from here on
 
     let pubkey = keypair.pubkey();
     info!(%pubkey, "Wallet loaded successfully. This service is now ready to sign transactions.");
+    if paper_trading_mode {
+        warn!(profile = %profile, "🚫 Paper trading: every signing request will be rejected.");
+    }
 
-    let state = Arc::new(AppState { keypair });
+    spawn_heartbeat!(redis::Client::open(redis_url.as_str())?, "signer");
+
+    let metrics = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let metrics_addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+    let metrics_listener = tokio::net::TcpListener::bind(metrics_addr).await?;
+    info!("📊 Prometheus metrics server listening on http://{}/metrics", metrics_addr);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(metrics_listener, metrics).await {
+            error!(error = %e, "Metrics server stopped.");
+        }
+    });
+
+    let state = Arc::new(AppState {
+        keypair,
+        paper_trading_mode,
+    });
 
     let app = Router::new()
         .route("/pubkey", get(get_pubkey))
@@ -70,6 +129,14 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
 #[instrument(skip(state), name = "get_pubkey_handler")]
 async fn get_pubkey(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     Json(serde_json::json!({ "pubkey": state.keypair.pubkey().to_string() }))
@@ -80,8 +147,11 @@ async fn sign_transaction(
     State(state): State<Arc<AppState>>,
     Json(request): Json<SignRequest>,
 ) -> Result<Json<SignResponse>, StatusCode> {
+    SIGN_REQUESTS_TOTAL.inc();
+
     // Check if paper trading mode is enabled - reject live orders
-    if std::env::var("PAPER_TRADING_MODE") == Ok("true".to_string()) {
+    if state.paper_trading_mode {
+        SIGN_REJECTIONS_TOTAL.with_label_values(&["paper_mode"]).inc();
         error!("🚫 PAPER TRADING MODE: Rejecting live transaction signing request");
         return Err(StatusCode::FORBIDDEN);
     }
@@ -90,6 +160,7 @@ async fn sign_transaction(
     {
         Ok(bytes) => bytes,
         Err(e) => {
+            SIGN_REJECTIONS_TOTAL.with_label_values(&["bad_encoding"]).inc();
             error!(error = %e, "Failed to decode base64 transaction");
             return Err(StatusCode::BAD_REQUEST);
         }
@@ -98,6 +169,7 @@ async fn sign_transaction(
     let mut tx: VersionedTransaction = match bincode::deserialize(&tx_bytes) {
         Ok(tx) => tx,
         Err(e) => {
+            SIGN_REJECTIONS_TOTAL.with_label_values(&["bad_transaction"]).inc();
             error!(error = %e, "Failed to deserialize transaction");
             return Err(StatusCode::BAD_REQUEST);
         }
@@ -122,11 +194,13 @@ async fn sign_transaction(
     let signed_tx_bytes = match bincode::serialize(&tx) {
         Ok(bytes) => bytes,
         Err(e) => {
+            SIGN_REJECTIONS_TOTAL.with_label_values(&["serialize_failed"]).inc();
             error!(error = %e, "Failed to serialize signed transaction");
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
 
+    SIGNED_TOTAL.inc();
     info!("Transaction signed successfully.");
     Ok(Json(SignResponse {
         signed_transaction_b64: base64::engine::general_purpose::STANDARD.encode(&signed_tx_bytes),
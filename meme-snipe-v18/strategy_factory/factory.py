@@ -12,10 +12,28 @@ STRATEGY_FAMILIES = [
     "liquidity_migration",
     "perp_basis_arb", 
     "dev_wallet_drain", 
-    "airdrop_rotation",
+    "holder_growth",
     "korean_time_burst", 
     "bridge_inflow", 
-    "rug_pull_sniffer"
+    "lp_unlock_short",
+    "ensemble",
+    "whale_tracker",
+    "launch_sniper",
+    "obi_scalper",
+    "funding_carry",
+    "xdex_arb",
+    "copy_trader",
+    "sentiment_momentum",
+    "volume_breakout",
+    "graduation_sniper",
+    "meme_pairs",
+    "vol_breakout",
+    "session_burst",
+    "meme_mm",
+    "liq_sniper",
+    "dip_buyer",
+    "basket_momentum",
+    "influencer_signal"
     # TODO: Add any additional strategies found in executor/src/strategies/
     # Exclude: template.rs.example
 ]
@@ -25,7 +43,7 @@ def get_default_params(family):
     if family == "momentum_5m":
         return {"lookback": 5, "vol_multiplier": 2.0, "price_change_threshold": 0.05}
     elif family == "mean_revert_1h":
-        return {"period_hours": 1, "z_score_threshold": 2.0}
+        return {"period_hours": 24, "z_score_threshold": 2.0}
     elif family == "social_buzz":
         return {"lookback_minutes": 10, "std_dev_threshold": 2.5}
     elif family == "liquidity_migration":
@@ -34,14 +52,231 @@ def get_default_params(family):
         return {"basis_threshold_pct": 0.5}
     elif family == "dev_wallet_drain":
         return {"dev_balance_threshold_pct": 2.0}
-    elif family == "airdrop_rotation":
-        return {"min_new_holders": 100}
+    elif family == "holder_growth":
+        # Three straight rises in holder growth, to at least 5/min, on 2x volume
+        return {
+            "min_holder_growth_per_min": 5.0,
+            "accel_periods": 3,
+            "volume_window": 10,
+            "volume_multiplier": 2.0,
+            "size_usd": 300.0,
+        }
     elif family == "korean_time_burst":
         return {"volume_multiplier_threshold": 1.5}
     elif family == "bridge_inflow":
         return {"min_bridge_volume_usd": 100000.0}
-    elif family == "rug_pull_sniffer":
-        return {"price_drop_pct": 0.8, "volume_multiplier": 5.0} # Example params for a simulated sniffer
+    elif family == "lp_unlock_short":
+        # Act on locks expiring within an hour or a single pull of 30%+ of the pool
+        return {
+            "unlock_warning_secs": 3600,
+            "min_locked_usd": 20000.0,
+            "removal_pct": 30.0,
+            "min_removal_usd": 10000.0,
+            "size_usd": 200.0,
+        }
+    elif family == "ensemble":
+        # Trade only when 2 of 3 children agree on direction within 5 minutes
+        return {
+            "children": [
+                {"family": f, "params": get_default_params(f)}
+                for f in ["momentum_5m", "mean_revert_1h", "social_buzz"]
+            ],
+            "quorum": 2,
+            "window_secs": 300,
+        }
+    elif family == "whale_tracker":
+        # Mirror 10% of tracked wallets' buys over $5k; score each buy after an hour
+        wallets = [w for w in os.getenv("WHALE_TRACKER_WALLETS", "").split(",") if w]
+        return {
+            "wallets": wallets,
+            "min_buy_usd": 5000.0,
+            "size_ratio": 0.1,
+            "max_size_usd": 250.0,
+            "prior_hit_rate": 0.5,
+            "min_hit_rate": 0.4,
+            "hit_horizon_secs": 3600,
+        }
+    elif family == "launch_sniper":
+        # Small, fast in-and-out: take 30% or get out within 15 minutes
+        return {
+            "max_launch_age_secs": 10,
+            "min_initial_liquidity_usd": 10000.0,
+            "require_lp_lock": True,
+            "max_creator_launches": 3,
+            "creator_window_secs": 86400,
+            "size_pct_of_liquidity": 0.5,
+            "max_size_usd": 50.0,
+            "take_profit_pct": 30.0,
+            "trailing_stop_pct": 10.0,
+            "max_hold_secs": 900,
+        }
+    elif family == "obi_scalper":
+        # Enter on a 60/40 book (imbalance 0.2) unless the spread is 2 std devs wide
+        return {
+            "window": 30,
+            "imbalance_threshold": 0.2,
+            "max_spread_z": 2.0,
+            "size_usd": 100.0,
+            "take_profit_pct": 2.0,
+            "max_hold_secs": 300,
+        }
+    elif family == "funding_carry":
+        # Drift funds hourly; enter above 50% APR, unwind below 10%
+        return {
+            "entry_apr": 0.5,
+            "exit_apr": 0.1,
+            "periods_per_year": 8760,
+            "size_usd": 200.0,
+        }
+    elif family == "xdex_arb":
+        return {
+            "venues": ["Raydium", "Orca"],
+            "fee_bps": 60.0,
+            "threshold_bps": 40.0,
+            "exit_spread_bps": 15.0,
+            "max_quote_age_secs": 10,
+            "size_usd": 250.0,
+            "max_hold_secs": 600,
+        }
+    elif family == "copy_trader":
+        # Copy 20% of leaders' buys over $1k if seen within 15s; exits follow the leader
+        leaders = [w for w in os.getenv("COPY_TRADER_LEADERS", "").split(",") if w]
+        return {
+            "leaders": leaders,
+            "min_leader_usd": 1000.0,
+            "size_ratio": 0.2,
+            "max_size_usd": 250.0,
+            "max_delay_secs": 15,
+        }
+    elif family == "sentiment_momentum":
+        return {
+            "half_life_secs": 900,
+            "source_weights": {"twitter": 1.0, "telegram": 0.6, "reddit": 0.4},
+            "default_source_weight": 0.3,
+            "min_mention_weight": 3.0,
+            "sentiment_threshold": 0.4,
+            "momentum_window": 5,
+            "momentum_threshold_pct": 2.0,
+            "size_usd": 200.0,
+        }
+    elif family == "volume_breakout":
+        # 4 hours of 5m candles in 24 price bins
+        return {
+            "candle_secs": 300,
+            "profile_candles": 48,
+            "bins": 24,
+            "node_multiplier": 1.5,
+            "volume_multiplier": 2.0,
+            "size_usd": 250.0,
+        }
+    elif family == "graduation_sniper":
+        # Buy curves 85-97% full and filling; sell into the migration spike
+        return {
+            "entry_progress_pct": 85.0,
+            "max_entry_progress_pct": 97.0,
+            "min_progress_per_min": 0.5,
+            "size_usd": 150.0,
+            "take_profit_pct": 40.0,
+            "trailing_stop_pct": 15.0,
+            "max_hold_secs": 7200,
+            "post_migration_hold_secs": 600,
+        }
+    elif family == "meme_pairs":
+        # Enter both legs at a 2-sigma log ratio, close back inside half a sigma
+        pairs = [p for p in os.getenv("MEME_PAIRS", "").split(",") if p]
+        return {
+            "pairs": pairs,
+            "window": 60,
+            "entry_z": 2.0,
+            "exit_z": 0.5,
+            "size_usd": 150.0,
+        }
+    elif family == "vol_breakout":
+        # 2-sigma bands over 20 5m closes
+        return {
+            "candle_secs": 300,
+            "window": 20,
+            "band_k": 2.0,
+            "min_vol_expansion": 1.1,
+            "min_volume_ratio": 1.0,
+            "size_usd": 250.0,
+        }
+    elif family == "session_burst":
+        # Seoul morning session on weekdays, the pattern korean_time_burst hard-codes
+        return {
+            "timezone": "Asia/Seoul",
+            "start_hour": 9,
+            "end_hour": 12,
+            "weekdays": ["Mon", "Tue", "Wed", "Thu", "Fri"],
+            "volume_window": 60,
+            "volume_multiplier": 3.0,
+            "min_volume_usd": 25000.0,
+            "size_usd": 300.0,
+        }
+    elif family == "meme_mm":
+        # 40bps-wide quotes refreshed every 30s; halt for 30min on a hard stop
+        tokens = [t for t in os.getenv("MEME_MM_TOKENS", "").split(",") if t]
+        return {
+            "tokens": tokens,
+            "quote_size_usd": 100.0,
+            "half_spread_bps": 20.0,
+            "skew_bps": 15.0,
+            "max_inventory_usd": 500.0,
+            "quote_ttl_secs": 30,
+            "markout_secs": 60,
+            "markout_window": 20,
+            "max_adverse_bps": 25.0,
+            "halt_secs": 1800,
+        }
+    elif family == "liq_sniper":
+        # Fade $250k+ of liquidations in 2min that moved price 5%; out within 10min
+        return {
+            "window_secs": 120,
+            "min_cascade_usd": 250000.0,
+            "min_move_pct": 5.0,
+            "size_usd": 200.0,
+            "take_profit_pct": 3.0,
+            "trailing_stop_pct": 2.0,
+            "max_hold_secs": 600,
+        }
+    elif family == "dip_buyer":
+        # Buy 25% off the 30min high once volume is under half its peak
+        return {
+            "window_mins": 30,
+            "min_drawdown_pct": 25.0,
+            "max_volume_ratio": 0.5,
+            "size_usd": 150.0,
+            "take_profit_pct": 12.0,
+            "trailing_stop_pct": 8.0,
+            "max_hold_secs": 3600,
+        }
+    elif family == "basket_momentum":
+        # Hold the 2 leaders of a basket up 5% over 1h with mentions up 50% on the hour
+        members = [m for m in os.getenv("BASKET_MOMENTUM_MEMBERS", "").split(",") if m]
+        return {
+            "members": members,
+            "lookback_secs": 3600,
+            "min_basket_return_pct": 5.0,
+            "social_window_secs": 3600,
+            "min_social_growth": 1.5,
+            "top_n": 2,
+            "size_usd": 150.0,
+            "rebalance_secs": 900,
+        }
+    elif family == "influencer_signal":
+        # Accounts of ~30k+ followers; calls count for 30min, buy before a 5% move
+        return {
+            "influence_scores": {},
+            "min_influence": 0.75,
+            "half_life_secs": 600,
+            "min_call_score": 1.5,
+            "max_price_move_pct": 5.0,
+            "max_call_age_secs": 1800,
+            "size_usd": 150.0,
+            "take_profit_pct": 25.0,
+            "trailing_stop_pct": 10.0,
+            "max_hold_secs": 7200,
+        }
     return {}
 
 def main():
@@ -1,32 +1,73 @@
 // wallet_guard/src/main.rs
 use anyhow::*;
-use axum::{routing::get, Router, Json};
+use axum::{http::StatusCode, routing::get, Router, Json};
+use lazy_static::lazy_static;
+use prometheus::{register_counter, register_gauge, Counter, Encoder, Gauge, TextEncoder};
 use solana_client::nonblocking::rpc_client::RpcClient;
+use shared_config::{Loader, Readiness};
+use shared_models::{set_trading_state, spawn_heartbeat, TradingState, PORTFOLIO_METRICS_KEY};
 use solana_sdk::pubkey::Pubkey;
-use std::{env, str::FromStr, time::Duration};
+use std::{str::FromStr, sync::Arc, time::Duration};
 use tracing::{info, warn, error};
 
+lazy_static! {
+    static ref BALANCE_SOL: Gauge = register_gauge!(
+        "wallet_guard_balance_sol",
+        "SOL balance of the trading wallet at the last check."
+    )
+    .unwrap();
+    static ref THRESHOLD_SOL: Gauge = register_gauge!(
+        "wallet_guard_low_balance_threshold_sol",
+        "Balance below which the wallet guard pauses trading."
+    )
+    .unwrap();
+    static ref LOW_BALANCE_TOTAL: Counter = register_counter!(
+        "wallet_guard_low_balance_total",
+        "Checks that found the wallet below the threshold and sent the kill switch."
+    )
+    .unwrap();
+    static ref CHECK_FAILURES_TOTAL: Counter = register_counter!(
+        "wallet_guard_check_failures_total",
+        "Balance checks that failed to reach the RPC."
+    )
+    .unwrap();
+}
+
 #[derive(Clone)]
 struct App {
     rpc: RpcClient,
     wallet_pubkey: Pubkey,
     threshold_lamports: u64,
     redis_url: String,
+    readiness: Arc<Readiness>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let log_level = shared_config::log_level::init();
     
-    let solana_rpc_url = env::var("SOLANA_RPC_URL")
-        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
-    let wallet_address = env::var("WALLET_ADDRESS")
-        .expect("WALLET_ADDRESS env var required");
-    let redis_url = env::var("REDIS_URL")
-        .unwrap_or_else(|_| "redis://redis:6379".to_string());
+    let mut config = Loader::new("wallet_guard");
+    let solana_rpc_url = config.get(
+        "SOLANA_RPC_URL",
+        "https://api.mainnet-beta.solana.com".to_string(),
+    );
+    let wallet_address: String = config.required("WALLET_ADDRESS");
+    let redis_url = config.get("REDIS_URL", "redis://redis:6379".to_string());
+    let wallet_pubkey = Pubkey::from_str(&wallet_address).unwrap_or_else(|e| {
+        config.invalid("WALLET_ADDRESS", e);
+        Pubkey::default()
+    });
+    config.finish();
+
+    let readiness = Arc::new(
+        Readiness::new("wallet_guard")
+            .redis(&redis_url)
+            .solana_rpc(&solana_rpc_url),
+    );
+    readiness.preflight().await.map_err(anyhow::Error::msg)?;
+    tokio::spawn(shared_config::readiness::monitor(readiness.clone()));
     
     let rpc = RpcClient::new(solana_rpc_url);
-    let wallet_pubkey = Pubkey::from_str(&wallet_address)?;
     let threshold_lamports = 20_000_000; // 0.02 SOL
     
     let app = App {
@@ -34,11 +75,26 @@ async fn main() -> Result<()> {
         wallet_pubkey,
         threshold_lamports,
         redis_url: redis_url.clone(),
+        readiness,
     };
     
     info!("🔒 Starting Wallet Guard on :7070...");
     info!("👛 Monitoring wallet: {}", wallet_address);
     info!("⚠️  Low balance threshold: {} SOL", threshold_lamports as f64 / 1e9);
+
+    spawn_heartbeat!(redis::Client::open(redis_url.as_str())?, "wallet_guard");
+    THRESHOLD_SOL.set(threshold_lamports as f64 / 1e9);
+
+    let metrics = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .merge(shared_config::log_level::router(log_level));
+    let metrics_listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+    info!("📊 Prometheus metrics server listening on http://0.0.0.0:9090/metrics");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(metrics_listener, metrics).await {
+            error!("Metrics server stopped: {}", e);
+        }
+    });
     
     // Start background monitor
     let monitor_app = app.clone();
@@ -50,6 +106,8 @@ async fn main() -> Result<()> {
     let api = Router::new()
         .route("/balance", get(get_balance))
         .route("/health", get(health_check))
+        .route("/live", get(health_check))
+        .route("/ready", get(ready_check))
         .with_state(app);
     
     let listener = tokio::net::TcpListener::bind("0.0.0.0:7070").await?;
@@ -82,6 +140,25 @@ async fn get_balance(
     }
 }
 
+async fn metrics_handler() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+async fn ready_check(
+    axum::extract::State(app): axum::extract::State<App>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let status = if app.readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(app.readiness.report()))
+}
+
 async fn health_check() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "service": "wallet_guard",
@@ -101,15 +178,20 @@ async fn monitor_wallet(app: App) {
         match get_wallet_balance(&app).await {
             Ok(balance) => {
                 let sol_balance = balance as f64 / 1e9;
+                BALANCE_SOL.set(sol_balance);
+                if let Err(e) = record_balance(&app.redis_url, sol_balance).await {
+                    warn!("Failed to record wallet balance: {}", e);
+                }
                 
                 if balance < app.threshold_lamports {
+                    LOW_BALANCE_TOTAL.inc();
                     let msg = format!("🚨 WALLET LOW: {:.4} SOL (below {:.4} SOL threshold)", 
                                      sol_balance, app.threshold_lamports as f64 / 1e9);
                     warn!("{}", msg);
                     
-                    // Send kill switch signal
-                    if let Err(e) = send_kill_switch(&app.redis_url, "PAUSE_WALLET_LOW").await {
-                        error!("Failed to send kill switch: {}", e);
+                    // Pause trading
+                    if let Err(e) = pause_trading(&app.redis_url).await {
+                        error!("Failed to pause trading: {}", e);
                     }
                     
                     // Send alert
@@ -121,6 +203,7 @@ async fn monitor_wallet(app: App) {
                 }
             }
             Err(e) => {
+                CHECK_FAILURES_TOTAL.inc();
                 error!("Failed to check wallet balance: {}", e);
             }
         }
@@ -129,19 +212,32 @@ async fn monitor_wallet(app: App) {
     }
 }
 
-async fn send_kill_switch(redis_url: &str, message: &str) -> Result<()> {
+async fn pause_trading(redis_url: &str) -> Result<()> {
     let client = redis::Client::open(redis_url)?;
     let mut conn = client.get_async_connection().await?;
     
-    redis::cmd("PUBLISH")
-        .arg("kill_switch_channel")
-        .arg(message)
-        .query_async(&mut conn)
-        .await?;
+    set_trading_state!(conn, "wallet_guard", TradingState::paused("WALLET_LOW"))?;
     
     Ok(())
 }
 
+/// The allocator sizes live capital from the wallet's value.
+async fn record_balance(redis_url: &str, sol_balance: f64) -> Result<()> {
+    let client = redis::Client::open(redis_url)?;
+    let mut conn = client.get_async_connection().await?;
+
+    redis::cmd("HSET")
+        .arg(PORTFOLIO_METRICS_KEY)
+        .arg("wallet_sol")
+        .arg(sol_balance)
+        .arg("wallet_updated_at")
+        .arg(chrono::Utc::now().timestamp())
+        .query_async::<_, ()>(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
 async fn send_alert(redis_url: &str, message: &str) -> Result<()> {
     let client = redis::Client::open(redis_url)?;
     let mut conn = client.get_async_connection().await?;